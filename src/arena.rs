@@ -0,0 +1,131 @@
+//! Compact storage of the paths of entries found by scans that hold the whole tree in memory (the files found by
+//! `--name-collisions` and `--hardlinks`, the tree of directories of `-d` without files and the digests of
+//! `--fingerprint`)
+//!
+//! Instead of keeping the full path of every entry, each directory is stored once, as the index of its parent and its
+//! own name, and entries only refer to the directory they lie in. Full paths are only built when they are printed, so
+//! the memory held per entry does not grow with the depth of the tree or the length of the initial path
+
+use std::ffi;
+use std::path;
+
+/// Index of a directory stored in a [PathArena]
+#[derive(Clone, Copy)]
+pub struct DirId(u32);
+
+/// Directory stored in a [PathArena]
+struct ArenaDir {
+    /// Directory that this directory lies in ([None] for the initial directory)
+    parent: Option<DirId>,
+    /// Name of the directory (the whole path for the initial directory)
+    name: Box<ffi::OsStr>,
+}
+
+/// Directories of a tree, each stored once and referred to by its index
+pub struct PathArena {
+    dirs: Vec<ArenaDir>,
+    /// Path of the directory that was interned last along with the indices of its ancestors (from the outermost), so
+    /// that interning the directories of a depth first traversal does not need to look up their paths
+    current: (path::PathBuf, Vec<DirId>),
+}
+
+impl PathArena {
+    /// Returns an arena that holds only the initial directory of a scan
+    ///
+    /// # Arguments
+    ///
+    /// - `p_init_path` - path of the directory that the scan starts from
+    pub fn new(p_init_path: &path::Path) -> PathArena {
+        return PathArena {
+            dirs: vec![ArenaDir {
+                parent: None,
+                name: p_init_path.as_os_str().into(),
+            }],
+            current: (p_init_path.to_path_buf(), vec![DirId(0)]),
+        };
+    }
+
+    /// Returns the index of a directory, storing it (and any of its ancestors that are not stored yet) if required
+    ///
+    /// Directories are looked up relative to the one interned last, so this is cheap as long as they are interned in
+    /// the order of a depth first traversal (a directory that was left and is interned again is stored once more)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_dir_path` - path of the directory, which must lie within the initial directory
+    pub fn intern_dir(&mut self, p_dir_path: &path::Path) -> DirId {
+        let (current_path, current_ids) = &mut self.current;
+
+        while current_ids.len() > 1 && !p_dir_path.starts_with(&*current_path) {
+            current_path.pop();
+            current_ids.pop();
+        }
+
+        for component in p_dir_path
+            .strip_prefix(&*current_path)
+            .unwrap_or(path::Path::new(""))
+        {
+            let id = DirId(self.dirs.len() as u32);
+            self.dirs.push(ArenaDir {
+                parent: current_ids.last().copied(),
+                name: component.into(),
+            });
+
+            current_path.push(component);
+            current_ids.push(id);
+        }
+
+        return *current_ids.last().unwrap_or(&DirId(0));
+    }
+
+    /// Returns the names of a directory and of the directories above it, from the outermost one below the initial
+    /// directory (empty for the initial directory)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_dir` - the directory
+    pub fn rel_names(&self, p_dir: DirId) -> Vec<&ffi::OsStr> {
+        let mut names = Vec::new();
+
+        let mut dir = p_dir;
+        while let Some(parent) = self.dirs[dir.0 as usize].parent {
+            names.push(&*self.dirs[dir.0 as usize].name);
+            dir = parent;
+        }
+
+        names.reverse();
+        return names;
+    }
+
+    /// Returns the full path of a directory
+    ///
+    /// # Arguments
+    ///
+    /// - `p_dir` - the directory
+    pub fn dir_path(&self, p_dir: DirId) -> path::PathBuf {
+        let mut res = path::PathBuf::from(&*self.dirs[0].name);
+        res.extend(self.rel_names(p_dir));
+        return res;
+    }
+
+    /// Returns the name of a directory (the whole path for the initial directory)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_dir` - the directory
+    pub fn name(&self, p_dir: DirId) -> &ffi::OsStr {
+        return &self.dirs[p_dir.0 as usize].name;
+    }
+
+    /// Returns the full path of an entry
+    ///
+    /// # Arguments
+    ///
+    /// - `p_dir` - directory that the entry lies in
+    /// - `p_name` - name of the entry
+    pub fn path(&self, p_dir: DirId, p_name: &ffi::OsStr) -> path::PathBuf {
+        let mut res = self.dir_path(p_dir);
+        res.push(p_name);
+        return res;
+    }
+}
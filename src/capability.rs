@@ -0,0 +1,255 @@
+//! Detecting which of the capabilities that columns and reports rely on are available (for `--capabilities`)
+//!
+//! Some capabilities depend on how fss was compiled or on the platform, and the timestamps depend on the filesystem
+//! the scan starts from. Instead of failing for every entry, the capabilities required by the given options are checked
+//! once before the scan, a single report of the ones that are not available is printed, and the columns that rely on
+//! them are left out (reports that can not run without them print no entries)
+
+use std::fs;
+use std::path;
+use std::sync;
+
+use crate::hash::HashAlgo;
+use crate::i18n::{tr, Msg};
+use crate::{clear_option, columns, get_option, PrgOptions};
+
+/// Capabilities that were found to be unavailable by [check], whose per-entry errors are not shown
+static UNAVAILABLE: sync::OnceLock<Vec<Capability>> = sync::OnceLock::new();
+
+/// Enumerates the capabilities that columns and reports rely on
+#[derive(Clone, Copy, PartialEq)]
+pub enum Capability {
+    /// Times of the last modification of entries
+    ModifiedTime,
+    /// Times of the last access of entries
+    AccessTime,
+    /// Times of the last status change of entries
+    ChangeTime,
+    /// Permissions of entries
+    Permissions,
+    /// Owners and groups of entries
+    Owners,
+    /// Hashing the contents of files
+    Hashing,
+    /// Reading the contents of files without polluting the page cache
+    CacheBypass,
+}
+
+/// Enumerates the reasons that a capability is not available for
+#[derive(Clone, Copy)]
+enum Unavailable {
+    /// Not supported on the platform that fss was compiled for
+    Platform,
+    /// Not supported by the filesystem that the scan starts from
+    Filesystem,
+    /// Not compiled in
+    NotCompiled,
+}
+
+impl Capability {
+    /// All the capabilities, in the order they are listed in the report
+    const ALL: [Capability; 7] = [
+        Capability::ModifiedTime,
+        Capability::AccessTime,
+        Capability::ChangeTime,
+        Capability::Permissions,
+        Capability::Owners,
+        Capability::Hashing,
+        Capability::CacheBypass,
+    ];
+
+    /// Returns the name of the capability (in the selected language)
+    fn name(&self) -> String {
+        return tr!(match self {
+            Capability::ModifiedTime => Msg::CapModifiedTime,
+            Capability::AccessTime => Msg::CapAccessTime,
+            Capability::ChangeTime => Msg::CapChangeTime,
+            Capability::Permissions => Msg::CapPermissions,
+            Capability::Owners => Msg::CapOwners,
+            Capability::Hashing => Msg::CapHashing,
+            Capability::CacheBypass => Msg::CapCacheBypass,
+        });
+    }
+
+    /// Returns whether any of the options that are set relies on the capability
+    fn is_required(&self) -> bool {
+        return match self {
+            Capability::ModifiedTime => {
+                get_option(PrgOptions::ShowLasttime)
+                    || get_option(PrgOptions::AgeHeatmap)
+                    || get_option(PrgOptions::Oldest)
+                    || get_option(PrgOptions::Newest)
+                    || get_option(PrgOptions::TimestampAnomalies)
+                    || get_option(PrgOptions::LsFormat)
+                    || get_option(PrgOptions::Long)
+                    || get_option(PrgOptions::Format)
+                    || get_option(PrgOptions::Printf)
+                    || get_option(PrgOptions::ExportSqlite)
+                    || get_option(PrgOptions::ExportParquet)
+            }
+            Capability::AccessTime => get_option(PrgOptions::ColdData),
+            Capability::ChangeTime => get_option(PrgOptions::TimestampAnomalies),
+            Capability::Permissions => {
+                get_option(PrgOptions::ShowPermissions)
+                    || get_option(PrgOptions::LsFormat)
+                    || get_option(PrgOptions::Long)
+                    || get_option(PrgOptions::Format)
+                    || get_option(PrgOptions::Printf)
+                    || get_option(PrgOptions::ExportSqlite)
+            }
+            Capability::Owners => {
+                get_option(PrgOptions::LsFormat)
+                    || get_option(PrgOptions::Long)
+                    || get_option(PrgOptions::ExportParquet)
+                    || columns::shows(columns::Column::Owner)
+            }
+            Capability::Hashing => get_option(PrgOptions::ShowHash),
+            Capability::CacheBypass => get_option(PrgOptions::NoCachePollution),
+        };
+    }
+
+    /// Returns why the capability is not available ([None] if it is available)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_metadata` - metadata of the directory that the scan starts from ([None] if it could not be read, in which
+    ///   case the timestamps are assumed to be available)
+    fn unavailable(&self, p_metadata: Option<&fs::Metadata>) -> Option<Unavailable> {
+        let available = match self {
+            Capability::ModifiedTime => {
+                !matches!(p_metadata.map(fs::Metadata::modified), Some(Err(_)))
+            }
+            Capability::AccessTime => {
+                !matches!(p_metadata.map(fs::Metadata::accessed), Some(Err(_)))
+            }
+            Capability::ChangeTime | Capability::Permissions | Capability::Owners => {
+                cfg!(target_family = "unix")
+            }
+            Capability::Hashing => HashAlgo::ALL.iter().any(|algo| algo.is_available()),
+            Capability::CacheBypass => cfg!(any(
+                target_os = "linux",
+                target_os = "android",
+                target_os = "macos"
+            )),
+        };
+
+        if available {
+            return None;
+        }
+
+        return Some(match self {
+            Capability::ModifiedTime | Capability::AccessTime => Unavailable::Filesystem,
+            Capability::Hashing => Unavailable::NotCompiled,
+            _ => Unavailable::Platform,
+        });
+    }
+
+    /// Clears the options that only add a column relying on the capability, so that the scan continues without it
+    fn degrade(&self) {
+        match self {
+            Capability::ModifiedTime => {
+                clear_option(PrgOptions::ShowLasttime);
+                clear_option(PrgOptions::AgeHeatmap);
+            }
+            Capability::Permissions => clear_option(PrgOptions::ShowPermissions),
+            Capability::Hashing => clear_option(PrgOptions::ShowHash),
+            Capability::CacheBypass => clear_option(PrgOptions::NoCachePollution),
+            Capability::AccessTime | Capability::ChangeTime | Capability::Owners => {}
+        }
+    }
+}
+
+impl Unavailable {
+    /// Returns the description of the reason (in the selected language)
+    fn describe(&self) -> String {
+        return tr!(match self {
+            Unavailable::Platform => Msg::CapUnsupportedPlatform,
+            Unavailable::Filesystem => Msg::CapUnsupportedFilesystem,
+            Unavailable::NotCompiled => Msg::CapNotCompiled,
+        });
+    }
+}
+
+/// Returns a line of a capability report
+///
+/// # Arguments
+///
+/// - `p_capability` - the capability
+/// - `p_status` - whether it is available, or why it is not
+fn capability_line(p_capability: Capability, p_status: &str) -> String {
+    return format!("    {:<28}{}\n", p_capability.name(), p_status);
+}
+
+/// Checks the capabilities required by the given options, printing a single report of the ones that are not available
+/// (on stderr) and leaving out the columns that rely on them
+///
+/// # Arguments
+///
+/// - `p_init_path` - path of the directory that the scan starts from
+pub fn check(p_init_path: &str) {
+    let metadata = fs::metadata(path::Path::new(p_init_path)).ok();
+
+    let unavailable: Vec<(Capability, Unavailable)> = Capability::ALL
+        .into_iter()
+        .filter(|capability| capability.is_required())
+        .filter_map(|capability| Some((capability, capability.unavailable(metadata.as_ref())?)))
+        .collect();
+
+    if !unavailable.is_empty() {
+        let mut report = tr!(Msg::CapabilitiesMissing, p_init_path);
+        for (capability, reason) in &unavailable {
+            report += &capability_line(*capability, &reason.describe());
+            capability.degrade();
+        }
+        eprint!("{}", report);
+    }
+
+    let _ = UNAVAILABLE.set(
+        unavailable
+            .into_iter()
+            .map(|(capability, _)| capability)
+            .collect(),
+    );
+}
+
+/// Returns `true` if the capability was found to be unavailable before the scan, in which case errors caused by it
+/// are not shown for each entry (since they have already been reported), `false` otherwise
+///
+/// # Arguments
+///
+/// - `p_capability` - the capability
+pub fn is_unavailable(p_capability: Capability) -> bool {
+    return UNAVAILABLE
+        .get()
+        .is_some_and(|unavailable| unavailable.contains(&p_capability));
+}
+
+/// Returns the report of all the capabilities (for `--capabilities`)
+///
+/// # Arguments
+///
+/// - `p_init_path` - path of the directory whose filesystem the timestamps are checked on
+pub fn capabilities_text(p_init_path: &str) -> String {
+    let metadata = fs::metadata(path::Path::new(p_init_path)).ok();
+
+    let mut text = tr!(Msg::CapabilitiesHeader, p_init_path);
+
+    for capability in Capability::ALL {
+        let status = match capability.unavailable(metadata.as_ref()) {
+            Some(reason) => reason.describe(),
+            None if capability == Capability::Hashing => {
+                let algos: Vec<&str> = HashAlgo::ALL
+                    .iter()
+                    .filter(|algo| algo.is_available())
+                    .map(|algo| algo.name())
+                    .collect();
+                tr!(Msg::CapAvailableWith, algos.join(", "))
+            }
+            None => tr!(Msg::CapAvailable),
+        };
+
+        text += &capability_line(capability, &status);
+    }
+
+    return text;
+}
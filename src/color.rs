@@ -0,0 +1,319 @@
+//! Coloring the output with ANSI escape sequences when it is written to a terminal
+//!
+//! Entries are colored by their kind (such as directories, symlinks and executables) with the colors given in
+//! `LS_COLORS` (or those of `dircolors` if it is not set), unless a heatmap is drawn. Whether colors are used is chosen
+//! with `--color` (`auto` by default, which uses them only if the output is written to a terminal)
+
+use std::env;
+use std::ffi;
+use std::io::IsTerminal;
+use std::path;
+use std::sync;
+use std::time;
+
+use crate::{record, SpecialFileType};
+
+/// Escape sequence that resets the color back to the default
+pub const RESET: &str = "\x1b[0m";
+
+/// Colors of the kinds of entries that are used if `LS_COLORS` is not set (the defaults of `dircolors`)
+const DEFAULT_LS_COLORS: &str =
+    "di=01;34:ln=01;36:or=40;31;01:ex=01;32:pi=40;33:so=01;35:bd=40;33;01:cd=40;33;01";
+
+/// Whether colors are always (`Some(true)`) or never (`Some(false)`) used, as given to `--color` ([None] for `auto`)
+static MODE: sync::OnceLock<Option<bool>> = sync::OnceLock::new();
+
+/// Colors of the kinds of entries and of the extensions of files (parsed from `LS_COLORS` when first needed)
+static THEME: sync::OnceLock<Theme> = sync::OnceLock::new();
+
+/// Number of seconds in a day
+const DAY_SECS: u64 = 24 * 60 * 60;
+
+/// Colors of the heatmaps from the hottest to the coldest
+const HEAT: [&str; 8] = [
+    "\x1b[38;5;196m",
+    "\x1b[38;5;202m",
+    "\x1b[38;5;208m",
+    "\x1b[38;5;214m",
+    "\x1b[38;5;220m",
+    "\x1b[38;5;45m",
+    "\x1b[38;5;33m",
+    "\x1b[38;5;21m",
+];
+
+/// Ages (in seconds) up to which the colors of the age heatmap are used, from the hottest (most recently modified)
+///
+/// Entries older than all of them get the coldest color
+const AGE_HEAT_LIMITS: [u64; 7] = [
+    60 * 60,
+    DAY_SECS,
+    7 * DAY_SECS,
+    30 * DAY_SECS,
+    90 * DAY_SECS,
+    365 * DAY_SECS,
+    3 * 365 * DAY_SECS,
+];
+
+/// Sizes (in bytes) up to which the colors of the size heatmap are used, from the coldest (smallest)
+///
+/// Entries larger than all of them get the hottest color
+const SIZE_HEAT_LIMITS: [u64; 7] = [
+    4 << 10,
+    64 << 10,
+    1 << 20,
+    16 << 20,
+    128 << 20,
+    1 << 30,
+    16 << 30,
+];
+
+/// Enumerates the kinds of entries that are colored differently
+#[derive(Clone, Copy, PartialEq)]
+pub enum Kind {
+    Dir,
+    Symlink,
+    /// Symlink whose target does not exist
+    Orphan,
+    File,
+    /// Regular file that can be executed by anyone
+    Executable,
+    Fifo,
+    Socket,
+    BlockDevice,
+    CharDevice,
+}
+
+impl Kind {
+    /// Returns the kind of an entry
+    ///
+    /// # Arguments
+    ///
+    /// - `p_path_os` - path of the entry (used for checking if the target of a symlink exists)
+    /// - `p_metadata` - metadata of the entry
+    pub fn of(p_path_os: &path::Path, p_metadata: &record::EntryMetadata) -> Kind {
+        if p_metadata.is_symlink() {
+            return match record::canonicalize(p_path_os) {
+                Ok(_) => Kind::Symlink,
+                Err(_) => Kind::Orphan,
+            };
+        }
+        if p_metadata.is_dir() {
+            return Kind::Dir;
+        }
+
+        return match p_metadata.special_file_type() {
+            #[cfg(target_family = "unix")]
+            SpecialFileType::Fifo => Kind::Fifo,
+            #[cfg(target_family = "unix")]
+            SpecialFileType::Socket => Kind::Socket,
+            #[cfg(target_family = "unix")]
+            SpecialFileType::BlockDevice => Kind::BlockDevice,
+            #[cfg(target_family = "unix")]
+            SpecialFileType::CharDevice => Kind::CharDevice,
+            SpecialFileType::NA if is_executable(p_metadata) => Kind::Executable,
+            SpecialFileType::NA => Kind::File,
+        };
+    }
+
+    /// Returns the key of the kind in `LS_COLORS`
+    fn key(&self) -> &'static str {
+        return match self {
+            Kind::Dir => "di",
+            Kind::Symlink => "ln",
+            Kind::Orphan => "or",
+            Kind::File => "fi",
+            Kind::Executable => "ex",
+            Kind::Fifo => "pi",
+            Kind::Socket => "so",
+            Kind::BlockDevice => "bd",
+            Kind::CharDevice => "cd",
+        };
+    }
+}
+
+/// Colors of the kinds of entries and of the extensions of files
+struct Theme {
+    /// Escape sequences of the kinds of entries, by their keys in `LS_COLORS` (such as `di`)
+    kinds: Vec<(String, String)>,
+    /// Escape sequences of the files whose names end with each suffix (given as `*.tar` in `LS_COLORS`)
+    suffixes: Vec<(String, String)>,
+}
+
+impl Theme {
+    /// Parses the colors in the format of `LS_COLORS` (such as `di=01;34:*.tar=01;31`)
+    ///
+    /// Entries that are not in the format are skipped like `ls` does
+    ///
+    /// # Arguments
+    ///
+    /// - `p_spec` - the colors
+    fn parse(p_spec: &str) -> Theme {
+        let mut theme = Theme {
+            kinds: Vec::new(),
+            suffixes: Vec::new(),
+        };
+
+        for item in p_spec.split(':') {
+            let Some((key, codes)) = item.split_once('=') else {
+                continue;
+            };
+
+            // codes that only reset the attributes leave the entry uncolored
+            let escape = if codes.bytes().all(|ch| ch == b'0' || ch == b';') {
+                String::new()
+            } else {
+                format!("\x1b[{}m", codes)
+            };
+
+            match key.strip_prefix('*') {
+                Some(suffix) => theme.suffixes.push((suffix.to_owned(), escape)),
+                None => theme.kinds.push((key.to_owned(), escape)),
+            }
+        }
+
+        return theme;
+    }
+
+    /// Returns the escape sequence of a kind of entry ([None] if the kind has no color)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_kind` - the kind of entry
+    fn kind(&self, p_kind: Kind) -> Option<&str> {
+        return self
+            .kinds
+            .iter()
+            .find(|(key, _)| key == p_kind.key())
+            .map(|(_, escape)| escape.as_str());
+    }
+}
+
+/// Sets whether colors are used, from the value given to `--color` (`auto`, `always` or `never`)
+///
+/// Returns `Err` if the value is not one of them
+///
+/// # Arguments
+///
+/// - `p_mode` - the value
+pub fn set_mode(p_mode: &str) -> Result<(), ()> {
+    let mode = match p_mode {
+        "auto" => None,
+        "always" => Some(true),
+        "never" => Some(false),
+        _ => return Err(()),
+    };
+
+    let _ = MODE.set(mode);
+    return Ok(());
+}
+
+/// Returns whether colors are enabled
+///
+/// Colors are enabled if `--color always` is given, or with `--color auto` (the default) if stdout is a terminal and
+/// `NO_COLOR` is not set, or if `CLICOLOR_FORCE` is set (to anything other than `0`)
+pub fn enabled() -> bool {
+    static ENABLED: sync::OnceLock<bool> = sync::OnceLock::new();
+
+    return *ENABLED.get_or_init(|| {
+        if let Some(Some(mode)) = MODE.get() {
+            return *mode;
+        }
+        if env::var_os("CLICOLOR_FORCE").is_some_and(|value| !value.is_empty() && value != "0") {
+            return true;
+        }
+        if env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+            return false;
+        }
+        return std::io::stdout().is_terminal();
+    });
+}
+
+/// Returns the escape sequence of the color of an entry of the given kind (empty if it has no color)
+///
+/// # Arguments
+///
+/// - `p_kind` - the kind of the entry
+/// - `p_name` - name of the entry (files are colored by the suffix of their name if it has a color)
+pub fn kind_color(p_kind: Kind, p_name: &ffi::OsStr) -> &'static str {
+    let theme = THEME.get_or_init(|| match env::var("LS_COLORS") {
+        Ok(spec) if !spec.is_empty() => Theme::parse(&spec),
+        _ => Theme::parse(DEFAULT_LS_COLORS),
+    });
+
+    // like ls, suffixes only apply to files that are not executable
+    if p_kind == Kind::File {
+        let name = p_name.to_string_lossy();
+        if let Some((_, escape)) = theme
+            .suffixes
+            .iter()
+            .find(|(suffix, _)| name.ends_with(suffix.as_str()))
+        {
+            return escape;
+        }
+    }
+
+    // symlinks whose targets do not exist are colored like the other symlinks if they have no color of their own
+    return match p_kind {
+        Kind::Orphan => theme.kind(Kind::Orphan).or(theme.kind(Kind::Symlink)),
+        _ => theme.kind(p_kind),
+    }
+    .unwrap_or("");
+}
+
+/// Returns the escape sequence of the heatmap color for an entry of the given age
+///
+/// # Arguments
+///
+/// - `p_age` - time since the entry was last modified
+pub fn age_heat(p_age: time::Duration) -> &'static str {
+    let idx = AGE_HEAT_LIMITS
+        .iter()
+        .position(|limit| p_age.as_secs() < *limit)
+        .unwrap_or(AGE_HEAT_LIMITS.len());
+
+    return HEAT[idx];
+}
+
+/// Returns the escape sequence of the heatmap color for an entry of the given size
+///
+/// # Arguments
+///
+/// - `p_size` - size of the entry in bytes
+pub fn size_heat(p_size: u64) -> &'static str {
+    let idx = SIZE_HEAT_LIMITS
+        .iter()
+        .position(|limit| p_size < *limit)
+        .unwrap_or(SIZE_HEAT_LIMITS.len());
+
+    return HEAT[HEAT.len() - 1 - idx];
+}
+
+/// Returns the escape sequence that ends a colored span ([RESET], or nothing if the span is not colored)
+///
+/// # Arguments
+///
+/// - `p_color` - escape sequence that started the span (empty if it is not colored)
+pub fn end(p_color: &str) -> &'static str {
+    if p_color.is_empty() {
+        return "";
+    }
+
+    return RESET;
+}
+
+/// Returns whether a regular file can be executed by anyone (never outside of unix-like systems)
+///
+/// # Arguments
+///
+/// - `p_metadata` - metadata of the file
+fn is_executable(p_metadata: &record::EntryMetadata) -> bool {
+    #[cfg(target_family = "unix")]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        return p_metadata.is_file() && p_metadata.permissions().mode() & 0o111 != 0;
+    }
+
+    #[cfg(not(target_family = "unix"))]
+    return false;
+}
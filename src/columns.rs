@@ -0,0 +1,499 @@
+//! Choosing and ordering the columns of a listing (for `--columns`)
+//!
+//! By default, each row of a listing holds the permissions (with `-p`), the time of the last modification (with `-l`),
+//! the hash (with `--hash`), the size and the name of an entry, in that order. With `--columns`, the rows hold the
+//! given columns instead, in the given order, and the permissions, modification time and hash columns are printed
+//! only if they are given (regardless of their own options). The times of the last access (`atime`) and of the last
+//! change to the status (`ctime`) of each entry can only be printed this way, as can whether each regular file holds
+//! text or binary data (`content`, see [crate::content]). Values that are not available (such as
+//! the owner of a replayed entry) are printed as `?`
+//!
+//! When the output is written to a terminal (or `--width` is given), names that do not fit in the rest of the row are
+//! shortened by replacing their middle with an ellipsis, so that each entry stays on a single line. When columns follow
+//! the name, the rows are held back until the listing is done instead, and the names are padded to the widest of them
+//! so that the columns after them line up
+
+use std::borrow::Cow;
+use std::env;
+use std::fmt;
+use std::fmt::Write;
+use std::io::IsTerminal;
+use std::path;
+use std::sync;
+
+use crate::content;
+use crate::find::type_letter;
+use crate::i18n::{tr, Msg};
+use crate::{
+    clear_option, get_option, hash, hash_column, indent_text, ls, record, set_option, PrgOptions,
+};
+
+#[cfg(target_family = "unix")]
+use crate::{format_modif_time, FMT_TIME_WIDTH, MODE_FMT};
+
+/// Columns that each row of a listing holds ([None] if `--columns` was not given)
+static COLUMNS: sync::OnceLock<Vec<Column>> = sync::OnceLock::new();
+
+/// Columns that each row of a listing holds by default (those that rely on an option are skipped if it is not set)
+const DEFAULT_COLUMNS: [Column; 5] = [
+    Column::Perms,
+    Column::Mtime,
+    Column::Hash,
+    Column::Size,
+    Column::Name,
+];
+
+/// Number of columns that rows are fitted in (0 if names are never shortened), given to `--width` or detected from the
+/// terminal when first needed
+static WIDTH: sync::OnceLock<usize> = sync::OnceLock::new();
+
+/// Width that the owner column is padded to
+const OWNER_WIDTH: usize = 10;
+
+/// Width that the content column is padded to
+const CONTENT_WIDTH: usize = 6;
+
+/// Width that the size column is padded to
+const SIZE_WIDTH: usize = 20;
+
+/// Fewest columns that a name is shortened to (rows that leave less room for the name are wrapped by the terminal
+/// instead)
+const MIN_NAME_WIDTH: usize = 12;
+
+/// Text that replaces the middle of a shortened name
+const ELLIPSIS: char = '…';
+
+/// Rows of the listing being printed that are held back until it is done, since columns follow the name (each split
+/// after the name, along with the number of columns taken up by the indentation and the name)
+static PENDING_ROWS: sync::Mutex<Vec<(String, usize, String)>> = sync::Mutex::new(Vec::new());
+
+/// Enumerates the columns that a row of a listing can hold
+#[derive(Clone, Copy, PartialEq)]
+pub enum Column {
+    /// Size of the entry (or its type for entries that have no size, such as symlinks)
+    Size,
+    /// Time of the last modification of the entry
+    Mtime,
+    /// Time of the last access to the entry
+    Atime,
+    /// Time of the last change to the status of the entry
+    Ctime,
+    /// Permissions of the entry
+    Perms,
+    /// Owner of the entry
+    Owner,
+    /// Hash of the contents of the entry (only for regular files)
+    Hash,
+    /// Whether the entry holds text or binary data (only for regular files)
+    Content,
+    /// Name of the entry (indented by its level, unless the no-tree option is set)
+    Name,
+}
+
+impl Column {
+    /// Returns the column with the given name, or [None] if no such column exists
+    ///
+    /// # Arguments
+    ///
+    /// - `p_name` - name of the column (as given to `--columns`)
+    fn from_name(p_name: &str) -> Option<Column> {
+        return match p_name {
+            "size" => Some(Column::Size),
+            "mtime" => Some(Column::Mtime),
+            "atime" => Some(Column::Atime),
+            "ctime" => Some(Column::Ctime),
+            "perms" => Some(Column::Perms),
+            "owner" => Some(Column::Owner),
+            "hash" => Some(Column::Hash),
+            "content" => Some(Column::Content),
+            "name" => Some(Column::Name),
+            _ => None,
+        };
+    }
+
+    /// Returns the option that the column relies on ([None] if it is always printed)
+    fn option(&self) -> Option<PrgOptions> {
+        return match self {
+            Column::Mtime => Some(PrgOptions::ShowLasttime),
+            Column::Perms => Some(PrgOptions::ShowPermissions),
+            Column::Hash => Some(PrgOptions::ShowHash),
+            Column::Size
+            | Column::Atime
+            | Column::Ctime
+            | Column::Owner
+            | Column::Content
+            | Column::Name => None,
+        };
+    }
+}
+
+/// Parses a comma-separated list of columns and sets it as the columns of each row (has no effect if they are already
+/// set)
+///
+/// Returns the name that is not a column if the list is invalid
+///
+/// # Arguments
+///
+/// - `p_list` - the list of columns (such as `size,mtime,name`)
+pub fn set_columns(p_list: &str) -> Result<(), String> {
+    let mut columns = Vec::new();
+
+    for name in p_list.split(',') {
+        let Some(column) = Column::from_name(name.trim()) else {
+            return Err(name.trim().to_owned());
+        };
+        if !columns.contains(&column) {
+            columns.push(column);
+        }
+    }
+
+    let _ = COLUMNS.set(columns);
+    return Ok(());
+}
+
+/// Sets the number of columns that rows are fitted in, from the value given to `--width` (0 to never shorten names)
+///
+/// Returns `Err` if the value is not a number
+///
+/// # Arguments
+///
+/// - `p_width` - the value
+pub fn set_width(p_width: &str) -> Result<(), ()> {
+    let width = p_width.parse::<usize>().map_err(|_| ())?;
+
+    let _ = WIDTH.set(width);
+    return Ok(());
+}
+
+/// Returns the number of columns that rows are fitted in (0 if names are never shortened)
+///
+/// Unless it is given with `--width`, it is the width of the terminal that the output is written to (from `COLUMNS` if
+/// it is set), and 0 if the output is not written to a terminal
+pub fn width() -> usize {
+    return *WIDTH.get_or_init(|| {
+        if !std::io::stdout().is_terminal() {
+            return 0;
+        }
+
+        if let Some(columns) = env::var("COLUMNS")
+            .ok()
+            .and_then(|columns| columns.parse::<usize>().ok())
+        {
+            return columns;
+        }
+
+        #[cfg(target_family = "unix")]
+        {
+            let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+            if unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } == 0 {
+                return size.ws_col as usize;
+            }
+        }
+
+        return 0;
+    });
+}
+
+/// Returns the text shortened to the given number of columns by replacing its middle with an ellipsis (escape
+/// sequences, such as colors, are kept and take up no columns)
+///
+/// # Arguments
+///
+/// - `p_text` - the text
+/// - `p_width` - number of columns to shorten it to
+fn ellipsize(p_text: &str, p_width: usize) -> Cow<'_, str> {
+    // the text is split into escape sequences and the characters that are printed
+    let mut pieces: Vec<(&str, bool)> = Vec::new();
+    let mut rest = p_text;
+    while let Some(ch) = rest.chars().next() {
+        let len = if rest.starts_with("\x1b[") {
+            rest.find(|ch: char| ch.is_ascii_alphabetic())
+                .map_or(rest.len(), |end| end + 1)
+        } else {
+            ch.len_utf8()
+        };
+        pieces.push((&rest[..len], !rest.starts_with('\x1b')));
+        rest = &rest[len..];
+    }
+
+    let visible = pieces.iter().filter(|(_, printed)| *printed).count();
+    if visible <= p_width {
+        return Cow::Borrowed(p_text);
+    }
+
+    // the start of the text is kept over its end when they cannot be kept evenly
+    let kept = p_width.saturating_sub(1);
+    let (head, tail) = (kept - kept / 2, kept / 2);
+
+    let mut res = String::with_capacity(p_text.len());
+    let mut idx = 0;
+    for (piece, printed) in pieces {
+        if !printed {
+            res.push_str(piece);
+            continue;
+        }
+
+        if idx == head {
+            res.push(ELLIPSIS);
+        }
+        if idx < head || idx >= visible - tail {
+            res.push_str(piece);
+        }
+        idx += 1;
+    }
+
+    return Cow::Owned(res);
+}
+
+/// Returns the number of columns taken up by the text (escape sequences, such as colors, take up none)
+///
+/// # Arguments
+///
+/// - `p_text` - the text
+fn visible_width(p_text: &str) -> usize {
+    let mut width = 0;
+    let mut rest = p_text;
+    while let Some(ch) = rest.chars().next() {
+        let len = if rest.starts_with("\x1b[") {
+            rest.find(|ch: char| ch.is_ascii_alphabetic())
+                .map_or(rest.len(), |end| end + 1)
+        } else {
+            width += 1;
+            ch.len_utf8()
+        };
+        rest = &rest[len..];
+    }
+
+    return width;
+}
+
+/// Sets the options of the columns given to `--columns` and clears those of the other columns, so that only the given
+/// columns are printed (has no effect if `--columns` was not given)
+pub fn apply_options() {
+    let Some(columns) = COLUMNS.get() else {
+        return;
+    };
+
+    for column in DEFAULT_COLUMNS {
+        if let Some(option) = column.option() {
+            match columns.contains(&column) {
+                true => set_option(option),
+                false => clear_option(option),
+            }
+        }
+    }
+}
+
+/// Returns whether each row holds the given column
+///
+/// # Arguments
+///
+/// - `p_column` - the column
+pub fn shows(p_column: Column) -> bool {
+    let columns = match COLUMNS.get() {
+        Some(columns) => columns.as_slice(),
+        None => DEFAULT_COLUMNS.as_slice(),
+    };
+
+    return columns.contains(&p_column) && p_column.option().is_none_or(get_option);
+}
+
+/// Prints a row of a listing, with the columns given to `--columns` (or the default ones)
+///
+/// Returns `false` if the row could be printed, `true` otherwise (nothing is printed in that case)
+///
+/// # Arguments
+///
+/// - `p_entry` - path and metadata of the entry ([None] for rows that stand for several entries, whose permissions,
+///   modification time, owner and hash are left blank)
+/// - `p_size` - text of the size column
+/// - `p_indent_width` - width that the name is indented by ([None] if it is not indented)
+/// - `p_name` - text of the name column (including the colors)
+#[allow(clippy::print_with_newline)]
+pub fn print_row(
+    p_entry: Option<(&path::Path, &record::EntryMetadata)>,
+    p_size: &str,
+    p_indent_width: Option<usize>,
+    p_name: fmt::Arguments,
+) -> bool {
+    let columns = match COLUMNS.get() {
+        Some(columns) => columns.as_slice(),
+        None => DEFAULT_COLUMNS.as_slice(),
+    };
+
+    // the modification time is read first, since the row is skipped if it could not be read
+    #[cfg(target_family = "unix")]
+    let mtime = match p_entry {
+        Some((path_os, metadata)) if shows(Column::Mtime) => match metadata.modified() {
+            Ok(time) => Some(time),
+            Err(_) => {
+                if get_option(PrgOptions::ShowErrors) {
+                    eprint!("{}", tr!(Msg::ModifiedTimeError, path_os.to_string_lossy()));
+                }
+                return true;
+            }
+        },
+        _ => None,
+    };
+
+    // the row is built up before it is printed, since it is held back if columns follow the name
+    let mut row = String::new();
+    let mut name_width = None;
+    let mut name_end = 0;
+
+    // the number of columns used so far by the row, to know how much room is left for the name
+    let mut used = 0;
+    let mut first = true;
+    let mut after_name = false;
+    for column in columns {
+        if !shows(*column) {
+            continue;
+        }
+
+        // the columns after the name are separated from it like the name is from the size (once it is padded)
+        if after_name {
+            row.push_str("    ");
+            after_name = false;
+        }
+
+        match column {
+            Column::Size => {
+                let _ = write!(row, "{:>SIZE_WIDTH$}", p_size);
+                used += SIZE_WIDTH.max(p_size.chars().count());
+            }
+            #[cfg(target_family = "unix")]
+            Column::Mtime => {
+                let time = mtime.map(format_modif_time).unwrap_or_default();
+                let _ = write!(row, "{:>FMT_TIME_WIDTH$}", time);
+                used += FMT_TIME_WIDTH.max(time.chars().count());
+            }
+            #[cfg(target_family = "unix")]
+            Column::Perms => {
+                use std::os::unix::fs::PermissionsExt;
+
+                // the permissions are printed first by default, so they are padded after them (and before them if they are not)
+                if !first {
+                    row.push_str("  ");
+                    used += 2;
+                }
+                match p_entry {
+                    Some((_, metadata)) => {
+                        let mode = metadata.permissions().mode() as usize;
+                        let _ = write!(
+                            row,
+                            "{}{}{}   ",
+                            MODE_FMT[(mode >> 6) & 7],
+                            MODE_FMT[(mode >> 3) & 7],
+                            MODE_FMT[mode & 7]
+                        );
+                    }
+                    None => row.push_str("            "),
+                }
+                used += 12;
+            }
+            #[cfg(target_family = "unix")]
+            Column::Atime | Column::Ctime => {
+                let time = match p_entry {
+                    Some((_, metadata)) => match column {
+                        Column::Atime => metadata.accessed(),
+                        _ => metadata.status_changed(),
+                    }
+                    .map_or("?".to_owned(), format_modif_time),
+                    None => "".to_owned(),
+                };
+                let _ = write!(row, "{:>FMT_TIME_WIDTH$}", time);
+                used += FMT_TIME_WIDTH.max(time.chars().count());
+            }
+            #[cfg(not(target_family = "unix"))]
+            Column::Mtime | Column::Atime | Column::Ctime | Column::Perms => {}
+            Column::Owner => {
+                let owner = match p_entry {
+                    Some((_, metadata)) => ls::owner_name(metadata).unwrap_or("?".to_owned()),
+                    None => "".to_owned(),
+                };
+                let _ = write!(row, "  {:<OWNER_WIDTH$}", owner);
+                used += 2 + OWNER_WIDTH.max(owner.chars().count());
+            }
+            Column::Hash => {
+                row.push_str(&hash_column(
+                    p_entry.filter(|(_, metadata)| metadata.is_file()),
+                ));
+                used += 2 + hash::get_hash_algo().map_or(0, |algo| algo.hex_len());
+            }
+            Column::Content => {
+                let kind = match p_entry {
+                    Some((path_os, metadata)) if type_letter(metadata) == Some('f') => {
+                        content::classify(path_os, metadata).map_or("?", |kind| kind.name())
+                    }
+                    _ => "",
+                };
+                let _ = write!(row, "  {:<CONTENT_WIDTH$}", kind);
+                used += 2 + CONTENT_WIDTH;
+            }
+            Column::Name => {
+                if !first {
+                    row.push_str("    ");
+                    used += 4;
+                }
+
+                let indent = p_indent_width
+                    .map(indent_text)
+                    .map(|indent| indent.to_string())
+                    .unwrap_or_default();
+                used += indent.chars().count();
+
+                // the name is only shortened if it is the last column and enough room is left for it
+                let name = p_name.to_string();
+                let room = width().saturating_sub(used);
+                let last = columns
+                    .iter()
+                    .skip_while(|other| *other != column)
+                    .skip(1)
+                    .all(|other| !shows(*other));
+                let start = row.len();
+                match last && room >= MIN_NAME_WIDTH {
+                    true => {
+                        let _ = write!(row, "{}{}", indent, ellipsize(&name, room));
+                    }
+                    false => {
+                        let _ = write!(row, "{}{}", indent, name);
+                    }
+                }
+                name_end = row.len();
+                if !last {
+                    name_width = Some(visible_width(&row[start..]));
+                }
+                after_name = true;
+            }
+        }
+        first = false;
+    }
+
+    match name_width {
+        Some(name_width) => {
+            if let Ok(mut pending) = PENDING_ROWS.lock() {
+                let tail = row.split_off(name_end);
+                pending.push((row, name_width, tail));
+            }
+        }
+        None => print!("{}\n", row),
+    }
+    return false;
+}
+
+/// Prints the rows that were held back because columns follow the name, with the names padded to the widest of them
+/// so that the columns after them line up (has no effect if no rows were held back)
+pub fn flush_rows() {
+    let Ok(mut pending) = PENDING_ROWS.lock() else {
+        return;
+    };
+
+    let widest = pending
+        .iter()
+        .map(|(_, name_width, _)| *name_width)
+        .max()
+        .unwrap_or(0);
+    for (head, name_width, tail) in pending.drain(..) {
+        print!("{}{:pad$}{}\n", head, "", tail, pad = widest - name_width);
+    }
+}
@@ -0,0 +1,250 @@
+//! Compressing the output with gzip or zstd (for `--compress`, `--compress-algo` and output files ending with `.gz`,
+//! `.zst` or `.zstd`)
+//!
+//! Everything printed to stdout is passed through a pipe to a thread that compresses it into what stdout pointed to
+//! before, so the output of every mode is compressed without having to know about it. Each algorithm is compiled in
+//! only if its cargo feature is enabled (`compress-gzip` and `compress-zstd`)
+
+use std::io::{self, Read, Write};
+use std::path;
+use std::sync;
+use std::thread;
+
+/// Number of bytes read from the pipe at once
+const CHUNK_LEN: usize = 1 << 16;
+
+/// Algorithm that the output is compressed with (gzip if none was set)
+static ALGO: sync::OnceLock<CompressAlgo> = sync::OnceLock::new();
+
+/// Thread that compresses the output ([None] if the output is not compressed)
+static COMPRESSOR: sync::Mutex<Option<thread::JoinHandle<io::Result<()>>>> = sync::Mutex::new(None);
+
+/// Enumerates the algorithms that the output can be compressed with
+#[derive(Clone, Copy, PartialEq)]
+pub enum CompressAlgo {
+    Gzip,
+    Zstd,
+}
+
+impl CompressAlgo {
+    /// Returns the algorithm with the given name ([None] if there is no such algorithm)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_name` - the name (`gzip` or `zstd`)
+    pub fn from_name(p_name: &str) -> Option<CompressAlgo> {
+        return match p_name {
+            "gzip" => Some(CompressAlgo::Gzip),
+            "zstd" => Some(CompressAlgo::Zstd),
+            _ => None,
+        };
+    }
+
+    /// Returns the algorithm that a file is compressed with judging by its extension ([None] if it is not compressed)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_path` - path of the file
+    pub fn from_path(p_path: &path::Path) -> Option<CompressAlgo> {
+        return match p_path.extension()?.to_str()? {
+            "gz" => Some(CompressAlgo::Gzip),
+            "zst" | "zstd" => Some(CompressAlgo::Zstd),
+            _ => None,
+        };
+    }
+
+    /// Returns the name of the algorithm
+    pub fn name(&self) -> &'static str {
+        return match self {
+            CompressAlgo::Gzip => "gzip",
+            CompressAlgo::Zstd => "zstd",
+        };
+    }
+
+    /// Returns whether the algorithm was compiled in
+    pub fn is_available(&self) -> bool {
+        return match self {
+            CompressAlgo::Gzip => cfg!(feature = "compress-gzip"),
+            CompressAlgo::Zstd => cfg!(feature = "compress-zstd"),
+        };
+    }
+}
+
+/// Writer that compresses the data written into it with one of the algorithms
+enum Encoder<W: Write> {
+    #[cfg(feature = "compress-gzip")]
+    Gzip(flate2::write::GzEncoder<W>),
+    #[cfg(feature = "compress-zstd")]
+    Zstd(zstd::stream::write::Encoder<'static, W>),
+    /// Stands in for the algorithms that were not compiled in (never created)
+    #[allow(dead_code)]
+    None(W),
+}
+
+impl<W: Write> Encoder<W> {
+    /// Returns a writer that compresses into the given writer with the given algorithm
+    ///
+    /// # Arguments
+    ///
+    /// - `p_algo` - the algorithm
+    /// - `p_writer` - the writer that the compressed data is written into
+    #[cfg_attr(
+        not(any(feature = "compress-gzip", feature = "compress-zstd")),
+        allow(unused_variables)
+    )]
+    fn new(p_algo: CompressAlgo, p_writer: W) -> io::Result<Encoder<W>> {
+        return match p_algo {
+            // no name or modification time is stored, so the same output is always compressed the same way
+            #[cfg(feature = "compress-gzip")]
+            CompressAlgo::Gzip => Ok(Encoder::Gzip(flate2::write::GzEncoder::new(
+                p_writer,
+                flate2::Compression::default(),
+            ))),
+            #[cfg(feature = "compress-zstd")]
+            CompressAlgo::Zstd => {
+                let mut encoder = zstd::stream::write::Encoder::new(p_writer, 0)?;
+                encoder.include_checksum(true)?;
+                Ok(Encoder::Zstd(encoder))
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("{} compression was not compiled in", p_algo.name()),
+            )),
+        };
+    }
+
+    /// Compresses the remaining data and writes the end of the compressed stream, returning the inner writer
+    fn finish(self) -> io::Result<W> {
+        return match self {
+            #[cfg(feature = "compress-gzip")]
+            Encoder::Gzip(encoder) => encoder.finish(),
+            #[cfg(feature = "compress-zstd")]
+            Encoder::Zstd(encoder) => encoder.finish(),
+            Encoder::None(writer) => Ok(writer),
+        };
+    }
+}
+
+impl<W: Write> Write for Encoder<W> {
+    fn write(&mut self, p_buff: &[u8]) -> io::Result<usize> {
+        return match self {
+            #[cfg(feature = "compress-gzip")]
+            Encoder::Gzip(encoder) => encoder.write(p_buff),
+            #[cfg(feature = "compress-zstd")]
+            Encoder::Zstd(encoder) => encoder.write(p_buff),
+            Encoder::None(writer) => writer.write(p_buff),
+        };
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        return match self {
+            #[cfg(feature = "compress-gzip")]
+            Encoder::Gzip(encoder) => encoder.flush(),
+            #[cfg(feature = "compress-zstd")]
+            Encoder::Zstd(encoder) => encoder.flush(),
+            Encoder::None(writer) => writer.flush(),
+        };
+    }
+}
+
+/// Sets the algorithm that the output is compressed with (has no effect if it is already set)
+///
+/// # Arguments
+///
+/// - `p_algo` - the algorithm
+pub fn set_algo(p_algo: CompressAlgo) {
+    let _ = ALGO.set(p_algo);
+}
+
+/// Returns the algorithm that the output is compressed with
+pub fn algo() -> CompressAlgo {
+    return *ALGO.get().unwrap_or(&CompressAlgo::Gzip);
+}
+
+#[cfg(target_family = "unix")]
+/// Starts compressing everything printed to stdout from then on into what stdout points to (see [finish])
+pub fn start() -> io::Result<()> {
+    use std::fs;
+    use std::os::unix::io::FromRawFd;
+
+    io::stdout().flush()?;
+
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let (mut reader, output) = unsafe {
+        let output_fd = libc::dup(libc::STDOUT_FILENO);
+        if output_fd < 0 || libc::dup2(fds[1], libc::STDOUT_FILENO) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        libc::close(fds[1]);
+
+        (
+            fs::File::from_raw_fd(fds[0]),
+            fs::File::from_raw_fd(output_fd),
+        )
+    };
+
+    let compressor = thread::spawn(move || -> io::Result<()> {
+        let mut writer = Encoder::new(algo(), io::BufWriter::new(output))?;
+        let mut buff = vec![0; CHUNK_LEN];
+
+        loop {
+            match reader.read(&mut buff)? {
+                0 => break,
+                len => writer.write_all(&buff[..len])?,
+            }
+        }
+
+        return writer.finish()?.flush();
+    });
+
+    if let Ok(mut handle) = COMPRESSOR.lock() {
+        *handle = Some(compressor);
+    }
+
+    return Ok(());
+}
+
+#[cfg(not(target_family = "unix"))]
+/// Starts compressing everything printed to stdout (not supported on this platform)
+pub fn start() -> io::Result<()> {
+    return Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "compressing the output is only supported on unix",
+    ));
+}
+
+#[cfg(target_family = "unix")]
+/// Waits for everything printed to stdout so far to be compressed (has no effect if the output is not compressed)
+///
+/// Nothing printed to stdout afterwards is written anywhere
+pub fn finish() -> io::Result<()> {
+    let Some(compressor) = COMPRESSOR.lock().ok().and_then(|mut handle| handle.take()) else {
+        return Ok(());
+    };
+
+    io::stdout().flush()?;
+
+    // stdout is the only end of the pipe that is left, so replacing it lets the compressor reach the end of the output
+    unsafe {
+        let null_fd = libc::open(c"/dev/null".as_ptr(), libc::O_WRONLY);
+        if null_fd < 0 || libc::dup2(null_fd, libc::STDOUT_FILENO) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        libc::close(null_fd);
+    }
+
+    return compressor
+        .join()
+        .unwrap_or_else(|_| Err(io::Error::other("the compressor stopped unexpectedly")));
+}
+
+#[cfg(not(target_family = "unix"))]
+/// Waits for everything printed to stdout so far to be compressed (the output is never compressed on this platform)
+pub fn finish() -> io::Result<()> {
+    return Ok(());
+}
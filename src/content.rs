@@ -0,0 +1,75 @@
+//! Classifying regular files as text or binary by their contents (for `--only-text`, `--only-binary` and the `content`
+//! column)
+//!
+//! Like git and grep, a file is binary if its first few kilobytes hold a NUL byte, and text otherwise (so empty files
+//! are text). Only regular files are classified, and files whose contents can not be read (such as replayed ones, since
+//! recordings do not hold the contents of files) are neither text nor binary
+
+use std::io::Read;
+use std::path;
+
+use crate::find::type_letter;
+use crate::{open_content_file, record, release_cached_pages};
+
+/// Number of bytes at the start of a file that are looked at to classify it (the same as git)
+const SNIFF_LEN: usize = 8000;
+
+/// Enumerates the kinds of contents that a regular file can hold
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// Contents without any NUL byte
+    Text,
+    /// Contents that hold a NUL byte
+    Binary,
+}
+
+impl Kind {
+    /// Returns the name of the kind, as printed in the `content` column
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Kind::Text => "text",
+            Kind::Binary => "binary",
+        };
+    }
+}
+
+/// Returns whether a regular file holds text or binary data ([None] if it is not a regular file or could not be read)
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+pub fn classify(p_path_os: &path::Path, p_metadata: &record::EntryMetadata) -> Option<Kind> {
+    return match head(p_path_os, p_metadata)?.contains(&0) {
+        true => Some(Kind::Binary),
+        false => Some(Kind::Text),
+    };
+}
+
+/// Returns the first [SNIFF_LEN] bytes of a regular file (or all of them if it is shorter, [None] if it is not a
+/// regular file or could not be read)
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+pub fn head(p_path_os: &path::Path, p_metadata: &record::EntryMetadata) -> Option<Vec<u8>> {
+    if type_letter(p_metadata) != Some('f') {
+        return None;
+    }
+
+    if record::is_replaying() {
+        return None;
+    }
+
+    let mut file = open_content_file(p_path_os).ok()?;
+
+    let mut buff = Vec::with_capacity(SNIFF_LEN);
+    file.by_ref()
+        .take(SNIFF_LEN as u64)
+        .read_to_end(&mut buff)
+        .ok()?;
+    release_cached_pages(&mut file);
+
+    return Some(buff);
+}
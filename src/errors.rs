@@ -0,0 +1,105 @@
+//! Handling the directories that can not be read (for `--on-error`)
+//!
+//! Every directory that fails to be opened through [record::read_dir](crate::record::read_dir) or
+//! [record::read_dir_unrecorded](crate::record::read_dir_unrecorded) is handled here, whichever traversal reads it. By
+//! default such directories are skipped (and only reported with `-e`). With `collect`, the errors are gathered so that
+//! a report with their counts can be printed at the end, and with `abort`, the traversals stop at the first of them
+//! (winding down like with `--max-entries`) so that the run can fail
+
+use std::collections::BTreeMap;
+use std::path;
+use std::sync;
+
+use crate::limit;
+
+/// Enumerates the ways of handling directories that can not be read
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum OnError {
+    /// Skip the directory and carry on
+    Skip,
+    /// Stop the traversals at the first such directory
+    Abort,
+    /// Skip the directory, and remember the error for the report at the end
+    Collect,
+}
+
+impl OnError {
+    /// Returns the way of handling errors with the given name ([None] if there is no such way)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_name` - the name (`skip`, `abort` or `collect`)
+    pub fn from_name(p_name: &str) -> Option<OnError> {
+        return match p_name {
+            "skip" => Some(OnError::Skip),
+            "abort" => Some(OnError::Abort),
+            "collect" => Some(OnError::Collect),
+            _ => None,
+        };
+    }
+}
+
+/// Way of handling directories that can not be read (skipped if it was not set)
+static ON_ERROR: sync::OnceLock<OnError> = sync::OnceLock::new();
+
+/// Error of each directory that could not be read while collecting them (each directory is only kept once, although
+/// some are read by several traversals)
+static ERRORS: sync::Mutex<BTreeMap<path::PathBuf, std::io::Error>> =
+    sync::Mutex::new(BTreeMap::new());
+
+/// Directory that the traversals were aborted at, along with its error
+static ABORTED: sync::OnceLock<(path::PathBuf, String)> = sync::OnceLock::new();
+
+/// Sets the way of handling directories that can not be read
+///
+/// # Arguments
+///
+/// - `p_on_error` - the way of handling them
+pub fn set_on_error(p_on_error: OnError) {
+    let _ = ON_ERROR.set(p_on_error);
+}
+
+/// Returns the way of handling directories that can not be read
+pub fn on_error() -> OnError {
+    return *ON_ERROR.get().unwrap_or(&OnError::Skip);
+}
+
+/// Handles a directory that could not be read
+///
+/// # Arguments
+///
+/// - `p_dir_path` - path of the directory
+/// - `p_error` - the error that occurred while opening it
+pub fn dir_error(p_dir_path: &path::Path, p_error: &std::io::Error) {
+    match on_error() {
+        OnError::Skip => {}
+        OnError::Abort => {
+            if ABORTED
+                .set((p_dir_path.to_path_buf(), p_error.to_string()))
+                .is_ok()
+            {
+                limit::stop();
+            }
+        }
+        OnError::Collect => {
+            if let Ok(mut errors) = ERRORS.lock() {
+                errors
+                    .entry(p_dir_path.to_path_buf())
+                    .or_insert_with(|| std::io::Error::new(p_error.kind(), p_error.to_string()));
+            }
+        }
+    }
+}
+
+/// Returns the directory that the traversals were aborted at along with its error ([None] if they were not)
+pub fn aborted() -> Option<&'static (path::PathBuf, String)> {
+    return ABORTED.get();
+}
+
+/// Returns the errors that were collected, each along with the path of its directory (sorted by path)
+pub fn take_errors() -> Vec<(path::PathBuf, std::io::Error)> {
+    return ERRORS
+        .lock()
+        .map(|mut errors| std::mem::take(&mut *errors).into_iter().collect())
+        .unwrap_or_default();
+}
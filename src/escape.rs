@@ -0,0 +1,128 @@
+//! Escaping and quoting the names of entries (for `--escape` and `--quote`)
+//!
+//! With `--escape`, the control characters (such as newlines and tabs) and the other characters that cannot be printed
+//! in a name are written as C-style escapes like `ls -b` does, so that a name can neither break the layout of a listing
+//! nor send control sequences to the terminal. Escapes such as `\n` are used where they exist, and the bytes of the
+//! other characters (as well as bytes that are not valid UTF-8) are written as octal escapes such as `\033`. With
+//! `--quote`, the names are escaped in the same way and also enclosed in double quotes like `ls -Q` does (escaping the
+//! double quotes within them)
+//!
+//! Names are escaped in the listings meant to be read (the tree, `--long`, `--ls-format`, `--names-only` and
+//! `--sizes-only`), but not by `-0` or the export formats, which keep names intact on purpose
+
+use std::borrow::Cow;
+use std::path;
+
+use crate::{get_option, PrgOptions};
+
+/// Returns the text to print for a name or path, escaped (and quoted) if `--escape` or `--quote` was given
+///
+/// # Arguments
+///
+/// - `p_name` - the name or path
+pub fn name<S: AsRef<path::Path> + ?Sized>(p_name: &S) -> Cow<'_, str> {
+    let name = p_name.as_ref().as_os_str();
+    if !escaping() {
+        return name.to_string_lossy();
+    }
+
+    #[cfg(target_family = "unix")]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        return Cow::Owned(escape_bytes(name.as_bytes()));
+    }
+
+    #[cfg(not(target_family = "unix"))]
+    return Cow::Owned(escape_bytes(name.to_string_lossy().as_bytes()));
+}
+
+/// Returns the text to print for a name or path that was already converted to a string, escaped (and quoted) if
+/// `--escape` or `--quote` was given
+///
+/// # Arguments
+///
+/// - `p_text` - the name or path
+#[cfg(not(target_family = "unix"))]
+pub fn text(p_text: &str) -> Cow<'_, str> {
+    if !escaping() {
+        return Cow::Borrowed(p_text);
+    }
+
+    return Cow::Owned(escape_bytes(p_text.as_bytes()));
+}
+
+/// Returns whether names should be escaped
+fn escaping() -> bool {
+    return get_option(PrgOptions::Escape) || get_option(PrgOptions::Quote);
+}
+
+/// Escapes a name given as bytes, and encloses it in double quotes if `--quote` was given
+///
+/// # Arguments
+///
+/// - `p_bytes` - the bytes of the name (which need not be valid UTF-8)
+fn escape_bytes(p_bytes: &[u8]) -> String {
+    let quote = get_option(PrgOptions::Quote);
+
+    let mut res = String::with_capacity(p_bytes.len() + 2);
+    if quote {
+        res.push('"');
+    }
+
+    for chunk in p_bytes.utf8_chunks() {
+        for ch in chunk.valid().chars() {
+            match ch {
+                '\x07' => res.push_str("\\a"),
+                '\x08' => res.push_str("\\b"),
+                '\t' => res.push_str("\\t"),
+                '\n' => res.push_str("\\n"),
+                '\x0b' => res.push_str("\\v"),
+                '\x0c' => res.push_str("\\f"),
+                '\r' => res.push_str("\\r"),
+                // backslashes separate the components of paths outside of unix-like systems
+                '\\' if cfg!(target_family = "unix") => res.push_str("\\\\"),
+                '"' if quote => res.push_str("\\\""),
+                _ if !is_printable(ch) => {
+                    let mut buff = [0u8; 4];
+                    for byte in ch.encode_utf8(&mut buff).bytes() {
+                        push_octal(&mut res, byte);
+                    }
+                }
+                _ => res.push(ch),
+            }
+        }
+        for byte in chunk.invalid() {
+            push_octal(&mut res, *byte);
+        }
+    }
+
+    if quote {
+        res.push('"');
+    }
+    return res;
+}
+
+/// Returns whether a character can be printed as is (control characters and the invisible characters that change the
+/// direction or the joining of the text around them cannot)
+///
+/// # Arguments
+///
+/// - `p_ch` - the character
+fn is_printable(p_ch: char) -> bool {
+    return !p_ch.is_control()
+        && !matches!(
+            p_ch,
+            '\u{200b}'..='\u{200f}' | '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}' | '\u{feff}'
+        );
+}
+
+/// Appends the octal escape of a byte (such as `\033`)
+///
+/// # Arguments
+///
+/// - `p_res` - the text to append to
+/// - `p_byte` - the byte
+fn push_octal(p_res: &mut String, p_byte: u8) {
+    p_res.push_str(&format!("\\{:03o}", p_byte));
+}
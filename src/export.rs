@@ -0,0 +1,744 @@
+//! Printing the entries of a scan in machine readable formats (for `--format` and `--printf`)
+//!
+//! The entries are selected like with `--names-only` (so the search, recursion and entry type options apply), and
+//! each one is printed with its path, type, size, permissions, time of the last modification, depth (1 for the
+//! entries of the initial directory) and MIME type (only for regular files with `--mime`, see [crate::mime]). Directories are printed before their entries, with their own size instead of the
+//! cumulative size of their contents (except in formats that can add it once their entries have been printed). Values that are not available (such as the permissions on other platforms) are
+//! left empty or null
+//!
+//! With `--printf`, each entry is instead printed on a line of its own by filling in the placeholders of a template
+
+use std::path;
+use std::sync;
+use std::time;
+
+use crate::{
+    escape_line_field, filter, find, get_option, int_to_formatted_slice, mime, record, PrgOptions,
+};
+
+/// Format that the entries are printed in ([None] if `--format` was not given)
+static FORMAT: sync::OnceLock<Format> = sync::OnceLock::new();
+
+/// Names of the columns of the formats that print a header row
+const COLUMNS: [&str; 7] = [
+    "path",
+    "type",
+    "size",
+    "permissions",
+    "mtime",
+    "depth",
+    "mime",
+];
+
+/// Template that each entry is printed with ([None] if `--printf` was not given)
+static TEMPLATE: sync::OnceLock<Vec<TemplatePiece>> = sync::OnceLock::new();
+
+/// Whether an entry has been printed yet (used to separate the entries of formats that need separators)
+static PRINTED_ANY: sync::atomic::AtomicBool = sync::atomic::AtomicBool::new(false);
+
+/// Path of the directory that the scan starts from (set by [begin])
+static ROOT: sync::OnceLock<path::PathBuf> = sync::OnceLock::new();
+
+/// Number of levels that the next item of a Markdown list can be nested in (one more than the level of the last item,
+/// since an item can not be nested deeper than that)
+static LIST_LEVELS: sync::atomic::AtomicUsize = sync::atomic::AtomicUsize::new(0);
+
+/// Enumerates the formats that the entries can be printed in
+#[derive(Clone, Copy, PartialEq)]
+pub enum Format {
+    /// A single JSON document holding the path of the initial directory and an array of entries
+    Json,
+    /// A JSON object per line for each entry, printed as soon as the entry is read
+    Ndjson,
+    /// Comma-separated values with a header row (fields are quoted if required, like RFC 4180)
+    Csv,
+    /// Tab-separated values with a header row (backslashes, tabs and newlines are escaped like in recordings)
+    Tsv,
+    /// A Markdown table, or a nested list when scanning recursively
+    Markdown,
+    /// A specification in the format of BSD mtree, with the path of each entry relative to the initial directory
+    Mtree,
+    /// A Graphviz digraph with a node for each entry (labelled by its name and size) and an edge from its directory
+    Dot,
+}
+
+/// Value of an entry that a placeholder of a template is replaced with
+#[derive(Clone, Copy)]
+enum Placeholder {
+    Name,
+    Path,
+    Size,
+    Perms,
+    Mtime,
+    Type,
+    Depth,
+    Mime,
+}
+
+/// Piece of a template given to `--printf`
+enum TemplatePiece {
+    /// Text that is printed as is (with the escape sequences already replaced)
+    Text(String),
+    /// Placeholder that is replaced with a value of the entry
+    Value(Placeholder),
+}
+
+impl Placeholder {
+    /// Returns the placeholder with the given name, or [None] if no such placeholder exists
+    ///
+    /// # Arguments
+    ///
+    /// - `p_name` - name of the placeholder (as written between braces)
+    fn from_name(p_name: &str) -> Option<Placeholder> {
+        return match p_name {
+            "name" => Some(Placeholder::Name),
+            "path" => Some(Placeholder::Path),
+            "size" => Some(Placeholder::Size),
+            "perms" => Some(Placeholder::Perms),
+            "mtime" => Some(Placeholder::Mtime),
+            "type" => Some(Placeholder::Type),
+            "depth" => Some(Placeholder::Depth),
+            "mime" => Some(Placeholder::Mime),
+            _ => None,
+        };
+    }
+}
+
+impl Format {
+    /// Returns the format with the given name, or [None] if no such format exists
+    ///
+    /// # Arguments
+    ///
+    /// - `p_name` - name of the format (as given to `--format`)
+    pub fn from_name(p_name: &str) -> Option<Format> {
+        return match p_name {
+            "json" => Some(Format::Json),
+            "ndjson" => Some(Format::Ndjson),
+            "csv" => Some(Format::Csv),
+            "tsv" => Some(Format::Tsv),
+            "markdown" => Some(Format::Markdown),
+            "mtree" => Some(Format::Mtree),
+            "dot" => Some(Format::Dot),
+            _ => None,
+        };
+    }
+}
+
+/// Sets the format that the entries are printed in (has no effect if it is already set)
+///
+/// # Arguments
+///
+/// - `p_format` - the format
+pub fn set_format(p_format: Format) {
+    let _ = FORMAT.set(p_format);
+}
+
+/// Returns the format that the entries are printed in ([None] if `--format` was not given)
+pub fn get_format() -> Option<Format> {
+    return FORMAT.get().copied();
+}
+
+/// Parses the template that each entry is printed with and sets it (has no effect if it is already set)
+///
+/// Placeholders are written as a name between braces (such as `{size}`), `{{` and `}}` stand for literal braces, and
+/// `\t`, `\n` and `\\` stand for a tab, a newline and a backslash. Returns the text that could not be parsed as a
+/// placeholder if the template is invalid
+///
+/// # Arguments
+///
+/// - `p_template` - the template
+pub fn set_template(p_template: &str) -> Result<(), String> {
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+
+    let mut chars = p_template.chars().peekable();
+    while let Some(char) = chars.next() {
+        match (char, chars.peek()) {
+            ('{', Some('{')) | ('}', Some('}')) => {
+                text.push(char);
+                chars.next();
+            }
+            ('\\', Some('t')) => {
+                text.push('\t');
+                chars.next();
+            }
+            ('\\', Some('n')) => {
+                text.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                text.push('\\');
+                chars.next();
+            }
+            ('{', _) => {
+                let name: String = chars.by_ref().take_while(|char| *char != '}').collect();
+                let Some(placeholder) = Placeholder::from_name(&name) else {
+                    return Err(format!("{{{}}}", name));
+                };
+
+                if !text.is_empty() {
+                    pieces.push(TemplatePiece::Text(std::mem::take(&mut text)));
+                }
+                pieces.push(TemplatePiece::Value(placeholder));
+            }
+            _ => text.push(char),
+        }
+    }
+    if !text.is_empty() {
+        pieces.push(TemplatePiece::Text(text));
+    }
+
+    let _ = TEMPLATE.set(pieces);
+    return Ok(());
+}
+
+/// Returns the name of the type of an entry (`file`, `dir`, `symlink`, `socket`, `block`, `char`, `fifo`, or `special`
+/// for special files on other platforms)
+///
+/// # Arguments
+///
+/// - `p_metadata` - metadata of the entry
+pub fn type_name(p_metadata: &record::EntryMetadata) -> &'static str {
+    return match find::type_letter(p_metadata) {
+        Some('f') => "file",
+        Some('d') => "dir",
+        Some('l') => "symlink",
+        Some('s') => "socket",
+        Some('b') => "block",
+        Some('c') => "char",
+        Some('p') => "fifo",
+        _ => "special",
+    };
+}
+
+/// Returns the permission bits of an entry in octal (such as `0755`, [None] on other platforms)
+///
+/// # Arguments
+///
+/// - `p_metadata` - metadata of the entry
+#[cfg_attr(not(target_family = "unix"), allow(unused_variables))]
+fn permissions(p_metadata: &record::EntryMetadata) -> Option<String> {
+    #[cfg(target_family = "unix")]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        return Some(format!("{:04o}", p_metadata.permissions().mode() & 0o7777));
+    }
+
+    #[cfg(not(target_family = "unix"))]
+    return None;
+}
+
+/// Returns the time of the last modification of an entry in RFC 3339 format ([None] if it is not available)
+///
+/// # Arguments
+///
+/// - `p_metadata` - metadata of the entry
+fn modified(p_metadata: &record::EntryMetadata) -> Option<String> {
+    let modified: time::SystemTime = p_metadata.modified().ok()?;
+    let time = Into::<chrono::DateTime<chrono::offset::Local>>::into(modified);
+
+    return Some(time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+}
+
+/// Returns the values of the columns of an entry (see [COLUMNS], values that are not available are empty)
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+/// - `p_depth` - depth of the entry (1 for the entries of the initial directory)
+fn fields(
+    p_path_os: &path::Path,
+    p_metadata: &record::EntryMetadata,
+    p_depth: usize,
+) -> [String; 7] {
+    return [
+        p_path_os.to_string_lossy().into_owned(),
+        type_name(p_metadata).to_owned(),
+        p_metadata.len().to_string(),
+        permissions(p_metadata).unwrap_or_default(),
+        modified(p_metadata).unwrap_or_default(),
+        p_depth.to_string(),
+        mime_type(p_path_os, p_metadata)
+            .unwrap_or_default()
+            .to_owned(),
+    ];
+}
+
+/// Returns the MIME type of an entry for the formats ([None] unless `--mime` was given, since detecting it reads the
+/// start of every file)
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+fn mime_type(p_path_os: &path::Path, p_metadata: &record::EntryMetadata) -> Option<&'static str> {
+    if !filter::mime_types_given() {
+        return None;
+    }
+
+    return mime::detect(p_path_os, p_metadata);
+}
+
+/// Returns a line of comma-separated values, where values holding commas, quotes or line breaks are quoted (with the
+/// quotes within them doubled)
+///
+/// # Arguments
+///
+/// - `p_values` - the values
+fn csv_line<S: AsRef<str>>(p_values: &[S]) -> String {
+    let values: Vec<String> = p_values
+        .iter()
+        .map(|value| {
+            let value = value.as_ref();
+            match value.contains([',', '"', '\n', '\r']) {
+                true => format!("\"{}\"", value.replace('"', "\"\"")),
+                false => value.to_owned(),
+            }
+        })
+        .collect();
+
+    return values.join(",") + "\n";
+}
+
+/// Returns a line of tab-separated values, where backslashes, tabs and newlines within the values are escaped
+///
+/// # Arguments
+///
+/// - `p_values` - the values
+fn tsv_line<S: AsRef<str>>(p_values: &[S]) -> String {
+    let values: Vec<String> = p_values
+        .iter()
+        .map(|value| escape_line_field(value.as_ref()))
+        .collect();
+
+    return values.join("\t") + "\n";
+}
+
+/// Returns a string with the characters that Markdown would interpret escaped (line breaks are replaced by `<br>`)
+///
+/// # Arguments
+///
+/// - `p_str` - the string
+fn markdown_text(p_str: &str) -> String {
+    let mut res = String::with_capacity(p_str.len());
+
+    for ch in p_str.chars() {
+        match ch {
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '#' => {
+                res.push('\\');
+                res.push(ch);
+            }
+            '\n' => res.push_str("<br>"),
+            '\r' => {}
+            ch => res.push(ch),
+        }
+    }
+
+    return res;
+}
+
+/// Returns an entry as an item of a nested Markdown list, with its name and its details in parentheses
+///
+/// The item is nested by the depth of the entry below the item of the initial directory, unless some of its ancestors were not printed (such as while
+/// searching), in which case it is nested below the last item and shows its path relative to the initial directory
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+/// - `p_depth` - depth of the entry (1 for the entries of the initial directory)
+fn markdown_item(
+    p_path_os: &path::Path,
+    p_metadata: &record::EntryMetadata,
+    p_depth: usize,
+) -> String {
+    let allowed = LIST_LEVELS.load(sync::atomic::Ordering::Relaxed);
+    let level = p_depth.min(allowed);
+    LIST_LEVELS.store(level + 1, sync::atomic::Ordering::Relaxed);
+
+    let name = match level < p_depth {
+        true => ROOT
+            .get()
+            .and_then(|root| p_path_os.strip_prefix(root).ok())
+            .unwrap_or(p_path_os)
+            .to_string_lossy(),
+        false => p_path_os.file_name().unwrap_or_default().to_string_lossy(),
+    };
+
+    let mut details = vec![
+        type_name(p_metadata).to_owned(),
+        format!("{} bytes", int_to_formatted_slice(p_metadata.len())),
+    ];
+    details.extend(permissions(p_metadata));
+    details.extend(modified(p_metadata));
+
+    return format!(
+        "{:indent$}- {} ({})\n",
+        "",
+        markdown_text(&name),
+        details.join(", "),
+        indent = 2 * level
+    );
+}
+
+/// Returns a row of a Markdown table
+///
+/// # Arguments
+///
+/// - `p_values` - the values of the cells
+fn markdown_row<S: AsRef<str>>(p_values: &[S]) -> String {
+    let values: Vec<String> = p_values
+        .iter()
+        .map(|value| markdown_text(value.as_ref()))
+        .collect();
+
+    return format!("| {} |\n", values.join(" | "));
+}
+
+/// Returns an entry as a line of an mtree specification
+///
+/// The path is relative to the initial directory (starting with `./`), with the bytes that are not printable ASCII
+/// characters (along with spaces, `#` and `\\`) escaped as a backslash followed by three octal digits. Only the
+/// keywords whose values are available are printed, and the size is only printed for regular files
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+fn mtree_line(p_path_os: &path::Path, p_metadata: &record::EntryMetadata) -> String {
+    let rel_path = ROOT
+        .get()
+        .and_then(|root| p_path_os.strip_prefix(root).ok())
+        .unwrap_or(p_path_os);
+
+    let mut res = String::from(".");
+    for byte in path::Path::new("/")
+        .join(rel_path)
+        .as_os_str()
+        .as_encoded_bytes()
+    {
+        match byte {
+            b'#' | b'\\' => res.push_str(&format!("\\{:03o}", byte)),
+            0x21..=0x7e => res.push(*byte as char),
+            _ => res.push_str(&format!("\\{:03o}", byte)),
+        }
+    }
+
+    let mtree_type = match type_name(p_metadata) {
+        "symlink" => "link",
+        name => name,
+    };
+    res.push_str(&format!(" type={}", mtree_type));
+
+    if let Some(mode) = permissions(p_metadata) {
+        res.push_str(&format!(" mode={}", mode));
+    }
+    if p_metadata.is_file() {
+        res.push_str(&format!(" size={}", p_metadata.len()));
+    }
+    if let Some(modified) = p_metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(time::UNIX_EPOCH).ok())
+    {
+        res.push_str(&format!(
+            " time={}.{:09}",
+            modified.as_secs(),
+            modified.subsec_nanos()
+        ));
+    }
+
+    res.push('\n');
+    return res;
+}
+
+/// Returns a string quoted and escaped as a Graphviz string
+///
+/// # Arguments
+///
+/// - `p_str` - the string
+fn dot_string(p_str: &str) -> String {
+    let escaped = p_str
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+
+    return format!("\"{}\"", escaped);
+}
+
+/// Returns the attributes of the node of an entry, labelled by its name and size (larger sizes are printed in a larger
+/// font, growing with the number of digits)
+///
+/// # Arguments
+///
+/// - `p_name` - name of the entry
+/// - `p_size` - size of the entry
+fn dot_label(p_name: &str, p_size: u64) -> String {
+    return format!(
+        "label={}, fontsize={}",
+        dot_string(&format!(
+            "{}\n{} bytes",
+            p_name,
+            int_to_formatted_slice(p_size)
+        )),
+        8 + 2 * p_size.to_string().len()
+    );
+}
+
+/// Returns the node of an entry and the edge from its directory as Graphviz statements
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+fn dot_node(p_path_os: &path::Path, p_metadata: &record::EntryMetadata) -> String {
+    let shape = match type_name(p_metadata) {
+        "file" => "shape=note",
+        "dir" => "shape=folder",
+        "symlink" => "shape=box, style=dashed",
+        _ => "shape=diamond",
+    };
+
+    // the cumulative size of a directory is only known once its entries have been printed (see [print_dir_size])
+    let name = p_path_os.file_name().unwrap_or_default().to_string_lossy();
+    let label = match p_metadata.is_dir() {
+        true => format!("label={}", dot_string(&name)),
+        false => dot_label(&name, p_metadata.len()),
+    };
+
+    let node = dot_string(&p_path_os.to_string_lossy());
+    let mut res = format!("  {} [{}, {}];\n", node, label, shape);
+
+    if let Some(parent) = p_path_os.parent() {
+        res.push_str(&format!(
+            "  {} -> {};\n",
+            dot_string(&parent.to_string_lossy()),
+            node
+        ));
+    }
+
+    return res;
+}
+
+/// Returns a string quoted and escaped as a JSON string
+///
+/// # Arguments
+///
+/// - `p_str` - the string
+fn json_string(p_str: &str) -> String {
+    let mut res = String::with_capacity(p_str.len() + 2);
+    res.push('"');
+
+    for ch in p_str.chars() {
+        match ch {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => res.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => res.push(ch),
+        }
+    }
+
+    res.push('"');
+    return res;
+}
+
+/// Returns an entry as a JSON object
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+/// - `p_depth` - depth of the entry (1 for the entries of the initial directory)
+fn json_object(
+    p_path_os: &path::Path,
+    p_metadata: &record::EntryMetadata,
+    p_depth: usize,
+) -> String {
+    let optional = |p_value: Option<String>| match p_value {
+        Some(value) => json_string(&value),
+        None => "null".to_owned(),
+    };
+
+    return format!(
+        "{{\"path\": {}, \"type\": \"{}\", \"size\": {}, \"permissions\": {}, \"mtime\": {}, \"depth\": {}, \"mime\": {}}}",
+        json_string(&p_path_os.to_string_lossy()),
+        type_name(p_metadata),
+        p_metadata.len(),
+        optional(permissions(p_metadata)),
+        optional(modified(p_metadata)),
+        p_depth,
+        optional(mime_type(p_path_os, p_metadata).map(str::to_owned))
+    );
+}
+
+/// Prints what comes before the entries in the selected format
+///
+/// # Arguments
+///
+/// - `p_init_path` - path of the directory that the scan starts from
+#[allow(clippy::print_with_newline)]
+pub fn begin(p_init_path: &str) {
+    let _ = ROOT.set(path::PathBuf::from(p_init_path));
+
+    match get_format() {
+        Some(Format::Json) => {
+            print!(
+                "{{\n  \"root\": {},\n  \"entries\": [",
+                json_string(p_init_path)
+            );
+        }
+        Some(Format::Csv) => print!("{}", csv_line(&COLUMNS)),
+        Some(Format::Tsv) => print!("{}", tsv_line(&COLUMNS)),
+        Some(Format::Markdown) if get_option(PrgOptions::ShowRecursive) => {
+            print!("- {}\n", markdown_text(p_init_path));
+            LIST_LEVELS.store(1, sync::atomic::Ordering::Relaxed);
+        }
+        Some(Format::Mtree) => print!("#mtree\n"),
+        Some(Format::Dot) => {
+            print!("digraph fss {{\n  rankdir=LR;\n  node [fontname=\"monospace\"];\n");
+            print!("  {} [shape=folder];\n", dot_string(p_init_path));
+        }
+        Some(Format::Markdown) => {
+            // sizes and depths are aligned to the right
+            print!("{}", markdown_row(&COLUMNS));
+            print!("| --- | --- | ---: | --- | --- | ---: | --- |\n");
+        }
+        Some(Format::Ndjson) | None => {}
+    }
+}
+
+/// Prints an entry in the selected format
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+/// - `p_depth` - depth of the entry (1 for the entries of the initial directory)
+#[allow(clippy::print_with_newline)]
+pub fn print_entry(p_path_os: &path::Path, p_metadata: &record::EntryMetadata, p_depth: usize) {
+    let first = !PRINTED_ANY.swap(true, sync::atomic::Ordering::Relaxed);
+
+    match get_format() {
+        Some(Format::Json) => {
+            let separator = if first { "\n" } else { ",\n" };
+            print!(
+                "{}    {}",
+                separator,
+                json_object(p_path_os, p_metadata, p_depth)
+            );
+        }
+        Some(Format::Ndjson) => print!("{}\n", json_object(p_path_os, p_metadata, p_depth)),
+        Some(Format::Csv) => print!("{}", csv_line(&fields(p_path_os, p_metadata, p_depth))),
+        Some(Format::Tsv) => print!("{}", tsv_line(&fields(p_path_os, p_metadata, p_depth))),
+        Some(Format::Markdown) if get_option(PrgOptions::ShowRecursive) => {
+            print!("{}", markdown_item(p_path_os, p_metadata, p_depth));
+        }
+        Some(Format::Markdown) => {
+            print!("{}", markdown_row(&fields(p_path_os, p_metadata, p_depth)));
+        }
+        Some(Format::Mtree) => print!("{}", mtree_line(p_path_os, p_metadata)),
+        Some(Format::Dot) => print!("{}", dot_node(p_path_os, p_metadata)),
+        None => {}
+    }
+}
+
+/// Prints an entry on a line of its own by filling in the placeholders of the template given to `--printf` (values
+/// that are not available are left empty)
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+/// - `p_depth` - depth of the entry (1 for the entries of the initial directory)
+#[allow(clippy::print_with_newline)]
+pub fn print_template_entry(
+    p_path_os: &path::Path,
+    p_metadata: &record::EntryMetadata,
+    p_depth: usize,
+) {
+    let Some(pieces) = TEMPLATE.get() else {
+        return;
+    };
+
+    let mut line = String::new();
+    for piece in pieces {
+        match piece {
+            TemplatePiece::Text(text) => line.push_str(text),
+            TemplatePiece::Value(Placeholder::Name) => {
+                line.push_str(&p_path_os.file_name().unwrap_or_default().to_string_lossy())
+            }
+            TemplatePiece::Value(Placeholder::Path) => line.push_str(&p_path_os.to_string_lossy()),
+            TemplatePiece::Value(Placeholder::Size) => line.push_str(&p_metadata.len().to_string()),
+            TemplatePiece::Value(Placeholder::Perms) => {
+                line.push_str(&permissions(p_metadata).unwrap_or_default())
+            }
+            TemplatePiece::Value(Placeholder::Mtime) => {
+                line.push_str(&modified(p_metadata).unwrap_or_default())
+            }
+            TemplatePiece::Value(Placeholder::Type) => line.push_str(type_name(p_metadata)),
+            TemplatePiece::Value(Placeholder::Depth) => line.push_str(&p_depth.to_string()),
+            TemplatePiece::Value(Placeholder::Mime) => {
+                line.push_str(mime::detect(p_path_os, p_metadata).unwrap_or_default())
+            }
+        }
+    }
+
+    print!("{}\n", line);
+}
+
+/// Prints the cumulative size of a directory once all of its entries have been printed (in the formats that show it)
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the directory
+/// - `p_size` - cumulative size of the files within the directory
+#[allow(clippy::print_with_newline)]
+pub fn print_dir_size(p_path_os: &path::Path, p_size: u64) {
+    if get_format() == Some(Format::Dot) {
+        // the attributes of a node can be given again, and the later ones take precedence
+        print!(
+            "  {} [{}];\n",
+            dot_string(&p_path_os.to_string_lossy()),
+            dot_label(
+                &p_path_os.file_name().unwrap_or_default().to_string_lossy(),
+                p_size
+            )
+        );
+    }
+}
+
+/// Prints what comes after the entries in the selected format
+///
+/// # Arguments
+///
+/// - `p_total_size` - cumulative size of the files within the initial directory
+pub fn finish(p_total_size: u64) {
+    match get_format() {
+        Some(Format::Json) => match PRINTED_ANY.load(sync::atomic::Ordering::Relaxed) {
+            true => print!("\n  ]\n}}\n"),
+            false => print!("]\n}}\n"),
+        },
+        Some(Format::Dot) => {
+            let init_path = ROOT
+                .get()
+                .map(|root| root.to_string_lossy())
+                .unwrap_or_default();
+            print!(
+                "  {} [{}];\n}}\n",
+                dot_string(&init_path),
+                dot_label(&init_path, p_total_size)
+            );
+        }
+        Some(Format::Ndjson)
+        | Some(Format::Csv)
+        | Some(Format::Tsv)
+        | Some(Format::Markdown)
+        | Some(Format::Mtree)
+        | None => {}
+    }
+}
@@ -0,0 +1,825 @@
+//! Narrowing down the entries shown by a scan by their properties (for `--ext`, `--type`, `--min-size`, `--max-size`,
+//! `--newer-than`, `--older-than`, `--accessed-within`, `--status-changed-within`, `--owner`, `--group`, `--perm`,
+//! `--empty-files`, `--broken-symlinks`, `--only-text`, `--only-binary` and `--mime`)
+//!
+//! With `--ext`, only the entries whose names end with one of the given extensions are shown (regardless of case, so
+//! `--ext jpg` also shows `IMG_0001.JPG`). Extensions of several parts such as `tar.gz` can be given as well. With
+//! `--type`, only the entries of the given types are shown, using the letters of `find -type` (and the types that are
+//! given are shown without needing `-f`, `-l` or `-s`). With `--min-size` and `--max-size`, only the regular files
+//! whose sizes lie in the given range (including its bounds) are shown, and with `--empty-files`, only the regular
+//! files that are empty (which are shown without needing `-f`), so that the summary counts them. Likewise, with
+//! `--broken-symlinks`, only the symlinks whose targets do not exist are shown (without needing `-l`), along with the
+//! paths that they point to. With `--only-text` and `--only-binary`, only the regular files whose contents are text or
+//! binary are shown (without needing `-f`), as classified by [crate::content]. With `--mime`, only the regular files
+//! whose MIME types (detected from their contents by [crate::mime]) match one of the given patterns are shown, where
+//! `*` stands for any text (so `image/*` shows every image) and a pattern without a slash stands for all of its
+//! subtypes (so `image` does too).
+//!
+//! Hidden entries (those whose names start with a dot, and on Windows those with the hidden attribute) are left out of
+//! listings like `ls` does, along with everything within them, unless `-a` is given. Searches still find them unless
+//! `--no-hidden` is given, which leaves them out of every scan. Listings of sizes and exports into databases account
+//! for everything, so they only leave hidden entries out with `--no-hidden`.
+//!
+//! With `--newer-than` and `--older-than`, only the entries last modified in the given window are shown, where each
+//! bound is either a date (such as `2024-01-01` or `2024-01-01 12:30`, in the local time zone) or an age (such as
+//! `30d`, counted back from the start of the scan).
+//! Likewise, `--accessed-within` and `--status-changed-within` only show the entries last accessed, or whose status
+//! (such as their permissions, owner or contents) last changed, at or after the given date or within the given age.
+//! Replayed entries do not record these times, so they never pass these two filters. With `--owner` and `--group`,
+//! only the entries owned by the given user or group are shown, given either by name (looked up in the user and group
+//! databases) or by ID. Replayed entries do not record their owners either, so they never pass these filters.
+//!
+//! With `--perm`, only the entries whose permission bits match the given mode are shown, like `find -perm`. The mode is
+//! either octal (such as `644`) or symbolic (such as `u+w,g=rx`, starting from no bits). A plain mode matches the
+//! permissions exactly, a mode starting with `-` matches if all of its bits are set (so `-u+w` shows whatever its owner
+//! can write to) and a mode starting with `/` matches if any of its bits are set (so `/022` shows whatever is writable
+//! by its group or by others).
+//!
+//! Directories never have an extension or a size of their own, so searches and flat listings (such as `--names-only`)
+//! do not show them with these filters (though the entries within them are still searched), while the tree still lists
+//! them so that the entries within them can be placed. Entries that are filtered out are neither shown nor counted in
+//! the summaries of the listing or of the matches, which report how many entries were filtered out instead
+
+use std::path;
+use std::sync;
+use std::time;
+
+use crate::content;
+use crate::find::{fnmatch, type_letter};
+use crate::{mime, record};
+
+#[cfg(target_family = "unix")]
+use crate::ls;
+use crate::{get_option, parse_age, parse_size, set_option, PrgOptions};
+
+/// Extensions given to `--ext`, in lowercase and without the leading dot ([None] if it was not given)
+static EXTENSIONS: sync::OnceLock<Vec<String>> = sync::OnceLock::new();
+
+/// Letters of the types given to `--type` ([None] if it was not given)
+static TYPES: sync::OnceLock<Vec<char>> = sync::OnceLock::new();
+
+/// Letters of the types that can be given to `--type` (files, directories, symlinks, sockets, block devices, character
+/// devices and pipes)
+const TYPE_LETTERS: [char; 7] = ['f', 'd', 'l', 's', 'b', 'c', 'p'];
+
+/// Smallest size of the shown files, given to `--min-size` ([None] if it was not given)
+static MIN_SIZE: sync::OnceLock<u64> = sync::OnceLock::new();
+
+/// Largest size of the shown files, given to `--max-size` ([None] if it was not given)
+static MAX_SIZE: sync::OnceLock<u64> = sync::OnceLock::new();
+
+/// Earliest time of the last modification of the shown entries, given to `--newer-than` ([None] if it was not given)
+static NEWER_THAN: sync::OnceLock<TimeBound> = sync::OnceLock::new();
+
+/// Time that the shown entries must have been last modified before, given to `--older-than` ([None] if it was not
+/// given)
+static OLDER_THAN: sync::OnceLock<TimeBound> = sync::OnceLock::new();
+
+/// Earliest time of the last access to the shown entries, given to `--accessed-within` ([None] if it was not given)
+static ACCESSED_WITHIN: sync::OnceLock<TimeBound> = sync::OnceLock::new();
+
+/// Earliest time of the last change to the status of the shown entries, given to `--status-changed-within` ([None] if
+/// it was not given)
+static STATUS_CHANGED_WITHIN: sync::OnceLock<TimeBound> = sync::OnceLock::new();
+
+/// ID of the user that owns the shown entries, given to `--owner` ([None] if it was not given)
+static OWNER: sync::OnceLock<u32> = sync::OnceLock::new();
+
+/// ID of the group that owns the shown entries, given to `--group` ([None] if it was not given)
+static GROUP: sync::OnceLock<u32> = sync::OnceLock::new();
+
+/// Mode that the permissions of the shown entries are matched against, given to `--perm` ([None] if it was not given)
+static PERM: sync::OnceLock<(PermMatch, u32)> = sync::OnceLock::new();
+
+/// Permission bits that a mode can hold (the setuid, setgid and sticky bits along with those of the owner, group and
+/// others)
+const PERM_BITS: u32 = 0o7777;
+
+/// Whether only the empty regular files are shown, set with `--empty-files`
+static EMPTY_FILES: sync::atomic::AtomicBool = sync::atomic::AtomicBool::new(false);
+
+/// Whether only the symlinks whose targets do not exist are shown, set with `--broken-symlinks`
+static BROKEN_SYMLINKS: sync::atomic::AtomicBool = sync::atomic::AtomicBool::new(false);
+
+/// Kind of contents of the shown files, set with `--only-text` or `--only-binary` ([None] if neither was given, and the
+/// last of them if both were)
+static CONTENT: sync::Mutex<Option<content::Kind>> = sync::Mutex::new(None);
+
+/// Patterns of the MIME types of the shown files given to `--mime`, in lowercase ([None] if it was not given)
+static MIME_TYPES: sync::OnceLock<Vec<Vec<char>>> = sync::OnceLock::new();
+
+/// Points in time that the time filters stand for, resolved from their bounds when first needed
+static TIME_WINDOWS: sync::OnceLock<TimeWindows> = sync::OnceLock::new();
+
+/// Formats of the dates that can be given to the time filters (dates without a time start at midnight)
+const DATE_FORMATS: [&str; 3] = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S"];
+
+/// Number of entries that would have been shown if they had not been filtered out
+static FILTERED_CNT: sync::atomic::AtomicU64 = sync::atomic::AtomicU64::new(0);
+
+/// Sets the extensions that the shown entries must have, from the comma-separated list given to `--ext`
+///
+/// Returns `Err` if the list does not hold any extension
+///
+/// # Arguments
+///
+/// - `p_list` - the list of extensions (such as `rs,toml,md`, optionally with the leading dots)
+pub fn set_extensions(p_list: &str) -> Result<(), ()> {
+    let extensions: Vec<String> = p_list
+        .split(',')
+        .map(|extension| extension.trim().trim_start_matches('.').to_lowercase())
+        .filter(|extension| !extension.is_empty())
+        .collect();
+
+    if extensions.is_empty() {
+        return Err(());
+    }
+
+    let _ = EXTENSIONS.set(extensions);
+    return Ok(());
+}
+
+/// Sets the patterns that the MIME types of the shown files must match, from the comma-separated list given to `--mime`
+///
+/// Returns `Err` if the list does not hold any pattern
+///
+/// # Arguments
+///
+/// - `p_list` - the list of patterns (such as `image/*,application/pdf`)
+pub fn set_mime_types(p_list: &str) -> Result<(), ()> {
+    let patterns: Vec<Vec<char>> = p_list
+        .split(',')
+        .map(|pattern| pattern.trim().to_lowercase())
+        .filter(|pattern| !pattern.is_empty())
+        .map(|pattern| match pattern.contains('/') {
+            true => pattern.chars().collect(),
+            false => format!("{}/*", pattern).chars().collect(),
+        })
+        .collect();
+
+    if patterns.is_empty() {
+        return Err(());
+    }
+
+    let _ = MIME_TYPES.set(patterns);
+    return Ok(());
+}
+
+/// Sets the types of the shown entries, from the comma-separated list of letters given to `--type`
+///
+/// Returns the name that is not a type if the list is invalid
+///
+/// # Arguments
+///
+/// - `p_list` - the list of types (such as `f,l`)
+pub fn set_types(p_list: &str) -> Result<(), String> {
+    let mut types = Vec::new();
+
+    for name in p_list.split(',') {
+        let name = name.trim();
+        let Some(letter) = name
+            .chars()
+            .next()
+            .filter(|letter| name.len() == 1 && TYPE_LETTERS.contains(letter))
+        else {
+            return Err(name.to_owned());
+        };
+        if !types.contains(&letter) {
+            types.push(letter);
+        }
+    }
+
+    let _ = TYPES.set(types);
+    return Ok(());
+}
+
+/// Sets the options that show the types given to `--type`, the files shown by `--empty-files`, `--only-text`,
+/// `--only-binary` and `--mime` and the symlinks shown by `--broken-symlinks` (has no effect if none of them was given)
+pub fn apply_options() {
+    if EMPTY_FILES.load(sync::atomic::Ordering::Relaxed)
+        || content_kind().is_some()
+        || MIME_TYPES.get().is_some()
+    {
+        set_option(PrgOptions::ShowFiles);
+    }
+    if BROKEN_SYMLINKS.load(sync::atomic::Ordering::Relaxed) {
+        set_option(PrgOptions::ShowSymlinks);
+    }
+
+    let Some(types) = TYPES.get() else {
+        return;
+    };
+
+    if types.contains(&'f') {
+        set_option(PrgOptions::ShowFiles);
+    }
+    if types.contains(&'l') {
+        set_option(PrgOptions::ShowSymlinks);
+    }
+    if types
+        .iter()
+        .any(|letter| matches!(letter, 's' | 'b' | 'c' | 'p'))
+    {
+        set_option(PrgOptions::ShowSpecial);
+    }
+}
+
+/// Bound of a time filter
+#[derive(Clone, Copy)]
+enum TimeBound {
+    /// Point in time that lies the given duration before the start of the scan
+    Ago(time::Duration),
+    /// Point in time in the local time zone
+    At(chrono::NaiveDateTime),
+}
+
+impl TimeBound {
+    /// Parses the bound given to a time filter, either as a date (such as `2024-01-01`) or as an age (such as `30d`)
+    ///
+    /// Returns [None] if it is neither
+    ///
+    /// # Arguments
+    ///
+    /// - `p_bound` - the bound
+    fn parse(p_bound: &str) -> Option<TimeBound> {
+        if let Ok(date) = chrono::NaiveDate::parse_from_str(p_bound, "%Y-%m-%d") {
+            return Some(TimeBound::At(date.and_hms_opt(0, 0, 0)?));
+        }
+
+        for format in DATE_FORMATS {
+            if let Ok(date_time) = chrono::NaiveDateTime::parse_from_str(p_bound, format) {
+                return Some(TimeBound::At(date_time));
+            }
+        }
+
+        return parse_age(p_bound).map(TimeBound::Ago);
+    }
+
+    /// Returns the point in time that the bound stands for ([None] if it does not exist, such as before the epoch)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_now` - the time that ages are counted back from
+    fn resolve(&self, p_now: time::SystemTime) -> Option<time::SystemTime> {
+        use chrono::TimeZone;
+
+        return match self {
+            TimeBound::Ago(age) => p_now.checked_sub(*age),
+            TimeBound::At(date_time) => chrono::Local
+                .from_local_datetime(date_time)
+                .earliest()
+                .map(time::SystemTime::from),
+        };
+    }
+}
+
+/// Points in time that the time filters stand for ([None] for those that were not given or do not exist)
+struct TimeWindows {
+    /// Earliest time of the last modification
+    newer_than: Option<time::SystemTime>,
+    /// Time that the last modification must be before
+    older_than: Option<time::SystemTime>,
+    /// Earliest time of the last access
+    accessed_within: Option<time::SystemTime>,
+    /// Earliest time of the last change to the status
+    status_changed_within: Option<time::SystemTime>,
+}
+
+impl TimeWindows {
+    /// Returns the points in time that the given time filters stand for, with ages counted back from now
+    fn resolve() -> TimeWindows {
+        let now = time::SystemTime::now();
+        let resolve = |p_bound: &sync::OnceLock<TimeBound>| {
+            p_bound.get().and_then(|bound| bound.resolve(now))
+        };
+
+        return TimeWindows {
+            newer_than: resolve(&NEWER_THAN),
+            older_than: resolve(&OLDER_THAN),
+            accessed_within: resolve(&ACCESSED_WITHIN),
+            status_changed_within: resolve(&STATUS_CHANGED_WITHIN),
+        };
+    }
+}
+
+/// Sets the smallest size of the shown files, from the value given to `--min-size` (such as `10M`)
+///
+/// Returns `Err` if the value is not a size
+///
+/// # Arguments
+///
+/// - `p_size` - the value
+pub fn set_min_size(p_size: &str) -> Result<(), ()> {
+    let _ = MIN_SIZE.set(parse_size(p_size).ok_or(())?);
+    return Ok(());
+}
+
+/// Sets the largest size of the shown files, from the value given to `--max-size` (such as `1G`)
+///
+/// Returns `Err` if the value is not a size
+///
+/// # Arguments
+///
+/// - `p_size` - the value
+pub fn set_max_size(p_size: &str) -> Result<(), ()> {
+    let _ = MAX_SIZE.set(parse_size(p_size).ok_or(())?);
+    return Ok(());
+}
+
+/// Shows only the empty regular files (for `--empty-files`)
+pub fn set_empty_files() {
+    EMPTY_FILES.store(true, sync::atomic::Ordering::Relaxed);
+}
+
+/// Shows only the symlinks whose targets do not exist (for `--broken-symlinks`)
+pub fn set_broken_symlinks() {
+    BROKEN_SYMLINKS.store(true, sync::atomic::Ordering::Relaxed);
+}
+
+/// Returns whether only the symlinks whose targets do not exist are shown
+pub fn broken_symlinks() -> bool {
+    return BROKEN_SYMLINKS.load(sync::atomic::Ordering::Relaxed);
+}
+
+/// Shows only the regular files whose contents are of the given kind (for `--only-text` and `--only-binary`)
+///
+/// # Arguments
+///
+/// - `p_kind` - the kind
+pub fn set_content(p_kind: content::Kind) {
+    if let Ok(mut kind) = CONTENT.lock() {
+        *kind = Some(p_kind);
+    }
+}
+
+/// Returns whether the shown files are filtered by their MIME types (so that their types have been asked for)
+pub fn mime_types_given() -> bool {
+    return MIME_TYPES.get().is_some();
+}
+
+/// Returns the kind of contents of the shown files ([None] if files are not filtered by their contents)
+fn content_kind() -> Option<content::Kind> {
+    return CONTENT.lock().ok().and_then(|kind| *kind);
+}
+
+/// Sets the earliest time of the last modification of the shown entries, from the date or age given to `--newer-than`
+///
+/// Returns `Err` if the value is neither a date nor an age
+///
+/// # Arguments
+///
+/// - `p_bound` - the value
+pub fn set_newer_than(p_bound: &str) -> Result<(), ()> {
+    let _ = NEWER_THAN.set(TimeBound::parse(p_bound).ok_or(())?);
+    return Ok(());
+}
+
+/// Sets the time that the shown entries must have been last modified before, from the date or age given to
+/// `--older-than`
+///
+/// Returns `Err` if the value is neither a date nor an age
+///
+/// # Arguments
+///
+/// - `p_bound` - the value
+pub fn set_older_than(p_bound: &str) -> Result<(), ()> {
+    let _ = OLDER_THAN.set(TimeBound::parse(p_bound).ok_or(())?);
+    return Ok(());
+}
+
+/// Sets the earliest time of the last access to the shown entries, from the date or age given to `--accessed-within`
+///
+/// Returns `Err` if the value is neither a date nor an age
+///
+/// # Arguments
+///
+/// - `p_bound` - the value
+pub fn set_accessed_within(p_bound: &str) -> Result<(), ()> {
+    let _ = ACCESSED_WITHIN.set(TimeBound::parse(p_bound).ok_or(())?);
+    return Ok(());
+}
+
+/// Sets the earliest time of the last change to the status of the shown entries, from the date or age given to
+/// `--status-changed-within`
+///
+/// Returns `Err` if the value is neither a date nor an age
+///
+/// # Arguments
+///
+/// - `p_bound` - the value
+pub fn set_status_changed_within(p_bound: &str) -> Result<(), ()> {
+    let _ = STATUS_CHANGED_WITHIN.set(TimeBound::parse(p_bound).ok_or(())?);
+    return Ok(());
+}
+
+/// Sets the user that owns the shown entries, from the name or ID given to `--owner`
+///
+/// Returns `Err` if no user has the given name and it is not an ID either
+///
+/// # Arguments
+///
+/// - `p_user` - the name or ID
+pub fn set_owner(p_user: &str) -> Result<(), ()> {
+    #[cfg(target_family = "unix")]
+    let uid = ls::lookup_user_id(p_user).or_else(|| p_user.parse::<u32>().ok());
+    #[cfg(not(target_family = "unix"))]
+    let uid = p_user.parse::<u32>().ok();
+
+    let _ = OWNER.set(uid.ok_or(())?);
+    return Ok(());
+}
+
+/// Sets the group that owns the shown entries, from the name or ID given to `--group`
+///
+/// Returns `Err` if no group has the given name and it is not an ID either
+///
+/// # Arguments
+///
+/// - `p_group` - the name or ID
+pub fn set_group(p_group: &str) -> Result<(), ()> {
+    #[cfg(target_family = "unix")]
+    let gid = ls::lookup_group_id(p_group).or_else(|| p_group.parse::<u32>().ok());
+    #[cfg(not(target_family = "unix"))]
+    let gid = p_group.parse::<u32>().ok();
+
+    let _ = GROUP.set(gid.ok_or(())?);
+    return Ok(());
+}
+
+#[cfg(target_family = "unix")]
+/// Returns the IDs of the user and group that own an entry ([None] for replayed entries)
+///
+/// # Arguments
+///
+/// - `p_metadata` - metadata of the entry
+fn owner_ids(p_metadata: &record::EntryMetadata) -> Option<(u32, u32)> {
+    use std::os::unix::fs::MetadataExt;
+
+    let raw = p_metadata.raw()?;
+    return Some((raw.uid(), raw.gid()));
+}
+
+#[cfg(not(target_family = "unix"))]
+/// Returns the IDs of the user and group that own an entry (not available on this platform)
+///
+/// # Arguments
+///
+/// - `_p_metadata` - metadata of the entry
+fn owner_ids(_p_metadata: &record::EntryMetadata) -> Option<(u32, u32)> {
+    return None;
+}
+
+/// How the permissions of an entry are matched against the mode given to `--perm`
+#[derive(Clone, Copy)]
+enum PermMatch {
+    /// The permissions are exactly the mode
+    Exact,
+    /// The permissions hold every bit of the mode (for modes starting with `-`)
+    All,
+    /// The permissions hold any bit of the mode, or the mode has no bits (for modes starting with `/`)
+    Any,
+}
+
+/// Sets the mode that the permissions of the shown entries are matched against, from the value given to `--perm`
+///
+/// Returns `Err` if the value is not a mode
+///
+/// # Arguments
+///
+/// - `p_mode` - the value (such as `644`, `-u+w` or `/022`)
+pub fn set_perm(p_mode: &str) -> Result<(), ()> {
+    let (perm_match, mode) = match p_mode.chars().next() {
+        Some('-') => (PermMatch::All, &p_mode[1..]),
+        Some('/') => (PermMatch::Any, &p_mode[1..]),
+        _ => (PermMatch::Exact, p_mode),
+    };
+
+    let _ = PERM.set((perm_match, parse_mode(mode).ok_or(())?));
+    return Ok(());
+}
+
+/// Parses a mode given either in octal (such as `644`) or symbolically (such as `u+w,g=rx`, starting from no bits)
+///
+/// Returns [None] if it is neither
+///
+/// # Arguments
+///
+/// - `p_mode` - the mode
+fn parse_mode(p_mode: &str) -> Option<u32> {
+    if !p_mode.is_empty() && p_mode.chars().all(|ch| ch.is_digit(8)) {
+        return u32::from_str_radix(p_mode, 8)
+            .ok()
+            .filter(|mode| mode & !PERM_BITS == 0);
+    }
+
+    let mut mode = 0;
+    for clause in p_mode.split(',') {
+        let op_idx = clause.find(['+', '-', '='])?;
+        let (who, rest) = clause.split_at(op_idx);
+
+        // the classes that the clause applies to (every class if none is given)
+        let mut who_mask = 0;
+        for ch in who.chars() {
+            who_mask |= match ch {
+                'u' => 0o4700,
+                'g' => 0o2070,
+                'o' => 0o1007,
+                'a' => 0o7777,
+                _ => return None,
+            };
+        }
+        if who_mask == 0 {
+            who_mask = 0o7777;
+        }
+
+        // a clause can hold several operators one after the other (such as `u+r-w`)
+        let mut op = ' ';
+        let mut bits = 0;
+        for ch in rest.chars().chain([',']) {
+            let perm_mask = match ch {
+                '+' | '-' | '=' | ',' => {
+                    mode = match op {
+                        '+' => mode | (bits & who_mask),
+                        '-' => mode & !(bits & who_mask),
+                        '=' => (mode & !who_mask) | (bits & who_mask),
+                        _ => mode,
+                    };
+                    (op, bits) = (ch, 0);
+                    continue;
+                }
+                'r' => 0o444,
+                'w' => 0o222,
+                'x' | 'X' => 0o111,
+                's' => 0o6000,
+                't' => 0o1000,
+                _ => return None,
+            };
+            bits |= perm_mask;
+        }
+    }
+
+    return Some(mode);
+}
+
+#[cfg(target_family = "unix")]
+/// Returns the permission bits of an entry
+///
+/// # Arguments
+///
+/// - `p_metadata` - metadata of the entry
+fn perm_bits(p_metadata: &record::EntryMetadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+
+    return Some(p_metadata.permissions().mode() & PERM_BITS);
+}
+
+#[cfg(not(target_family = "unix"))]
+/// Returns the permission bits of an entry (not available on this platform)
+///
+/// # Arguments
+///
+/// - `_p_metadata` - metadata of the entry
+fn perm_bits(_p_metadata: &record::EntryMetadata) -> Option<u32> {
+    return None;
+}
+
+/// Returns whether an entry is skipped for being hidden
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+/// - `p_listing` - whether the entries are being listed or exported rather than searched or sized (hidden entries are
+///   only skipped in searches and sizes with `--no-hidden`)
+pub fn hides(p_path_os: &path::Path, p_metadata: &record::EntryMetadata, p_listing: bool) -> bool {
+    if get_option(PrgOptions::ShowHidden) || !(p_listing || get_option(PrgOptions::NoHidden)) {
+        return false;
+    }
+
+    return is_hidden(p_path_os, p_metadata);
+}
+
+/// Returns whether an entry is hidden (its name starts with a dot, or on Windows, it has the hidden attribute)
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+fn is_hidden(p_path_os: &path::Path, p_metadata: &record::EntryMetadata) -> bool {
+    if p_path_os
+        .file_name()
+        .is_some_and(|name| name.as_encoded_bytes().starts_with(b"."))
+    {
+        return true;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::fs::MetadataExt;
+
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+        if p_metadata
+            .raw()
+            .is_some_and(|raw| raw.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0)
+        {
+            return true;
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    let _ = p_metadata;
+
+    return false;
+}
+
+/// Returns whether any filter was given
+pub fn active() -> bool {
+    return EXTENSIONS.get().is_some()
+        || TYPES.get().is_some()
+        || MIN_SIZE.get().is_some()
+        || MAX_SIZE.get().is_some()
+        || EMPTY_FILES.load(sync::atomic::Ordering::Relaxed)
+        || BROKEN_SYMLINKS.load(sync::atomic::Ordering::Relaxed)
+        || content_kind().is_some()
+        || MIME_TYPES.get().is_some()
+        || NEWER_THAN.get().is_some()
+        || OLDER_THAN.get().is_some()
+        || ACCESSED_WITHIN.get().is_some()
+        || STATUS_CHANGED_WITHIN.get().is_some()
+        || OWNER.get().is_some()
+        || GROUP.get().is_some()
+        || PERM.get().is_some();
+}
+
+/// Returns whether an entry that is about to be shown passes every filter, and counts it as filtered out if it does not
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+pub fn keep(p_path_os: &path::Path, p_metadata: &record::EntryMetadata) -> bool {
+    if matches(p_path_os, p_metadata) {
+        return true;
+    }
+
+    FILTERED_CNT.fetch_add(1, sync::atomic::Ordering::Relaxed);
+    return false;
+}
+
+/// Returns the number of entries that were filtered out so far
+pub fn filtered_cnt() -> u64 {
+    return FILTERED_CNT.load(sync::atomic::Ordering::Relaxed);
+}
+
+/// Starts counting the entries that are filtered out from 0 again (before scanning each of several directories)
+pub fn reset_filtered_cnt() {
+    FILTERED_CNT.store(0, sync::atomic::Ordering::Relaxed);
+}
+
+/// Returns whether an entry passes every filter that was given (every entry passes if none was given)
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+pub fn matches(p_path_os: &path::Path, p_metadata: &record::EntryMetadata) -> bool {
+    if let Some(extensions) = EXTENSIONS.get() {
+        if p_metadata.is_dir() || !has_extension(p_path_os, extensions) {
+            return false;
+        }
+    }
+
+    if let Some(types) = TYPES.get() {
+        if !type_letter(p_metadata).is_some_and(|letter| types.contains(&letter)) {
+            return false;
+        }
+    }
+
+    if MIN_SIZE.get().is_some() || MAX_SIZE.get().is_some() {
+        if type_letter(p_metadata) != Some('f') {
+            return false;
+        }
+
+        let size = p_metadata.len();
+        if MIN_SIZE.get().is_some_and(|min_size| size < *min_size)
+            || MAX_SIZE.get().is_some_and(|max_size| size > *max_size)
+        {
+            return false;
+        }
+    }
+
+    if EMPTY_FILES.load(sync::atomic::Ordering::Relaxed)
+        && (type_letter(p_metadata) != Some('f') || p_metadata.len() != 0)
+    {
+        return false;
+    }
+
+    // a symlink is broken if the path it points to can not be resolved, the same way as it is colored as an orphan
+    if BROKEN_SYMLINKS.load(sync::atomic::Ordering::Relaxed)
+        && !(p_metadata.is_symlink() && record::canonicalize(p_path_os).is_err())
+    {
+        return false;
+    }
+
+    if NEWER_THAN.get().is_some() || OLDER_THAN.get().is_some() {
+        let windows = TIME_WINDOWS.get_or_init(TimeWindows::resolve);
+
+        // the window includes its start but not its end, so that adjoining windows do not overlap
+        let Ok(modified) = p_metadata.modified() else {
+            return false;
+        };
+        if windows
+            .newer_than
+            .is_some_and(|newer_than| modified < newer_than)
+            || windows
+                .older_than
+                .is_some_and(|older_than| modified >= older_than)
+        {
+            return false;
+        }
+    }
+
+    if ACCESSED_WITHIN.get().is_some() {
+        let windows = TIME_WINDOWS.get_or_init(TimeWindows::resolve);
+        if !p_metadata.accessed().is_ok_and(|accessed| {
+            return windows
+                .accessed_within
+                .is_none_or(|within| accessed >= within);
+        }) {
+            return false;
+        }
+    }
+
+    if STATUS_CHANGED_WITHIN.get().is_some() {
+        let windows = TIME_WINDOWS.get_or_init(TimeWindows::resolve);
+        if !p_metadata.status_changed().is_ok_and(|changed| {
+            return windows
+                .status_changed_within
+                .is_none_or(|within| changed >= within);
+        }) {
+            return false;
+        }
+    }
+
+    if OWNER.get().is_some() || GROUP.get().is_some() {
+        let Some((uid, gid)) = owner_ids(p_metadata) else {
+            return false;
+        };
+        if OWNER.get().is_some_and(|owner| uid != *owner)
+            || GROUP.get().is_some_and(|group| gid != *group)
+        {
+            return false;
+        }
+    }
+
+    if let Some((perm_match, mode)) = PERM.get() {
+        let Some(bits) = perm_bits(p_metadata) else {
+            return false;
+        };
+        let matched = match perm_match {
+            PermMatch::Exact => bits == *mode,
+            PermMatch::All => bits & mode == *mode,
+            PermMatch::Any => *mode == 0 || bits & mode != 0,
+        };
+        if !matched {
+            return false;
+        }
+    }
+
+    // the contents are checked last, since reading them is slower than looking at the metadata
+    if let Some(kind) = content_kind() {
+        if content::classify(p_path_os, p_metadata) != Some(kind) {
+            return false;
+        }
+    }
+    if let Some(patterns) = MIME_TYPES.get() {
+        let Some(mime) = mime::detect(p_path_os, p_metadata) else {
+            return false;
+        };
+        let mime: Vec<char> = mime.chars().collect();
+        if !patterns.iter().any(|pattern| fnmatch(pattern, &mime)) {
+            return false;
+        }
+    }
+
+    return true;
+}
+
+/// Returns whether the name of an entry ends with one of the extensions (regardless of case)
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_extensions` - the extensions, in lowercase and without the leading dot
+fn has_extension(p_path_os: &path::Path, p_extensions: &[String]) -> bool {
+    let Some(name) = p_path_os.file_name() else {
+        return false;
+    };
+    let name = name.to_string_lossy().to_lowercase();
+
+    // the name needs more than the extension itself, so that a dotfile such as `.rs` has no extension
+    return p_extensions.iter().any(|extension| {
+        return name.len() > extension.len() + 1
+            && name.ends_with(extension.as_str())
+            && name[..name.len() - extension.len()].ends_with('.');
+    });
+}
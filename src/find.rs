@@ -0,0 +1,532 @@
+//! Evaluating a subset of the expressions of POSIX find (for `--find-expr`)
+//!
+//! The supported primaries are `-name`, `-iname`, `-type`, `-size`, `-mtime`, `-maxdepth`, `-prune` and `-print`,
+//! which can be combined with `!` (or `-not`), `-a` (or `-and`, or nothing), `-o` (or `-or`) and parentheses. Like
+//! find, the entries for which the expression is true are printed, unless it contains `-print` (in which case only the
+//! entries reaching a `-print` are printed)
+
+use std::path;
+use std::time;
+
+use crate::i18n::{tr, Msg};
+use crate::{escape, record};
+use crate::{get_option, progress, PrgOptions, SpecialFileType};
+
+/// Enumerates the ways in which a number given to a primary (such as `-size +10k`) can be compared
+#[derive(Clone, Copy)]
+enum Cmp {
+    /// The value must be less than the number (`-n`)
+    Less,
+    /// The value must be exactly the number (`n`)
+    Exact,
+    /// The value must be more than the number (`+n`)
+    More,
+}
+
+impl Cmp {
+    /// Splits the comparison off a number given to a primary, and returns it along with the number
+    ///
+    /// Returns [None] if the number could not be parsed
+    ///
+    /// # Arguments
+    ///
+    /// - `p_arg` - the number, optionally preceded by `+` or `-`
+    fn parse(p_arg: &str) -> Option<(Cmp, u64)> {
+        let (cmp, number) = if let Some(number) = p_arg.strip_prefix('+') {
+            (Cmp::More, number)
+        } else if let Some(number) = p_arg.strip_prefix('-') {
+            (Cmp::Less, number)
+        } else {
+            (Cmp::Exact, p_arg)
+        };
+
+        return Some((cmp, number.parse().ok()?));
+    }
+
+    /// Returns whether a value satisfies the comparison
+    ///
+    /// # Arguments
+    ///
+    /// - `p_value` - the value to compare
+    /// - `p_number` - the number that was given to the primary
+    fn matches(&self, p_value: u64, p_number: u64) -> bool {
+        return match self {
+            Cmp::Less => p_value < p_number,
+            Cmp::Exact => p_value == p_number,
+            Cmp::More => p_value > p_number,
+        };
+    }
+}
+
+/// Node of a parsed expression
+enum Expr {
+    /// `-name` (or `-iname` if the flag is set) with its pattern
+    Name(String, bool),
+    /// `-type` with the letter of the type
+    Type(char),
+    /// `-size` with its comparison, number and the size of its unit in bytes
+    Size(Cmp, u64, u64),
+    /// `-mtime` with its comparison and number of days
+    Mtime(Cmp, u64),
+    /// `-prune`
+    Prune,
+    /// `-print`
+    Print,
+    /// `!` with its operand
+    Not(Box<Expr>),
+    /// `-a` with its operands
+    And(Box<Expr>, Box<Expr>),
+    /// `-o` with its operands
+    Or(Box<Expr>, Box<Expr>),
+}
+
+/// Enumerates the errors that can occur while parsing an expression
+pub enum ParseError {
+    /// A primary or operator that is not supported (or not known)
+    UnknownPrimary(String),
+    /// A primary whose argument is missing
+    MissingArgument(String),
+    /// A primary whose argument is invalid, along with the argument
+    InvalidArgument(String, String),
+    /// An operator without an operand, a misplaced parenthesis or an unterminated quote
+    Syntax,
+}
+
+impl ParseError {
+    /// Prints the error (translated to the language of the messages)
+    pub fn print(&self) {
+        match self {
+            ParseError::UnknownPrimary(primary) => {
+                print!("{}", tr!(Msg::FindUnknownPrimary, primary));
+            }
+            ParseError::MissingArgument(primary) => {
+                print!("{}", tr!(Msg::FindMissingArgument, primary));
+            }
+            ParseError::InvalidArgument(primary, arg) => {
+                print!("{}", tr!(Msg::FindInvalidArgument, arg, primary));
+            }
+            ParseError::Syntax => print!("{}", tr!(Msg::FindSyntaxError)),
+        }
+    }
+}
+
+/// A parsed find expression
+pub struct FindExpr {
+    /// The expression ([None] if it is empty, in which case every entry is printed)
+    expr: Option<Expr>,
+    /// Maximum depth at which entries are evaluated (entries directly under the initial directory lie at depth 1)
+    max_depth: Option<u64>,
+    /// Whether the expression contains `-print` (if not, the entries for which it is true are printed)
+    has_print: bool,
+}
+
+/// Splits an expression into words like a shell would (separated by whitespace, with quotes and backslashes escaping)
+///
+/// Returns [None] if a quote is not terminated
+///
+/// # Arguments
+///
+/// - `p_expr` - the expression
+fn split_words(p_expr: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = p_expr.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next()? {
+                        quote if quote == c => break,
+                        '\\' if c == '"' => word.push(chars.next()?),
+                        other => word.push(other),
+                    }
+                }
+            }
+            '\\' => word.get_or_insert_with(String::new).push(chars.next()?),
+            c if c.is_whitespace() => words.extend(word.take()),
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+
+    return Some(words);
+}
+
+/// Parser over the words of an expression (recursive descent, from the lowest precedence operator to the highest)
+struct Parser {
+    /// The words of the expression
+    words: Vec<String>,
+    /// Position of the next word to parse
+    pos: usize,
+    /// Maximum depth given with `-maxdepth`
+    max_depth: Option<u64>,
+    /// Whether `-print` was found
+    has_print: bool,
+}
+
+impl Parser {
+    /// Returns the next word without consuming it
+    fn peek(&self) -> Option<&str> {
+        return self.words.get(self.pos).map(String::as_str);
+    }
+
+    /// Parses operands joined by `-o`
+    fn parse_or(&mut self) -> Result<Option<Expr>, ParseError> {
+        let Some(mut expr) = self.parse_and()? else {
+            return Ok(None);
+        };
+
+        while matches!(self.peek(), Some("-o" | "-or")) {
+            self.pos += 1;
+            let rhs = self.parse_and()?.ok_or(ParseError::Syntax)?;
+            expr = Expr::Or(Box::new(expr), Box::new(rhs));
+        }
+
+        return Ok(Some(expr));
+    }
+
+    /// Parses operands joined by `-a` (or by nothing)
+    fn parse_and(&mut self) -> Result<Option<Expr>, ParseError> {
+        let Some(mut expr) = self.parse_not()? else {
+            return Ok(None);
+        };
+
+        loop {
+            if matches!(self.peek(), Some("-a" | "-and")) {
+                self.pos += 1;
+                let rhs = self.parse_not()?.ok_or(ParseError::Syntax)?;
+                expr = Expr::And(Box::new(expr), Box::new(rhs));
+            } else if let Some(rhs) = self.parse_not()? {
+                expr = Expr::And(Box::new(expr), Box::new(rhs));
+            } else {
+                return Ok(Some(expr));
+            }
+        }
+    }
+
+    /// Parses an operand that may be negated with `!` (or `-not`)
+    fn parse_not(&mut self) -> Result<Option<Expr>, ParseError> {
+        if matches!(self.peek(), Some("!" | "-not")) {
+            self.pos += 1;
+            let operand = self.parse_not()?.ok_or(ParseError::Syntax)?;
+            return Ok(Some(Expr::Not(Box::new(operand))));
+        }
+
+        return self.parse_primary();
+    }
+
+    /// Parses a primary or an expression in parentheses
+    ///
+    /// Returns [None] at the end of the expression, before a closing parenthesis and before `-o` or `-a`
+    fn parse_primary(&mut self) -> Result<Option<Expr>, ParseError> {
+        let Some(word) = self.peek().map(str::to_owned) else {
+            return Ok(None);
+        };
+
+        if matches!(word.as_str(), ")" | "-o" | "-or" | "-a" | "-and") {
+            return Ok(None);
+        }
+        self.pos += 1;
+
+        if word == "(" {
+            let expr = self.parse_or()?.ok_or(ParseError::Syntax)?;
+            if self.peek() != Some(")") {
+                return Err(ParseError::Syntax);
+            }
+            self.pos += 1;
+            return Ok(Some(expr));
+        }
+
+        // the primaries without an argument
+        match word.as_str() {
+            "-prune" => return Ok(Some(Expr::Prune)),
+            "-print" => {
+                self.has_print = true;
+                return Ok(Some(Expr::Print));
+            }
+            "-name" | "-iname" | "-type" | "-size" | "-mtime" | "-maxdepth" => {}
+            _ => return Err(ParseError::UnknownPrimary(word)),
+        }
+
+        let Some(arg) = self.peek().map(str::to_owned) else {
+            return Err(ParseError::MissingArgument(word));
+        };
+        self.pos += 1;
+
+        let invalid = || ParseError::InvalidArgument(word.clone(), arg.clone());
+
+        let expr = match word.as_str() {
+            "-name" => Expr::Name(arg.clone(), false),
+            "-iname" => Expr::Name(arg.to_lowercase(), true),
+            "-type" => match arg.as_str() {
+                "f" | "d" | "l" | "s" | "b" | "c" | "p" => Expr::Type(arg.chars().next().unwrap()),
+                _ => return Err(invalid()),
+            },
+            "-size" => {
+                // the size is counted in blocks of 512 bytes if no unit is given
+                let (number, unit_len) = match arg.chars().last() {
+                    Some('c') => (&arg[..arg.len() - 1], 1),
+                    Some('w') => (&arg[..arg.len() - 1], 2),
+                    Some('b') => (&arg[..arg.len() - 1], 512),
+                    Some('k') => (&arg[..arg.len() - 1], 1 << 10),
+                    Some('M') => (&arg[..arg.len() - 1], 1 << 20),
+                    Some('G') => (&arg[..arg.len() - 1], 1 << 30),
+                    _ => (arg.as_str(), 512),
+                };
+                let (cmp, number) = Cmp::parse(number).ok_or_else(invalid)?;
+                Expr::Size(cmp, number, unit_len)
+            }
+            "-mtime" => {
+                let (cmp, days) = Cmp::parse(&arg).ok_or_else(invalid)?;
+                Expr::Mtime(cmp, days)
+            }
+            _ => {
+                // like find, -maxdepth applies to the whole expression wherever it is given, so it is not a node
+                self.max_depth = Some(arg.parse().map_err(|_| invalid())?);
+                return self.parse_not();
+            }
+        };
+
+        return Ok(Some(expr));
+    }
+}
+
+/// Parses an expression
+///
+/// # Arguments
+///
+/// - `p_expr` - the expression (such as `-name '*.rs' -type f`)
+pub fn parse(p_expr: &str) -> Result<FindExpr, ParseError> {
+    let words = split_words(p_expr).ok_or(ParseError::Syntax)?;
+
+    let mut parser = Parser {
+        words,
+        pos: 0,
+        max_depth: None,
+        has_print: false,
+    };
+
+    let expr = parser.parse_or()?;
+
+    // anything left over is a closing parenthesis without an opening one
+    if parser.pos < parser.words.len() {
+        return Err(ParseError::Syntax);
+    }
+
+    return Ok(FindExpr {
+        expr,
+        max_depth: parser.max_depth,
+        has_print: parser.has_print,
+    });
+}
+
+/// Returns whether a name matches a shell pattern (with `*`, `?` and `[...]`, where `\` escapes the next character)
+///
+/// # Arguments
+///
+/// - `p_pattern` - the pattern
+/// - `p_name` - the name to match
+pub fn fnmatch(p_pattern: &[char], p_name: &[char]) -> bool {
+    let Some((&first, rest)) = p_pattern.split_first() else {
+        return p_name.is_empty();
+    };
+
+    match first {
+        '*' => {
+            return (0..=p_name.len()).any(|skip| fnmatch(rest, &p_name[skip..]));
+        }
+        '?' => {
+            return !p_name.is_empty() && fnmatch(rest, &p_name[1..]);
+        }
+        '[' => {
+            let Some((&c, name_rest)) = p_name.split_first() else {
+                return false;
+            };
+
+            // the set ends at the first closing bracket that is not its first character
+            let negated = matches!(rest.first(), Some('!' | '^'));
+            let set = if negated { &rest[1..] } else { rest };
+            let Some(end) = set.iter().skip(1).position(|&c| c == ']').map(|i| i + 1) else {
+                // without a closing bracket, the bracket is an ordinary character
+                return c == '[' && fnmatch(rest, name_rest);
+            };
+
+            let mut in_set = false;
+            let mut i = 0;
+            while i < end {
+                if i + 2 < end && set[i + 1] == '-' {
+                    in_set |= set[i] <= c && c <= set[i + 2];
+                    i += 3;
+                } else {
+                    in_set |= set[i] == c;
+                    i += 1;
+                }
+            }
+
+            return in_set != negated && fnmatch(&set[end + 1..], name_rest);
+        }
+        '\\' if !rest.is_empty() => {
+            return p_name.first() == Some(&rest[0]) && fnmatch(&rest[1..], &p_name[1..]);
+        }
+        _ => {
+            return p_name.first() == Some(&first) && fnmatch(rest, &p_name[1..]);
+        }
+    }
+}
+
+/// Returns the letter that `-type` uses for the type of an entry ([None] for special files on other platforms)
+///
+/// # Arguments
+///
+/// - `p_metadata` - metadata of the entry
+pub fn type_letter(p_metadata: &record::EntryMetadata) -> Option<char> {
+    if p_metadata.is_symlink() {
+        return Some('l');
+    }
+    if p_metadata.is_dir() {
+        return Some('d');
+    }
+
+    return match p_metadata.special_file_type() {
+        #[cfg(target_family = "unix")]
+        SpecialFileType::Socket => Some('s'),
+        #[cfg(target_family = "unix")]
+        SpecialFileType::BlockDevice => Some('b'),
+        #[cfg(target_family = "unix")]
+        SpecialFileType::CharDevice => Some('c'),
+        #[cfg(target_family = "unix")]
+        SpecialFileType::Fifo => Some('p'),
+        SpecialFileType::NA if p_metadata.is_file() => Some('f'),
+        SpecialFileType::NA => None,
+    };
+}
+
+/// State of the evaluation of an expression for a single entry
+struct Evaluation<'a> {
+    /// Path of the entry
+    path: &'a path::Path,
+    /// Metadata of the entry
+    metadata: &'a record::EntryMetadata,
+    /// Time that the ages of entries are measured from
+    now: time::SystemTime,
+    /// Whether `-prune` was evaluated for the entry
+    pruned: bool,
+}
+
+impl Evaluation<'_> {
+    /// Evaluates an expression (or part of one) for the entry, printing it if `-print` is evaluated
+    ///
+    /// # Arguments
+    ///
+    /// - `p_expr` - the expression
+    #[allow(clippy::print_with_newline)]
+    fn eval(&mut self, p_expr: &Expr) -> bool {
+        return match p_expr {
+            Expr::Name(pattern, ignore_case) => {
+                let Some(name) = self.path.file_name() else {
+                    return false;
+                };
+                let name = name.to_string_lossy();
+                let name = if *ignore_case {
+                    name.to_lowercase()
+                } else {
+                    name.into_owned()
+                };
+
+                fnmatch(
+                    &pattern.chars().collect::<Vec<char>>(),
+                    &name.chars().collect::<Vec<char>>(),
+                )
+            }
+            Expr::Type(letter) => type_letter(self.metadata) == Some(*letter),
+            Expr::Size(cmp, number, unit_len) => {
+                // like find, the size is rounded up to the next whole unit
+                cmp.matches(self.metadata.len().div_ceil(*unit_len), *number)
+            }
+            Expr::Mtime(cmp, days) => {
+                let Ok(modified) = self.metadata.modified() else {
+                    return false;
+                };
+                let age = self.now.duration_since(modified).unwrap_or_default();
+
+                cmp.matches(age.as_secs() / 86400, *days)
+            }
+            Expr::Prune => {
+                self.pruned = true;
+                true
+            }
+            Expr::Print => {
+                print!("{}\n", escape::name(&self.path));
+                true
+            }
+            Expr::Not(operand) => !self.eval(operand),
+            Expr::And(lhs, rhs) => self.eval(lhs) && self.eval(rhs),
+            Expr::Or(lhs, rhs) => self.eval(lhs) || self.eval(rhs),
+        };
+    }
+}
+
+/// Recursively evaluates an expression for the entries of a directory, printing the ones that it selects
+///
+/// # Arguments
+///
+/// - `p_find_expr` - the expression
+/// - `p_depth` - depth at which the entries of the directory lie (1 for the initial directory)
+/// - `p_dir_path` - path of the directory
+/// - `p_now` - time that the ages of entries are measured from
+#[allow(clippy::print_with_newline)]
+pub fn find_entries(
+    p_find_expr: &FindExpr,
+    p_depth: u64,
+    p_dir_path: &path::Path,
+    p_now: time::SystemTime,
+) {
+    let entries = match record::read_dir(p_dir_path) {
+        Ok(entries) => entries,
+        Err(error) => {
+            if get_option(PrgOptions::ShowErrors) {
+                eprint!(
+                    "{}",
+                    tr!(Msg::IterateError, p_dir_path.to_string_lossy(), error)
+                );
+            }
+            return;
+        }
+    };
+
+    for entry in entries {
+        progress::tick();
+
+        // if the current entry or its metadata could not be read for some reason, then silently skip it
+        let Ok((path_os, metadata)) = entry else {
+            continue;
+        };
+
+        let mut evaluation = Evaluation {
+            path: &path_os,
+            metadata: &metadata,
+            now: p_now,
+            pruned: false,
+        };
+
+        let selected = match &p_find_expr.expr {
+            Some(expr) => evaluation.eval(expr),
+            None => true,
+        };
+
+        if selected && !p_find_expr.has_print {
+            print!("{}\n", escape::name(&path_os));
+        }
+
+        if metadata.is_dir()
+            && !evaluation.pruned
+            && p_find_expr
+                .max_depth
+                .is_none_or(|max_depth| p_depth < max_depth)
+        {
+            find_entries(p_find_expr, 1 + p_depth, &path_os, p_now);
+        }
+    }
+}
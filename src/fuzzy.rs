@@ -0,0 +1,161 @@
+//! Searching names with a fuzzy pattern (for `--fuzzy`)
+//!
+//! A name matches if it holds every character of the pattern in the same order, though not necessarily next to each
+//! other (so `mnrs` matches `main.rs`). Like fzf, the pattern ignores case unless it holds an uppercase character. Each
+//! match is scored by how the characters of the pattern line up with the name - characters that follow each other or
+//! start a word (after `-`, `_`, `.`, a space or a change to uppercase) score more, while gaps between them score
+//! less. The matching entries are held until the search finishes, and then shown ordered from the best match to the
+//! worst (with the shorter paths first among equal scores). With `--full-path`, the pattern is matched against the
+//! path of each entry relative to the initial directory instead of its name
+
+use std::path;
+use std::sync;
+
+use crate::{record, search_subject};
+
+/// Score of each character of the pattern found in the name
+const MATCH_SCORE: i64 = 16;
+
+/// Extra score of a character that directly follows the previous character of the pattern in the name
+const CONSECUTIVE_BONUS: i64 = 8;
+
+/// Extra score of a character at the start of the name
+const START_BONUS: i64 = 10;
+
+/// Extra score of a character that starts a word within the name
+const WORD_BONUS: i64 = 8;
+
+/// Score lost by a gap between two characters of the pattern (regardless of its length)
+const GAP_START_PENALTY: i64 = 3;
+
+/// Score lost by each character of a gap between two characters of the pattern
+const GAP_PENALTY: i64 = 1;
+
+/// Entries that matched the pattern so far, along with their scores
+static MATCHES: sync::Mutex<Vec<Match>> = sync::Mutex::new(Vec::new());
+
+/// Entry that matched the pattern
+pub struct Match {
+    /// How well the name of the entry matches the pattern (higher is better)
+    pub score: i64,
+    /// Path of the entry
+    pub path: path::PathBuf,
+    /// Metadata of the entry
+    pub metadata: record::EntryMetadata,
+}
+
+/// Returns how well a name matches a pattern ([None] if it does not hold every character of the pattern in order)
+///
+/// # Arguments
+///
+/// - `p_pattern` - the pattern
+/// - `p_name` - the name
+pub fn score(p_pattern: &str, p_name: &str) -> Option<i64> {
+    let ignore_case = !p_pattern.chars().any(char::is_uppercase);
+    let pattern: Vec<char> = match ignore_case {
+        true => p_pattern.to_lowercase().chars().collect(),
+        false => p_pattern.chars().collect(),
+    };
+    let name: Vec<char> = p_name.chars().collect();
+
+    if pattern.is_empty() {
+        return Some(0);
+    }
+
+    let folded: Vec<char> = match ignore_case {
+        true => name
+            .iter()
+            .map(|ch| ch.to_lowercase().next().unwrap_or(*ch))
+            .collect(),
+        false => name.clone(),
+    };
+
+    // best[j] is the best score of the characters of the pattern so far, with the last of them at j in the name
+    let mut best: Vec<Option<i64>> = vec![None; name.len()];
+    for (i, &ch) in pattern.iter().enumerate() {
+        let mut next: Vec<Option<i64>> = vec![None; name.len()];
+
+        for j in 0..name.len() {
+            if folded[j] != ch {
+                continue;
+            }
+
+            let bonus = if j == 0 {
+                START_BONUS
+            } else if matches!(name[j - 1], '-' | '_' | '.' | ' ')
+                || (name[j - 1].is_lowercase() && name[j].is_uppercase())
+            {
+                WORD_BONUS
+            } else {
+                0
+            };
+
+            // the first character can be anywhere, while the others continue the best of the earlier positions
+            let prev = if i == 0 {
+                Some(0)
+            } else {
+                (0..j)
+                    .filter_map(|k| {
+                        let score = best[k]?;
+                        return Some(match j - k - 1 {
+                            0 => score + CONSECUTIVE_BONUS,
+                            gap => score - GAP_START_PENALTY - GAP_PENALTY * gap as i64,
+                        });
+                    })
+                    .max()
+            };
+
+            next[j] = prev.map(|prev| prev + MATCH_SCORE + bonus);
+        }
+
+        best = next;
+    }
+
+    return best.into_iter().flatten().max();
+}
+
+/// Holds an entry until the search finishes if its name (or its relative path with `--full-path`) matches any of the
+/// patterns (scored by the one it matches best)
+///
+/// # Arguments
+///
+/// - `p_patterns` - the patterns
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+pub fn add(p_patterns: &[String], p_path_os: &path::Path, p_metadata: &record::EntryMetadata) {
+    let Some(name) = search_subject(p_path_os, false) else {
+        return;
+    };
+    let Some(score) = p_patterns
+        .iter()
+        .filter_map(|pattern| score(pattern, &name))
+        .max()
+    else {
+        return;
+    };
+
+    if let Ok(mut matches) = MATCHES.lock() {
+        matches.push(Match {
+            score,
+            path: p_path_os.to_path_buf(),
+            metadata: p_metadata.clone(),
+        });
+    }
+}
+
+/// Returns the entries that matched the pattern, from the best match to the worst
+pub fn take_matches() -> Vec<Match> {
+    let mut matches = match MATCHES.lock() {
+        Ok(mut matches) => std::mem::take(&mut *matches),
+        Err(_) => Vec::new(),
+    };
+
+    matches.sort_by(|a, b| {
+        return b
+            .score
+            .cmp(&a.score)
+            .then_with(|| a.path.as_os_str().len().cmp(&b.path.as_os_str().len()))
+            .then_with(|| a.path.cmp(&b.path));
+    });
+    return matches;
+}
@@ -0,0 +1,179 @@
+//! Matching entries against a glob pattern (for `--glob`)
+//!
+//! A pattern without a slash (such as `*.rs`) is matched against the name of each entry, while a pattern with a slash
+//! (such as `src/**/*.rs`) is matched against the path of each entry relative to the directory that the scan starts
+//! from. Besides `*`, `?` and `[...]` (which never match a slash), `**` matches any number of directories and
+//! `{a,b}` matches any of the comma-separated alternatives (which can be nested). With `--ignore-case`, both the
+//! pattern and the paths are lowercased before they are matched. With `--full-path`, every pattern is matched against
+//! the relative path of each entry (so `*_golden.json` only matches entries directly within the directory)
+
+use std::path;
+use std::sync;
+
+use crate::find::fnmatch;
+use crate::fold_case;
+use crate::ignore::match_components;
+
+/// Patterns that the alternatives of each of the patterns given to `--glob` expand to (a given pattern matches an entry
+/// if any of its expansions matches it, [None] if it was not given)
+static GLOBS: sync::OnceLock<Vec<Vec<Pattern>>> = sync::OnceLock::new();
+
+/// Directory that the patterns with a slash are relative to (the one being scanned, when several are)
+static BASE: sync::RwLock<Option<path::PathBuf>> = sync::RwLock::new(None);
+
+/// Single pattern that the alternatives of a glob expand to
+enum Pattern {
+    /// Pattern without a slash, which is matched against the name of an entry
+    Name(Vec<char>),
+    /// Components of a pattern with a slash, which is matched against the relative path of an entry
+    Path(Vec<Vec<char>>),
+}
+
+/// Sets the patterns that entries are matched against
+///
+/// # Arguments
+///
+/// - `p_patterns` - the patterns (in the order that they were given)
+/// - `p_full_path` - whether patterns without a slash are matched against the relative path as well
+pub fn set_patterns(p_patterns: &[String], p_full_path: bool) {
+    let patterns = p_patterns
+        .iter()
+        .map(|pattern| parse(pattern, p_full_path))
+        .collect();
+
+    let _ = GLOBS.set(patterns);
+}
+
+/// Sets the directory that the patterns with a slash are relative to
+///
+/// # Arguments
+///
+/// - `p_base` - directory that the scan starts from
+pub fn set_base(p_base: &path::Path) {
+    if let Ok(mut base) = BASE.write() {
+        *base = Some(p_base.to_path_buf());
+    }
+}
+
+/// Returns the patterns that a glob pattern expands to
+///
+/// # Arguments
+///
+/// - `p_pattern` - the pattern
+/// - `p_full_path` - whether a pattern without a slash is matched against the relative path as well
+fn parse(p_pattern: &str, p_full_path: bool) -> Vec<Pattern> {
+    return expand_braces(p_pattern)
+        .into_iter()
+        .map(|pattern| {
+            let pattern = pattern.trim_start_matches("./");
+            if !pattern.contains('/') && !p_full_path {
+                return Pattern::Name(pattern.chars().collect());
+            }
+
+            let components = pattern
+                .trim_start_matches('/')
+                .split('/')
+                .filter(|component| !component.is_empty())
+                .map(|component| component.chars().collect())
+                .collect();
+            return Pattern::Path(components);
+        })
+        .collect();
+}
+
+/// Returns whether an entry matches one of the patterns given to `--glob` (every entry matches if it was not given)
+///
+/// # Arguments
+///
+/// - `p_idx` - index of the pattern (in the order that they were given)
+/// - `p_path_os` - path of the entry
+pub fn matches(p_idx: usize, p_path_os: &path::Path) -> bool {
+    let Some(globs) = GLOBS.get() else {
+        return true;
+    };
+    let Some(patterns) = globs.get(p_idx) else {
+        return false;
+    };
+
+    let name: Vec<char> = fold_case(p_path_os.file_name().unwrap_or_default().to_string_lossy())
+        .chars()
+        .collect();
+
+    // entries outside of the initial directory (such as while watching it through a symlink) are matched by their names
+    let base = BASE.read().ok();
+    let relative = base
+        .as_ref()
+        .and_then(|base| base.as_deref())
+        .and_then(|base| p_path_os.strip_prefix(base).ok());
+    let components: Vec<Vec<char>> = match relative {
+        Some(relative) => relative
+            .iter()
+            .map(|component| fold_case(component.to_string_lossy()).chars().collect())
+            .collect(),
+        None => vec![name.clone()],
+    };
+
+    return patterns.iter().any(|pattern| match pattern {
+        Pattern::Name(pattern) => fnmatch(pattern, &name),
+        Pattern::Path(components_pattern) => match_components(components_pattern, &components),
+    });
+}
+
+/// Returns the patterns that the alternatives (`{a,b}`) within a pattern expand to
+///
+/// Braces that are escaped, unbalanced or do not hold a comma are kept as they are
+///
+/// # Arguments
+///
+/// - `p_pattern` - the pattern
+fn expand_braces(p_pattern: &str) -> Vec<String> {
+    let chars: Vec<char> = p_pattern.chars().collect();
+
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            '{' => {
+                // the alternatives are separated by the commas that are not nested in other braces
+                let mut depth = 0;
+                let mut commas = Vec::new();
+                let mut end = None;
+                let mut j = i + 1;
+                while j < chars.len() {
+                    match chars[j] {
+                        '\\' => j += 1,
+                        '{' => depth += 1,
+                        '}' if depth == 0 => {
+                            end = Some(j);
+                            break;
+                        }
+                        '}' => depth -= 1,
+                        ',' if depth == 0 => commas.push(j),
+                        _ => {}
+                    }
+                    j += 1;
+                }
+
+                let Some(end) = end.filter(|_| !commas.is_empty()) else {
+                    i += 1;
+                    continue;
+                };
+
+                let prefix: String = chars[..i].iter().collect();
+                let suffix: String = chars[end + 1..].iter().collect();
+                let bounds: Vec<usize> = [i].into_iter().chain(commas).chain([end]).collect();
+
+                return bounds
+                    .windows(2)
+                    .flat_map(|bound| {
+                        let alternative: String = chars[bound[0] + 1..bound[1]].iter().collect();
+                        return expand_braces(&format!("{}{}{}", prefix, alternative, suffix));
+                    })
+                    .collect();
+            }
+            _ => i += 1,
+        }
+    }
+
+    return vec![p_pattern.to_owned()];
+}
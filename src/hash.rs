@@ -0,0 +1,666 @@
+//! Hashing of file contents using pluggable backends
+//!
+//! Each backend implements [ContentHasher] and is compiled in only if its cargo feature is enabled
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{IsTerminal, Write};
+use std::path;
+use std::sync;
+use std::time;
+
+use crate::i18n::{tr, Msg};
+use crate::pool;
+use crate::{
+    escape_line_field, open_content_file, read_block, release_cached_pages, unescape_line_field,
+};
+
+/// Number of bytes of a file that are read and fed to the hasher at once
+const HASH_CHUNK_LEN: usize = 1 << 20;
+
+/// Files smaller than this many bytes are hashed without reporting progress
+const HASH_PROGRESS_MIN_LEN: u64 = 256 << 20;
+
+/// Minimum time between two updates of the progress of hashing a file
+const HASH_PROGRESS_INTERVAL: time::Duration = time::Duration::from_millis(500);
+
+/// First line of a hash cache or checkpoint file (identifies the format)
+const HASH_CACHE_HEADER: &str = "# fss hash cache v1";
+
+/// Hashing algorithm selected by the user (the fastest available one is used if none was selected)
+static HASH_ALGO: sync::OnceLock<HashAlgo> = sync::OnceLock::new();
+
+/// Cache of previously calculated hashes ([None] if neither a hash cache nor a checkpoint file was given)
+static HASH_CACHE: sync::Mutex<Option<HashCache>> = sync::Mutex::new(None);
+
+/// Files being hashed ahead of the traversal by the work pool
+static HASHES_AHEAD: pool::Pending<std::io::Result<String>> = pool::Pending::new();
+
+/// Cache of previously calculated hashes, persisted to a file between runs
+struct HashCache {
+    /// Path of the file that the cache is loaded from and saved to ([None] if only a checkpoint file was given)
+    path: Option<path::PathBuf>,
+    /// Cached hashes indexed by the name of the algorithm and the absolute path of the file (so that the hashes of
+    /// different algorithms are kept side by side)
+    entries: HashMap<(String, path::PathBuf), CachedHash>,
+    /// Whether any hashes were added since the cache was loaded
+    dirty: bool,
+    /// Path of the checkpoint file and the file itself, opened for appending ([None] if no checkpoint file was given)
+    checkpoint: Option<(path::PathBuf, fs::File)>,
+}
+
+impl HashCache {
+    /// Returns a new, empty cache which is neither saved nor checkpointed
+    fn new() -> HashCache {
+        return HashCache {
+            path: None,
+            entries: HashMap::new(),
+            dirty: false,
+            checkpoint: None,
+        };
+    }
+}
+
+/// Hash of a file along with the properties that are used to check if the file has changed since it was hashed
+struct CachedHash {
+    /// Name of the algorithm the hash was calculated with
+    algo: String,
+    /// Identifier of the device the file lies on (always 0 on windows)
+    dev: u64,
+    /// Inode number of the file (always 0 on windows)
+    ino: u64,
+    /// Length of the file (in bytes)
+    size: u64,
+    /// Modification time of the file (in nanoseconds since the UNIX epoch)
+    mtime: u128,
+    /// Hash of the file as a hexadecimal string
+    hash: String,
+}
+
+impl CachedHash {
+    /// Returns a cache entry for the given file and hash
+    ///
+    /// # Arguments
+    ///
+    /// - `p_algo` - the algorithm the hash was calculated with
+    /// - `p_metadata` - metadata of the file
+    /// - `p_hash` - hash of the file
+    fn new(p_algo: HashAlgo, p_metadata: &fs::Metadata, p_hash: String) -> CachedHash {
+        #[cfg(target_family = "unix")]
+        let (dev, ino) = {
+            use std::os::unix::fs::MetadataExt;
+
+            (p_metadata.dev(), p_metadata.ino())
+        };
+
+        #[cfg(not(target_family = "unix"))]
+        let (dev, ino) = (0, 0);
+
+        let mtime = p_metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|time| time.as_nanos())
+            .unwrap_or(0);
+
+        return CachedHash {
+            algo: p_algo.name().to_owned(),
+            dev,
+            ino,
+            size: p_metadata.len(),
+            mtime,
+            hash: p_hash,
+        };
+    }
+
+    /// Returns `true` if this entry has the same algorithm and file properties as another, `false` otherwise
+    ///
+    /// # Arguments
+    ///
+    /// - `p_other` - the entry to compare with
+    fn is_same_file(&self, p_other: &CachedHash) -> bool {
+        return self.algo == p_other.algo
+            && self.dev == p_other.dev
+            && self.ino == p_other.ino
+            && self.size == p_other.size
+            && self.mtime == p_other.mtime;
+    }
+}
+
+/// Trait implemented by each hashing backend
+pub trait ContentHasher {
+    /// Feeds the given bytes into the hash
+    ///
+    /// # Arguments
+    ///
+    /// - `p_bytes` - the bytes to feed into the hash
+    fn update(&mut self, p_bytes: &[u8]);
+
+    /// Consumes the hasher and returns the hash formatted as a lowercase hexadecimal string
+    fn finalize_hex(self: Box<Self>) -> String;
+}
+
+/// Enumerates all the hashing algorithms that can be used (some of them may not be compiled in)
+#[derive(Clone, Copy, PartialEq)]
+pub enum HashAlgo {
+    /// BLAKE3 (cryptographic, multi-threaded for large files)
+    Blake3,
+    /// SHA-256 (cryptographic, uses the SHA extensions of the CPU if present)
+    Sha256,
+    /// XXH3 (64-bit, non-cryptographic, only suitable for finding duplicates)
+    Xxh3,
+}
+
+impl HashAlgo {
+    /// All the hashing algorithms, in the order they are listed in the help
+    pub const ALL: [HashAlgo; 3] = [HashAlgo::Blake3, HashAlgo::Sha256, HashAlgo::Xxh3];
+
+    /// Returns the algorithm with the given name, or [None] if no such algorithm exists
+    ///
+    /// # Arguments
+    ///
+    /// - `p_name` - name of the algorithm (as accepted by the `--hash-algo` flag)
+    pub fn from_name(p_name: &str) -> Option<HashAlgo> {
+        return match p_name {
+            "blake3" => Some(HashAlgo::Blake3),
+            "sha256" => Some(HashAlgo::Sha256),
+            "xxh3" => Some(HashAlgo::Xxh3),
+            _ => None,
+        };
+    }
+
+    /// Returns the name of the algorithm
+    pub fn name(&self) -> &'static str {
+        return match self {
+            HashAlgo::Blake3 => "blake3",
+            HashAlgo::Sha256 => "sha256",
+            HashAlgo::Xxh3 => "xxh3",
+        };
+    }
+
+    /// Returns the length of the hexadecimal string produced by the algorithm
+    pub fn hex_len(&self) -> usize {
+        return match self {
+            HashAlgo::Blake3 => 64,
+            HashAlgo::Sha256 => 64,
+            HashAlgo::Xxh3 => 16,
+        };
+    }
+
+    /// Returns `true` if the backend of the algorithm was compiled in, `false` otherwise
+    pub fn is_available(&self) -> bool {
+        return match self {
+            HashAlgo::Blake3 => cfg!(feature = "hash-blake3"),
+            HashAlgo::Sha256 => cfg!(feature = "hash-sha256"),
+            HashAlgo::Xxh3 => cfg!(feature = "hash-xxh3"),
+        };
+    }
+
+    /// Returns the fastest algorithm that is available ([None] if no algorithm was compiled in)
+    ///
+    /// BLAKE3 is preferred, followed by SHA-256 if the CPU has SHA extensions. XXH3 is only chosen when no
+    /// cryptographic algorithm is fast, and SHA-256 is the last resort
+    pub fn fastest() -> Option<HashAlgo> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        let has_sha_ext = std::arch::is_x86_feature_detected!("sha");
+
+        #[cfg(target_arch = "aarch64")]
+        let has_sha_ext = std::arch::is_aarch64_feature_detected!("sha2");
+
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+        let has_sha_ext = false;
+
+        let mut preference = vec![HashAlgo::Blake3];
+        if has_sha_ext {
+            preference.push(HashAlgo::Sha256);
+        }
+        preference.push(HashAlgo::Xxh3);
+        preference.push(HashAlgo::Sha256);
+
+        return preference.into_iter().find(|algo| algo.is_available());
+    }
+
+    /// Returns a new hasher for the algorithm
+    ///
+    /// Must only be called if the algorithm is available (see [HashAlgo::is_available])
+    pub fn new_hasher(&self) -> Box<dyn ContentHasher> {
+        match self {
+            #[cfg(feature = "hash-blake3")]
+            HashAlgo::Blake3 => Box::new(Blake3Hasher(blake3::Hasher::new())),
+            #[cfg(feature = "hash-sha256")]
+            HashAlgo::Sha256 => Box::new(Sha256Hasher(<sha2::Sha256 as sha2::Digest>::new())),
+            #[cfg(feature = "hash-xxh3")]
+            HashAlgo::Xxh3 => Box::new(Xxh3Hasher(xxhash_rust::xxh3::Xxh3::new())),
+            #[allow(unreachable_patterns)]
+            _ => unreachable!("hashing algorithm {} is not compiled in", self.name()),
+        }
+    }
+}
+
+#[cfg(feature = "hash-blake3")]
+/// Backend for [HashAlgo::Blake3]
+struct Blake3Hasher(blake3::Hasher);
+
+#[cfg(feature = "hash-blake3")]
+impl ContentHasher for Blake3Hasher {
+    fn update(&mut self, p_bytes: &[u8]) {
+        // spreading the work across threads only pays off for large inputs
+        if p_bytes.len() >= (128 << 10) {
+            self.0.update_rayon(p_bytes);
+        } else {
+            self.0.update(p_bytes);
+        }
+    }
+
+    fn finalize_hex(self: Box<Self>) -> String {
+        return self.0.finalize().to_hex().to_string();
+    }
+}
+
+#[cfg(feature = "hash-sha256")]
+/// Backend for [HashAlgo::Sha256]
+struct Sha256Hasher(sha2::Sha256);
+
+#[cfg(feature = "hash-sha256")]
+impl ContentHasher for Sha256Hasher {
+    fn update(&mut self, p_bytes: &[u8]) {
+        sha2::Digest::update(&mut self.0, p_bytes);
+    }
+
+    fn finalize_hex(self: Box<Self>) -> String {
+        return to_hex(&sha2::Digest::finalize(self.0));
+    }
+}
+
+#[cfg(feature = "hash-xxh3")]
+/// Backend for [HashAlgo::Xxh3]
+struct Xxh3Hasher(xxhash_rust::xxh3::Xxh3);
+
+#[cfg(feature = "hash-xxh3")]
+impl ContentHasher for Xxh3Hasher {
+    fn update(&mut self, p_bytes: &[u8]) {
+        self.0.update(p_bytes);
+    }
+
+    fn finalize_hex(self: Box<Self>) -> String {
+        return format!("{:016x}", self.0.digest());
+    }
+}
+
+#[cfg(feature = "hash-sha256")]
+/// Returns the given bytes formatted as a lowercase hexadecimal string
+///
+/// # Arguments
+///
+/// - `p_bytes` - the bytes to format
+fn to_hex(p_bytes: &[u8]) -> String {
+    use std::fmt::Write;
+
+    let mut res = String::with_capacity(2 * p_bytes.len());
+    for byte in p_bytes {
+        let _ = write!(res, "{:02x}", byte);
+    }
+
+    return res;
+}
+
+/// Sets the hashing algorithm to use (has no effect if it was already set)
+///
+/// # Arguments
+///
+/// - `p_algo` - the algorithm to use
+pub fn set_hash_algo(p_algo: HashAlgo) {
+    let _ = HASH_ALGO.set(p_algo);
+}
+
+/// Returns the hashing algorithm to use (the fastest available one if none was set)
+///
+/// Returns [None] if no hashing algorithm was compiled in
+pub fn get_hash_algo() -> Option<HashAlgo> {
+    if let Some(algo) = HASH_ALGO.get() {
+        return Some(*algo);
+    }
+
+    let algo = HashAlgo::fastest()?;
+    return Some(*HASH_ALGO.get_or_init(|| algo));
+}
+
+/// Reads the entries of a hash cache or checkpoint file into the given map
+///
+/// A file that does not exist yet is treated as an empty file, and lines that could not be parsed are skipped
+///
+/// # Arguments
+///
+/// - `p_path` - path of the file to read
+/// - `p_entries` - map to insert the entries into (entries for the same algorithm and path are replaced)
+fn read_cache_entries(
+    p_path: &path::Path,
+    p_entries: &mut HashMap<(String, path::PathBuf), CachedHash>,
+) -> std::io::Result<()> {
+    let contents = match fs::read_to_string(p_path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(error) => return Err(error),
+    };
+
+    let mut lines = contents.lines();
+
+    if let Some(header) = lines.next() {
+        if header != HASH_CACHE_HEADER {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "not a hash cache file",
+            ));
+        }
+    }
+
+    for line in lines {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [algo, dev, ino, size, mtime, hash, file_path] = fields[..] else {
+            continue;
+        };
+
+        let (Ok(dev), Ok(ino), Ok(size), Ok(mtime)) =
+            (dev.parse(), ino.parse(), size.parse(), mtime.parse())
+        else {
+            continue;
+        };
+
+        p_entries.insert(
+            (
+                algo.to_owned(),
+                path::PathBuf::from(unescape_line_field(file_path)),
+            ),
+            CachedHash {
+                algo: algo.to_owned(),
+                dev,
+                ino,
+                size,
+                mtime,
+                hash: hash.to_owned(),
+            },
+        );
+    }
+
+    return Ok(());
+}
+
+/// Writes a single entry of a hash cache or checkpoint file
+///
+/// # Arguments
+///
+/// - `p_writer` - the writer to write the entry to
+/// - `p_file_path` - absolute path of the file that the entry belongs to
+/// - `p_entry` - the entry to write
+fn write_cache_entry(
+    p_writer: &mut impl Write,
+    p_file_path: &path::Path,
+    p_entry: &CachedHash,
+) -> std::io::Result<()> {
+    return writeln!(
+        p_writer,
+        "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+        p_entry.algo,
+        p_entry.dev,
+        p_entry.ino,
+        p_entry.size,
+        p_entry.mtime,
+        p_entry.hash,
+        escape_line_field(&p_file_path.to_string_lossy())
+    );
+}
+
+/// Loads the hash cache from the given file, which is also where the cache is saved to by [save_hash_cache]
+///
+/// A file that does not exist yet is treated as an empty cache, and lines that could not be parsed are skipped
+///
+/// # Arguments
+///
+/// - `p_path` - path of the hash cache file
+pub fn load_hash_cache(p_path: &path::Path) -> std::io::Result<()> {
+    let Ok(mut hash_cache) = HASH_CACHE.lock() else {
+        return Ok(());
+    };
+
+    let cache = hash_cache.get_or_insert_with(HashCache::new);
+
+    read_cache_entries(p_path, &mut cache.entries)?;
+    cache.path = Some(p_path.to_path_buf());
+
+    return Ok(());
+}
+
+/// Resumes from the given checkpoint file (if it exists), and appends every newly calculated hash to it
+///
+/// Each hash is written to the checkpoint as soon as its file has been hashed, so an interrupted run can be resumed
+/// by running it again with the same checkpoint, which skips every file that was completely hashed before. The
+/// checkpoint is removed by [finish_checkpoint] once the run completes
+///
+/// # Arguments
+///
+/// - `p_path` - path of the checkpoint file
+pub fn load_checkpoint(p_path: &path::Path) -> std::io::Result<()> {
+    let Ok(mut hash_cache) = HASH_CACHE.lock() else {
+        return Ok(());
+    };
+
+    let cache = hash_cache.get_or_insert_with(HashCache::new);
+
+    read_cache_entries(p_path, &mut cache.entries)?;
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(p_path)?;
+
+    if file.metadata()?.len() == 0 {
+        writeln!(file, "{}", HASH_CACHE_HEADER)?;
+    }
+
+    cache.checkpoint = Some((p_path.to_path_buf(), file));
+
+    return Ok(());
+}
+
+/// Removes the checkpoint file since the run it belongs to has completed (has no effect if no checkpoint was given)
+pub fn finish_checkpoint() -> std::io::Result<()> {
+    let Ok(mut hash_cache) = HASH_CACHE.lock() else {
+        return Ok(());
+    };
+
+    let Some((checkpoint_path, _)) = hash_cache
+        .as_mut()
+        .and_then(|cache| cache.checkpoint.take())
+    else {
+        return Ok(());
+    };
+
+    return fs::remove_file(checkpoint_path);
+}
+
+/// Saves the hash cache to the file it was loaded from (has no effect if no cache was loaded or nothing was added to it)
+///
+/// The cache is written to a temporary file first which then replaces the original, so an interrupted run can not
+/// leave behind a truncated cache
+pub fn save_hash_cache() -> std::io::Result<()> {
+    let Ok(hash_cache) = HASH_CACHE.lock() else {
+        return Ok(());
+    };
+
+    let Some(cache) = hash_cache.as_ref() else {
+        return Ok(());
+    };
+
+    let Some(cache_path) = cache.path.as_ref() else {
+        return Ok(());
+    };
+
+    if !cache.dirty {
+        return Ok(());
+    }
+
+    let mut tmp_path = cache_path.clone().into_os_string();
+    tmp_path.push(".tmp");
+
+    let mut writer = std::io::BufWriter::new(fs::File::create(&tmp_path)?);
+
+    writeln!(writer, "{}", HASH_CACHE_HEADER)?;
+    for ((_, file_path), entry) in &cache.entries {
+        write_cache_entry(&mut writer, file_path, entry)?;
+    }
+
+    writer.flush()?;
+    drop(writer);
+
+    return fs::rename(&tmp_path, cache_path);
+}
+
+/// Hashes the contents of a file using the selected algorithm and returns the hash as a hexadecimal string
+///
+/// If a hash cache was loaded, the cached hash is returned without reading the file as long as the file's size,
+/// modification time and inode are unchanged, and newly calculated hashes are added to the cache
+///
+/// # Arguments
+///
+/// - `p_path` - path of the file to hash
+/// - `p_metadata` - metadata of the file to hash
+pub fn hash_file(p_path: &path::Path, p_metadata: &fs::Metadata) -> std::io::Result<String> {
+    if let Some(hash) = HASHES_AHEAD.take(p_path) {
+        return hash;
+    }
+
+    return hash_file_now(p_path, p_metadata, true);
+}
+
+/// Hands files to the work pool to be hashed ahead of the traversal, so that [hash_file] only has to pick up their
+/// hashes (has no effect if the work pool was not started)
+///
+/// Returns the files handed to the pool, whose hashes are discarded when it is dropped if they were not picked up
+///
+/// # Arguments
+///
+/// - `p_files` - path and metadata of each file to hash
+pub fn hash_ahead(
+    p_files: Vec<(path::PathBuf, fs::Metadata)>,
+) -> Option<pool::Ahead<std::io::Result<String>>> {
+    let work_pool = pool::work_pool()?;
+
+    // the progress of hashing large files is not reported, since the lines of the threads would overwrite each other
+    return Some(
+        HASHES_AHEAD.submit(work_pool, p_files, |path_os, metadata| {
+            hash_file_now(path_os, &metadata, false)
+        }),
+    );
+}
+
+/// Hashes the contents of a file (or looks up its hash in the hash cache), see [hash_file]
+///
+/// # Arguments
+///
+/// - `p_path` - path of the file to hash
+/// - `p_metadata` - metadata of the file to hash
+/// - `p_show_progress` - whether the progress of hashing a large file is reported
+fn hash_file_now(
+    p_path: &path::Path,
+    p_metadata: &fs::Metadata,
+    p_show_progress: bool,
+) -> std::io::Result<String> {
+    let Some(algo) = get_hash_algo() else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "no hashing algorithm was compiled in",
+        ));
+    };
+
+    // relative paths are resolved so that the cache can be shared between runs from different directories
+    let key = (algo.name().to_owned(), std::path::absolute(p_path)?);
+    let current = CachedHash::new(algo, p_metadata, String::new());
+
+    if let Ok(hash_cache) = HASH_CACHE.lock() {
+        if let Some(entry) = hash_cache
+            .as_ref()
+            .and_then(|cache| cache.entries.get(&key))
+        {
+            if entry.is_same_file(&current) {
+                return Ok(entry.hash.clone());
+            }
+        }
+    }
+
+    let hash = hash_contents(p_path, p_metadata.len(), algo, p_show_progress)?;
+
+    if let Ok(mut hash_cache) = HASH_CACHE.lock() {
+        if let Some(cache) = hash_cache.as_mut() {
+            let entry = CachedHash {
+                hash: hash.clone(),
+                ..current
+            };
+
+            // the checkpoint is written to (and flushed) immediately, so that the hash survives an interruption
+            if let Some((_, checkpoint)) = cache.checkpoint.as_mut() {
+                write_cache_entry(checkpoint, &key.1, &entry)?;
+            }
+
+            cache.entries.insert(key, entry);
+            cache.dirty = true;
+        }
+    }
+
+    return Ok(hash);
+}
+
+/// Reads the contents of a file in chunks and returns their hash as a hexadecimal string
+///
+/// The progress of hashing large files is reported on stderr (only if it is a terminal)
+///
+/// # Arguments
+///
+/// - `p_path` - path of the file to hash
+/// - `p_len` - length of the file (used for reporting progress)
+/// - `p_algo` - algorithm to hash with
+/// - `p_show_progress` - whether the progress is reported (if the file is large enough)
+fn hash_contents(
+    p_path: &path::Path,
+    p_len: u64,
+    p_algo: HashAlgo,
+    p_show_progress: bool,
+) -> std::io::Result<String> {
+    let mut file = open_content_file(p_path)?;
+    let mut hasher = p_algo.new_hasher();
+    let mut chunk = vec![0u8; HASH_CHUNK_LEN];
+
+    let show_progress =
+        p_show_progress && p_len >= HASH_PROGRESS_MIN_LEN && std::io::stderr().is_terminal();
+    let mut last_progress = time::Instant::now();
+    let mut hashed_len: u64 = 0;
+
+    loop {
+        let read = read_block(&mut file, &mut chunk)?;
+        if read == 0 {
+            break;
+        }
+
+        hasher.update(&chunk[..read]);
+        release_cached_pages(&mut file);
+
+        hashed_len += read as u64;
+        if show_progress && last_progress.elapsed() >= HASH_PROGRESS_INTERVAL {
+            last_progress = time::Instant::now();
+            eprint!(
+                "{}",
+                tr!(
+                    Msg::HashProgress,
+                    p_path.to_string_lossy(),
+                    100 * hashed_len / p_len.max(1)
+                )
+            );
+        }
+    }
+
+    // clear the progress line so that it does not get mixed up with the output
+    if show_progress {
+        eprint!("\r\x1b[2K");
+    }
+
+    return Ok(hasher.finalize_hex());
+}
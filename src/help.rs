@@ -0,0 +1,1531 @@
+//! Generation of the help from the definitions of the options
+//!
+//! Each option is defined once in [OPTIONS] along with whether it is available on the current platform and with the
+//! compiled-in features, so that the help never lists flags that cannot be used
+
+use crate::hash::HashAlgo;
+use crate::i18n::{tr, Msg};
+
+/// Width of the column in which the flags of an option are printed
+const FLAG_COLUMN_WIDTH: usize = 28;
+
+/// Indentation of the lines of a section
+const INDENT: &str = "    ";
+
+/// Enumerates the sections that the options are grouped into
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    Listing,
+    EntryTypes,
+    Filters,
+    Contents,
+    Reports,
+    Transfer,
+    Layout,
+    Search,
+    Watch,
+    Recording,
+    Misc,
+}
+
+/// Definition of a single command-line option
+struct OptionDef {
+    /// short flag of the option (if any)
+    short: Option<&'static str>,
+    /// long flag of the option
+    long: &'static str,
+    /// placeholder for the value that follows the flag (if any)
+    value: Option<&'static str>,
+    /// description of the option
+    desc: Msg,
+    /// section the option is listed under
+    section: Section,
+    /// whether the option can be used on this platform with the compiled-in features
+    available: bool,
+}
+
+/// Definition of a section of the help
+struct SectionDef {
+    section: Section,
+    /// title of the section
+    title: Msg,
+    /// arguments of the examples of the section, along with whether all the flags they use are available
+    examples: &'static [(&'static str, bool)],
+}
+
+/// Whether any hashing algorithm was compiled in
+const HASH_AVAILABLE: bool = cfg!(any(
+    feature = "hash-blake3",
+    feature = "hash-sha256",
+    feature = "hash-xxh3"
+));
+
+/// Whether compressing the output with gzip (the default algorithm) can be done
+const GZIP_AVAILABLE: bool = cfg!(all(target_family = "unix", feature = "compress-gzip"));
+
+/// Whether compressing the output with any algorithm can be done
+const COMPRESS_AVAILABLE: bool = cfg!(all(
+    target_family = "unix",
+    any(feature = "compress-gzip", feature = "compress-zstd")
+));
+
+/// Whether Parquet export was compiled in
+const PARQUET_AVAILABLE: bool = cfg!(feature = "export-parquet");
+
+/// Whether detecting MIME types was compiled in
+const MIME_AVAILABLE: bool = cfg!(feature = "mime");
+
+/// Whether POSIX-style permissions and modification times can be shown
+const UNIX_AVAILABLE: bool = cfg!(target_family = "unix");
+
+/// Whether the mount points of filesystems (such as the pseudo ones that are skipped) are known
+const LINUX_AVAILABLE: bool = cfg!(target_os = "linux");
+
+/// Whether reading files without polluting the page cache is supported
+const NO_CACHE_AVAILABLE: bool = cfg!(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos"
+));
+
+/// Definitions of all the options, in the order they are listed in the help
+const OPTIONS: &[OptionDef] = &[
+    OptionDef {
+        short: Some("-r"),
+        long: "--recursive",
+        value: Some("[depth]"),
+        desc: Msg::OptRecursive,
+        section: Section::Listing,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--min-depth",
+        value: Some("<depth>"),
+        desc: Msg::OptMinDepth,
+        section: Section::Listing,
+        available: true,
+    },
+    OptionDef {
+        short: Some("-L"),
+        long: "--follow",
+        value: None,
+        desc: Msg::OptFollow,
+        section: Section::Listing,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--paths-from",
+        value: Some("<file>"),
+        desc: Msg::OptPathsFrom,
+        section: Section::Listing,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--max-entries",
+        value: Some("<n>"),
+        desc: Msg::OptMaxEntries,
+        section: Section::Listing,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--timeout",
+        value: Some("<time>"),
+        desc: Msg::OptTimeout,
+        section: Section::Listing,
+        available: true,
+    },
+    OptionDef {
+        short: Some("-p"),
+        long: "--permissions",
+        value: None,
+        desc: Msg::OptPermissions,
+        section: Section::Listing,
+        available: UNIX_AVAILABLE,
+    },
+    OptionDef {
+        short: Some("-t"),
+        long: "--modification-time",
+        value: None,
+        desc: Msg::OptModificationTime,
+        section: Section::Listing,
+        available: UNIX_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--human-readable",
+        value: None,
+        desc: Msg::OptHumanReadable,
+        section: Section::Listing,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--si",
+        value: None,
+        desc: Msg::OptSi,
+        section: Section::Listing,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--binary",
+        value: None,
+        desc: Msg::OptBinary,
+        section: Section::Listing,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--block-size",
+        value: Some("<size>"),
+        desc: Msg::OptBlockSize,
+        section: Section::Listing,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--relative-time",
+        value: None,
+        desc: Msg::OptRelativeTime,
+        section: Section::Listing,
+        available: UNIX_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--time-format",
+        value: Some("<format>"),
+        desc: Msg::OptTimeFormat,
+        section: Section::Listing,
+        available: UNIX_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--time-zone",
+        value: Some("<zone>"),
+        desc: Msg::OptTimeZone,
+        section: Section::Listing,
+        available: UNIX_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--columns",
+        value: Some("<list>"),
+        desc: Msg::OptColumns,
+        section: Section::Listing,
+        available: true,
+    },
+    OptionDef {
+        short: Some("-f"),
+        long: "--files",
+        value: None,
+        desc: Msg::OptFiles,
+        section: Section::EntryTypes,
+        available: true,
+    },
+    OptionDef {
+        short: Some("-l"),
+        long: "--symlinks",
+        value: None,
+        desc: Msg::OptSymlinks,
+        section: Section::EntryTypes,
+        available: true,
+    },
+    OptionDef {
+        short: Some("-s"),
+        long: "--special",
+        value: None,
+        desc: Msg::OptSpecial,
+        section: Section::EntryTypes,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--ext",
+        value: Some("<exts>"),
+        desc: Msg::OptExt,
+        section: Section::Filters,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--type",
+        value: Some("<types>"),
+        desc: Msg::OptType,
+        section: Section::Filters,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--min-size",
+        value: Some("<size>"),
+        desc: Msg::OptMinSize,
+        section: Section::Filters,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--max-size",
+        value: Some("<size>"),
+        desc: Msg::OptMaxSize,
+        section: Section::Filters,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--empty-files",
+        value: None,
+        desc: Msg::OptEmptyFiles,
+        section: Section::Filters,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--broken-symlinks",
+        value: None,
+        desc: Msg::OptBrokenSymlinks,
+        section: Section::Filters,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--only-text",
+        value: None,
+        desc: Msg::OptOnlyText,
+        section: Section::Filters,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--only-binary",
+        value: None,
+        desc: Msg::OptOnlyBinary,
+        section: Section::Filters,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--mime",
+        value: Some("<types>"),
+        desc: Msg::OptMime,
+        section: Section::Filters,
+        available: MIME_AVAILABLE,
+    },
+    OptionDef {
+        short: Some("-a"),
+        long: "--all",
+        value: None,
+        desc: Msg::OptAll,
+        section: Section::Filters,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--no-hidden",
+        value: None,
+        desc: Msg::OptNoHidden,
+        section: Section::Filters,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--exclude",
+        value: Some("<pattern>"),
+        desc: Msg::OptExclude,
+        section: Section::Filters,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--exclude-from",
+        value: Some("<file>"),
+        desc: Msg::OptExcludeFrom,
+        section: Section::Filters,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--prune-common",
+        value: None,
+        desc: Msg::OptPruneCommon,
+        section: Section::Filters,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--prune-dir",
+        value: Some("<name>"),
+        desc: Msg::OptPruneDir,
+        section: Section::Filters,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--respect-gitignore",
+        value: None,
+        desc: Msg::OptRespectGitignore,
+        section: Section::Filters,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--no-ignore-dot",
+        value: None,
+        desc: Msg::OptNoIgnoreDot,
+        section: Section::Filters,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--skip-fs",
+        value: Some("<types>"),
+        desc: Msg::OptSkipFs,
+        section: Section::Filters,
+        available: LINUX_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--no-skip-fs",
+        value: None,
+        desc: Msg::OptNoSkipFs,
+        section: Section::Filters,
+        available: LINUX_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--newer-than",
+        value: Some("<when>"),
+        desc: Msg::OptNewerThan,
+        section: Section::Filters,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--older-than",
+        value: Some("<when>"),
+        desc: Msg::OptOlderThan,
+        section: Section::Filters,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--accessed-within",
+        value: Some("<when>"),
+        desc: Msg::OptAccessedWithin,
+        section: Section::Filters,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--status-changed-within",
+        value: Some("<when>"),
+        desc: Msg::OptStatusChangedWithin,
+        section: Section::Filters,
+        available: UNIX_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--owner",
+        value: Some("<user>"),
+        desc: Msg::OptOwner,
+        section: Section::Filters,
+        available: UNIX_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--group",
+        value: Some("<group>"),
+        desc: Msg::OptGroup,
+        section: Section::Filters,
+        available: UNIX_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--perm",
+        value: Some("<mode>"),
+        desc: Msg::OptPerm,
+        section: Section::Filters,
+        available: UNIX_AVAILABLE,
+    },
+    OptionDef {
+        short: Some("-d"),
+        long: "--dir-size",
+        value: None,
+        desc: Msg::OptDirSize,
+        section: Section::Contents,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--hash",
+        value: None,
+        desc: Msg::OptHash,
+        section: Section::Contents,
+        available: HASH_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--hash-algo",
+        value: Some("<algo>"),
+        desc: Msg::OptHashAlgo,
+        section: Section::Contents,
+        available: HASH_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--hash-cache",
+        value: Some("<file>"),
+        desc: Msg::OptHashCache,
+        section: Section::Contents,
+        available: HASH_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--checkpoint",
+        value: Some("<file>"),
+        desc: Msg::OptCheckpoint,
+        section: Section::Contents,
+        available: HASH_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--dirs-only",
+        value: None,
+        desc: Msg::OptDirsOnly,
+        section: Section::Contents,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--fingerprint",
+        value: None,
+        desc: Msg::OptFingerprint,
+        section: Section::Contents,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--name-collisions",
+        value: None,
+        desc: Msg::OptNameCollisions,
+        section: Section::Reports,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--same-size",
+        value: None,
+        desc: Msg::OptSameSize,
+        section: Section::Reports,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--hardlinks",
+        value: None,
+        desc: Msg::OptHardlinks,
+        section: Section::Reports,
+        available: UNIX_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--oldest",
+        value: Some("<count>"),
+        desc: Msg::OptOldest,
+        section: Section::Reports,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--newest",
+        value: Some("<count>"),
+        desc: Msg::OptNewest,
+        section: Section::Reports,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--cold-data",
+        value: None,
+        desc: Msg::OptColdData,
+        section: Section::Reports,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--atime-older",
+        value: Some("<age>"),
+        desc: Msg::OptAtimeOlder,
+        section: Section::Reports,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--timestamp-anomalies",
+        value: None,
+        desc: Msg::OptTimestampAnomalies,
+        section: Section::Reports,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--ctime-gap",
+        value: Some("<age>"),
+        desc: Msg::OptCtimeGap,
+        section: Section::Reports,
+        available: UNIX_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--empty-dirs",
+        value: None,
+        desc: Msg::OptEmptyDirs,
+        section: Section::Reports,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--nested-empty",
+        value: None,
+        desc: Msg::OptNestedEmpty,
+        section: Section::Reports,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--treemap",
+        value: Some("<file>"),
+        desc: Msg::OptTreemap,
+        section: Section::Reports,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--transfer-estimate",
+        value: Some("<dest>"),
+        desc: Msg::OptTransferEstimate,
+        section: Section::Transfer,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--block-estimate",
+        value: None,
+        desc: Msg::OptBlockEstimate,
+        section: Section::Transfer,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--no-cache-pollution",
+        value: None,
+        desc: Msg::OptNoCachePollution,
+        section: Section::Transfer,
+        available: NO_CACHE_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--no-tree",
+        value: None,
+        desc: Msg::OptNoTree,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--indent",
+        value: Some("<width>"),
+        desc: Msg::OptIndent,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--indent-style",
+        value: Some("<style>"),
+        desc: Msg::OptIndentStyle,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--tree",
+        value: None,
+        desc: Msg::OptTree,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: Some("-b"),
+        long: "--escape",
+        value: None,
+        desc: Msg::OptEscape,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: Some("-Q"),
+        long: "--quote",
+        value: None,
+        desc: Msg::OptQuote,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--color",
+        value: Some("<when>"),
+        desc: Msg::OptColor,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--width",
+        value: Some("<cols>"),
+        desc: Msg::OptWidth,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--age-heatmap",
+        value: None,
+        desc: Msg::OptAgeHeatmap,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--size-heatmap",
+        value: None,
+        desc: Msg::OptSizeHeatmap,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--split-output",
+        value: Some("<dir>"),
+        desc: Msg::OptSplitOutput,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--output",
+        value: Some("<file>"),
+        desc: Msg::OptOutput,
+        section: Section::Layout,
+        available: UNIX_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--append",
+        value: None,
+        desc: Msg::OptAppend,
+        section: Section::Layout,
+        available: UNIX_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--compress",
+        value: None,
+        desc: Msg::OptCompress,
+        section: Section::Layout,
+        available: GZIP_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--compress-algo",
+        value: Some("<algo>"),
+        desc: Msg::OptCompressAlgo,
+        section: Section::Layout,
+        available: COMPRESS_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--paging",
+        value: Some("<when>"),
+        desc: Msg::OptPaging,
+        section: Section::Layout,
+        available: UNIX_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--names-only",
+        value: None,
+        desc: Msg::OptNamesOnly,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: Some("-0"),
+        long: "--print0",
+        value: None,
+        desc: Msg::OptPrint0,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--sizes-only",
+        value: None,
+        desc: Msg::OptSizesOnly,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--ls-format",
+        value: None,
+        desc: Msg::OptLsFormat,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--long",
+        value: None,
+        desc: Msg::OptLong,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--ordered",
+        value: None,
+        desc: Msg::OptOrdered,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--format",
+        value: Some("<fmt>"),
+        desc: Msg::OptFormat,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--printf",
+        value: Some("<template>"),
+        desc: Msg::OptPrintf,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--export-sqlite",
+        value: Some("<file>"),
+        desc: Msg::OptExportSqlite,
+        section: Section::Layout,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--export-parquet",
+        value: Some("<file>"),
+        desc: Msg::OptExportParquet,
+        section: Section::Layout,
+        available: PARQUET_AVAILABLE,
+    },
+    OptionDef {
+        short: Some("-S"),
+        long: "--search",
+        value: Some("<phrase>"),
+        desc: Msg::OptSearch,
+        section: Section::Search,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--search-noext",
+        value: Some("<phrase>"),
+        desc: Msg::OptSearchNoext,
+        section: Section::Search,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--contains",
+        value: Some("<phrase>"),
+        desc: Msg::OptContains,
+        section: Section::Search,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--glob",
+        value: Some("<pattern>"),
+        desc: Msg::OptGlob,
+        section: Section::Search,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--fuzzy",
+        value: Some("<phrase>"),
+        desc: Msg::OptFuzzy,
+        section: Section::Search,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--patterns-from",
+        value: Some("<file>"),
+        desc: Msg::OptPatternsFrom,
+        section: Section::Search,
+        available: true,
+    },
+    OptionDef {
+        short: Some("-i"),
+        long: "--ignore-case",
+        value: None,
+        desc: Msg::OptIgnoreCase,
+        section: Section::Search,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--invert-match",
+        value: None,
+        desc: Msg::OptInvertMatch,
+        section: Section::Search,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--full-path",
+        value: None,
+        desc: Msg::OptFullPath,
+        section: Section::Search,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--search-tree",
+        value: None,
+        desc: Msg::OptSearchTree,
+        section: Section::Search,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--max-results",
+        value: Some("<n>"),
+        desc: Msg::OptMaxResults,
+        section: Section::Search,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--bfs",
+        value: None,
+        desc: Msg::OptBfs,
+        section: Section::Search,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--counts-by-dir",
+        value: None,
+        desc: Msg::OptCountsByDir,
+        section: Section::Search,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--find-expr",
+        value: Some("<expr>"),
+        desc: Msg::OptFindExpr,
+        section: Section::Search,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--watch",
+        value: None,
+        desc: Msg::OptWatch,
+        section: Section::Watch,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--debounce",
+        value: Some("<ms>"),
+        desc: Msg::OptDebounce,
+        section: Section::Watch,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--on-create",
+        value: Some("<cmd>"),
+        desc: Msg::OptOnCreate,
+        section: Section::Watch,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--on-modify",
+        value: Some("<cmd>"),
+        desc: Msg::OptOnModify,
+        section: Section::Watch,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--on-delete",
+        value: Some("<cmd>"),
+        desc: Msg::OptOnDelete,
+        section: Section::Watch,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--record",
+        value: Some("<file>"),
+        desc: Msg::OptRecord,
+        section: Section::Recording,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--replay",
+        value: Some("<file>"),
+        desc: Msg::OptReplay,
+        section: Section::Recording,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--snapshot",
+        value: Some("<file>"),
+        desc: Msg::OptSnapshot,
+        section: Section::Recording,
+        available: true,
+    },
+    OptionDef {
+        short: Some("-e"),
+        long: "--show-err",
+        value: None,
+        desc: Msg::OptShowErr,
+        section: Section::Misc,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--on-error",
+        value: Some("<mode>"),
+        desc: Msg::OptOnError,
+        section: Section::Misc,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--measure-latency",
+        value: None,
+        desc: Msg::OptMeasureLatency,
+        section: Section::Misc,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--walk-threads",
+        value: Some("<n>"),
+        desc: Msg::OptWalkThreads,
+        section: Section::Misc,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--work-threads",
+        value: Some("<n>"),
+        desc: Msg::OptWorkThreads,
+        section: Section::Misc,
+        available: HASH_AVAILABLE,
+    },
+    OptionDef {
+        short: None,
+        long: "--progress",
+        value: None,
+        desc: Msg::OptProgress,
+        section: Section::Misc,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--progress-from",
+        value: Some("<file>"),
+        desc: Msg::OptProgressFrom,
+        section: Section::Misc,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--policy",
+        value: Some("<file>"),
+        desc: Msg::OptPolicy,
+        section: Section::Misc,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--lang",
+        value: Some("<lang>"),
+        desc: Msg::OptLang,
+        section: Section::Misc,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--capabilities",
+        value: None,
+        desc: Msg::OptCapabilities,
+        section: Section::Misc,
+        available: true,
+    },
+    OptionDef {
+        short: Some("-h"),
+        long: "--help",
+        value: None,
+        desc: Msg::OptHelp,
+        section: Section::Misc,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--help-full",
+        value: None,
+        desc: Msg::OptHelpFull,
+        section: Section::Misc,
+        available: true,
+    },
+    OptionDef {
+        short: None,
+        long: "--man",
+        value: None,
+        desc: Msg::OptMan,
+        section: Section::Misc,
+        available: true,
+    },
+];
+
+/// Definitions of all the sections, in the order they are listed in the help
+const SECTIONS: &[SectionDef] = &[
+    SectionDef {
+        section: Section::Listing,
+        title: Msg::SectionListing,
+        examples: &[
+            ("\"..\" -r 2", !UNIX_AVAILABLE),
+            ("\"..\" -r 2 -p -t", UNIX_AVAILABLE),
+            ("-r -f -t --relative-time", UNIX_AVAILABLE),
+            (
+                "-r -f -t --time-format \"%Y-%m-%d %H:%M:%S\" --time-zone utc",
+                UNIX_AVAILABLE,
+            ),
+            ("\"..\" -r 2 -f --columns name,size,owner", true),
+            ("-r -f -d --human-readable", true),
+            ("-r 2 -d --block-size 1M", true),
+            ("--paths-from \"dirs.txt\" -r 1", true),
+            ("\"/\" -r -d --max-entries 100000", true),
+            ("\"/mnt/nfs\" -r -f --timeout 30s", true),
+        ],
+    },
+    SectionDef {
+        section: Section::EntryTypes,
+        title: Msg::SectionEntryTypes,
+        examples: &[("\"..\" -f -l -s", true)],
+    },
+    SectionDef {
+        section: Section::Filters,
+        title: Msg::SectionFilters,
+        examples: &[
+            ("-r -f --ext rs,toml,md", true),
+            ("-r --type l,p --contains \"log\"", true),
+            ("-r -f --min-size 10M --max-size 1G", true),
+            ("-r -f --newer-than 2024-01-01 --older-than 30d", true),
+            ("-r -f -p --perm /o+w", UNIX_AVAILABLE),
+        ],
+    },
+    SectionDef {
+        section: Section::Contents,
+        title: Msg::SectionContents,
+        examples: &[
+            ("-r -d --dirs-only", true),
+            ("-r -f --hash --hash-cache hashes.txt", HASH_AVAILABLE),
+        ],
+    },
+    SectionDef {
+        section: Section::Reports,
+        title: Msg::SectionReports,
+        examples: &[
+            ("--name-collisions --same-size", true),
+            ("\"/srv/data\" --oldest 20 --newest 5", true),
+            ("\"/srv/data\" --cold-data --atime-older 365d", true),
+            (
+                "\"/srv/data\" --timestamp-anomalies --ctime-gap 20y",
+                UNIX_AVAILABLE,
+            ),
+            ("\"/srv/data\" -r --empty-dirs --nested-empty", true),
+            ("\"/srv/backups\" -r --hardlinks", UNIX_AVAILABLE),
+            ("\"/srv/data\" -r 3 -f --treemap usage.svg", true),
+        ],
+    },
+    SectionDef {
+        section: Section::Transfer,
+        title: Msg::SectionTransfer,
+        examples: &[(
+            "\"src\" -r --transfer-estimate \"backup/src\" --block-estimate",
+            true,
+        )],
+    },
+    SectionDef {
+        section: Section::Layout,
+        title: Msg::SectionLayout,
+        examples: &[
+            ("-r -f --no-tree", true),
+            ("-r 20 --indent 2 --indent-style bars", true),
+            ("-r 3 -f --tree", true),
+            ("\"/srv/uploads\" -r -f --quote", true),
+            ("-r -f --color always | less -R", true),
+            ("-r -f --width 80 > listing.txt", true),
+            ("-r -f --age-heatmap", true),
+            ("-r -f -d --size-heatmap", true),
+            ("\"/srv/projects\" -r -f -d --split-output reports", true),
+            (
+                "\"/srv/data\" -r -f --names-only --output files.txt --append",
+                UNIX_AVAILABLE,
+            ),
+            ("-r -f --contains \".log\" --names-only | xargs gzip", true),
+            ("-r -f -0 --contains \".log\" | xargs -0 gzip", true),
+            ("-r 2 --sizes-only | sort -n", true),
+            ("-r -f -l --ls-format", true),
+            ("-r 2 --long --ordered", true),
+            ("-r -f --no-tree --ordered", true),
+            ("-r -f --format json > scan.json", true),
+            (
+                "\"/srv/data\" -r -f -l --format csv --output scan.csv.gz",
+                GZIP_AVAILABLE,
+            ),
+            ("\"/srv/data\" -r -f --paging auto", UNIX_AVAILABLE),
+            ("-r -f --printf \"{path}\\t{size}\\t{mtime}\"", true),
+            ("\"/srv/data\" -r -f -l -s --export-sqlite scan.db", true),
+            (
+                "\"/srv/data\" -r -f -l -s --export-parquet scan.parquet",
+                PARQUET_AVAILABLE,
+            ),
+        ],
+    },
+    SectionDef {
+        section: Section::Search,
+        title: Msg::SectionSearch,
+        examples: &[
+            ("-r -d -S \"proc\"", true),
+            ("-r -f --contains \".rs\"", true),
+            ("-r -f --glob \"src/**/*.{rs,toml}\"", true),
+            ("-r -f -i -S \"makefile\"", true),
+            ("-r -f --fuzzy \"cfgldr\"", true),
+            ("-r -f --contains \".log\" --contains \".tmp\"", true),
+            ("-r -f --patterns-from \"names.txt\"", true),
+            ("\"tests\" -r -f --invert-match --glob \"test_*.py\"", true),
+            (
+                "--find-expr \"-name target -prune -o -name '*.rs' -size +8k\"",
+                true,
+            ),
+        ],
+    },
+    SectionDef {
+        section: Section::Watch,
+        title: Msg::SectionWatch,
+        examples: &[(
+            "\"src\" -r --watch --debounce 500 --on-modify \"cargo build\"",
+            true,
+        )],
+    },
+    SectionDef {
+        section: Section::Recording,
+        title: Msg::SectionRecording,
+        examples: &[
+            ("-r -f --record scan.txt", true),
+            ("--replay scan.txt -r -f --no-tree", true),
+            ("\"/srv/data\" -r -f -l --snapshot scan.fss", true),
+        ],
+    },
+    SectionDef {
+        section: Section::Misc,
+        title: Msg::SectionMisc,
+        examples: &[
+            ("-r -e --measure-latency", true),
+            ("\"/srv\" -r -d --on-error collect", true),
+            (
+                "-r -f --hash --walk-threads 4 --work-threads 8",
+                HASH_AVAILABLE,
+            ),
+            (
+                "\"/srv/data\" -r -f --record scan.txt --progress > files.txt",
+                true,
+            ),
+            ("\"/mnt/usb\" --capabilities", true),
+        ],
+    },
+];
+
+/// Topics that are documented after the options in the full help and the man page, along with whether they apply to
+/// this build
+const TOPICS: &[(Msg, Msg, bool)] = &[
+    (Msg::DocListingTitle, Msg::DocListing, true),
+    (Msg::DocFormatsTitle, Msg::DocFormats, true),
+    (Msg::DocFingerprintTitle, Msg::DocFingerprint, true),
+    (Msg::DocFindExprTitle, Msg::DocFindExpr, true),
+    (Msg::DocIgnoreTitle, Msg::DocIgnore, true),
+    (Msg::DocPolicyTitle, Msg::DocPolicy, true),
+    (Msg::DocRecordingTitle, Msg::DocRecording, true),
+    (Msg::DocHashCacheTitle, Msg::DocHashCache, HASH_AVAILABLE),
+    (Msg::DocEnvironmentTitle, Msg::DocEnvironment, true),
+    (Msg::DocExitStatusTitle, Msg::DocExitStatus, true),
+];
+
+/// Returns the help, listing only the options that are available on this platform with the compiled-in features
+///
+/// # Arguments
+///
+/// - `p_process_name` - name of the current process (used in the usage line and the examples)
+pub fn help_text(p_process_name: &str) -> String {
+    let mut res = tr!(Msg::HelpHeader, p_process_name, p_process_name);
+
+    for section in SECTIONS {
+        let mut options = OPTIONS
+            .iter()
+            .filter(|option| option.section == section.section && option.available)
+            .peekable();
+
+        if options.peek().is_none() {
+            continue;
+        }
+
+        res.push('\n');
+        res.push_str(&tr!(section.title));
+
+        for option in options {
+            res.push_str(&option_line(option));
+        }
+
+        for (args, available) in section.examples {
+            if *available {
+                res.push_str(INDENT);
+                res.push_str(&tr!(Msg::HelpExample, p_process_name, args));
+            }
+        }
+    }
+
+    return res;
+}
+
+/// Returns the line of the help that describes an option
+///
+/// # Arguments
+///
+/// - `p_option` - definition of the option
+fn option_line(p_option: &OptionDef) -> String {
+    let mut flags = match p_option.short {
+        Some(short) => format!("{}, {}", short, p_option.long),
+        None => format!("    {}", p_option.long),
+    };
+
+    if let Some(value) = p_option.value {
+        flags.push(' ');
+        flags.push_str(value);
+    }
+
+    let desc = option_desc(p_option);
+
+    let width = FLAG_COLUMN_WIDTH.max(flags.len() + 2);
+
+    return format!("{}{:<width$}{}", INDENT, flags, desc, width = width);
+}
+
+/// Returns the documentation of the output formats and the environment, as shown after the help by `--help-full`
+pub fn topics_text() -> String {
+    let mut res = String::new();
+
+    for (title, body, _) in TOPICS.iter().filter(|(_, _, available)| *available) {
+        res.push('\n');
+        res.push_str(&tr!(*title));
+
+        for line in tr!(*body).lines() {
+            if !line.is_empty() {
+                res.push_str(INDENT);
+                res.push_str(line);
+            }
+            res.push('\n');
+        }
+    }
+
+    return res;
+}
+
+/// Returns a man page (in roff) generated from the same definitions as the help
+pub fn man_page() -> String {
+    let mut res = String::new();
+
+    res.push_str(&format!(
+        ".TH FSS 1 \"\" \"fss {}\" \"User Commands\"\n",
+        env!("CARGO_PKG_VERSION")
+    ));
+    res.push_str(&format!(
+        ".SH NAME\nfss \\- {}",
+        roff_escape(&tr!(Msg::ManName))
+    ));
+    res.push_str(".SH SYNOPSIS\n.B fss\n[\\fIPATH\\fR...] [\\fIoptions\\fR]\n");
+    res.push_str(".SH DESCRIPTION\n");
+    res.push_str(&roff_paragraphs(&tr!(Msg::ManDescription)));
+
+    res.push_str(".SH OPTIONS\n");
+    for section in SECTIONS {
+        let options: Vec<&OptionDef> = OPTIONS
+            .iter()
+            .filter(|option| option.section == section.section && option.available)
+            .collect();
+
+        if options.is_empty() {
+            continue;
+        }
+
+        res.push_str(&format!(
+            ".SS {}\n",
+            roff_escape(&title_text(section.title))
+        ));
+
+        for option in options {
+            let flags = match option.short {
+                Some(short) => format!("{}, {}", short, option.long),
+                None => option.long.to_owned(),
+            };
+
+            res.push_str(&format!(".TP\n\\fB{}\\fR", roff_escape(&flags)));
+            if let Some(value) = option.value {
+                res.push_str(&format!(" \\fI{}\\fR", roff_escape(value)));
+            }
+            res.push('\n');
+            res.push_str(&roff_escape(&option_desc(option)));
+        }
+    }
+
+    for (title, body, _) in TOPICS.iter().filter(|(_, _, available)| *available) {
+        res.push_str(&format!(
+            ".SH {}\n",
+            roff_escape(&title_text(*title).to_uppercase())
+        ));
+        res.push_str(&roff_paragraphs(&tr!(*body)));
+    }
+
+    res.push_str(".SH EXAMPLES\n");
+    for section in SECTIONS {
+        let examples: Vec<&str> = section
+            .examples
+            .iter()
+            .filter(|(_, available)| *available)
+            .map(|(args, _)| *args)
+            .collect();
+
+        if examples.is_empty() {
+            continue;
+        }
+
+        res.push_str(&format!(
+            ".PP\n{}\n.RS\n.nf\n",
+            roff_escape(&title_text(section.title))
+        ));
+        for args in examples {
+            res.push_str(&format!("fss {}\n", roff_escape(args)));
+        }
+        res.push_str(".fi\n.RE\n");
+    }
+
+    res.push_str(".SH \"SEE ALSO\"\n.BR du (1),\n.BR find (1),\n.BR ls (1)\n");
+
+    return res;
+}
+
+/// Returns the description of an option (ending with a newline)
+///
+/// # Arguments
+///
+/// - `p_option` - definition of the option
+fn option_desc(p_option: &OptionDef) -> String {
+    return match p_option.desc {
+        // the algorithms depend on the features that were compiled in
+        Msg::OptHashAlgo => {
+            let names = HashAlgo::ALL
+                .iter()
+                .filter(|algo| algo.is_available())
+                .map(|algo| algo.name())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            tr!(Msg::OptHashAlgo, names)
+        }
+        desc => tr!(desc),
+    };
+}
+
+/// Returns the title of a section or topic without the trailing colon and newline
+///
+/// # Arguments
+///
+/// - `p_title` - the title
+fn title_text(p_title: Msg) -> String {
+    return tr!(p_title).trim_end().trim_end_matches(':').to_owned();
+}
+
+/// Returns the given text converted to roff paragraphs
+///
+/// Paragraphs are separated by blank lines, and paragraphs whose lines are all indented are kept as they are
+///
+/// # Arguments
+///
+/// - `p_text` - the text to convert
+fn roff_paragraphs(p_text: &str) -> String {
+    let mut res = String::new();
+
+    for paragraph in p_text.trim_end().split("\n\n") {
+        if paragraph.lines().all(|line| line.starts_with(INDENT)) {
+            res.push_str(".RS\n.nf\n");
+            for line in paragraph.lines() {
+                res.push_str(&roff_escape(&line[INDENT.len()..]));
+                res.push('\n');
+            }
+            res.push_str(".fi\n.RE\n");
+        } else {
+            res.push_str(".PP\n");
+            res.push_str(&roff_escape(paragraph));
+            res.push('\n');
+        }
+    }
+
+    return res;
+}
+
+/// Returns the given text with the characters that have a special meaning in roff escaped
+///
+/// # Arguments
+///
+/// - `p_text` - the text to escape
+fn roff_escape(p_text: &str) -> String {
+    let escaped = p_text.replace('\\', "\\e").replace('-', "\\-");
+
+    // lines starting with a dot or an apostrophe would be treated as requests
+    return escaped
+        .split_inclusive('\n')
+        .map(|line| match line.starts_with(['.', '\'']) {
+            true => format!("\\&{}", line),
+            false => line.to_owned(),
+        })
+        .collect();
+}
@@ -0,0 +1,2127 @@
+//! Translations of the messages shown to the user
+//!
+//! Every message is identified by a [Msg] and written in English in [english]. Other languages translate as many
+//! messages as they can, and the messages that they do not translate are shown in English. Output that is meant to be
+//! read by other programs (entry lines, fingerprints, recordings) is never translated
+
+use std::env;
+use std::fmt;
+use std::fmt::Write;
+use std::sync;
+
+/// Language selected by the user (detected from the environment if none was selected)
+static LANG: sync::OnceLock<Lang> = sync::OnceLock::new();
+
+/// Enumerates the languages that messages can be shown in
+#[derive(Clone, Copy, PartialEq)]
+pub enum Lang {
+    En,
+    De,
+    Es,
+}
+
+impl Lang {
+    /// Returns the language with the given name ([None] if there is no such language)
+    ///
+    /// Locale names such as `de_DE.UTF-8` are accepted as well, and the `C` and `POSIX` locales are treated as English
+    ///
+    /// # Arguments
+    ///
+    /// - `p_name` - name of the language or locale
+    pub fn from_name(p_name: &str) -> Option<Lang> {
+        let code = p_name
+            .split(['_', '.', '@', '-'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        return match code.as_str() {
+            "en" | "c" | "posix" => Some(Lang::En),
+            "de" => Some(Lang::De),
+            "es" => Some(Lang::Es),
+            _ => None,
+        };
+    }
+}
+
+/// Enumerates all messages that can be shown to the user
+#[derive(Clone, Copy)]
+pub enum Msg {
+    HelpHeader,
+    HelpExample,
+    SectionListing,
+    SectionEntryTypes,
+    SectionFilters,
+    SectionContents,
+    SectionReports,
+    SectionTransfer,
+    SectionLayout,
+    SectionSearch,
+    SectionWatch,
+    SectionRecording,
+    SectionMisc,
+    OptRecursive,
+    OptMinDepth,
+    OptFollow,
+    OptPathsFrom,
+    OptMaxEntries,
+    OptTimeout,
+    OptPermissions,
+    OptModificationTime,
+    OptHumanReadable,
+    OptSi,
+    OptBinary,
+    OptBlockSize,
+    OptRelativeTime,
+    OptTimeFormat,
+    OptTimeZone,
+    OptColumns,
+    OptFiles,
+    OptSymlinks,
+    OptSpecial,
+    OptExt,
+    OptType,
+    OptMinSize,
+    OptMaxSize,
+    OptEmptyFiles,
+    OptBrokenSymlinks,
+    OptOnlyText,
+    OptOnlyBinary,
+    OptMime,
+    OptAll,
+    OptNoHidden,
+    OptExclude,
+    OptExcludeFrom,
+    OptPruneCommon,
+    OptPruneDir,
+    OptRespectGitignore,
+    OptNoIgnoreDot,
+    OptSkipFs,
+    OptNoSkipFs,
+    OptNewerThan,
+    OptOlderThan,
+    OptAccessedWithin,
+    OptStatusChangedWithin,
+    OptOwner,
+    OptGroup,
+    OptPerm,
+    OptDirSize,
+    OptHash,
+    OptHashAlgo,
+    OptHashCache,
+    OptCheckpoint,
+    OptDirsOnly,
+    OptNameCollisions,
+    OptSameSize,
+    OptOldest,
+    OptHardlinks,
+    OptNewest,
+    OptColdData,
+    OptAtimeOlder,
+    OptTimestampAnomalies,
+    OptCtimeGap,
+    OptEmptyDirs,
+    OptNestedEmpty,
+    OptTreemap,
+    OptFingerprint,
+    OptTransferEstimate,
+    OptBlockEstimate,
+    OptNoCachePollution,
+    OptNoTree,
+    OptIndent,
+    OptIndentStyle,
+    OptTree,
+    OptEscape,
+    OptQuote,
+    OptColor,
+    OptWidth,
+    OptAgeHeatmap,
+    OptSizeHeatmap,
+    OptSplitOutput,
+    OptOutput,
+    OptAppend,
+    OptCompress,
+    OptCompressAlgo,
+    OptPaging,
+    OptNamesOnly,
+    OptPrint0,
+    OptSizesOnly,
+    OptLsFormat,
+    OptLong,
+    OptPrintf,
+    OptOrdered,
+    OptFormat,
+    OptExportSqlite,
+    OptExportParquet,
+    OptWalkThreads,
+    OptWorkThreads,
+    OptSearch,
+    OptSearchNoext,
+    OptContains,
+    OptGlob,
+    OptFuzzy,
+    OptPatternsFrom,
+    OptIgnoreCase,
+    OptInvertMatch,
+    OptFullPath,
+    OptSearchTree,
+    OptMaxResults,
+    OptCountsByDir,
+    OptBfs,
+    OptFindExpr,
+    OptWatch,
+    OptDebounce,
+    OptOnCreate,
+    OptOnModify,
+    OptOnDelete,
+    OptRecord,
+    OptSnapshot,
+    OptReplay,
+    OptShowErr,
+    OptOnError,
+    OptMeasureLatency,
+    OptProgress,
+    OptProgressFrom,
+    OptPolicy,
+    OptLang,
+    OptCapabilities,
+    OptHelp,
+    OptHelpFull,
+    OptMan,
+    ManName,
+    ManDescription,
+    DocListingTitle,
+    DocListing,
+    DocFormatsTitle,
+    DocFormats,
+    DocFingerprintTitle,
+    DocFingerprint,
+    DocFindExprTitle,
+    DocFindExpr,
+    DocIgnoreTitle,
+    DocIgnore,
+    DocPolicyTitle,
+    DocPolicy,
+    DocRecordingTitle,
+    DocRecording,
+    DocHashCacheTitle,
+    DocHashCache,
+    DocEnvironmentTitle,
+    DocEnvironment,
+    DocExitStatusTitle,
+    DocExitStatus,
+    EmptyOption,
+    UnknownOption,
+    UnknownLang,
+    RecursionDepthZero,
+    NotAnInteger,
+    NotAPositiveInteger,
+    IgnoringRecursive,
+    IgnoringDebounce,
+    IgnoringOption,
+    OneSearchMode,
+    OneHeatmap,
+    OnePlainMode,
+    Terminating,
+    MissingSearchPattern,
+    MissingDestPath,
+    MissingSplitDir,
+    MissingTreemapPath,
+    MissingPolicy,
+    MissingPatternsFile,
+    MissingPathsFile,
+    MissingEntryCount,
+    MissingTimeout,
+    MissingOnError,
+    MissingHashAlgo,
+    MissingCompressAlgo,
+    MissingIndentWidth,
+    MissingThreadCount,
+    MissingIndentStyle,
+    MissingFormat,
+    MissingTemplate,
+    MissingColumns,
+    MissingTimeFormat,
+    MissingBlockSize,
+    MissingColorMode,
+    MissingWidth,
+    MissingExtensions,
+    MissingMimeTypes,
+    MissingFsTypes,
+    MissingTypes,
+    MissingSize,
+    MissingTimeBound,
+    MissingUser,
+    MissingExcludePattern,
+    MissingExcludeFile,
+    MissingPruneDir,
+    MissingDepth,
+    ExcludesReadError,
+    MissingGroup,
+    MissingPerm,
+    MissingPagingMode,
+    MissingTimeZone,
+    MissingDbPath,
+    MissingParquetPath,
+    MissingFindExpr,
+    MissingHashCache,
+    MissingCheckpoint,
+    MissingDebounce,
+    MissingFileCount,
+    MissingResultCount,
+    MissingAge,
+    InvalidAge,
+    InvalidTimeout,
+    MissingCommand,
+    MissingRecording,
+    MissingLang,
+    HashAlgoNotCompiled,
+    CompressAlgoNotCompiled,
+    ParquetNotCompiled,
+    MimeNotCompiled,
+    UnknownHashAlgo,
+    UnknownCompressAlgo,
+    UnknownOnError,
+    UnknownIndentStyle,
+    UnknownFormat,
+    UnknownPlaceholder,
+    UnknownColumn,
+    InvalidTimeFormat,
+    InvalidBlockSize,
+    UnknownColorMode,
+    InvalidWidth,
+    InvalidExtensions,
+    InvalidMimeTypes,
+    InvalidFsTypes,
+    UnknownType,
+    InvalidSize,
+    InvalidTimeBound,
+    UnknownUser,
+    UnknownGroup,
+    InvalidPerm,
+    UnknownPagingMode,
+    UnknownTimeZone,
+    FindUnknownPrimary,
+    FindMissingArgument,
+    FindInvalidArgument,
+    FindSyntaxError,
+    HashCacheReadError,
+    PatternsReadError,
+    NoPatternsInFile,
+    PathsReadError,
+    NoPathsInFile,
+    IgnoreFileReadError,
+    HashCacheWriteError,
+    CheckpointReadError,
+    CheckpointRemoveError,
+    RecordReplayModes,
+    RecordAndReplay,
+    AppendWithoutOutput,
+    BlockSizeWithHumanReadable,
+    BfsWithSearchTree,
+    BfsWithoutSearch,
+    MaxEntriesWithWatch,
+    EntriesTruncated,
+    TimeoutWithWatch,
+    TraversalTimedOut,
+    OnErrorWithWatch,
+    ScanAborted,
+    ErrorReportDirs,
+    ErrorReportDir,
+    ErrorReportKinds,
+    ErrorReportKind,
+    ErrorReportTotal,
+    MultiplePathsModes,
+    PathHeader,
+    CombinedScanSummary,
+    CombinedSearchSummary,
+    OutputWhileWatching,
+    SplitOutputModes,
+    OnePolicy,
+    PolicyReadError,
+    PolicySyntaxError,
+    PolicyUnknownKey,
+    PolicyInvalidValue,
+    PolicyOptionNotAllowed,
+    PolicyOutputError,
+    MissingOutputPath,
+    OutputCreateError,
+    OutputWriteError,
+    CompressError,
+    PagerError,
+    PolicyBudgetsHeader,
+    PolicyOverBudget,
+    PolicyRetentionHeader,
+    PolicySummary,
+    SplitDirCreateError,
+    TreemapWriteError,
+    SqliteCreateError,
+    SqliteWriteError,
+    #[cfg_attr(not(feature = "export-parquet"), allow(dead_code))]
+    ParquetCreateError,
+    #[cfg_attr(not(feature = "export-parquet"), allow(dead_code))]
+    ParquetWriteError,
+    SplitExeError,
+    ReportCreateError,
+    ReportRunError,
+    ReportWritten,
+    SplitOutputSummary,
+    RecordingReadError,
+    RecordingCreateError,
+    RecordingWriteError,
+    IterateError,
+    MetadataError,
+    ModifiedTimeError,
+    AccessTimeError,
+    AtimeUnreliable,
+    SymlinkTargetError,
+    DirSizeTraverseError,
+    DirSizeEntryError,
+    HashError,
+    HashProgress,
+    ScanProgress,
+    ScanProgressEstimate,
+    ScanSummary,
+    ScanSummaryRecursive,
+    SearchSummaryMatches,
+    SearchSummaryPatterns,
+    SearchSummaryPattern,
+    SearchStopped,
+    SearchSummaryDirs,
+    SearchSummaryDir,
+    SearchSummaryExts,
+    SearchSummaryExt,
+    SearchSummaryNoExt,
+    FilesystemLoops,
+    FilterSummary,
+    SearchSummaryTraversal,
+    DirsOnlyTotal,
+    TreemapSummary,
+    SqliteSummary,
+    #[cfg_attr(not(feature = "export-parquet"), allow(dead_code))]
+    ParquetSummary,
+    NameCollisionSummary,
+    OldestFiles,
+    NewestFiles,
+    ColdDataHeader,
+    ColdDir,
+    ColdDataSummary,
+    TimestampAnomaliesHeader,
+    TimestampAnomaliesSummary,
+    EmptyDirsHeader,
+    EmptyDirsSummary,
+    AnomalyFuture,
+    HardlinksHeader,
+    HardlinkGroup,
+    HardlinksSummary,
+    AnomalyEpoch,
+    AnomalyAfterCtime,
+    AnomalyCtimeGap,
+    TimeJustNow,
+    TimeFuture,
+    TimeMinuteAgo,
+    TimeMinutesAgo,
+    TimeHourAgo,
+    TimeHoursAgo,
+    TimeDayAgo,
+    TimeDaysAgo,
+    TimeMonthAgo,
+    TimeMonthsAgo,
+    TimeYearAgo,
+    TimeYearsAgo,
+    TransferSummary,
+    SlowestDirectories,
+    LatencyByFilesystem,
+    FilesystemLatency,
+    Watching,
+    WatchReport,
+    WatchMore,
+    HookExitError,
+    HookRunError,
+    CapabilitiesHeader,
+    CapabilitiesMissing,
+    CapModifiedTime,
+    CapAccessTime,
+    CapChangeTime,
+    CapPermissions,
+    CapOwners,
+    CapHashing,
+    CapCacheBypass,
+    CapAvailable,
+    CapAvailableWith,
+    CapUnsupportedPlatform,
+    CapUnsupportedFilesystem,
+    CapNotCompiled,
+}
+
+/// Returns the given message formatted with the given arguments (in the selected language)
+///
+/// # Arguments
+///
+/// - `msg` - the message to format
+/// - `args` - values to substitute for the `{}` placeholders of the message, in order
+macro_rules! tr {
+    ($msg:expr) => {
+        $crate::i18n::fill($crate::i18n::text($msg), &[])
+    };
+    ($msg:expr, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::fill($crate::i18n::text($msg), &[$(&$arg as &dyn std::fmt::Display),+])
+    };
+}
+
+pub(crate) use tr;
+
+/// Sets the language that messages are shown in (has no effect if a message has already been shown)
+///
+/// # Arguments
+///
+/// - `p_lang` - the language to show messages in
+pub fn set_lang(p_lang: Lang) {
+    let _ = LANG.set(p_lang);
+}
+
+/// Returns the language that messages are shown in
+///
+/// If no language was set, it is taken from the first of `LC_ALL`, `LC_MESSAGES` and `LANG` that is set (English is
+/// used if that language is not supported)
+fn get_lang() -> Lang {
+    return *LANG.get_or_init(|| {
+        for var in ["LC_ALL", "LC_MESSAGES", "LANG"] {
+            match env::var(var) {
+                Ok(value) if !value.is_empty() => {
+                    return Lang::from_name(&value).unwrap_or(Lang::En)
+                }
+                _ => {}
+            }
+        }
+
+        Lang::En
+    });
+}
+
+/// Returns the template of a message in the selected language (in English if it is not translated)
+///
+/// # Arguments
+///
+/// - `p_msg` - the message
+pub fn text(p_msg: Msg) -> &'static str {
+    let translated = match get_lang() {
+        Lang::En => None,
+        Lang::De => german(p_msg),
+        Lang::Es => spanish(p_msg),
+    };
+
+    return translated.unwrap_or_else(|| english(p_msg));
+}
+
+/// Returns a message template with each `{}` replaced by the next argument
+///
+/// Like in format strings, `{{` and `}}` stand for literal braces
+///
+/// # Arguments
+///
+/// - `p_template` - the template of the message
+/// - `p_args` - values to substitute for the placeholders, in order
+pub fn fill(p_template: &str, p_args: &[&dyn fmt::Display]) -> String {
+    let mut res = String::with_capacity(p_template.len());
+    let mut args = p_args.iter();
+    let mut chars = p_template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('{', Some('{')) | ('}', Some('}')) => {
+                chars.next();
+                res.push(c);
+            }
+            ('{', Some('}')) => {
+                chars.next();
+                if let Some(arg) = args.next() {
+                    let _ = write!(res, "{}", arg);
+                }
+            }
+            _ => res.push(c),
+        }
+    }
+
+    return res;
+}
+
+/// Returns the English template of a message
+///
+/// # Arguments
+///
+/// - `p_msg` - the message
+fn english(p_msg: Msg) -> &'static str {
+    return match p_msg {
+        Msg::HelpHeader => "\n\
+            File System Scanner (dumblebots.com)\n\
+            \n\
+            Usage: {} [PATH...] [options]\n\
+            Scan through the filesystem starting from each PATH.\n\
+            \n\
+            Example: {} \"..\" --recursive --files\n\
+            \n\
+            Options:\n",
+        Msg::HelpExample => "Example: {} {}\n",
+        Msg::SectionListing => "Listing:\n",
+        Msg::SectionEntryTypes => "Entry types:\n",
+        Msg::SectionFilters => "Filtering:\n",
+        Msg::SectionContents => "Sizes and contents:\n",
+        Msg::SectionReports => "Reports:\n",
+        Msg::SectionTransfer => "Transfers:\n",
+        Msg::SectionLayout => "Layout:\n",
+        Msg::SectionSearch => "Searching:\n",
+        Msg::SectionWatch => "Watching:\n",
+        Msg::SectionRecording => "Recording:\n",
+        Msg::SectionMisc => "Miscellaneous:\n",
+        Msg::OptRecursive => "Recursively scan directories (up to depth levels deep if it is given)\n",
+        Msg::OptMinDepth => "Only show entries at least depth levels deep (1 for the entries of the scanned directory)\n",
+        Msg::OptFollow => "Descend into symlinks to directories like the directories they point to (skipping the ones that loop back)\n",
+        Msg::OptPathsFrom => "Scan each of the paths in file (or stdin if it is -), one on each line or separated by NUL bytes\n",
+        Msg::OptMaxEntries => "Stop the traversal once n entries have been read (the output is marked as truncated)\n",
+        Msg::OptTimeout => "Stop the traversal once time has passed, such as 30s or 5m (the output is marked as incomplete)\n",
+        Msg::OptPermissions => "Print Permissions of each entry\n",
+        Msg::OptModificationTime => "Print the time when each entry was last modified\n",
+        Msg::OptHumanReadable => "Print sizes in binary units (such as 4.2 MiB) instead of bytes\n",
+        Msg::OptSi => "Print sizes in powers of 1000 (such as 1.3 GB) instead of bytes\n",
+        Msg::OptBinary => "Print sizes in powers of 1024 (such as 4.2 MiB) instead of bytes (same as --human-readable)\n",
+        Msg::OptBlockSize => "Print sizes as the number of blocks of size they take up (such as 1K, like du)\n",
+        Msg::OptRelativeTime => "Print the modification times as the time since then (such as 3 days ago) instead of the date\n",
+        Msg::OptTimeFormat => "Print the modification times in format (a strftime format, defaults to %b %d %Y  %H:%M)\n",
+        Msg::OptTimeZone => "Print times in zone (utc, local, an offset such as +05:30 or a name such as Europe/Berlin)\n",
+        Msg::OptColumns => "Print only the columns in list, in its order (size, mtime, atime, ctime, perms, owner, hash, content or name, separated by commas)\n",
+        Msg::OptFiles => "Show Regular Files (normally hidden)\n",
+        Msg::OptSymlinks => "Show Symlinks (normally hidden)\n",
+        Msg::OptSpecial => "Show Special Files such as sockets, pipes, etc. (normally hidden)\n",
+        Msg::OptExt => "Only show files whose names end with one of the comma-separated extensions in exts (regardless of case)\n",
+        Msg::OptType => "Only show entries of the comma-separated types in types (f for files, d for directories, l for symlinks, s for sockets, b for block devices, c for character devices and p for pipes)\n",
+        Msg::OptMinSize => "Only show regular files of at least size bytes (such as 10M, with K, M, G or T)\n",
+        Msg::OptMaxSize => "Only show regular files of at most size bytes (such as 1G, with K, M, G or T)\n",
+        Msg::OptEmptyFiles => "Only show empty regular files\n",
+        Msg::OptBrokenSymlinks => "Only show symlinks whose targets do not exist, along with the paths they point to\n",
+        Msg::OptOnlyText => "Only show regular files holding text (without a NUL byte in their first 8000 bytes)\n",
+        Msg::OptOnlyBinary => "Only show regular files holding binary data (with a NUL byte in their first 8000 bytes)\n",
+        Msg::OptMime => "Only show regular files whose MIME types, detected from their contents, match one of types (such as image/* or application/pdf, separated by commas)\n",
+        Msg::OptAll => "Also list and export hidden entries (whose names start with a dot)\n",
+        Msg::OptNoHidden => "Leave hidden entries out of every scan, including searches\n",
+        Msg::OptExclude => "Skip the entries matching pattern (like a line of .fssignore, can be repeated) without reading excluded directories\n",
+        Msg::OptExcludeFrom => "Skip the entries matching the patterns in file (one per line, like --exclude)\n",
+        Msg::OptPruneCommon => "Skip the directories of dependencies, builds and caches (node_modules, target, .venv, .git and __pycache__)\n",
+        Msg::OptPruneDir => "Skip the directories named name wherever they lie (can be repeated to extend --prune-common)\n",
+        Msg::OptRespectGitignore => "Skip the entries ignored by .gitignore files, .git/info/exclude and the global excludes of git\n",
+        Msg::OptNoIgnoreDot => "Do not honor .ignore and .fdignore files (.fssignore files are still honored)\n",
+        Msg::OptSkipFs => "Skip the mount points of the comma-separated filesystem types in types instead of the pseudo ones (such as proc, sysfs and devtmpfs)\n",
+        Msg::OptNoSkipFs => "Do not skip the mount points of pseudo filesystems\n",
+        Msg::OptNewerThan => "Only show entries last modified at or after when (a date such as 2024-01-01 or an age such as 30d)\n",
+        Msg::OptOlderThan => "Only show entries last modified before when (a date such as 2024-01-01 or an age such as 30d)\n",
+        Msg::OptAccessedWithin => "Only show entries last accessed at or after when (a date such as 2024-01-01 or an age such as 30d)\n",
+        Msg::OptStatusChangedWithin => "Only show entries whose status last changed at or after when (a date such as 2024-01-01 or an age such as 30d)\n",
+        Msg::OptOwner => "Only show entries owned by user (a name or an ID)\n",
+        Msg::OptGroup => "Only show entries owned by group (a name or an ID)\n",
+        Msg::OptPerm => "Only show entries whose permissions are mode (such as 644 or u=rw), or hold all (-mode) or any (/mode) of its bits\n",
+        Msg::OptDirSize => "Print directory sizes (calculated as the sum of sizes of all contained entries recursively)\n",
+        Msg::OptHash => "Print the hash of the contents of each file\n",
+        Msg::OptHashAlgo => "Hash using algo ({}, defaults to the fastest available)\n",
+        Msg::OptHashCache => "Reuse hashes stored in file for unchanged files (and store new ones in it)\n",
+        Msg::OptCheckpoint => "Record hashes in file as they are calculated, to resume an interrupted run\n",
+        Msg::OptDirsOnly => "Only show directories (sorted by cumulative size when used with -d, like du)\n",
+        Msg::OptNameCollisions => "Report groups of files in different directories that share the same name\n",
+        Msg::OptSameSize => "Only report files that share a name if they also have the same size (and hash with --hash)\n",
+        Msg::OptHardlinks => "Report the files with more than one hard link, grouped by the inode they share\n",
+        Msg::OptOldest => "Report the count least recently modified files along with their ages\n",
+        Msg::OptNewest => "Report the count most recently modified files along with their ages\n",
+        Msg::OptColdData => "Report the files that have not been accessed for a long time and their size per directory\n",
+        Msg::OptAtimeOlder => "Treat files that have not been accessed for age as cold (such as 90d, defaults to 180d)\n",
+        Msg::OptTimestampAnomalies => "Report files modified in the future, at the epoch or long before their last status change\n",
+        Msg::OptCtimeGap => "Treat files modified more than age before their last status change as suspicious (defaults to 10y)\n",
+        Msg::OptEmptyDirs => "Report the directories that hold no entries\n",
+        Msg::OptNestedEmpty => "Also report the directories that hold nothing but empty directories\n",
+        Msg::OptTreemap => "Render the cumulative sizes of the directories as a squarified treemap into file (an SVG image)\n",
+        Msg::OptFingerprint => "Print a compact digest of each directory (for checking if replicas are in sync)\n",
+        Msg::OptTransferEstimate => "Estimate the bytes an rsync-like sync from PATH to dest would transfer (either can be a recording)\n",
+        Msg::OptBlockEstimate => "Compare changed files block-by-block instead of counting their full size\n",
+        Msg::OptNoCachePollution => "Read file contents without evicting other data from the page cache\n",
+        Msg::OptNoTree => "Print the absolute path of each entry (without indendation) instead of tree form\n",
+        Msg::OptIndent => "Indent each level of nesting by width columns (defaults to 4)\n",
+        Msg::OptIndentStyle => "Indent with style (spaces, dots, bars or tree, defaults to spaces)\n",
+        Msg::OptTree => "Connect each entry to its directory with lines like tree (same as --indent-style tree)\n",
+        Msg::OptEscape => "Print control and other non-printable characters in names as escapes like \\n or \\033 (like ls -b)\n",
+        Msg::OptQuote => "Escape names like --escape and enclose them in double quotes (like ls -Q)\n",
+        Msg::OptColor => "Color each entry by its kind with the colors of LS_COLORS when (auto, always or never, defaults to auto, only on a terminal)\n",
+        Msg::OptWidth => "Shorten names that do not fit in cols columns with an ellipsis (defaults to the width of the terminal, 0 to never shorten them)\n",
+        Msg::OptAgeHeatmap => "Color each entry by the time since it was last modified (red for recent, blue for old)\n",
+        Msg::OptSizeHeatmap => "Color each entry by its size (red for large, blue for small, directories only with -d)\n",
+        Msg::OptSplitOutput => "Write the report of each directory directly under PATH into a file named after it in dir\n",
+        Msg::OptOutput => "Write the output into file, which is only replaced once the scan finishes (errors are still shown)\n",
+        Msg::OptAppend => "Append the output to the file given to --output instead of replacing its contents\n",
+        Msg::OptCompress => "Compress the output with gzip (done on its own for output files ending with .gz, .zst or .zstd)\n",
+        Msg::OptCompressAlgo => "Compress the output with algo (gzip or zstd)\n",
+        Msg::OptPaging => "Pass the output through $PAGER (or less -R) when (auto, always or never, defaults to never, auto only pages on a terminal)\n",
+        Msg::OptNamesOnly => "Only print the path of each entry, one per line\n",
+        Msg::OptPrint0 => "Only print the path of each entry, each followed by a NUL byte instead of a newline (for xargs -0)\n",
+        Msg::OptSizesOnly => "Only print the size (in bytes) and path of each entry separated by a tab, like du\n",
+        Msg::OptLsFormat => "Print each entry like ls -l (mode, links, owner, group, size, date and path)\n",
+        Msg::OptLong => "List the entries of each directory by name like ls -lR, in columns aligned per directory\n",
+        Msg::OptPrintf => "Print each entry on a line of template, with {name}, {path}, {size}, {perms}, {mtime}, {type}, {depth} and {mime} filled in\n",
+        Msg::OptOrdered => "Print the entries of each directory sorted by name (instead of the order they are read in)\n",
+        Msg::OptFormat => "Print the path, type, size, permissions, modification time and depth of each entry in fmt (json, ndjson, csv, tsv, markdown, mtree or dot)\n",
+        Msg::OptExportSqlite => "Write each entry into the table entries of the SQLite database file, along with the id of its directory\n",
+        Msg::OptExportParquet => "Write the path, size, modification time, type and owner of each entry into the Parquet file file\n",
+        Msg::OptWalkThreads => "Read the subdirectories of each directory ahead of the scan on n threads\n",
+        Msg::OptWorkThreads => "Hash the printed files ahead of the scan on n threads (separately from --walk-threads)\n",
+        Msg::OptSearch => "Only show entries whose name completely matches phrase\n",
+        Msg::OptSearchNoext => "Only show entries whose name(not counting the extension) completely matches phrase\n",
+        Msg::OptContains => "Only show entries whose name contains phrase\n",
+        Msg::OptGlob => "Only show entries matching the glob pattern (with *, ?, [...], ** and {a,b}, matched against the path relative to PATH if it has a slash)\n",
+        Msg::OptFuzzy => "Only show entries whose name holds the characters of phrase in order, from the best match to the worst (ignores case unless phrase has uppercase characters)\n",
+        Msg::OptPatternsFrom => "Search for the patterns in file, one on each line (matched like --search unless another search option is given)\n",
+        Msg::OptIgnoreCase => "Match the search patterns regardless of case (such as Makefile and makefile)\n",
+        Msg::OptInvertMatch => "Only show entries that match none of the search patterns (such as files that break a naming convention)\n",
+        Msg::OptFullPath => "Match the search patterns against the path of each entry relative to PATH instead of its name\n",
+        Msg::OptSearchTree => "Indent the matches under the directories that they lie in (like a scan) instead of printing their paths\n",
+        Msg::OptMaxResults => "Stop searching once n matching entries have been shown\n",
+        Msg::OptCountsByDir => "Summarize how many of the matches lie in each directory and have each extension\n",
+        Msg::OptBfs => "Search breadth-first, showing the matches of each level before the ones deeper down\n",
+        Msg::OptFindExpr => "Only print the paths of the entries selected by a find expression (see below)\n",
+        Msg::OptWatch => "Watch PATH for changes and report them (the search options filter the changes)\n",
+        Msg::OptDebounce => "Report changes only once no further changes have happened for ms milliseconds\n",
+        Msg::OptOnCreate => "Run cmd for every created entry (with {{}} replaced by its path)\n",
+        Msg::OptOnModify => "Run cmd for every modified entry (with {{}} replaced by its path)\n",
+        Msg::OptOnDelete => "Run cmd for every deleted entry (with {{}} replaced by its path)\n",
+        Msg::OptRecord => "Record every entry read during the scan into file\n",
+        Msg::OptReplay => "Re-run the scan recorded in file instead of reading the filesystem\n",
+        Msg::OptSnapshot => "Record the scan like --record, as a compact binary snapshot with the full metadata\n",
+        Msg::OptShowErr => "Show errors\n",
+        Msg::OptOnError => "Skip directories that can not be read, abort at the first one, or collect them into a report at the end (skip, abort or collect)\n",
+        Msg::OptMeasureLatency => "Measure the time taken to read each directory and report the slowest ones\n",
+        Msg::OptProgress => "Show the progress of the scan on stderr (with an estimate of the time left if the scan is recorded over a previous recording)\n",
+        Msg::OptProgressFrom => "Estimate the progress of the scan from the number of entries in file (a recording of a previous scan)\n",
+        Msg::OptPolicy => "Run the scan described by a TOML policy in file (options given after it override the policy)\n",
+        Msg::OptLang => "Show messages in lang (en, de or es, defaults to the language of the locale)\n",
+        Msg::OptCapabilities => "Print which columns and reports are supported by this build, platform and the filesystem of PATH\n",
+        Msg::OptHelp => "Print Usage Instructions\n",
+        Msg::OptHelpFull => "Print Usage Instructions along with the description of the output formats\n",
+        Msg::OptMan => "Print a man page (in roff)\n",
+        Msg::ManName => "high performance command-line tool to navigate through the filesystem\n",
+        Msg::ManDescription => "\
+            fss scans through the filesystem starting from each PATH (the current directory if none is given). Only \
+            directories are listed by default, and the number of files, symlinks and special files in each directory \
+            is summarised instead.\n\
+            \n\
+            Directories can be scanned recursively, and entries can be searched for by their name, or summarised \
+            with fingerprints, directory sizes and hashes of their contents.\n",
+        Msg::DocListingTitle => "Listing output:\n",
+        Msg::DocListing => "\
+            Each entry is printed on its own line, either in tree form (indented by its depth) or as its absolute \
+            path with --no-tree. The names of directories are enclosed in <>, and symlinks are followed by -> and \
+            their target.\n\
+            \n\
+            The columns before the name are, in order, the permissions (-p), the time of the last modification \
+            (-t), the hash (--hash) and the size. Files always show their size and directories show theirs with -d. \
+            Symlinks and special files show their type instead, and ERROR is shown if a value could not be read.\n\
+            \n\
+            Entries of the hidden types are summarised in a single line per directory (such as <2 symlinks>), and \
+            the number of entries of each type is summarised after the listing.\n",
+        Msg::DocFingerprintTitle => "Fingerprint output:\n",
+        Msg::DocFingerprint => "\
+            The first line is \"# fss fingerprint v1\". Every other line describes a directory by its path relative \
+            to PATH (. for PATH itself), followed by a tab and the cumulative values of its subtree -\n\
+            \n\
+            \x20   files=N symlinks=N special=N dirs=N size=BYTES newest=SECONDS names=HASH\n\
+            \n\
+            newest is the latest modification time in seconds since the Unix epoch, and names is a hash of the names \
+            and types of all the entries in the subtree (ERROR if some of them could not be read). Directories whose \
+            lines are equal on two machines are very likely in sync.\n",
+        Msg::DocFindExprTitle => "Find expressions:\n",
+        Msg::DocFindExpr => "\
+            --find-expr prints the path of every entry under PATH (not PATH itself) for which the expression is \
+            true, like find. It is a single argument, which is split into words like a shell would. The supported \
+            primaries are -\n\
+            \n\
+            \x20   -name pattern / -iname pattern           name matches the pattern (*, ? and [...])\n\
+            \x20   -type f|d|l|s|b|c|p                      type of the entry (symlinks are not followed)\n\
+            \x20   -size [+-]N[cwbkMG]                      size in units (512 byte blocks by default), rounded up\n\
+            \x20   -mtime [+-]N                             last modified N whole days ago\n\
+            \x20   -maxdepth N                              do not descend below depth N\n\
+            \x20   -prune                                   do not descend into the directory\n\
+            \x20   -print                                   print the path (if given, nothing else is printed)\n\
+            \n\
+            + means more than N and - means less than N. Primaries are combined with ( ), ! (or -not), -a (or -and, \
+            or nothing) and -o (or -or), in order of precedence.\n",
+        Msg::DocIgnoreTitle => "Ignore files:\n",
+        Msg::DocIgnore => "\
+            A .fssignore file has the syntax of .gitignore, and excludes the entries it matches from every scan of \
+            the directory it lies in and of everything below it (the rules of deeper files and later lines take \
+            precedence, and ! re-includes an entry). Ignored entries are neither shown nor counted in sizes and \
+            summaries, and ignored directories are not read. Recordings lack the ignored entries, so replays are not \
+            affected by later changes to the files. Patterns given to --exclude are applied like a .fssignore in \
+            PATH, except that one ending with /** excludes the directory itself. The .ignore and .fdignore files are honored the same way \
+            unless --no-ignore-dot is given. With --respect-gitignore, .gitignore files (including those \
+            above PATH within its repository), .git/info/exclude and the global excludes file of git are honored \
+            the same way.\n",
+        Msg::DocPolicyTitle => "Policies:\n",
+        Msg::DocPolicy => "\
+            --policy reads a scan from a TOML file (only tables, strings, integers, booleans and arrays are \
+            supported) -\n\
+            \n\
+            \x20   path = \"dir\"                             directory to scan (the directory of the policy by default)\n\
+            \x20   [options] name = value                   an option that selects the entries (true for flags, arrays to repeat it)\n\
+            \x20   [excludes] patterns = [\"pattern\", ...]   entries to exclude, like a .fssignore in PATH\n\
+            \x20   [budgets] \"dir\" = \"10G\"                  maximum size of a directory (K, M, G or T)\n\
+            \x20   [retention] \"dir\" = \"90d\"                maximum age of the files in a directory\n\
+            \x20   [output] file = \"file\"                   write the output into file instead of stdout\n\
+            \n\
+            path and file are relative to the policy, and the directories of budgets and retention to PATH. \
+            Options given after --policy override the ones of the policy. The directories over budget and the files \
+            kept too long are listed after the scan, and fss exits with status 1 if there are any. Policies can only \
+            set the options that select and filter the entries (such as --recursive, --files, --exclude, --search, \
+            -d or --dirs-only), so that running one never runs a command or writes a file other than the one of \
+            [output].\n",
+        Msg::DocFormatsTitle => "Formats:\n",
+        Msg::DocFormats => "\
+            --format prints the entries selected like with --names-only, each with its path, type (file, dir, \
+            symlink, socket, block, char, fifo or special), size, permissions (in octal), modification time (in RFC \
+            3339) and depth (1 for the entries of PATH). Directories come before their entries, with their own size -\n\
+            \n\
+            \x20   json      {\"root\": ..., \"entries\": [{\"path\": ..., \"type\": ..., \"size\": ..., \"permissions\": ..., \n\
+            \x20             \"mtime\": ..., \"depth\": ...}, ...]} (values that are not available are null)\n\
+            \x20   ndjson    one object of the json format per line, printed as soon as the entry is read\n\
+            \x20   csv       header row, then path,type,size,permissions,mtime,depth per entry (values holding commas, \n\
+            \x20             quotes or line breaks are quoted, values that are not available are empty)\n\
+            \x20   tsv       like csv, but separated by tabs (backslashes, tabs and newlines are escaped as \\\\, \\t and \\n)\n\
+            \x20   markdown  a table with the columns of csv, or with -r a nested list of names (below PATH) followed by \n\
+            \x20             their details in parentheses\n\
+            \x20   mtree     \"#mtree\", then ./path type=... mode=... size=... time=... per entry like BSD mtree (paths \n\
+            \x20             are relative to PATH, the size is only given for files)\n\
+            \x20   dot       a Graphviz digraph with a node per entry labelled by its name and size (the cumulative \n\
+            \x20             size for the directories that were scanned) and an edge from its directory\n",
+        Msg::DocRecordingTitle => "Recordings:\n",
+        Msg::DocRecording => "\
+            Recordings written by --record start with \"# fss recording v1\", followed by one line per event with \
+            tab-separated fields (backslashes, tabs and newlines in paths are escaped) -\n\
+            \n\
+            \x20   R root                                   initial directory\n\
+            \x20   D dir / X dir error                      directory opened / could not be opened\n\
+            \x20   E dir name kind len mode mtime           entry of a directory\n\
+            \x20   e dir error                              entry that could not be read\n\
+            \x20   C path canonical / c path error          canonical path\n\
+            \x20   T path 0|1                               whether a symlink points to a directory\n\
+            \n\
+            Snapshots written by --snapshot are MessagePack values, the string \"fss snapshot v1\" followed by an array \
+            per event with the same fields, except that entries refer to their directory by its index (directories \
+            are numbered from 0 in the order they are opened), carry the access, change and birth times in \
+            nanoseconds followed by the owner, group, inode, device and number of links (nil if not available), and \
+            only symlinks have a canonical path stored -\n\
+            \n\
+            \x20   E dir-index name kind len mode mtime atime ctime btime uid gid ino dev nlink\n\
+            \x20   e dir-index error\n\
+            \n\
+            --replay re-runs listings and searches from a recording or a snapshot, so the contents of files are not \
+            available.\n",
+        Msg::DocHashCacheTitle => "Hash caches:\n",
+        Msg::DocHashCache => "\
+            Hash caches (--hash-cache) and checkpoints (--checkpoint) start with \"# fss hash cache v1\", followed by \
+            one line per file with the tab-separated fields -\n\
+            \n\
+            \x20   algo device inode size mtime hash path\n\
+            \n\
+            A cached hash is only reused if the algorithm, device, inode, size and modification time of the file are \
+            unchanged.\n",
+        Msg::DocEnvironmentTitle => "Environment:\n",
+        Msg::DocEnvironment => "\
+            LC_ALL, LC_MESSAGES and LANG select the language of the messages (the first of them that is set is \
+            used, unless --lang is given).\n\
+            \n\
+            TZ selects the time zone that modification times are shown in.\n\
+            \n\
+            Colors (such as the ones of --age-heatmap and --size-heatmap) are only used when the output is a terminal. NO_COLOR disables \
+            them, and CLICOLOR_FORCE enables them even if the output is not a terminal.\n",
+        Msg::DocExitStatusTitle => "Exit Status:\n",
+        Msg::DocExitStatus => "\
+            \x20   0     the run succeeded (a search found at least one matching entry)\n\
+            \x20   1     a search found no matching entry, or the scan violated its policy\n\
+            \x20   2     a search could not read the directory that it starts from\n\
+            \x20   3     the scan was aborted at a directory that could not be read (with --on-error abort)\n\
+            \x20   255   an option was invalid, or the output could not be written\n\
+            \n\
+            Like grep, a search exits with 1 or 2 even if it violated its policy.\n",
+        Msg::EmptyOption => "Ignoring Unknown Option of length 0\n",
+        Msg::UnknownOption => "Ignoring unknown option {}\n",
+        Msg::UnknownLang => "Unknown language \"{}\" (expected en, de or es)\n",
+        Msg::RecursionDepthZero => "Maximum recursion depth must be greater than 0!\n",
+        Msg::NotAnInteger => "Could not convert \"{}\" to an integer\n",
+        Msg::NotAPositiveInteger => "\"{}\" is not a positive integer\n",
+        Msg::IgnoringRecursive => "Ignoring recursive option\n",
+        Msg::IgnoringDebounce => "Ignoring debounce option\n",
+        Msg::IgnoringOption => "Ignoring option {}\n",
+        Msg::OneSearchMode => "Can only set one search mode at a time\n",
+        Msg::OneHeatmap => "Can only draw one heatmap at a time\n",
+        Msg::OnePlainMode => "Can only set one of --names-only, --sizes-only, --ls-format, --format, --export-sqlite, --export-parquet, --long, --printf and --print0 at a time\n",
+        Msg::Terminating => "Terminating...",
+        Msg::MissingSearchPattern => "No Search Pattern provided after {} flag\n",
+        Msg::MissingDestPath => "No destination path provided after {} flag\n",
+        Msg::MissingSplitDir => "No directory provided after {} flag\n",
+        Msg::MissingTreemapPath => "No image file provided after {} flag\n",
+        Msg::MissingPolicy => "No policy provided after {} flag\n",
+        Msg::MissingPatternsFile => "No pattern file provided after {} flag\n",
+        Msg::MissingPathsFile => "No path file provided after {} flag\n",
+        Msg::MissingEntryCount => "No number of entries provided after {} flag\n",
+        Msg::MissingTimeout => "No time budget provided after {} flag\n",
+        Msg::MissingOnError => "No way of handling errors provided after {} flag\n",
+        Msg::MissingHashAlgo => "No hashing algorithm provided after {} flag\n",
+        Msg::MissingCompressAlgo => "No compression algorithm provided after {} flag\n",
+        Msg::MissingIndentWidth => "No indentation width provided after {} flag\n",
+        Msg::MissingThreadCount => "No number of threads provided after {} flag\n",
+        Msg::MissingIndentStyle => "No indentation style provided after {} flag\n",
+        Msg::MissingFormat => "No output format provided after {} flag\n",
+        Msg::MissingTemplate => "No template provided after {} flag\n",
+        Msg::MissingColumns => "No columns provided after {} flag\n",
+        Msg::MissingTimeFormat => "No time format provided after {} flag\n",
+        Msg::MissingBlockSize => "No block size provided after {} flag\n",
+        Msg::MissingWidth => "No width provided after {} flag\n",
+        Msg::MissingExtensions => "No extensions provided after {} flag\n",
+        Msg::MissingMimeTypes => "No MIME types provided after {} flag\n",
+        Msg::MissingFsTypes => "No filesystem types provided after {} flag\n",
+        Msg::MissingTypes => "No types provided after {} flag\n",
+        Msg::MissingSize => "No size provided after {} flag\n",
+        Msg::MissingTimeBound => "No date or age provided after {} flag\n",
+        Msg::MissingUser => "No user provided after {} flag\n",
+        Msg::MissingExcludePattern => "No exclude pattern provided after {} flag\n",
+        Msg::MissingExcludeFile => "No exclude file provided after {} flag\n",
+        Msg::MissingPruneDir => "No directory name provided after {} flag\n",
+        Msg::MissingDepth => "No depth provided after {} flag\n",
+        Msg::ExcludesReadError => "Error while reading exclude file \"{}\"\n{}\n",
+        Msg::MissingGroup => "No group provided after {} flag\n",
+        Msg::MissingPerm => "No mode provided after {} flag\n",
+        Msg::MissingPagingMode => "No paging mode provided after {} flag\n",
+        Msg::MissingColorMode => "No color mode provided after {} flag\n",
+        Msg::MissingTimeZone => "No time zone provided after {} flag\n",
+        Msg::MissingDbPath => "No database file provided after {} flag\n",
+        Msg::MissingParquetPath => "No Parquet file provided after {} flag\n",
+        Msg::MissingFindExpr => "No find expression provided after {} flag\n",
+        Msg::MissingHashCache => "No hash cache file provided after {} flag\n",
+        Msg::MissingCheckpoint => "No checkpoint file provided after {} flag\n",
+        Msg::MissingDebounce => "No debounce time provided after {} flag\n",
+        Msg::MissingFileCount => "No number of files provided after {} flag\n",
+        Msg::MissingResultCount => "No number of results provided after {} flag\n",
+        Msg::MissingAge => "No age provided after {} flag\n",
+        Msg::InvalidAge => "Could not convert \"{}\" to an age (expected a number followed by s, m, h, d, w or y)\n",
+        Msg::InvalidTimeout => "Could not convert \"{}\" to a time budget (expected a number of seconds, optionally followed by s, m, h, d, w or y)\n",
+        Msg::MissingCommand => "No command provided after {} flag\n",
+        Msg::MissingRecording => "No recording file provided after {} flag\n",
+        Msg::MissingLang => "No language provided after {} flag\n",
+        Msg::HashAlgoNotCompiled => "Hashing algorithm {} was not compiled in\n",
+        Msg::CompressAlgoNotCompiled => "Compression algorithm {} was not compiled in\n",
+        Msg::ParquetNotCompiled => "Parquet export was not compiled in\n",
+        Msg::MimeNotCompiled => "Detecting MIME types was not compiled in\n",
+        Msg::UnknownHashAlgo => "Unknown hashing algorithm \"{}\" (expected blake3, sha256 or xxh3)\n",
+        Msg::UnknownCompressAlgo => "Unknown compression algorithm \"{}\" (expected gzip or zstd)\n",
+        Msg::UnknownOnError => "Unknown way of handling errors \"{}\" (expected skip, abort or collect)\n",
+        Msg::UnknownIndentStyle => "Unknown indentation style \"{}\" (expected spaces, dots, bars or tree)\n",
+        Msg::UnknownFormat => "Unknown output format \"{}\" (expected json, ndjson, csv, tsv, markdown, mtree or dot)\n",
+        Msg::UnknownPlaceholder => "Unknown placeholder \"{}\" in the template (expected {name}, {path}, {size}, {perms}, {mtime}, {type} or {depth})\n",
+        Msg::UnknownColumn => "Unknown column \"{}\" (expected size, mtime, atime, ctime, perms, owner, hash, content or name)\n",
+        Msg::InvalidTimeFormat => "Invalid time format \"{}\" (expected a strftime format such as %Y-%m-%d %H:%M)\n",
+        Msg::InvalidBlockSize => "Could not convert \"{}\" to a block size (expected a positive number followed by K, M, G or T)\n",
+        Msg::InvalidWidth => "Could not convert \"{}\" to a width (expected a number of columns)\n",
+        Msg::InvalidExtensions => "No extensions in \"{}\" (expected a comma-separated list such as rs,toml,md)\n",
+        Msg::InvalidMimeTypes => "No MIME types in \"{}\" (expected a comma-separated list such as image/*,application/pdf)\n",
+        Msg::InvalidFsTypes => "No filesystem types in \"{}\" (expected a comma-separated list such as proc,sysfs,nfs)\n",
+        Msg::UnknownType => "Unknown type \"{}\" (expected f, d, l, s, b, c or p)\n",
+        Msg::InvalidSize => "Could not convert \"{}\" to a size (expected a number of bytes, optionally followed by K, M, G or T)\n",
+        Msg::InvalidTimeBound => "Could not convert \"{}\" to a date or an age (expected a date such as 2024-01-01 or an age such as 30d)\n",
+        Msg::UnknownUser => "Unknown user \"{}\" (expected a user name or ID)\n",
+        Msg::UnknownGroup => "Unknown group \"{}\" (expected a group name or ID)\n",
+        Msg::InvalidPerm => "Could not convert \"{}\" to a mode (expected an octal mode such as 644 or a symbolic one such as u+w, optionally starting with - or /)\n",
+        Msg::UnknownPagingMode => "Unknown paging mode \"{}\" (expected auto, always or never)\n",
+        Msg::UnknownColorMode => "Unknown color mode \"{}\" (expected auto, always or never)\n",
+        Msg::UnknownTimeZone => "Unknown time zone \"{}\" (expected utc, local, an offset such as +05:30 or a name such as Europe/Berlin)\n",
+        Msg::FindUnknownPrimary => "Unsupported find primary or operator {}\n",
+        Msg::FindMissingArgument => "No argument provided after find primary {}\n",
+        Msg::FindInvalidArgument => "Invalid argument \"{}\" for find primary {}\n",
+        Msg::FindSyntaxError => "Invalid find expression (missing operand, unmatched parenthesis or unterminated quote)\n",
+        Msg::HashCacheReadError => "Error while reading hash cache \"{}\"\n{}\n",
+        Msg::PatternsReadError => "Error while reading patterns \"{}\"\n{}\n",
+        Msg::NoPatternsInFile => "No patterns in \"{}\"\n",
+        Msg::PathsReadError => "Error while reading paths \"{}\"\n{}\n",
+        Msg::NoPathsInFile => "No paths in \"{}\"\n",
+        Msg::IgnoreFileReadError => "Error while reading ignore file \"{}\"\n{}\n",
+        Msg::HashCacheWriteError => "Error while writing hash cache\n{}\n",
+        Msg::CheckpointReadError => "Error while reading checkpoint \"{}\"\n{}\n",
+        Msg::CheckpointRemoveError => "Error while removing checkpoint\n{}\n",
+        Msg::RecordReplayModes => "Scans can only be recorded and replayed while listing or searching\n",
+        Msg::RecordAndReplay => "Can not record and replay a scan at the same time\n",
+        Msg::AppendWithoutOutput => "Can only append to an output file given with --output\n",
+        Msg::BlockSizeWithHumanReadable => "Can not set --block-size along with --human-readable, --si or --binary\n",
+        Msg::BfsWithSearchTree => "Can not set --bfs along with --search-tree\n",
+        Msg::BfsWithoutSearch => "Can only set --bfs along with a search (--search, --search-noext, --contains, --glob or --fuzzy) that prints its matches as a list\n",
+        Msg::MaxEntriesWithWatch => "Can not set --max-entries along with --watch\n",
+        Msg::TimeoutWithWatch => "Can not set --timeout along with --watch\n",
+        Msg::OnErrorWithWatch => "Can only set --on-error skip along with --watch\n",
+        Msg::ScanAborted => "ABORTED: the traversal stopped at \"{}\", which could not be read (--on-error abort)\n{}\n\n",
+        Msg::ErrorReportDirs => "Directories that could not be read\n",
+        Msg::ErrorReportDir => "\"{}\": {}\n",
+        Msg::ErrorReportKinds => "\nSummary of errors\n",
+        Msg::ErrorReportKind => "<{} {}>\n",
+        Msg::ErrorReportTotal => "<{} total errors>\n\n",
+        Msg::TraversalTimedOut => "INCOMPLETE: the traversal stopped once its time budget of {}s ran out (--timeout), so the output and the summaries are incomplete\n\n",
+        Msg::EntriesTruncated => "TRUNCATED: the traversal stopped after {} entries (--max-entries), so the output and the summaries are incomplete\n\n",
+        Msg::MultiplePathsModes => "Can only scan a single path with --watch, --record, --replay, --policy, --split-output, --treemap, --format, --export-sqlite and --export-parquet\n",
+        Msg::PathHeader => "{}:\n",
+        Msg::CombinedScanSummary => "Summary of all {} paths\n\
+            <{} files>\n\
+            <{} symlinks>\n\
+            <{} special files>\n\
+            <{} subdirectories>\n\
+            <{} total entries>\n\
+            \n",
+        Msg::CombinedSearchSummary => "Summary of matching entries in all {} paths\n\
+            <{} files>\n\
+            <{} symlinks>\n\
+            <{} special files>\n\
+            <{} subdirectories>\n\
+            <{} total entries>\n\
+            \n",
+        Msg::OutputWhileWatching => "The output can not be written into a file while watching, since the scan never finishes\n",
+        Msg::SplitOutputModes => "The output can not be split while watching, recording, replaying, estimating transfers, following a policy or writing it into a file\n",
+        Msg::OnePolicy => "Can only follow one policy at a time\n",
+        Msg::PolicyReadError => "Error while reading policy \"{}\"\n{}\n",
+        Msg::PolicySyntaxError => "Could not parse line {} of policy \"{}\"\n",
+        Msg::PolicyUnknownKey => "Unknown key {} in policy \"{}\"\n",
+        Msg::PolicyInvalidValue => "Invalid value for key {} in policy \"{}\"\n",
+        Msg::PolicyOptionNotAllowed => "Option --{} can not be set by policy \"{}\" (policies can only set the options that select and filter the scanned entries)\n",
+        Msg::PolicyOutputError => "Error while creating output file \"{}\"\n{}\n",
+        Msg::MissingOutputPath => "No output file provided after {} flag\n",
+        Msg::OutputCreateError => "Error while creating output file \"{}\"\n{}\n",
+        Msg::OutputWriteError => "Error while writing output file \"{}\"\n{}\n",
+        Msg::CompressError => "Error while compressing the output\n{}\n",
+        Msg::PagerError => "Error while paging the output\n{}\n",
+        Msg::PolicyBudgetsHeader => "\nBudgets of directories in \"{}\" (size and budget)\n",
+        Msg::PolicyOverBudget => "{} <over budget>\n",
+        Msg::PolicyRetentionHeader => "\nFiles in \"{}\" kept longer than {}\n",
+        Msg::PolicySummary => "\n<{} directories over budget>\n<{} files kept too long>\n",
+        Msg::SplitDirCreateError => "Error while creating the directory \"{}\" for the reports\n{}\n",
+        Msg::TreemapWriteError => "Error while writing the treemap into \"{}\"\n{}\n",
+        Msg::SqliteCreateError => "Error while creating the database \"{}\"\n{}\n",
+        Msg::SqliteWriteError => "Error while writing the entries into the database \"{}\"\n{}\n",
+        Msg::ParquetCreateError => "Error while creating the Parquet file \"{}\"\n{}\n",
+        Msg::ParquetWriteError => "Error while writing the entries into the Parquet file \"{}\"\n{}\n",
+        Msg::SplitExeError => "Could not find the path of fss to scan the subdirectories with\n",
+        Msg::ReportCreateError => "Error while creating the report \"{}\"\n{}\n",
+        Msg::ReportRunError => "Error while scanning \"{}\" for its report\n{}\n",
+        Msg::ReportWritten => "Wrote the report of \"{}\" to \"{}\"\n",
+        Msg::SplitOutputSummary => "\n<{} reports written to \"{}\">\n",
+        Msg::RecordingReadError => "Error while reading recording \"{}\"\n{}\n",
+        Msg::RecordingCreateError => "Error while creating recording \"{}\"\n{}\n",
+        Msg::RecordingWriteError => "Error while writing recording \"{}\"\n{}\n",
+        Msg::IterateError => "Error while iterating over \"{}\"\n{}\n",
+        Msg::MetadataError => "Error while getting metadata of \"{}\"\n{}\n",
+        Msg::ModifiedTimeError => "Error while getting last modified time of \"{}\"\n",
+        Msg::AccessTimeError => "Error while getting the time of last access of \"{}\"\n{}\n",
+        Msg::AtimeUnreliable => "Access times are not recorded on \"{}\" (it is mounted with noatime), so the report is not reliable\n",
+        Msg::SymlinkTargetError => "Error while reading target of symlink \"{}\"\n{}\n",
+        Msg::DirSizeTraverseError => "Error while traversing {} while calculating size of directory {}\n{}\n",
+        Msg::DirSizeEntryError => {
+            "Error while reading an entry of {} while calculating size of directory {}\n{}\n"
+        }
+        Msg::HashError => "Error while hashing \"{}\"\n{}\n",
+        Msg::HashProgress => "\rHashing \"{}\" ({}%)",
+        Msg::ScanProgress => "\rRead {} entries",
+        Msg::ScanProgressEstimate => "\r[{}{}] {}% ({} of about {} entries, {} left)",
+        Msg::ScanSummary => "\n\
+            Summary of \"{}\"\n\
+            <{} files>\n\
+            <{} symlinks>\n\
+            <{} special files>\n\
+            <{} subdirectories>\n\
+            <{} total entries>\n\
+            \n",
+        Msg::ScanSummaryRecursive => "Including subdirectories\n\
+            <{} files>\n\
+            <{} symlinks>\n\
+            <{} special files>\n\
+            <{} subdirectories>\n\
+            <{} total entries>\n\
+            \n",
+        Msg::SearchSummaryMatches => "\n\
+            Summary of matching entries\n\
+            <{} files>\n\
+            <{} symlinks>\n\
+            <{} special files>\n\
+            <{} subdirectories>\n\
+            <{} total entries>\n\
+            \n",
+        Msg::SearchSummaryPatterns => "Summary of matches of each pattern\n",
+        Msg::SearchSummaryPattern => "<{} entries matching \"{}\">\n",
+        Msg::SearchStopped => "The search stopped after {} matches, so not every entry was traversed\n\n",
+        Msg::SearchSummaryDirs => "Summary of matches in each directory\n",
+        Msg::SearchSummaryDir => "<{} entries in \"{}\">\n",
+        Msg::SearchSummaryExts => "Summary of matches with each extension\n",
+        Msg::SearchSummaryExt => "<{} entries ending with \".{}\">\n",
+        Msg::SearchSummaryNoExt => "<{} entries without an extension>\n",
+        Msg::FilesystemLoops => "Skipped {} directories or followed symlinks that loop back to a directory above them\n",
+        Msg::FilterSummary => "Filtered out\n\
+            <{} entries>\n\
+            \n",
+        Msg::SearchSummaryTraversal => "Summary of traversal of \"{}\"\n\
+            <{} files>\n\
+            <{} symlinks>\n\
+            <{} special files>\n\
+            <{} subdirectories>\n\
+            <{} total entries>\n\
+            \n",
+        Msg::DirsOnlyTotal => "\n\
+            Total size of \"{}\"\n\
+            <{} bytes>\n\
+            \n",
+        Msg::TreemapSummary => "Treemap of \"{}\" written into \"{}\"\n\
+            <{} directories>\n\
+            <{} bytes>\n\
+            \n",
+        Msg::SqliteSummary => "Entries written into the database \"{}\"\n\
+            <{} entries>\n\
+            \n",
+        Msg::ParquetSummary => "Entries written into the Parquet file \"{}\"\n\
+            <{} entries>\n\
+            \n",
+        Msg::NameCollisionSummary => "Summary of name collisions in \"{}\"\n\
+            <{} shared names>\n\
+            <{} files>\n\
+            \n",
+        Msg::OldestFiles => "Oldest files in \"{}\"\n",
+        Msg::NewestFiles => "Newest files in \"{}\"\n",
+        Msg::ColdDataHeader => "Files in \"{}\" that have not been accessed for {}\n",
+        Msg::ColdDir => "{} <{} cold files>\n",
+        Msg::ColdDataSummary => "\n\
+            Summary of cold data\n\
+            <{} cold files>\n\
+            <{} bytes of cold data>\n\
+            <{} bytes in total>\n\
+            \n",
+        Msg::TimestampAnomaliesHeader => "Files in \"{}\" with suspicious timestamps\n",
+        Msg::TimestampAnomaliesSummary => "\n\
+            Summary of timestamp anomalies\n\
+            <{} files with suspicious timestamps>\n\
+            \n",
+        Msg::EmptyDirsHeader => "Empty directories in \"{}\"\n",
+        Msg::EmptyDirsSummary => "\n\
+            Summary of empty directories\n\
+            <{} empty directories>\n\
+            \n",
+        Msg::HardlinksHeader => "Files in \"{}\" with more than one hard link\n",
+        Msg::HardlinkGroup => "\nInode {} <{} links>\n",
+        Msg::HardlinksSummary => "\n\
+            Summary of hard links\n\
+            <{} shared inodes>\n\
+            <{} files>\n\
+            <{} bytes saved by the links>\n\
+            \n",
+        Msg::AnomalyFuture => "modified in the future",
+        Msg::AnomalyEpoch => "modified at the epoch",
+        Msg::AnomalyAfterCtime => "modified after its last status change",
+        Msg::AnomalyCtimeGap => "modified more than {} before its last status change",
+        Msg::TimeJustNow => "just now",
+        Msg::TimeFuture => "in the future",
+        Msg::TimeMinuteAgo => "1 minute ago",
+        Msg::TimeMinutesAgo => "{} minutes ago",
+        Msg::TimeHourAgo => "1 hour ago",
+        Msg::TimeHoursAgo => "{} hours ago",
+        Msg::TimeDayAgo => "1 day ago",
+        Msg::TimeDaysAgo => "{} days ago",
+        Msg::TimeMonthAgo => "1 month ago",
+        Msg::TimeMonthsAgo => "{} months ago",
+        Msg::TimeYearAgo => "1 year ago",
+        Msg::TimeYearsAgo => "{} years ago",
+        Msg::TransferSummary => "\n\
+            Transfer estimate from \"{}\" to \"{}\"\n\
+            <{} new files ({} bytes)>\n\
+            <{} changed files ({} bytes)>\n\
+            <{} unchanged files>\n\
+            <{} bytes to transfer>\n\
+            \n",
+        Msg::SlowestDirectories => "Slowest directories\n",
+        Msg::LatencyByFilesystem => "\nRead latency by filesystem\n",
+        Msg::FilesystemLatency => "<{}> ({} directories, slowest {})",
+        Msg::Watching => "Watching \"{}\" (press Ctrl+C to stop)\n",
+        Msg::WatchReport => "[{}] <{} created> <{} modified> <{} deleted>\n",
+        Msg::WatchMore => "<{} more>",
+        Msg::HookExitError => "Hook \"{}\" for \"{}\" exited with {}\n",
+        Msg::HookRunError => "Error while running hook \"{}\" for \"{}\"\n{}\n",
+        Msg::CapabilitiesHeader => "Capabilities for \"{}\"\n",
+        Msg::CapabilitiesMissing => "Some capabilities are not available for \"{}\", so the scan continues without them\n",
+        Msg::CapModifiedTime => "modification times",
+        Msg::CapAccessTime => "access times",
+        Msg::CapChangeTime => "status change times",
+        Msg::CapPermissions => "permissions",
+        Msg::CapOwners => "owners and groups",
+        Msg::CapHashing => "hashing",
+        Msg::CapCacheBypass => "page cache bypass",
+        Msg::CapAvailable => "available",
+        Msg::CapAvailableWith => "available ({})",
+        Msg::CapUnsupportedPlatform => "not supported on this platform",
+        Msg::CapUnsupportedFilesystem => "not supported by the filesystem",
+        Msg::CapNotCompiled => "not compiled in",
+    };
+}
+
+/// Returns the German template of a message ([None] if it is not translated)
+///
+/// # Arguments
+///
+/// - `p_msg` - the message
+fn german(p_msg: Msg) -> Option<&'static str> {
+    return Some(match p_msg {
+        Msg::HelpHeader => "\n\
+            Dateisystem-Scanner (dumblebots.com)\n\
+            \n\
+            Verwendung: {} [PFAD...] [Optionen]\n\
+            Durchsucht das Dateisystem ab jedem PFAD.\n\
+            \n\
+            Beispiel: {} \"..\" --recursive --files\n\
+            \n\
+            Optionen:\n",
+        Msg::HelpExample => "Beispiel: {} {}\n",
+        Msg::SectionListing => "Auflisten:\n",
+        Msg::SectionEntryTypes => "Eintragsarten:\n",
+        Msg::SectionFilters => "Filtern:\n",
+        Msg::SectionContents => "Größen und Inhalte:\n",
+        Msg::SectionReports => "Berichte:\n",
+        Msg::SectionTransfer => "Übertragungen:\n",
+        Msg::SectionLayout => "Darstellung:\n",
+        Msg::SectionSearch => "Suchen:\n",
+        Msg::SectionWatch => "Beobachten:\n",
+        Msg::SectionRecording => "Aufzeichnen:\n",
+        Msg::SectionMisc => "Sonstiges:\n",
+        Msg::OptRecursive => "Verzeichnisse rekursiv durchsuchen (höchstens depth Ebenen tief, falls angegeben)\n",
+        Msg::OptMinDepth => "Nur Einträge zeigen, die mindestens depth Ebenen tief liegen (1 für die Einträge des durchsuchten Verzeichnisses)\n",
+        Msg::OptFollow => "In symbolische Links auf Verzeichnisse wie in die Verzeichnisse selbst absteigen (außer in solche, die zurückführen)\n",
+        Msg::OptPathsFrom => "Jeden der Pfade in file (oder in stdin, wenn es - ist) durchsuchen, einen pro Zeile oder durch NUL-Bytes getrennt\n",
+        Msg::OptMaxEntries => "Den Durchlauf beenden, sobald n Einträge gelesen wurden (die Ausgabe wird als abgeschnitten markiert)\n",
+        Msg::OptTimeout => "Den Durchlauf beenden, sobald time vergangen ist, etwa 30s oder 5m (die Ausgabe wird als unvollständig markiert)\n",
+        Msg::OptPermissions => "Berechtigungen jedes Eintrags ausgeben\n",
+        Msg::OptModificationTime => "Zeitpunkt der letzten Änderung jedes Eintrags ausgeben\n",
+        Msg::OptHumanReadable => "Größen in binären Einheiten (etwa 4.2 MiB) statt in Bytes ausgeben\n",
+        Msg::OptSi => "Größen in Potenzen von 1000 (etwa 1.3 GB) statt in Bytes ausgeben\n",
+        Msg::OptBinary => "Größen in Potenzen von 1024 (etwa 4.2 MiB) statt in Bytes ausgeben (wie --human-readable)\n",
+        Msg::OptBlockSize => "Größen als Anzahl der belegten Blöcke der Größe size ausgeben (etwa 1K, wie du)\n",
+        Msg::OptRelativeTime => "Änderungszeitpunkte als die seitdem vergangene Zeit (etwa vor 3 Tagen) statt des Datums ausgeben\n",
+        Msg::OptTimeFormat => "Änderungszeitpunkte im Format format ausgeben (ein strftime-Format, standardmäßig %b %d %Y  %H:%M)\n",
+        Msg::OptTimeZone => "Zeitpunkte in der Zeitzone zone ausgeben (utc, local, ein Versatz wie +05:30 oder ein Name wie Europe/Berlin)\n",
+        Msg::OptColumns => "Nur die Spalten aus list in deren Reihenfolge ausgeben (size, mtime, atime, ctime, perms, owner, hash, content oder name, durch Kommas getrennt)\n",
+        Msg::OptFiles => "Reguläre Dateien anzeigen (normalerweise ausgeblendet)\n",
+        Msg::OptSymlinks => "Symlinks anzeigen (normalerweise ausgeblendet)\n",
+        Msg::OptSpecial => "Spezialdateien wie Sockets, Pipes usw. anzeigen (normalerweise ausgeblendet)\n",
+        Msg::OptExt => "Nur Dateien anzeigen, deren Namen auf eine der durch Kommas getrennten Endungen in exts enden (ohne Beachtung der Groß- und Kleinschreibung)\n",
+        Msg::OptType => "Nur Einträge der durch Kommas getrennten Arten in types anzeigen (f für Dateien, d für Verzeichnisse, l für symbolische Links, s für Sockets, b für Blockgeräte, c für zeichenorientierte Geräte und p für Pipes)\n",
+        Msg::OptMinSize => "Nur reguläre Dateien mit mindestens size Bytes anzeigen (etwa 10M, mit K, M, G oder T)\n",
+        Msg::OptMaxSize => "Nur reguläre Dateien mit höchstens size Bytes anzeigen (etwa 1G, mit K, M, G oder T)\n",
+        Msg::OptEmptyFiles => "Nur leere reguläre Dateien anzeigen\n",
+        Msg::OptBrokenSymlinks => "Nur symbolische Links anzeigen, deren Ziele nicht existieren, zusammen mit den Pfaden, auf die sie zeigen\n",
+        Msg::OptOnlyText => "Nur reguläre Dateien mit Text anzeigen (ohne NUL-Byte in ihren ersten 8000 Bytes)\n",
+        Msg::OptOnlyBinary => "Nur reguläre Dateien mit Binärdaten anzeigen (mit einem NUL-Byte in ihren ersten 8000 Bytes)\n",
+        Msg::OptMime => "Nur reguläre Dateien anzeigen, deren anhand ihres Inhalts erkannter MIME-Typ einem der types entspricht (etwa image/* oder application/pdf, durch Kommas getrennt)\n",
+        Msg::OptAll => "Auch versteckte Einträge auflisten und exportieren (deren Namen mit einem Punkt beginnen)\n",
+        Msg::OptNoHidden => "Versteckte Einträge bei jedem Scan auslassen, auch bei Suchen\n",
+        Msg::OptExclude => "Zum Muster passende Einträge überspringen (wie eine Zeile von .fssignore, wiederholbar), ohne ausgeschlossene Verzeichnisse zu lesen\n",
+        Msg::OptExcludeFrom => "Zu den Mustern in der Datei passende Einträge überspringen (eines pro Zeile, wie --exclude)\n",
+        Msg::OptPruneCommon => "Verzeichnisse von Abhängigkeiten, Builds und Caches überspringen (node_modules, target, .venv, .git und __pycache__)\n",
+        Msg::OptPruneDir => "Verzeichnisse mit dem Namen name überall überspringen (wiederholbar, um --prune-common zu erweitern)\n",
+        Msg::OptRespectGitignore => "Von .gitignore-Dateien, .git/info/exclude und den globalen Ausschlüssen von git ignorierte Einträge überspringen\n",
+        Msg::OptNoIgnoreDot => ".ignore- und .fdignore-Dateien nicht beachten (.fssignore-Dateien werden weiterhin beachtet)\n",
+        Msg::OptSkipFs => "Die Einhängepunkte der durch Kommas getrennten Dateisystemtypen in types statt der Pseudo-Dateisysteme (wie proc, sysfs und devtmpfs) überspringen\n",
+        Msg::OptNoSkipFs => "Die Einhängepunkte von Pseudo-Dateisystemen nicht überspringen\n",
+        Msg::OptNewerThan => "Nur Einträge anzeigen, die zu oder nach when zuletzt geändert wurden (ein Datum wie 2024-01-01 oder ein Alter wie 30d)\n",
+        Msg::OptOlderThan => "Nur Einträge anzeigen, die vor when zuletzt geändert wurden (ein Datum wie 2024-01-01 oder ein Alter wie 30d)\n",
+        Msg::OptAccessedWithin => "Nur Einträge anzeigen, auf die zu oder nach when zuletzt zugegriffen wurde (ein Datum wie 2024-01-01 oder ein Alter wie 30d)\n",
+        Msg::OptStatusChangedWithin => "Nur Einträge anzeigen, deren Status sich zu oder nach when zuletzt geändert hat (ein Datum wie 2024-01-01 oder ein Alter wie 30d)\n",
+        Msg::OptOwner => "Nur Einträge anzeigen, die user gehören (ein Name oder eine ID)\n",
+        Msg::OptGroup => "Nur Einträge anzeigen, die der Gruppe group gehören (ein Name oder eine ID)\n",
+        Msg::OptPerm => "Nur Einträge anzeigen, deren Berechtigungen mode sind (wie 644 oder u=rw) oder alle (-mode) oder einige (/mode) seiner Bits enthalten\n",
+        Msg::OptDirSize => "Verzeichnisgrößen ausgeben (rekursiv als Summe der Größen aller enthaltenen Einträge berechnet)\n",
+        Msg::OptHash => "Hash des Inhalts jeder Datei ausgeben\n",
+        Msg::OptHashAlgo => "Mit algo hashen ({}, standardmäßig der schnellste verfügbare)\n",
+        Msg::OptHashCache => "In file gespeicherte Hashes für unveränderte Dateien wiederverwenden (und neue darin speichern)\n",
+        Msg::OptCheckpoint => "Hashes während der Berechnung in file festhalten, um einen abgebrochenen Lauf fortzusetzen\n",
+        Msg::OptDirsOnly => "Nur Verzeichnisse anzeigen (zusammen mit -d wie bei du nach Gesamtgröße sortiert)\n",
+        Msg::OptNameCollisions => "Gruppen von Dateien in verschiedenen Verzeichnissen mit demselben Namen melden\n",
+        Msg::OptSameSize => "Dateien mit gleichem Namen nur melden, wenn auch ihre Größe (und mit --hash ihr Hash) gleich ist\n",
+        Msg::OptHardlinks => "Dateien mit mehr als einem harten Link melden, gruppiert nach ihrem gemeinsamen Inode\n",
+        Msg::OptOldest => "Die count am längsten nicht geänderten Dateien samt ihrem Alter melden\n",
+        Msg::OptNewest => "Die count zuletzt geänderten Dateien samt ihrem Alter melden\n",
+        Msg::OptColdData => "Dateien, auf die lange nicht zugegriffen wurde, und ihre Größe pro Verzeichnis melden\n",
+        Msg::OptAtimeOlder => "Dateien, auf die seit age nicht zugegriffen wurde, als kalt betrachten (etwa 90d, standardmäßig 180d)\n",
+        Msg::OptTimestampAnomalies => "Dateien melden, die in der Zukunft, zur Epoche oder lange vor ihrer letzten Statusänderung geändert wurden\n",
+        Msg::OptCtimeGap => "Dateien, die mehr als age vor ihrer letzten Statusänderung geändert wurden, als verdächtig betrachten (standardmäßig 10y)\n",
+        Msg::OptEmptyDirs => "Verzeichnisse melden, die keine Einträge enthalten\n",
+        Msg::OptNestedEmpty => "Auch Verzeichnisse melden, die nur leere Verzeichnisse enthalten\n",
+        Msg::OptTreemap => "Die kumulierten Größen der Verzeichnisse als squarified Treemap in file zeichnen (ein SVG-Bild)\n",
+        Msg::OptFingerprint => "Kompakten Digest jedes Verzeichnisses ausgeben (um zu prüfen, ob Replikate synchron sind)\n",
+        Msg::OptTransferEstimate => "Bytes schätzen, die eine rsync-artige Synchronisation von PFAD nach dest übertragen würde (beide können Aufzeichnungen sein)\n",
+        Msg::OptBlockEstimate => "Geänderte Dateien blockweise vergleichen, statt ihre volle Größe zu zählen\n",
+        Msg::OptNoCachePollution => "Dateiinhalte lesen, ohne andere Daten aus dem Page-Cache zu verdrängen\n",
+        Msg::OptNoTree => "Absoluten Pfad jedes Eintrags (ohne Einrückung) statt der Baumform ausgeben\n",
+        Msg::OptIndent => "Jede Verschachtelungsebene um width Spalten einrücken (standardmäßig 4)\n",
+        Msg::OptIndentStyle => "Mit style einrücken (spaces, dots, bars oder tree, standardmäßig spaces)\n",
+        Msg::OptTree => "Jeden Eintrag wie bei tree mit Linien an sein Verzeichnis anbinden (wie --indent-style tree)\n",
+        Msg::OptEscape => "Steuerzeichen und andere nicht druckbare Zeichen in Namen als Escapes wie \\n oder \\033 ausgeben (wie ls -b)\n",
+        Msg::OptQuote => "Namen wie bei --escape maskieren und in doppelte Anführungszeichen setzen (wie ls -Q)\n",
+        Msg::OptColor => "Jeden Eintrag nach seiner Art mit den Farben aus LS_COLORS einfärben when (auto, always oder never, standardmäßig auto, nur auf einem Terminal)\n",
+        Msg::OptWidth => "Namen, die nicht in cols Spalten passen, mit Auslassungspunkten kürzen (standardmäßig die Breite des Terminals, 0 um sie nie zu kürzen)\n",
+        Msg::OptAgeHeatmap => "Jeden Eintrag nach der Zeit seit der letzten Änderung einfärben (rot für neu, blau für alt)\n",
+        Msg::OptSizeHeatmap => "Jeden Eintrag nach seiner Größe einfärben (rot für groß, blau für klein, Verzeichnisse nur mit -d)\n",
+        Msg::OptSplitOutput => "Den Bericht jedes Verzeichnisses direkt unter PATH in eine nach ihm benannte Datei in dir schreiben\n",
+        Msg::OptOutput => "Die Ausgabe in file schreiben, das erst nach Abschluss des Scans ersetzt wird (Fehler werden weiterhin angezeigt)\n",
+        Msg::OptAppend => "Die Ausgabe an die mit --output angegebene Datei anhängen, statt ihren Inhalt zu ersetzen\n",
+        Msg::OptCompress => "Die Ausgabe mit gzip komprimieren (geschieht bei Ausgabedateien mit der Endung .gz, .zst oder .zstd von selbst)\n",
+        Msg::OptCompressAlgo => "Die Ausgabe mit algo komprimieren (gzip oder zstd)\n",
+        Msg::OptPaging => "Die Ausgabe durch $PAGER (oder less -R) leiten when (auto, always oder never, standardmäßig never, auto nur auf einem Terminal)\n",
+        Msg::OptNamesOnly => "Nur den Pfad jedes Eintrags ausgeben, einen pro Zeile\n",
+        Msg::OptPrint0 => "Nur den Pfad jedes Eintrags ausgeben, jeweils gefolgt von einem NUL-Byte statt eines Zeilenumbruchs (für xargs -0)\n",
+        Msg::OptSizesOnly => "Nur die Größe (in Bytes) und den Pfad jedes Eintrags durch einen Tabulator getrennt ausgeben, wie du\n",
+        Msg::OptLsFormat => "Jeden Eintrag wie ls -l ausgeben (Modus, Links, Besitzer, Gruppe, Größe, Datum und Pfad)\n",
+        Msg::OptLong => "Die Einträge jedes Verzeichnisses wie ls -lR nach Namen auflisten, in je Verzeichnis ausgerichteten Spalten\n",
+        Msg::OptPrintf => "Jeden Eintrag als Zeile nach template ausgeben, mit {name}, {path}, {size}, {perms}, {mtime}, {type}, {depth} und {mime} ausgefüllt\n",
+        Msg::OptOrdered => "Die Einträge jedes Verzeichnisses nach Namen sortiert ausgeben (statt in der Lesereihenfolge)\n",
+        Msg::OptFormat => "Pfad, Typ, Größe, Berechtigungen, Änderungszeit und Tiefe jedes Eintrags in fmt ausgeben (json, ndjson, csv, tsv, markdown, mtree oder dot)\n",
+        Msg::OptExportSqlite => "Jeden Eintrag zusammen mit der ID seines Verzeichnisses in die Tabelle entries der SQLite-Datenbank file schreiben\n",
+        Msg::OptExportParquet => "Pfad, Größe, Änderungszeit, Typ und Besitzer jedes Eintrags in die Parquet-Datei file schreiben\n",
+        Msg::OptWalkThreads => "Die Unterverzeichnisse jedes Verzeichnisses vorab mit n Threads lesen\n",
+        Msg::OptWorkThreads => "Die ausgegebenen Dateien vorab mit n Threads hashen (getrennt von --walk-threads)\n",
+        Msg::OptSearch => "Nur Einträge anzeigen, deren Name vollständig phrase entspricht\n",
+        Msg::OptSearchNoext => "Nur Einträge anzeigen, deren Name (ohne Endung) vollständig phrase entspricht\n",
+        Msg::OptContains => "Nur Einträge anzeigen, deren Name phrase enthält\n",
+        Msg::OptGlob => "Nur Einträge anzeigen, die auf das Glob-Muster pattern passen (mit *, ?, [...], ** und {a,b}, mit einem Schrägstrich gegen den Pfad relativ zu PATH geprüft)\n",
+        Msg::OptFuzzy => "Nur Einträge anzeigen, deren Name die Zeichen von phrase in dieser Reihenfolge enthält, von der besten zur schlechtesten Übereinstimmung (ohne Beachtung der Groß- und Kleinschreibung, außer phrase enthält Großbuchstaben)\n",
+        Msg::OptPatternsFrom => "Nach den Mustern in file suchen, eines pro Zeile (wie mit --search geprüft, außer eine andere Suchoption wird angegeben)\n",
+        Msg::OptIgnoreCase => "Suchmuster ohne Beachtung der Groß- und Kleinschreibung anwenden (etwa Makefile und makefile)\n",
+        Msg::OptInvertMatch => "Nur Einträge anzeigen, die auf keines der Suchmuster passen (etwa Dateien, die gegen eine Namenskonvention verstoßen)\n",
+        Msg::OptFullPath => "Die Suchmuster mit dem Pfad jedes Eintrags relativ zu PATH statt mit seinem Namen vergleichen\n",
+        Msg::OptSearchTree => "Die Treffer unter den Verzeichnissen einrücken, in denen sie liegen (wie bei einem Scan), statt ihre Pfade auszugeben\n",
+        Msg::OptMaxResults => "Die Suche beenden, sobald n passende Einträge angezeigt wurden\n",
+        Msg::OptCountsByDir => "Zusammenfassen, wie viele der Treffer in jedem Verzeichnis liegen und jede Endung haben\n",
+        Msg::OptBfs => "In der Breite suchen und die Treffer jeder Ebene vor den tiefer liegenden anzeigen\n",
+        Msg::OptFindExpr => "Nur die Pfade der Einträge ausgeben, die ein find-Ausdruck auswählt (siehe unten)\n",
+        Msg::OptWatch => "PFAD auf Änderungen beobachten und sie melden (die Suchoptionen filtern die Änderungen)\n",
+        Msg::OptDebounce => "Änderungen erst melden, wenn ms Millisekunden lang keine weiteren Änderungen erfolgt sind\n",
+        Msg::OptOnCreate => "cmd für jeden erstellten Eintrag ausführen ({{}} wird durch seinen Pfad ersetzt)\n",
+        Msg::OptOnModify => "cmd für jeden geänderten Eintrag ausführen ({{}} wird durch seinen Pfad ersetzt)\n",
+        Msg::OptOnDelete => "cmd für jeden gelöschten Eintrag ausführen ({{}} wird durch seinen Pfad ersetzt)\n",
+        Msg::OptRecord => "Jeden beim Durchsuchen gelesenen Eintrag in file aufzeichnen\n",
+        Msg::OptReplay => "Den in file aufgezeichneten Durchlauf wiederholen, statt das Dateisystem zu lesen\n",
+        Msg::OptSnapshot => "Den Durchlauf wie --record als kompakten binären Schnappschuss mit allen Metadaten aufzeichnen\n",
+        Msg::OptShowErr => "Fehler anzeigen\n",
+        Msg::OptOnError => "Nicht lesbare Verzeichnisse überspringen, beim ersten abbrechen oder am Ende in einem Bericht sammeln (skip, abort oder collect)\n",
+        Msg::OptMeasureLatency => "Die Lesezeit jedes Verzeichnisses messen und die langsamsten melden\n",
+        Msg::OptProgress => "Den Fortschritt des Scans auf stderr anzeigen (mit einer Schätzung der Restzeit, wenn der Scan über eine frühere Aufzeichnung aufgezeichnet wird)\n",
+        Msg::OptProgressFrom => "Den Fortschritt des Scans anhand der Anzahl der Einträge in file (einer Aufzeichnung eines früheren Scans) schätzen\n",
+        Msg::OptPolicy => "Den von einer TOML-Richtlinie in file beschriebenen Scan ausführen (danach angegebene Optionen überschreiben die Richtlinie)\n",
+        Msg::OptLang => "Meldungen in lang anzeigen (en, de oder es, standardmäßig die Sprache der Locale)\n",
+        Msg::OptCapabilities => "Ausgeben, welche Spalten und Berichte von diesem Build, der Plattform und dem Dateisystem von PATH unterstützt werden\n",
+        Msg::OptHelp => "Gebrauchsanweisung ausgeben\n",
+        Msg::OptHelpFull => "Gebrauchsanweisung samt Beschreibung der Ausgabeformate ausgeben\n",
+        Msg::OptMan => "Eine Manpage (in roff) ausgeben\n",
+        Msg::EmptyOption => "Ignoriere unbekannte Option der Länge 0\n",
+        Msg::UnknownOption => "Ignoriere unbekannte Option {}\n",
+        Msg::UnknownLang => "Unbekannte Sprache \"{}\" (erwartet: en, de oder es)\n",
+        Msg::RecursionDepthZero => "Die maximale Rekursionstiefe muss größer als 0 sein!\n",
+        Msg::NotAnInteger => "\"{}\" konnte nicht in eine Ganzzahl umgewandelt werden\n",
+        Msg::NotAPositiveInteger => "\"{}\" ist keine positive Ganzzahl\n",
+        Msg::IgnoringRecursive => "Ignoriere die Option für rekursives Durchsuchen\n",
+        Msg::IgnoringDebounce => "Ignoriere die Debounce-Option\n",
+        Msg::IgnoringOption => "Ignoriere die Option {}\n",
+        Msg::OneSearchMode => "Es kann nur ein Suchmodus gleichzeitig gesetzt werden\n",
+        Msg::OneHeatmap => "Es kann nur eine Heatmap gleichzeitig gezeichnet werden\n",
+        Msg::OnePlainMode => "Es kann nur eine der Optionen --names-only, --sizes-only, --ls-format, --format, --export-sqlite, --export-parquet, --long, --printf und --print0 gleichzeitig gesetzt werden\n",
+        Msg::Terminating => "Beende...",
+        Msg::MissingSearchPattern => "Kein Suchmuster nach der Option {} angegeben\n",
+        Msg::MissingDestPath => "Kein Zielpfad nach der Option {} angegeben\n",
+        Msg::MissingSplitDir => "Kein Verzeichnis nach der Option {} angegeben\n",
+        Msg::MissingTreemapPath => "Keine Bilddatei nach der Option {} angegeben\n",
+        Msg::MissingPolicy => "Keine Richtlinie nach der Option {} angegeben\n",
+        Msg::MissingPatternsFile => "Keine Musterdatei nach der Option {} angegeben\n",
+        Msg::MissingPathsFile => "Keine Pfaddatei nach der Option {} angegeben\n",
+        Msg::MissingEntryCount => "Keine Anzahl von Einträgen nach der Option {} angegeben\n",
+        Msg::MissingTimeout => "Kein Zeitbudget nach der Option {} angegeben\n",
+        Msg::MissingOnError => "Keine Fehlerbehandlung nach der Option {} angegeben\n",
+        Msg::MissingHashAlgo => "Kein Hash-Algorithmus nach der Option {} angegeben\n",
+        Msg::MissingCompressAlgo => "Kein Kompressionsalgorithmus nach der Option {} angegeben\n",
+        Msg::MissingIndentWidth => "Keine Einrückungsbreite nach der Option {} angegeben\n",
+        Msg::MissingThreadCount => "Keine Anzahl von Threads nach der Option {} angegeben\n",
+        Msg::MissingIndentStyle => "Kein Einrückungsstil nach der Option {} angegeben\n",
+        Msg::MissingFormat => "Kein Ausgabeformat nach der Option {} angegeben\n",
+        Msg::MissingTemplate => "Keine Vorlage nach der Option {} angegeben\n",
+        Msg::MissingColumns => "Keine Spalten nach der Option {} angegeben\n",
+        Msg::MissingTimeFormat => "Kein Zeitformat nach der Option {} angegeben\n",
+        Msg::MissingBlockSize => "Keine Blockgröße nach der Option {} angegeben\n",
+        Msg::MissingWidth => "Keine Breite nach der Option {} angegeben\n",
+        Msg::MissingExtensions => "Keine Endungen nach der Option {} angegeben\n",
+        Msg::MissingMimeTypes => "Keine MIME-Typen nach der Option {} angegeben\n",
+        Msg::MissingFsTypes => "Keine Dateisystemtypen nach der Option {} angegeben\n",
+        Msg::MissingTypes => "Keine Arten nach der Option {} angegeben\n",
+        Msg::MissingSize => "Keine Größe nach der Option {} angegeben\n",
+        Msg::MissingTimeBound => "Kein Datum und kein Alter nach der Option {} angegeben\n",
+        Msg::MissingUser => "Kein Benutzer nach der Option {} angegeben\n",
+        Msg::MissingExcludePattern => "Kein Ausschlussmuster nach der Option {} angegeben\n",
+        Msg::MissingExcludeFile => "Keine Ausschlussdatei nach der Option {} angegeben\n",
+        Msg::MissingPruneDir => "Kein Verzeichnisname nach der Option {} angegeben\n",
+        Msg::MissingDepth => "Keine Tiefe nach der Option {} angegeben\n",
+        Msg::ExcludesReadError => "Fehler beim Lesen der Ausschlussdatei \"{}\"\n{}\n",
+        Msg::MissingGroup => "Keine Gruppe nach der Option {} angegeben\n",
+        Msg::MissingPerm => "Kein Modus nach der Option {} angegeben\n",
+        Msg::MissingPagingMode => "Kein Seitenmodus nach der Option {} angegeben\n",
+        Msg::MissingColorMode => "Kein Farbmodus nach der Option {} angegeben\n",
+        Msg::MissingTimeZone => "Keine Zeitzone nach der Option {} angegeben\n",
+        Msg::MissingDbPath => "Keine Datenbankdatei nach der Option {} angegeben\n",
+        Msg::MissingParquetPath => "Keine Parquet-Datei nach der Option {} angegeben\n",
+        Msg::MissingFindExpr => "Kein find-Ausdruck nach der Option {} angegeben\n",
+        Msg::MissingHashCache => "Keine Hash-Cache-Datei nach der Option {} angegeben\n",
+        Msg::MissingCheckpoint => "Keine Checkpoint-Datei nach der Option {} angegeben\n",
+        Msg::MissingDebounce => "Keine Debounce-Zeit nach der Option {} angegeben\n",
+        Msg::MissingFileCount => "Keine Anzahl von Dateien nach der Option {} angegeben\n",
+        Msg::MissingResultCount => "Keine Anzahl von Ergebnissen nach der Option {} angegeben\n",
+        Msg::MissingAge => "Kein Alter nach der Option {} angegeben\n",
+        Msg::InvalidAge => "\"{}\" konnte nicht in ein Alter umgewandelt werden (erwartet: eine Zahl gefolgt von s, m, h, d, w oder y)\n",
+        Msg::InvalidTimeout => "\"{}\" konnte nicht in ein Zeitbudget umgewandelt werden (erwartet: eine Anzahl von Sekunden, optional gefolgt von s, m, h, d, w oder y)\n",
+        Msg::MissingCommand => "Kein Befehl nach der Option {} angegeben\n",
+        Msg::MissingRecording => "Keine Aufzeichnungsdatei nach der Option {} angegeben\n",
+        Msg::MissingLang => "Keine Sprache nach der Option {} angegeben\n",
+        Msg::HashAlgoNotCompiled => "Der Hash-Algorithmus {} wurde nicht einkompiliert\n",
+        Msg::CompressAlgoNotCompiled => "Der Kompressionsalgorithmus {} wurde nicht einkompiliert\n",
+        Msg::ParquetNotCompiled => "Der Parquet-Export wurde nicht einkompiliert\n",
+        Msg::MimeNotCompiled => "Die Erkennung von MIME-Typen wurde nicht einkompiliert\n",
+        Msg::UnknownHashAlgo => "Unbekannter Hash-Algorithmus \"{}\" (erwartet: blake3, sha256 oder xxh3)\n",
+        Msg::UnknownCompressAlgo => "Unbekannter Kompressionsalgorithmus \"{}\" (erwartet: gzip oder zstd)\n",
+        Msg::UnknownOnError => "Unbekannte Fehlerbehandlung \"{}\" (erwartet: skip, abort oder collect)\n",
+        Msg::UnknownIndentStyle => "Unbekannter Einrückungsstil \"{}\" (erwartet: spaces, dots, bars oder tree)\n",
+        Msg::UnknownFormat => "Unbekanntes Ausgabeformat \"{}\" (erwartet: json, ndjson, csv, tsv, markdown, mtree oder dot)\n",
+        Msg::UnknownPlaceholder => "Unbekannter Platzhalter \"{}\" in der Vorlage (erwartet: {name}, {path}, {size}, {perms}, {mtime}, {type} oder {depth})\n",
+        Msg::UnknownColumn => "Unbekannte Spalte \"{}\" (erwartet: size, mtime, atime, ctime, perms, owner, hash, content oder name)\n",
+        Msg::InvalidTimeFormat => "Ungültiges Zeitformat \"{}\" (erwartet: ein strftime-Format wie %Y-%m-%d %H:%M)\n",
+        Msg::InvalidBlockSize => "\"{}\" konnte nicht in eine Blockgröße umgewandelt werden (erwartet: eine positive Zahl gefolgt von K, M, G oder T)\n",
+        Msg::InvalidWidth => "\"{}\" konnte nicht in eine Breite umgewandelt werden (erwartet: eine Anzahl von Spalten)\n",
+        Msg::InvalidExtensions => "Keine Endungen in \"{}\" (erwartet: eine durch Kommas getrennte Liste wie rs,toml,md)\n",
+        Msg::InvalidMimeTypes => "Keine MIME-Typen in \"{}\" (erwartet: eine durch Kommas getrennte Liste wie image/*,application/pdf)\n",
+        Msg::InvalidFsTypes => "Keine Dateisystemtypen in \"{}\" (erwartet: eine durch Kommas getrennte Liste wie proc,sysfs,nfs)\n",
+        Msg::UnknownType => "Unbekannte Art \"{}\" (erwartet: f, d, l, s, b, c oder p)\n",
+        Msg::InvalidSize => "\"{}\" konnte nicht in eine Größe umgewandelt werden (erwartet: eine Anzahl von Bytes, optional gefolgt von K, M, G oder T)\n",
+        Msg::InvalidTimeBound => "\"{}\" konnte nicht in ein Datum oder ein Alter umgewandelt werden (erwartet: ein Datum wie 2024-01-01 oder ein Alter wie 30d)\n",
+        Msg::UnknownUser => "Unbekannter Benutzer \"{}\" (erwartet: ein Benutzername oder eine ID)\n",
+        Msg::UnknownGroup => "Unbekannte Gruppe \"{}\" (erwartet: ein Gruppenname oder eine ID)\n",
+        Msg::InvalidPerm => "\"{}\" konnte nicht in einen Modus umgewandelt werden (erwartet: ein oktaler Modus wie 644 oder ein symbolischer wie u+w, optional mit - oder / am Anfang)\n",
+        Msg::UnknownPagingMode => "Unbekannter Seitenmodus \"{}\" (erwartet: auto, always oder never)\n",
+        Msg::UnknownColorMode => "Unbekannter Farbmodus \"{}\" (erwartet: auto, always oder never)\n",
+        Msg::UnknownTimeZone => "Unbekannte Zeitzone \"{}\" (erwartet: utc, local, ein Versatz wie +05:30 oder ein Name wie Europe/Berlin)\n",
+        Msg::FindUnknownPrimary => "Nicht unterstützter find-Ausdruck oder Operator {}\n",
+        Msg::FindMissingArgument => "Kein Argument nach dem find-Ausdruck {} angegeben\n",
+        Msg::FindInvalidArgument => "Ungültiges Argument \"{}\" für den find-Ausdruck {}\n",
+        Msg::FindSyntaxError => "Ungültiger find-Ausdruck (fehlender Operand, unpaarige Klammer oder nicht beendetes Anführungszeichen)\n",
+        Msg::HashCacheReadError => "Fehler beim Lesen des Hash-Caches \"{}\"\n{}\n",
+        Msg::PatternsReadError => "Fehler beim Lesen der Muster \"{}\"\n{}\n",
+        Msg::NoPatternsInFile => "Keine Muster in \"{}\"\n",
+        Msg::PathsReadError => "Fehler beim Lesen der Pfade \"{}\"\n{}\n",
+        Msg::NoPathsInFile => "Keine Pfade in \"{}\"\n",
+        Msg::IgnoreFileReadError => "Fehler beim Lesen der Ignorier-Datei \"{}\"\n{}\n",
+        Msg::HashCacheWriteError => "Fehler beim Schreiben des Hash-Caches\n{}\n",
+        Msg::CheckpointReadError => "Fehler beim Lesen des Checkpoints \"{}\"\n{}\n",
+        Msg::CheckpointRemoveError => "Fehler beim Entfernen des Checkpoints\n{}\n",
+        Msg::RecordReplayModes => {
+            "Scans können nur beim Auflisten oder Suchen aufgezeichnet und wiedergegeben werden\n"
+        }
+        Msg::RecordAndReplay => "Ein Scan kann nicht gleichzeitig aufgezeichnet und wiedergegeben werden\n",
+        Msg::AppendWithoutOutput => "Es kann nur an eine mit --output angegebene Ausgabedatei angehängt werden\n",
+        Msg::BlockSizeWithHumanReadable => "--block-size kann nicht zusammen mit --human-readable, --si oder --binary gesetzt werden\n",
+        Msg::BfsWithSearchTree => "--bfs kann nicht zusammen mit --search-tree gesetzt werden\n",
+        Msg::BfsWithoutSearch => "--bfs kann nur zusammen mit einer Suche (--search, --search-noext, --contains, --glob oder --fuzzy) gesetzt werden, die ihre Treffer als Liste ausgibt\n",
+        Msg::MaxEntriesWithWatch => "--max-entries kann nicht zusammen mit --watch gesetzt werden\n",
+        Msg::TimeoutWithWatch => "--timeout kann nicht zusammen mit --watch gesetzt werden\n",
+        Msg::OnErrorWithWatch => "Mit --watch kann nur --on-error skip gesetzt werden\n",
+        Msg::ScanAborted => "ABGEBROCHEN: der Durchlauf wurde bei \"{}\" beendet, das nicht gelesen werden konnte (--on-error abort)\n{}\n\n",
+        Msg::ErrorReportDirs => "Verzeichnisse, die nicht gelesen werden konnten\n",
+        Msg::ErrorReportDir => "\"{}\": {}\n",
+        Msg::ErrorReportKinds => "\nZusammenfassung der Fehler\n",
+        Msg::ErrorReportKind => "<{} {}>\n",
+        Msg::ErrorReportTotal => "<{} Fehler insgesamt>\n\n",
+        Msg::TraversalTimedOut => "UNVOLLSTÄNDIG: der Durchlauf wurde nach Ablauf seines Zeitbudgets von {} s beendet (--timeout), daher sind die Ausgabe und die Zusammenfassungen unvollständig\n\n",
+        Msg::EntriesTruncated => "ABGESCHNITTEN: der Durchlauf wurde nach {} Einträgen beendet (--max-entries), daher sind die Ausgabe und die Zusammenfassungen unvollständig\n\n",
+        Msg::MultiplePathsModes => "Mit --watch, --record, --replay, --policy, --split-output, --treemap, --format, --export-sqlite und --export-parquet kann nur ein einziger Pfad durchsucht werden\n",
+        Msg::PathHeader => "{}:\n",
+        Msg::CombinedScanSummary => "Zusammenfassung aller {} Pfade\n\
+            <{} Dateien>\n\
+            <{} Symlinks>\n\
+            <{} Spezialdateien>\n\
+            <{} Unterverzeichnisse>\n\
+            <{} Einträge insgesamt>\n\
+            \n",
+        Msg::CombinedSearchSummary => "Zusammenfassung der passenden Einträge in allen {} Pfaden\n\
+            <{} Dateien>\n\
+            <{} Symlinks>\n\
+            <{} Spezialdateien>\n\
+            <{} Unterverzeichnisse>\n\
+            <{} Einträge insgesamt>\n\
+            \n",
+        Msg::OutputWhileWatching => "Die Ausgabe kann beim Beobachten nicht in eine Datei geschrieben werden, da der Scan nie endet\n",
+        Msg::SplitOutputModes => "Die Ausgabe kann beim Beobachten, Aufzeichnen, Wiedergeben, Schätzen von Übertragungen, Befolgen einer Richtlinie oder Schreiben in eine Datei nicht aufgeteilt werden\n",
+        Msg::OnePolicy => "Es kann nur eine Richtlinie gleichzeitig befolgt werden\n",
+        Msg::PolicyReadError => "Fehler beim Lesen der Richtlinie \"{}\"\n{}\n",
+        Msg::PolicySyntaxError => "Zeile {} der Richtlinie \"{}\" konnte nicht gelesen werden\n",
+        Msg::PolicyUnknownKey => "Unbekannter Schlüssel {} in der Richtlinie \"{}\"\n",
+        Msg::PolicyInvalidValue => "Ungültiger Wert für den Schlüssel {} in der Richtlinie \"{}\"\n",
+        Msg::PolicyOptionNotAllowed => "Die Option --{} kann nicht durch die Richtlinie \"{}\" gesetzt werden (Richtlinien können nur die Optionen setzen, die die durchsuchten Einträge auswählen und filtern)\n",
+        Msg::PolicyOutputError => "Fehler beim Erstellen der Ausgabedatei \"{}\"\n{}\n",
+        Msg::MissingOutputPath => "Keine Ausgabedatei nach der Option {} angegeben\n",
+        Msg::OutputCreateError => "Fehler beim Erstellen der Ausgabedatei \"{}\"\n{}\n",
+        Msg::OutputWriteError => "Fehler beim Schreiben der Ausgabedatei \"{}\"\n{}\n",
+        Msg::CompressError => "Fehler beim Komprimieren der Ausgabe\n{}\n",
+        Msg::PagerError => "Fehler beim seitenweisen Anzeigen der Ausgabe\n{}\n",
+        Msg::PolicyBudgetsHeader => "\nBudgets der Verzeichnisse in \"{}\" (Größe und Budget)\n",
+        Msg::PolicyOverBudget => "{} <über dem Budget>\n",
+        Msg::PolicyRetentionHeader => "\nDateien in \"{}\", die länger als {} aufbewahrt werden\n",
+        Msg::PolicySummary => "\n<{} Verzeichnisse über dem Budget>\n<{} zu lange aufbewahrte Dateien>\n",
+        Msg::SplitDirCreateError => "Fehler beim Erstellen des Verzeichnisses \"{}\" für die Berichte\n{}\n",
+        Msg::TreemapWriteError => "Fehler beim Schreiben der Treemap in \"{}\"\n{}\n",
+        Msg::SqliteCreateError => "Fehler beim Erstellen der Datenbank \"{}\"\n{}\n",
+        Msg::SqliteWriteError => "Fehler beim Schreiben der Einträge in die Datenbank \"{}\"\n{}\n",
+        Msg::ParquetCreateError => "Fehler beim Erstellen der Parquet-Datei \"{}\"\n{}\n",
+        Msg::ParquetWriteError => "Fehler beim Schreiben der Einträge in die Parquet-Datei \"{}\"\n{}\n",
+        Msg::SplitExeError => "Der Pfad von fss zum Scannen der Unterverzeichnisse wurde nicht gefunden\n",
+        Msg::ReportCreateError => "Fehler beim Erstellen des Berichts \"{}\"\n{}\n",
+        Msg::ReportRunError => "Fehler beim Scannen von \"{}\" für seinen Bericht\n{}\n",
+        Msg::ReportWritten => "Bericht von \"{}\" nach \"{}\" geschrieben\n",
+        Msg::SplitOutputSummary => "\n<{} Berichte nach \"{}\" geschrieben>\n",
+        Msg::RecordingReadError => "Fehler beim Lesen der Aufzeichnung \"{}\"\n{}\n",
+        Msg::RecordingCreateError => "Fehler beim Erstellen der Aufzeichnung \"{}\"\n{}\n",
+        Msg::RecordingWriteError => "Fehler beim Schreiben der Aufzeichnung \"{}\"\n{}\n",
+        Msg::IterateError => "Fehler beim Durchlaufen von \"{}\"\n{}\n",
+        Msg::MetadataError => "Fehler beim Lesen der Metadaten von \"{}\"\n{}\n",
+        Msg::ModifiedTimeError => "Fehler beim Lesen der Änderungszeit von \"{}\"\n",
+        Msg::AccessTimeError => "Fehler beim Lesen der letzten Zugriffszeit von \"{}\"\n{}\n",
+        Msg::AtimeUnreliable => "Auf \"{}\" werden keine Zugriffszeiten gespeichert (mit noatime eingehängt), daher ist der Bericht nicht verlässlich\n",
+        Msg::SymlinkTargetError => "Fehler beim Lesen des Ziels des Symlinks \"{}\"\n{}\n",
+        Msg::DirSizeTraverseError => {
+            "Fehler beim Durchlaufen von {} während der Größenberechnung des Verzeichnisses {}\n{}\n"
+        }
+        Msg::DirSizeEntryError => {
+            "Fehler beim Lesen eines Eintrags von {} während der Größenberechnung des Verzeichnisses {}\n{}\n"
+        }
+        Msg::HashError => "Fehler beim Berechnen des Hashes von \"{}\"\n{}\n",
+        Msg::HashProgress => "\rBerechne Hash von \"{}\" ({}%)",
+        Msg::ScanProgress => "\r{} Einträge gelesen",
+        Msg::ScanProgressEstimate => "\r[{}{}] {}% ({} von etwa {} Einträgen, noch {})",
+        Msg::ScanSummary => "\n\
+            Zusammenfassung von \"{}\"\n\
+            <{} Dateien>\n\
+            <{} Symlinks>\n\
+            <{} Spezialdateien>\n\
+            <{} Unterverzeichnisse>\n\
+            <{} Einträge insgesamt>\n\
+            \n",
+        Msg::ScanSummaryRecursive => "Einschließlich Unterverzeichnissen\n\
+            <{} Dateien>\n\
+            <{} Symlinks>\n\
+            <{} Spezialdateien>\n\
+            <{} Unterverzeichnisse>\n\
+            <{} Einträge insgesamt>\n\
+            \n",
+        Msg::SearchSummaryMatches => "\n\
+            Zusammenfassung der passenden Einträge\n\
+            <{} Dateien>\n\
+            <{} Symlinks>\n\
+            <{} Spezialdateien>\n\
+            <{} Unterverzeichnisse>\n\
+            <{} Einträge insgesamt>\n\
+            \n",
+        Msg::SearchSummaryPatterns => "Zusammenfassung der Treffer jedes Musters\n",
+        Msg::SearchSummaryPattern => "<{} Einträge passend zu \"{}\">\n",
+        Msg::SearchStopped => "Die Suche wurde nach {} Treffern beendet, daher wurden nicht alle Einträge durchlaufen\n\n",
+        Msg::SearchSummaryDirs => "Zusammenfassung der Treffer in jedem Verzeichnis\n",
+        Msg::SearchSummaryDir => "<{} Einträge in \"{}\">\n",
+        Msg::SearchSummaryExts => "Zusammenfassung der Treffer mit jeder Endung\n",
+        Msg::SearchSummaryExt => "<{} Einträge mit der Endung \".{}\">\n",
+        Msg::SearchSummaryNoExt => "<{} Einträge ohne Endung>\n",
+        Msg::FilesystemLoops => "{} Verzeichnisse oder verfolgte symbolische Links übersprungen, die auf ein darüberliegendes Verzeichnis zurückführen\n",
+        Msg::FilterSummary => "Herausgefiltert\n\
+            <{} Einträge>\n\
+            \n",
+        Msg::SearchSummaryTraversal => "Zusammenfassung des Durchlaufs von \"{}\"\n\
+            <{} Dateien>\n\
+            <{} Symlinks>\n\
+            <{} Spezialdateien>\n\
+            <{} Unterverzeichnisse>\n\
+            <{} Einträge insgesamt>\n\
+            \n",
+        Msg::DirsOnlyTotal => "\n\
+            Gesamtgröße von \"{}\"\n\
+            <{} Bytes>\n\
+            \n",
+        Msg::TreemapSummary => "Treemap von \"{}\" in \"{}\" geschrieben\n\
+            <{} Verzeichnisse>\n\
+            <{} Bytes>\n\
+            \n",
+        Msg::SqliteSummary => "Einträge in die Datenbank \"{}\" geschrieben\n\
+            <{} Einträge>\n\
+            \n",
+        Msg::ParquetSummary => "Einträge in die Parquet-Datei \"{}\" geschrieben\n\
+            <{} Einträge>\n\
+            \n",
+        Msg::NameCollisionSummary => "Zusammenfassung der Namenskollisionen in \"{}\"\n\
+            <{} mehrfach vorkommende Namen>\n\
+            <{} Dateien>\n\
+            \n",
+        Msg::OldestFiles => "Älteste Dateien in \"{}\"\n",
+        Msg::NewestFiles => "Neueste Dateien in \"{}\"\n",
+        Msg::ColdDataHeader => "Dateien in \"{}\", auf die seit {} nicht zugegriffen wurde\n",
+        Msg::ColdDir => "{} <{} kalte Dateien>\n",
+        Msg::ColdDataSummary => "\n\
+            Zusammenfassung der kalten Daten\n\
+            <{} kalte Dateien>\n\
+            <{} Bytes kalte Daten>\n\
+            <{} Bytes insgesamt>\n\
+            \n",
+        Msg::TimestampAnomaliesHeader => "Dateien in \"{}\" mit verdächtigen Zeitstempeln\n",
+        Msg::TimestampAnomaliesSummary => "\n\
+            Zusammenfassung der Zeitstempel-Anomalien\n\
+            <{} Dateien mit verdächtigen Zeitstempeln>\n\
+            \n",
+        Msg::EmptyDirsHeader => "Leere Verzeichnisse in \"{}\"\n",
+        Msg::EmptyDirsSummary => "\n\
+            Zusammenfassung der leeren Verzeichnisse\n\
+            <{} leere Verzeichnisse>\n\
+            \n",
+        Msg::HardlinksHeader => "Dateien in \"{}\" mit mehr als einem harten Link\n",
+        Msg::HardlinkGroup => "\nInode {} <{} Links>\n",
+        Msg::HardlinksSummary => "\n\
+            Zusammenfassung der harten Links\n\
+            <{} gemeinsame Inodes>\n\
+            <{} Dateien>\n\
+            <{} Bytes durch die Links gespart>\n\
+            \n",
+        Msg::AnomalyFuture => "in der Zukunft geändert",
+        Msg::AnomalyEpoch => "zur Epoche geändert",
+        Msg::AnomalyAfterCtime => "nach der letzten Statusänderung geändert",
+        Msg::AnomalyCtimeGap => "mehr als {} vor der letzten Statusänderung geändert",
+        Msg::TimeJustNow => "gerade eben",
+        Msg::TimeFuture => "in der Zukunft",
+        Msg::TimeMinuteAgo => "vor 1 Minute",
+        Msg::TimeMinutesAgo => "vor {} Minuten",
+        Msg::TimeHourAgo => "vor 1 Stunde",
+        Msg::TimeHoursAgo => "vor {} Stunden",
+        Msg::TimeDayAgo => "vor 1 Tag",
+        Msg::TimeDaysAgo => "vor {} Tagen",
+        Msg::TimeMonthAgo => "vor 1 Monat",
+        Msg::TimeMonthsAgo => "vor {} Monaten",
+        Msg::TimeYearAgo => "vor 1 Jahr",
+        Msg::TimeYearsAgo => "vor {} Jahren",
+        Msg::TransferSummary => "\n\
+            Geschätzte Übertragung von \"{}\" nach \"{}\"\n\
+            <{} neue Dateien ({} Bytes)>\n\
+            <{} geänderte Dateien ({} Bytes)>\n\
+            <{} unveränderte Dateien>\n\
+            <{} zu übertragende Bytes>\n\
+            \n",
+        Msg::SlowestDirectories => "Langsamste Verzeichnisse\n",
+        Msg::LatencyByFilesystem => "\nLeselatenz nach Dateisystem\n",
+        Msg::FilesystemLatency => "<{}> ({} Verzeichnisse, langsamstes {})",
+        Msg::Watching => "Überwache \"{}\" (Strg+C zum Beenden)\n",
+        Msg::WatchReport => "[{}] <{} erstellt> <{} geändert> <{} gelöscht>\n",
+        Msg::WatchMore => "<{} weitere>",
+        Msg::HookExitError => "Hook \"{}\" für \"{}\" wurde beendet mit {}\n",
+        Msg::HookRunError => "Fehler beim Ausführen des Hooks \"{}\" für \"{}\"\n{}\n",
+        Msg::CapabilitiesHeader => "Fähigkeiten für \"{}\"\n",
+        Msg::CapabilitiesMissing => "Einige Fähigkeiten sind für \"{}\" nicht verfügbar, daher wird der Scan ohne sie fortgesetzt\n",
+        Msg::CapModifiedTime => "Änderungszeiten",
+        Msg::CapAccessTime => "Zugriffszeiten",
+        Msg::CapChangeTime => "Statusänderungszeiten",
+        Msg::CapPermissions => "Berechtigungen",
+        Msg::CapOwners => "Besitzer und Gruppen",
+        Msg::CapHashing => "Hashen",
+        Msg::CapCacheBypass => "Umgehen des Page-Caches",
+        Msg::CapAvailable => "verfügbar",
+        Msg::CapAvailableWith => "verfügbar ({})",
+        Msg::CapUnsupportedPlatform => "auf dieser Plattform nicht unterstützt",
+        Msg::CapUnsupportedFilesystem => "vom Dateisystem nicht unterstützt",
+        Msg::CapNotCompiled => "nicht einkompiliert",
+        // messages added later are shown in English until they are translated
+        #[allow(unreachable_patterns)]
+        _ => return None,
+    });
+}
+
+/// Returns the Spanish template of a message ([None] if it is not translated)
+///
+/// # Arguments
+///
+/// - `p_msg` - the message
+fn spanish(p_msg: Msg) -> Option<&'static str> {
+    return Some(match p_msg {
+        Msg::HelpHeader => "\n\
+            Escáner del sistema de archivos (dumblebots.com)\n\
+            \n\
+            Uso: {} [RUTA...] [opciones]\n\
+            Recorre el sistema de archivos a partir de cada RUTA.\n\
+            \n\
+            Ejemplo: {} \"..\" --recursive --files\n\
+            \n\
+            Opciones:\n",
+        Msg::HelpExample => "Ejemplo: {} {}\n",
+        Msg::SectionListing => "Listado:\n",
+        Msg::SectionEntryTypes => "Tipos de entrada:\n",
+        Msg::SectionFilters => "Filtrado:\n",
+        Msg::SectionContents => "Tamaños y contenidos:\n",
+        Msg::SectionReports => "Informes:\n",
+        Msg::SectionTransfer => "Transferencias:\n",
+        Msg::SectionLayout => "Presentación:\n",
+        Msg::SectionSearch => "Búsqueda:\n",
+        Msg::SectionWatch => "Vigilancia:\n",
+        Msg::SectionRecording => "Grabación:\n",
+        Msg::SectionMisc => "Otros:\n",
+        Msg::OptRecursive => "Recorrer directorios recursivamente (hasta depth niveles si se indica)\n",
+        Msg::OptMinDepth => "Mostrar solo las entradas que están al menos a depth niveles de profundidad (1 para las entradas del directorio escaneado)\n",
+        Msg::OptFollow => "Descender a los enlaces simbólicos a directorios como a los propios directorios (salvo a los que forman un bucle)\n",
+        Msg::OptPathsFrom => "Escanear cada una de las rutas de file (o de stdin si es -), una por línea o separadas por bytes NUL\n",
+        Msg::OptMaxEntries => "Detener el recorrido en cuanto se hayan leído n entradas (la salida se marca como truncada)\n",
+        Msg::OptTimeout => "Detener el recorrido en cuanto haya pasado time, como 30s o 5m (la salida se marca como incompleta)\n",
+        Msg::OptPermissions => "Mostrar los permisos de cada entrada\n",
+        Msg::OptModificationTime => "Mostrar la hora de la última modificación de cada entrada\n",
+        Msg::OptHumanReadable => "Mostrar los tamaños en unidades binarias (como 4.2 MiB) en lugar de bytes\n",
+        Msg::OptSi => "Mostrar los tamaños en potencias de 1000 (como 1.3 GB) en lugar de bytes\n",
+        Msg::OptBinary => "Mostrar los tamaños en potencias de 1024 (como 4.2 MiB) en lugar de bytes (igual que --human-readable)\n",
+        Msg::OptBlockSize => "Mostrar los tamaños como el número de bloques de tamaño size que ocupan (como 1K, como du)\n",
+        Msg::OptRelativeTime => "Mostrar las fechas de modificación como el tiempo transcurrido desde entonces (como hace 3 días) en lugar de la fecha\n",
+        Msg::OptTimeFormat => "Mostrar las fechas de modificación en format (un formato de strftime, por defecto %b %d %Y  %H:%M)\n",
+        Msg::OptTimeZone => "Mostrar las fechas en la zona zone (utc, local, un desfase como +05:30 o un nombre como Europe/Berlin)\n",
+        Msg::OptColumns => "Mostrar solo las columnas de list, en su orden (size, mtime, atime, ctime, perms, owner, hash, content o name, separadas por comas)\n",
+        Msg::OptFiles => "Mostrar archivos regulares (ocultos normalmente)\n",
+        Msg::OptSymlinks => "Mostrar enlaces simbólicos (ocultos normalmente)\n",
+        Msg::OptSpecial => "Mostrar archivos especiales como sockets, tuberías, etc. (ocultos normalmente)\n",
+        Msg::OptExt => "Mostrar solo los archivos cuyo nombre termina en una de las extensiones separadas por comas de exts (sin distinguir mayúsculas)\n",
+        Msg::OptType => "Mostrar solo las entradas de los tipos separados por comas de types (f para archivos, d para directorios, l para enlaces simbólicos, s para sockets, b para dispositivos de bloques, c para dispositivos de caracteres y p para tuberías)\n",
+        Msg::OptMinSize => "Mostrar solo los archivos regulares de al menos size bytes (como 10M, con K, M, G o T)\n",
+        Msg::OptMaxSize => "Mostrar solo los archivos regulares de como mucho size bytes (como 1G, con K, M, G o T)\n",
+        Msg::OptEmptyFiles => "Mostrar solo los archivos regulares vacíos\n",
+        Msg::OptBrokenSymlinks => "Mostrar solo los enlaces simbólicos cuyos destinos no existen, junto con las rutas a las que apuntan\n",
+        Msg::OptOnlyText => "Mostrar solo los archivos regulares que contienen texto (sin un byte NUL en sus primeros 8000 bytes)\n",
+        Msg::OptOnlyBinary => "Mostrar solo los archivos regulares que contienen datos binarios (con un byte NUL en sus primeros 8000 bytes)\n",
+        Msg::OptMime => "Mostrar solo los archivos regulares cuyo tipo MIME, detectado a partir de su contenido, coincide con uno de types (como image/* o application/pdf, separados por comas)\n",
+        Msg::OptAll => "Listar y exportar también las entradas ocultas (cuyos nombres empiezan con un punto)\n",
+        Msg::OptNoHidden => "Omitir las entradas ocultas en todos los escaneos, incluidas las búsquedas\n",
+        Msg::OptExclude => "Omitir las entradas que coinciden con el patrón (como una línea de .fssignore, se puede repetir) sin leer los directorios excluidos\n",
+        Msg::OptExcludeFrom => "Omitir las entradas que coinciden con los patrones del archivo (uno por línea, como --exclude)\n",
+        Msg::OptPruneCommon => "Omitir los directorios de dependencias, compilaciones y cachés (node_modules, target, .venv, .git y __pycache__)\n",
+        Msg::OptPruneDir => "Omitir los directorios llamados name dondequiera que estén (se puede repetir para ampliar --prune-common)\n",
+        Msg::OptRespectGitignore => "Omitir las entradas ignoradas por los archivos .gitignore, .git/info/exclude y las exclusiones globales de git\n",
+        Msg::OptNoIgnoreDot => "No respetar los archivos .ignore y .fdignore (los archivos .fssignore se siguen respetando)\n",
+        Msg::OptSkipFs => "Omitir los puntos de montaje de los tipos de sistema de archivos separados por comas de types en lugar de los pseudo sistemas (como proc, sysfs y devtmpfs)\n",
+        Msg::OptNoSkipFs => "No omitir los puntos de montaje de los pseudo sistemas de archivos\n",
+        Msg::OptNewerThan => "Mostrar solo las entradas modificadas por última vez en o después de when (una fecha como 2024-01-01 o una antigüedad como 30d)\n",
+        Msg::OptOlderThan => "Mostrar solo las entradas modificadas por última vez antes de when (una fecha como 2024-01-01 o una antigüedad como 30d)\n",
+        Msg::OptAccessedWithin => "Mostrar solo las entradas accedidas por última vez en o después de when (una fecha como 2024-01-01 o una antigüedad como 30d)\n",
+        Msg::OptStatusChangedWithin => "Mostrar solo las entradas cuyo estado cambió por última vez en o después de when (una fecha como 2024-01-01 o una antigüedad como 30d)\n",
+        Msg::OptOwner => "Mostrar solo las entradas cuyo propietario es user (un nombre o un ID)\n",
+        Msg::OptGroup => "Mostrar solo las entradas cuyo grupo es group (un nombre o un ID)\n",
+        Msg::OptPerm => "Mostrar solo las entradas cuyos permisos son mode (como 644 o u=rw), o tienen todos (-mode) o alguno (/mode) de sus bits\n",
+        Msg::OptDirSize => "Mostrar el tamaño de los directorios (suma recursiva de los tamaños de todas sus entradas)\n",
+        Msg::OptHash => "Mostrar el hash del contenido de cada archivo\n",
+        Msg::OptHashAlgo => "Calcular hashes con algo ({}, por defecto el más rápido disponible)\n",
+        Msg::OptHashCache => "Reutilizar los hashes guardados en file para archivos sin cambios (y guardar ahí los nuevos)\n",
+        Msg::OptCheckpoint => "Registrar los hashes en file a medida que se calculan, para reanudar una ejecución interrumpida\n",
+        Msg::OptDirsOnly => "Mostrar solo directorios (ordenados por tamaño acumulado junto con -d, como du)\n",
+        Msg::OptNameCollisions => "Informar de grupos de archivos en distintos directorios que comparten el mismo nombre\n",
+        Msg::OptSameSize => "Informar de archivos con el mismo nombre solo si también tienen el mismo tamaño (y hash con --hash)\n",
+        Msg::OptHardlinks => "Informar de los archivos con más de un enlace duro, agrupados por el inodo que comparten\n",
+        Msg::OptOldest => "Informar de los count archivos modificados hace más tiempo junto con su antigüedad\n",
+        Msg::OptNewest => "Informar de los count archivos modificados más recientemente junto con su antigüedad\n",
+        Msg::OptColdData => "Informar de los archivos a los que no se ha accedido en mucho tiempo y de su tamaño por directorio\n",
+        Msg::OptAtimeOlder => "Considerar fríos los archivos a los que no se ha accedido en age (por ejemplo 90d, por defecto 180d)\n",
+        Msg::OptTimestampAnomalies => "Informar de archivos modificados en el futuro, en la época o mucho antes de su último cambio de estado\n",
+        Msg::OptCtimeGap => "Considerar sospechosos los archivos modificados más de age antes de su último cambio de estado (por defecto 10y)\n",
+        Msg::OptEmptyDirs => "Informar de los directorios que no contienen ninguna entrada\n",
+        Msg::OptNestedEmpty => "Informar también de los directorios que solo contienen directorios vacíos\n",
+        Msg::OptTreemap => "Dibujar los tamaños acumulados de los directorios como un treemap cuadrado en file (una imagen SVG)\n",
+        Msg::OptFingerprint => "Mostrar un resumen compacto de cada directorio (para comprobar si las réplicas están sincronizadas)\n",
+        Msg::OptTransferEstimate => "Estimar los bytes que transferiría una sincronización tipo rsync de RUTA a dest (ambas pueden ser grabaciones)\n",
+        Msg::OptBlockEstimate => "Comparar los archivos modificados bloque a bloque en lugar de contar su tamaño completo\n",
+        Msg::OptNoCachePollution => "Leer el contenido de los archivos sin expulsar otros datos de la caché de páginas\n",
+        Msg::OptNoTree => "Mostrar la ruta absoluta de cada entrada (sin sangría) en lugar del árbol\n",
+        Msg::OptIndent => "Sangrar cada nivel de anidamiento width columnas (por defecto 4)\n",
+        Msg::OptIndentStyle => "Sangrar con style (spaces, dots, bars o tree, por defecto spaces)\n",
+        Msg::OptTree => "Unir cada entrada a su directorio con líneas como tree (igual que --indent-style tree)\n",
+        Msg::OptEscape => "Mostrar los caracteres de control y otros no imprimibles de los nombres como escapes como \\n o \\033 (como ls -b)\n",
+        Msg::OptQuote => "Escapar los nombres como --escape y encerrarlos entre comillas dobles (como ls -Q)\n",
+        Msg::OptColor => "Colorear cada entrada según su tipo con los colores de LS_COLORS when (auto, always o never, por defecto auto, solo en una terminal)\n",
+        Msg::OptWidth => "Acortar con puntos suspensivos los nombres que no caben en cols columnas (por defecto el ancho de la terminal, 0 para no acortarlos nunca)\n",
+        Msg::OptAgeHeatmap => "Colorear cada entrada según el tiempo desde su última modificación (rojo si es reciente, azul si es antigua)\n",
+        Msg::OptSizeHeatmap => "Colorear cada entrada según su tamaño (rojo si es grande, azul si es pequeña, directorios solo con -d)\n",
+        Msg::OptSplitOutput => "Escribir el informe de cada directorio situado directamente bajo PATH en un archivo con su nombre dentro de dir\n",
+        Msg::OptOutput => "Escribir la salida en file, que solo se reemplaza cuando termina el escaneo (los errores se siguen mostrando)\n",
+        Msg::OptAppend => "Añadir la salida al archivo indicado con --output en lugar de reemplazar su contenido\n",
+        Msg::OptCompress => "Comprimir la salida con gzip (se hace por sí solo para archivos de salida terminados en .gz, .zst o .zstd)\n",
+        Msg::OptCompressAlgo => "Comprimir la salida con algo (gzip o zstd)\n",
+        Msg::OptPaging => "Pasar la salida por $PAGER (o less -R) when (auto, always o never, por defecto never, auto solo en una terminal)\n",
+        Msg::OptNamesOnly => "Mostrar solo la ruta de cada entrada, una por línea\n",
+        Msg::OptPrint0 => "Mostrar solo la ruta de cada entrada, seguida de un byte NUL en lugar de un salto de línea (para xargs -0)\n",
+        Msg::OptSizesOnly => "Mostrar solo el tamaño (en bytes) y la ruta de cada entrada separados por un tabulador, como du\n",
+        Msg::OptLsFormat => "Mostrar cada entrada como ls -l (modo, enlaces, propietario, grupo, tamaño, fecha y ruta)\n",
+        Msg::OptLong => "Listar las entradas de cada directorio por nombre como ls -lR, en columnas alineadas por directorio\n",
+        Msg::OptPrintf => "Mostrar cada entrada en una línea según template, con {name}, {path}, {size}, {perms}, {mtime}, {type}, {depth} y {mime} completados\n",
+        Msg::OptOrdered => "Mostrar las entradas de cada directorio ordenadas por nombre (en lugar del orden de lectura)\n",
+        Msg::OptFormat => "Mostrar la ruta, el tipo, el tamaño, los permisos, la hora de modificación y la profundidad de cada entrada en fmt (json, ndjson, csv, tsv, markdown, mtree o dot)\n",
+        Msg::OptExportSqlite => "Escribir cada entrada junto con el id de su directorio en la tabla entries de la base de datos SQLite file\n",
+        Msg::OptExportParquet => "Escribir la ruta, el tamaño, la fecha de modificación, el tipo y el propietario de cada entrada en el archivo Parquet file\n",
+        Msg::OptWalkThreads => "Leer por adelantado los subdirectorios de cada directorio con n hilos\n",
+        Msg::OptWorkThreads => "Calcular por adelantado el hash de los archivos mostrados con n hilos (aparte de --walk-threads)\n",
+        Msg::OptSearch => "Mostrar solo las entradas cuyo nombre coincide completamente con phrase\n",
+        Msg::OptSearchNoext => "Mostrar solo las entradas cuyo nombre (sin la extensión) coincide completamente con phrase\n",
+        Msg::OptContains => "Mostrar solo las entradas cuyo nombre contiene phrase\n",
+        Msg::OptGlob => "Mostrar solo las entradas que coinciden con el patrón glob pattern (con *, ?, [...], ** y {a,b}, comparado con la ruta relativa a PATH si tiene una barra)\n",
+        Msg::OptFuzzy => "Mostrar solo las entradas cuyo nombre contiene los caracteres de phrase en orden, de la mejor coincidencia a la peor (sin distinguir mayúsculas salvo que phrase tenga mayúsculas)\n",
+        Msg::OptPatternsFrom => "Buscar los patrones de file, uno por línea (comparados como con --search salvo que se indique otra opción de búsqueda)\n",
+        Msg::OptIgnoreCase => "Aplicar los patrones de búsqueda sin distinguir mayúsculas de minúsculas (como Makefile y makefile)\n",
+        Msg::OptInvertMatch => "Mostrar solo las entradas que no coinciden con ninguno de los patrones de búsqueda (como archivos que no siguen una convención de nombres)\n",
+        Msg::OptFullPath => "Comparar los patrones de búsqueda con la ruta de cada entrada relativa a PATH en lugar de con su nombre\n",
+        Msg::OptSearchTree => "Sangrar las coincidencias bajo los directorios en los que se encuentran (como en un escaneo) en lugar de mostrar sus rutas\n",
+        Msg::OptMaxResults => "Detener la búsqueda en cuanto se hayan mostrado n entradas coincidentes\n",
+        Msg::OptCountsByDir => "Resumir cuántas de las coincidencias se encuentran en cada directorio y tienen cada extensión\n",
+        Msg::OptBfs => "Buscar en anchura, mostrando las coincidencias de cada nivel antes que las más profundas\n",
+        Msg::OptFindExpr => "Mostrar solo las rutas de las entradas seleccionadas por una expresión de find (ver abajo)\n",
+        Msg::OptWatch => "Vigilar los cambios en RUTA e informar de ellos (las opciones de búsqueda filtran los cambios)\n",
+        Msg::OptDebounce => "Informar de los cambios solo cuando no haya habido más cambios durante ms milisegundos\n",
+        Msg::OptOnCreate => "Ejecutar cmd para cada entrada creada (sustituyendo {{}} por su ruta)\n",
+        Msg::OptOnModify => "Ejecutar cmd para cada entrada modificada (sustituyendo {{}} por su ruta)\n",
+        Msg::OptOnDelete => "Ejecutar cmd para cada entrada eliminada (sustituyendo {{}} por su ruta)\n",
+        Msg::OptRecord => "Grabar en file cada entrada leída durante el recorrido\n",
+        Msg::OptReplay => "Repetir el recorrido grabado en file en lugar de leer el sistema de archivos\n",
+        Msg::OptSnapshot => "Grabar el recorrido como --record, en una instantánea binaria compacta con todos los metadatos\n",
+        Msg::OptShowErr => "Mostrar errores\n",
+        Msg::OptOnError => "Omitir los directorios que no se pueden leer, abortar en el primero o reunirlos en un informe al final (skip, abort o collect)\n",
+        Msg::OptMeasureLatency => "Medir el tiempo de lectura de cada directorio e informar de los más lentos\n",
+        Msg::OptProgress => "Mostrar el progreso del escaneo en stderr (con una estimación del tiempo restante si se graba sobre una grabación anterior)\n",
+        Msg::OptProgressFrom => "Estimar el progreso del escaneo a partir del número de entradas de file (una grabación de un escaneo anterior)\n",
+        Msg::OptPolicy => "Ejecutar el escaneo descrito por una política TOML en file (las opciones indicadas después la sobrescriben)\n",
+        Msg::OptLang => "Mostrar los mensajes en lang (en, de o es, por defecto el idioma de la configuración regional)\n",
+        Msg::OptCapabilities => "Mostrar qué columnas e informes admiten esta compilación, la plataforma y el sistema de archivos de PATH\n",
+        Msg::OptHelp => "Mostrar las instrucciones de uso\n",
+        Msg::OptHelpFull => "Mostrar las instrucciones de uso junto con la descripción de los formatos de salida\n",
+        Msg::OptMan => "Mostrar una página de manual (en roff)\n",
+        Msg::EmptyOption => "Ignorando opción desconocida de longitud 0\n",
+        Msg::UnknownOption => "Ignorando opción desconocida {}\n",
+        Msg::UnknownLang => "Idioma desconocido \"{}\" (se esperaba en, de o es)\n",
+        Msg::RecursionDepthZero => "¡La profundidad máxima de recursión debe ser mayor que 0!\n",
+        Msg::NotAnInteger => "No se pudo convertir \"{}\" a un entero\n",
+        Msg::NotAPositiveInteger => "\"{}\" no es un entero positivo\n",
+        Msg::IgnoringRecursive => "Ignorando la opción recursiva\n",
+        Msg::IgnoringDebounce => "Ignorando la opción de debounce\n",
+        Msg::IgnoringOption => "Ignorando la opción {}\n",
+        Msg::OneSearchMode => "Solo se puede usar un modo de búsqueda a la vez\n",
+        Msg::OneHeatmap => "Solo se puede dibujar un mapa de calor a la vez\n",
+        Msg::OnePlainMode => "Solo se puede usar una de las opciones --names-only, --sizes-only, --ls-format, --format, --export-sqlite, --export-parquet, --long, --printf y --print0 a la vez\n",
+        Msg::Terminating => "Terminando...",
+        Msg::MissingSearchPattern => "No se indicó un patrón de búsqueda después de la opción {}\n",
+        Msg::MissingDestPath => "No se indicó una ruta de destino después de la opción {}\n",
+        Msg::MissingSplitDir => "No se indicó un directorio después de la opción {}\n",
+        Msg::MissingTreemapPath => "No se indicó un archivo de imagen después de la opción {}\n",
+        Msg::MissingPolicy => "No se indicó una política después de la opción {}\n",
+        Msg::MissingPatternsFile => "No se indicó un archivo de patrones después de la opción {}\n",
+        Msg::MissingPathsFile => "No se indicó un archivo de rutas después de la opción {}\n",
+        Msg::MissingEntryCount => "No se indicó un número de entradas después de la opción {}\n",
+        Msg::MissingTimeout => "No se indicó un límite de tiempo después de la opción {}\n",
+        Msg::MissingOnError => "No se indicó un modo de tratar los errores después de la opción {}\n",
+        Msg::MissingHashAlgo => "No se indicó un algoritmo de hash después de la opción {}\n",
+        Msg::MissingCompressAlgo => "No se indicó un algoritmo de compresión después de la opción {}\n",
+        Msg::MissingIndentWidth => "No se indicó un ancho de sangría después de la opción {}\n",
+        Msg::MissingThreadCount => "No se indicó un número de hilos después de la opción {}\n",
+        Msg::MissingIndentStyle => "No se indicó un estilo de sangría después de la opción {}\n",
+        Msg::MissingFormat => "No se indicó un formato de salida después de la opción {}\n",
+        Msg::MissingTemplate => "No se indicó una plantilla después de la opción {}\n",
+        Msg::MissingColumns => "No se indicaron columnas después de la opción {}\n",
+        Msg::MissingTimeFormat => "No se indicó un formato de hora después de la opción {}\n",
+        Msg::MissingBlockSize => "No se indicó un tamaño de bloque después de la opción {}\n",
+        Msg::MissingWidth => "No se indicó un ancho después de la opción {}\n",
+        Msg::MissingExtensions => "No se indicaron extensiones después de la opción {}\n",
+        Msg::MissingMimeTypes => "No se indicaron tipos MIME después de la opción {}\n",
+        Msg::MissingFsTypes => "No se indicaron tipos de sistema de archivos después de la opción {}\n",
+        Msg::MissingTypes => "No se indicaron tipos después de la opción {}\n",
+        Msg::MissingSize => "No se indicó un tamaño después de la opción {}\n",
+        Msg::MissingTimeBound => "No se indicó una fecha ni una antigüedad después de la opción {}\n",
+        Msg::MissingUser => "No se indicó un usuario después de la opción {}\n",
+        Msg::MissingExcludePattern => "No se indicó un patrón de exclusión después de la opción {}\n",
+        Msg::MissingExcludeFile => "No se indicó un archivo de exclusiones después de la opción {}\n",
+        Msg::MissingPruneDir => "No se indicó un nombre de directorio después de la opción {}\n",
+        Msg::MissingDepth => "No se indicó una profundidad después de la opción {}\n",
+        Msg::ExcludesReadError => "Error al leer el archivo de exclusiones \"{}\"\n{}\n",
+        Msg::MissingGroup => "No se indicó un grupo después de la opción {}\n",
+        Msg::MissingPerm => "No se indicó un modo después de la opción {}\n",
+        Msg::MissingPagingMode => "No se indicó un modo de paginación después de la opción {}\n",
+        Msg::MissingColorMode => "No se indicó un modo de color después de la opción {}\n",
+        Msg::MissingTimeZone => "No se indicó una zona horaria después de la opción {}\n",
+        Msg::MissingDbPath => "No se indicó un archivo de base de datos después de la opción {}\n",
+        Msg::MissingParquetPath => "No se indicó un archivo Parquet después de la opción {}\n",
+        Msg::MissingFindExpr => "No se indicó una expresión de find después de la opción {}\n",
+        Msg::MissingHashCache => "No se indicó un archivo de caché de hashes después de la opción {}\n",
+        Msg::MissingCheckpoint => "No se indicó un archivo de punto de control después de la opción {}\n",
+        Msg::MissingDebounce => "No se indicó un tiempo de debounce después de la opción {}\n",
+        Msg::MissingFileCount => "No se indicó un número de archivos después de la opción {}\n",
+        Msg::MissingResultCount => "No se indicó un número de resultados después de la opción {}\n",
+        Msg::MissingAge => "No se indicó una antigüedad después de la opción {}\n",
+        Msg::InvalidAge => "No se pudo convertir \"{}\" a una antigüedad (se esperaba un número seguido de s, m, h, d, w o y)\n",
+        Msg::InvalidTimeout => "No se pudo convertir \"{}\" a un límite de tiempo (se esperaba un número de segundos, seguido opcionalmente de s, m, h, d, w o y)\n",
+        Msg::MissingCommand => "No se indicó un comando después de la opción {}\n",
+        Msg::MissingRecording => "No se indicó un archivo de grabación después de la opción {}\n",
+        Msg::MissingLang => "No se indicó un idioma después de la opción {}\n",
+        Msg::HashAlgoNotCompiled => "El algoritmo de hash {} no fue compilado\n",
+        Msg::CompressAlgoNotCompiled => "El algoritmo de compresión {} no fue compilado\n",
+        Msg::ParquetNotCompiled => "La exportación a Parquet no fue compilada\n",
+        Msg::MimeNotCompiled => "La detección de tipos MIME no fue compilada\n",
+        Msg::UnknownHashAlgo => "Algoritmo de hash desconocido \"{}\" (se esperaba blake3, sha256 o xxh3)\n",
+        Msg::UnknownCompressAlgo => "Algoritmo de compresión desconocido \"{}\" (se esperaba gzip o zstd)\n",
+        Msg::UnknownOnError => "Modo de tratar los errores desconocido \"{}\" (se esperaba skip, abort o collect)\n",
+        Msg::UnknownIndentStyle => "Estilo de sangría desconocido \"{}\" (se esperaba spaces, dots, bars o tree)\n",
+        Msg::UnknownFormat => "Formato de salida desconocido \"{}\" (se esperaba json, ndjson, csv, tsv, markdown, mtree o dot)\n",
+        Msg::UnknownPlaceholder => "Marcador desconocido \"{}\" en la plantilla (se esperaba {name}, {path}, {size}, {perms}, {mtime}, {type} o {depth})\n",
+        Msg::UnknownColumn => "Columna desconocida \"{}\" (se esperaba size, mtime, atime, ctime, perms, owner, hash, content o name)\n",
+        Msg::InvalidTimeFormat => "Formato de hora no válido \"{}\" (se esperaba un formato de strftime como %Y-%m-%d %H:%M)\n",
+        Msg::InvalidBlockSize => "No se pudo convertir \"{}\" a un tamaño de bloque (se esperaba un número positivo seguido de K, M, G o T)\n",
+        Msg::InvalidWidth => "No se pudo convertir \"{}\" a un ancho (se esperaba un número de columnas)\n",
+        Msg::InvalidExtensions => "No hay extensiones en \"{}\" (se esperaba una lista separada por comas como rs,toml,md)\n",
+        Msg::InvalidMimeTypes => "No hay tipos MIME en \"{}\" (se esperaba una lista separada por comas como image/*,application/pdf)\n",
+        Msg::InvalidFsTypes => "No hay tipos de sistema de archivos en \"{}\" (se esperaba una lista separada por comas como proc,sysfs,nfs)\n",
+        Msg::UnknownType => "Tipo desconocido \"{}\" (se esperaba f, d, l, s, b, c o p)\n",
+        Msg::InvalidSize => "No se pudo convertir \"{}\" a un tamaño (se esperaba un número de bytes, seguido opcionalmente de K, M, G o T)\n",
+        Msg::InvalidTimeBound => "No se pudo convertir \"{}\" a una fecha o una antigüedad (se esperaba una fecha como 2024-01-01 o una antigüedad como 30d)\n",
+        Msg::UnknownUser => "Usuario desconocido \"{}\" (se esperaba un nombre o un ID de usuario)\n",
+        Msg::UnknownGroup => "Grupo desconocido \"{}\" (se esperaba un nombre o un ID de grupo)\n",
+        Msg::InvalidPerm => "No se pudo convertir \"{}\" a un modo (se esperaba un modo octal como 644 o uno simbólico como u+w, opcionalmente empezando con - o /)\n",
+        Msg::UnknownPagingMode => "Modo de paginación desconocido \"{}\" (se esperaba auto, always o never)\n",
+        Msg::UnknownColorMode => "Modo de color desconocido \"{}\" (se esperaba auto, always o never)\n",
+        Msg::UnknownTimeZone => "Zona horaria desconocida \"{}\" (se esperaba utc, local, un desfase como +05:30 o un nombre como Europe/Berlin)\n",
+        Msg::FindUnknownPrimary => "Primario u operador de find no soportado {}\n",
+        Msg::FindMissingArgument => "No se indicó un argumento después del primario de find {}\n",
+        Msg::FindInvalidArgument => "Argumento \"{}\" no válido para el primario de find {}\n",
+        Msg::FindSyntaxError => "Expresión de find no válida (falta un operando, paréntesis sin pareja o comillas sin cerrar)\n",
+        Msg::HashCacheReadError => "Error al leer la caché de hashes \"{}\"\n{}\n",
+        Msg::PatternsReadError => "Error al leer los patrones \"{}\"\n{}\n",
+        Msg::NoPatternsInFile => "No hay patrones en \"{}\"\n",
+        Msg::PathsReadError => "Error al leer las rutas \"{}\"\n{}\n",
+        Msg::NoPathsInFile => "No hay rutas en \"{}\"\n",
+        Msg::IgnoreFileReadError => "Error al leer el archivo de exclusiones \"{}\"\n{}\n",
+        Msg::HashCacheWriteError => "Error al escribir la caché de hashes\n{}\n",
+        Msg::CheckpointReadError => "Error al leer el punto de control \"{}\"\n{}\n",
+        Msg::CheckpointRemoveError => "Error al eliminar el punto de control\n{}\n",
+        Msg::RecordReplayModes => "Los escaneos solo se pueden grabar y reproducir al listar o buscar\n",
+        Msg::RecordAndReplay => "No se puede grabar y reproducir un escaneo al mismo tiempo\n",
+        Msg::AppendWithoutOutput => "Solo se puede añadir a un archivo de salida indicado con --output\n",
+        Msg::BlockSizeWithHumanReadable => "No se puede usar --block-size junto con --human-readable, --si o --binary\n",
+        Msg::BfsWithSearchTree => "No se puede usar --bfs junto con --search-tree\n",
+        Msg::BfsWithoutSearch => "Solo se puede usar --bfs junto con una búsqueda (--search, --search-noext, --contains, --glob o --fuzzy) que imprima sus coincidencias como una lista\n",
+        Msg::MaxEntriesWithWatch => "No se puede usar --max-entries junto con --watch\n",
+        Msg::TimeoutWithWatch => "No se puede usar --timeout junto con --watch\n",
+        Msg::OnErrorWithWatch => "Solo se puede usar --on-error skip junto con --watch\n",
+        Msg::ScanAborted => "ABORTADO: el recorrido se detuvo en \"{}\", que no se pudo leer (--on-error abort)\n{}\n\n",
+        Msg::ErrorReportDirs => "Directorios que no se pudieron leer\n",
+        Msg::ErrorReportDir => "\"{}\": {}\n",
+        Msg::ErrorReportKinds => "\nResumen de los errores\n",
+        Msg::ErrorReportKind => "<{} {}>\n",
+        Msg::ErrorReportTotal => "<{} errores en total>\n\n",
+        Msg::TraversalTimedOut => "INCOMPLETO: el recorrido se detuvo al agotarse su límite de tiempo de {} s (--timeout), así que la salida y los resúmenes están incompletos\n\n",
+        Msg::EntriesTruncated => "TRUNCADO: el recorrido se detuvo tras {} entradas (--max-entries), así que la salida y los resúmenes están incompletos\n\n",
+        Msg::MultiplePathsModes => "Solo se puede escanear una única ruta con --watch, --record, --replay, --policy, --split-output, --treemap, --format, --export-sqlite y --export-parquet\n",
+        Msg::PathHeader => "{}:\n",
+        Msg::CombinedScanSummary => "Resumen de todas las {} rutas\n\
+            <{} archivos>\n\
+            <{} enlaces simbólicos>\n\
+            <{} archivos especiales>\n\
+            <{} subdirectorios>\n\
+            <{} entradas en total>\n\
+            \n",
+        Msg::CombinedSearchSummary => "Resumen de las entradas coincidentes en todas las {} rutas\n\
+            <{} archivos>\n\
+            <{} enlaces simbólicos>\n\
+            <{} archivos especiales>\n\
+            <{} subdirectorios>\n\
+            <{} entradas en total>\n\
+            \n",
+        Msg::OutputWhileWatching => "La salida no se puede escribir en un archivo al vigilar, ya que el escaneo nunca termina\n",
+        Msg::SplitOutputModes => "La salida no se puede dividir al vigilar, grabar, reproducir, estimar transferencias, seguir una política o escribirla en un archivo\n",
+        Msg::OnePolicy => "Solo se puede seguir una política a la vez\n",
+        Msg::PolicyReadError => "Error al leer la política \"{}\"\n{}\n",
+        Msg::PolicySyntaxError => "No se pudo interpretar la línea {} de la política \"{}\"\n",
+        Msg::PolicyUnknownKey => "Clave desconocida {} en la política \"{}\"\n",
+        Msg::PolicyInvalidValue => "Valor no válido para la clave {} en la política \"{}\"\n",
+        Msg::PolicyOptionNotAllowed => "La opción --{} no puede establecerse en la política \"{}\" (las políticas solo pueden establecer las opciones que seleccionan y filtran las entradas escaneadas)\n",
+        Msg::PolicyOutputError => "Error al crear el archivo de salida \"{}\"\n{}\n",
+        Msg::MissingOutputPath => "No se indicó un archivo de salida después de la opción {}\n",
+        Msg::OutputCreateError => "Error al crear el archivo de salida \"{}\"\n{}\n",
+        Msg::OutputWriteError => "Error al escribir el archivo de salida \"{}\"\n{}\n",
+        Msg::CompressError => "Error al comprimir la salida\n{}\n",
+        Msg::PagerError => "Error al paginar la salida\n{}\n",
+        Msg::PolicyBudgetsHeader => "\nPresupuestos de los directorios en \"{}\" (tamaño y presupuesto)\n",
+        Msg::PolicyOverBudget => "{} <por encima del presupuesto>\n",
+        Msg::PolicyRetentionHeader => "\nArchivos en \"{}\" conservados más de {}\n",
+        Msg::PolicySummary => "\n<{} directorios por encima del presupuesto>\n<{} archivos conservados demasiado tiempo>\n",
+        Msg::SplitDirCreateError => "Error al crear el directorio \"{}\" para los informes\n{}\n",
+        Msg::TreemapWriteError => "Error al escribir el treemap en \"{}\"\n{}\n",
+        Msg::SqliteCreateError => "Error al crear la base de datos \"{}\"\n{}\n",
+        Msg::SqliteWriteError => "Error al escribir las entradas en la base de datos \"{}\"\n{}\n",
+        Msg::ParquetCreateError => "Error al crear el archivo Parquet \"{}\"\n{}\n",
+        Msg::ParquetWriteError => "Error al escribir las entradas en el archivo Parquet \"{}\"\n{}\n",
+        Msg::SplitExeError => "No se encontró la ruta de fss para escanear los subdirectorios\n",
+        Msg::ReportCreateError => "Error al crear el informe \"{}\"\n{}\n",
+        Msg::ReportRunError => "Error al escanear \"{}\" para su informe\n{}\n",
+        Msg::ReportWritten => "Informe de \"{}\" escrito en \"{}\"\n",
+        Msg::SplitOutputSummary => "\n<{} informes escritos en \"{}\">\n",
+        Msg::RecordingReadError => "Error al leer la grabación \"{}\"\n{}\n",
+        Msg::RecordingCreateError => "Error al crear la grabación \"{}\"\n{}\n",
+        Msg::RecordingWriteError => "Error al escribir la grabación \"{}\"\n{}\n",
+        Msg::IterateError => "Error al recorrer \"{}\"\n{}\n",
+        Msg::MetadataError => "Error al obtener los metadatos de \"{}\"\n{}\n",
+        Msg::ModifiedTimeError => "Error al obtener la hora de la última modificación de \"{}\"\n",
+        Msg::AccessTimeError => "Error al obtener la hora del último acceso de \"{}\"\n{}\n",
+        Msg::AtimeUnreliable => "En \"{}\" no se registran las horas de acceso (está montado con noatime), así que el informe no es fiable\n",
+        Msg::SymlinkTargetError => "Error al leer el destino del enlace simbólico \"{}\"\n{}\n",
+        Msg::DirSizeTraverseError => {
+            "Error al recorrer {} mientras se calculaba el tamaño del directorio {}\n{}\n"
+        }
+        Msg::DirSizeEntryError => {
+            "Error al leer una entrada de {} mientras se calculaba el tamaño del directorio {}\n{}\n"
+        }
+        Msg::HashError => "Error al calcular el hash de \"{}\"\n{}\n",
+        Msg::HashProgress => "\rCalculando el hash de \"{}\" ({}%)",
+        Msg::ScanProgress => "\r{} entradas leídas",
+        Msg::ScanProgressEstimate => "\r[{}{}] {}% ({} de unas {} entradas, faltan {})",
+        Msg::ScanSummary => "\n\
+            Resumen de \"{}\"\n\
+            <{} archivos>\n\
+            <{} enlaces simbólicos>\n\
+            <{} archivos especiales>\n\
+            <{} subdirectorios>\n\
+            <{} entradas en total>\n\
+            \n",
+        Msg::ScanSummaryRecursive => "Incluyendo subdirectorios\n\
+            <{} archivos>\n\
+            <{} enlaces simbólicos>\n\
+            <{} archivos especiales>\n\
+            <{} subdirectorios>\n\
+            <{} entradas en total>\n\
+            \n",
+        Msg::SearchSummaryMatches => "\n\
+            Resumen de las entradas coincidentes\n\
+            <{} archivos>\n\
+            <{} enlaces simbólicos>\n\
+            <{} archivos especiales>\n\
+            <{} subdirectorios>\n\
+            <{} entradas en total>\n\
+            \n",
+        Msg::SearchSummaryPatterns => "Resumen de las coincidencias de cada patrón\n",
+        Msg::SearchSummaryPattern => "<{} entradas que coinciden con \"{}\">\n",
+        Msg::SearchStopped => "La búsqueda se detuvo tras {} coincidencias, así que no se recorrieron todas las entradas\n\n",
+        Msg::SearchSummaryDirs => "Resumen de las coincidencias en cada directorio\n",
+        Msg::SearchSummaryDir => "<{} entradas en \"{}\">\n",
+        Msg::SearchSummaryExts => "Resumen de las coincidencias con cada extensión\n",
+        Msg::SearchSummaryExt => "<{} entradas que terminan en \".{}\">\n",
+        Msg::SearchSummaryNoExt => "<{} entradas sin extensión>\n",
+        Msg::FilesystemLoops => "Se omitieron {} directorios o enlaces simbólicos seguidos que vuelven a un directorio superior\n",
+        Msg::FilterSummary => "Filtradas\n\
+            <{} entradas>\n\
+            \n",
+        Msg::SearchSummaryTraversal => "Resumen del recorrido de \"{}\"\n\
+            <{} archivos>\n\
+            <{} enlaces simbólicos>\n\
+            <{} archivos especiales>\n\
+            <{} subdirectorios>\n\
+            <{} entradas en total>\n\
+            \n",
+        Msg::DirsOnlyTotal => "\n\
+            Tamaño total de \"{}\"\n\
+            <{} bytes>\n\
+            \n",
+        Msg::TreemapSummary => "Treemap de \"{}\" escrito en \"{}\"\n\
+            <{} directorios>\n\
+            <{} bytes>\n\
+            \n",
+        Msg::SqliteSummary => "Entradas escritas en la base de datos \"{}\"\n\
+            <{} entradas>\n\
+            \n",
+        Msg::ParquetSummary => "Entradas escritas en el archivo Parquet \"{}\"\n\
+            <{} entradas>\n\
+            \n",
+        Msg::NameCollisionSummary => "Resumen de colisiones de nombres en \"{}\"\n\
+            <{} nombres compartidos>\n\
+            <{} archivos>\n\
+            \n",
+        Msg::OldestFiles => "Archivos más antiguos en \"{}\"\n",
+        Msg::NewestFiles => "Archivos más recientes en \"{}\"\n",
+        Msg::ColdDataHeader => "Archivos en \"{}\" a los que no se ha accedido en {}\n",
+        Msg::ColdDir => "{} <{} archivos fríos>\n",
+        Msg::ColdDataSummary => "\n\
+            Resumen de datos fríos\n\
+            <{} archivos fríos>\n\
+            <{} bytes de datos fríos>\n\
+            <{} bytes en total>\n\
+            \n",
+        Msg::TimestampAnomaliesHeader => "Archivos en \"{}\" con marcas de tiempo sospechosas\n",
+        Msg::TimestampAnomaliesSummary => "\n\
+            Resumen de anomalías de marcas de tiempo\n\
+            <{} archivos con marcas de tiempo sospechosas>\n\
+            \n",
+        Msg::EmptyDirsHeader => "Directorios vacíos en \"{}\"\n",
+        Msg::EmptyDirsSummary => "\n\
+            Resumen de directorios vacíos\n\
+            <{} directorios vacíos>\n\
+            \n",
+        Msg::HardlinksHeader => "Archivos en \"{}\" con más de un enlace duro\n",
+        Msg::HardlinkGroup => "\nInodo {} <{} enlaces>\n",
+        Msg::HardlinksSummary => "\n\
+            Resumen de enlaces duros\n\
+            <{} inodos compartidos>\n\
+            <{} archivos>\n\
+            <{} bytes ahorrados por los enlaces>\n\
+            \n",
+        Msg::AnomalyFuture => "modificado en el futuro",
+        Msg::AnomalyEpoch => "modificado en la época",
+        Msg::AnomalyAfterCtime => "modificado después de su último cambio de estado",
+        Msg::AnomalyCtimeGap => "modificado más de {} antes de su último cambio de estado",
+        Msg::TimeJustNow => "justo ahora",
+        Msg::TimeFuture => "en el futuro",
+        Msg::TimeMinuteAgo => "hace 1 minuto",
+        Msg::TimeMinutesAgo => "hace {} minutos",
+        Msg::TimeHourAgo => "hace 1 hora",
+        Msg::TimeHoursAgo => "hace {} horas",
+        Msg::TimeDayAgo => "hace 1 día",
+        Msg::TimeDaysAgo => "hace {} días",
+        Msg::TimeMonthAgo => "hace 1 mes",
+        Msg::TimeMonthsAgo => "hace {} meses",
+        Msg::TimeYearAgo => "hace 1 año",
+        Msg::TimeYearsAgo => "hace {} años",
+        Msg::TransferSummary => "\n\
+            Estimación de la transferencia de \"{}\" a \"{}\"\n\
+            <{} archivos nuevos ({} bytes)>\n\
+            <{} archivos modificados ({} bytes)>\n\
+            <{} archivos sin cambios>\n\
+            <{} bytes a transferir>\n\
+            \n",
+        Msg::SlowestDirectories => "Directorios más lentos\n",
+        Msg::LatencyByFilesystem => "\nLatencia de lectura por sistema de archivos\n",
+        Msg::FilesystemLatency => "<{}> ({} directorios, el más lento {})",
+        Msg::Watching => "Vigilando \"{}\" (pulse Ctrl+C para detener)\n",
+        Msg::WatchReport => "[{}] <{} creados> <{} modificados> <{} eliminados>\n",
+        Msg::WatchMore => "<{} más>",
+        Msg::HookExitError => "El hook \"{}\" para \"{}\" terminó con {}\n",
+        Msg::HookRunError => "Error al ejecutar el hook \"{}\" para \"{}\"\n{}\n",
+        Msg::CapabilitiesHeader => "Capacidades para \"{}\"\n",
+        Msg::CapabilitiesMissing => "Algunas capacidades no están disponibles para \"{}\", así que el escaneo continúa sin ellas\n",
+        Msg::CapModifiedTime => "horas de modificación",
+        Msg::CapAccessTime => "horas de acceso",
+        Msg::CapChangeTime => "horas de cambio de estado",
+        Msg::CapPermissions => "permisos",
+        Msg::CapOwners => "propietarios y grupos",
+        Msg::CapHashing => "hash",
+        Msg::CapCacheBypass => "omitir la caché de páginas",
+        Msg::CapAvailable => "disponible",
+        Msg::CapAvailableWith => "disponible ({})",
+        Msg::CapUnsupportedPlatform => "no admitido en esta plataforma",
+        Msg::CapUnsupportedFilesystem => "no admitido por el sistema de archivos",
+        Msg::CapNotCompiled => "no compilado",
+        // messages added later are shown in English until they are translated
+        #[allow(unreachable_patterns)]
+        _ => return None,
+    });
+}
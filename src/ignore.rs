@@ -0,0 +1,557 @@
+//! Excluding entries listed in `.fssignore` files from scans
+//!
+//! A `.fssignore` file has the syntax of `.gitignore` and applies to the directory it lies in and everything below it
+//! (rules of deeper files and later lines take precedence, and `!` re-includes an entry). Ignored entries are skipped
+//! as if they did not exist, so they are neither shown nor counted in sizes and summaries, and ignored directories are
+//! not read at all. The rules of a directory are read when the directory is read during the scan, so only the files
+//! within the scanned tree are honored (while replaying, the recording already lacks the ignored entries)
+//!
+//! The generic `.ignore` and `.fdignore` files (shared with tools such as ripgrep and fd) are honored the same way,
+//! unless `--no-ignore-dot` is given. Within a directory, `.fssignore` takes precedence over them, and they take
+//! precedence over `.gitignore`
+//!
+//! The excludes of a policy (see [crate::policy]) and the patterns given to `--exclude` are applied like an ignore file
+//! in the directory that the scan starts from, except that a pattern ending with `/**` excludes the directory itself as
+//! well, so that it is not read at all. `--prune-common` excludes the directories in [COMMON_PRUNED_DIRS] (and
+//! `--prune-dir` any others) wherever they lie, and the mount points of pseudo filesystems are always excluded (see
+//! [crate::pseudofs])
+//!
+//! With `--respect-gitignore`, the `.gitignore` files are honored the same way, along with the `.git/info/exclude` of
+//! each repository and the global excludes file of git (`core.excludesFile`, or `git/ignore` in the config directory).
+//! The `.gitignore` files of the directories between the root of the repository and the directory that the scan starts
+//! from apply as well, like they do for git
+
+use std::env;
+use std::fs;
+use std::path;
+use std::sync;
+
+use crate::find::fnmatch;
+use crate::i18n::{tr, Msg};
+use crate::{get_option, pseudofs, PrgOptions};
+
+/// Name of the files that list the entries to ignore
+const IGNORE_FILE_NAME: &str = ".fssignore";
+
+/// Names of the directories (of dependencies, builds and caches) that are excluded with `--prune-common`
+pub const COMMON_PRUNED_DIRS: [&str; 5] =
+    ["node_modules", "target", ".venv", ".git", "__pycache__"];
+
+/// Names of the generic ignore files (honored unless `--no-ignore-dot` is given), from the one that takes the least
+/// precedence
+const DOT_IGNORE_FILE_NAMES: [&str; 2] = [".ignore", ".fdignore"];
+
+/// Name of the files that list the entries that git ignores (honored with `--respect-gitignore`)
+const GITIGNORE_FILE_NAME: &str = ".gitignore";
+
+/// Name of the directory that holds the data of a git repository
+const GIT_DIR_NAME: &str = ".git";
+
+/// Rules of the directories currently being scanned, from the outermost (each along with the path of the directory)
+///
+/// Since directories are scanned depth first, the directories that are not ancestors of the one being read are popped
+/// off before it is pushed
+static DIR_RULES: sync::Mutex<Vec<(path::PathBuf, IgnoreRules)>> = sync::Mutex::new(Vec::new());
+
+/// Rules that apply to every scan in addition to the ignore files (relative to the directory that the scan starts from)
+static EXCLUDES: sync::OnceLock<sync::Arc<Vec<Rule>>> = sync::OnceLock::new();
+
+/// Single line of an ignore file
+struct Rule {
+    /// Components of the pattern (`**` matches any number of components)
+    components: Vec<Vec<char>>,
+    /// Whether the pattern only matches directories (it ended with `/`)
+    dir_only: bool,
+    /// Whether the pattern re-includes the entries it matches (it started with `!`)
+    negated: bool,
+}
+
+/// Rules of a single ignore file
+struct RuleSet {
+    /// Directory that the ignore file lies in (patterns are matched against paths relative to it)
+    base: path::PathBuf,
+    /// Components of the path of the base relative to the directory that the patterns are relative to (only set for
+    /// ignore files that lie above the directory that the scan starts from)
+    prefix: Vec<Vec<char>>,
+    /// Rules in the order they are listed
+    rules: sync::Arc<Vec<Rule>>,
+}
+
+/// Rules that apply to the entries of a directory (those of the directory itself and of its ancestors)
+#[derive(Clone, Default)]
+pub struct IgnoreRules(sync::Arc<Vec<sync::Arc<RuleSet>>>);
+
+impl Rule {
+    /// Parses a line of an ignore file ([None] if it is blank or a comment)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_line` - the line
+    fn parse(p_line: &str) -> Option<Rule> {
+        // trailing spaces are removed unless they are escaped
+        let mut line = p_line.trim_end_matches(['\r', '\n']);
+        while line.ends_with(' ') && !line.ends_with("\\ ") {
+            line = &line[..line.len() - 1];
+        }
+
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (
+                false,
+                line.strip_prefix('\\')
+                    .filter(|rest| rest.starts_with(['#', '!']))
+                    .unwrap_or(line),
+            ),
+        };
+
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+
+        if line.is_empty() {
+            return None;
+        }
+
+        // a pattern without a slash (other than at the end) matches at any depth, otherwise it is relative to the base
+        let anchored = line.contains('/');
+        let mut components: Vec<Vec<char>> = Vec::new();
+        if !anchored {
+            components.push(vec!['*', '*']);
+        }
+        components.extend(
+            line.trim_start_matches('/')
+                .split('/')
+                .map(|component| component.chars().collect()),
+        );
+
+        return Some(Rule {
+            components,
+            dir_only,
+            negated,
+        });
+    }
+
+    /// Parses an exclude pattern ([None] if it is blank or a comment)
+    ///
+    /// Unlike in ignore files, a pattern that matches everything inside a directory (such as `target/**`) matches the
+    /// directory itself, so that it is skipped instead of being read only for all of its entries to be ignored
+    ///
+    /// # Arguments
+    ///
+    /// - `p_line` - the pattern
+    fn parse_exclude(p_line: &str) -> Option<Rule> {
+        let mut rule = Rule::parse(p_line)?;
+
+        if !rule.negated
+            && rule.components.len() > 1
+            && rule
+                .components
+                .last()
+                .is_some_and(|last| last.as_slice() == ['*', '*'])
+        {
+            rule.components.pop();
+            rule.dir_only = true;
+        }
+
+        return Some(rule);
+    }
+
+    /// Returns whether the rule matches an entry
+    ///
+    /// # Arguments
+    ///
+    /// - `p_components` - components of the path of the entry relative to the base of the rule
+    /// - `p_is_dir` - whether the entry is a directory
+    fn matches(&self, p_components: &[Vec<char>], p_is_dir: bool) -> bool {
+        return (p_is_dir || !self.dir_only) && match_components(&self.components, p_components);
+    }
+}
+
+/// Returns whether the components of a path match those of a pattern
+///
+/// # Arguments
+///
+/// - `p_pattern` - components of the pattern
+/// - `p_path` - components of the path
+pub fn match_components(p_pattern: &[Vec<char>], p_path: &[Vec<char>]) -> bool {
+    let Some((first, rest)) = p_pattern.split_first() else {
+        return p_path.is_empty();
+    };
+
+    if first.as_slice() == ['*', '*'] {
+        // a trailing ** matches everything inside a directory, but not the directory itself
+        if rest.is_empty() {
+            return !p_path.is_empty();
+        }
+        return (0..=p_path.len()).any(|skip| match_components(rest, &p_path[skip..]));
+    }
+
+    return !p_path.is_empty()
+        && fnmatch(first, &p_path[0])
+        && match_components(rest, &p_path[1..]);
+}
+
+impl IgnoreRules {
+    /// Returns whether an entry is ignored by the rules
+    ///
+    /// # Arguments
+    ///
+    /// - `p_path` - path of the entry
+    /// - `p_is_dir` - whether the entry is a directory
+    pub fn is_ignored(&self, p_path: &path::Path, p_is_dir: bool) -> bool {
+        // mount points of pseudo filesystems are skipped wherever they lie, regardless of the rules
+        if p_is_dir && pseudofs::is_skipped(p_path) {
+            return true;
+        }
+
+        let mut ignored = false;
+
+        for rule_set in self.0.iter() {
+            let Ok(relative) = p_path.strip_prefix(&rule_set.base) else {
+                continue;
+            };
+            let components: Vec<Vec<char>> = rule_set
+                .prefix
+                .iter()
+                .cloned()
+                .chain(
+                    relative
+                        .iter()
+                        .map(|component| component.to_string_lossy().chars().collect()),
+                )
+                .collect();
+
+            for rule in rule_set.rules.iter() {
+                if rule.matches(&components, p_is_dir) {
+                    ignored = !rule.negated;
+                }
+            }
+        }
+
+        return ignored;
+    }
+
+    /// Returns the rules that apply to the entries of a subdirectory (these rules along with its own ignore files)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_dir_path` - path of the subdirectory
+    pub fn below(&self, p_dir_path: &path::Path) -> IgnoreRules {
+        let own_rule_sets = read_rule_sets(p_dir_path);
+        if own_rule_sets.is_empty() {
+            return self.clone();
+        }
+
+        let mut rule_sets = self.0.as_ref().clone();
+        rule_sets.extend(own_rule_sets.into_iter().map(sync::Arc::new));
+
+        return IgnoreRules(sync::Arc::new(rule_sets));
+    }
+}
+
+/// Reads the ignore files of a directory, from the one that takes the least precedence
+///
+/// # Arguments
+///
+/// - `p_dir_path` - path of the directory
+fn read_rule_sets(p_dir_path: &path::Path) -> Vec<RuleSet> {
+    let mut rule_sets = Vec::new();
+
+    if get_option(PrgOptions::RespectGitignore) {
+        // the excludes of a repository lie in its root, and take less precedence than its .gitignore files
+        let git_dir_path = p_dir_path.join(GIT_DIR_NAME);
+        if git_dir_path.is_dir() {
+            rule_sets.extend(read_rule_set(
+                &git_dir_path.join("info").join("exclude"),
+                p_dir_path,
+                Vec::new(),
+            ));
+        }
+        rule_sets.extend(read_rule_set(
+            &p_dir_path.join(GITIGNORE_FILE_NAME),
+            p_dir_path,
+            Vec::new(),
+        ));
+    }
+
+    if !get_option(PrgOptions::NoIgnoreDot) {
+        for file_name in DOT_IGNORE_FILE_NAMES {
+            rule_sets.extend(read_rule_set(
+                &p_dir_path.join(file_name),
+                p_dir_path,
+                Vec::new(),
+            ));
+        }
+    }
+
+    rule_sets.extend(read_rule_set(
+        &p_dir_path.join(IGNORE_FILE_NAME),
+        p_dir_path,
+        Vec::new(),
+    ));
+
+    return rule_sets;
+}
+
+/// Reads an ignore file ([None] if it does not exist)
+///
+/// # Arguments
+///
+/// - `p_file_path` - path of the ignore file
+/// - `p_base` - directory that the patterns are matched against paths relative to
+/// - `p_prefix` - components of the path of the base relative to the directory that the patterns are relative to
+fn read_rule_set(
+    p_file_path: &path::Path,
+    p_base: &path::Path,
+    p_prefix: Vec<Vec<char>>,
+) -> Option<RuleSet> {
+    let contents = match fs::read_to_string(p_file_path) {
+        Ok(contents) => contents,
+        Err(error) => {
+            if error.kind() != std::io::ErrorKind::NotFound && get_option(PrgOptions::ShowErrors) {
+                eprint!(
+                    "{}",
+                    tr!(
+                        Msg::IgnoreFileReadError,
+                        p_file_path.to_string_lossy(),
+                        error
+                    )
+                );
+            }
+            return None;
+        }
+    };
+
+    return Some(RuleSet {
+        base: p_base.to_path_buf(),
+        prefix: p_prefix,
+        rules: sync::Arc::new(contents.lines().filter_map(Rule::parse).collect()),
+    });
+}
+
+/// Returns the rules that apply to the directory that the scan starts from before its own ignore files are read (the
+/// git ignore files above it and the excludes of the policy)
+///
+/// # Arguments
+///
+/// - `p_dir_path` - path of the directory
+fn start_rules(p_dir_path: &path::Path) -> IgnoreRules {
+    let mut rule_sets = Vec::new();
+
+    if get_option(PrgOptions::RespectGitignore) {
+        rule_sets.extend(read_git_rule_sets(p_dir_path));
+    }
+
+    if let Some(excludes) = EXCLUDES.get().filter(|excludes| !excludes.is_empty()) {
+        rule_sets.push(RuleSet {
+            base: p_dir_path.to_path_buf(),
+            prefix: Vec::new(),
+            rules: excludes.clone(),
+        });
+    }
+
+    return IgnoreRules(sync::Arc::new(
+        rule_sets.into_iter().map(sync::Arc::new).collect(),
+    ));
+}
+
+/// Reads the global excludes file of git, along with the ignore files of the repository that lie above a directory
+/// (the directory is not within a repository if none of its ancestors hold a `.git` directory)
+///
+/// # Arguments
+///
+/// - `p_dir_path` - path of the directory
+fn read_git_rule_sets(p_dir_path: &path::Path) -> Vec<RuleSet> {
+    let components = |p_path: &path::Path| -> Vec<Vec<char>> {
+        return p_path
+            .iter()
+            .map(|component| component.to_string_lossy().chars().collect())
+            .collect();
+    };
+
+    // the ancestors are found through the absolute path, while the patterns are matched against the given one
+    let dir_path = fs::canonicalize(p_dir_path).unwrap_or_default();
+    let ancestors: Vec<&path::Path> = dir_path.ancestors().skip(1).collect();
+    let repo_depth = ancestors
+        .iter()
+        .position(|ancestor| ancestor.join(GIT_DIR_NAME).is_dir());
+    let prefix = |p_ancestor: &path::Path| -> Vec<Vec<char>> {
+        return dir_path
+            .strip_prefix(p_ancestor)
+            .map(&components)
+            .unwrap_or_default();
+    };
+
+    let mut rule_sets = Vec::new();
+
+    // patterns of the global excludes file are relative to the root of the repository
+    if let Some(file_path) = global_excludes_path() {
+        let prefix = match repo_depth {
+            Some(depth) => prefix(ancestors[depth]),
+            None => Vec::new(),
+        };
+        rule_sets.extend(read_rule_set(&file_path, p_dir_path, prefix));
+    }
+
+    let Some(repo_depth) = repo_depth else {
+        return rule_sets;
+    };
+
+    for (depth, ancestor) in ancestors[..=repo_depth].iter().enumerate().rev() {
+        let prefix = prefix(ancestor);
+
+        if depth == repo_depth {
+            rule_sets.extend(read_rule_set(
+                &ancestor.join(GIT_DIR_NAME).join("info").join("exclude"),
+                p_dir_path,
+                prefix.clone(),
+            ));
+        }
+        rule_sets.extend(read_rule_set(
+            &ancestor.join(GITIGNORE_FILE_NAME),
+            p_dir_path,
+            prefix,
+        ));
+    }
+
+    return rule_sets;
+}
+
+/// Returns the path of the global excludes file of git ([None] if the home directory is not known)
+///
+/// The file is given by `core.excludesFile` in the global config of git, and is `git/ignore` in the config directory
+/// otherwise
+fn global_excludes_path() -> Option<path::PathBuf> {
+    let home = env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .filter(|home| !home.is_empty())
+        .map(path::PathBuf::from)?;
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(path::PathBuf::from)
+        .unwrap_or(home.join(".config"));
+
+    // ~/.gitconfig is read after the config in the config directory, so its value takes precedence
+    let mut excludes_file = None;
+    for config_path in [
+        config_dir.join("git").join("config"),
+        home.join(".gitconfig"),
+    ] {
+        let Ok(contents) = fs::read_to_string(&config_path) else {
+            continue;
+        };
+
+        let mut in_core = false;
+        for line in contents.lines().map(str::trim) {
+            if let Some(section) = line.strip_prefix('[') {
+                in_core = section
+                    .trim_end_matches(']')
+                    .trim()
+                    .eq_ignore_ascii_case("core");
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            if in_core && key.trim().eq_ignore_ascii_case("excludesfile") {
+                excludes_file = Some(value.trim().trim_matches('"').to_owned());
+            }
+        }
+    }
+
+    return Some(match excludes_file {
+        Some(file) => match file.strip_prefix("~/") {
+            Some(rest) => home.join(rest),
+            None => path::PathBuf::from(file),
+        },
+        None => config_dir.join("git").join("ignore"),
+    });
+}
+
+/// Pops the directories that are not ancestors of a directory off the stack of directories being scanned, and
+/// returns the rules of the innermost remaining one ([None] if the directory itself is on top of the stack)
+///
+/// # Arguments
+///
+/// - `p_dir_rules` - stack of directories being scanned
+/// - `p_dir_path` - path of the directory
+fn pop_to_parent(
+    p_dir_rules: &mut Vec<(path::PathBuf, IgnoreRules)>,
+    p_dir_path: &path::Path,
+) -> Option<IgnoreRules> {
+    while p_dir_rules
+        .last()
+        .is_some_and(|(dir_path, _)| !p_dir_path.starts_with(dir_path))
+    {
+        p_dir_rules.pop();
+    }
+
+    return match p_dir_rules.last() {
+        // the same directory can be read more than once (such as for calculating its size before listing it)
+        Some((dir_path, _)) if dir_path == p_dir_path => None,
+        Some((_, rules)) => Some(rules.clone()),
+        // the excludes are relative to the directory that the scan starts from (the first one that is read)
+        None => Some(start_rules(p_dir_path)),
+    };
+}
+
+/// Returns the rules that apply to the entries of a directory that is being read
+///
+/// # Arguments
+///
+/// - `p_dir_path` - path of the directory
+pub fn dir_rules(p_dir_path: &path::Path) -> IgnoreRules {
+    let Ok(mut dir_rules) = DIR_RULES.lock() else {
+        return IgnoreRules::default();
+    };
+
+    let Some(parent_rules) = pop_to_parent(&mut dir_rules, p_dir_path) else {
+        return dir_rules
+            .last()
+            .map(|(_, rules)| rules.clone())
+            .unwrap_or_default();
+    };
+
+    let rules = parent_rules.below(p_dir_path);
+    dir_rules.push((p_dir_path.to_path_buf(), rules.clone()));
+
+    return rules;
+}
+
+/// Marks a directory as being read with rules that were already determined (by reading it ahead of the traversal),
+/// so that the directories read below it inherit them
+///
+/// # Arguments
+///
+/// - `p_dir_path` - path of the directory
+/// - `p_rules` - rules that apply to the entries of the directory
+pub fn enter_dir(p_dir_path: &path::Path, p_rules: &IgnoreRules) {
+    let Ok(mut dir_rules) = DIR_RULES.lock() else {
+        return;
+    };
+
+    if pop_to_parent(&mut dir_rules, p_dir_path).is_some() {
+        dir_rules.push((p_dir_path.to_path_buf(), p_rules.clone()));
+    }
+}
+
+/// Sets the patterns (with the syntax of ignore files) that are excluded from every scan, relative to the directory
+/// that the scan starts from
+///
+/// # Arguments
+///
+/// - `p_patterns` - the patterns (those of the policy followed by those given to `--exclude`)
+pub fn set_excludes(p_patterns: &[String]) {
+    let _ = EXCLUDES.set(sync::Arc::new(
+        p_patterns
+            .iter()
+            .filter_map(|line| Rule::parse_exclude(line))
+            .collect(),
+    ));
+}
@@ -0,0 +1,104 @@
+//! Stopping traversals early once they have read a number of entries (for `--max-entries`), once a time budget ran
+//! out (for `--timeout`) or once a directory could not be read (for `--on-error abort`)
+//!
+//! Every entry read through [record::read_dir](crate::record::read_dir) and
+//! [record::read_dir_unrecorded](crate::record::read_dir_unrecorded) counts towards the limit, across all of the paths
+//! being scanned, and the budget is checked before each of them is handed out. Once either is exhausted, no more
+//! entries are handed out, so each traversal winds down as if the directories that are left were empty, and the
+//! summaries only count the entries that were read before
+
+use std::sync;
+use std::sync::atomic;
+use std::time;
+
+/// Number of entries after which traversals stop ([None] if they are not limited)
+static MAX_ENTRIES: sync::OnceLock<u64> = sync::OnceLock::new();
+
+/// Number of entries read so far (including the ones that were refused once the limit was reached)
+static READ_CNT: atomic::AtomicU64 = atomic::AtomicU64::new(0);
+
+/// Whether an entry was refused because the limit was reached
+static TRUNCATED: atomic::AtomicBool = atomic::AtomicBool::new(false);
+
+/// Time budget of the traversals along with the instant that it runs out at ([None] if they are not limited)
+static DEADLINE: sync::OnceLock<(time::Duration, time::Instant)> = sync::OnceLock::new();
+
+/// Whether an entry was refused because the time budget ran out
+static TIMED_OUT: atomic::AtomicBool = atomic::AtomicBool::new(false);
+
+/// Whether the traversals were told to stop
+static STOPPED: atomic::AtomicBool = atomic::AtomicBool::new(false);
+
+/// Sets the number of entries after which traversals stop
+///
+/// # Arguments
+///
+/// - `p_max_entries` - the number of entries
+pub fn set_max_entries(p_max_entries: u64) {
+    let _ = MAX_ENTRIES.set(p_max_entries);
+}
+
+/// Returns the number of entries after which traversals stop ([None] if they are not limited)
+pub fn max_entries() -> Option<u64> {
+    return MAX_ENTRIES.get().copied();
+}
+
+/// Sets the time budget of the traversals (starting from now)
+///
+/// # Arguments
+///
+/// - `p_timeout` - the time budget
+pub fn set_timeout(p_timeout: time::Duration) {
+    let _ = DEADLINE.set((p_timeout, time::Instant::now() + p_timeout));
+}
+
+/// Returns the time budget of the traversals ([None] if they are not limited)
+pub fn timeout() -> Option<time::Duration> {
+    return DEADLINE.get().map(|(timeout, _)| *timeout);
+}
+
+/// Counts an entry that was read, and returns whether it is still within the limit and the time budget (traversals
+/// stop at the first entry that is not)
+pub fn take_entry() -> bool {
+    if STOPPED.load(atomic::Ordering::Relaxed) {
+        return false;
+    }
+
+    if let Some((_, deadline)) = DEADLINE.get() {
+        if time::Instant::now() >= *deadline {
+            TIMED_OUT.store(true, atomic::Ordering::Relaxed);
+            return false;
+        }
+    }
+
+    let Some(max_entries) = MAX_ENTRIES.get() else {
+        return true;
+    };
+
+    if READ_CNT.fetch_add(1, atomic::Ordering::Relaxed) < *max_entries {
+        return true;
+    }
+
+    TRUNCATED.store(true, atomic::Ordering::Relaxed);
+    return false;
+}
+
+/// Returns whether any entry was left out because the limit was reached
+pub fn truncated() -> bool {
+    return TRUNCATED.load(atomic::Ordering::Relaxed);
+}
+
+/// Returns whether any entry was left out because the time budget ran out
+pub fn timed_out() -> bool {
+    return TIMED_OUT.load(atomic::Ordering::Relaxed);
+}
+
+/// Stops the traversals, so that no more entries are handed out
+pub fn stop() {
+    STOPPED.store(true, atomic::Ordering::Relaxed);
+}
+
+/// Returns whether the traversals stopped before reading every entry (for any of the reasons above)
+pub fn stopped_early() -> bool {
+    return truncated() || timed_out() || STOPPED.load(atomic::Ordering::Relaxed);
+}
@@ -0,0 +1,486 @@
+//! Formatting entries like the long listing of ls (for `--ls-format` and `--long`)
+//!
+//! Each entry is printed on a single line with its mode, number of links, owner, group, size, time of the last
+//! modification and path, separated by whitespace like `ls -l`. Since the entries are printed as they are read, the
+//! columns have fixed minimum widths instead of being aligned per directory. Values that are not available (such as
+//! the owner of a replayed entry) are printed as `?`
+//!
+//! With `--long`, the entries are instead listed by name one directory at a time like `ls -lR`, so the columns are
+//! aligned across the entries of each directory, and the subdirectories are listed after the directory they lie in
+
+use std::path;
+use std::time;
+
+#[cfg(target_family = "unix")]
+use std::sync;
+
+use crate::i18n::{tr, Msg};
+use crate::{
+    color, entry_color, escape, filter, find, get_option, matches_search, progress, record,
+    PrgOptions,
+};
+
+/// Entries modified longer ago than this (about 6 months, like ls) show the year instead of the time of day
+const RECENT_LIMIT: time::Duration = time::Duration::from_secs(365 * 24 * 60 * 60 / 2);
+
+#[cfg(target_family = "unix")]
+/// Names of the users looked up so far, by their IDs
+static USER_NAMES: sync::Mutex<Vec<(u32, String)>> = sync::Mutex::new(Vec::new());
+
+#[cfg(target_family = "unix")]
+/// Names of the groups looked up so far, by their IDs
+static GROUP_NAMES: sync::Mutex<Vec<(u32, String)>> = sync::Mutex::new(Vec::new());
+
+#[cfg(target_family = "unix")]
+/// Returns the mode of an entry as shown by ls (such as `drwxr-xr-x`)
+///
+/// # Arguments
+///
+/// - `p_metadata` - metadata of the entry
+fn mode_string(p_metadata: &record::EntryMetadata) -> String {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = p_metadata.permissions().mode();
+
+    let mut chars = vec![match find::type_letter(p_metadata) {
+        Some('f') => '-',
+        Some(letter) => letter,
+        None => '?',
+    }];
+
+    // the setuid, setgid and sticky bits replace the execute bit of the user, group and others respectively
+    for (shift, special_bit, special_char) in [(6, 0o4000, 's'), (3, 0o2000, 's'), (0, 0o1000, 't')]
+    {
+        let bits = (mode >> shift) & 7;
+
+        chars.push(if bits & 4 != 0 { 'r' } else { '-' });
+        chars.push(if bits & 2 != 0 { 'w' } else { '-' });
+        chars.push(match (bits & 1 != 0, mode & special_bit != 0) {
+            (true, true) => special_char,
+            (false, true) => special_char.to_ascii_uppercase(),
+            (true, false) => 'x',
+            (false, false) => '-',
+        });
+    }
+
+    return chars.into_iter().collect();
+}
+
+#[cfg(not(target_family = "unix"))]
+/// Returns the mode of an entry as shown by ls (the permissions are not available on this platform)
+///
+/// # Arguments
+///
+/// - `p_metadata` - metadata of the entry
+fn mode_string(p_metadata: &record::EntryMetadata) -> String {
+    let type_char = match find::type_letter(p_metadata) {
+        Some('f') => '-',
+        Some(letter) => letter,
+        None => '?',
+    };
+
+    return format!("{}?????????", type_char);
+}
+
+#[cfg(target_family = "unix")]
+/// Returns the name of a user or group, looking it up only the first time ([None] if it has no name)
+///
+/// # Arguments
+///
+/// - `p_cache` - names looked up so far
+/// - `p_id` - ID of the user or group
+/// - `p_lookup` - function that looks up the name of an ID
+fn cached_name(
+    p_cache: &sync::Mutex<Vec<(u32, String)>>,
+    p_id: u32,
+    p_lookup: fn(u32) -> Option<String>,
+) -> Option<String> {
+    let mut cache = p_cache.lock().ok()?;
+
+    if let Some((_, name)) = cache.iter().find(|(id, _)| *id == p_id) {
+        return Some(name.clone());
+    }
+
+    let name = p_lookup(p_id)?;
+    cache.push((p_id, name.clone()));
+
+    return Some(name);
+}
+
+#[cfg(target_family = "unix")]
+/// Looks up the name of a user in the user database ([None] if it has no entry)
+///
+/// # Arguments
+///
+/// - `p_uid` - ID of the user
+fn lookup_user(p_uid: u32) -> Option<String> {
+    let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut buff = vec![0 as libc::c_char; 16 << 10];
+    let mut result = std::ptr::null_mut();
+
+    let error = unsafe {
+        libc::getpwuid_r(
+            p_uid,
+            &mut passwd,
+            buff.as_mut_ptr(),
+            buff.len(),
+            &mut result,
+        )
+    };
+    if error != 0 || result.is_null() {
+        return None;
+    }
+
+    let name = unsafe { std::ffi::CStr::from_ptr(passwd.pw_name) };
+    return Some(name.to_string_lossy().into_owned());
+}
+
+#[cfg(target_family = "unix")]
+/// Looks up the name of a group in the group database ([None] if it has no entry)
+///
+/// # Arguments
+///
+/// - `p_gid` - ID of the group
+fn lookup_group(p_gid: u32) -> Option<String> {
+    let mut group: libc::group = unsafe { std::mem::zeroed() };
+    let mut buff = vec![0 as libc::c_char; 16 << 10];
+    let mut result = std::ptr::null_mut();
+
+    let error = unsafe {
+        libc::getgrgid_r(
+            p_gid,
+            &mut group,
+            buff.as_mut_ptr(),
+            buff.len(),
+            &mut result,
+        )
+    };
+    if error != 0 || result.is_null() {
+        return None;
+    }
+
+    let name = unsafe { std::ffi::CStr::from_ptr(group.gr_name) };
+    return Some(name.to_string_lossy().into_owned());
+}
+
+#[cfg(target_family = "unix")]
+/// Returns the owner of an entry (its ID if the owner has no name, and [None] for replayed entries)#[cfg(target_family = "unix")]
+/// Looks up the ID of a user in the user database by its name ([None] if it has no entry)
+///
+/// # Arguments
+///
+/// - `p_name` - name of the user
+pub fn lookup_user_id(p_name: &str) -> Option<u32> {
+    let name = std::ffi::CString::new(p_name).ok()?;
+    let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut buff = vec![0 as libc::c_char; 16 << 10];
+    let mut result = std::ptr::null_mut();
+
+    let error = unsafe {
+        libc::getpwnam_r(
+            name.as_ptr(),
+            &mut passwd,
+            buff.as_mut_ptr(),
+            buff.len(),
+            &mut result,
+        )
+    };
+    if error != 0 || result.is_null() {
+        return None;
+    }
+
+    return Some(passwd.pw_uid);
+}
+
+#[cfg(target_family = "unix")]
+/// Looks up the ID of a group in the group database by its name ([None] if it has no entry)
+///
+/// # Arguments
+///
+/// - `p_name` - name of the group
+pub fn lookup_group_id(p_name: &str) -> Option<u32> {
+    let name = std::ffi::CString::new(p_name).ok()?;
+    let mut group: libc::group = unsafe { std::mem::zeroed() };
+    let mut buff = vec![0 as libc::c_char; 16 << 10];
+    let mut result = std::ptr::null_mut();
+
+    let error = unsafe {
+        libc::getgrnam_r(
+            name.as_ptr(),
+            &mut group,
+            buff.as_mut_ptr(),
+            buff.len(),
+            &mut result,
+        )
+    };
+    if error != 0 || result.is_null() {
+        return None;
+    }
+
+    return Some(group.gr_gid);
+}
+
+///
+/// # Arguments
+///
+/// - `p_metadata` - metadata of the entry
+pub fn owner_name(p_metadata: &record::EntryMetadata) -> Option<String> {
+    use std::os::unix::fs::MetadataExt;
+
+    let uid = p_metadata.raw()?.uid();
+
+    return Some(cached_name(&USER_NAMES, uid, lookup_user).unwrap_or(uid.to_string()));
+}
+
+#[cfg(not(target_family = "unix"))]
+/// Returns the owner of an entry (not available on this platform)
+///
+/// # Arguments
+///
+/// - `_p_metadata` - metadata of the entry
+pub fn owner_name(_p_metadata: &record::EntryMetadata) -> Option<String> {
+    return None;
+}
+
+#[cfg(target_family = "unix")]
+/// Returns the number of links, owner and group of an entry (its IDs if the owner or group have no name)
+///
+/// # Arguments
+///
+/// - `p_metadata` - metadata of the entry
+fn links_owner_group(p_metadata: &record::EntryMetadata) -> (String, String, String) {
+    use std::os::unix::fs::MetadataExt;
+
+    // replayed entries do not record these
+    let Some(raw) = p_metadata.raw() else {
+        return ("?".to_owned(), "?".to_owned(), "?".to_owned());
+    };
+
+    let owner = owner_name(p_metadata).unwrap_or_default();
+    let group = cached_name(&GROUP_NAMES, raw.gid(), lookup_group).unwrap_or(raw.gid().to_string());
+
+    return (raw.nlink().to_string(), owner, group);
+}
+
+#[cfg(not(target_family = "unix"))]
+/// Returns the number of links, owner and group of an entry (not available on this platform)
+///
+/// # Arguments
+///
+/// - `_p_metadata` - metadata of the entry
+fn links_owner_group(_p_metadata: &record::EntryMetadata) -> (String, String, String) {
+    return ("?".to_owned(), "?".to_owned(), "?".to_owned());
+}
+
+/// Returns the time of the last modification of an entry as shown by ls (the year is shown instead of the time of
+/// day if it was not modified in the last 6 months)
+///
+/// # Arguments
+///
+/// - `p_metadata` - metadata of the entry
+fn time_string(p_metadata: &record::EntryMetadata) -> String {
+    let Ok(modified) = p_metadata.modified() else {
+        return format!("{:>12}", "?");
+    };
+
+    let recent = time::SystemTime::now()
+        .duration_since(modified)
+        .is_ok_and(|age| age < RECENT_LIMIT);
+
+    let time = Into::<chrono::DateTime<chrono::offset::Local>>::into(modified);
+    if recent {
+        return time.format("%b %e %H:%M").to_string();
+    }
+    return time.format("%b %e  %Y").to_string();
+}
+
+/// Returns the text that follows the name of an entry (the target of a symlink like ls, only if it could be read)
+///
+/// Like ls, the target is shown as it is stored in the symlink, even if it is relative or does not exist. While
+/// replaying, the resolved target is shown instead, since recordings only hold that
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+fn target_suffix(p_path_os: &path::Path, p_metadata: &record::EntryMetadata) -> String {
+    if !p_metadata.is_symlink() {
+        return String::new();
+    }
+
+    let target = match record::is_replaying() {
+        true => record::canonicalize(p_path_os),
+        false => record::read_link(p_path_os),
+    };
+    return match target {
+        Ok(target) => format!(" -> {}", escape::name(&target)),
+        Err(_) => String::new(),
+    };
+}
+
+/// Prints an entry in the format of `ls -l`, with its path in place of its name
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+pub fn print_entry(p_path_os: &path::Path, p_metadata: &record::EntryMetadata) {
+    let (links, owner, group) = links_owner_group(p_metadata);
+
+    print!(
+        "{} {:>3} {:<8} {:<8} {:>8} {} {}{}\n",
+        mode_string(p_metadata),
+        links,
+        owner,
+        group,
+        p_metadata.len(),
+        time_string(p_metadata),
+        escape::name(p_path_os),
+        target_suffix(p_path_os, p_metadata)
+    );
+}
+
+/// Prints the entries of a directory in the format of `ls -l`, aligning the columns across them, and then lists its
+/// subdirectories in the same way (if they lie within the maximum recursion depth)
+///
+/// Returns the error if the directory could not be read
+///
+/// # Arguments
+///
+/// - `p_dir_path` - path of the directory
+/// - `p_level` - level of nesting of the directory (0 for the initial directory)
+/// - `p_max_level` - maximum level of nesting to list (0 for no limit)
+/// - `p_search_patterns` - patterns that the names of the printed entries must match one of (if a search option is set)
+/// - `p_shown_cnt` - number of entries printed so far (incremented for each entry printed)
+fn print_long_dir(
+    p_dir_path: &path::Path,
+    p_level: usize,
+    p_max_level: u64,
+    p_search_patterns: &[String],
+    p_shown_cnt: &mut u64,
+) -> std::io::Result<()> {
+    let recursive = get_option(PrgOptions::ShowRecursive)
+        && (p_max_level == 0 || p_level < p_max_level as usize);
+
+    let mut rows = Vec::new();
+    let mut subdirs = Vec::new();
+    for entry in record::read_dir(p_dir_path)? {
+        progress::tick();
+
+        // entries that could not be read are skipped like in the other listings, and so are hidden entries
+        let Ok((path_os, metadata)) = entry else {
+            continue;
+        };
+        if filter::hides(&path_os, &metadata, true) {
+            continue;
+        }
+
+        if recursive && metadata.is_dir() {
+            subdirs.push(path_os.clone());
+        }
+
+        // like the other listings, a search only prints the matching entries (while still listing every directory)
+        if !matches_search(&path_os, p_search_patterns).unwrap_or(false) {
+            continue;
+        }
+        *p_shown_cnt += 1;
+
+        let (links, owner, group) = links_owner_group(&metadata);
+        let name = escape::name(path_os.file_name().unwrap_or_default());
+        let color = entry_color(&path_os, &metadata, Some(metadata.len()));
+        let name = format!("{}{}{}", color, name, color::end(color));
+        rows.push([
+            mode_string(&metadata),
+            links,
+            owner,
+            group,
+            metadata.len().to_string(),
+            time_string(&metadata),
+            name + &target_suffix(&path_os, &metadata),
+        ]);
+    }
+
+    // like ls, every column is as wide as its widest value, with the numbers aligned to the right
+    let mut widths = [0; 6];
+    for row in &rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(value.chars().count());
+        }
+    }
+
+    for row in &rows {
+        print!(
+            "{:<w0$} {:>w1$} {:<w2$} {:<w3$} {:>w4$} {:<w5$} {}\n",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            row[4],
+            row[5],
+            row[6],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+            w4 = widths[4],
+            w5 = widths[5]
+        );
+    }
+
+    for subdir in subdirs {
+        print!("\n{}:\n", escape::name(&subdir));
+
+        if let Err(error) = print_long_dir(
+            &subdir,
+            1 + p_level,
+            p_max_level,
+            p_search_patterns,
+            p_shown_cnt,
+        ) {
+            if get_option(PrgOptions::ShowErrors) {
+                eprint!(
+                    "{}",
+                    tr!(Msg::IterateError, subdir.to_string_lossy(), error)
+                );
+            }
+        }
+    }
+
+    return Ok(());
+}
+
+/// Lists the entries of the initial directory like `ls -l` (and those of its subdirectories like `ls -lR` if the show
+/// recursive option is set), each directory after a line with its path
+///
+/// Returns the number of entries that were printed ([None] if the initial directory could not be read)
+///
+/// # Arguments
+///
+/// - `p_init_path` - path of the directory to list
+/// - `p_search_patterns` - patterns that the names of the printed entries must match one of (if a search option is set)
+/// - `p_max_level` - maximum level of nesting to list (0 for no limit)
+pub fn long_init(
+    p_init_path: &str,
+    p_search_patterns: &[String],
+    p_max_level: &u64,
+) -> Option<u64> {
+    if get_option(PrgOptions::ShowRecursive) {
+        print!("{}:\n", escape::name(p_init_path));
+    }
+
+    let mut shown_cnt = 0;
+    let res = print_long_dir(
+        path::Path::new(p_init_path),
+        0,
+        *p_max_level,
+        p_search_patterns,
+        &mut shown_cnt,
+    );
+    progress::finish();
+
+    if let Err(error) = res {
+        print!("{}", tr!(Msg::IterateError, p_init_path, error));
+        return None;
+    }
+    return Some(shown_cnt);
+}
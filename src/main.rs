@@ -1,7 +1,59 @@
+#![allow(clippy::needless_return)]
+#![allow(clippy::print_with_newline)]
+
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::env;
+use std::ffi;
+use std::ffi::OsString;
+use std::fmt;
 use std::fs;
+use std::io::Read;
 use std::path;
 use std::process;
+use std::sync;
+use std::sync::atomic;
+use std::time;
+
+use i18n::{tr, Msg};
+
+mod arena;
+mod capability;
+mod color;
+mod columns;
+mod compress;
+mod content;
+mod errors;
+mod escape;
+mod export;
+mod filter;
+mod find;
+mod fuzzy;
+mod glob;
+mod hash;
+mod help;
+mod i18n;
+mod ignore;
+mod limit;
+mod ls;
+mod mime;
+mod msgpack;
+mod pager;
+#[cfg(feature = "export-parquet")]
+mod parquet;
+mod policy;
+mod pool;
+mod progress;
+mod pseudofs;
+mod record;
+mod sqlite;
+mod treemap;
+mod watch;
 
 /// Maximum allowed length of the provided path after which any further characters are ignored
 const MAX_PATH_LEN: usize = 256;
@@ -10,18 +62,114 @@ const MAX_PATH_LEN: usize = 256;
 /// Width of the string that contains the formatted last modified time of an entry
 const FMT_TIME_WIDTH: usize = 20;
 
+/// Format (as accepted by strftime) that modification times are printed in, unless `--time-format` is given
+const DEFAULT_TIME_FORMAT: &str = "%b %d %Y  %H:%M";
+
+/// Directories that hold the database of time zones (searched in order)
+const ZONEINFO_DIRS: [&str; 3] = [
+    "/usr/share/zoneinfo",
+    "/usr/lib/zoneinfo",
+    "/usr/share/lib/zoneinfo",
+];
+
 /// Maximum allowed length of the string that stores a formatted integer
 const MAX_FMT_INT_LEN: usize = 32;
 
-/// Number of spaces by which to further indent each subsequent nested directory's entries
-const INDENT_COL_WIDTH: usize = 4;
+/// Number of columns by which to further indent each subsequent nested directory's entries (unless set with `--indent`)
+const DEFAULT_INDENT_COL_WIDTH: usize = 4;
+
+/// Offset basis of the 64-bit FNV-1a hash (used for hashing names in fingerprints)
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+
+/// Prime of the 64-bit FNV-1a hash (used for hashing names in fingerprints)
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Smallest block size used while estimating the changed blocks of a file (same as rsync)
+const MIN_BLOCK_LEN: u64 = 700;
+
+/// Largest block size used while estimating the changed blocks of a file (same as rsync)
+const MAX_BLOCK_LEN: u64 = 1 << 17;
+
+/// Number of bytes of a file to read before asking the kernel to drop its cached pages (if the no cache pollution option is set)
+const CACHE_RELEASE_INTERVAL: u64 = 8 << 20;
+
+/// Number of slowest directories to show in the latency report
+const MAX_LATENCY_REPORT_LEN: usize = 10;
+
+/// Largest difference between two timestamps that is not considered an anomaly (allows for clocks that are slightly off)
+const TIMESTAMP_TOLERANCE: time::Duration = time::Duration::from_secs(60);
+
+/// Status that a search exits with if no entry matched (like grep)
+const EXIT_NO_MATCH: i32 = 1;
+
+/// Status that a search exits with if the initial directory could not be searched (like grep)
+const EXIT_SEARCH_ERROR: i32 = 2;
+
+/// Status that a run exits with if it was aborted at a directory that could not be read (for `--on-error abort`)
+const EXIT_ABORTED: i32 = 3;
 
 /// Array of permissions strings indexed by mode value
 #[cfg(target_family = "unix")]
 const MODE_FMT: [&str; 8] = ["---", "--x", "-w-", "-wx", "r--", "r-x", "rw-", "rwx"];
 
 /// Bitmask to contain the options set by the user
-static mut OPTION_MASK: usize = 0;
+static mut OPTION_MASK: u128 = 0;
+
+/// Number of columns to indent each level of nesting by, and the style to indent with (the defaults are used if not set)
+static INDENT: sync::OnceLock<(usize, IndentStyle)> = sync::OnceLock::new();
+
+/// Whether the entry being printed at each level of nesting is the last line of its directory (only kept while
+/// indenting with tree lines)
+static TREE_BRANCHES: sync::Mutex<Vec<bool>> = sync::Mutex::new(Vec::new());
+
+/// Directories above the entries being searched, from the shallowest to the deepest (only kept while showing the
+/// matches as a tree)
+static SEARCH_ANCESTORS: sync::Mutex<Vec<SearchAncestor>> = sync::Mutex::new(Vec::new());
+
+/// Directories waiting to be searched, from the shallowest to the deepest (only kept while searching breadth-first)
+static SEARCH_QUEUE: sync::Mutex<VecDeque<QueuedDir>> = sync::Mutex::new(VecDeque::new());
+
+/// Depth that entries need to lie at to be shown, set with `--min-depth` (the entries of the initial directory lie at a
+/// depth of 1)
+static MIN_DEPTH: sync::OnceLock<usize> = sync::OnceLock::new();
+
+/// Number of matches after which a search stops, set with `--max-results` ([None] if the search is never stopped)
+static MAX_RESULTS: sync::OnceLock<u64> = sync::OnceLock::new();
+
+/// Read latencies of all directories traversed so far (only recorded if the measure latency option is set)
+static DIR_LATENCIES: sync::Mutex<Vec<DirLatency>> = sync::Mutex::new(Vec::new());
+
+/// Number of shown entries matching each of the search patterns (only counted if several patterns were given)
+static PATTERN_MATCH_CNTS: sync::Mutex<Vec<u64>> = sync::Mutex::new(Vec::new());
+
+/// Number of shown matches within each directory (only counted with `--counts-by-dir`)
+static DIR_MATCH_CNTS: sync::Mutex<BTreeMap<path::PathBuf, u64>> =
+    sync::Mutex::new(BTreeMap::new());
+
+/// Number of shown matches (other than directories) with each extension, lowercased ([None] for the ones without an
+/// extension, only counted with `--counts-by-dir`)
+static EXT_MATCH_CNTS: sync::Mutex<BTreeMap<Option<String>, u64>> =
+    sync::Mutex::new(BTreeMap::new());
+
+/// Search patterns that names are compared with as a whole (with `--search` and `--search-noext`), so that each name
+/// is looked up once instead of being compared with thousands of patterns read from a file
+static EXACT_PATTERNS: sync::OnceLock<HashSet<String>> = sync::OnceLock::new();
+
+/// Directory that the paths matched against the search patterns are relative to (only set with `--full-path`, to each
+/// of the directories being scanned in turn)
+static SEARCH_ROOT: sync::RwLock<Option<path::PathBuf>> = sync::RwLock::new(None);
+
+/// Output file given to `--output`, along with the temporary file that is written instead of it until the scan finishes
+static OUTPUT: sync::OnceLock<OutputFile> = sync::OnceLock::new();
+
+/// Whether the temporary file written instead of the output file has replaced it
+static OUTPUT_DONE: atomic::AtomicBool = atomic::AtomicBool::new(false);
+
+/// Size of the blocks that sizes are printed as a number of with `--block-size` (sizes are printed in bytes if not set)
+static BLOCK_SIZE: sync::OnceLock<u64> = sync::OnceLock::new();
+
+/// Format (as accepted by strftime) that modification times are printed in (the default format is used if not set)
+static TIME_FORMAT: sync::OnceLock<String> = sync::OnceLock::new();
 
 /// Enumerates all the possible options that the user can provide from the command line
 enum PrgOptions {
@@ -53,7 +201,167 @@ enum PrgOptions {
     ShowErrors = 12,
     /// Option that specifies if usage instructions need to be printed
     Help = 13,
+    /// Option that specifies if only directories should be shown (without any aggregate entries)
+    DirsOnly = 14,
+    /// Option that specifies if a digest of each directory should be printed instead of its entries
+    Fingerprint = 15,
+    /// Option that specifies if the amount of data needed to sync the initial directory to another should be estimated
+    TransferEstimate = 16,
+    /// Option that specifies if changed files should be compared block-by-block while estimating the transfer size
+    BlockEstimate = 17,
+    /// Option that specifies if the time taken to read each directory should be measured and reported
+    MeasureLatency = 18,
+    /// Option that specifies if file contents should be read without polluting the page cache of the kernel
+    NoCachePollution = 19,
+    /// Option that specifies if the hash of the contents of each file should be printed
+    ShowHash = 20,
+    /// Option that specifies if the directory should be watched for changes instead of being scanned once
+    Watch = 21,
+    /// Option that specifies if every entry read during the scan should be recorded into a file
+    Record = 22,
+    /// Option that specifies if a recorded scan should be replayed instead of reading the filesystem
+    Replay = 23,
+    /// Option that specifies if the full help (including the description of the output formats) needs to be printed
+    HelpFull = 24,
+    /// Option that specifies if a man page needs to be printed
+    Man = 25,
+    /// Option that specifies if groups of files in different directories that share the same name should be reported
+    NameCollisions = 26,
+    /// Option that specifies if files that share a name must also have the same size to be reported as a collision
+    SameSize = 27,
+    /// Option that specifies if the least recently modified files should be reported
+    Oldest = 28,
+    /// Option that specifies if the most recently modified files should be reported
+    Newest = 29,
+    /// Option that specifies if files that have not been accessed for a long time should be reported per directory
+    ColdData = 30,
+    /// Option that specifies if files with suspicious timestamps should be reported
+    TimestampAnomalies = 31,
+    /// Option that specifies if entries should be colored by the time since they were last modified
+    AgeHeatmap = 32,
+    /// Option that specifies if entries should be colored by their size
+    SizeHeatmap = 33,
+    /// Option that specifies if the progress of the scan should be reported
+    Progress = 34,
+    /// Option that specifies if a separate report should be written for each directory directly under the initial one
+    SplitOutput = 35,
+    /// Option that specifies if only the path of each entry should be printed
+    NamesOnly = 36,
+    /// Option that specifies if only the size and path of each entry should be printed (like du)
+    SizesOnly = 37,
+    /// Option that specifies if the entries should be selected with a find expression
+    FindExpr = 38,
+    /// Option that specifies if each entry should be printed like the long listing of ls
+    LsFormat = 39,
+    /// Option that specifies if the scan is described by a policy
+    Policy = 40,
+    /// Option that specifies if the entries of each directory should be printed sorted by name
+    Ordered = 41,
+    /// Option that specifies if the capabilities available for the initial directory need to be printed
+    Capabilities = 42,
+    /// Option that specifies if the entries should be printed in a machine readable format
+    Format = 43,
+    /// Option that specifies if the sizes of the directories should be rendered as a treemap
+    Treemap = 44,
+    /// Option that specifies if the entries should be written into a SQLite database
+    ExportSqlite = 45,
+    /// Option that specifies if the recording should be written as a binary snapshot
+    Snapshot = 46,
+    /// Option that specifies if the entries should be written into a Parquet file
+    ExportParquet = 47,
+    /// Option that specifies if the entries should be listed like ls -lR
+    Long = 48,
+    /// Option that specifies if the entries should be printed with a template
+    Printf = 49,
+    /// Option that specifies if the output should be written into a file
+    Output = 50,
+    /// Option that specifies if the output should be appended to the output file instead of replacing it
+    Append = 51,
+    /// Option that specifies if the output should be compressed with gzip
+    Compress = 52,
+    /// Option that specifies if only the paths of the entries should be printed, separated by NUL bytes
+    Print0 = 53,
+    /// Option that specifies if control characters and other non-printable characters in names should be escaped
+    Escape = 54,
+    /// Option that specifies if names should be escaped and enclosed in double quotes
+    Quote = 55,
+    /// Option that specifies if the modification times should be printed as the time since then
+    RelativeTime = 56,
+    /// Option that specifies if sizes should be printed in binary units (such as 4.2 MiB) instead of bytes
+    HumanReadable = 57,
+    /// Option that specifies if human-readable sizes should be printed in powers of 1000 (such as 1.3 GB)
+    Si = 58,
+    /// Option that specifies if only those entries whose path matches a given glob pattern should be shown
+    SearchGlob = 59,
+    /// Option that specifies if the search patterns should match names regardless of their case
+    IgnoreCase = 60,
+    /// Option that specifies if only those entries whose name fuzzily matches a given pattern should be shown
+    SearchFuzzy = 61,
+    /// Option that specifies if only those entries that match none of the search patterns should be shown
+    InvertMatch = 62,
+    /// Option that specifies if the directories that hold no entries need to be reported
+    EmptyDirs = 63,
+    /// Option that specifies if hidden entries should be shown (they are hidden while listing otherwise)
+    ShowHidden = 64,
+    /// Option that specifies if hidden entries should be hidden in every mode (instead of only while listing)
+    NoHidden = 65,
+    /// Option that specifies if the entries ignored by git should be skipped
+    RespectGitignore = 66,
+    /// Option that specifies if the generic ignore files (.ignore and .fdignore) should not be honored
+    NoIgnoreDot = 67,
+    /// Option that specifies if the search patterns should match the relative paths of entries instead of their names
+    FullPath = 68,
+    /// Option that specifies if the files with more than one hard link should be reported, grouped by their inodes
+    Hardlinks = 69,
+    /// Option that specifies if the matches of a search should be indented under the directories that they lie in
+    SearchTree = 70,
+    /// Option that specifies if the matches of a search should be counted per directory and per extension
+    CountsByDir = 71,
+    /// Option that specifies if symlinks to directories should be descended into like the directories they point to
+    Follow = 72,
+    /// Option that specifies if searches should traverse the tree breadth-first instead of depth-first
+    Bfs = 73,
+}
+
+/// Enumerates the styles that nested entries can be indented with
+#[derive(Clone, Copy, PartialEq)]
+enum IndentStyle {
+    /// Only spaces
+    Spaces,
+    /// A dot at the start of each level, followed by spaces
+    Dots,
+    /// A vertical bar at the start of each level, followed by spaces
+    Bars,
+    /// Lines connecting each entry to its directory, like tree(1)
+    Tree,
+}
+
+impl IndentStyle {
+    /// Returns the style with the given name ([None] if there is no such style)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_name` - name of the style (as given to `--indent-style`)
+    fn from_name(p_name: &str) -> Option<IndentStyle> {
+        return match p_name {
+            "spaces" => Some(IndentStyle::Spaces),
+            "dots" => Some(IndentStyle::Dots),
+            "bars" => Some(IndentStyle::Bars),
+            "tree" => Some(IndentStyle::Tree),
+            _ => None,
+        };
+    }
+
+    /// Returns the character that each level of indentation starts with
+    fn guide(&self) -> char {
+        return match self {
+            IndentStyle::Spaces => ' ',
+            IndentStyle::Dots => '.',
+            IndentStyle::Bars | IndentStyle::Tree => '|',
+        };
+    }
 }
+
 /// Enumerates all the special file types, or not applicable
 #[derive(PartialEq)]
 enum SpecialFileType {
@@ -191,55 +499,212 @@ impl EntryCounter {
     fn dec_dir_cnt(&mut self, p_dec_amt: u64) {
         self._num_dirs -= p_dec_amt;
     }
+
+    /// Increments the count of each type of entry by the count of another [`EntryCounter`](EntryCounter)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_other` - the counts to add
+    fn add(&mut self, p_other: &EntryCounter) {
+        self.inc_file_cnt(p_other.get_file_cnt());
+        self.inc_symlink_cnt(p_other.get_symlink_cnt());
+        self.inc_special_cnt(p_other.get_special_cnt());
+        self.inc_dir_cnt(p_other.get_dir_cnt());
+    }
 }
 
-#[cfg(target_family = "unix")]
-/// Prints the permissions of a filesystem entry given the metadata
+/// Node of the directory tree that is built while scanning in dirs-only mode
+struct DirNode {
+    /// The directory, as stored in the arena of the tree
+    dir: arena::DirId,
+    /// Metadata of the directory (used for printing permissions and modification time)
+    metadata: record::EntryMetadata,
+    /// Cumulative size of the directory ([None] if it could not be calculated, always 0 if sizes are not shown)
+    size: Option<u64>,
+    /// Subdirectories of the directory that lie within the maximum recursion depth
+    children: Vec<DirNode>,
+}
+
+/// Canonical path of the entry being shown without indentation
 ///
-/// # Arguments
+/// Instead of resolving every entry on its own, the canonical path of the initial directory is resolved once and kept
+/// in a single buffer, which the name of each entry is pushed onto while it is shown (and while its entries are
+/// scanned, for directories) and popped off afterwards. There is no buffer if the initial directory could not be
+/// canonicalized (such as while replaying an older recording), in which case the entries are resolved on their own
+struct CanonicalPath(Option<path::PathBuf>);
+
+/// Indentation printed before an entry, which is written out directly instead of being built as a string
+struct IndentText {
+    /// Number of levels of nesting
+    levels: usize,
+    /// Number of columns of each level
+    col_width: usize,
+    /// Style that each level is drawn with
+    style: IndentStyle,
+    /// Whether the entry at each level up to that of the printed line is the last line of its directory (only used
+    /// for tree lines)
+    branches: Vec<bool>,
+}
+
+/// Line that is printed last for a directory (which ends its branch while indenting with tree lines)
+#[derive(PartialEq)]
+enum TreeEnd {
+    /// Nothing is printed for the directory
+    Nothing,
+    /// The entry with the given index among those read from the directory
+    Entry(usize),
+    /// The number of files that are not shown
+    Files,
+    /// The number of symlinks that are not shown
+    Symlinks,
+    /// The number of special files that are not shown
+    Special,
+}
+
+/// Digest of a directory that is built while scanning in fingerprint mode
 ///
-/// - `metadata` - metadata of the entry whose permissions need to be printed
-macro_rules! print_permissions {
-    ($metadata:ident) => {
-        use std::os::unix::fs::PermissionsExt;
+/// All the values are cumulative, i.e. they include the entries of all subdirectories as well
+struct Fingerprint {
+    /// The directory, as stored in the arena of the scan
+    dir: arena::DirId,
+    /// Number of entries of each type
+    counts: EntryCounter,
+    /// Total size of all regular files
+    size: u64,
+    /// Newest modification time (in seconds since the UNIX epoch) among all entries
+    newest: u64,
+    /// Hash of the names and types of all entries ([None] if some directory could not be read)
+    name_hash: Option<u64>,
+    /// Digests of subdirectories that lie within the maximum recursion depth
+    children: Vec<Fingerprint>,
+}
 
-        // get the raw bits representing the permissions of the entry
-        let mode = $metadata.permissions().mode() as usize;
+/// Regular file that is found while looking for name collisions (its name is the key it is grouped by, and its path is
+/// built from the arena that its directory is stored in once it is printed)
+struct NamedFile {
+    /// Directory that the file lies in
+    dir: arena::DirId,
+    /// Size of the file
+    len: u64,
+}
 
-        unsafe {
-            // for each user, group and other, there are 7 possible modes
-            // each mode has a unique representation of characters
-            // use an array of string slices to store what is to be printed
-            // for each of the 7 possible values
-            print!(
-                "{}{}{}   ",
-                MODE_FMT.get_unchecked((mode >> 6) & 7),
-                MODE_FMT.get_unchecked((mode >> 3) & 7),
-                MODE_FMT.get_unchecked((mode >> 0) & 7)
-            )
+/// Directory above the entries being searched, which is printed before the first match within it when the matches are
+/// shown as a tree
+struct SearchAncestor {
+    /// Path of the directory
+    path: path::PathBuf,
+    /// Metadata of the directory
+    metadata: record::EntryMetadata,
+    /// Level at which the directory lies (0 for the entries of the initial directory)
+    level: usize,
+    /// Whether the directory has been printed (either as a match or before a match within it)
+    printed: bool,
+}
+
+/// Directory that is searched once the directories above it and the ones before it at the same level have been searched
+/// (with `--bfs`)
+struct QueuedDir {
+    /// Path of the directory
+    path: path::PathBuf,
+    /// Canonical path of the directory ([None] if it is not needed)
+    canonical: Option<path::PathBuf>,
+    /// Level at which the entries of the directory lie
+    level: usize,
+}
+
+/// Inode that more than one of the files found in hardlinks mode link to
+struct LinkedInode {
+    /// Number of hard links to the inode (including those outside of the initial directory)
+    nlink: u64,
+    /// Size of the file
+    len: u64,
+    /// Directory and name of each hard link to the inode within the initial directory
+    links: Vec<(arena::DirId, OsString)>,
+}
+
+/// Files within a directory that have not been accessed for a long time (found in cold data mode)
+#[derive(Default)]
+struct ColdDir {
+    /// Number of cold files directly within the directory
+    cold_cnt: u64,
+    /// Total size of the cold files directly within the directory
+    cold_size: u64,
+    /// Total size of all files directly within the directory
+    total_size: u64,
+}
+
+/// Structure to store the time taken to read a directory
+struct DirLatency {
+    /// Path of the directory
+    path: path::PathBuf,
+    /// Identifier of the filesystem that the directory lies on (device number on unix, always 0 otherwise)
+    fs_id: u64,
+    /// Time spent in reading the entries of the directory and their metadata
+    latency: time::Duration,
+}
+
+/// Side of a transfer estimate, whose directories are either read from the filesystem or from a recording or snapshot
+/// of a scan
+enum TransferSide {
+    Live,
+    Recorded(record::RecordedTree),
+}
+
+impl TransferSide {
+    /// Returns the side for the given path along with the directory that it starts from (a recording or snapshot is
+    /// loaded if the path is a file, and it starts from the directory that the recorded scan started from)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_path` - path of the directory, or of the recording or snapshot
+    fn open(p_path: &str) -> std::io::Result<(TransferSide, path::PathBuf)> {
+        if !fs::metadata(p_path).is_ok_and(|metadata| metadata.is_file()) {
+            return Ok((TransferSide::Live, path::PathBuf::from(p_path)));
         }
-    };
+
+        let tree = record::load_tree(path::Path::new(p_path))?;
+        let root = tree.root().to_path_buf();
+        return Ok((TransferSide::Recorded(tree), root));
+    }
+
+    /// Returns the recorded entries of a directory (the side must have been recorded)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_dir_path` - path of the directory
+    fn read_dir(
+        &self,
+        p_dir_path: &path::Path,
+    ) -> std::io::Result<Vec<(path::PathBuf, record::EntryMetadata)>> {
+        return match self {
+            TransferSide::Recorded(tree) => tree.read_dir(p_dir_path),
+            TransferSide::Live => Err(std::io::Error::other("the directory is not recorded")),
+        };
+    }
 }
 
-#[cfg(target_family = "unix")]
-/// Prints the modification time of a filesystem entry
-///
-/// # Arguments
-///
-/// - `metadata` - metadata of the entry whose permissions are to be printed
-/// - `path` - path of the entry (used in the error message if the time could not be read)
-macro_rules! print_modif_time {
-    ($metadata:ident, $path:expr) => {
-        let Ok(time) = $metadata.modified() else {
-                    if get_option(PrgOptions::ShowErrors) {
-                        eprint!("Error while getting last modified time of \"{}\"\n", $path);
-                    }
-                    return true;
-                };
+/// Output file given to `--output`, along with the temporary file that is written instead of it until the scan finishes
+struct OutputFile {
+    /// Path of the output file
+    path: path::PathBuf,
+    /// Path of the temporary file (next to the output file, so that it can be renamed over it)
+    tmp_path: path::PathBuf,
+    /// The temporary file (which stdout is redirected into)
+    tmp_file: fs::File,
+}
 
-        let time = Into::<chrono::DateTime<chrono::offset::Local>>::into(time);
-        print!("{:>FMT_TIME_WIDTH$}", time.format("%b %d %Y  %H:%M"));
-    };
+/// Structure to accumulate the amount of data that needs to be transferred to sync two directories
+struct TransferEstimate {
+    /// Number of files that are not present in the destination
+    new_files: u64,
+    /// Number of bytes of files that are not present in the destination
+    new_bytes: u64,
+    /// Number of files whose size or modification time differs from the destination
+    changed_files: u64,
+    /// Number of bytes that need to be transferred for changed files
+    changed_bytes: u64,
+    /// Number of files that are the same in the destination
+    unchanged_files: u64,
 }
 
 /// Sets the given option in a mask (has not effect if the option is already set)
@@ -249,7 +714,7 @@ macro_rules! print_modif_time {
 /// - `p_bit` - the bit/option to be set
 fn set_option(p_bit: PrgOptions) {
     unsafe {
-        OPTION_MASK |= 1usize << (p_bit as usize);
+        OPTION_MASK |= 1u128 << (p_bit as usize);
     }
 }
 
@@ -263,7 +728,7 @@ fn set_option(p_bit: PrgOptions) {
 ///
 /// `True` if the option is set, `False` otherwise
 fn get_option(p_bit: PrgOptions) -> bool {
-    unsafe { OPTION_MASK & (1usize << (p_bit as usize)) != 0 }
+    unsafe { OPTION_MASK & (1u128 << (p_bit as usize)) != 0 }
 }
 
 /// Clears the given option in a mask (has not effect if the option is already unset)
@@ -271,11 +736,275 @@ fn get_option(p_bit: PrgOptions) -> bool {
 /// # Arguments
 ///
 /// - `p_bit` - the bit/option to be unset
-#[allow(dead_code)]
 fn clear_option(p_bit: PrgOptions) {
     unsafe {
-        OPTION_MASK &= !(1usize << (p_bit as usize));
+        OPTION_MASK &= !(1u128 << (p_bit as usize));
+    }
+}
+
+/// Records the time taken to read a directory (has no effect if the measure latency option is not set)
+///
+/// # Arguments
+///
+/// - `p_dir_path` - path of the directory that was read
+/// - `p_latency` - time spent in reading the entries of the directory and their metadata
+fn record_dir_latency(p_dir_path: &path::Path, p_latency: time::Duration) {
+    if !get_option(PrgOptions::MeasureLatency) {
+        return;
+    }
+
+    #[cfg(target_family = "unix")]
+    let fs_id = {
+        use std::os::unix::fs::MetadataExt;
+
+        fs::metadata(p_dir_path)
+            .map(|metadata| metadata.dev())
+            .unwrap_or(0)
+    };
+
+    #[cfg(not(target_family = "unix"))]
+    let fs_id = 0;
+
+    if let Ok(mut latencies) = DIR_LATENCIES.lock() {
+        latencies.push(DirLatency {
+            path: p_dir_path.to_path_buf(),
+            fs_id,
+            latency: p_latency,
+        });
+    }
+}
+
+/// Prints the slowest directories and the read latency of each filesystem (based on the recorded latencies)
+fn print_latency_report() {
+    // the latencies are taken, so that the report of each of several directories only holds the ones read within it
+    let mut latencies = match DIR_LATENCIES.lock() {
+        Ok(mut latencies) => std::mem::take(&mut *latencies),
+        Err(_) => return,
+    };
+
+    let fmt_latency =
+        |p_latency: time::Duration| format!("{:.3} ms", p_latency.as_secs_f64() * 1000.0);
+
+    latencies.sort_by_key(|dir_latency| std::cmp::Reverse(dir_latency.latency));
+
+    print!("{}", tr!(Msg::SlowestDirectories));
+    for dir_latency in latencies.iter().take(MAX_LATENCY_REPORT_LEN) {
+        print!(
+            "{:>20}    <{}>\n",
+            fmt_latency(dir_latency.latency),
+            dir_latency.path.to_string_lossy()
+        );
+    }
+
+    // group the directories by filesystem, and identify each filesystem by its shallowest directory
+    // (number of directories, total latency, slowest latency, shallowest directory)
+    let mut filesystems: Vec<(u64, u64, time::Duration, time::Duration, &path::Path)> = Vec::new();
+
+    for dir_latency in latencies.iter() {
+        let fs_entry = filesystems
+            .iter_mut()
+            .find(|fs_entry| fs_entry.0 == dir_latency.fs_id);
+
+        if let Some(fs_entry) = fs_entry {
+            fs_entry.1 += 1;
+            fs_entry.2 += dir_latency.latency;
+            fs_entry.3 = fs_entry.3.max(dir_latency.latency);
+            if dir_latency.path.components().count() < fs_entry.4.components().count() {
+                fs_entry.4 = &dir_latency.path;
+            }
+        } else {
+            filesystems.push((
+                dir_latency.fs_id,
+                1,
+                dir_latency.latency,
+                dir_latency.latency,
+                &dir_latency.path,
+            ));
+        }
+    }
+
+    filesystems.sort_by_key(|fs_entry| std::cmp::Reverse(fs_entry.2 / fs_entry.1 as u32));
+
+    print!("{}", tr!(Msg::LatencyByFilesystem));
+    for (_, dir_cnt, total, slowest, path) in filesystems {
+        print!(
+            "{:>20}    {}\n",
+            fmt_latency(total / dir_cnt as u32),
+            tr!(
+                Msg::FilesystemLatency,
+                path.to_string_lossy(),
+                int_to_formatted_slice(dir_cnt),
+                fmt_latency(slowest)
+            )
+        );
+    }
+    print!("\n");
+}
+
+/// Returns the hash of the contents of a file as a column (a blank column if no path is given)
+///
+/// If the file could not be hashed (or is being replayed from a recording, which does not store contents), ERROR is
+/// returned in place of the hash
+///
+/// # Arguments
+///
+/// - `p_file` - path and metadata of the file whose contents are to be hashed ([None] for entries that are not regular files)
+fn hash_column(p_file: Option<(&path::Path, &record::EntryMetadata)>) -> String {
+    let Some(algo) = hash::get_hash_algo() else {
+        return String::new();
+    };
+    let hex_len = algo.hex_len();
+
+    let hash = match p_file {
+        Some((path_os, metadata)) => match metadata
+            .raw()
+            .ok_or_else(|| std::io::Error::other("contents of replayed files are not recorded"))
+            .and_then(|metadata| hash::hash_file(path_os, metadata))
+        {
+            Ok(hash) => hash,
+            Err(error) => {
+                if get_option(PrgOptions::ShowErrors) {
+                    eprint!("{}", tr!(Msg::HashError, path_os.to_string_lossy(), error));
+                }
+                "ERROR".to_owned()
+            }
+        },
+        None => "".to_owned(),
+    };
+
+    return format!("  {:hex_len$}", hash);
+}
+
+/// Hands the regular files of a directory whose hashes are going to be printed to the work pool, so that they are
+/// hashed ahead of the traversal
+///
+/// Returns the files handed to the pool (their hashes are discarded when it is dropped if they were not printed)
+///
+/// # Arguments
+///
+/// - `p_entries` - entries of the directory (only those that were read up front are considered)
+/// - `p_search_patterns` - patterns that the names of the printed entries must match one of (if a search option is set)
+fn hash_files_ahead(
+    p_entries: &record::ReadEntries,
+    p_search_patterns: &[String],
+) -> Option<pool::Ahead<std::io::Result<String>>> {
+    if !get_option(PrgOptions::ShowHash) || !get_option(PrgOptions::ShowFiles) {
+        return None;
+    }
+
+    let files = p_entries
+        .remaining()
+        .iter()
+        .filter_map(|entry| {
+            let (path_os, metadata) = entry.as_ref().ok()?;
+            let raw = metadata.raw()?;
+
+            if !metadata.is_file()
+                || metadata.special_file_type() != SpecialFileType::NA
+                || !matches_search(path_os, p_search_patterns)?
+                || !filter::matches(path_os, metadata)
+            {
+                return None;
+            }
+            return Some((path_os.clone(), raw.clone()));
+        })
+        .collect();
+
+    return hash::hash_ahead(files);
+}
+
+impl CanonicalPath {
+    /// Returns the canonical path of the initial directory
+    ///
+    /// # Arguments
+    ///
+    /// - `p_init_path` - path of the initial directory
+    /// - `p_needed` - whether the entries are shown by their canonical paths (no buffer is kept otherwise)
+    fn new(p_init_path: &path::Path, p_needed: bool) -> CanonicalPath {
+        if !p_needed {
+            return CanonicalPath(None);
+        }
+        return CanonicalPath(record::canonicalize(p_init_path).ok());
+    }
+
+    /// Appends the name of an entry of the current directory (which must be popped off once the entry is shown)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_path_os` - path of the entry
+    fn push(&mut self, p_path_os: &path::Path) {
+        if let Some(buff) = self.0.as_mut() {
+            buff.push(p_path_os.file_name().unwrap_or_default());
+        }
+    }
+
+    /// Removes the name of the entry that was appended last
+    fn pop(&mut self) {
+        if let Some(buff) = self.0.as_mut() {
+            buff.pop();
+        }
+    }
+
+    /// Returns the canonical path of the entry that was appended last ([None] if there is no buffer)
+    fn get(&self) -> Option<&path::Path> {
+        return self.0.as_deref();
+    }
+}
+
+/// Returns the canonical path of an entry, resolving it only if it is not known already
+///
+/// # Arguments
+///
+/// - `p_canonical` - canonical path of the entry (if it is known)
+/// - `p_path_os` - path of the entry
+fn resolve_canonical<'a>(
+    p_canonical: Option<&'a path::Path>,
+    p_path_os: &path::Path,
+) -> std::io::Result<Cow<'a, path::Path>> {
+    return match p_canonical {
+        Some(canonical) => Ok(Cow::Borrowed(canonical)),
+        None => record::canonicalize(p_path_os).map(Cow::Owned),
+    };
+}
+
+/// Returns the given size formatted for a listing, with thousands separators (or in the largest unit that keeps it at
+/// least 1 with `--human-readable`, such as `4.2 MiB` or `1.3 GB` with `--si`, or as the number of blocks it takes up
+/// with `--block-size`)
+///
+/// # Arguments
+///
+/// - `p_size` - the size (in bytes)
+fn format_size(p_size: u64) -> String {
+    const BINARY_UNITS: [&str; 6] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+    const SI_UNITS: [&str; 6] = ["kB", "MB", "GB", "TB", "PB", "EB"];
+
+    // like du, a partially filled block counts as a whole one
+    if let Some(block_size) = BLOCK_SIZE.get() {
+        return int_to_formatted_slice(p_size.div_ceil(*block_size)).to_owned();
+    }
+
+    if !get_option(PrgOptions::HumanReadable) {
+        return int_to_formatted_slice(p_size).to_owned();
+    }
+
+    let (base, units) = match get_option(PrgOptions::Si) {
+        true => (1000.0, SI_UNITS),
+        false => (1024.0, BINARY_UNITS),
+    };
+
+    if (p_size as f64) < base {
+        return format!("{} B", p_size);
+    }
+
+    // the value is moved to the next unit once it would be rounded up to the base
+    let mut value = p_size as f64 / base;
+    let mut unit = 0;
+    while value >= base - 0.05 && unit + 1 < units.len() {
+        value /= base;
+        unit += 1;
     }
+
+    return format!("{:.1} {}", value, units[unit]);
 }
 
 /// Returns an &str slice that contains the given integer formatted with the thousands seperator
@@ -303,7 +1032,7 @@ where
         BUFF_LEN = 0;
 
         if p_number == 0u64 {
-            BUFF[BUFF_LEN] = '0' as u8;
+            BUFF[BUFF_LEN] = b'0';
             BUFF_LEN += 1;
         }
 
@@ -315,7 +1044,7 @@ where
             BUFF_LEN += 1;
 
             if (BUFF_LEN % 4) == 3 && p_number != 0 {
-                BUFF[BUFF_LEN] = ',' as u8;
+                BUFF[BUFF_LEN] = b',';
                 BUFF_LEN += 1;
             }
         }
@@ -324,7 +1053,10 @@ where
             (BUFF[i], BUFF[BUFF_LEN - i - 1]) = (BUFF[BUFF_LEN - i - 1], BUFF[i]);
         }
 
-        return &std::str::from_utf8_unchecked(&BUFF)[..BUFF_LEN];
+        return std::str::from_utf8_unchecked(std::slice::from_raw_parts(
+            &raw const BUFF as *const u8,
+            BUFF_LEN,
+        ));
     }
 }
 
@@ -337,15 +1069,18 @@ where
 /// - `p_init_dir_path' - the initial directory whose size is to be calculated
 /// - 'p_dir_path' - the current directory whose size is to be calculated
 fn calc_dir_size(p_init_dir_path: &path::Path, p_dir_path: &path::Path) -> Option<u64> {
-    let entries = match fs::read_dir(&p_dir_path) {
+    let entries = match record::read_dir(p_dir_path) {
         Ok(values) => values,
         Err(error) => {
             if get_option(PrgOptions::ShowErrors) {
                 eprint!(
-                    "Error while traversing {} while calculating size of directory {}\n{}\n",
-                    p_dir_path.to_string_lossy(),
-                    p_init_dir_path.to_string_lossy(),
-                    error
+                    "{}",
+                    tr!(
+                        Msg::DirSizeTraverseError,
+                        p_dir_path.to_string_lossy(),
+                        p_init_dir_path.to_string_lossy(),
+                        error
+                    )
                 );
             }
             return None;
@@ -355,29 +1090,26 @@ fn calc_dir_size(p_init_dir_path: &path::Path, p_dir_path: &path::Path) -> Optio
     let mut res: u64 = 0;
 
     for entry in entries {
-        // if the current enty could not be read, silently skip it
-        let Ok(entry) = entry else {
-            continue;
-        };
-
-        let path_os = entry.path();
-
-        let metadata = match entry.metadata() {
-            Ok(metadata) => metadata,
+        let (path_os, metadata) = match entry {
+            Ok(entry) => entry,
             Err(error) => {
                 if get_option(PrgOptions::ShowErrors) {
                     eprint!(
-                        "Error while getting metadata of {} while calculating size of directory {}\n{}\n",
-                        path_os.to_string_lossy(),
-                        p_init_dir_path.to_string_lossy(),
-                        error
+                        "{}",
+                        tr!(
+                            Msg::DirSizeEntryError,
+                            p_dir_path.to_string_lossy(),
+                            p_init_dir_path.to_string_lossy(),
+                            error
+                        )
                     );
                 }
                 return None;
             }
         };
 
-        if metadata.is_symlink() {
+        // hidden entries add to the size unless they are left out of every scan
+        if metadata.is_symlink() || filter::hides(&path_os, &metadata, false) {
             continue;
         }
 
@@ -386,7 +1118,7 @@ fn calc_dir_size(p_init_dir_path: &path::Path, p_dir_path: &path::Path) -> Optio
         if metadata.is_file() {
             res += metadata.len();
         } else if metadata.is_dir() {
-            let dir_size = match calc_dir_size(&p_init_dir_path, &path_os) {
+            let dir_size = match calc_dir_size(p_init_dir_path, &path_os) {
                 Some(dir_size) => dir_size,
                 None => {
                     return None;
@@ -400,1406 +1132,6568 @@ fn calc_dir_size(p_init_dir_path: &path::Path, p_dir_path: &path::Path) -> Optio
     return Some(res);
 }
 
-#[cfg(not(target_family = "unix"))]
-/// Removes the verbatim "\\?\" prefix in UNC paths on windows
+/// Builds the tree of subdirectories of a directory, calculating the cumulative size of each directory in the same pass
+///
+/// Subdirectories that lie deeper than the maximum recursion depth (or that are hidden) are not stored in the tree, but
+/// are still traversed if directory sizes need to be shown (so that the cumulative sizes stay correct)
 ///
 /// # Arguments
 ///
-/// - 'p_path' - the path from which the verbatim prefix is to be removed
-fn adjust_verbatim_unc(p_path: &str) -> &str {
-    const VERBATIM_UNC_PREFIX: &str = r#"\\?\"#;
-    const VERBATIM_UNC_PREFIX_LEN: usize = VERBATIM_UNC_PREFIX.len();
-
-    if p_path.starts_with(VERBATIM_UNC_PREFIX) {
-        return &p_path[VERBATIM_UNC_PREFIX_LEN..];
-    }
+/// - `p_max_level` - maximum level upto which subdirectories are stored (a value of 0 denotes no limit)
+/// - `p_level` - level at which the entries of the current directory are shown (0 for the initial directory)
+/// - `p_arena` - arena that the stored directories are interned into
+/// - `p_dir` - the current directory, as stored in the arena (that of its parent if it is not stored)
+/// - `p_dir_path` - path of the current directory
+/// - `p_metadata` - metadata of the current directory
+fn build_dir_tree(
+    p_max_level: &u64,
+    p_level: usize,
+    p_arena: &mut arena::PathArena,
+    p_dir: arena::DirId,
+    p_dir_path: &path::Path,
+    p_metadata: fs::Metadata,
+) -> DirNode {
+    let mut node = DirNode {
+        dir: p_dir,
+        metadata: record::EntryMetadata::from_metadata(p_metadata),
+        size: Some(0),
+        children: Vec::new(),
+    };
 
-    return p_path;
-}
+    // the subdirectories of the initial directory are always shown, while those of nested directories
+    // are only shown if the recursive option is set and the maximum depth has not been crossed
+    let keep_children = p_level == 0
+        || (get_option(PrgOptions::ShowRecursive)
+            && (*p_max_level == 0u64 || p_level <= (*p_max_level as usize)));
 
-#[cfg(target_family = "unix")]
-/// Prints a symlink without indentation
-///
-/// Returns `false` if the symlink could be logged, `true` otherwise
-///
-/// # Arguments
-///
-/// - 'p_metadata' - reference to the metadata of the symlink entry (used for getting the destination)
-/// - `p_path_os` - reference to the entry's path
-/// - 'p_is_dir' - whether the target of the symlink is a directory or not
-fn show_symlink_noindent(
-    p_metadata: &fs::Metadata,
-    p_path_os: &path::Path,
-    p_is_dir: bool,
-) -> bool {
-    // borrow the filename (silently skip the current entry if this could not be done)
-    let path = p_path_os.to_string_lossy();
+    // if the subdirectories will neither be shown nor contribute to a size, then there is no need to read the directory
+    if !keep_children && !get_option(PrgOptions::ShowDirSize) {
+        return node;
+    }
 
-    // get the canonicalized path name (print the error and exit if this could not be done)
-    let dest_path = match p_path_os.canonicalize() {
-        Ok(dest_path) => dest_path,
+    let entries = match record::read_dir_unrecorded(p_dir_path) {
+        Ok(values) => values,
         Err(error) => {
             if get_option(PrgOptions::ShowErrors) {
                 eprint!(
-                    "Error while reading target of symlink \"{}\"\n{}\n",
-                    path, error
+                    "{}",
+                    tr!(Msg::IterateError, p_dir_path.to_string_lossy(), error)
                 );
             }
-            return true;
+            node.size = None;
+            return node;
         }
     };
+    let ignore_rules = ignore::dir_rules(p_dir_path);
 
-    if get_option(PrgOptions::ShowPermissions) {
-        print_permissions!(p_metadata);
-    }
+    for entry in entries {
+        // if the current entry could not be read, silently skip it
+        let Ok(entry) = entry else {
+            continue;
+        };
 
-    if get_option(PrgOptions::ShowLasttime) {
-        print_modif_time!(p_metadata, path);
-    }
+        let path_os = entry.path();
 
-    // if the target is a directory, enclose the symlink and target within angle brackets <>
-    if p_is_dir {
-        print!(
-            "{:>20}    <{}> -> <{}>\n",
-            "SYMLINK",
-            path,
-            dest_path.to_string_lossy()
-        );
-    } else {
-        print!(
-            "{:>20}    {} -> {}\n",
-            "SYMLINK",
-            path,
-            dest_path.to_string_lossy()
-        );
+        // if the metadata of an entry could not be read, then the size of the directory can not be calculated
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(error) => {
+                if get_option(PrgOptions::ShowErrors) {
+                    eprint!(
+                        "{}",
+                        tr!(Msg::MetadataError, path_os.to_string_lossy(), error)
+                    );
+                }
+                node.size = None;
+                continue;
+            }
+        };
+
+        if metadata.is_symlink() || ignore_rules.is_ignored(&path_os, metadata.is_dir()) {
+            continue;
+        }
+
+        // hidden entries still add to the sizes (like with "-d" in listings), but hidden subdirectories are not shown
+        // unless they are left out of every scan
+        let entry_metadata = record::EntryMetadata::from_metadata(metadata.clone());
+        if filter::hides(&path_os, &entry_metadata, false) {
+            continue;
+        }
+        let hidden = filter::hides(&path_os, &entry_metadata, true);
+        if hidden && !get_option(PrgOptions::ShowDirSize) {
+            continue;
+        }
+
+        if metadata.is_file() {
+            if get_option(PrgOptions::ShowDirSize) {
+                node.size = node.size.map(|size| size + metadata.len());
+            }
+        } else if metadata.is_dir() {
+            // only the subdirectories that are stored are interned (the others are only read for their sizes)
+            let stored = keep_children && !hidden;
+            let child_dir = match stored {
+                true => p_arena.intern_dir(&path_os),
+                false => p_dir,
+            };
+            let child = build_dir_tree(
+                p_max_level,
+                1 + p_level,
+                p_arena,
+                child_dir,
+                &path_os,
+                metadata,
+            );
+
+            node.size = node
+                .size
+                .zip(child.size)
+                .map(|(size, child_size)| size + child_size);
+
+            if stored {
+                node.children.push(child);
+            }
+        }
+    }
+
+    // the sort by size is stable, so subdirectories of the same size stay sorted by name
+    if get_option(PrgOptions::Ordered) {
+        let arena = &*p_arena;
+        node.children
+            .sort_by(|first, second| arena.name(first.dir).cmp(arena.name(second.dir)));
+    }
+
+    // like du, show the largest subdirectories first (directories whose size could not be calculated are shown last)
+    if get_option(PrgOptions::ShowDirSize) {
+        node.children
+            .sort_by_key(|child| std::cmp::Reverse(child.size));
     }
 
-    return false;
+    return node;
 }
 
-#[cfg(not(target_family = "unix"))]
-/// Prints a symlink without indentation
-///
-/// Returns `false` if the symlink could be logged, `true` otherwise
+/// Recursively visits the regular files within a directory
 ///
 /// # Arguments
 ///
-/// - 'p_metadata' - reference to the metadata of the symlink entry (used for getting the destination)
-/// - `p_path_os` - reference to the entry's path
-/// - 'p_is_dir' - whether the target of the symlink is a directory or not
-fn show_symlink_noindent(
-    _p_metadata: &fs::Metadata,
-    p_path_os: &path::Path,
-    p_is_dir: bool,
-) -> bool {
-    // borrow the filename (silently skip the current entry if this could not be done)
-    let path = p_path_os.to_string_lossy();
-
-    // get the canonicalized path name (print the error and exit if this could not be done)
-    let dest_path = match p_path_os.canonicalize() {
-        Ok(dest_path) => dest_path,
+/// - `p_max_level` - maximum level upto which subdirectories are traversed (a value of 0 denotes no limit)
+/// - `p_level` - level at which the entries of the current directory lie (0 for the initial directory)
+/// - `p_dir_path` - path of the current directory
+/// - `p_visit` - function that is called with the path and metadata of each regular file
+fn visit_files(
+    p_max_level: &u64,
+    p_level: usize,
+    p_dir_path: &path::Path,
+    p_visit: &mut impl FnMut(path::PathBuf, fs::Metadata),
+) {
+    let entries = match record::read_dir_unrecorded(p_dir_path) {
+        Ok(values) => values,
         Err(error) => {
             if get_option(PrgOptions::ShowErrors) {
                 eprint!(
-                    "Error while reading target of symlink \"{}\"\n{}\n",
-                    path, error
+                    "{}",
+                    tr!(Msg::IterateError, p_dir_path.to_string_lossy(), error)
                 );
             }
-            return true;
+            return;
         }
     };
+    let ignore_rules = ignore::dir_rules(p_dir_path);
 
-    let dest_path = dest_path.to_string_lossy();
+    for entry in entries {
+        // if the current entry could not be read, silently skip it
+        let Ok(entry) = entry else {
+            continue;
+        };
 
-    // if the target is a directory, enclose the symlink and target within angle brackets <>
-    if p_is_dir {
-        print!(
-            "{:>20}    <{}> -> <{}>\n",
-            "SYMLINK",
-            adjust_verbatim_unc(&path),
-            adjust_verbatim_unc(&dest_path)
-        );
-    } else {
-        print!(
-            "{:>20}    {} -> {}\n",
-            "SYMLINK",
-            adjust_verbatim_unc(&path),
-            adjust_verbatim_unc(&dest_path)
-        );
-    }
+        let path_os = entry.path();
+
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(error) => {
+                if get_option(PrgOptions::ShowErrors) {
+                    eprint!(
+                        "{}",
+                        tr!(Msg::MetadataError, path_os.to_string_lossy(), error)
+                    );
+                }
+                continue;
+            }
+        };
 
-    return false;
+        if ignore_rules.is_ignored(&path_os, metadata.is_dir()) {
+            continue;
+        }
+
+        if metadata.is_file() {
+            p_visit(path_os, metadata);
+        } else if metadata.is_dir() && (*p_max_level == 0u64 || p_level < (*p_max_level as usize)) {
+            visit_files(p_max_level, 1 + p_level, &path_os, p_visit);
+        }
+    }
 }
 
-#[cfg(target_family = "unix")]
-/// Prints a symlink with indentation
+/// Recursively finds the empty directories within a directory
 ///
-/// Returns `false` if the symlink could be logged, true otherwise
+/// Returns whether the directory itself is empty (with `p_nested`, whether it holds nothing but empty directories)
 ///
 /// # Arguments
 ///
-/// - 'p_indent_width' - number of spaces to leave before printing the entry
-/// - 'p_metadata' - reference to the metadata of the symlink entry (used for getting the destination)
-/// - `p_path_os` - reference to the entry's path
-/// - 'p_is_dir' - whether the target of the symlink is a directory or not
-fn show_symlink(
-    p_indent_width: usize,
-    p_metadata: &fs::Metadata,
-    p_path_os: &path::Path,
-    p_is_dir: bool,
+/// - `p_max_level` - maximum level upto which subdirectories are traversed (a value of 0 denotes no limit)
+/// - `p_level` - level at which the entries of the current directory lie (0 for the initial directory)
+/// - `p_dir_path` - path of the current directory
+/// - `p_nested` - whether directories that hold nothing but empty directories are empty as well
+/// - `p_empty_dirs` - empty directories found so far
+fn find_empty_dirs(
+    p_max_level: &u64,
+    p_level: usize,
+    p_dir_path: &path::Path,
+    p_nested: bool,
+    p_empty_dirs: &mut Vec<path::PathBuf>,
 ) -> bool {
-    // borrow the filename (silently skip the current entry if this could not be done)
-    let Some(path) = p_path_os.file_name() else {
-        return true;
-    };
-
-    // get the canonicalized path name
-    let dest_path = match p_path_os.canonicalize() {
-        Ok(dest_path) => dest_path,
+    let entries = match record::read_dir_unrecorded(p_dir_path) {
+        Ok(values) => values,
         Err(error) => {
             if get_option(PrgOptions::ShowErrors) {
                 eprint!(
-                    "Error while reading target of symlink \"{}\"\n{}\n",
-                    path.to_string_lossy(),
-                    error
+                    "{}",
+                    tr!(Msg::IterateError, p_dir_path.to_string_lossy(), error)
                 );
             }
-            return true;
+            return false;
         }
     };
+    let ignore_rules = ignore::dir_rules(p_dir_path);
 
-    if get_option(PrgOptions::ShowPermissions) {
-        print_permissions!(p_metadata);
-    }
+    let mut empty = true;
+    for entry in entries {
+        // entries that could not be read still make the directory not empty
+        let Ok(entry) = entry else {
+            empty = false;
+            continue;
+        };
 
-    if get_option(PrgOptions::ShowLasttime) {
-        print_modif_time!(p_metadata, path.to_string_lossy());
+        let path_os = entry.path();
+        let is_dir = entry.file_type().is_ok_and(|file_type| file_type.is_dir());
+
+        // ignored directories are neither reported nor looked into, and subdirectories below the levels that are
+        // traversed are never known to be empty, so both count as contents
+        if !is_dir
+            || ignore_rules.is_ignored(&path_os, true)
+            || (*p_max_level != 0u64 && p_level >= (*p_max_level as usize))
+        {
+            empty = false;
+            continue;
+        }
+
+        let sub_empty = find_empty_dirs(p_max_level, 1 + p_level, &path_os, p_nested, p_empty_dirs);
+        empty &= p_nested && sub_empty;
     }
 
-    // if the target is a directory, enclose the symlink and the target within angled brackets <>
-    if p_is_dir {
-        print!(
-            "{:>20}    {:p_indent_width$}<{}> -> <{}>\n",
-            "SYMLINK",
-            "",
-            path.to_string_lossy(),
-            dest_path.to_string_lossy()
-        );
-    } else {
-        print!(
-            "{:>20}    {:p_indent_width$}{} -> {}\n",
-            "SYMLINK",
-            "",
-            path.to_string_lossy(),
-            dest_path.to_string_lossy()
-        );
+    if empty && p_level > 0 {
+        p_empty_dirs.push(p_dir_path.to_path_buf());
     }
 
-    return false;
+    return empty;
 }
 
-#[cfg(not(target_family = "unix"))]
-/// Prints a symlink with indentation
-///
-/// Returns `false` if the symlink could be logged, true otherwise
+/// Returns the device, inode and number of hard links of a file ([None] on other platforms)
 ///
 /// # Arguments
 ///
-/// - 'p_indent_width' - number of spaces to leave before printing the entry
-/// - '_p_metadata' - reference to the metadata of the symlink entry (used for getting the destination)
-/// - `p_path_os` - reference to the entry's path
-/// - 'p_is_dir' - whether the target of the symlink is a directory or not
-fn show_symlink(
-    p_indent_width: usize,
-    _p_metadata: &fs::Metadata,
-    p_path_os: &path::Path,
-    p_is_dir: bool,
-) -> bool {
-    // borrow the filename (silently skip the current entry if this could not be done)
-    let Some(path) = p_path_os.file_name() else {
-        return true;
-    };
-
-    // get the canonicalized path name
-    let dest_path = match p_path_os.canonicalize() {
-        Ok(dest_path) => dest_path,
-        Err(error) => {
-            if get_option(PrgOptions::ShowErrors) {
-                eprint!(
-                    "Error while reading target of symlink \"{}\"\n{}\n",
-                    path.to_string_lossy(),
-                    error
-                );
-            }
-            return true;
-        }
-    };
+/// - `p_metadata` - metadata of the file
+#[cfg_attr(not(target_family = "unix"), allow(unused_variables))]
+fn hardlink_info(p_metadata: &fs::Metadata) -> Option<(u64, u64, u64)> {
+    #[cfg(target_family = "unix")]
+    {
+        use std::os::unix::fs::MetadataExt;
 
-    // if the target is a directory, enclose the symlink and the target within angled brackets <>
-    if p_is_dir {
-        print!(
-            "{:>20}    {:p_indent_width$}<{}> -> <{}>\n",
-            "SYMLINK",
-            "",
-            path.to_string_lossy(),
-            dest_path.to_string_lossy()
-        );
-    } else {
-        print!(
-            "{:>20}    {:p_indent_width$}{} -> {}\n",
-            "SYMLINK",
-            "",
-            path.to_string_lossy(),
-            dest_path.to_string_lossy()
-        );
+        return Some((p_metadata.dev(), p_metadata.ino(), p_metadata.nlink()));
     }
 
-    return false;
+    #[cfg(not(target_family = "unix"))]
+    return None;
 }
 
-#[cfg(target_family = "unix")]
-/// Prints a file without indentation
+/// Splits a group of files with the same name into the groups of files that also have the same size and/or hash (as
+/// selected by the user), dropping the groups with a single file
 ///
-/// Returns `false` if the file could be logged, `true` otherwise
+/// Files that could not be hashed are reported (if errors are shown) and left out
 ///
 /// # Arguments
 ///
-/// - 'p_metadata' - reference to the metadata of the file entry (used for printing length)
-/// - 'p_indent_width' - number of spaces to leave before printing the entry
-/// - `p_path_os` - reference to the entry's path
-/// - 'p_file_len' - length of the file (in bytes)
-fn show_file_noindent(p_metadata: &fs::Metadata, p_path_os: &path::Path, p_file_len: &u64) -> bool {
-    let Ok(path) = p_path_os.canonicalize() else {
-        return true;
-    };
-
-    if get_option(PrgOptions::ShowPermissions) {
-        print_permissions!(p_metadata);
+/// - `p_arena` - arena that the directories of the files are stored in
+/// - `p_name` - name that the files share
+/// - `p_files` - files that share the same name
+fn split_name_collisions(
+    p_arena: &arena::PathArena,
+    p_name: &ffi::OsStr,
+    p_files: Vec<NamedFile>,
+) -> Vec<Vec<(NamedFile, Option<String>)>> {
+    let mut by_size: BTreeMap<u64, Vec<NamedFile>> = BTreeMap::new();
+
+    for file in p_files {
+        let size = match get_option(PrgOptions::SameSize) {
+            true => file.len,
+            false => 0,
+        };
+        by_size.entry(size).or_default().push(file);
     }
 
-    if get_option(PrgOptions::ShowLasttime) {
-        print_modif_time!(p_metadata, path.to_string_lossy());
-    }
+    let mut res = Vec::new();
 
-    print!(
-        "{:>20}    {}\n",
-        int_to_formatted_slice(*p_file_len),
-        path.to_string_lossy()
-    );
+    for files in by_size.into_values().filter(|files| files.len() > 1) {
+        if !get_option(PrgOptions::ShowHash) {
+            res.push(files.into_iter().map(|file| (file, None)).collect());
+            continue;
+        }
+
+        // the hash is only calculated for files whose name (and size) is shared with some other file
+        let mut by_hash: BTreeMap<String, Vec<(NamedFile, Option<String>)>> = BTreeMap::new();
+
+        for file in files {
+            // the metadata is read again since keeping it for every file would take up too much memory
+            let path_os = p_arena.path(file.dir, p_name);
+            let hashed = fs::symlink_metadata(&path_os)
+                .and_then(|metadata| hash::hash_file(&path_os, &metadata));
+
+            match hashed {
+                Ok(hash) => by_hash
+                    .entry(hash.clone())
+                    .or_default()
+                    .push((file, Some(hash))),
+                Err(error) => {
+                    if get_option(PrgOptions::ShowErrors) {
+                        eprint!("{}", tr!(Msg::HashError, path_os.to_string_lossy(), error));
+                    }
+                }
+            }
+        }
+
+        res.extend(by_hash.into_values().filter(|group| group.len() > 1));
+    }
 
-    return false;
+    return res;
 }
 
-#[cfg(not(target_family = "unix"))]
-/// Prints a file without indentation
+/// Returns the given hash updated with the given bytes using the 64-bit FNV-1a algorithm
 ///
-/// Returns `false` if the file could be logged, `true` otherwise
+/// FNV-1a is used since its output does not depend on the platform or the version of the compiler,
+/// so hashes calculated on different machines can be compared with each other
 ///
 /// # Arguments
 ///
-/// - 'p_metadata' - reference to the metadata of the file entry (used for printing length)
-/// - 'p_indent_width' - number of spaces to leave before printing the entry
-/// - `p_path_os` - reference to the entry's path
-/// - 'p_file_len' - length of the file (in bytes)
-fn show_file_noindent(
-    _p_metadata: &fs::Metadata,
-    p_path_os: &path::Path,
-    p_file_len: &u64,
-) -> bool {
-    let Ok(path) = p_path_os.canonicalize() else {
-        return true;
-    };
-
-    let path = path.to_string_lossy();
-
-    print!(
-        "{:>20}    {}\n",
-        int_to_formatted_slice(*p_file_len),
-        adjust_verbatim_unc(&path)
-    );
+/// - `p_hash` - the hash to update ([FNV_OFFSET_BASIS] to start a new hash)
+/// - `p_bytes` - the bytes to feed into the hash
+fn fnv1a_update(mut p_hash: u64, p_bytes: &[u8]) -> u64 {
+    for byte in p_bytes {
+        p_hash ^= *byte as u64;
+        p_hash = p_hash.wrapping_mul(FNV_PRIME);
+    }
 
-    return false;
+    return p_hash;
 }
 
-/// Prints a file with indentation
-///
-/// Returns `false` if the file could be logged, `true` otherwise
+/// Returns the given string with backslashes, tabs and newlines escaped, so that it can be stored as a single field of a line
 ///
 /// # Arguments
 ///
-/// - 'p_indent_width' - number of spaces to leave before printing the entry
-/// - 'p_metadata' - reference to the metadata of the file entry (used for printing length)
-/// - `p_path_os` - reference to the entry's path
-/// - 'p_file_len' - length of the file (in bytes)
-fn show_file(p_indent_width: usize, p_metadata: &fs::Metadata, p_path_os: &path::Path) -> bool {
-    let Some(path) = p_path_os.file_name() else {
-        return true;
-    };
+/// - `p_field` - the string to escape
+fn escape_line_field(p_field: &str) -> String {
+    return p_field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n");
+}
 
-    #[cfg(target_family = "unix")]
-    if get_option(PrgOptions::ShowPermissions) {
-        print_permissions!(p_metadata);
-    }
+/// Reverses the escaping done by [escape_line_field]
+///
+/// # Arguments
+///
+/// - `p_field` - the escaped string
+fn unescape_line_field(p_field: &str) -> String {
+    let mut res = String::with_capacity(p_field.len());
+    let mut chars = p_field.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            res.push(c);
+            continue;
+        }
 
-    #[cfg(target_family = "unix")]
-    if get_option(PrgOptions::ShowLasttime) {
-        print_modif_time!(p_metadata, path.to_string_lossy());
+        match chars.next() {
+            Some('t') => res.push('\t'),
+            Some('n') => res.push('\n'),
+            Some(c) => res.push(c),
+            None => res.push('\\'),
+        }
     }
 
-    print!(
-        "{:>20}    {:p_indent_width$}{}\n",
-        int_to_formatted_slice(p_metadata.len()),
-        "",
-        path.to_string_lossy()
-    );
-
-    return false;
+    return res;
 }
 
-#[cfg(target_family = "unix")]
-/// Prints a directory without indentation
+/// Recursively calculates the digest of a directory
 ///
-/// Returns `false` if the directory could be logged, `true` otherwise
+/// Digests of subdirectories that lie deeper than the maximum recursion depth are not stored, but the subdirectories
+/// are still traversed so that the cumulative values stay correct
 ///
 /// # Arguments
 ///
-/// - 'p_metadata' - reference to the metadata of the directory entry (used for getting the last modification time)
-/// - `p_path_os` - reference to the entry's path
-fn show_dir_noindent(p_metadata: &fs::Metadata, p_path_os: &path::Path) -> bool {
-    let Ok(path) = p_path_os.canonicalize() else {
-        return true;
+/// - `p_max_level` - maximum level upto which digests of subdirectories are stored (a value of 0 denotes no limit)
+/// - `p_level` - level at which the entries of the current directory are shown (0 for the initial directory)
+/// - `p_arena` - arena that the directories whose digests are stored are interned into
+/// - `p_dir` - the current directory, as stored in the arena (that of its parent if its digest is not stored)
+/// - `p_dir_path` - path of the current directory
+fn build_fingerprint(
+    p_max_level: &u64,
+    p_level: usize,
+    p_arena: &mut arena::PathArena,
+    p_dir: arena::DirId,
+    p_dir_path: &path::Path,
+) -> Fingerprint {
+    let mut fingerprint = Fingerprint {
+        dir: p_dir,
+        counts: EntryCounter::new(),
+        size: 0,
+        newest: 0,
+        name_hash: None,
+        children: Vec::new(),
     };
 
-    // see if the directory size needs to be printed (if yes, then check if it can be calculated)
-    let sz = if get_option(PrgOptions::ShowDirSize) {
-        if let Some(size) = calc_dir_size(&p_path_os, &p_path_os) {
-            int_to_formatted_slice(size)
-        } else {
-            "ERROR"
-        }
-    } else {
-        ""
-    };
+    let keep_children = p_level == 0
+        || (get_option(PrgOptions::ShowRecursive)
+            && (*p_max_level == 0u64 || p_level <= (*p_max_level as usize)));
 
-    if get_option(PrgOptions::ShowPermissions) {
-        print_permissions!(p_metadata);
-    }
+    let entries = match record::read_dir_unrecorded(p_dir_path) {
+        Ok(values) => values,
+        Err(error) => {
+            if get_option(PrgOptions::ShowErrors) {
+                eprint!(
+                    "{}",
+                    tr!(Msg::IterateError, p_dir_path.to_string_lossy(), error)
+                );
+            }
+            return fingerprint;
+        }
+    };
+    let ignore_rules = ignore::dir_rules(p_dir_path);
 
-    if get_option(PrgOptions::ShowLasttime) {
-        print_modif_time!(p_metadata, path.to_string_lossy());
-    }
+    // the names are hashed in sorted order, since the order in which entries are read differs between filesystems
+    let mut names: Vec<(String, u8, Option<u64>)> = Vec::new();
+    let mut complete = true;
 
-    print!("{:>20}    <{}>\n", sz, path.to_string_lossy());
+    for entry in entries {
+        let Ok(entry) = entry else {
+            complete = false;
+            continue;
+        };
 
-    return false;
-}
+        let path_os = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
 
-#[cfg(not(target_family = "unix"))]
-/// Prints a directory without indentation
-///
-/// Returns `false` if the directory could be logged, `true` otherwise
-///
-/// # Arguments
-///
-/// - 'p_metadata' - reference to the metadata of the directory entry (used for getting the last modification time)
-/// - `p_path_os` - reference to the entry's path
-fn show_dir_noindent(_p_metadata: &fs::Metadata, p_path_os: &path::Path) -> bool {
-    let Ok(path) = p_path_os.canonicalize() else {
-        return true;
-    };
+        let Ok(metadata) = entry.metadata() else {
+            complete = false;
+            continue;
+        };
 
-    let path = path.to_string_lossy();
+        if ignore_rules.is_ignored(&path_os, metadata.is_dir()) {
+            continue;
+        }
 
-    // see if the directory size needs to be printed (if yes, then check if it can be calculated)
-    let sz = if get_option(PrgOptions::ShowDirSize) {
-        if let Some(size) = calc_dir_size(&p_path_os, &p_path_os) {
-            int_to_formatted_slice(size)
+        if let Ok(time) = metadata.modified() {
+            if let Ok(time) = time.duration_since(std::time::UNIX_EPOCH) {
+                fingerprint.newest = fingerprint.newest.max(time.as_secs());
+            }
+        }
+
+        if metadata.is_symlink() {
+            fingerprint.counts.inc_symlink_cnt(1);
+            names.push((name, b'l', None));
+        } else if metadata.is_file() {
+            fingerprint.counts.inc_file_cnt(1);
+            fingerprint.size += metadata.len();
+            names.push((name, b'f', None));
+        } else if metadata.is_dir() {
+            let child_dir = match keep_children {
+                true => p_arena.intern_dir(&path_os),
+                false => p_dir,
+            };
+            let child = build_fingerprint(p_max_level, 1 + p_level, p_arena, child_dir, &path_os);
+
+            fingerprint.counts.inc_file_cnt(child.counts.get_file_cnt());
+            fingerprint
+                .counts
+                .inc_symlink_cnt(child.counts.get_symlink_cnt());
+            fingerprint
+                .counts
+                .inc_special_cnt(child.counts.get_special_cnt());
+            fingerprint
+                .counts
+                .inc_dir_cnt(1 + child.counts.get_dir_cnt());
+            fingerprint.size += child.size;
+            fingerprint.newest = fingerprint.newest.max(child.newest);
+
+            complete = complete && child.name_hash.is_some();
+            names.push((name, b'd', child.name_hash));
+
+            if keep_children {
+                fingerprint.children.push(child);
+            }
         } else {
-            "ERROR"
+            fingerprint.counts.inc_special_cnt(1);
+            names.push((name, b's', None));
         }
-    } else {
-        ""
-    };
+    }
+
+    names.sort();
+    let arena = &*p_arena;
+    fingerprint
+        .children
+        .sort_by(|a, b| arena.name(a.dir).cmp(arena.name(b.dir)));
+
+    if complete {
+        let mut hash = FNV_OFFSET_BASIS;
+        for (name, kind, child_hash) in &names {
+            hash = fnv1a_update(hash, name.as_bytes());
+            hash = fnv1a_update(hash, &[0, *kind]);
+            if let Some(child_hash) = child_hash {
+                hash = fnv1a_update(hash, &child_hash.to_le_bytes());
+            }
+        }
+        fingerprint.name_hash = Some(hash);
+    }
 
-    print!("{:>20}    <{}>\n", sz, adjust_verbatim_unc(&path));
+    return fingerprint;
+}
 
-    return false;
+/// A file opened for reading its contents (see [open_content_file])
+///
+/// With the no cache pollution option, it remembers which of the pages of the file were already cached before it was
+/// opened, so that only the pages that reading it pulled into the cache are dropped again
+struct ContentFile {
+    /// The file
+    file: fs::File,
+    /// Offset up to which the contents have been read
+    read_len: u64,
+    /// Offset up to which the cached pages have been dropped
+    released_len: u64,
+    /// Whether each page of the file was cached before it was opened, in the format of `mincore` ([None] if it is not
+    /// known, in which case every page read is dropped)
+    #[cfg_attr(not(any(target_os = "linux", target_os = "android")), allow(dead_code))]
+    resident: Option<Vec<u8>>,
 }
 
-#[cfg(target_family = "unix")]
-/// Prints a directory with indentation
+impl std::io::Read for ContentFile {
+    fn read(&mut self, p_buff: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.file.read(p_buff)?;
+        self.read_len += read as u64;
+        return Ok(read);
+    }
+}
+
+/// Opens a file for reading its contents
 ///
-/// Returns `false` if the directory could be logged, `true` otherwise
+/// If the no cache pollution option is set, the kernel is told that the file will be read only once, sequentially,
+/// and the pages of the file that are already cached are noted (on macOS, caching is disabled for the file altogether)
 ///
 /// # Arguments
 ///
-/// - 'p_indent_width' - number of spaces to leave before printing the entry
-/// - `p_path_os` - reference to the entry's path
-fn show_dir(p_indent_width: usize, p_metadata: &fs::Metadata, p_path_os: &path::Path) -> bool {
-    let Some(path) = p_path_os.file_name() else {
-        return true;
-    };
+/// - `p_path` - path of the file to open
+fn open_content_file(p_path: &path::Path) -> std::io::Result<ContentFile> {
+    let file = fs::File::open(p_path)?;
 
-    // see if the directory size needs to be printed (if yes, then check if it can be calculated)
-    // if it need not be printed, simply put an empty string
-    // if it needs to be printed and can be calculated, format and print it
-    // it if needs to be printed and can not be calculated, print ERROR
-    let sz = if get_option(PrgOptions::ShowDirSize) {
-        if let Some(size) = calc_dir_size(&p_path_os, &p_path_os) {
-            int_to_formatted_slice(size)
-        } else {
-            "ERROR"
+    if get_option(PrgOptions::NoCachePollution) {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        unsafe {
+            use std::os::unix::io::AsRawFd;
+
+            libc::posix_fadvise(file.as_raw_fd(), 0, 0, libc::POSIX_FADV_SEQUENTIAL);
         }
-    } else {
-        ""
-    };
 
-    if get_option(PrgOptions::ShowPermissions) {
-        print_permissions!(p_metadata);
-    }
+        #[cfg(target_os = "macos")]
+        unsafe {
+            use std::os::unix::io::AsRawFd;
 
-    if get_option(PrgOptions::ShowLasttime) {
-        print_modif_time!(p_metadata, path.to_string_lossy());
+            libc::fcntl(file.as_raw_fd(), libc::F_NOCACHE, 1);
+        }
     }
 
-    print!(
-        "{:>20}    {:p_indent_width$}<{}>\n",
-        sz,
-        "",
-        path.to_string_lossy()
-    );
-
-    return false;
+    // the pages that are already cached are noted before any of the file is read
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    let resident = match get_option(PrgOptions::NoCachePollution) {
+        true => cached_pages(&file),
+        false => None,
+    };
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    let resident = None;
+
+    return Ok(ContentFile {
+        file,
+        read_len: 0,
+        released_len: 0,
+        resident,
+    });
 }
 
-#[cfg(not(target_family = "unix"))]
-/// Prints a directory with indentation
+/// Returns whether each page of a file is cached, in the format of `mincore` ([None] if it could not be found out)
 ///
-/// Returns `false` if the directory could be logged, `true` otherwise
+/// The file is mapped without being read, so finding out does not pull any of its pages into the cache
 ///
 /// # Arguments
 ///
-/// - 'p_indent_width' - number of spaces to leave before printing the entry
-/// - 'p_metadata' - reference to the metadata of the directory entry (used for getting the last modification time)
-/// - `p_path_os` - reference to the entry's path
-fn show_dir(p_indent_width: usize, _p_metadata: &fs::Metadata, p_path_os: &path::Path) -> bool {
-    let Some(path) = p_path_os.file_name() else {
-        return true;
-    };
+/// - `p_file` - the file
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn cached_pages(p_file: &fs::File) -> Option<Vec<u8>> {
+    use std::os::unix::io::AsRawFd;
+
+    let len = p_file.metadata().ok()?.len() as usize;
+    if len == 0 {
+        return Some(Vec::new());
+    }
 
-    // see if the directory size needs to be printed (if yes, then check if it can be calculated)
-    // if it need not be printed, simply put an empty string
-    // if it needs to be printed and can be calculated, format and print it
-    // it if needs to be printed and can not be calculated, print ERROR
-    let sz = if get_option(PrgOptions::ShowDirSize) {
-        if let Some(size) = calc_dir_size(&p_path_os, &p_path_os) {
-            int_to_formatted_slice(size)
-        } else {
-            "ERROR"
+    unsafe {
+        let page_len = libc::sysconf(libc::_SC_PAGESIZE) as usize;
+        let addr = libc::mmap(
+            std::ptr::null_mut(),
+            len,
+            libc::PROT_READ,
+            libc::MAP_SHARED,
+            p_file.as_raw_fd(),
+            0,
+        );
+        if addr == libc::MAP_FAILED {
+            return None;
         }
-    } else {
-        ""
-    };
 
-    print!(
-        "{:>20}    {:p_indent_width$}<{}>\n",
-        sz,
-        "",
-        path.to_string_lossy()
-    );
+        let mut pages = vec![0u8; len.div_ceil(page_len)];
+        let res = libc::mincore(addr, len, pages.as_mut_ptr());
+        libc::munmap(addr, len);
 
-    return false;
+        return match res {
+            0 => Some(pages),
+            _ => None,
+        };
+    }
 }
 
-#[cfg(target_family = "unix")]
-/// Prints a special file without indentation
+/// Asks the kernel to drop the cached pages of a file opened using [open_content_file] that were read since the last
+/// call (except those that were already cached before the file was opened)
 ///
-/// Returns `false` if the special file could be logged, `true` otherwise
+/// Has no effect if the no cache pollution option is not set, or if the platform does not support it
 ///
 /// # Arguments
 ///
-/// - 'p_metadata' - reference to the metadata of the special file entry (used for getting the last modification time)
-/// - `p_path_os` - reference to the entry's path
-/// - 'p_special_file_type' - the type of special file ([SpecialFileType::NA] on windows)
-fn show_special_noindent(
-    p_metadata: &fs::Metadata,
-    p_path_os: &path::Path,
-    p_special_file_type: &SpecialFileType,
-) -> bool {
-    let Ok(path) = p_path_os.canonicalize() else {
-        return true;
-    };
+/// - `p_file` - the file whose cached pages are to be dropped
+fn release_cached_pages(p_file: &mut ContentFile) {
+    p_file.release_pages(p_file.read_len);
+}
 
-    let special_type = match p_special_file_type {
-        SpecialFileType::Socket => "SOCKET",
-        SpecialFileType::BlockDevice => "BLOCK DEVICE",
-        SpecialFileType::CharDevice => "CHAR DEVICE",
-        SpecialFileType::Fifo => "FIFO PIPE",
-        _ => "SPECIAL",
-    };
+impl ContentFile {
+    /// Asks the kernel to drop the cached pages of the file up to an offset that were not dropped yet (except those
+    /// that were already cached before the file was opened)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_end_len` - offset up to which the pages are dropped
+    #[allow(unused_variables)]
+    fn release_pages(&mut self, p_end_len: u64) {
+        if !get_option(PrgOptions::NoCachePollution) || p_end_len <= self.released_len {
+            return;
+        }
 
-    if get_option(PrgOptions::ShowPermissions) {
-        print_permissions!(p_metadata);
-    }
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        unsafe {
+            use std::os::unix::io::AsRawFd;
+
+            let fd = self.file.as_raw_fd();
+            let page_len = libc::sysconf(libc::_SC_PAGESIZE) as u64;
+            let first_page = self.released_len / page_len;
+            let end_page = p_end_len.div_ceil(page_len);
+
+            // the pages are dropped in runs of those that were not cached before (pages beyond the ones that were
+            // checked, such as of a file that grew since it was opened, were not cached either)
+            let mut run_start: Option<u64> = None;
+            for page in first_page..=end_page {
+                let was_cached = page < end_page
+                    && self
+                        .resident
+                        .as_ref()
+                        .and_then(|resident| resident.get(page as usize))
+                        .is_some_and(|state| state & 1 == 1);
+
+                match (run_start, page < end_page && !was_cached) {
+                    (None, true) => run_start = Some(page),
+                    (Some(start), false) => {
+                        libc::posix_fadvise(
+                            fd,
+                            (start * page_len) as libc::off_t,
+                            ((page - start) * page_len) as libc::off_t,
+                            libc::POSIX_FADV_DONTNEED,
+                        );
+                        run_start = None;
+                    }
+                    _ => {}
+                }
+            }
+        }
 
-    if get_option(PrgOptions::ShowLasttime) {
-        print_modif_time!(p_metadata, path.to_string_lossy());
+        self.released_len = p_end_len;
     }
+}
 
-    print!("{:>20}    {}\n", special_type, path.to_string_lossy());
-    return false;
+impl Drop for ContentFile {
+    fn drop(&mut self) {
+        // the kernel reads ahead of what was read, so once the file is closed, the pages up to its end are dropped
+        let len = match self.file.metadata() {
+            Ok(metadata) => metadata.len().max(self.read_len),
+            Err(_) => self.read_len,
+        };
+        self.release_pages(len);
+    }
 }
 
-#[cfg(not(target_family = "unix"))]
-/// Prints a special file without indentation
+/// Fills the given buffer as much as possible from a reader and returns the number of bytes read
 ///
-/// Returns `false` if the special file could be logged, `true` otherwise
+/// The number of bytes read is less than the length of the buffer only if the end of the reader was reached
 ///
 /// # Arguments
 ///
-/// - 'p_metadata' - reference to the metadata of the special file entry (used for getting the last modification time)
-/// - `p_path_os` - reference to the entry's path
-/// - 'p_special_file_type' - the type of special file ([SpecialFileType::NA] on windows)
-fn show_special_noindent(
-    _p_metadata: &fs::Metadata,
-    p_path_os: &path::Path,
-    _p_special_file_type: &SpecialFileType,
-) -> bool {
-    let Ok(path) = p_path_os.canonicalize() else {
-        return true;
-    };
-
-    let path = path.to_string_lossy();
-
-    let special_type = "SPECAL";
+/// - `p_reader` - the reader to read from
+/// - `p_buff` - the buffer to fill
+fn read_block(p_reader: &mut impl std::io::Read, p_buff: &mut [u8]) -> std::io::Result<usize> {
+    let mut len = 0;
+
+    while len < p_buff.len() {
+        let read = p_reader.read(&mut p_buff[len..])?;
+        if read == 0 {
+            break;
+        }
+        len += read;
+    }
 
-    print!("{:>20}    {}\n", special_type, adjust_verbatim_unc(&path));
-    return false;
+    return Ok(len);
 }
 
-#[cfg(target_family = "unix")]
-/// Prints a directory with indentation
+/// Returns the number of bytes of a file that differ from an older version of the same file
 ///
-/// Returns `false` if the special file could be logged, `true` otherwise
+/// Both files are compared block-by-block, and every block that differs (or is missing in the older version) is
+/// counted fully. The block size is chosen the same way as rsync (the square root of the file's length). Unlike rsync,
+/// blocks are only compared at the same offset, so data that has moved within the file is counted as changed
+///
+/// Returns [None] if either of the files could not be read
 ///
 /// # Arguments
 ///
-/// - 'p_indent_width' - number of spaces to leave before printing the entry
-/// - 'p_metadata' - reference to the metadata of the special file entry (used for getting the last modification time)
-/// - `p_path_os` - reference to the entry's path
-/// - 'p_special_file_type' - the type of special file ([SpecialFileType::NA] on windows)
-fn show_special(
-    p_indent_width: usize,
-    p_metadata: &fs::Metadata,
-    p_path_os: &path::Path,
-    p_special_file_type: &SpecialFileType,
-) -> bool {
-    let Some(path) = p_path_os.file_name() else {
-        return true;
-    };
+/// - `p_src_path` - path of the new version of the file
+/// - `p_dst_path` - path of the old version of the file
+/// - `p_src_len` - length of the new version of the file
+fn calc_changed_block_bytes(
+    p_src_path: &path::Path,
+    p_dst_path: &path::Path,
+    p_src_len: u64,
+) -> Option<u64> {
+    let block_len = ((p_src_len as f64).sqrt() as u64 & !7).clamp(MIN_BLOCK_LEN, MAX_BLOCK_LEN);
+
+    let mut src = std::io::BufReader::new(open_content_file(p_src_path).ok()?);
+    let mut dst = std::io::BufReader::new(open_content_file(p_dst_path).ok()?);
+
+    let mut src_block = vec![0u8; block_len as usize];
+    let mut dst_block = vec![0u8; block_len as usize];
 
-    let special_type = match p_special_file_type {
-        SpecialFileType::Socket => "SOCKET",
-        SpecialFileType::BlockDevice => "BLOCK DEVICE",
-        SpecialFileType::CharDevice => "CHAR DEVICE",
-        SpecialFileType::Fifo => "FIFO PIPE",
-        _ => "SPECIAL",
-    };
+    let mut res: u64 = 0;
+    let mut offset: u64 = 0;
 
-    if get_option(PrgOptions::ShowPermissions) {
-        print_permissions!(p_metadata);
-    }
+    loop {
+        let src_read = read_block(&mut src, &mut src_block).ok()?;
+        if src_read == 0 {
+            break;
+        }
 
-    if get_option(PrgOptions::ShowLasttime) {
-        print_modif_time!(p_metadata, path.to_string_lossy());
+        let dst_read = read_block(&mut dst, &mut dst_block).ok()?;
+
+        if src_read != dst_read || src_block[..src_read] != dst_block[..dst_read] {
+            res += src_read as u64;
+        }
+
+        // periodically drop the pages read so far, so that huge files do not fill up the page cache
+        offset += src_read as u64;
+        if offset % CACHE_RELEASE_INTERVAL < src_read as u64 {
+            release_cached_pages(src.get_mut());
+            release_cached_pages(dst.get_mut());
+        }
     }
 
-    print!(
-        "{:>20}    {:p_indent_width$}{}\n",
-        special_type,
-        "",
-        path.to_string_lossy()
-    );
-    return false;
+    release_cached_pages(src.get_mut());
+    release_cached_pages(dst.get_mut());
+
+    return Some(res);
 }
 
-#[cfg(not(target_family = "unix"))]
-/// Prints a directory with indentation
-///
-/// Returns `false` if the special file could be logged, `true` otherwise
+/// Returns the entries of a source directory of a transfer estimate, each along with its metadata (the entries that
+/// could not be read and the ignored ones are left out)
 ///
 /// # Arguments
 ///
-/// - 'p_indent_width' - number of spaces to leave before printing the entry
-/// - 'p_metadata' - reference to the metadata of the special file entry (used for getting the last modification time)
-/// - `p_path_os` - reference to the entry's path
-/// - 'p_special_file_type' - the type of special file ([SpecialFileType::NA] on windows)
-fn show_special(
-    p_indent_width: usize,
-    _p_metadata: &fs::Metadata,
-    p_path_os: &path::Path,
-    _p_special_file_type: &SpecialFileType,
-) -> bool {
-    let Some(path) = p_path_os.file_name() else {
-        return true;
+/// - `p_side` - the side that the directory is read from
+/// - `p_dir_path` - path of the directory
+fn transfer_src_entries(
+    p_side: &TransferSide,
+    p_dir_path: &path::Path,
+) -> std::io::Result<Vec<(path::PathBuf, record::EntryMetadata)>> {
+    // recordings already leave out the entries that were ignored while they were made
+    let TransferSide::Live = p_side else {
+        return p_side.read_dir(p_dir_path);
     };
 
-    let special_type = "SPECIAL";
+    let ignore_rules = ignore::dir_rules(p_dir_path);
+    return Ok(record::read_dir_unrecorded(p_dir_path)?
+        .flatten()
+        .filter_map(|entry| Some((entry.path(), entry.metadata().ok()?)))
+        .filter(|(path_os, metadata)| !ignore_rules.is_ignored(path_os, metadata.is_dir()))
+        .map(|(path_os, metadata)| (path_os, record::EntryMetadata::from_metadata(metadata)))
+        .collect());
+}
 
-    print!(
-        "{:>20}    {:p_indent_width$}{}\n",
-        special_type,
-        "",
-        path.to_string_lossy()
-    );
-    return false;
+/// Returns the metadata of each entry of a destination directory of a transfer estimate by its name (empty if the
+/// directory does not exist or could not be read)
+///
+/// # Arguments
+///
+/// - `p_side` - the side that the directory is read from
+/// - `p_dir_path` - path of the directory
+fn transfer_dst_entries(
+    p_side: &TransferSide,
+    p_dir_path: &path::Path,
+) -> HashMap<OsString, record::EntryMetadata> {
+    // a missing destination directory is expected, so it is neither counted nor handled like the unreadable ones
+    let entries = match p_side {
+        TransferSide::Live => fs::read_dir(p_dir_path).map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    let metadata = entry.metadata().ok()?;
+                    Some((entry.path(), record::EntryMetadata::from_metadata(metadata)))
+                })
+                .collect()
+        }),
+        TransferSide::Recorded(_) => p_side.read_dir(p_dir_path),
+    };
+
+    return entries
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(path_os, metadata)| Some((path_os.file_name()?.to_owned(), metadata)))
+        .collect();
 }
 
-/// Scans through directory given its path and prints its contents based on the flags given
+/// Recursively compares a directory with another and accumulates the amount of data that would need to be transferred
+/// to make the destination the same as the source
 ///
-/// Returns None on success and [`std::io::Error`](std::io::Error) if an error was encountered (propagates the error up the stack)
-fn scan_path(
-    p_entry_cnts_init: &mut EntryCounter,
-    p_entry_cnts_full: &mut EntryCounter,
-    p_max_level: &u64,
-    p_level: usize,
-    p_current_path: &path::Path,
+/// Like rsync, a file is considered unchanged if its size and modification time (in seconds) are the same in both directories
+///
+/// # Arguments
+///
+/// - `p_estimate` - the estimate to accumulate into
+/// - `p_src` - the side that the source directory is read from
+/// - `p_src_path` - path of the source directory
+/// - `p_dst` - the side that the destination directory is read from
+/// - `p_dst_path` - path of the corresponding destination directory (need not exist)
+fn estimate_transfer(
+    p_estimate: &mut TransferEstimate,
+    p_src: &TransferSide,
+    p_src_path: &path::Path,
+    p_dst: &TransferSide,
+    p_dst_path: &path::Path,
 ) -> Option<std::io::Error> {
-    // calculate the indent width to be used while printing the entries in the current directory
-    let indent_width = INDENT_COL_WIDTH * p_level;
-    // instantiate structure to hold the number of entries of each type in the current directory (not recursive)
-    let mut cur_entry_cnts = EntryCounter::new();
-    // total size of files in the current directory (only used when printing summary)
-    let mut total_file_size: u64 = 0;
-
-    // try to read the entries of the current directory
-    // if the entries could not be iterated over (for example, due to insufficient permissions or the current entry being a file)
-    // then return from the function and report this to the caller
-    let entries = match fs::read_dir(&p_current_path) {
+    let entries = match transfer_src_entries(p_src, p_src_path) {
         Ok(values) => values,
         Err(error) => {
             return Some(error);
         }
     };
+    let dst_entries = transfer_dst_entries(p_dst, p_dst_path);
 
-    for entry in entries {
-        // if the current entry could not be found for some reason, then silently skip it
-        let Ok(entry) = entry else {
+    for (path_os, metadata) in entries {
+        let Some(name) = path_os.file_name() else {
             continue;
         };
+        let dst_path_os = p_dst_path.join(name);
 
-        // get the metadata about this entry (will be used to query its type and in the case of regular files, its size)
-        // if the metadata could not be queries, silently skip this entry
-        let Ok(metadata) = entry.metadata() else {
+        if metadata.is_symlink() {
             continue;
-        };
+        }
 
-        // get the path to the current entry
-        let path_os = entry.path();
+        if metadata.is_file() {
+            let Some(dst_metadata) = dst_entries.get(name) else {
+                p_estimate.new_files += 1;
+                p_estimate.new_bytes += metadata.len();
+                continue;
+            };
 
-        // check for special file (on unix style operating systems, get the specific type as well)
-        #[cfg(target_family = "unix")]
-        let special_file_type = {
-            use std::os::unix::fs::FileTypeExt;
-
-            if metadata.file_type().is_socket() {
-                SpecialFileType::Socket
-            } else if metadata.file_type().is_block_device() {
-                SpecialFileType::BlockDevice
-            } else if metadata.file_type().is_char_device() {
-                SpecialFileType::CharDevice
-            } else if metadata.file_type().is_fifo() {
-                SpecialFileType::Fifo
-            } else {
-                SpecialFileType::NA
+            let modif_secs = |p_metadata: &record::EntryMetadata| {
+                p_metadata
+                    .modified()
+                    .ok()
+                    .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|time| time.as_secs())
+            };
+
+            if dst_metadata.is_file()
+                && dst_metadata.len() == metadata.len()
+                && modif_secs(dst_metadata) == modif_secs(&metadata)
+            {
+                p_estimate.unchanged_files += 1;
+                continue;
+            }
+
+            // recordings do not hold the contents of the files, so they can only be compared block-by-block if both
+            // sides are read from the filesystem (if the block-level estimate could not be calculated, assume that the
+            // whole file needs to be sent)
+            let changed_bytes = if get_option(PrgOptions::BlockEstimate)
+                && dst_metadata.is_file()
+                && matches!((p_src, p_dst), (TransferSide::Live, TransferSide::Live))
+            {
+                calc_changed_block_bytes(&path_os, &dst_path_os, metadata.len())
+                    .unwrap_or(metadata.len())
+            } else {
+                metadata.len()
+            };
+
+            p_estimate.changed_files += 1;
+            p_estimate.changed_bytes += changed_bytes;
+        } else if metadata.is_dir() {
+            if let Some(error) = estimate_transfer(p_estimate, p_src, &path_os, p_dst, &dst_path_os)
+            {
+                if get_option(PrgOptions::ShowErrors) {
+                    eprint!(
+                        "{}",
+                        tr!(Msg::IterateError, path_os.to_string_lossy(), error)
+                    );
+                }
+            }
+        }
+    }
+
+    return None;
+}
+
+#[cfg(not(target_family = "unix"))]
+/// Removes the verbatim "\\?\" prefix in UNC paths on windows
+///
+/// # Arguments
+///
+/// - 'p_path' - the path from which the verbatim prefix is to be removed
+fn adjust_verbatim_unc(p_path: &str) -> &str {
+    const VERBATIM_UNC_PREFIX: &str = r#"\\?\"#;
+    const VERBATIM_UNC_PREFIX_LEN: usize = VERBATIM_UNC_PREFIX.len();
+
+    if p_path.starts_with(VERBATIM_UNC_PREFIX) {
+        return &p_path[VERBATIM_UNC_PREFIX_LEN..];
+    }
+
+    return p_path;
+}
+
+#[cfg(target_family = "unix")]
+/// Prints a symlink without indentation
+///
+/// Returns `false` if the symlink could be logged, `true` otherwise
+///
+/// # Arguments
+///
+/// - 'p_metadata' - reference to the metadata of the symlink entry (used for getting the destination)
+/// - `p_path_os` - reference to the entry's path
+/// - 'p_is_dir' - whether the target of the symlink is a directory or not
+fn show_symlink_noindent(
+    p_metadata: &record::EntryMetadata,
+    p_path_os: &path::Path,
+    p_is_dir: bool,
+) -> bool {
+    // borrow the filename (silently skip the current entry if this could not be done)
+    let path = p_path_os.to_string_lossy();
+
+    // get the canonicalized path name (print the error and exit if this could not be done)
+    let dest_path = match record::canonicalize(p_path_os) {
+        Ok(dest_path) => dest_path,
+        // broken symlinks are shown with the path stored in them while they are looked for
+        Err(_) if filter::broken_symlinks() => {
+            record::read_link(p_path_os).unwrap_or(path::PathBuf::from("?"))
+        }
+        Err(error) => {
+            if get_option(PrgOptions::ShowErrors) {
+                eprint!("{}", tr!(Msg::SymlinkTargetError, path, error));
+            }
+            return true;
+        }
+    };
+
+    let color = entry_color(p_path_os, p_metadata, None);
+
+    // if the target is a directory, enclose the symlink and target within angle brackets <>
+    if p_is_dir {
+        return columns::print_row(
+            Some((p_path_os, p_metadata)),
+            "SYMLINK",
+            None,
+            format_args!(
+                "{}<{}>{} -> <{}>",
+                color,
+                escape::name(p_path_os),
+                color::end(color),
+                escape::name(&dest_path)
+            ),
+        );
+    }
+
+    return columns::print_row(
+        Some((p_path_os, p_metadata)),
+        "SYMLINK",
+        None,
+        format_args!(
+            "{}{}{} -> {}",
+            color,
+            escape::name(p_path_os),
+            color::end(color),
+            escape::name(&dest_path)
+        ),
+    );
+}
+
+#[cfg(not(target_family = "unix"))]
+/// Prints a symlink without indentation
+///
+/// Returns `false` if the symlink could be logged, `true` otherwise
+///
+/// # Arguments
+///
+/// - 'p_metadata' - reference to the metadata of the symlink entry (used for getting the destination)
+/// - `p_path_os` - reference to the entry's path
+/// - 'p_is_dir' - whether the target of the symlink is a directory or not
+fn show_symlink_noindent(
+    p_metadata: &record::EntryMetadata,
+    p_path_os: &path::Path,
+    p_is_dir: bool,
+) -> bool {
+    // borrow the filename (silently skip the current entry if this could not be done)
+    let path = p_path_os.to_string_lossy();
+
+    // get the canonicalized path name (print the error and exit if this could not be done)
+    let dest_path = match record::canonicalize(p_path_os) {
+        Ok(dest_path) => dest_path,
+        // broken symlinks are shown with the path stored in them while they are looked for
+        Err(_) if filter::broken_symlinks() => {
+            record::read_link(p_path_os).unwrap_or(path::PathBuf::from("?"))
+        }
+        Err(error) => {
+            if get_option(PrgOptions::ShowErrors) {
+                eprint!("{}", tr!(Msg::SymlinkTargetError, path, error));
+            }
+            return true;
+        }
+    };
+
+    let dest_path = dest_path.to_string_lossy();
+
+    let color = entry_color(p_path_os, p_metadata, None);
+
+    // if the target is a directory, enclose the symlink and target within angle brackets <>
+    if p_is_dir {
+        return columns::print_row(
+            Some((p_path_os, p_metadata)),
+            "SYMLINK",
+            None,
+            format_args!(
+                "{}<{}>{} -> <{}>",
+                color,
+                escape::text(adjust_verbatim_unc(&path)),
+                color::end(color),
+                escape::text(adjust_verbatim_unc(&dest_path))
+            ),
+        );
+    }
+
+    return columns::print_row(
+        Some((p_path_os, p_metadata)),
+        "SYMLINK",
+        None,
+        format_args!(
+            "{}{}{} -> {}",
+            color,
+            escape::text(adjust_verbatim_unc(&path)),
+            color::end(color),
+            escape::text(adjust_verbatim_unc(&dest_path))
+        ),
+    );
+}
+
+#[cfg(target_family = "unix")]
+/// Prints a symlink with indentation
+///
+/// Returns `false` if the symlink could be logged, true otherwise
+///
+/// # Arguments
+///
+/// - 'p_indent_width' - number of spaces to leave before printing the entry
+/// - 'p_metadata' - reference to the metadata of the symlink entry (used for getting the destination)
+/// - `p_path_os` - reference to the entry's path
+/// - 'p_is_dir' - whether the target of the symlink is a directory or not
+fn show_symlink(
+    p_indent_width: usize,
+    p_metadata: &record::EntryMetadata,
+    p_path_os: &path::Path,
+    p_is_dir: bool,
+) -> bool {
+    // borrow the filename (silently skip the current entry if this could not be done)
+    let Some(path) = p_path_os.file_name() else {
+        return true;
+    };
+
+    // get the canonicalized path name
+    let dest_path = match record::canonicalize(p_path_os) {
+        Ok(dest_path) => dest_path,
+        // broken symlinks are shown with the path stored in them while they are looked for
+        Err(_) if filter::broken_symlinks() => {
+            record::read_link(p_path_os).unwrap_or(path::PathBuf::from("?"))
+        }
+        Err(error) => {
+            if get_option(PrgOptions::ShowErrors) {
+                eprint!(
+                    "{}",
+                    tr!(Msg::SymlinkTargetError, path.to_string_lossy(), error)
+                );
+            }
+            return true;
+        }
+    };
+
+    let color = entry_color(p_path_os, p_metadata, None);
+
+    // if the target is a directory, enclose the symlink and the target within angled brackets <>
+    if p_is_dir {
+        return columns::print_row(
+            Some((p_path_os, p_metadata)),
+            "SYMLINK",
+            Some(p_indent_width),
+            format_args!(
+                "{}<{}>{} -> <{}>",
+                color,
+                escape::name(&path),
+                color::end(color),
+                escape::name(&dest_path)
+            ),
+        );
+    }
+
+    return columns::print_row(
+        Some((p_path_os, p_metadata)),
+        "SYMLINK",
+        Some(p_indent_width),
+        format_args!(
+            "{}{}{} -> {}",
+            color,
+            escape::name(&path),
+            color::end(color),
+            escape::name(&dest_path)
+        ),
+    );
+}
+
+#[cfg(not(target_family = "unix"))]
+/// Prints a symlink with indentation
+///
+/// Returns `false` if the symlink could be logged, true otherwise
+///
+/// # Arguments
+///
+/// - 'p_indent_width' - number of spaces to leave before printing the entry
+/// - '_p_metadata' - reference to the metadata of the symlink entry (used for getting the destination)
+/// - `p_path_os` - reference to the entry's path
+/// - 'p_is_dir' - whether the target of the symlink is a directory or not
+fn show_symlink(
+    p_indent_width: usize,
+    p_metadata: &record::EntryMetadata,
+    p_path_os: &path::Path,
+    p_is_dir: bool,
+) -> bool {
+    // borrow the filename (silently skip the current entry if this could not be done)
+    let Some(path) = p_path_os.file_name() else {
+        return true;
+    };
+
+    // get the canonicalized path name
+    let dest_path = match record::canonicalize(p_path_os) {
+        Ok(dest_path) => dest_path,
+        // broken symlinks are shown with the path stored in them while they are looked for
+        Err(_) if filter::broken_symlinks() => {
+            record::read_link(p_path_os).unwrap_or(path::PathBuf::from("?"))
+        }
+        Err(error) => {
+            if get_option(PrgOptions::ShowErrors) {
+                eprint!(
+                    "{}",
+                    tr!(Msg::SymlinkTargetError, path.to_string_lossy(), error)
+                );
+            }
+            return true;
+        }
+    };
+
+    let color = entry_color(p_path_os, p_metadata, None);
+
+    // if the target is a directory, enclose the symlink and the target within angled brackets <>
+    if p_is_dir {
+        return columns::print_row(
+            Some((p_path_os, p_metadata)),
+            "SYMLINK",
+            Some(p_indent_width),
+            format_args!(
+                "{}<{}>{} -> <{}>",
+                color,
+                escape::name(&path),
+                color::end(color),
+                escape::name(&dest_path)
+            ),
+        );
+    }
+
+    return columns::print_row(
+        Some((p_path_os, p_metadata)),
+        "SYMLINK",
+        Some(p_indent_width),
+        format_args!(
+            "{}{}{} -> {}",
+            color,
+            escape::name(&path),
+            color::end(color),
+            escape::name(&dest_path)
+        ),
+    );
+}
+
+#[cfg(target_family = "unix")]
+/// Prints a file without indentation
+///
+/// Returns `false` if the file could be logged, `true` otherwise
+///
+/// # Arguments
+///
+/// - 'p_metadata' - reference to the metadata of the file entry (used for printing length)
+/// - `p_path_os` - reference to the entry's path
+/// - `p_canonical` - canonical path of the entry (resolved from the entry's path if it is not known)
+/// - 'p_file_len' - length of the file (in bytes)
+fn show_file_noindent(
+    p_metadata: &record::EntryMetadata,
+    p_path_os: &path::Path,
+    p_canonical: Option<&path::Path>,
+    p_file_len: &u64,
+) -> bool {
+    let Ok(path) = resolve_canonical(p_canonical, p_path_os) else {
+        return true;
+    };
+
+    let color = entry_color(p_path_os, p_metadata, Some(*p_file_len));
+
+    return columns::print_row(
+        Some((p_path_os, p_metadata)),
+        &format_size(*p_file_len),
+        None,
+        format_args!("{}{}{}", color, escape::name(&path), color::end(color)),
+    );
+}
+
+#[cfg(not(target_family = "unix"))]
+/// Prints a file without indentation
+///
+/// Returns `false` if the file could be logged, `true` otherwise
+///
+/// # Arguments
+///
+/// - 'p_metadata' - reference to the metadata of the file entry (used for printing length)
+/// - `p_path_os` - reference to the entry's path
+/// - `p_canonical` - canonical path of the entry (resolved from the entry's path if it is not known)
+/// - 'p_file_len' - length of the file (in bytes)
+fn show_file_noindent(
+    p_metadata: &record::EntryMetadata,
+    p_path_os: &path::Path,
+    p_canonical: Option<&path::Path>,
+    p_file_len: &u64,
+) -> bool {
+    let Ok(path) = resolve_canonical(p_canonical, p_path_os) else {
+        return true;
+    };
+
+    let path = path.to_string_lossy();
+
+    let color = entry_color(p_path_os, p_metadata, Some(*p_file_len));
+
+    return columns::print_row(
+        Some((p_path_os, p_metadata)),
+        &format_size(*p_file_len),
+        None,
+        format_args!(
+            "{}{}{}",
+            color,
+            escape::text(adjust_verbatim_unc(&path)),
+            color::end(color)
+        ),
+    );
+}
+
+/// Prints a file with indentation
+///
+/// Returns `false` if the file could be logged, `true` otherwise
+///
+/// # Arguments
+///
+/// - 'p_indent_width' - number of spaces to leave before printing the entry
+/// - 'p_metadata' - reference to the metadata of the file entry (used for printing length)
+/// - `p_path_os` - reference to the entry's path
+/// - 'p_file_len' - length of the file (in bytes)
+fn show_file(
+    p_indent_width: usize,
+    p_metadata: &record::EntryMetadata,
+    p_path_os: &path::Path,
+) -> bool {
+    let Some(path) = p_path_os.file_name() else {
+        return true;
+    };
+
+    let color = entry_color(p_path_os, p_metadata, Some(p_metadata.len()));
+
+    return columns::print_row(
+        Some((p_path_os, p_metadata)),
+        &format_size(p_metadata.len()),
+        Some(p_indent_width),
+        format_args!("{}{}{}", color, escape::name(&path), color::end(color)),
+    );
+}
+
+#[cfg(target_family = "unix")]
+/// Prints a directory without indentation
+///
+/// Returns `false` if the directory could be logged, `true` otherwise
+///
+/// # Arguments
+///
+/// - 'p_metadata' - reference to the metadata of the directory entry (used for getting the last modification time)
+/// - `p_path_os` - reference to the entry's path
+/// - `p_canonical` - canonical path of the entry (resolved from the entry's path if it is not known)
+fn show_dir_noindent(
+    p_metadata: &record::EntryMetadata,
+    p_path_os: &path::Path,
+    p_canonical: Option<&path::Path>,
+) -> bool {
+    let Ok(path) = resolve_canonical(p_canonical, p_path_os) else {
+        return true;
+    };
+
+    // see if the directory size needs to be printed (if yes, then check if it can be calculated)
+    let size = if get_option(PrgOptions::ShowDirSize) {
+        calc_dir_size(p_path_os, p_path_os)
+    } else {
+        None
+    };
+
+    let sz = match size {
+        Some(size) => format_size(size),
+        None if get_option(PrgOptions::ShowDirSize) => "ERROR".to_owned(),
+        None => String::new(),
+    };
+
+    let color = entry_color(p_path_os, p_metadata, size);
+
+    return columns::print_row(
+        Some((p_path_os, p_metadata)),
+        &sz,
+        None,
+        format_args!("{}<{}>{}", color, escape::name(&path), color::end(color)),
+    );
+}
+
+#[cfg(not(target_family = "unix"))]
+/// Prints a directory without indentation
+///
+/// Returns `false` if the directory could be logged, `true` otherwise
+///
+/// # Arguments
+///
+/// - 'p_metadata' - reference to the metadata of the directory entry (used for getting the last modification time)
+/// - `p_path_os` - reference to the entry's path
+/// - `p_canonical` - canonical path of the entry (resolved from the entry's path if it is not known)
+fn show_dir_noindent(
+    p_metadata: &record::EntryMetadata,
+    p_path_os: &path::Path,
+    p_canonical: Option<&path::Path>,
+) -> bool {
+    let Ok(path) = resolve_canonical(p_canonical, p_path_os) else {
+        return true;
+    };
+
+    let path = path.to_string_lossy();
+
+    // see if the directory size needs to be printed (if yes, then check if it can be calculated)
+    let size = if get_option(PrgOptions::ShowDirSize) {
+        calc_dir_size(p_path_os, p_path_os)
+    } else {
+        None
+    };
+
+    let sz = match size {
+        Some(size) => format_size(size),
+        None if get_option(PrgOptions::ShowDirSize) => "ERROR".to_owned(),
+        None => String::new(),
+    };
+
+    let color = entry_color(p_path_os, p_metadata, size);
+
+    return columns::print_row(
+        Some((p_path_os, p_metadata)),
+        &sz,
+        None,
+        format_args!(
+            "{}<{}>{}",
+            color,
+            escape::text(adjust_verbatim_unc(&path)),
+            color::end(color)
+        ),
+    );
+}
+
+#[cfg(target_family = "unix")]
+/// Prints a directory with indentation
+///
+/// Returns `false` if the directory could be logged, `true` otherwise
+///
+/// # Arguments
+///
+/// - 'p_indent_width' - number of spaces to leave before printing the entry
+/// - `p_path_os` - reference to the entry's path
+fn show_dir(
+    p_indent_width: usize,
+    p_metadata: &record::EntryMetadata,
+    p_path_os: &path::Path,
+) -> bool {
+    let Some(path) = p_path_os.file_name() else {
+        return true;
+    };
+
+    // see if the directory size needs to be printed (if yes, then check if it can be calculated)
+    // if it need not be printed, simply put an empty string
+    // if it needs to be printed and can be calculated, format and print it
+    // it if needs to be printed and can not be calculated, print ERROR
+    let size = if get_option(PrgOptions::ShowDirSize) {
+        calc_dir_size(p_path_os, p_path_os)
+    } else {
+        None
+    };
+
+    let sz = match size {
+        Some(size) => format_size(size),
+        None if get_option(PrgOptions::ShowDirSize) => "ERROR".to_owned(),
+        None => String::new(),
+    };
+
+    let color = entry_color(p_path_os, p_metadata, size);
+
+    return columns::print_row(
+        Some((p_path_os, p_metadata)),
+        &sz,
+        Some(p_indent_width),
+        format_args!("{}<{}>{}", color, escape::name(&path), color::end(color)),
+    );
+}
+
+#[cfg(not(target_family = "unix"))]
+/// Prints a directory with indentation
+///
+/// Returns `false` if the directory could be logged, `true` otherwise
+///
+/// # Arguments
+///
+/// - 'p_indent_width' - number of spaces to leave before printing the entry
+/// - 'p_metadata' - reference to the metadata of the directory entry (used for getting the last modification time)
+/// - `p_path_os` - reference to the entry's path
+fn show_dir(
+    p_indent_width: usize,
+    p_metadata: &record::EntryMetadata,
+    p_path_os: &path::Path,
+) -> bool {
+    let Some(path) = p_path_os.file_name() else {
+        return true;
+    };
+
+    // see if the directory size needs to be printed (if yes, then check if it can be calculated)
+    // if it need not be printed, simply put an empty string
+    // if it needs to be printed and can be calculated, format and print it
+    // it if needs to be printed and can not be calculated, print ERROR
+    let size = if get_option(PrgOptions::ShowDirSize) {
+        calc_dir_size(p_path_os, p_path_os)
+    } else {
+        None
+    };
+
+    let sz = match size {
+        Some(size) => format_size(size),
+        None if get_option(PrgOptions::ShowDirSize) => "ERROR".to_owned(),
+        None => String::new(),
+    };
+
+    let color = entry_color(p_path_os, p_metadata, size);
+
+    return columns::print_row(
+        Some((p_path_os, p_metadata)),
+        &sz,
+        Some(p_indent_width),
+        format_args!("{}<{}>{}", color, escape::name(&path), color::end(color)),
+    );
+}
+
+#[cfg(target_family = "unix")]
+/// Prints a special file without indentation
+///
+/// Returns `false` if the special file could be logged, `true` otherwise
+///
+/// # Arguments
+///
+/// - 'p_metadata' - reference to the metadata of the special file entry (used for getting the last modification time)
+/// - `p_path_os` - reference to the entry's path
+/// - `p_canonical` - canonical path of the entry (resolved from the entry's path if it is not known)
+/// - 'p_special_file_type' - the type of special file ([SpecialFileType::NA] on windows)
+fn show_special_noindent(
+    p_metadata: &record::EntryMetadata,
+    p_path_os: &path::Path,
+    p_canonical: Option<&path::Path>,
+    p_special_file_type: &SpecialFileType,
+) -> bool {
+    let Ok(path) = resolve_canonical(p_canonical, p_path_os) else {
+        return true;
+    };
+
+    let special_type = match p_special_file_type {
+        SpecialFileType::Socket => "SOCKET",
+        SpecialFileType::BlockDevice => "BLOCK DEVICE",
+        SpecialFileType::CharDevice => "CHAR DEVICE",
+        SpecialFileType::Fifo => "FIFO PIPE",
+        _ => "SPECIAL",
+    };
+
+    let color = entry_color(p_path_os, p_metadata, None);
+
+    return columns::print_row(
+        Some((p_path_os, p_metadata)),
+        special_type,
+        None,
+        format_args!("{}{}{}", color, escape::name(&path), color::end(color)),
+    );
+}
+
+#[cfg(not(target_family = "unix"))]
+/// Prints a special file without indentation
+///
+/// Returns `false` if the special file could be logged, `true` otherwise
+///
+/// # Arguments
+///
+/// - 'p_metadata' - reference to the metadata of the special file entry (used for getting the last modification time)
+/// - `p_path_os` - reference to the entry's path
+/// - `p_canonical` - canonical path of the entry (resolved from the entry's path if it is not known)
+/// - 'p_special_file_type' - the type of special file ([SpecialFileType::NA] on windows)
+fn show_special_noindent(
+    p_metadata: &record::EntryMetadata,
+    p_path_os: &path::Path,
+    p_canonical: Option<&path::Path>,
+    _p_special_file_type: &SpecialFileType,
+) -> bool {
+    let Ok(path) = resolve_canonical(p_canonical, p_path_os) else {
+        return true;
+    };
+
+    let path = path.to_string_lossy();
+
+    let special_type = "SPECAL";
+
+    let color = entry_color(p_path_os, p_metadata, None);
+
+    return columns::print_row(
+        Some((p_path_os, p_metadata)),
+        special_type,
+        None,
+        format_args!(
+            "{}{}{}",
+            color,
+            escape::text(adjust_verbatim_unc(&path)),
+            color::end(color)
+        ),
+    );
+}
+
+#[cfg(target_family = "unix")]
+/// Prints a directory with indentation
+///
+/// Returns `false` if the special file could be logged, `true` otherwise
+///
+/// # Arguments
+///
+/// - 'p_indent_width' - number of spaces to leave before printing the entry
+/// - 'p_metadata' - reference to the metadata of the special file entry (used for getting the last modification time)
+/// - `p_path_os` - reference to the entry's path
+/// - 'p_special_file_type' - the type of special file ([SpecialFileType::NA] on windows)
+fn show_special(
+    p_indent_width: usize,
+    p_metadata: &record::EntryMetadata,
+    p_path_os: &path::Path,
+    p_special_file_type: &SpecialFileType,
+) -> bool {
+    let Some(path) = p_path_os.file_name() else {
+        return true;
+    };
+
+    let special_type = match p_special_file_type {
+        SpecialFileType::Socket => "SOCKET",
+        SpecialFileType::BlockDevice => "BLOCK DEVICE",
+        SpecialFileType::CharDevice => "CHAR DEVICE",
+        SpecialFileType::Fifo => "FIFO PIPE",
+        _ => "SPECIAL",
+    };
+
+    let color = entry_color(p_path_os, p_metadata, None);
+
+    return columns::print_row(
+        Some((p_path_os, p_metadata)),
+        special_type,
+        Some(p_indent_width),
+        format_args!("{}{}{}", color, escape::name(&path), color::end(color)),
+    );
+}
+
+#[cfg(not(target_family = "unix"))]
+/// Prints a directory with indentation
+///
+/// Returns `false` if the special file could be logged, `true` otherwise
+///
+/// # Arguments
+///
+/// - 'p_indent_width' - number of spaces to leave before printing the entry
+/// - 'p_metadata' - reference to the metadata of the special file entry (used for getting the last modification time)
+/// - `p_path_os` - reference to the entry's path
+/// - 'p_special_file_type' - the type of special file ([SpecialFileType::NA] on windows)
+fn show_special(
+    p_indent_width: usize,
+    p_metadata: &record::EntryMetadata,
+    p_path_os: &path::Path,
+    _p_special_file_type: &SpecialFileType,
+) -> bool {
+    let Some(path) = p_path_os.file_name() else {
+        return true;
+    };
+
+    let special_type = "SPECIAL";
+
+    let color = entry_color(p_path_os, p_metadata, None);
+
+    return columns::print_row(
+        Some((p_path_os, p_metadata)),
+        special_type,
+        Some(p_indent_width),
+        format_args!("{}{}{}", color, escape::name(&path), color::end(color)),
+    );
+}
+
+/// Prints a node of the directory tree built in dirs-only mode
+///
+/// Returns `false` if the directory could be logged, `true` otherwise
+///
+/// # Arguments
+///
+/// - 'p_arena' - arena that the directories of the tree are stored in
+/// - 'p_indent_width' - number of spaces to leave before printing the entry (ignored if the no-tree option is set)
+/// - 'p_node' - reference to the node of the directory to print
+fn show_dir_node(p_arena: &arena::PathArena, p_indent_width: usize, p_node: &DirNode) -> bool {
+    let path_os = p_arena.dir_path(p_node.dir);
+
+    // in no-tree mode, the absolute path is printed without indentation
+    let (path, indent_width) = if get_option(PrgOptions::ShowNotree) {
+        let Ok(path) = path_os.canonicalize() else {
+            return true;
+        };
+        (path, 0)
+    } else {
+        let Some(path) = path_os.file_name() else {
+            return true;
+        };
+        (path::PathBuf::from(path), p_indent_width)
+    };
+
+    #[cfg(target_family = "unix")]
+    let path = escape::name(&path);
+    #[cfg(not(target_family = "unix"))]
+    let path = escape::text(adjust_verbatim_unc(&path.to_string_lossy())).into_owned();
+
+    let size = if get_option(PrgOptions::ShowDirSize) {
+        p_node.size
+    } else {
+        None
+    };
+
+    let sz = match size {
+        Some(size) => format_size(size),
+        None if get_option(PrgOptions::ShowDirSize) => "ERROR".to_owned(),
+        None => String::new(),
+    };
+
+    let color = entry_color(&path_os, &p_node.metadata, size);
+
+    return columns::print_row(
+        Some((&path_os, &p_node.metadata)),
+        &sz,
+        Some(indent_width),
+        format_args!("{}<{}>{}", color, path, color::end(color)),
+    );
+}
+
+/// Returns the number of columns to indent each level of nesting by
+fn indent_col_width() -> usize {
+    return INDENT
+        .get()
+        .map_or(DEFAULT_INDENT_COL_WIDTH, |indent| indent.0);
+}
+
+/// Returns whether nested entries are indented with tree lines (in which case the entries of each directory are read
+/// up front, since drawing the line of an entry needs to know whether it is the last line of its directory)
+fn draws_tree_lines() -> bool {
+    return !get_option(PrgOptions::ShowNotree)
+        && INDENT
+            .get()
+            .is_some_and(|indent| indent.1 == IndentStyle::Tree);
+}
+
+/// Returns whether the entries at a level lie above the depth given to `--min-depth` (they are not shown, though the
+/// directories among them are still descended into)
+///
+/// # Arguments
+///
+/// - `p_level` - level at which the entries lie (0 for the initial directory)
+fn below_min_depth(p_level: usize) -> bool {
+    return p_level + 1 < MIN_DEPTH.get().copied().unwrap_or(0);
+}
+
+/// Returns the number of levels of nesting that are not shown due to `--min-depth` (the shown entries are indented as
+/// if the shallowest of them lay in the initial directory)
+fn skipped_levels() -> usize {
+    return MIN_DEPTH.get().copied().unwrap_or(0).saturating_sub(1);
+}
+
+/// Sets whether the line about to be printed at the given level of nesting is the last one of its directory (has no
+/// effect unless indenting with tree lines)
+///
+/// # Arguments
+///
+/// - `p_level` - level of nesting of the line
+/// - `p_last` - whether the line is the last one of its directory
+fn set_tree_branch(p_level: usize, p_last: bool) {
+    if !draws_tree_lines() {
+        return;
+    }
+
+    let mut branches = TREE_BRANCHES.lock().unwrap();
+    branches.truncate(p_level);
+    branches.resize(p_level, false);
+    branches.push(p_last);
+}
+
+/// Returns the line that is printed last among those of a directory whose entries were all read up front
+///
+/// # Arguments
+///
+/// - `p_entries` - entries of the directory (only those that were read up front are considered)
+fn tree_end(p_entries: &record::ReadEntries) -> TreeEnd {
+    let mut end = TreeEnd::Nothing;
+    let (mut hidden_files, mut hidden_symlinks, mut hidden_special) = (false, false, false);
+
+    for (idx, entry) in p_entries.remaining().iter().enumerate() {
+        let Ok((path_os, metadata)) = entry else {
+            continue;
+        };
+
+        // entries that are hidden or filtered out are skipped as if they did not exist
+        if filter::hides(path_os, metadata, true)
+            || (!metadata.is_dir() && !filter::matches(path_os, metadata))
+        {
+            continue;
+        }
+
+        let shown = if metadata.is_symlink() {
+            hidden_symlinks |= !get_option(PrgOptions::ShowSymlinks);
+            get_option(PrgOptions::ShowSymlinks)
+        } else if metadata.is_file() && metadata.special_file_type() == SpecialFileType::NA {
+            hidden_files |= !get_option(PrgOptions::ShowFiles);
+            get_option(PrgOptions::ShowFiles)
+        } else if metadata.is_dir() {
+            true
+        } else {
+            hidden_special |= !get_option(PrgOptions::ShowSpecial);
+            get_option(PrgOptions::ShowSpecial)
+        };
+
+        if shown {
+            end = TreeEnd::Entry(idx);
+        }
+    }
+
+    // the numbers of entries that are not shown are printed after all the entries, in this order
+    if hidden_special {
+        return TreeEnd::Special;
+    } else if hidden_symlinks {
+        return TreeEnd::Symlinks;
+    } else if hidden_files {
+        return TreeEnd::Files;
+    }
+    return end;
+}
+
+/// Returns the indentation to print before an entry
+///
+/// # Arguments
+///
+/// - `p_indent_width` - number of columns to indent the entry by (a multiple of the width of a level)
+fn indent_text(p_indent_width: usize) -> IndentText {
+    let (col_width, style) = INDENT
+        .get()
+        .copied()
+        .unwrap_or((DEFAULT_INDENT_COL_WIDTH, IndentStyle::Spaces));
+
+    // the shallowest shown entries lie in different directories, so tree lines do not connect them (each of them is
+    // drawn like the root of its own tree)
+    let mut skipped = skipped_levels();
+    if skipped > 0 && draws_tree_lines() {
+        skipped += 1;
+    }
+
+    let branches = if draws_tree_lines() {
+        TREE_BRANCHES
+            .lock()
+            .unwrap()
+            .iter()
+            .skip(skipped)
+            .copied()
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    return IndentText {
+        levels: p_indent_width
+            .checked_div(col_width)
+            .unwrap_or(0)
+            .saturating_sub(skipped),
+        col_width,
+        style,
+        branches,
+    };
+}
+
+impl fmt::Display for IndentText {
+    fn fmt(&self, p_formatter: &mut fmt::Formatter) -> fmt::Result {
+        // tree lines also connect the entries of the initial directory, so they take up an extra level
+        if self.style == IndentStyle::Tree && self.branches.len() > self.levels {
+            for last in &self.branches[..self.levels] {
+                let guide = if *last { ' ' } else { '│' };
+                write!(
+                    p_formatter,
+                    "{}{:pad$}",
+                    guide,
+                    "",
+                    pad = self.col_width - 1
+                )?;
+            }
+
+            let corner = if self.branches[self.levels] {
+                '└'
+            } else {
+                '├'
+            };
+            write!(p_formatter, "{}", corner)?;
+            for _ in 2..self.col_width {
+                write!(p_formatter, "─")?;
+            }
+            if self.col_width > 1 {
+                write!(p_formatter, " ")?;
+            }
+
+            return Ok(());
+        }
+
+        for _ in 0..self.levels {
+            write!(
+                p_formatter,
+                "{}{:pad$}",
+                self.style.guide(),
+                "",
+                pad = self.col_width - 1
+            )?;
+        }
+
+        return Ok(());
+    }
+}
+
+/// Returns the escape sequence of the color of an entry (empty if colors are disabled)
+///
+/// Entries are colored by their kind, unless a heatmap is drawn (in which case only the heatmap colors them)
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry (the entry is not colored by age if its modification time is not available)
+/// - `p_size` - size of the entry (the entry is not colored by size if it is [None])
+fn entry_color(
+    p_path_os: &path::Path,
+    p_metadata: &record::EntryMetadata,
+    p_size: Option<u64>,
+) -> &'static str {
+    if !color::enabled() {
+        return "";
+    }
+
+    if get_option(PrgOptions::SizeHeatmap) {
+        return p_size.map_or("", color::size_heat);
+    }
+
+    if !get_option(PrgOptions::AgeHeatmap) {
+        return color::kind_color(
+            color::Kind::of(p_path_os, p_metadata),
+            p_path_os.file_name().unwrap_or_default(),
+        );
+    }
+
+    let Ok(modified) = p_metadata.modified() else {
+        return "";
+    };
+
+    // entries modified in the future are as hot as the ones modified just now
+    let age = time::SystemTime::now()
+        .duration_since(modified)
+        .unwrap_or_default();
+
+    return color::age_heat(age);
+}
+
+/// Recursively prints the subdirectories of a node of the directory tree built in dirs-only mode
+///
+/// # Arguments
+///
+/// - `p_arena` - arena that the directories of the tree are stored in
+/// - `p_level` - level at which the subdirectories are to be printed
+/// - `p_node` - reference to the node whose subdirectories are to be printed
+fn print_dir_tree(p_arena: &arena::PathArena, p_level: usize, p_node: &DirNode) {
+    for (idx, child) in p_node.children.iter().enumerate() {
+        set_tree_branch(p_level, idx + 1 == p_node.children.len());
+        if below_min_depth(p_level) || !show_dir_node(p_arena, indent_col_width() * p_level, child)
+        {
+            print_dir_tree(p_arena, 1 + p_level, child);
+        }
+    }
+}
+
+/// Returns the time budget given as a number of seconds, optionally followed by a unit like an age (see [parse_age])
+///
+/// Returns [None] if the time budget could not be parsed or is zero
+///
+/// # Arguments
+///
+/// - `p_timeout` - the time budget to parse (such as `30s`)
+fn parse_timeout(p_timeout: &str) -> Option<time::Duration> {
+    let timeout = match p_timeout.parse::<u64>() {
+        Ok(secs) => time::Duration::from_secs(secs),
+        Err(_) => parse_age(p_timeout)?,
+    };
+
+    return Some(timeout).filter(|timeout| !timeout.is_zero());
+}
+
+/// Returns the age given as a number followed by a unit (`s`, `m`, `h`, `d`, `w` or `y`, days if there is no unit)
+///
+/// Returns [None] if the age could not be parsed
+///
+/// # Arguments
+///
+/// - `p_age` - the age to parse (such as `180d`)
+fn parse_age(p_age: &str) -> Option<time::Duration> {
+    let (number, unit_secs) = match p_age.char_indices().last()? {
+        (i, 's') => (&p_age[..i], 1),
+        (i, 'm') => (&p_age[..i], 60),
+        (i, 'h') => (&p_age[..i], 3600),
+        (i, 'd') => (&p_age[..i], 86400),
+        (i, 'w') => (&p_age[..i], 7 * 86400),
+        (i, 'y') => (&p_age[..i], 365 * 86400),
+        _ => (p_age, 86400),
+    };
+
+    let secs = number.parse::<u64>().ok()?.checked_mul(unit_secs)?;
+
+    return Some(time::Duration::from_secs(secs));
+}
+
+/// Returns the size given as a number followed by a unit (`K`, `M`, `G` or `T` for powers of 1024, bytes if there is
+/// no unit)
+///
+/// Returns [None] if the size could not be parsed
+///
+/// # Arguments
+///
+/// - `p_size` - the size to parse (such as `10G`)
+fn parse_size(p_size: &str) -> Option<u64> {
+    let (number, unit_len) = match p_size.char_indices().last()? {
+        (i, 'K' | 'k') => (&p_size[..i], 1u64 << 10),
+        (i, 'M') => (&p_size[..i], 1 << 20),
+        (i, 'G') => (&p_size[..i], 1 << 30),
+        (i, 'T') => (&p_size[..i], 1 << 40),
+        _ => (p_size, 1),
+    };
+
+    return number.parse::<u64>().ok()?.checked_mul(unit_len);
+}
+
+#[cfg(target_os = "linux")]
+/// Returns the mount point of the filesystem that a path lies on if the filesystem does not record access times
+///
+/// Returns [None] if access times are recorded, or if the mount point could not be found
+///
+/// # Arguments
+///
+/// - `p_path` - the path whose filesystem is checked
+fn noatime_mount_point(p_path: &path::Path) -> Option<path::PathBuf> {
+    let path_os = p_path.canonicalize().ok()?;
+    let mounts = fs::read_to_string("/proc/self/mountinfo").ok()?;
+
+    // the fifth field of each line is the mount point and the sixth one holds the options of the mount
+    let (mount_point, options) = mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(' ').skip(4);
+            let mount_point = path::PathBuf::from(fields.next()?.replace("\\040", " "));
+            return Some((mount_point, fields.next()?.to_owned()));
+        })
+        .filter(|(mount_point, _)| path_os.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.as_os_str().len())?;
+
+    return match options.split(',').any(|option| option == "noatime") {
+        true => Some(mount_point),
+        false => None,
+    };
+}
+
+/// Returns a description of what is suspicious about the timestamps of a file ([None] if nothing is)
+///
+/// Modification times in the future or at the UNIX epoch are suspicious, and so are modification times that are
+/// newer than the time of the last status change or older than it by more than the given gap (unix only)
+///
+/// # Arguments
+///
+/// - `p_now` - the current time
+/// - `p_modified` - time of the last modification of the file
+/// - `p_metadata` - metadata of the file
+/// - `p_max_ctime_gap` - largest plausible gap between the modification time and the time of the last status change
+#[cfg_attr(not(target_family = "unix"), allow(unused_variables))]
+fn timestamp_anomalies(
+    p_now: time::SystemTime,
+    p_modified: time::SystemTime,
+    p_metadata: &fs::Metadata,
+    p_max_ctime_gap: time::Duration,
+) -> Option<String> {
+    // small differences are tolerated, since the clocks of network filesystems are rarely exactly in sync
+    if p_modified > p_now + TIMESTAMP_TOLERANCE {
+        return Some(tr!(Msg::AnomalyFuture));
+    }
+
+    // timestamps that were lost or zeroed while copying end up within a day of the epoch (depending on the timezone)
+    if p_modified <= time::UNIX_EPOCH + time::Duration::from_secs(86400) {
+        return Some(tr!(Msg::AnomalyEpoch));
+    }
+
+    #[cfg(target_family = "unix")]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        let changed = time::UNIX_EPOCH
+            + time::Duration::new(
+                p_metadata.ctime().max(0) as u64,
+                p_metadata.ctime_nsec() as u32,
+            );
+
+        // setting the modification time also updates the status change time, so the former can not be newer
+        if p_modified > changed + TIMESTAMP_TOLERANCE {
+            return Some(tr!(Msg::AnomalyAfterCtime));
+        }
+
+        if changed
+            .duration_since(p_modified)
+            .is_ok_and(|gap| gap > p_max_ctime_gap)
+        {
+            return Some(tr!(Msg::AnomalyCtimeGap, format_age(p_max_ctime_gap)));
+        }
+    }
+
+    return None;
+}
+
+/// Returns the given age formatted with its two largest units (such as `3d 4h`, or `3d` if there are no hours)
+///
+/// # Arguments
+///
+/// - `p_age` - the age to format
+fn format_age(p_age: time::Duration) -> String {
+    const UNITS: [(u64, &str); 5] = [
+        (365 * 86400, "y"),
+        (86400, "d"),
+        (3600, "h"),
+        (60, "m"),
+        (1, "s"),
+    ];
+
+    let secs = p_age.as_secs();
+
+    for (i, (unit_secs, unit)) in UNITS.iter().enumerate() {
+        if secs < *unit_secs {
+            continue;
+        }
+
+        // the second unit is left out if it is zero (the seconds are never followed by another unit)
+        return match UNITS.get(i + 1) {
+            Some((next_secs, next_unit)) if secs % unit_secs >= *next_secs => {
+                format!(
+                    "{}{} {}{}",
+                    secs / unit_secs,
+                    unit,
+                    (secs % unit_secs) / next_secs,
+                    next_unit
+                )
+            }
+            _ => format!("{}{}", secs / unit_secs, unit),
+        };
+    }
+
+    return "0s".to_owned();
+}
+
+/// Returns the time of the last modification of an entry formatted for a listing (as the time since then with
+/// `--relative-time`, such as `3 days ago`)
+///
+/// # Arguments
+///
+/// - `p_modified` - time of the last modification
+fn format_modif_time(p_modified: time::SystemTime) -> String {
+    if get_option(PrgOptions::RelativeTime) {
+        return format_relative_time(p_modified);
+    }
+
+    let format = TIME_FORMAT
+        .get()
+        .map_or(DEFAULT_TIME_FORMAT, String::as_str);
+    let time = Into::<chrono::DateTime<chrono::offset::Local>>::into(p_modified);
+    return time.format(format).to_string();
+}
+
+/// Sets the format that modification times are printed in (has no effect if it is already set)
+///
+/// Returns `Err` if the format holds a specifier that strftime does not accept
+///
+/// # Arguments
+///
+/// - `p_format` - the format (such as `%Y-%m-%d %H:%M:%S`)
+fn set_time_format(p_format: &str) -> Result<(), ()> {
+    // an invalid specifier would only surface as an error while printing the first time
+    if chrono::format::StrftimeItems::new(p_format)
+        .any(|item| matches!(item, chrono::format::Item::Error))
+    {
+        return Err(());
+    }
+
+    let _ = TIME_FORMAT.set(p_format.to_owned());
+    return Ok(());
+}
+
+/// Sets the time zone that times are printed in (`utc`, `local`, an offset from UTC such as `+05:30`, or the name of a
+/// zone in the time zone database such as `Europe/Berlin`)
+///
+/// The zone is set through the `TZ` environment variable, so that it applies to every time that is printed in local
+/// time (including those of `--format`, `--ls-format` and `--long`)
+///
+/// Returns `Err` if the zone is not valid
+///
+/// # Arguments
+///
+/// - `p_zone` - the time zone
+fn set_time_zone(p_zone: &str) -> Result<(), ()> {
+    let tz = if p_zone.eq_ignore_ascii_case("local") {
+        return Ok(());
+    } else if p_zone.eq_ignore_ascii_case("utc") {
+        "UTC".to_owned()
+    } else if let Some(offset) = parse_utc_offset(p_zone) {
+        // POSIX counts offsets westwards, so their sign is the opposite of the usual one
+        let sign = if offset < 0 { '+' } else { '-' };
+        let offset = offset.unsigned_abs();
+        let (hours, minutes) = (offset / 3600, (offset % 3600) / 60);
+        format!(
+            "<{}>{}{:02}:{:02}",
+            p_zone.replace(':', ""),
+            sign,
+            hours,
+            minutes
+        )
+    } else if is_zone_name(p_zone) {
+        p_zone.to_owned()
+    } else {
+        return Err(());
+    };
+
+    std::env::set_var("TZ", tz);
+    return Ok(());
+}
+
+/// Parses an offset from UTC such as `+05:30`, `-0800` or `+01`, and returns it in seconds
+///
+/// # Arguments
+///
+/// - `p_offset` - the offset
+fn parse_utc_offset(p_offset: &str) -> Option<i32> {
+    let sign = match p_offset.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+
+    let digits = p_offset[1..].replace(':', "");
+    if !digits.bytes().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+
+    let (hours, minutes) = match digits.len() {
+        2 => (digits.parse::<i32>().ok()?, 0),
+        4 => (
+            digits[..2].parse::<i32>().ok()?,
+            digits[2..].parse::<i32>().ok()?,
+        ),
+        _ => return None,
+    };
+    if hours > 14 || minutes > 59 {
+        return None;
+    }
+
+    return Some(sign * (hours * 3600 + minutes * 60));
+}
+
+/// Returns whether a zone with the given name exists in the time zone database (such as `Europe/Berlin`)
+///
+/// # Arguments
+///
+/// - `p_name` - name of the zone
+fn is_zone_name(p_name: &str) -> bool {
+    // names are relative paths within the database, so they cannot climb out of it
+    if p_name.is_empty()
+        || path::Path::new(p_name)
+            .components()
+            .any(|component| !matches!(component, path::Component::Normal(_)))
+    {
+        return false;
+    }
+
+    let tzdir = std::env::var("TZDIR").ok();
+    return tzdir
+        .iter()
+        .map(String::as_str)
+        .chain(ZONEINFO_DIRS)
+        .any(|dir| path::Path::new(dir).join(p_name).is_file());
+}
+
+/// Returns the time since the given time in words, with its largest unit (such as `3 days ago`)
+///
+/// # Arguments
+///
+/// - `p_time` - the time
+fn format_relative_time(p_time: time::SystemTime) -> String {
+    // months are counted as 30 days, so that 12 months fall just short of a year
+    const UNITS: [(u64, Msg, Msg); 5] = [
+        (365 * 86400, Msg::TimeYearAgo, Msg::TimeYearsAgo),
+        (30 * 86400, Msg::TimeMonthAgo, Msg::TimeMonthsAgo),
+        (86400, Msg::TimeDayAgo, Msg::TimeDaysAgo),
+        (3600, Msg::TimeHourAgo, Msg::TimeHoursAgo),
+        (60, Msg::TimeMinuteAgo, Msg::TimeMinutesAgo),
+    ];
+
+    let Ok(age) = time::SystemTime::now().duration_since(p_time) else {
+        return tr!(Msg::TimeFuture);
+    };
+    let secs = age.as_secs();
+
+    for (unit_secs, one, many) in UNITS {
+        if secs < unit_secs {
+            continue;
+        }
+
+        return match secs / unit_secs {
+            1 => tr!(one),
+            count => tr!(many, count),
+        };
+    }
+
+    return tr!(Msg::TimeJustNow);
+}
+
+/// Prints a file along with its age (and the time of its last modification, if it needs to be shown)
+///
+/// # Arguments
+///
+/// - `p_now` - the time that the age is measured from
+/// - `p_modified` - time of the last modification of the file
+/// - `p_path` - path of the file
+/// - `p_size` - size of the file
+fn print_file_age(
+    p_now: time::SystemTime,
+    p_modified: time::SystemTime,
+    p_path: &path::Path,
+    p_size: u64,
+) {
+    // files modified in the future (due to clock skew) are treated as brand new
+    let age = p_now.duration_since(p_modified).unwrap_or_default();
+
+    #[cfg(target_family = "unix")]
+    if get_option(PrgOptions::ShowLasttime) {
+        print!("{:>FMT_TIME_WIDTH$}", format_modif_time(p_modified));
+    }
+
+    print!(
+        "{:>12}{:>20}    {}\n",
+        format_age(age),
+        format_size(p_size),
+        p_path.to_string_lossy()
+    );
+}
+
+/// Recursively prints the digest of a directory followed by the digests of its subdirectories (one line each)
+///
+/// # Arguments
+///
+/// - `p_arena` - arena that the directories of the digests are stored in
+/// - `p_fingerprint` - reference to the digest of the directory
+fn print_fingerprint(p_arena: &arena::PathArena, p_fingerprint: &Fingerprint) {
+    let name_hash = match p_fingerprint.name_hash {
+        Some(hash) => format!("{:016x}", hash),
+        None => "ERROR".to_owned(),
+    };
+
+    // the path is relative to the initial directory (with "/" as the separator), and its tabs and newlines are
+    // escaped so that every digest stays on a single line
+    let names = p_arena.rel_names(p_fingerprint.dir);
+    let rel_path = match names.is_empty() {
+        true => ".".to_owned(),
+        false => names
+            .iter()
+            .map(|name| name.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+    };
+    let rel_path = escape_line_field(&rel_path);
+
+    print!(
+        "{}\tfiles={} symlinks={} special={} dirs={} size={} newest={} names={}\n",
+        rel_path,
+        p_fingerprint.counts.get_file_cnt(),
+        p_fingerprint.counts.get_symlink_cnt(),
+        p_fingerprint.counts.get_special_cnt(),
+        p_fingerprint.counts.get_dir_cnt(),
+        p_fingerprint.size,
+        p_fingerprint.newest,
+        name_hash
+    );
+
+    for child in &p_fingerprint.children {
+        print_fingerprint(p_arena, child);
+    }
+}
+
+/// Returns whether the name of an entry matches any of the search patterns based on the search mode that is set (or
+/// none of them, if the invert-match option is set)
+///
+/// If no search mode is set, every entry matches. Returns [None] if the name of the entry could not be read
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_search_patterns` - the patterns to match against
+fn matches_search(p_path_os: &path::Path, p_search_patterns: &[String]) -> Option<bool> {
+    if p_search_patterns.is_empty() {
+        return Some(true);
+    }
+
+    let matches = matches_any_pattern(p_path_os, p_search_patterns)?;
+    return Some(matches != get_option(PrgOptions::InvertMatch));
+}
+
+/// Returns whether the name of an entry matches any of the search patterns based on the search mode that is set
+///
+/// Returns [None] if the name of the entry could not be read
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_search_patterns` - the patterns to match against
+fn matches_any_pattern(p_path_os: &path::Path, p_search_patterns: &[String]) -> Option<bool> {
+    if let Some(exact_patterns) = EXACT_PATTERNS.get() {
+        let subject = search_subject(p_path_os, get_option(PrgOptions::SearchNoext))?;
+        return Some(exact_patterns.contains(&*fold_case(subject)));
+    }
+
+    for (idx, pattern) in p_search_patterns.iter().enumerate() {
+        if matches_pattern(p_path_os, idx, pattern)? {
+            return Some(true);
+        }
+    }
+
+    return Some(false);
+}
+
+/// Returns whether the name of an entry matches one of the search patterns based on the search mode that is set
+///
+/// Returns [None] if the name of the entry could not be read
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_idx` - index of the pattern among the search patterns (glob patterns are parsed up front in that order)
+/// - `p_pattern` - the pattern to match against
+fn matches_pattern(p_path_os: &path::Path, p_idx: usize, p_pattern: &str) -> Option<bool> {
+    if get_option(PrgOptions::SearchGlob) {
+        return Some(glob::matches(p_idx, p_path_os));
+    }
+
+    if get_option(PrgOptions::SearchFuzzy) {
+        let file_name = search_subject(p_path_os, false)?;
+
+        return Some(fuzzy::score(p_pattern, &file_name).is_some());
+    }
+
+    if get_option(PrgOptions::SearchNoext) {
+        // get the filename of this entry without the extension
+        let file_stem = fold_case(search_subject(p_path_os, true)?);
+
+        return Some(*file_stem == *p_pattern);
+    }
+
+    // get the filename of this entry
+    let file_name = fold_case(search_subject(p_path_os, false)?);
+
+    if get_option(PrgOptions::SearchExact) {
+        return Some(*file_name == *p_pattern);
+    }
+
+    return Some(file_name.contains(p_pattern));
+}
+
+/// Returns what the search patterns are matched against for an entry - its name, or its path relative to the initial
+/// directory with `--full-path` (with components separated by `/` on every platform)
+///
+/// Returns [None] if the name of the entry could not be read
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_without_ext` - whether the extension of the entry is left out
+fn search_subject(p_path_os: &path::Path, p_without_ext: bool) -> Option<Cow<'_, str>> {
+    let name = match p_without_ext {
+        true => p_path_os.file_stem()?,
+        false => p_path_os.file_name()?,
+    };
+
+    // entries outside of the initial directory (such as while watching it through a symlink) are matched by their names
+    let search_root = SEARCH_ROOT.read().ok();
+    let Some(relative) = search_root
+        .as_ref()
+        .and_then(|root| root.as_deref())
+        .and_then(|root| p_path_os.strip_prefix(root).ok())
+    else {
+        return Some(name.to_string_lossy());
+    };
+
+    let mut subject = String::new();
+    for component in relative.parent().into_iter().flat_map(path::Path::iter) {
+        subject.push_str(&component.to_string_lossy());
+        subject.push('/');
+    }
+    subject.push_str(&name.to_string_lossy());
+
+    return Some(Cow::Owned(subject));
+}
+
+/// Counts a shown entry towards each of the search patterns that it matches (has no effect unless several patterns
+/// were given, since the matches of a single pattern are not broken down, or if the invert-match option is set, since
+/// the shown entries match none of them)
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_search_patterns` - the patterns to match against
+fn count_pattern_matches(p_path_os: &path::Path, p_search_patterns: &[String]) {
+    if p_search_patterns.len() < 2 || get_option(PrgOptions::InvertMatch) {
+        return;
+    }
+
+    let Ok(mut cnts) = PATTERN_MATCH_CNTS.lock() else {
+        return;
+    };
+    cnts.resize(p_search_patterns.len(), 0);
+
+    for (idx, pattern) in p_search_patterns.iter().enumerate() {
+        if matches_pattern(p_path_os, idx, pattern) == Some(true) {
+            cnts[idx] += 1;
+        }
+    }
+}
+
+/// Counts a shown entry towards the directory that it lies in and towards its extension (has no effect unless the
+/// counts by dir option is set, and directories are not counted towards any extension)
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+fn count_match_location(p_path_os: &path::Path, p_metadata: &record::EntryMetadata) {
+    if !get_option(PrgOptions::CountsByDir) {
+        return;
+    }
+
+    if let (Some(parent), Ok(mut cnts)) = (p_path_os.parent(), DIR_MATCH_CNTS.lock()) {
+        *cnts.entry(parent.to_path_buf()).or_insert(0) += 1;
+    }
+
+    if p_metadata.is_dir() {
+        return;
+    }
+
+    if let Ok(mut cnts) = EXT_MATCH_CNTS.lock() {
+        let ext = p_path_os
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase());
+        *cnts.entry(ext).or_insert(0) += 1;
+    }
+}
+
+/// Prints the number of shown matches within each directory and with each extension (from the most matches to the
+/// fewest)
+fn print_match_location_cnts() {
+    let mut dir_cnts: Vec<(path::PathBuf, u64)> = DIR_MATCH_CNTS
+        .lock()
+        .map(|mut cnts| std::mem::take(&mut *cnts).into_iter().collect())
+        .unwrap_or_default();
+    dir_cnts.sort_by_key(|(_, cnt)| Reverse(*cnt));
+
+    print!("{}", tr!(Msg::SearchSummaryDirs));
+    for (dir, cnt) in dir_cnts {
+        print!(
+            "{}",
+            tr!(
+                Msg::SearchSummaryDir,
+                int_to_formatted_slice(cnt),
+                dir.to_string_lossy()
+            )
+        );
+    }
+    print!("\n");
+
+    let mut ext_cnts: Vec<(Option<String>, u64)> = EXT_MATCH_CNTS
+        .lock()
+        .map(|mut cnts| std::mem::take(&mut *cnts).into_iter().collect())
+        .unwrap_or_default();
+    ext_cnts.sort_by_key(|(_, cnt)| Reverse(*cnt));
+
+    print!("{}", tr!(Msg::SearchSummaryExts));
+    for (ext, cnt) in ext_cnts {
+        match ext {
+            Some(ext) => print!(
+                "{}",
+                tr!(Msg::SearchSummaryExt, int_to_formatted_slice(cnt), ext)
+            ),
+            None => print!(
+                "{}",
+                tr!(Msg::SearchSummaryNoExt, int_to_formatted_slice(cnt))
+            ),
+        }
+    }
+    print!("\n");
+}
+
+/// Returns the report of the directories that could not be read (for `--on-error collect`), listing each of them along
+/// with its error and then the number of errors of each kind (from the most to the fewest)
+///
+/// # Arguments
+///
+/// - `p_errors` - the errors, each along with the path of its directory
+fn error_report(p_errors: Vec<(path::PathBuf, std::io::Error)>) -> String {
+    let mut res = tr!(Msg::ErrorReportDirs);
+    let mut kind_cnts: BTreeMap<String, u64> = BTreeMap::new();
+    for (dir_path, error) in &p_errors {
+        res.push_str(&tr!(Msg::ErrorReportDir, dir_path.to_string_lossy(), error));
+        *kind_cnts.entry(error.kind().to_string()).or_insert(0) += 1;
+    }
+
+    let mut kind_cnts: Vec<(String, u64)> = kind_cnts.into_iter().collect();
+    kind_cnts.sort_by_key(|(_, cnt)| Reverse(*cnt));
+
+    res.push_str(&tr!(Msg::ErrorReportKinds));
+    for (kind, cnt) in kind_cnts {
+        res.push_str(&tr!(
+            Msg::ErrorReportKind,
+            int_to_formatted_slice(cnt),
+            kind
+        ));
+    }
+    res.push_str(&tr!(
+        Msg::ErrorReportTotal,
+        int_to_formatted_slice(p_errors.len() as u64)
+    ));
+
+    return res;
+}
+
+/// Returns whether as many matches as were asked for with `--max-results` have been shown (always false if it was not
+/// given)
+///
+/// # Arguments
+///
+/// - `p_entry_cnts_match` - number of shown entries of each type
+fn max_results_reached(p_entry_cnts_match: &EntryCounter) -> bool {
+    return MAX_RESULTS
+        .get()
+        .is_some_and(|max| p_entry_cnts_match.get_entry_cnt() >= *max);
+}
+
+/// Prints the directories above a match that have not been printed yet, each indented by its level (for
+/// `--search-tree`, the directories above the depth given to `--min-depth` are not printed)
+fn show_search_ancestors() {
+    let Ok(mut ancestors) = SEARCH_ANCESTORS.lock() else {
+        return;
+    };
+
+    for ancestor in ancestors.iter_mut().filter(|ancestor| !ancestor.printed) {
+        ancestor.printed = true;
+
+        if !below_min_depth(ancestor.level) {
+            show_dir(
+                indent_col_width() * ancestor.level,
+                &ancestor.metadata,
+                &ancestor.path,
+            );
+        }
+    }
+}
+
+/// Reads the search patterns from a file given to `--patterns-from`, one on each line (empty lines are skipped)
+///
+/// # Arguments
+///
+/// - `p_path` - path of the file
+fn read_patterns(p_path: &path::Path) -> std::io::Result<Vec<String>> {
+    let contents = fs::read_to_string(p_path)?;
+
+    return Ok(contents
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_owned())
+        .collect());
+}
+
+/// Reads the paths to scan from a file given to `--paths-from` (or from stdin if it is "-"), separated by NUL bytes if
+/// it holds any and by newlines otherwise (empty paths are skipped)
+///
+/// # Arguments
+///
+/// - `p_path` - path of the file
+fn read_paths(p_path: &str) -> std::io::Result<Vec<String>> {
+    let mut contents = String::new();
+    match p_path {
+        "-" => {
+            std::io::stdin().read_to_string(&mut contents)?;
+        }
+        _ => contents = fs::read_to_string(p_path)?,
+    }
+
+    // NUL bytes are the only separator that can not be part of a path (the output of find -print0 or fss -0)
+    let paths: Vec<&str> = match contents.contains('\0') {
+        true => contents.split('\0').collect(),
+        false => contents
+            .lines()
+            .map(|line| line.trim_end_matches('\r'))
+            .collect(),
+    };
+
+    return Ok(paths
+        .into_iter()
+        .filter(|path| !path.is_empty())
+        .map(|path| path.to_owned())
+        .collect());
+}
+
+/// Returns a name in lowercase if the case of names is ignored while searching (the search pattern is lowercased once
+/// while parsing the arguments instead)
+///
+/// # Arguments
+///
+/// - `p_name` - the name
+fn fold_case(p_name: Cow<'_, str>) -> Cow<'_, str> {
+    if !get_option(PrgOptions::IgnoreCase) {
+        return p_name;
+    }
+    return Cow::Owned(p_name.to_lowercase());
+}
+
+/// Prints the entries of a directory without any formatting, so that they can be piped into other tools
+///
+/// With names only, the path of each entry is printed on a line of its own (before the entries of a subdirectory). With
+/// sizes only, the size and the path of each entry are printed separated by a tab (after the entries of a
+/// subdirectory, like du), where the size of a directory is the cumulative size of the files within it
+///
+/// Returns the cumulative size of the files within the directory
+///
+/// # Arguments
+///
+/// - `p_max_level` - maximum level upto which entries are printed (a value of 0 denotes no limit)
+/// - `p_level` - level at which the entries of the current directory lie (0 for the initial directory)
+/// - `p_dir_path` - path of the current directory
+/// - `p_search_patterns` - patterns that the names of the printed entries must match one of (if a search option is set)
+/// - `p_shown` - whether the entries of the current directory are printed (they are only counted otherwise)
+/// - `p_shown_cnt` - number of entries printed so far (incremented for each entry printed)
+fn print_plain_entries(
+    p_max_level: &u64,
+    p_level: usize,
+    p_dir_path: &path::Path,
+    p_search_patterns: &[String],
+    p_shown: bool,
+    p_shown_cnt: &mut u64,
+) -> u64 {
+    let entries = match record::read_dir(p_dir_path) {
+        Ok(values) => values,
+        Err(error) => {
+            if get_option(PrgOptions::ShowErrors) {
+                eprint!(
+                    "{}",
+                    tr!(Msg::IterateError, p_dir_path.to_string_lossy(), error)
+                );
+            }
+            return 0;
+        }
+    };
+
+    let sizes_only = get_option(PrgOptions::SizesOnly);
+    let ls_format = get_option(PrgOptions::LsFormat);
+    let format = get_option(PrgOptions::Format);
+    let sqlite = get_option(PrgOptions::ExportSqlite);
+    let parquet = get_option(PrgOptions::ExportParquet);
+    let printf = get_option(PrgOptions::Printf);
+    let print0 = get_option(PrgOptions::Print0);
+    let show_subdirs = p_shown
+        && get_option(PrgOptions::ShowRecursive)
+        && (*p_max_level == 0u64 || p_level < (*p_max_level as usize));
+
+    let mut total_size: u64 = 0;
+
+    for entry in entries {
+        progress::tick();
+
+        // if the current entry or its metadata could not be read for some reason, then silently skip it
+        let Ok((path_os, metadata)) = entry else {
+            continue;
+        };
+
+        // hidden entries are not printed, but still add to the sizes of the directories above them (unless they are
+        // left out of every scan)
+        if filter::hides(&path_os, &metadata, false) {
+            continue;
+        }
+        let hidden = filter::hides(&path_os, &metadata, true);
+        if hidden && !sizes_only && !format {
+            continue;
+        }
+
+        let shown = p_shown
+            && !hidden
+            && !below_min_depth(p_level)
+            && matches_search(&path_os, p_search_patterns).unwrap_or(false)
+            && filter::keep(&path_os, &metadata);
+
+        let (size, show_type) = if metadata.is_symlink() {
+            (None, get_option(PrgOptions::ShowSymlinks))
+        } else if metadata.is_dir() {
+            // with names only, the subdirectories beyond the recursion depth are not needed
+            if !sizes_only && !show_subdirs {
+                (None, true)
+            } else {
+                if shown {
+                    *p_shown_cnt += 1;
+                }
+
+                if shown && ls_format {
+                    ls::print_entry(&path_os, &metadata);
+                } else if shown && format {
+                    export::print_entry(&path_os, &metadata, 1 + p_level);
+                } else if shown && printf {
+                    export::print_template_entry(&path_os, &metadata, 1 + p_level);
+                } else if shown && sqlite {
+                    sqlite::insert_entry(&path_os, &metadata);
+                } else if shown && parquet {
+                    #[cfg(feature = "export-parquet")]
+                    parquet::insert_entry(&path_os, &metadata);
+                } else if shown && print0 {
+                    print_null_terminated(&path_os);
+                } else if shown && !sizes_only {
+                    print!("{}\n", escape::name(&path_os));
+                }
+
+                let dir_size = print_plain_entries(
+                    p_max_level,
+                    1 + p_level,
+                    &path_os,
+                    p_search_patterns,
+                    show_subdirs && !hidden,
+                    p_shown_cnt,
+                );
+
+                if shown && sizes_only {
+                    print!("{}\t{}\n", dir_size, escape::name(&path_os));
+                } else if shown && format {
+                    export::print_dir_size(&path_os, dir_size);
+                }
+                total_size += dir_size;
+                continue;
+            }
+        } else if metadata.is_file() && metadata.special_file_type() == SpecialFileType::NA {
+            total_size += metadata.len();
+            (Some(metadata.len()), get_option(PrgOptions::ShowFiles))
+        } else {
+            (None, get_option(PrgOptions::ShowSpecial))
+        };
+
+        if !shown || !show_type {
+            continue;
+        }
+        *p_shown_cnt += 1;
+
+        // symlinks and special files do not add to the size of a directory, but their own size is still printed
+        if ls_format {
+            ls::print_entry(&path_os, &metadata);
+        } else if format {
+            export::print_entry(&path_os, &metadata, 1 + p_level);
+        } else if printf {
+            export::print_template_entry(&path_os, &metadata, 1 + p_level);
+        } else if sqlite {
+            sqlite::insert_entry(&path_os, &metadata);
+        } else if parquet {
+            #[cfg(feature = "export-parquet")]
+            parquet::insert_entry(&path_os, &metadata);
+        } else if print0 {
+            print_null_terminated(&path_os);
+        } else if sizes_only {
+            print!(
+                "{}\t{}\n",
+                size.unwrap_or(metadata.len()),
+                escape::name(&path_os)
+            );
+        } else {
+            print!("{}\n", escape::name(&path_os));
+        }
+    }
+
+    return total_size;
+}
+
+/// Prints the path of an entry followed by a NUL byte instead of a newline (for `--print0`)
+///
+/// The path is printed as is on unix-like systems (even if it is not valid UTF-8), so that it can be read back exactly
+///
+/// # Arguments
+///
+/// - `p_path` - the path of the entry
+fn print_null_terminated(p_path: &path::Path) {
+    #[cfg(target_family = "unix")]
+    {
+        use std::io::Write;
+        use std::os::unix::ffi::OsStrExt;
+
+        let mut stdout = std::io::stdout().lock();
+        let _ = stdout.write_all(p_path.as_os_str().as_bytes());
+        let _ = stdout.write_all(b"\0");
+    }
+
+    #[cfg(not(target_family = "unix"))]
+    print!("{}\0", p_path.to_string_lossy());
+}
+
+/// Scans through directory given its path and prints its contents based on the flags given
+///
+/// Returns None on success and [`std::io::Error`](std::io::Error) if an error was encountered (propagates the error up the stack)
+fn scan_path(
+    p_entry_cnts_init: &mut EntryCounter,
+    p_entry_cnts_full: &mut EntryCounter,
+    p_max_level: &u64,
+    p_level: usize,
+    p_current_path: &path::Path,
+    p_canonical_path: &mut CanonicalPath,
+) -> Option<std::io::Error> {
+    // calculate the indent width to be used while printing the entries in the current directory
+    let indent_width = indent_col_width() * p_level;
+    // the entries above the minimum depth are only counted (directories are still descended into)
+    let shown = !below_min_depth(p_level);
+    // instantiate structure to hold the number of entries of each type in the current directory (not recursive)
+    let mut cur_entry_cnts = EntryCounter::new();
+    // total size of files in the current directory (only used when printing summary)
+    let mut total_file_size: u64 = 0;
+
+    // try to read the entries of the current directory
+    // if the entries could not be iterated over (for example, due to insufficient permissions or the current entry being a file)
+    // then return from the function and report this to the caller
+    let read_start = time::Instant::now();
+    let mut entries = match record::read_dir(p_current_path) {
+        Ok(values) => values,
+        Err(error) => {
+            return Some(error);
+        }
+    };
+    // time spent in reading the entries of the current directory and their metadata
+    let mut latency = read_start.elapsed();
+
+    let _hashes_ahead = hash_files_ahead(&entries, &[]);
+
+    // with tree lines, the entries were read up front, so the line that ends the branch of the directory is known
+    let tree_end = tree_end(&entries);
+    let mut entry_idx = 0;
+
+    loop {
+        // read the next entry along with its metadata (will be used to query its type and in the case of regular files, its size)
+        let read_start = time::Instant::now();
+        let next = entries.next();
+        latency += read_start.elapsed();
+
+        let Some(next) = next else {
+            break;
+        };
+
+        progress::tick();
+        set_tree_branch(p_level, tree_end == TreeEnd::Entry(entry_idx));
+        entry_idx += 1;
+
+        // if the current entry or its metadata could not be read for some reason, then silently skip it
+        let Ok((path_os, metadata)) = next else {
+            continue;
+        };
+
+        // check for special file (on unix style operating systems, get the specific type as well)
+        let special_file_type = metadata.special_file_type();
+
+        // hidden entries are skipped along with everything within them
+        if filter::hides(&path_os, &metadata, true) {
+            continue;
+        }
+
+        // entries that are filtered out are skipped as if they did not exist (directories are kept to hold the tree)
+        if !metadata.is_dir() && !filter::keep(&path_os, &metadata) {
+            continue;
+        }
+
+        if metadata.is_symlink() {
+            cur_entry_cnts.inc_symlink_cnt(1);
+
+            // skip if the show symlinks option is not set
+            if !get_option(PrgOptions::ShowSymlinks) || !shown {
+                continue;
+            }
+
+            // depending on whether the absolute path (without indentation) needs to be printed,
+            // try to print the current entry
+            let failed = if get_option(PrgOptions::ShowNotree) {
+                show_symlink_noindent(&metadata, &path_os, record::is_dir(&path_os))
+            } else {
+                show_symlink(indent_width, &metadata, &path_os, record::is_dir(&path_os))
+            };
+
+            // if the entry could not be printed, then remove its contribution from the counts
+            if failed {
+                cur_entry_cnts.dec_symlink_cnt(1);
+            }
+        } else if metadata.is_file() && special_file_type == SpecialFileType::NA {
+            cur_entry_cnts.inc_file_cnt(1);
+
+            // skip if the show files option is not set
+            // since the number and size of files are aggregated at the end,
+            // add it's size to the total file size
+            if !get_option(PrgOptions::ShowFiles) {
+                total_file_size += metadata.len();
+                continue;
+            }
+            if !shown {
+                continue;
+            }
+
+            // depending on whether the absolute path (without indentation) needs to be printed,
+            // try to print the current entry
+            let failed = if get_option(PrgOptions::ShowNotree) {
+                p_canonical_path.push(&path_os);
+                let failed = show_file_noindent(
+                    &metadata,
+                    &path_os,
+                    p_canonical_path.get(),
+                    &metadata.len(),
+                );
+                p_canonical_path.pop();
+                failed
+            } else {
+                show_file(indent_width, &metadata, &path_os)
+            };
+
+            // if the entry could not be counted, then remove its contribution from the counts
+            if failed {
+                cur_entry_cnts.dec_file_cnt(1);
+            }
+        } else if metadata.is_dir() {
+            cur_entry_cnts.inc_dir_cnt(1);
+
+            // the name of the directory stays appended to the canonical path while its entries are scanned
+            p_canonical_path.push(&path_os);
+
+            // depending on whether the absolute path (without indentation) needs to be printed,
+            // try to print the current entry
+            let failed = if !shown {
+                false
+            } else if get_option(PrgOptions::ShowNotree) {
+                show_dir_noindent(&metadata, &path_os, p_canonical_path.get())
+            } else {
+                show_dir(indent_width, &metadata, &path_os)
+            };
+
+            // if the entry could not be printed, then remove its contribution from the counts
+            // otherwise, recursively print its contents if the show recursive option is set
+            if failed {
+                cur_entry_cnts.dec_dir_cnt(1);
+            } else {
+                if get_option(PrgOptions::ShowRecursive)
+                    && (*p_max_level == 0u64 || p_level < (*p_max_level as usize))
+                {
+                    if let Some(error) = scan_path(
+                        p_entry_cnts_init,
+                        p_entry_cnts_full,
+                        p_max_level,
+                        1 + p_level,
+                        &path_os,
+                        p_canonical_path,
+                    ) {
+                        if get_option(PrgOptions::ShowErrors) {
+                            eprint!(
+                                "{}",
+                                tr!(Msg::IterateError, path_os.to_string_lossy(), error)
+                            );
+                        }
+                    }
+                }
+            }
+
+            p_canonical_path.pop();
+        } else {
+            cur_entry_cnts.inc_special_cnt(1);
+
+            if !get_option(PrgOptions::ShowSpecial) || !shown {
+                continue;
+            }
+
+            // depending on whether the absolute path (without indentation) needs to be printed,
+            // try to print the current entry
+            let failed = if get_option(PrgOptions::ShowNotree) {
+                p_canonical_path.push(&path_os);
+                let failed = show_special_noindent(
+                    &metadata,
+                    &path_os,
+                    p_canonical_path.get(),
+                    &special_file_type,
+                );
+                p_canonical_path.pop();
+                failed
+            } else {
+                show_special(indent_width, &metadata, &path_os, &special_file_type)
+            };
+
+            // if the entry could not be printed, remove its contribution from the counts
+            if failed {
+                cur_entry_cnts.dec_special_cnt(1);
+            }
+        }
+    }
+
+    // for the current directory, the summary needs to be printed for all the entries that were not supposed to be shown
+    // for example, if the show files option is not set, the number of files along with their aggregated size needs
+    // to be printed as a logical entry within the current directory
+    // this is only to be done if the show absolute option is not set
+    if !get_option(PrgOptions::ShowNotree) && shown {
+        // the total size of the files only needs to be printd if the show size option is set for directories
+        // this is because the aggregated files are shown as a logical directory entry (as if the files were within another directory)
+        // if the option was set, print the formatted size, otherwise print and empty string
+        // for special file and symlink aggregate entries, an empty string needs to be printed if the show size option
+        // is not set, and a - character need to be printed if the option is set
+        let (file_sz, sz) = if get_option(PrgOptions::ShowDirSize) {
+            (format_size(total_file_size), "-")
+        } else {
+            (String::new(), "")
+        };
+
+        // if the show files option is not set and there are special files, group them together and show the count
+        if !get_option(PrgOptions::ShowFiles) && cur_entry_cnts.get_file_cnt() != 0 {
+            set_tree_branch(p_level, tree_end == TreeEnd::Files);
+            columns::print_row(
+                None,
+                &file_sz,
+                Some(indent_width),
+                format_args!(
+                    "<{} files>",
+                    int_to_formatted_slice(cur_entry_cnts.get_file_cnt())
+                ),
+            );
+        }
+
+        // if the show symlinks option is not set and there are special files, group them together and show the count
+        if !get_option(PrgOptions::ShowSymlinks) && cur_entry_cnts.get_symlink_cnt() != 0 {
+            set_tree_branch(p_level, tree_end == TreeEnd::Symlinks);
+            columns::print_row(
+                None,
+                sz,
+                Some(indent_width),
+                format_args!(
+                    "<{} symlinks>",
+                    int_to_formatted_slice(cur_entry_cnts.get_symlink_cnt())
+                ),
+            );
+        }
+
+        // if the show special option is not set and there are special files, group them together and show the count
+        if !get_option(PrgOptions::ShowSpecial) && cur_entry_cnts.get_special_cnt() != 0 {
+            set_tree_branch(p_level, tree_end == TreeEnd::Special);
+            columns::print_row(
+                None,
+                sz,
+                Some(indent_width),
+                format_args!(
+                    "<{} special entries>",
+                    int_to_formatted_slice(cur_entry_cnts.get_special_cnt())
+                ),
+            );
+        }
+    }
+
+    // update the final and initial summaries with the current directory's traversal summary
+    if p_level == 0 {
+        p_entry_cnts_init.inc_symlink_cnt(cur_entry_cnts.get_symlink_cnt());
+        p_entry_cnts_init.inc_file_cnt(cur_entry_cnts.get_file_cnt());
+        p_entry_cnts_init.inc_dir_cnt(cur_entry_cnts.get_dir_cnt());
+        p_entry_cnts_init.inc_special_cnt(cur_entry_cnts.get_special_cnt());
+    }
+
+    p_entry_cnts_full.inc_symlink_cnt(cur_entry_cnts.get_symlink_cnt());
+    p_entry_cnts_full.inc_file_cnt(cur_entry_cnts.get_file_cnt());
+    p_entry_cnts_full.inc_dir_cnt(cur_entry_cnts.get_dir_cnt());
+    p_entry_cnts_full.inc_special_cnt(cur_entry_cnts.get_special_cnt());
+
+    record_dir_latency(p_current_path, latency);
+
+    return None;
+}
+
+fn search_path(
+    p_entry_cnts_match: &mut EntryCounter,
+    p_entry_cnts_full: &mut EntryCounter,
+    p_max_level: &u64,
+    p_level: usize,
+    p_current_path: &path::Path,
+    p_search_patterns: &[String],
+    p_canonical_path: &mut CanonicalPath,
+) -> Option<std::io::Error> {
+    // instantiate structure to hold the number of entries of each type in the current directory (not recursive)
+    let mut cur_entry_cnts = EntryCounter::new();
+    // with "--search-tree", matches are indented like while scanning instead of being printed by their paths
+    let search_tree = get_option(PrgOptions::SearchTree);
+    let indent_width = indent_col_width() * p_level;
+
+    // try to read the entries of the current directory
+    // if the entries could not be iterated over (for example, due to insufficient permissions or the current entry being a file)
+    // then return from the function and report this to the caller
+    let read_start = time::Instant::now();
+    let mut entries = match record::read_dir(p_current_path) {
+        Ok(values) => values,
+        Err(error) => {
+            return Some(error);
+        }
+    };
+    // time spent in reading the entries of the current directory and their metadata
+    let mut latency = read_start.elapsed();
+
+    let _hashes_ahead = hash_files_ahead(&entries, p_search_patterns);
+
+    // fuzzy matches are shown once the search finishes, ordered by how well they match (entries that do not match have
+    // no such order, so they are shown right away when the match is inverted)
+    let defer_matches = get_option(PrgOptions::SearchFuzzy) && !get_option(PrgOptions::InvertMatch);
+
+    loop {
+        // once enough matches have been shown, the remaining entries are not read (neither here nor in the directories
+        // that this one lies in)
+        if max_results_reached(p_entry_cnts_match) {
+            break;
+        }
+
+        // read the next entry along with its metadata (will be used to query its type and in the case of regular files, its size)
+        let read_start = time::Instant::now();
+        let next = entries.next();
+        latency += read_start.elapsed();
+
+        let Some(next) = next else {
+            break;
+        };
+
+        progress::tick();
+
+        // if the current entry or its metadata could not be read for some reason, then silently skip it
+        let Ok((path_os, metadata)) = next else {
+            continue;
+        };
+
+        // check for special file (on unix style operating systems, get the specific type as well)
+        let special_file_type = metadata.special_file_type();
+
+        // hidden entries are only skipped while searching with "--no-hidden"
+        if filter::hides(&path_os, &metadata, false) {
+            continue;
+        }
+
+        // if the name of this entry could not be read, silently skip it
+        let Some(matches) = matches_search(&path_os, p_search_patterns) else {
+            continue;
+        };
+        let matches = matches && !below_min_depth(p_level) && filter::keep(&path_os, &metadata);
+
+        if metadata.is_symlink() {
+            // skip if the show symlinks option is not set
+            if !get_option(PrgOptions::ShowSymlinks) {
+                cur_entry_cnts.inc_symlink_cnt(1);
+                continue;
+            }
+
+            if !matches {
+                cur_entry_cnts.inc_symlink_cnt(1);
+                continue;
+            }
+
+            if defer_matches {
+                fuzzy::add(p_search_patterns, &path_os, &metadata);
+                cur_entry_cnts.inc_symlink_cnt(1);
+                continue;
+            }
+
+            let failed = if search_tree {
+                show_search_ancestors();
+                show_symlink(indent_width, &metadata, &path_os, record::is_dir(&path_os))
+            } else {
+                show_symlink_noindent(&metadata, &path_os, record::is_dir(&path_os))
+            };
+
+            if !failed {
+                cur_entry_cnts.inc_symlink_cnt(1);
+                p_entry_cnts_match.inc_symlink_cnt(1);
+                count_pattern_matches(&path_os, p_search_patterns);
+                count_match_location(&path_os, &metadata);
+            }
+        } else if metadata.is_file() && special_file_type == SpecialFileType::NA {
+            if !get_option(PrgOptions::ShowFiles) {
+                cur_entry_cnts.inc_file_cnt(1);
+                continue;
+            }
+
+            if !matches {
+                cur_entry_cnts.inc_file_cnt(1);
+                continue;
+            }
+
+            if defer_matches {
+                fuzzy::add(p_search_patterns, &path_os, &metadata);
+                cur_entry_cnts.inc_file_cnt(1);
+                continue;
+            }
+
+            let failed = if search_tree {
+                show_search_ancestors();
+                show_file(indent_width, &metadata, &path_os)
+            } else {
+                p_canonical_path.push(&path_os);
+                let failed = show_file_noindent(
+                    &metadata,
+                    &path_os,
+                    p_canonical_path.get(),
+                    &metadata.len(),
+                );
+                p_canonical_path.pop();
+                failed
+            };
+
+            if !failed {
+                cur_entry_cnts.inc_file_cnt(1);
+                p_entry_cnts_match.inc_file_cnt(1);
+                count_pattern_matches(&path_os, p_search_patterns);
+                count_match_location(&path_os, &metadata);
+            }
+        } else if metadata.is_dir() {
+            // the name of the directory stays appended to the canonical path while its entries are searched
+            p_canonical_path.push(&path_os);
+
+            if !matches {
+                cur_entry_cnts.inc_dir_cnt(1);
+            } else if defer_matches {
+                fuzzy::add(p_search_patterns, &path_os, &metadata);
+                cur_entry_cnts.inc_dir_cnt(1);
+            } else {
+                let failed = if search_tree {
+                    show_search_ancestors();
+                    show_dir(indent_width, &metadata, &path_os)
+                } else {
+                    show_dir_noindent(&metadata, &path_os, p_canonical_path.get())
+                };
+
+                if !failed {
+                    cur_entry_cnts.inc_dir_cnt(1);
+                    p_entry_cnts_match.inc_dir_cnt(1);
+                    count_pattern_matches(&path_os, p_search_patterns);
+                    count_match_location(&path_os, &metadata);
+                    count_match_location(&path_os, &metadata);
+                }
+            }
+
+            // the directory is only printed before the entries within it if any of them match (unless it matched itself)
+            let descend = get_option(PrgOptions::ShowRecursive)
+                && (*p_max_level == 0u64 || p_level < (*p_max_level as usize));
+            if descend && search_tree {
+                if let Ok(mut ancestors) = SEARCH_ANCESTORS.lock() {
+                    ancestors.push(SearchAncestor {
+                        path: path_os.clone(),
+                        metadata: metadata.clone(),
+                        level: p_level,
+                        printed: matches && !defer_matches,
+                    });
+                }
+            }
+
+            // breadth-first, the directory is searched once every directory before it has been (it stays appended to
+            // the canonical path of the queued directory instead)
+            if descend && get_option(PrgOptions::Bfs) {
+                if let Ok(mut queue) = SEARCH_QUEUE.lock() {
+                    queue.push_back(QueuedDir {
+                        path: path_os.clone(),
+                        canonical: p_canonical_path.get().map(path::Path::to_path_buf),
+                        level: 1 + p_level,
+                    });
+                }
+            } else if descend {
+                if let Some(error) = search_path(
+                    p_entry_cnts_match,
+                    p_entry_cnts_full,
+                    p_max_level,
+                    1 + p_level,
+                    &path_os,
+                    p_search_patterns,
+                    p_canonical_path,
+                ) {
+                    if get_option(PrgOptions::ShowErrors) {
+                        eprint!(
+                            "{}",
+                            tr!(Msg::IterateError, path_os.to_string_lossy(), error)
+                        );
+                    }
+                }
+            }
+
+            if descend && search_tree {
+                if let Ok(mut ancestors) = SEARCH_ANCESTORS.lock() {
+                    ancestors.pop();
+                }
+            }
+            p_canonical_path.pop();
+        } else {
+            if !get_option(PrgOptions::ShowSpecial) {
+                cur_entry_cnts.inc_special_cnt(1);
+                continue;
+            }
+
+            if !matches {
+                cur_entry_cnts.inc_special_cnt(1);
+                continue;
+            }
+
+            if defer_matches {
+                fuzzy::add(p_search_patterns, &path_os, &metadata);
+                cur_entry_cnts.inc_special_cnt(1);
+                continue;
+            }
+
+            let failed = if search_tree {
+                show_search_ancestors();
+                show_special(indent_width, &metadata, &path_os, &special_file_type)
+            } else {
+                p_canonical_path.push(&path_os);
+                let failed = show_special_noindent(
+                    &metadata,
+                    &path_os,
+                    p_canonical_path.get(),
+                    &special_file_type,
+                );
+                p_canonical_path.pop();
+                failed
+            };
+
+            if !failed {
+                cur_entry_cnts.inc_special_cnt(1);
+                p_entry_cnts_match.inc_special_cnt(1);
+                count_pattern_matches(&path_os, p_search_patterns);
+                count_match_location(&path_os, &metadata);
+            }
+        }
+    }
+
+    p_entry_cnts_full.inc_symlink_cnt(cur_entry_cnts.get_symlink_cnt());
+    p_entry_cnts_full.inc_file_cnt(cur_entry_cnts.get_file_cnt());
+    p_entry_cnts_full.inc_dir_cnt(cur_entry_cnts.get_dir_cnt());
+    p_entry_cnts_full.inc_special_cnt(cur_entry_cnts.get_special_cnt());
+
+    record_dir_latency(p_current_path, latency);
+
+    return None;
+}
+
+/// Scans through a directory and prints its entries along with the summaries
+///
+/// Returns the number of entries of each type within the directory and its subdirectories ([None] if the initial
+/// directory could not be scanned)
+fn scan_path_init(p_init_path: &str, p_max_level: &u64) -> Option<EntryCounter> {
+    // create new containers to store files in current directory and subdirectories respectively
+    let mut entry_cnts_init = EntryCounter::new();
+    let mut entry_cnts_full: EntryCounter = EntryCounter::new();
+
+    // create a path object over the initial path
+    let init_path = path::Path::new(&p_init_path);
+
+    // check if the path could be iterated over
+    // if an error occours (such as insufficient permissions, non-existant directory)
+    // then report it and return without printing the summary of traversal
+    let error = scan_path(
+        &mut entry_cnts_init,
+        &mut entry_cnts_full,
+        p_max_level,
+        0,
+        init_path,
+        &mut CanonicalPath::new(init_path, get_option(PrgOptions::ShowNotree)),
+    );
+    progress::finish();
+    columns::flush_rows();
+
+    if let Some(error) = error {
+        print!("{}", tr!(Msg::IterateError, p_init_path, error));
+        return None;
+    }
+
+    let file_cnt = int_to_formatted_slice(entry_cnts_init.get_file_cnt()).to_owned();
+    let symlink_cnt = int_to_formatted_slice(entry_cnts_init.get_symlink_cnt()).to_owned();
+    let special_cnt = int_to_formatted_slice(entry_cnts_init.get_special_cnt()).to_owned();
+    let dir_cnt = int_to_formatted_slice(entry_cnts_init.get_dir_cnt()).to_owned();
+    let total_cnt = int_to_formatted_slice(entry_cnts_init.get_entry_cnt()).to_owned();
+
+    // Unformatted summary string for directory to traverse (not including subdirectories)
+    print!(
+        "{}",
+        tr!(
+            Msg::ScanSummary,
+            p_init_path,
+            file_cnt,
+            symlink_cnt,
+            special_cnt,
+            dir_cnt,
+            total_cnt
+        )
+    );
+
+    // the complete summary is only printed if the recursive traversal option was set
+    if get_option(PrgOptions::ShowRecursive) {
+        let file_cnt = int_to_formatted_slice(entry_cnts_full.get_file_cnt()).to_owned();
+        let symlink_cnt = int_to_formatted_slice(entry_cnts_full.get_symlink_cnt()).to_owned();
+        let special_cnt = int_to_formatted_slice(entry_cnts_full.get_special_cnt()).to_owned();
+        let dir_cnt = int_to_formatted_slice(entry_cnts_full.get_dir_cnt()).to_owned();
+        let total_cnt = int_to_formatted_slice(entry_cnts_full.get_entry_cnt()).to_owned();
+
+        // Unformatted summary string for the directory to traverse (including subdirectories)
+        print!(
+            "{}",
+            tr!(
+                Msg::ScanSummaryRecursive,
+                file_cnt,
+                symlink_cnt,
+                special_cnt,
+                dir_cnt,
+                total_cnt
+            )
+        );
+    }
+
+    if filter::active() {
+        print!(
+            "{}",
+            tr!(
+                Msg::FilterSummary,
+                int_to_formatted_slice(filter::filtered_cnt())
+            )
+        );
+    }
+
+    if get_option(PrgOptions::MeasureLatency) {
+        print_latency_report();
+    }
+
+    return Some(entry_cnts_full);
+}
+
+/// Shows the entries that matched a fuzzy pattern during the search, from the best match to the worst
+///
+/// # Arguments
+///
+/// - `p_entry_cnts_match` - number of shown entries of each type (updated with those shown here)
+/// - `p_search_patterns` - the patterns that were searched for
+fn show_fuzzy_matches(p_entry_cnts_match: &mut EntryCounter, p_search_patterns: &[String]) {
+    for fuzzy::Match {
+        path: path_os,
+        metadata,
+        ..
+    } in fuzzy::take_matches()
+    {
+        // the matches are only known once the search finishes, so only the best of them are shown with "--max-results"
+        if max_results_reached(p_entry_cnts_match) {
+            break;
+        }
+
+        let special_file_type = metadata.special_file_type();
+
+        let failed = if metadata.is_symlink() {
+            let failed = show_symlink_noindent(&metadata, &path_os, record::is_dir(&path_os));
+            if !failed {
+                p_entry_cnts_match.inc_symlink_cnt(1);
+            }
+            failed
+        } else if metadata.is_file() && special_file_type == SpecialFileType::NA {
+            let failed = show_file_noindent(&metadata, &path_os, None, &metadata.len());
+            if !failed {
+                p_entry_cnts_match.inc_file_cnt(1);
+            }
+            failed
+        } else if metadata.is_dir() {
+            let failed = show_dir_noindent(&metadata, &path_os, None);
+            if !failed {
+                p_entry_cnts_match.inc_dir_cnt(1);
+            }
+            failed
+        } else {
+            let failed = show_special_noindent(&metadata, &path_os, None, &special_file_type);
+            if !failed {
+                p_entry_cnts_match.inc_special_cnt(1);
+            }
+            failed
+        };
+
+        if !failed {
+            count_pattern_matches(&path_os, p_search_patterns);
+            count_match_location(&path_os, &metadata);
+        }
+    }
+}
+
+/// Searches through a directory and prints the matching entries along with the summaries
+///
+/// Returns the number of matching entries of each type that were shown ([None] if the initial directory could not be
+/// searched)
+fn search_path_init(
+    p_init_path: &str,
+    p_search_patterns: &[String],
+    p_max_level: &u64,
+) -> Option<EntryCounter> {
+    let mut entry_cnts_match = EntryCounter::new();
+    let mut entry_cnts_total: EntryCounter = EntryCounter::new();
+
+    let init_path = path::Path::new(&p_init_path);
+
+    let error = search_path(
+        &mut entry_cnts_match,
+        &mut entry_cnts_total,
+        p_max_level,
+        0,
+        init_path,
+        p_search_patterns,
+        &mut CanonicalPath::new(init_path, true),
+    );
+
+    // breadth-first, the directories are searched in the order that they were found, once each level is done
+    while let Some(dir) = SEARCH_QUEUE
+        .lock()
+        .ok()
+        .and_then(|mut queue| queue.pop_front())
+    {
+        if max_results_reached(&entry_cnts_match) {
+            break;
+        }
+
+        if let Some(error) = search_path(
+            &mut entry_cnts_match,
+            &mut entry_cnts_total,
+            p_max_level,
+            dir.level,
+            &dir.path,
+            p_search_patterns,
+            &mut CanonicalPath(dir.canonical),
+        ) {
+            if get_option(PrgOptions::ShowErrors) {
+                eprint!(
+                    "{}",
+                    tr!(Msg::IterateError, dir.path.to_string_lossy(), error)
+                );
+            }
+        }
+    }
+    progress::finish();
+
+    if let Some(error) = error {
+        if get_option(PrgOptions::ShowErrors) {
+            eprint!("{}", tr!(Msg::IterateError, p_init_path, error));
+        }
+        return None;
+    }
+
+    if get_option(PrgOptions::SearchFuzzy) {
+        show_fuzzy_matches(&mut entry_cnts_match, p_search_patterns);
+    }
+    columns::flush_rows();
+
+    let file_cnt = int_to_formatted_slice(entry_cnts_match.get_file_cnt()).to_owned();
+    let symlink_cnt = int_to_formatted_slice(entry_cnts_match.get_symlink_cnt()).to_owned();
+    let special_cnt = int_to_formatted_slice(entry_cnts_match.get_special_cnt()).to_owned();
+    let dir_cnt = int_to_formatted_slice(entry_cnts_match.get_dir_cnt()).to_owned();
+    let total_cnt = int_to_formatted_slice(entry_cnts_match.get_entry_cnt()).to_owned();
+
+    // Unformatted summary string for number of entries found matching search pattern (in search mode)
+    print!(
+        "{}",
+        tr!(
+            Msg::SearchSummaryMatches,
+            file_cnt,
+            symlink_cnt,
+            special_cnt,
+            dir_cnt,
+            total_cnt
+        )
+    );
+
+    // the matches are broken down by pattern if several were given (an entry counts towards each pattern it matches),
+    // unless the match is inverted
+    if p_search_patterns.len() > 1 && !get_option(PrgOptions::InvertMatch) {
+        let pattern_cnts = PATTERN_MATCH_CNTS
+            .lock()
+            .map(|mut cnts| std::mem::take(&mut *cnts))
+            .unwrap_or_default();
+
+        print!("{}", tr!(Msg::SearchSummaryPatterns));
+        for (idx, pattern) in p_search_patterns.iter().enumerate() {
+            let cnt = pattern_cnts.get(idx).copied().unwrap_or(0);
+            print!(
+                "{}",
+                tr!(
+                    Msg::SearchSummaryPattern,
+                    int_to_formatted_slice(cnt),
+                    pattern
+                )
+            );
+        }
+        print!("\n");
+    }
+
+    if get_option(PrgOptions::CountsByDir) {
+        print_match_location_cnts();
+    }
+
+    if filter::active() {
+        print!(
+            "{}",
+            tr!(
+                Msg::FilterSummary,
+                int_to_formatted_slice(filter::filtered_cnt())
+            )
+        );
+    }
+
+    let file_cnt = int_to_formatted_slice(entry_cnts_total.get_file_cnt()).to_owned();
+    let symlink_cnt = int_to_formatted_slice(entry_cnts_total.get_symlink_cnt()).to_owned();
+    let special_cnt = int_to_formatted_slice(entry_cnts_total.get_special_cnt()).to_owned();
+    let dir_cnt = int_to_formatted_slice(entry_cnts_total.get_dir_cnt()).to_owned();
+    let total_cnt = int_to_formatted_slice(entry_cnts_total.get_entry_cnt()).to_owned();
+
+    // Unformatted summary string for number of entries traversed while matching search pattern (in search mode)
+    print!(
+        "{}",
+        tr!(
+            Msg::SearchSummaryTraversal,
+            p_init_path,
+            file_cnt,
+            symlink_cnt,
+            special_cnt,
+            dir_cnt,
+            total_cnt
+        )
+    );
+
+    // fuzzy matches are ranked once every entry has been read, so only the other searches stop early
+    if max_results_reached(&entry_cnts_match) && !get_option(PrgOptions::SearchFuzzy) {
+        print!(
+            "{}",
+            tr!(
+                Msg::SearchStopped,
+                int_to_formatted_slice(entry_cnts_match.get_entry_cnt())
+            )
+        );
+    }
+
+    if get_option(PrgOptions::MeasureLatency) {
+        print_latency_report();
+    }
+
+    return Some(entry_cnts_match);
+}
+
+/// Prints the entries of the initial directory in the plain mode or format that is set (see [print_plain_entries])
+///
+/// Returns the number of entries that were printed ([None] if the initial directory could not be read, or the export
+/// could not be created)
+///
+/// # Arguments
+///
+/// - `p_init_path` - path of the directory to print the entries of
+/// - `p_search_patterns` - patterns that the names of the printed entries must match one of (if a search option is set)
+/// - `p_max_level` - maximum level upto which entries are printed (a value of 0 denotes no limit)
+fn plain_init(p_init_path: &str, p_search_patterns: &[String], p_max_level: &u64) -> Option<u64> {
+    let init_path = path::Path::new(p_init_path);
+
+    if let Err(error) = record::read_dir(init_path) {
+        print!("{}", tr!(Msg::IterateError, p_init_path, error));
+        return None;
+    }
+
+    if let Err(error) = sqlite::begin(p_init_path) {
+        let db_path = sqlite::get_db_path().unwrap_or(path::Path::new(""));
+        print!(
+            "{}",
+            tr!(Msg::SqliteCreateError, db_path.to_string_lossy(), error)
+        );
+        return None;
+    }
+
+    #[cfg(feature = "export-parquet")]
+    if let Err(error) = parquet::begin() {
+        let parquet_path = parquet::get_parquet_path().unwrap_or(path::Path::new(""));
+        print!(
+            "{}",
+            tr!(
+                Msg::ParquetCreateError,
+                parquet_path.to_string_lossy(),
+                error
+            )
+        );
+        return None;
+    }
+
+    export::begin(p_init_path);
+    let mut shown_cnt = 0;
+    let total_size = print_plain_entries(
+        p_max_level,
+        0,
+        init_path,
+        p_search_patterns,
+        true,
+        &mut shown_cnt,
+    );
+    export::finish(total_size);
+    progress::finish();
+    sqlite::finish();
+    #[cfg(feature = "export-parquet")]
+    parquet::finish();
+
+    // like du, the initial directory itself comes last
+    if get_option(PrgOptions::SizesOnly) {
+        print!("{}\t{}\n", total_size, escape::name(p_init_path));
+    }
+
+    return Some(shown_cnt);
+}
+
+fn find_expr_init(p_init_path: &str, p_find_expr: &find::FindExpr) {
+    let init_path = path::Path::new(p_init_path);
+
+    if let Err(error) = record::read_dir(init_path) {
+        print!("{}", tr!(Msg::IterateError, p_init_path, error));
+        return;
+    }
+
+    find::find_entries(p_find_expr, 1, init_path, time::SystemTime::now());
+    progress::finish();
+}
+
+fn dirs_only_init(p_init_path: &str, p_max_level: &u64) {
+    // create a path object over the initial path
+    let init_path = path::Path::new(&p_init_path);
+
+    // check if the path could be iterated over
+    // if an error occours (such as insufficient permissions, non-existant directory)
+    // then report it and return without printing the summary of traversal
+    let metadata = match fs::read_dir(init_path).and_then(|_| fs::metadata(init_path)) {
+        Ok(metadata) => metadata,
+        Err(error) => {
+            print!("{}", tr!(Msg::IterateError, p_init_path, error));
+            return;
+        }
+    };
+
+    let mut arena = arena::PathArena::new(init_path);
+    let root_dir = arena.intern_dir(init_path);
+    let root = build_dir_tree(p_max_level, 0, &mut arena, root_dir, init_path, metadata);
+
+    print_dir_tree(&arena, 0, &root);
+    columns::flush_rows();
+
+    // the cumulative size of the initial directory is printed last (like du)
+    if get_option(PrgOptions::ShowDirSize) {
+        let total_size = if let Some(size) = root.size {
+            int_to_formatted_slice(size)
+        } else {
+            "ERROR"
+        };
+
+        print!("{}", tr!(Msg::DirsOnlyTotal, p_init_path, total_size));
+    }
+}
+
+fn name_collisions_init(p_init_path: &str, p_max_level: &u64) {
+    let init_path = path::Path::new(&p_init_path);
+
+    if let Err(error) = fs::read_dir(init_path) {
+        print!("{}", tr!(Msg::IterateError, p_init_path, error));
+        return;
+    }
+
+    let mut arena = arena::PathArena::new(init_path);
+    let mut files: BTreeMap<OsString, Vec<NamedFile>> = BTreeMap::new();
+    visit_files(p_max_level, 0, init_path, &mut |path_os, metadata| {
+        let dir = arena.intern_dir(path_os.parent().unwrap_or(init_path));
+        let name = path_os.file_name().unwrap_or_default().to_owned();
+        files.entry(name).or_default().push(NamedFile {
+            dir,
+            len: metadata.len(),
+        });
+    });
+
+    let mut name_cnt: u64 = 0;
+    let mut file_cnt: u64 = 0;
+
+    for (name, files) in files {
+        if files.len() < 2 {
+            continue;
+        }
+
+        let groups = split_name_collisions(&arena, &name, files);
+        if !groups.is_empty() {
+            name_cnt += 1;
+        }
+
+        for group in groups {
+            let mut group: Vec<_> = group
+                .into_iter()
+                .map(|(file, hash)| (arena.path(file.dir, &name), file.len, hash))
+                .collect();
+            group.sort_by(|a, b| a.0.cmp(&b.0));
+
+            file_cnt += group.len() as u64;
+
+            print!("{}\n", escape::name(&name));
+
+            for (path_os, len, hash) in &group {
+                if let (Some(hash), Some(algo)) = (hash, hash::get_hash_algo()) {
+                    print!("  {:hex_len$}", hash, hex_len = algo.hex_len());
+                }
+
+                print!("{:>20}    {}\n", format_size(*len), escape::name(path_os));
+            }
+
+            print!("\n");
+        }
+    }
+
+    print!(
+        "{}",
+        tr!(Msg::NameCollisionSummary, p_init_path, name_cnt, file_cnt)
+    );
+}
+
+fn hardlinks_init(p_init_path: &str, p_max_level: &u64) {
+    let init_path = path::Path::new(&p_init_path);
+
+    if let Err(error) = fs::read_dir(init_path) {
+        print!("{}", tr!(Msg::IterateError, p_init_path, error));
+        return;
+    }
+
+    // inodes are only unique within a device, so the links are grouped by both
+    let mut arena = arena::PathArena::new(init_path);
+    let mut inodes: BTreeMap<(u64, u64), LinkedInode> = BTreeMap::new();
+    visit_files(p_max_level, 0, init_path, &mut |path_os, metadata| {
+        let Some((dev, ino, nlink)) = hardlink_info(&metadata).filter(|info| info.2 > 1) else {
+            return;
+        };
+
+        let dir = arena.intern_dir(path_os.parent().unwrap_or(init_path));
+        let name = path_os.file_name().unwrap_or_default().to_owned();
+        inodes
+            .entry((dev, ino))
+            .or_insert_with(|| LinkedInode {
+                nlink,
+                len: metadata.len(),
+                links: Vec::new(),
+            })
+            .links
+            .push((dir, name));
+    });
+
+    // the groups are sorted by their first paths, since the order in which entries are read differs between filesystems
+    let mut groups: Vec<(u64, LinkedInode, Vec<path::PathBuf>)> = inodes
+        .into_iter()
+        .map(|((_, ino), inode)| {
+            let mut paths: Vec<path::PathBuf> = inode
+                .links
+                .iter()
+                .map(|(dir, name)| arena.path(*dir, name))
+                .collect();
+            paths.sort();
+            return (ino, inode, paths);
+        })
+        .collect();
+    groups.sort_by(|a, b| a.2.cmp(&b.2));
+
+    let mut file_cnt: u64 = 0;
+    let mut saved_size: u64 = 0;
+
+    print!("{}", tr!(Msg::HardlinksHeader, p_init_path));
+
+    for (ino, inode, paths) in &groups {
+        // every link after the first one takes up no extra space
+        file_cnt += paths.len() as u64;
+        saved_size += inode.len * (paths.len() as u64 - 1);
+
+        print!("{}", tr!(Msg::HardlinkGroup, ino, inode.nlink));
+        for path_os in paths {
+            print!(
+                "{:>20}    {}\n",
+                format_size(inode.len),
+                escape::name(path_os)
+            );
+        }
+    }
+
+    let inode_cnt = int_to_formatted_slice(groups.len() as u64).to_owned();
+    let file_cnt = int_to_formatted_slice(file_cnt).to_owned();
+    let saved_size = int_to_formatted_slice(saved_size).to_owned();
+
+    print!(
+        "{}",
+        tr!(Msg::HardlinksSummary, inode_cnt, file_cnt, saved_size)
+    );
+}
+
+fn file_age_init(p_init_path: &str, p_max_level: &u64, p_oldest_cnt: usize, p_newest_cnt: usize) {
+    let init_path = path::Path::new(&p_init_path);
+
+    if let Err(error) = fs::read_dir(init_path) {
+        print!("{}", tr!(Msg::IterateError, p_init_path, error));
+        return;
+    }
+
+    // only the required number of files are kept, the oldest ones in a max-heap and the newest ones in a min-heap
+    let mut oldest: BinaryHeap<(time::SystemTime, path::PathBuf, u64)> = BinaryHeap::new();
+    let mut newest: BinaryHeap<Reverse<(time::SystemTime, path::PathBuf, u64)>> = BinaryHeap::new();
+
+    visit_files(p_max_level, 0, init_path, &mut |path_os, metadata| {
+        let modified = match metadata.modified() {
+            Ok(time) => time,
+            Err(_) => {
+                // the error was already reported once for all files if the filesystem has no modification times
+                if get_option(PrgOptions::ShowErrors)
+                    && !capability::is_unavailable(capability::Capability::ModifiedTime)
+                {
+                    eprint!("{}", tr!(Msg::ModifiedTimeError, path_os.to_string_lossy()));
+                }
+                return;
+            }
+        };
+
+        if get_option(PrgOptions::Oldest) {
+            oldest.push((modified, path_os.clone(), metadata.len()));
+            if oldest.len() > p_oldest_cnt {
+                oldest.pop();
+            }
+        }
+
+        if get_option(PrgOptions::Newest) {
+            newest.push(Reverse((modified, path_os, metadata.len())));
+            if newest.len() > p_newest_cnt {
+                newest.pop();
+            }
+        }
+    });
+
+    let now = time::SystemTime::now();
+
+    if get_option(PrgOptions::Oldest) {
+        print!("{}", tr!(Msg::OldestFiles, p_init_path));
+        for (modified, path_os, size) in oldest.into_sorted_vec() {
+            print_file_age(now, modified, &path_os, size);
+        }
+        print!("\n");
+    }
+
+    if get_option(PrgOptions::Newest) {
+        print!("{}", tr!(Msg::NewestFiles, p_init_path));
+        for Reverse((modified, path_os, size)) in newest.into_sorted_vec() {
+            print_file_age(now, modified, &path_os, size);
+        }
+        print!("\n");
+    }
+}
+
+fn cold_data_init(p_init_path: &str, p_max_level: &u64, p_min_age: time::Duration) {
+    let init_path = path::Path::new(&p_init_path);
+
+    if let Err(error) = fs::read_dir(init_path) {
+        print!("{}", tr!(Msg::IterateError, p_init_path, error));
+        return;
+    }
+
+    #[cfg(target_os = "linux")]
+    if let Some(mount_point) = noatime_mount_point(init_path) {
+        eprint!(
+            "{}",
+            tr!(Msg::AtimeUnreliable, mount_point.to_string_lossy())
+        );
+    }
+
+    // files accessed before this time are cold (if the time can not be represented, no file is old enough)
+    let Some(threshold) = time::SystemTime::now().checked_sub(p_min_age) else {
+        return;
+    };
+
+    let mut dirs: BTreeMap<path::PathBuf, ColdDir> = BTreeMap::new();
+
+    visit_files(p_max_level, 0, init_path, &mut |path_os, metadata| {
+        let accessed = match metadata.accessed() {
+            Ok(time) => time,
+            Err(error) => {
+                if get_option(PrgOptions::ShowErrors)
+                    && !capability::is_unavailable(capability::Capability::AccessTime)
+                {
+                    eprint!(
+                        "{}",
+                        tr!(Msg::AccessTimeError, path_os.to_string_lossy(), error)
+                    );
+                }
+                return;
+            }
+        };
+
+        let dir = dirs
+            .entry(path_os.parent().unwrap_or(init_path).to_path_buf())
+            .or_default();
+
+        dir.total_size += metadata.len();
+        if accessed < threshold {
+            dir.cold_cnt += 1;
+            dir.cold_size += metadata.len();
+        }
+    });
+
+    let total_size: u64 = dirs.values().map(|dir| dir.total_size).sum();
+
+    // like du, the directories with the most cold data are shown first
+    let mut dirs: Vec<(path::PathBuf, ColdDir)> = dirs
+        .into_iter()
+        .filter(|(_, dir)| dir.cold_cnt > 0)
+        .collect();
+    dirs.sort_by_key(|(_, dir)| Reverse(dir.cold_size));
+
+    print!(
+        "{}",
+        tr!(Msg::ColdDataHeader, p_init_path, format_age(p_min_age))
+    );
+
+    for (dir_path, dir) in &dirs {
+        // the share of the size of the directory that is cold (empty cold files make up all of an empty directory)
+        let cold_percent = (dir.cold_size * 100)
+            .checked_div(dir.total_size)
+            .unwrap_or(100);
+
+        print!(
+            "{:>20}{:>6}%    {}",
+            format_size(dir.cold_size),
+            cold_percent,
+            tr!(Msg::ColdDir, dir_path.to_string_lossy(), dir.cold_cnt)
+        );
+    }
+
+    let cold_cnt =
+        int_to_formatted_slice(dirs.iter().map(|(_, dir)| dir.cold_cnt).sum::<u64>()).to_owned();
+    let cold_size =
+        int_to_formatted_slice(dirs.iter().map(|(_, dir)| dir.cold_size).sum::<u64>()).to_owned();
+    let total_size = int_to_formatted_slice(total_size).to_owned();
+
+    print!(
+        "{}",
+        tr!(Msg::ColdDataSummary, cold_cnt, cold_size, total_size)
+    );
+}
+
+fn empty_dirs_init(p_init_path: &str, p_max_level: &u64, p_nested: bool) {
+    let init_path = path::Path::new(&p_init_path);
+
+    if let Err(error) = fs::read_dir(init_path) {
+        print!("{}", tr!(Msg::IterateError, p_init_path, error));
+        return;
+    }
+
+    let mut empty_dirs = Vec::new();
+    find_empty_dirs(p_max_level, 0, init_path, p_nested, &mut empty_dirs);
+
+    // the directories are printed by path, so that the nested ones follow the directories they lie in
+    empty_dirs.sort();
+
+    print!("{}", tr!(Msg::EmptyDirsHeader, p_init_path));
+    for dir_path in &empty_dirs {
+        print!("{}\n", dir_path.to_string_lossy());
+    }
+
+    let empty_cnt = int_to_formatted_slice(empty_dirs.len() as u64).to_owned();
+    print!("{}", tr!(Msg::EmptyDirsSummary, empty_cnt));
+}
+
+fn timestamp_anomalies_init(p_init_path: &str, p_max_level: &u64, p_max_ctime_gap: time::Duration) {
+    let init_path = path::Path::new(&p_init_path);
+
+    if let Err(error) = fs::read_dir(init_path) {
+        print!("{}", tr!(Msg::IterateError, p_init_path, error));
+        return;
+    }
+
+    let now = time::SystemTime::now();
+    let mut anomalies: Vec<(path::PathBuf, time::SystemTime, String)> = Vec::new();
+
+    visit_files(p_max_level, 0, init_path, &mut |path_os, metadata| {
+        let modified = match metadata.modified() {
+            Ok(time) => time,
+            Err(_) => {
+                // the error was already reported once for all files if the filesystem has no modification times
+                if get_option(PrgOptions::ShowErrors)
+                    && !capability::is_unavailable(capability::Capability::ModifiedTime)
+                {
+                    eprint!("{}", tr!(Msg::ModifiedTimeError, path_os.to_string_lossy()));
+                }
+                return;
+            }
+        };
+
+        if let Some(anomaly) = timestamp_anomalies(now, modified, &metadata, p_max_ctime_gap) {
+            anomalies.push((path_os, modified, anomaly));
+        }
+    });
+
+    // the files are sorted by path, since the order in which entries are read differs between filesystems
+    anomalies.sort_by(|a, b| a.0.cmp(&b.0));
+
+    print!("{}", tr!(Msg::TimestampAnomaliesHeader, p_init_path));
+
+    for (path_os, modified, anomaly) in &anomalies {
+        print!(
+            "{:>FMT_TIME_WIDTH$}    {}    ({})\n",
+            format_modif_time(*modified),
+            path_os.to_string_lossy(),
+            anomaly
+        );
+    }
+
+    print!(
+        "{}",
+        tr!(
+            Msg::TimestampAnomaliesSummary,
+            int_to_formatted_slice(anomalies.len() as u64)
+        )
+    );
+}
+
+fn fingerprint_init(p_init_path: &str, p_max_level: &u64) {
+    let init_path = path::Path::new(&p_init_path);
+
+    if let Err(error) = fs::read_dir(init_path) {
+        print!("{}", tr!(Msg::IterateError, p_init_path, error));
+        return;
+    }
+
+    let mut arena = arena::PathArena::new(init_path);
+    let root_dir = arena.intern_dir(init_path);
+    let fingerprint = build_fingerprint(p_max_level, 0, &mut arena, root_dir, init_path);
+
+    // the header identifies the format, so that fingerprints from different versions are not compared by mistake
+    print!("# fss fingerprint v1\n");
+    print_fingerprint(&arena, &fingerprint);
+}
+
+fn transfer_estimate_init(p_init_path: &str, p_dest_path: &str) {
+    let mut estimate = TransferEstimate {
+        new_files: 0,
+        new_bytes: 0,
+        changed_files: 0,
+        changed_bytes: 0,
+        unchanged_files: 0,
+    };
+
+    // either side can be a recording or snapshot of an earlier scan instead of a directory
+    let (src, src_path) = match TransferSide::open(p_init_path) {
+        Ok(side) => side,
+        Err(error) => {
+            print!("{}", tr!(Msg::RecordingReadError, p_init_path, error));
+            return;
+        }
+    };
+    let (dst, dst_path) = match TransferSide::open(p_dest_path) {
+        Ok(side) => side,
+        Err(error) => {
+            print!("{}", tr!(Msg::RecordingReadError, p_dest_path, error));
+            return;
+        }
+    };
+
+    if let Some(error) = estimate_transfer(&mut estimate, &src, &src_path, &dst, &dst_path) {
+        print!("{}", tr!(Msg::IterateError, p_init_path, error));
+        return;
+    }
+
+    let new_files = int_to_formatted_slice(estimate.new_files).to_owned();
+    let new_bytes = int_to_formatted_slice(estimate.new_bytes).to_owned();
+    let changed_files = int_to_formatted_slice(estimate.changed_files).to_owned();
+    let changed_bytes = int_to_formatted_slice(estimate.changed_bytes).to_owned();
+    let unchanged_files = int_to_formatted_slice(estimate.unchanged_files).to_owned();
+    let total_bytes =
+        int_to_formatted_slice(estimate.new_bytes + estimate.changed_bytes).to_owned();
+
+    // Unformatted summary string for the estimated amount of data to transfer
+    print!(
+        "{}",
+        tr!(
+            Msg::TransferSummary,
+            p_init_path,
+            p_dest_path,
+            new_files,
+            new_bytes,
+            changed_files,
+            changed_bytes,
+            unchanged_files,
+            total_bytes
+        )
+    );
+}
+
+/// Redirects stdout into a file, so that everything printed from then on is written into it
+///
+/// # Arguments
+///
+/// - `p_path` - path of the file
+/// - `p_append` - whether to append to the file instead of overwriting it
+fn redirect_stdout(p_path: &path::Path, p_append: bool) -> std::io::Result<()> {
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .append(p_append)
+        .truncate(!p_append)
+        .open(p_path)?;
+
+    return redirect_stdout_into(&file);
+}
+
+#[cfg(target_family = "unix")]
+/// Redirects stdout into an open file, so that everything printed from then on is written into it
+///
+/// # Arguments
+///
+/// - `p_file` - the file
+fn redirect_stdout_into(p_file: &fs::File) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::io::AsRawFd;
+
+    std::io::stdout().flush()?;
+
+    if unsafe { libc::dup2(p_file.as_raw_fd(), libc::STDOUT_FILENO) } < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    return Ok(());
+}
+
+#[cfg(not(target_family = "unix"))]
+/// Redirects stdout into an open file (not supported on this platform)
+///
+/// # Arguments
+///
+/// - `_p_file` - the file
+fn redirect_stdout_into(_p_file: &fs::File) -> std::io::Result<()> {
+    return Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "output files are only supported on unix",
+    ));
+}
+
+/// Creates a temporary file with a unique name next to the output file given to `--output` (named like the hidden
+/// temporary files of rsync), and returns its path along with the file
+///
+/// # Arguments
+///
+/// - `p_path` - path of the output file
+fn create_tmp_output(p_path: &path::Path) -> std::io::Result<(path::PathBuf, fs::File)> {
+    let dir_path = p_path.parent().unwrap_or(path::Path::new(""));
+    let name = p_path.file_name().unwrap_or_default().to_string_lossy();
+
+    let mut attempt: u32 = 0;
+    loop {
+        let tmp_path = dir_path.join(format!(".{}.{}-{}.tmp", name, process::id(), attempt));
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&tmp_path)
+        {
+            Ok(file) => return Ok((tmp_path, file)),
+            Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists && attempt < 100 => {
+                attempt += 1;
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+/// Removes the temporary file written instead of the output file given to `--output`, unless it has replaced the
+/// output file (runs when the process exits, so that no temporary file is left behind whichever way it exits)
+#[cfg(target_family = "unix")]
+extern "C" fn remove_tmp_output() {
+    if let Some(output) = OUTPUT.get() {
+        if !OUTPUT_DONE.load(atomic::Ordering::Relaxed) {
+            let _ = fs::remove_file(&output.tmp_path);
+        }
+    }
+}
+
+/// Redirects stdout into a temporary file next to the output file given to `--output`, which replaces the output file
+/// once the scan finishes (see [finish_output])
+///
+/// The temporary file is removed if the process exits before that, so the output file is either left as it was or
+/// replaced with the complete output
+///
+/// # Arguments
+///
+/// - `p_path` - path of the output file
+/// - `p_append` - whether to keep the contents of the output file and append the output to them
+fn start_output(p_path: &path::Path, p_append: bool) -> std::io::Result<()> {
+    let (tmp_path, mut tmp_file) = create_tmp_output(p_path)?;
+
+    // the previous contents are copied into the temporary file, so that they are kept if the scan does not finish
+    let copied = match p_append {
+        true => match fs::File::open(p_path) {
+            Ok(mut file) => std::io::copy(&mut file, &mut tmp_file).map(|_| ()),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(error),
+        },
+        false => Ok(()),
+    };
+
+    if let Err(error) = copied.and_then(|_| redirect_stdout_into(&tmp_file)) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(error);
+    }
+
+    let _ = OUTPUT.set(OutputFile {
+        path: p_path.to_path_buf(),
+        tmp_path,
+        tmp_file,
+    });
+
+    #[cfg(target_family = "unix")]
+    unsafe {
+        libc::atexit(remove_tmp_output);
+    }
+    return Ok(());
+}
+
+/// Replaces the output file given to `--output` with the temporary file that was written instead of it, once its
+/// contents have reached the disk (has no effect if `--output` was not given)
+fn finish_output() -> std::io::Result<()> {
+    use std::io::Write;
+
+    let Some(output) = OUTPUT.get() else {
+        return Ok(());
+    };
+
+    std::io::stdout().flush()?;
+    output.tmp_file.sync_all()?;
+    fs::rename(&output.tmp_path, &output.path)?;
+
+    OUTPUT_DONE.store(true, atomic::Ordering::Relaxed);
+    return Ok(());
+}
+
+/// Writes a separate report for each directory directly under the initial directory, by running fss on each of them
+/// with its output redirected to a file named after the directory
+///
+/// # Arguments
+///
+/// - `p_init_path` - path of the directory whose subdirectories are reported on
+/// - `p_split_dir` - directory to write the reports into (created if it does not exist)
+/// - `p_args` - arguments to run fss with for each subdirectory (without the path and the split output option)
+fn split_output_init(p_init_path: &str, p_split_dir: &str, p_args: &[String]) {
+    let split_dir = path::Path::new(p_split_dir);
+
+    if let Err(error) = fs::create_dir_all(split_dir) {
+        print!("{}", tr!(Msg::SplitDirCreateError, p_split_dir, error));
+        return;
+    }
+
+    let entries = match fs::read_dir(p_init_path) {
+        Ok(entries) => entries,
+        Err(error) => {
+            print!("{}", tr!(Msg::IterateError, p_init_path, error));
+            return;
+        }
+    };
+
+    let Ok(exe_path) = env::current_exe() else {
+        print!("{}", tr!(Msg::SplitExeError));
+        return;
+    };
+
+    // the reports are written in the order of the names, and never for the directory holding the reports itself (nor
+    // for hidden directories, which are left out as they are in listings)
+    let split_dir_canonical = split_dir.canonicalize().ok();
+    let mut dirs: Vec<path::PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+        .filter(|entry| {
+            entry.metadata().is_ok_and(|metadata| {
+                !filter::hides(
+                    &entry.path(),
+                    &record::EntryMetadata::from_metadata(metadata),
+                    true,
+                )
+            })
+        })
+        .map(|entry| entry.path())
+        .filter(|dir_path| dir_path.canonicalize().ok() != split_dir_canonical)
+        .collect();
+    dirs.sort();
+
+    let mut report_cnt: u64 = 0;
+
+    for dir_path in &dirs {
+        let Some(name) = dir_path.file_name() else {
+            continue;
+        };
+
+        let mut report_name = name.to_os_string();
+        report_name.push(".txt");
+        let report_path = split_dir.join(report_name);
+
+        let report = match fs::File::create(&report_path) {
+            Ok(report) => report,
+            Err(error) => {
+                print!(
+                    "{}",
+                    tr!(Msg::ReportCreateError, report_path.to_string_lossy(), error)
+                );
+                continue;
+            }
+        };
+
+        let status = process::Command::new(&exe_path)
+            .arg(dir_path)
+            .args(p_args)
+            .stdout(report)
+            .status();
+
+        match status {
+            Ok(_) => {
+                report_cnt += 1;
+                print!(
+                    "{}",
+                    tr!(
+                        Msg::ReportWritten,
+                        dir_path.to_string_lossy(),
+                        report_path.to_string_lossy()
+                    )
+                );
+            }
+            Err(error) => {
+                print!(
+                    "{}",
+                    tr!(Msg::ReportRunError, dir_path.to_string_lossy(), error)
+                );
+            }
+        }
+    }
+
+    print!(
+        "{}",
+        tr!(
+            Msg::SplitOutputSummary,
+            int_to_formatted_slice(report_cnt),
+            p_split_dir
+        )
+    );
+}
+
+fn main() {
+    // like other command line tools, the scan stops quietly once whatever reads its output goes away (such as head),
+    // instead of panicking on the next entry that it fails to print
+    #[cfg(target_family = "unix")]
+    unsafe {
+        libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+    }
+
+    // Paths to start the scan process from, in the order they were given (the current directory if none was given)
+    let mut init_paths: Vec<String> = Vec::new();
+
+    // Patterns to search for (entries matching any of them are shown, since the search flag can be repeated)
+    let mut search_patterns: Vec<String> = Vec::new();
+
+    // whether the previous flag was "-r" or "--recursive"
+    let mut specify_recur_depth: bool = false;
+
+    let mut specify_search_path: bool = false;
+
+    // whether the previous flag was "--patterns-from"
+    let mut specify_patterns_from: bool = false;
+
+    // whether the previous flag was "--paths-from"
+    let mut specify_paths_from: bool = false;
+
+    // Path of the directory to estimate the transfer size against
+    let mut dest_path: String = "".to_owned();
+
+    // whether the previous flag was "--transfer-estimate"
+    let mut specify_dest_path: bool = false;
+
+    // whether the previous flag was "--compress-algo"
+    let mut specify_compress_algo: bool = false;
+
+    // whether the previous flag was "--hash-algo"
+    let mut specify_hash_algo: bool = false;
+
+    // whether the previous flag was "--hash-cache"
+    let mut specify_hash_cache: bool = false;
+
+    // whether the previous flag was "--checkpoint"
+    let mut specify_checkpoint: bool = false;
+
+    // number of threads that read directories and hash files ahead of the traversal (none by default)
+    let mut walk_threads: usize = 0;
+    let mut work_threads: usize = 0;
+
+    // whether the previous flag was "--walk-threads" or "--work-threads"
+    let mut specify_walk_threads: bool = false;
+    let mut specify_work_threads: bool = false;
+
+    // time to wait after the last change before reporting changes in watch mode
+    let mut debounce = time::Duration::ZERO;
+
+    // whether the previous flag was "--debounce"
+    let mut specify_debounce: bool = false;
+
+    // commands to run for changes in watch mode
+    let mut watch_hooks = watch::WatchHooks {
+        on_create: None,
+        on_modify: None,
+        on_delete: None,
+    };
+
+    // Path of the file to record the scan into
+    let mut record_path: String = "".to_owned();
+
+    // whether the previous flag was "--record"
+    let mut specify_record: bool = false;
+
+    // Path of the recording to replay the scan from
+    let mut replay_path: String = "".to_owned();
+
+    // whether the previous flag was "--replay"
+    let mut specify_replay: bool = false;
+
+    // number of columns to indent each level of nesting by, and the style to indent with
+    let mut indent_col_width = DEFAULT_INDENT_COL_WIDTH;
+    let mut indent_style = IndentStyle::Spaces;
+
+    // whether the previous flag was "--indent" or "--indent-style"
+    let mut specify_indent: bool = false;
+    let mut specify_indent_style: bool = false;
+
+    // whether the previous flag was "--format"
+    let mut specify_format: bool = false;
+
+    // whether the previous flag was "--columns"
+    let mut specify_columns: bool = false;
+
+    // whether the previous flag was "--printf"
+    let mut specify_template: bool = false;
+
+    // whether the previous flag was "--color"
+    let mut specify_color: bool = false;
+
+    // whether the previous flag was "--width"
+    let mut specify_width: bool = false;
+
+    // whether the previous flag was "--ext"
+    let mut specify_ext: bool = false;
+
+    // whether the previous flag was "--mime"
+    let mut specify_mime: bool = false;
+
+    // whether the previous flag was "--type"
+    let mut specify_type: bool = false;
+
+    // whether the previous flag was "--min-size"
+    let mut specify_min_size: bool = false;
+
+    // whether the previous flag was "--max-size"
+    let mut specify_max_size: bool = false;
+
+    // whether the previous flag was "--newer-than"
+    let mut specify_newer_than: bool = false;
+
+    // whether the previous flag was "--older-than"
+    let mut specify_older_than: bool = false;
+
+    // whether the previous flag was "--owner"
+    let mut specify_owner: bool = false;
+
+    // whether the previous flag was "--group"
+    let mut specify_group: bool = false;
+
+    // whether the previous flag was "--perm"
+    let mut specify_perm: bool = false;
+
+    // whether the previous flag was "--accessed-within"
+    let mut specify_accessed_within: bool = false;
+
+    // whether the previous flag was "--status-changed-within"
+    let mut specify_status_changed_within: bool = false;
+
+    // whether the previous flag was "--paging"
+    let mut specify_paging: bool = false;
+
+    // whether the previous flag was "--block-size"
+    let mut specify_block_size: bool = false;
+
+    // whether the previous flag was "--time-format"
+    let mut specify_time_format: bool = false;
+
+    // whether the previous flag was "--time-zone"
+    let mut specify_time_zone: bool = false;
+
+    // whether the previous flag was "--export-sqlite"
+    let mut specify_sqlite: bool = false;
+
+    // whether the previous flag was "--export-parquet"
+    let mut specify_parquet: bool = false;
+
+    // Expression to select entries with for "--find-expr"
+    let mut find_expr: Option<find::FindExpr> = None;
+
+    // whether the previous flag was "--find-expr"
+    let mut specify_find_expr: bool = false;
+
+    // Directory to write a report per subdirectory into with "--split-output"
+    let mut split_dir: String = "".to_owned();
+
+    // whether the previous flag was "--split-output"
+    let mut specify_split_dir: bool = false;
+
+    // Path of the file to write the output into with "--output"
+    let mut output_path: String = "".to_owned();
+
+    // whether the previous flag was "--output"
+    let mut specify_output: bool = false;
+
+    // Path of the image to render the treemap into with "--treemap"
+    let mut treemap_path: String = "".to_owned();
+
+    // whether the previous flag was "--treemap"
+    let mut specify_treemap: bool = false;
+
+    // positions of the arguments that are not passed on to the scans of the subdirectories with "--split-output"
+    // (the path and the split output option along with its value)
+    let mut split_skipped_args: Vec<usize> = Vec::new();
+
+    // Path of the recording of a previous scan to estimate the progress from
+    let mut progress_path: String = "".to_owned();
+
+    // whether the previous flag was "--progress-from"
+    let mut specify_progress_from: bool = false;
+
+    // the kind of change that the previous flag specified a hook for (if any)
+    let mut specify_hook: Option<watch::ChangeKind> = None;
+
+    // number of files to report with "--oldest" and "--newest"
+    let mut oldest_cnt: usize = 0;
+    let mut newest_cnt: usize = 0;
+
+    // whether the previous flag was "--oldest" or "--newest"
+    let mut specify_oldest: bool = false;
+    let mut specify_newest: bool = false;
+
+    // whether directories that hold nothing but empty directories are reported with "--empty-dirs"
+    let mut nested_empty = false;
+
+    // files that have not been accessed for this long are reported with "--cold-data"
+    let mut cold_age = time::Duration::from_secs(180 * 86400);
+
+    // whether the previous flag was "--atime-older"
+    let mut specify_cold_age: bool = false;
+
+    // largest plausible gap between the modification and status change times of a file with "--timestamp-anomalies"
+    let mut ctime_gap = time::Duration::from_secs(10 * 365 * 86400);
+
+    // whether the previous flag was "--ctime-gap"
+    let mut specify_ctime_gap: bool = false;
+
+    // maximum number of levels to recurse until if the PrgOptions::ShowRecursive option is set (a value of 0 denotes no limit)
+    let mut max_recur_level: u64 = 0;
+
+    // patterns of the entries to exclude from the scan (those of the policy followed by those given to "--exclude")
+    let mut excludes: Vec<String> = Vec::new();
+
+    // whether the previous flag was "--exclude"
+    let mut specify_exclude: bool = false;
+
+    // whether the previous flag was "--exclude-from"
+    let mut specify_exclude_from: bool = false;
+
+    // whether the previous flag was "--prune-dir"
+    let mut specify_prune_dir: bool = false;
+    // whether the previous flag was "--skip-fs"
+    let mut specify_skip_fs: bool = false;
+
+    // whether the previous flag was "--min-depth"
+    let mut specify_min_depth: bool = false;
+
+    // whether the previous flag was "--max-results"
+    let mut specify_max_results: bool = false;
+
+    // whether the previous flag was "--max-entries"
+    let mut specify_max_entries: bool = false;
+
+    // whether the previous flag was "--timeout"
+    let mut specify_timeout: bool = false;
+
+    // whether the previous flag was "--on-error"
+    let mut specify_on_error: bool = false;
+
+    // the arguments that a policy expands into are placed before the others, so that the options given on the command
+    // line override those of the policy
+    let mut args: Vec<String> = env::args().collect();
+    let mut policy: Option<policy::Policy> = None;
+    if let Some(i) = args.iter().position(|arg| arg == "--policy") {
+        let Some(policy_path) = args.get(i + 1).cloned() else {
+            print!("{}", tr!(Msg::MissingPolicy, "--policy"));
+            process::exit(-1);
+        };
+
+        match policy::load_policy(path::Path::new(&policy_path)) {
+            Ok(loaded) => {
+                args.drain(i..i + 2);
+                args.splice(1..1, loaded.args.iter().cloned());
+                excludes.extend(loaded.excludes.iter().cloned());
+                policy = Some(loaded);
+            }
+            Err(error) => {
+                error.print(&policy_path);
+                process::exit(-1);
+            }
+        }
+
+        set_option(PrgOptions::Policy);
+    }
+
+    // the language is selected before the other options are parsed, so that the messages about them are translated too
+    let lang_flag = args.iter().position(|arg| arg == "--lang");
+    if let Some(i) = lang_flag {
+        let Some(name) = args.get(i + 1).cloned() else {
+            print!("{}", tr!(Msg::MissingLang, "--lang"));
+            process::exit(-1);
+        };
+
+        match i18n::Lang::from_name(&name) {
+            Some(lang) => i18n::set_lang(lang),
+            None => {
+                print!("{}", tr!(Msg::UnknownLang, name));
+                process::exit(-1);
+            }
+        }
+    }
+
+    for (i, arg) in args.iter().cloned().enumerate().skip(1) {
+        // the language was already selected above
+        if lang_flag.is_some_and(|lang_flag| i == lang_flag || i == lang_flag + 1) {
+            continue;
+        }
+
+        // the expression starts with a dash (or is empty), so it is taken as is
+        if specify_find_expr {
+            specify_find_expr = false;
+            match find::parse(&arg) {
+                Ok(expr) => find_expr = Some(expr),
+                Err(error) => {
+                    error.print();
+                    print!("{}", tr!(Msg::Terminating));
+                    process::exit(-1);
+                }
+            }
+            continue;
+        }
+
+        // modes that must hold all of their bits start with a dash, so the mode is also taken as is
+        if specify_perm {
+            specify_perm = false;
+            if filter::set_perm(&arg).is_err() {
+                print!("{}", tr!(Msg::InvalidPerm, arg));
+                process::exit(-1);
+            }
+            continue;
+        }
+
+        // offsets west of UTC start with a dash, so the time zone is also taken as is
+        if specify_time_zone {
+            specify_time_zone = false;
+            if set_time_zone(&arg).is_err() {
+                print!("{}", tr!(Msg::UnknownTimeZone, arg));
+                process::exit(-1);
+            }
+            continue;
+        }
+
+        // paths are read from stdin if the file is "-", so the file is also taken as is
+        if specify_paths_from {
+            specify_paths_from = false;
+            match read_paths(&arg) {
+                Ok(paths) if paths.is_empty() => {
+                    print!("{}", tr!(Msg::NoPathsInFile, arg));
+                    process::exit(-1);
+                }
+                Ok(paths) => init_paths.extend(paths),
+                Err(error) => {
+                    print!("{}", tr!(Msg::PathsReadError, arg, error));
+                    process::exit(-1);
+                }
             }
-        };
+            continue;
+        }
 
-        #[cfg(not(target_family = "unix"))]
-        let special_file_type = SpecialFileType::NA;
+        let arg_len = arg.len();
 
-        if metadata.is_symlink() {
-            cur_entry_cnts.inc_symlink_cnt(1);
+        if arg_len == 0 {
+            print!("{}", tr!(Msg::EmptyOption));
+            continue;
+        }
 
-            // skip if the show symlinks option is not set
-            if !get_option(PrgOptions::ShowSymlinks) {
+        if !arg.starts_with('-') {
+            if specify_recur_depth {
+                specify_recur_depth = false;
+                if let Ok(depth) = arg.parse::<u64>() {
+                    max_recur_level = depth;
+                    if depth == 0 {
+                        print!("{}", tr!(Msg::RecursionDepthZero));
+                        print!("{}", tr!(Msg::IgnoringRecursive));
+                        clear_option(PrgOptions::ShowRecursive);
+                    }
+                    continue;
+                } else {
+                    print!("{}", tr!(Msg::NotAnInteger, arg));
+                    print!("{}", tr!(Msg::IgnoringRecursive));
+                    clear_option(PrgOptions::ShowRecursive);
+
+                    continue;
+                }
+            } else if specify_search_path {
+                search_patterns.push(arg.clone());
+                continue;
+            } else if specify_patterns_from {
+                specify_patterns_from = false;
+                match read_patterns(path::Path::new(&arg)) {
+                    Ok(patterns) if patterns.is_empty() => {
+                        print!("{}", tr!(Msg::NoPatternsInFile, arg));
+                        process::exit(-1);
+                    }
+                    Ok(patterns) => search_patterns.extend(patterns),
+                    Err(error) => {
+                        print!("{}", tr!(Msg::PatternsReadError, arg, error));
+                        process::exit(-1);
+                    }
+                }
+                continue;
+            } else if specify_dest_path {
+                specify_dest_path = false;
+                dest_path = arg.clone();
+                continue;
+            } else if specify_hash_cache {
+                specify_hash_cache = false;
+                if let Err(error) = hash::load_hash_cache(path::Path::new(&arg)) {
+                    print!("{}", tr!(Msg::HashCacheReadError, arg, error));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_record {
+                specify_record = false;
+                record_path = arg.clone();
+                continue;
+            } else if specify_replay {
+                specify_replay = false;
+                replay_path = arg.clone();
+                continue;
+            } else if specify_indent {
+                specify_indent = false;
+                match arg.parse::<usize>() {
+                    Ok(width) => indent_col_width = width,
+                    Err(_) => {
+                        print!("{}", tr!(Msg::NotAnInteger, arg));
+                        print!("{}", tr!(Msg::IgnoringOption, "--indent"));
+                    }
+                }
+                continue;
+            } else if specify_indent_style {
+                specify_indent_style = false;
+                match IndentStyle::from_name(&arg) {
+                    Some(style) => indent_style = style,
+                    None => {
+                        print!("{}", tr!(Msg::UnknownIndentStyle, arg));
+                        process::exit(-1);
+                    }
+                }
+                continue;
+            } else if specify_format {
+                specify_format = false;
+                match export::Format::from_name(&arg) {
+                    Some(format) => export::set_format(format),
+                    None => {
+                        print!("{}", tr!(Msg::UnknownFormat, arg));
+                        process::exit(-1);
+                    }
+                }
+                continue;
+            } else if specify_columns {
+                specify_columns = false;
+                if let Err(column) = columns::set_columns(&arg) {
+                    print!("{}", tr!(Msg::UnknownColumn, column));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_template {
+                specify_template = false;
+                if let Err(placeholder) = export::set_template(&arg) {
+                    print!("{}", tr!(Msg::UnknownPlaceholder, placeholder));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_color {
+                specify_color = false;
+                if color::set_mode(&arg).is_err() {
+                    print!("{}", tr!(Msg::UnknownColorMode, arg));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_paging {
+                specify_paging = false;
+                if pager::set_mode(&arg).is_err() {
+                    print!("{}", tr!(Msg::UnknownPagingMode, arg));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_width {
+                specify_width = false;
+                if columns::set_width(&arg).is_err() {
+                    print!("{}", tr!(Msg::InvalidWidth, arg));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_ext {
+                specify_ext = false;
+                if filter::set_extensions(&arg).is_err() {
+                    print!("{}", tr!(Msg::InvalidExtensions, arg));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_mime {
+                specify_mime = false;
+                if filter::set_mime_types(&arg).is_err() {
+                    print!("{}", tr!(Msg::InvalidMimeTypes, arg));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_type {
+                specify_type = false;
+                if let Err(name) = filter::set_types(&arg) {
+                    print!("{}", tr!(Msg::UnknownType, name));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_min_size {
+                specify_min_size = false;
+                if filter::set_min_size(&arg).is_err() {
+                    print!("{}", tr!(Msg::InvalidSize, arg));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_max_size {
+                specify_max_size = false;
+                if filter::set_max_size(&arg).is_err() {
+                    print!("{}", tr!(Msg::InvalidSize, arg));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_newer_than {
+                specify_newer_than = false;
+                if filter::set_newer_than(&arg).is_err() {
+                    print!("{}", tr!(Msg::InvalidTimeBound, arg));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_older_than {
+                specify_older_than = false;
+                if filter::set_older_than(&arg).is_err() {
+                    print!("{}", tr!(Msg::InvalidTimeBound, arg));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_exclude {
+                specify_exclude = false;
+                excludes.push(arg.clone());
+                continue;
+            } else if specify_prune_dir {
+                specify_prune_dir = false;
+                excludes.push(format!("{}/", arg.trim_end_matches('/')));
+                continue;
+            } else if specify_skip_fs {
+                specify_skip_fs = false;
+                if pseudofs::set_types(&arg).is_err() {
+                    print!("{}", tr!(Msg::InvalidFsTypes, arg));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_exclude_from {
+                specify_exclude_from = false;
+                match read_patterns(path::Path::new(&arg)) {
+                    // lines starting with ';' are comments as well (like in the exclude files of rsync)
+                    Ok(patterns) => excludes.extend(
+                        patterns
+                            .into_iter()
+                            .filter(|pattern| !pattern.starts_with(';')),
+                    ),
+                    Err(error) => {
+                        print!("{}", tr!(Msg::ExcludesReadError, arg, error));
+                        process::exit(-1);
+                    }
+                }
+                continue;
+            } else if specify_owner {
+                specify_owner = false;
+                if filter::set_owner(&arg).is_err() {
+                    print!("{}", tr!(Msg::UnknownUser, arg));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_group {
+                specify_group = false;
+                if filter::set_group(&arg).is_err() {
+                    print!("{}", tr!(Msg::UnknownGroup, arg));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_accessed_within {
+                specify_accessed_within = false;
+                if filter::set_accessed_within(&arg).is_err() {
+                    print!("{}", tr!(Msg::InvalidTimeBound, arg));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_status_changed_within {
+                specify_status_changed_within = false;
+                if filter::set_status_changed_within(&arg).is_err() {
+                    print!("{}", tr!(Msg::InvalidTimeBound, arg));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_block_size {
+                specify_block_size = false;
+                match parse_size(&arg) {
+                    Some(block_size) if block_size > 0 => {
+                        let _ = BLOCK_SIZE.set(block_size);
+                    }
+                    _ => {
+                        print!("{}", tr!(Msg::InvalidBlockSize, arg));
+                        process::exit(-1);
+                    }
+                }
+                continue;
+            } else if specify_time_format {
+                specify_time_format = false;
+                if set_time_format(&arg).is_err() {
+                    print!("{}", tr!(Msg::InvalidTimeFormat, arg));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_sqlite {
+                specify_sqlite = false;
+                sqlite::set_db_path(&arg);
+                continue;
+            } else if specify_parquet {
+                specify_parquet = false;
+                #[cfg(feature = "export-parquet")]
+                parquet::set_parquet_path(&arg);
+                continue;
+            } else if specify_split_dir {
+                specify_split_dir = false;
+                split_dir = arg.clone();
+                split_skipped_args.push(i);
+                continue;
+            } else if specify_output {
+                specify_output = false;
+                output_path = arg.clone();
+                continue;
+            } else if specify_treemap {
+                specify_treemap = false;
+                treemap_path = arg.clone();
+                continue;
+            } else if specify_progress_from {
+                specify_progress_from = false;
+                progress_path = arg.clone();
+                continue;
+            } else if let Some(kind) = specify_hook.take() {
+                match kind {
+                    watch::ChangeKind::Created => watch_hooks.on_create = Some(arg.clone()),
+                    watch::ChangeKind::Modified => watch_hooks.on_modify = Some(arg.clone()),
+                    watch::ChangeKind::Deleted => watch_hooks.on_delete = Some(arg.clone()),
+                }
+                continue;
+            } else if specify_min_depth {
+                specify_min_depth = false;
+                if let Ok(depth) = arg.parse::<usize>() {
+                    let _ = MIN_DEPTH.set(depth);
+                } else {
+                    print!("{}", tr!(Msg::NotAnInteger, arg));
+                    print!("{}", tr!(Msg::IgnoringOption, "--min-depth"));
+                }
+                continue;
+            } else if specify_max_results {
+                specify_max_results = false;
+                match arg.parse::<u64>() {
+                    Ok(cnt) if cnt > 0 => {
+                        let _ = MAX_RESULTS.set(cnt);
+                    }
+                    _ => {
+                        print!("{}", tr!(Msg::NotAPositiveInteger, arg));
+                        print!("{}", tr!(Msg::IgnoringOption, "--max-results"));
+                    }
+                }
+                continue;
+            } else if specify_max_entries {
+                specify_max_entries = false;
+                match arg.parse::<u64>() {
+                    Ok(cnt) if cnt > 0 => limit::set_max_entries(cnt),
+                    _ => {
+                        print!("{}", tr!(Msg::NotAPositiveInteger, arg));
+                        print!("{}", tr!(Msg::IgnoringOption, "--max-entries"));
+                    }
+                }
+                continue;
+            } else if specify_timeout {
+                specify_timeout = false;
+                let Some(timeout) = parse_timeout(&arg) else {
+                    print!("{}", tr!(Msg::InvalidTimeout, arg));
+                    process::exit(-1);
+                };
+                limit::set_timeout(timeout);
+                continue;
+            } else if specify_on_error {
+                specify_on_error = false;
+                let Some(on_error) = errors::OnError::from_name(&arg) else {
+                    print!("{}", tr!(Msg::UnknownOnError, arg));
+                    process::exit(-1);
+                };
+                errors::set_on_error(on_error);
+                continue;
+            } else if specify_debounce {
+                specify_debounce = false;
+                if let Ok(millis) = arg.parse::<u64>() {
+                    debounce = time::Duration::from_millis(millis);
+                } else {
+                    print!("{}", tr!(Msg::NotAnInteger, arg));
+                    print!("{}", tr!(Msg::IgnoringDebounce));
+                }
+                continue;
+            } else if specify_oldest || specify_newest {
+                let flag = if specify_oldest {
+                    "--oldest"
+                } else {
+                    "--newest"
+                };
+                match arg.parse::<usize>() {
+                    Ok(cnt) if cnt > 0 => {
+                        if specify_oldest {
+                            oldest_cnt = cnt;
+                            set_option(PrgOptions::Oldest);
+                        } else {
+                            newest_cnt = cnt;
+                            set_option(PrgOptions::Newest);
+                        }
+                    }
+                    _ => {
+                        print!("{}", tr!(Msg::NotAPositiveInteger, arg));
+                        print!("{}", tr!(Msg::IgnoringOption, flag));
+                    }
+                }
+                specify_oldest = false;
+                specify_newest = false;
+                continue;
+            } else if specify_cold_age || specify_ctime_gap {
+                let Some(age) = parse_age(&arg) else {
+                    print!("{}", tr!(Msg::InvalidAge, arg));
+                    process::exit(-1);
+                };
+                if specify_cold_age {
+                    cold_age = age;
+                } else {
+                    ctime_gap = age;
+                }
+                specify_cold_age = false;
+                specify_ctime_gap = false;
+                continue;
+            } else if specify_walk_threads || specify_work_threads {
+                let flag = if specify_walk_threads {
+                    "--walk-threads"
+                } else {
+                    "--work-threads"
+                };
+                match arg.parse::<usize>() {
+                    Ok(threads) if specify_walk_threads => walk_threads = threads,
+                    Ok(threads) => work_threads = threads,
+                    Err(_) => {
+                        print!("{}", tr!(Msg::NotAnInteger, arg));
+                        print!("{}", tr!(Msg::IgnoringOption, flag));
+                    }
+                }
+                specify_walk_threads = false;
+                specify_work_threads = false;
+                continue;
+            } else if specify_checkpoint {
+                specify_checkpoint = false;
+                if let Err(error) = hash::load_checkpoint(path::Path::new(&arg)) {
+                    print!("{}", tr!(Msg::CheckpointReadError, arg, error));
+                    process::exit(-1);
+                }
+                continue;
+            } else if specify_compress_algo {
+                specify_compress_algo = false;
+                match compress::CompressAlgo::from_name(&arg) {
+                    Some(algo) => compress::set_algo(algo),
+                    None => {
+                        print!("{}", tr!(Msg::UnknownCompressAlgo, arg));
+                        process::exit(-1);
+                    }
+                }
+                continue;
+            } else if specify_hash_algo {
+                specify_hash_algo = false;
+                match hash::HashAlgo::from_name(&arg) {
+                    Some(algo) if algo.is_available() => hash::set_hash_algo(algo),
+                    Some(algo) => {
+                        print!("{}", tr!(Msg::HashAlgoNotCompiled, algo.name()));
+                        process::exit(-1);
+                    }
+                    None => {
+                        print!("{}", tr!(Msg::UnknownHashAlgo, arg));
+                        process::exit(-1);
+                    }
+                }
+                continue;
+            } else {
+                split_skipped_args.push(i);
+                let mut init_path = arg.clone();
+                if init_path.len() > MAX_PATH_LEN {
+                    init_path = init_path[..MAX_PATH_LEN].to_owned();
+                }
+                init_paths.push(init_path);
                 continue;
             }
+        }
+        specify_recur_depth = false;
+        specify_search_path = false;
+        specify_patterns_from = false;
+        specify_paths_from = false;
+        specify_dest_path = false;
+        specify_hash_algo = false;
+        specify_hash_cache = false;
+        specify_checkpoint = false;
+        specify_walk_threads = false;
+        specify_work_threads = false;
+        specify_oldest = false;
+        specify_newest = false;
+        specify_cold_age = false;
+        specify_ctime_gap = false;
+        specify_debounce = false;
+        specify_record = false;
+        specify_replay = false;
+        specify_progress_from = false;
+        specify_split_dir = false;
+        specify_output = false;
+        specify_treemap = false;
+        specify_find_expr = false;
+        specify_indent = false;
+        specify_indent_style = false;
+        specify_format = false;
+        specify_columns = false;
+        specify_template = false;
+        specify_color = false;
+        specify_width = false;
+        specify_paging = false;
+        specify_ext = false;
+        specify_mime = false;
+        specify_block_size = false;
+        specify_type = false;
+        specify_min_size = false;
+        specify_max_size = false;
+        specify_newer_than = false;
+        specify_older_than = false;
+        specify_accessed_within = false;
+        specify_status_changed_within = false;
+        specify_exclude = false;
+        specify_exclude_from = false;
+        specify_prune_dir = false;
+        specify_skip_fs = false;
+        specify_min_depth = false;
+        specify_max_results = false;
+        specify_max_entries = false;
+        specify_timeout = false;
+        specify_on_error = false;
+        specify_owner = false;
+        specify_group = false;
+        specify_perm = false;
+        specify_time_format = false;
+        specify_time_zone = false;
+        specify_sqlite = false;
+        specify_parquet = false;
+        specify_hook = None;
 
-            // depending on whether the absolute path (without indentation) needs to be printed,
-            // try to print the current entry
-            let failed = if get_option(PrgOptions::ShowNotree) {
-                show_symlink_noindent(&metadata, &path_os, path_os.is_dir())
+        if arg == "-h" || arg == "--help" {
+            set_option(PrgOptions::Help);
+        } else if arg == "--help-full" {
+            set_option(PrgOptions::HelpFull);
+        } else if arg == "--man" {
+            set_option(PrgOptions::Man);
+        } else if arg == "--capabilities" {
+            set_option(PrgOptions::Capabilities);
+        } else if arg == "-e" || arg == "--show-err" {
+            set_option(PrgOptions::ShowErrors);
+        } else if arg == "-r" || arg == "--recursive" {
+            set_option(PrgOptions::ShowRecursive);
+            specify_recur_depth = true;
+        } else if arg == "-L" || arg == "--follow" {
+            set_option(PrgOptions::Follow);
+        } else if arg == "--min-depth" {
+            specify_min_depth = true;
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingDepth, arg));
+                process::exit(-1);
+            }
+        } else if arg == "-f" || arg == "--files" {
+            set_option(PrgOptions::ShowFiles);
+        } else if arg == "-l" || arg == "--symlinks" {
+            set_option(PrgOptions::ShowSymlinks);
+        } else if arg == "-s" || arg == "--special" {
+            set_option(PrgOptions::ShowSpecial);
+        } else if arg == "-d" || arg == "--dir-size" {
+            set_option(PrgOptions::ShowDirSize);
+        } else if arg == "-b" || arg == "--escape" {
+            set_option(PrgOptions::Escape);
+        } else if arg == "-Q" || arg == "--quote" {
+            set_option(PrgOptions::Quote);
+        } else if arg == "--names-only"
+            || arg == "--sizes-only"
+            || arg == "--ls-format"
+            || arg == "--format"
+            || arg == "--export-sqlite"
+            || arg == "--export-parquet"
+            || arg == "--long"
+            || arg == "--printf"
+            || arg == "-0"
+            || arg == "--print0"
+        {
+            if get_option(PrgOptions::NamesOnly)
+                || get_option(PrgOptions::SizesOnly)
+                || get_option(PrgOptions::LsFormat)
+                || get_option(PrgOptions::Format)
+                || get_option(PrgOptions::ExportSqlite)
+                || get_option(PrgOptions::ExportParquet)
+                || get_option(PrgOptions::Long)
+                || get_option(PrgOptions::Printf)
+                || get_option(PrgOptions::Print0)
+            {
+                print!("{}", tr!(Msg::OnePlainMode));
+                print!("{}", tr!(Msg::Terminating));
+                process::exit(-1);
+            }
+
+            if arg == "--names-only" {
+                set_option(PrgOptions::NamesOnly);
+            } else if arg == "--sizes-only" {
+                set_option(PrgOptions::SizesOnly);
+            } else if arg == "--ls-format" {
+                set_option(PrgOptions::LsFormat);
+            } else if arg == "--long" {
+                set_option(PrgOptions::Long);
+            } else if arg == "-0" || arg == "--print0" {
+                set_option(PrgOptions::Print0);
+            } else if arg == "--format" {
+                set_option(PrgOptions::Format);
+                specify_format = true;
+
+                if args.len() <= i + 1 {
+                    print!("{}", tr!(Msg::MissingFormat, arg));
+                    process::exit(-1);
+                }
+            } else if arg == "--printf" {
+                set_option(PrgOptions::Printf);
+                specify_template = true;
+
+                if args.len() <= i + 1 {
+                    print!("{}", tr!(Msg::MissingTemplate, arg));
+                    process::exit(-1);
+                }
+            } else if arg == "--export-sqlite" {
+                set_option(PrgOptions::ExportSqlite);
+                specify_sqlite = true;
+
+                if args.len() <= i + 1 {
+                    print!("{}", tr!(Msg::MissingDbPath, arg));
+                    process::exit(-1);
+                }
             } else {
-                show_symlink(indent_width, &metadata, &path_os, path_os.is_dir())
-            };
+                if !cfg!(feature = "export-parquet") {
+                    print!("{}", tr!(Msg::ParquetNotCompiled));
+                    process::exit(-1);
+                }
 
-            // if the entry could not be printed, then remove its contribution from the counts
-            if failed {
-                cur_entry_cnts.dec_symlink_cnt(1);
+                set_option(PrgOptions::ExportParquet);
+                specify_parquet = true;
+
+                if args.len() <= i + 1 {
+                    print!("{}", tr!(Msg::MissingParquetPath, arg));
+                    process::exit(-1);
+                }
             }
-        } else if metadata.is_file() && special_file_type == SpecialFileType::NA {
-            cur_entry_cnts.inc_file_cnt(1);
+        } else if arg == "--dirs-only" {
+            set_option(PrgOptions::DirsOnly);
+        } else if arg == "--name-collisions" {
+            set_option(PrgOptions::NameCollisions);
+        } else if arg == "--hardlinks" {
+            set_option(PrgOptions::Hardlinks);
+        } else if arg == "--ordered" {
+            set_option(PrgOptions::Ordered);
+        } else if arg == "--same-size" {
+            set_option(PrgOptions::SameSize);
+        } else if arg == "--oldest" || arg == "--newest" {
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingFileCount, arg));
+                process::exit(-1);
+            }
+            specify_oldest = arg == "--oldest";
+            specify_newest = arg == "--newest";
+        } else if arg == "--cold-data" {
+            set_option(PrgOptions::ColdData);
+        } else if arg == "--atime-older" || arg == "--ctime-gap" {
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingAge, arg));
+                process::exit(-1);
+            }
+            specify_cold_age = arg == "--atime-older";
+            specify_ctime_gap = arg == "--ctime-gap";
+        } else if arg == "--timestamp-anomalies" {
+            set_option(PrgOptions::TimestampAnomalies);
+        } else if arg == "--empty-dirs" {
+            set_option(PrgOptions::EmptyDirs);
+        } else if arg == "--nested-empty" {
+            nested_empty = true;
+        } else if arg == "--fingerprint" {
+            set_option(PrgOptions::Fingerprint);
+        } else if arg == "--treemap" {
+            set_option(PrgOptions::Treemap);
+            specify_treemap = true;
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingTreemapPath, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--transfer-estimate" {
+            set_option(PrgOptions::TransferEstimate);
+            specify_dest_path = true;
 
-            // skip if the show files option is not set
-            // since the number and size of files are aggregated at the end,
-            // add it's size to the total file size
-            if !get_option(PrgOptions::ShowFiles) {
-                total_file_size += metadata.len();
-                continue;
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingDestPath, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--block-estimate" {
+            set_option(PrgOptions::BlockEstimate);
+        } else if arg == "--hash" {
+            set_option(PrgOptions::ShowHash);
+        } else if arg == "--on-error" {
+            specify_on_error = true;
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingOnError, arg));
+                process::exit(-1);
             }
+        } else if arg == "--hash-algo" {
+            set_option(PrgOptions::ShowHash);
+            specify_hash_algo = true;
 
-            // depending on whether the absolute path (without indentation) needs to be printed,
-            // try to print the current entry
-            let failed = if get_option(PrgOptions::ShowNotree) {
-                show_file_noindent(&metadata, &path_os, &metadata.len())
-            } else {
-                show_file(indent_width, &metadata, &path_os)
-            };
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingHashAlgo, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--hash-cache" {
+            specify_hash_cache = true;
 
-            // if the entry could not be counted, then remove its contribution from the counts
-            if failed {
-                cur_entry_cnts.dec_file_cnt(1);
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingHashCache, arg));
+                process::exit(-1);
             }
-        } else if metadata.is_dir() {
-            cur_entry_cnts.inc_dir_cnt(1);
+        } else if arg == "--checkpoint" {
+            specify_checkpoint = true;
 
-            // depending on whether the absolute path (without indentation) needs to be printed,
-            // try to print the current entry
-            let failed = if get_option(PrgOptions::ShowNotree) {
-                show_dir_noindent(&metadata, &path_os)
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingCheckpoint, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--walk-threads" || arg == "--work-threads" {
+            if arg == "--walk-threads" {
+                specify_walk_threads = true;
             } else {
-                show_dir(indent_width, &metadata, &path_os)
-            };
+                specify_work_threads = true;
+            }
 
-            // if the entry could not be printed, then remove its contribution from the counts
-            // otherwise, recursively print its contents if the show recursive option is set
-            if failed {
-                cur_entry_cnts.dec_dir_cnt(1);
-            } else {
-                if get_option(PrgOptions::ShowRecursive)
-                    && (*p_max_level == 0u64 || p_level < (*p_max_level as usize))
-                {
-                    if let Some(error) = scan_path(
-                        p_entry_cnts_init,
-                        p_entry_cnts_full,
-                        p_max_level,
-                        1 + p_level,
-                        &path_os,
-                    ) {
-                        if get_option(PrgOptions::ShowErrors) {
-                            eprint!(
-                                "Error while iterating over \"{}\"\n{}\n",
-                                path_os.to_string_lossy(),
-                                error
-                            );
-                        }
-                    }
-                }
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingThreadCount, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--no-cache-pollution" {
+            set_option(PrgOptions::NoCachePollution);
+        } else if arg == "--measure-latency" {
+            set_option(PrgOptions::MeasureLatency);
+        } else if arg == "--watch" {
+            set_option(PrgOptions::Watch);
+        } else if arg == "--debounce" {
+            specify_debounce = true;
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingDebounce, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--on-create" || arg == "--on-modify" || arg == "--on-delete" {
+            specify_hook = Some(match arg.as_str() {
+                "--on-create" => watch::ChangeKind::Created,
+                "--on-modify" => watch::ChangeKind::Modified,
+                _ => watch::ChangeKind::Deleted,
+            });
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingCommand, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--record" || arg == "--snapshot" {
+            set_option(PrgOptions::Record);
+            if arg == "--snapshot" {
+                set_option(PrgOptions::Snapshot);
+            }
+            specify_record = true;
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingRecording, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--replay" {
+            set_option(PrgOptions::Replay);
+            specify_replay = true;
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingRecording, arg));
+                process::exit(-1);
             }
-        } else {
-            cur_entry_cnts.inc_special_cnt(1);
+        } else if arg == "--find-expr" {
+            set_option(PrgOptions::FindExpr);
+            specify_find_expr = true;
 
-            if !get_option(PrgOptions::ShowSpecial) {
-                continue;
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingFindExpr, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--policy" {
+            // the first policy was already expanded above
+            print!("{}", tr!(Msg::OnePolicy));
+            print!("{}", tr!(Msg::Terminating));
+            process::exit(-1);
+        } else if arg == "--indent" {
+            specify_indent = true;
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingIndentWidth, arg));
+                process::exit(-1);
             }
+        } else if arg == "--indent-style" {
+            specify_indent_style = true;
 
-            // depending on whether the absolute path (without indentation) needs to be printed,
-            // try to print the current entry
-            let failed = if get_option(PrgOptions::ShowNotree) {
-                show_special_noindent(&metadata, &path_os, &special_file_type)
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingIndentStyle, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--output" {
+            set_option(PrgOptions::Output);
+            specify_output = true;
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingOutputPath, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--append" {
+            set_option(PrgOptions::Append);
+        } else if arg == "--compress" {
+            set_option(PrgOptions::Compress);
+        } else if arg == "--compress-algo" {
+            set_option(PrgOptions::Compress);
+            specify_compress_algo = true;
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingCompressAlgo, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--split-output" {
+            set_option(PrgOptions::SplitOutput);
+            specify_split_dir = true;
+            split_skipped_args.push(i);
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingSplitDir, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--progress" {
+            set_option(PrgOptions::Progress);
+        } else if arg == "--progress-from" {
+            set_option(PrgOptions::Progress);
+            specify_progress_from = true;
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingRecording, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--age-heatmap" || arg == "--size-heatmap" {
+            if get_option(PrgOptions::AgeHeatmap) || get_option(PrgOptions::SizeHeatmap) {
+                print!("{}", tr!(Msg::OneHeatmap));
+                print!("{}", tr!(Msg::Terminating));
+                process::exit(-1);
+            }
+
+            if arg == "--age-heatmap" {
+                set_option(PrgOptions::AgeHeatmap);
             } else {
-                show_special(indent_width, &metadata, &path_os, &special_file_type)
-            };
+                set_option(PrgOptions::SizeHeatmap);
+            }
+        } else if arg == "--no-tree" {
+            set_option(PrgOptions::ShowNotree);
+        } else if arg == "--tree" {
+            indent_style = IndentStyle::Tree;
+        } else if arg == "-S" || arg == "--search" {
+            if get_option(PrgOptions::SearchNoext)
+                || get_option(PrgOptions::SearchContains)
+                || get_option(PrgOptions::SearchGlob)
+                || get_option(PrgOptions::SearchFuzzy)
+            {
+                print!("{}", tr!(Msg::OneSearchMode));
+                print!("{}", tr!(Msg::Terminating));
+                process::exit(-1);
+            }
 
-            // if the entry could not be printed, remove its contribution from the counts
-            if failed {
-                cur_entry_cnts.dec_special_cnt(1);
+            specify_search_path = true;
+            set_option(PrgOptions::SearchExact);
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingSearchPattern, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--search-noext" {
+            if get_option(PrgOptions::SearchExact)
+                || get_option(PrgOptions::SearchContains)
+                || get_option(PrgOptions::SearchGlob)
+                || get_option(PrgOptions::SearchFuzzy)
+            {
+                print!("{}", tr!(Msg::OneSearchMode));
+                print!("{}", tr!(Msg::Terminating));
+                process::exit(-1);
             }
-        }
-    }
 
-    // for the current directory, the summary needs to be printed for all the entries that were not supposed to be shown
-    // for example, if the show files option is not set, the number of files along with their aggregated size needs
-    // to be printed as a logical entry within the current directory
-    // this is only to be done if the show absolute option is not set
-    if !get_option(PrgOptions::ShowNotree) {
-        // the total size of the files only needs to be printd if the show size option is set for directories
-        // this is because the aggregated files are shown as a logical directory entry (as if the files were within another directory)
-        // if the option was set, print the formatted size, otherwise print and empty string
-        // for special file and symlink aggregate entries, an empty string needs to be printed if the show size option
-        // is not set, and a - character need to be printed if the option is set
-        let (file_sz, sz) = if get_option(PrgOptions::ShowDirSize) {
-            (int_to_formatted_slice(total_file_size), '-')
-        } else {
-            ("", ' ')
-        };
+            specify_search_path = true;
+            set_option(PrgOptions::SearchNoext);
 
-        // if the show files option is not set and there are special files, group them together and show the count
-        if !get_option(PrgOptions::ShowFiles) && cur_entry_cnts.get_file_cnt() != 0 {
-            #[cfg(target_family = "unix")]
-            if get_option(PrgOptions::ShowPermissions) {
-                print!("            ");
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingSearchPattern, arg));
+                process::exit(-1);
             }
-            #[cfg(target_family = "unix")]
-            if get_option(PrgOptions::ShowLasttime) {
-                print!("{:FMT_TIME_WIDTH$}", ' ');
+        } else if arg == "--contains" {
+            if get_option(PrgOptions::SearchNoext)
+                || get_option(PrgOptions::SearchExact)
+                || get_option(PrgOptions::SearchGlob)
+                || get_option(PrgOptions::SearchFuzzy)
+            {
+                print!("{}", tr!(Msg::OneSearchMode));
+                print!("{}", tr!(Msg::Terminating));
+                process::exit(-1);
             }
-            print!(
-                "{:>20}    {:indent_width$}<{} files>\n",
-                file_sz,
-                "",
-                int_to_formatted_slice(cur_entry_cnts.get_file_cnt())
-            );
-        }
 
-        // if the show symlinks option is not set and there are special files, group them together and show the count
-        if !get_option(PrgOptions::ShowSymlinks) && cur_entry_cnts.get_symlink_cnt() != 0 {
-            #[cfg(target_family = "unix")]
-            if get_option(PrgOptions::ShowPermissions) {
-                print!("            ");
+            specify_search_path = true;
+            set_option(PrgOptions::SearchContains);
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingSearchPattern, arg));
+                process::exit(-1);
             }
-            #[cfg(target_family = "unix")]
-            if get_option(PrgOptions::ShowLasttime) {
-                print!("{:FMT_TIME_WIDTH$}", ' ');
+        } else if arg == "--max-entries" {
+            specify_max_entries = true;
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingEntryCount, arg));
+                process::exit(-1);
             }
-            print!(
-                "{:>20}    {:indent_width$}<{} symlinks>\n",
-                sz,
-                "",
-                int_to_formatted_slice(cur_entry_cnts.get_symlink_cnt())
-            );
-        }
+        } else if arg == "--timeout" {
+            specify_timeout = true;
 
-        // if the show special option is not set and there are special files, group them together and show the count
-        if !get_option(PrgOptions::ShowSpecial) && cur_entry_cnts.get_special_cnt() != 0 {
-            #[cfg(target_family = "unix")]
-            if get_option(PrgOptions::ShowPermissions) {
-                print!("            ");
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingTimeout, arg));
+                process::exit(-1);
             }
-            print!(
-                "{:>20}    {:indent_width$}<{} special entries>\n",
-                sz,
-                "",
-                int_to_formatted_slice(cur_entry_cnts.get_special_cnt())
-            );
-        }
-    }
+        } else if arg == "--paths-from" {
+            specify_paths_from = true;
 
-    // update the final and initial summaries with the current directory's traversal summary
-    if p_level == 0 {
-        p_entry_cnts_init.inc_symlink_cnt(cur_entry_cnts.get_symlink_cnt());
-        p_entry_cnts_init.inc_file_cnt(cur_entry_cnts.get_file_cnt());
-        p_entry_cnts_init.inc_dir_cnt(cur_entry_cnts.get_dir_cnt());
-        p_entry_cnts_init.inc_special_cnt(cur_entry_cnts.get_special_cnt());
-    }
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingPathsFile, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--patterns-from" {
+            specify_patterns_from = true;
 
-    p_entry_cnts_full.inc_symlink_cnt(cur_entry_cnts.get_symlink_cnt());
-    p_entry_cnts_full.inc_file_cnt(cur_entry_cnts.get_file_cnt());
-    p_entry_cnts_full.inc_dir_cnt(cur_entry_cnts.get_dir_cnt());
-    p_entry_cnts_full.inc_special_cnt(cur_entry_cnts.get_special_cnt());
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingPatternsFile, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--max-results" {
+            specify_max_results = true;
 
-    return None;
-}
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingResultCount, arg));
+                process::exit(-1);
+            }
+        } else if arg == "-i" || arg == "--ignore-case" {
+            set_option(PrgOptions::IgnoreCase);
+        } else if arg == "--invert-match" {
+            set_option(PrgOptions::InvertMatch);
+        } else if arg == "--full-path" {
+            set_option(PrgOptions::FullPath);
+        } else if arg == "--search-tree" {
+            set_option(PrgOptions::SearchTree);
+        } else if arg == "--bfs" {
+            set_option(PrgOptions::Bfs);
+        } else if arg == "--counts-by-dir" {
+            set_option(PrgOptions::CountsByDir);
+        } else if arg == "--empty-files" {
+            filter::set_empty_files();
+        } else if arg == "--broken-symlinks" {
+            filter::set_broken_symlinks();
+        } else if arg == "--only-text" {
+            filter::set_content(content::Kind::Text);
+        } else if arg == "--only-binary" {
+            filter::set_content(content::Kind::Binary);
+        } else if arg == "-a" || arg == "--all" {
+            set_option(PrgOptions::ShowHidden);
+            clear_option(PrgOptions::NoHidden);
+        } else if arg == "--no-hidden" {
+            set_option(PrgOptions::NoHidden);
+            clear_option(PrgOptions::ShowHidden);
+        } else if arg == "--respect-gitignore" {
+            set_option(PrgOptions::RespectGitignore);
+        } else if arg == "--no-ignore-dot" {
+            set_option(PrgOptions::NoIgnoreDot);
+        } else if arg == "--skip-fs" {
+            specify_skip_fs = true;
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingFsTypes, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--no-skip-fs" {
+            pseudofs::skip_none();
+        } else if arg == "--glob" {
+            if get_option(PrgOptions::SearchExact)
+                || get_option(PrgOptions::SearchNoext)
+                || get_option(PrgOptions::SearchContains)
+                || get_option(PrgOptions::SearchFuzzy)
+            {
+                print!("{}", tr!(Msg::OneSearchMode));
+                print!("{}", tr!(Msg::Terminating));
+                process::exit(-1);
+            }
 
-fn search_path(
-    p_entry_cnts_match: &mut EntryCounter,
-    p_entry_cnts_full: &mut EntryCounter,
-    p_max_level: &u64,
-    p_level: usize,
-    p_current_path: &path::Path,
-    p_search_path: &str,
-) -> Option<std::io::Error> {
-    // instantiate structure to hold the number of entries of each type in the current directory (not recursive)
-    let mut cur_entry_cnts = EntryCounter::new();
+            specify_search_path = true;
+            set_option(PrgOptions::SearchGlob);
 
-    // try to read the entries of the current directory
-    // if the entries could not be iterated over (for example, due to insufficient permissions or the current entry being a file)
-    // then return from the function and report this to the caller
-    let entries = match fs::read_dir(&p_current_path) {
-        Ok(values) => values,
-        Err(error) => {
-            return Some(error);
-        }
-    };
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingSearchPattern, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--fuzzy" {
+            if get_option(PrgOptions::SearchExact)
+                || get_option(PrgOptions::SearchNoext)
+                || get_option(PrgOptions::SearchContains)
+                || get_option(PrgOptions::SearchGlob)
+            {
+                print!("{}", tr!(Msg::OneSearchMode));
+                print!("{}", tr!(Msg::Terminating));
+                process::exit(-1);
+            }
 
-    for entry in entries {
-        // if the current entry could not be found for some reason, then silently skip it
-        let Ok(entry) = entry else {
-            continue;
-        };
+            specify_search_path = true;
+            set_option(PrgOptions::SearchFuzzy);
 
-        // get the metadata about this entry (will be used to query its type and in the case of regular files, its size)
-        // if the metadata could not be queries, silently skip this entry
-        let Ok(metadata) = entry.metadata() else {
-            continue;
-        };
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingSearchPattern, arg));
+                process::exit(-1);
+            }
+        } else if arg == "-p" || arg == "--permissions" {
+            #[cfg(target_family = "unix")]
+            set_option(PrgOptions::ShowPermissions);
+        } else if arg == "-t" || arg == "--modification-time" {
+            #[cfg(target_family = "unix")]
+            set_option(PrgOptions::ShowLasttime);
+        } else if arg == "--color" {
+            specify_color = true;
 
-        // get the path to the current entry
-        let path_os = entry.path();
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingColorMode, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--paging" {
+            specify_paging = true;
 
-        // check for special file (on unix style operating systems, get the specific type as well)
-        #[cfg(target_family = "unix")]
-        let special_file_type = {
-            use std::os::unix::fs::FileTypeExt;
-
-            if metadata.file_type().is_socket() {
-                SpecialFileType::Socket
-            } else if metadata.file_type().is_block_device() {
-                SpecialFileType::BlockDevice
-            } else if metadata.file_type().is_char_device() {
-                SpecialFileType::CharDevice
-            } else if metadata.file_type().is_fifo() {
-                SpecialFileType::Fifo
-            } else {
-                SpecialFileType::NA
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingPagingMode, arg));
+                process::exit(-1);
             }
-        };
+        } else if arg == "--width" {
+            specify_width = true;
 
-        #[cfg(not(target_family = "unix"))]
-        let special_file_type = SpecialFileType::NA;
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingWidth, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--ext" {
+            specify_ext = true;
 
-        let matches = if get_option(PrgOptions::SearchNoext) {
-            // get the filename of this entry without the extension
-            let Some(file_stem) = path_os.file_stem() else {
-                continue;
-            };
-            let file_stem = file_stem.to_string_lossy();
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingExtensions, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--mime" {
+            if !cfg!(feature = "mime") {
+                print!("{}", tr!(Msg::MimeNotCompiled));
+                process::exit(-1);
+            }
 
-            *file_stem == *p_search_path
-        } else {
-            // get the filename of this entry
-            let Some(file_name) = path_os.file_name() else {
-                continue;
-            };
-            let file_name = file_name.to_string_lossy();
+            specify_mime = true;
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingMimeTypes, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--type" {
+            specify_type = true;
 
-            if get_option(PrgOptions::SearchExact) {
-                *file_name == *p_search_path
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingTypes, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--min-size" || arg == "--max-size" {
+            if arg == "--min-size" {
+                specify_min_size = true;
             } else {
-                file_name.contains(p_search_path)
+                specify_max_size = true;
             }
-        };
 
-        if metadata.is_symlink() {
-            // skip if the show symlinks option is not set
-            if !get_option(PrgOptions::ShowSymlinks) {
-                cur_entry_cnts.inc_symlink_cnt(1);
-                continue;
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingSize, arg));
+                process::exit(-1);
             }
+        } else if arg == "--exclude" {
+            specify_exclude = true;
 
-            if !matches {
-                cur_entry_cnts.inc_symlink_cnt(1);
-                continue;
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingExcludePattern, arg));
+                process::exit(-1);
             }
+        } else if arg == "--exclude-from" {
+            specify_exclude_from = true;
 
-            let failed = show_symlink_noindent(&metadata, &path_os, path_os.is_dir());
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingExcludeFile, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--prune-common" {
+            excludes.extend(
+                ignore::COMMON_PRUNED_DIRS
+                    .iter()
+                    .map(|name| format!("{}/", name)),
+            );
+        } else if arg == "--prune-dir" {
+            specify_prune_dir = true;
 
-            if !failed {
-                cur_entry_cnts.inc_symlink_cnt(1);
-                p_entry_cnts_match.inc_symlink_cnt(1);
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingPruneDir, arg));
+                process::exit(-1);
             }
-        } else if metadata.is_file() && special_file_type == SpecialFileType::NA {
-            if !get_option(PrgOptions::ShowFiles) {
-                cur_entry_cnts.inc_file_cnt(1);
-                continue;
+        } else if arg == "--owner" {
+            specify_owner = true;
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingUser, arg));
+                process::exit(-1);
             }
+        } else if arg == "--group" {
+            specify_group = true;
 
-            if !matches {
-                cur_entry_cnts.inc_file_cnt(1);
-                continue;
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingGroup, arg));
+                process::exit(-1);
             }
+        } else if arg == "--perm" {
+            specify_perm = true;
 
-            let failed = show_file_noindent(&metadata, &path_os, &metadata.len());
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingPerm, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--newer-than"
+            || arg == "--older-than"
+            || arg == "--accessed-within"
+            || arg == "--status-changed-within"
+        {
+            match arg.as_str() {
+                "--newer-than" => specify_newer_than = true,
+                "--older-than" => specify_older_than = true,
+                "--accessed-within" => specify_accessed_within = true,
+                _ => specify_status_changed_within = true,
+            }
 
-            if !failed {
-                cur_entry_cnts.inc_file_cnt(1);
-                p_entry_cnts_match.inc_file_cnt(1);
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingTimeBound, arg));
+                process::exit(-1);
             }
-        } else if metadata.is_dir() {
-            if !matches {
-                cur_entry_cnts.inc_dir_cnt(1);
-            } else {
-                let failed = show_dir_noindent(&metadata, &path_os);
+        } else if arg == "--human-readable" || arg == "--binary" {
+            set_option(PrgOptions::HumanReadable);
+            clear_option(PrgOptions::Si);
+        } else if arg == "--si" {
+            set_option(PrgOptions::HumanReadable);
+            set_option(PrgOptions::Si);
+        } else if arg == "--block-size" {
+            specify_block_size = true;
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingBlockSize, arg));
+                process::exit(-1);
+            }
+        } else if arg == "--relative-time" {
+            set_option(PrgOptions::RelativeTime);
+        } else if arg == "--time-format" {
+            specify_time_format = true;
 
-                if !failed {
-                    cur_entry_cnts.inc_dir_cnt(1);
-                    p_entry_cnts_match.inc_dir_cnt(1);
-                }
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingTimeFormat, arg));
+                process::exit(-1);
             }
+        } else if arg == "--time-zone" {
+            specify_time_zone = true;
 
-            if get_option(PrgOptions::ShowRecursive)
-                && (*p_max_level == 0u64 || p_level < (*p_max_level as usize))
-            {
-                if let Some(error) = search_path(
-                    p_entry_cnts_match,
-                    p_entry_cnts_full,
-                    p_max_level,
-                    1 + p_level,
-                    &path_os,
-                    p_search_path,
-                ) {
-                    if get_option(PrgOptions::ShowErrors) {
-                        eprint!(
-                            "Error while iterating over \"{}\"\n{}\n",
-                            path_os.to_string_lossy(),
-                            error
-                        );
-                    }
-                }
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingTimeZone, arg));
+                process::exit(-1);
             }
-        } else {
-            if !get_option(PrgOptions::ShowSpecial) {
-                cur_entry_cnts.inc_special_cnt(1);
-                continue;
+        } else if arg == "--columns" {
+            specify_columns = true;
+
+            if args.len() <= i + 1 {
+                print!("{}", tr!(Msg::MissingColumns, arg));
+                process::exit(-1);
             }
+        } else {
+            print!("{}", tr!(Msg::UnknownOption, arg));
+        }
+    }
+
+    // the path of a policy is overridden by the ones given on the command line, like its options
+    if policy.is_some() && init_paths.len() > 1 {
+        init_paths.remove(0);
+    }
+    if init_paths.is_empty() {
+        init_paths.push(".".to_owned());
+    }
+    // Path that the modes which only scan a single directory start from
+    let mut init_path = init_paths[0].clone();
 
-            if !matches {
-                cur_entry_cnts.inc_special_cnt(1);
-                continue;
-            }
+    let _ = INDENT.set((indent_col_width, indent_style));
 
-            let failed = show_special_noindent(&metadata, &path_os, &special_file_type);
+    ignore::set_excludes(&excludes);
 
-            if !failed {
-                cur_entry_cnts.inc_special_cnt(1);
-                p_entry_cnts_match.inc_special_cnt(1);
-            }
-        }
+    // the columns given to --columns replace those added by their own options (such as -p), wherever they were given
+    columns::apply_options();
+
+    // the types given to --type are shown even if their own options (such as -f) were not given
+    filter::apply_options();
+
+    if get_option(PrgOptions::Help) {
+        // Name of current process
+        let process_name = std::env::args().next().unwrap_or("fss".to_owned());
+
+        print!("{}\n", help::help_text(&process_name));
+
+        process::exit(0);
     }
 
-    p_entry_cnts_full.inc_symlink_cnt(cur_entry_cnts.get_symlink_cnt());
-    p_entry_cnts_full.inc_file_cnt(cur_entry_cnts.get_file_cnt());
-    p_entry_cnts_full.inc_dir_cnt(cur_entry_cnts.get_dir_cnt());
-    p_entry_cnts_full.inc_special_cnt(cur_entry_cnts.get_special_cnt());
+    if get_option(PrgOptions::HelpFull) {
+        let process_name = std::env::args().next().unwrap_or("fss".to_owned());
 
-    return None;
-}
+        print!(
+            "{}{}\n",
+            help::help_text(&process_name),
+            help::topics_text()
+        );
 
-fn scan_path_init(p_init_path: &str, p_max_level: &u64) {
-    // create new containers to store files in current directory and subdirectories respectively
-    let mut entry_cnts_init = EntryCounter::new();
-    let mut entry_cnts_full: EntryCounter = EntryCounter::new();
+        process::exit(0);
+    }
 
-    // create a path object over the initial path
-    let init_path = path::Path::new(&p_init_path);
+    if get_option(PrgOptions::Man) {
+        print!("{}", help::man_page());
 
-    // check if the path could be iterated over
-    // if an error occours (such as insufficient permissions, non-existant directory)
-    // then report it and return without printing the summary of traversal
-    if let Some(error) = scan_path(
-        &mut entry_cnts_init,
-        &mut entry_cnts_full,
-        p_max_level,
-        0,
-        init_path,
-    ) {
+        process::exit(0);
+    }
+
+    if get_option(PrgOptions::Capabilities) {
+        print!("{}", capability::capabilities_text(&init_path));
+
+        process::exit(0);
+    }
+
+    if get_option(PrgOptions::Record) || get_option(PrgOptions::Replay) {
+        // only listing and searching read the filesystem through the recording
+        if get_option(PrgOptions::Watch)
+            || get_option(PrgOptions::TransferEstimate)
+            || get_option(PrgOptions::Fingerprint)
+            || get_option(PrgOptions::DirsOnly)
+            || get_option(PrgOptions::NameCollisions)
+            || get_option(PrgOptions::Hardlinks)
+            || get_option(PrgOptions::Oldest)
+            || get_option(PrgOptions::Newest)
+            || get_option(PrgOptions::ColdData)
+            || get_option(PrgOptions::TimestampAnomalies)
+            || get_option(PrgOptions::EmptyDirs)
+        {
+            print!("{}", tr!(Msg::RecordReplayModes));
+            process::exit(-1);
+        }
+
+        if get_option(PrgOptions::Record) && get_option(PrgOptions::Replay) {
+            print!("{}", tr!(Msg::RecordAndReplay));
+            process::exit(-1);
+        }
+    }
+
+    // each of these produces a single recording, report file or document (or never finishes), so it covers one directory
+    if init_paths.len() > 1
+        && (get_option(PrgOptions::Watch)
+            || get_option(PrgOptions::Record)
+            || get_option(PrgOptions::Replay)
+            || get_option(PrgOptions::Policy)
+            || get_option(PrgOptions::SplitOutput)
+            || get_option(PrgOptions::Treemap)
+            || get_option(PrgOptions::Format)
+            || get_option(PrgOptions::ExportSqlite)
+            || get_option(PrgOptions::ExportParquet))
+    {
+        print!("{}", tr!(Msg::MultiplePathsModes));
+        process::exit(-1);
+    }
+
+    // output files are compressed if their names say so, with the algorithm that their extension stands for (unless
+    // another one was given)
+    if let Some(algo) = compress::CompressAlgo::from_path(path::Path::new(&output_path)) {
+        set_option(PrgOptions::Compress);
+        compress::set_algo(algo);
+    }
+    if get_option(PrgOptions::Compress) && !compress::algo().is_available() {
         print!(
-            "Error while iterating over \"{}\"\n{}\n",
-            p_init_path, error
+            "{}",
+            tr!(Msg::CompressAlgoNotCompiled, compress::algo().name())
         );
-        return;
+        process::exit(-1);
     }
 
-    let file_cnt = int_to_formatted_slice(entry_cnts_init.get_file_cnt()).to_owned();
-    let symlink_cnt = int_to_formatted_slice(entry_cnts_init.get_symlink_cnt()).to_owned();
-    let special_cnt = int_to_formatted_slice(entry_cnts_init.get_special_cnt()).to_owned();
-    let dir_cnt = int_to_formatted_slice(entry_cnts_init.get_dir_cnt()).to_owned();
-    let total_cnt = int_to_formatted_slice(entry_cnts_init.get_entry_cnt()).to_owned();
+    // the directories above a match are printed along with it, which needs them to be searched depth-first
+    if get_option(PrgOptions::Bfs) && get_option(PrgOptions::SearchTree) {
+        print!("{}", tr!(Msg::BfsWithSearchTree));
+        process::exit(-1);
+    }
+    // only the searches that print their matches one below the other are done breadth-first
+    if get_option(PrgOptions::Bfs)
+        && (!(get_option(PrgOptions::SearchExact)
+            || get_option(PrgOptions::SearchNoext)
+            || get_option(PrgOptions::SearchContains)
+            || get_option(PrgOptions::SearchGlob)
+            || get_option(PrgOptions::SearchFuzzy))
+            || find_expr.is_some()
+            || get_option(PrgOptions::Watch)
+            || get_option(PrgOptions::Long)
+            || get_option(PrgOptions::NamesOnly)
+            || get_option(PrgOptions::SizesOnly)
+            || get_option(PrgOptions::LsFormat)
+            || get_option(PrgOptions::Format)
+            || get_option(PrgOptions::ExportSqlite)
+            || get_option(PrgOptions::ExportParquet)
+            || get_option(PrgOptions::Printf)
+            || get_option(PrgOptions::Print0))
+    {
+        print!("{}", tr!(Msg::BfsWithoutSearch));
+        process::exit(-1);
+    }
 
-    // Unformatted summary string for directory to traverse (not including subdirectories)
-    print!(
-        "\n\
-            Summary of \"{}\"\n\
-            <{} files>\n\
-            <{} symlinks>\n\
-            <{} special files>\n\
-            <{} subdirectories>\n\
-            <{} total entries>\n\
-            \n",
-        p_init_path, file_cnt, symlink_cnt, special_cnt, dir_cnt, total_cnt
-    );
+    // every snapshot of a watched directory is read in full, since a partial one would report the rest as deleted
+    if limit::max_entries().is_some() && get_option(PrgOptions::Watch) {
+        print!("{}", tr!(Msg::MaxEntriesWithWatch));
+        process::exit(-1);
+    }
+    if limit::timeout().is_some() && get_option(PrgOptions::Watch) {
+        print!("{}", tr!(Msg::TimeoutWithWatch));
+        process::exit(-1);
+    }
+    if errors::on_error() != errors::OnError::Skip && get_option(PrgOptions::Watch) {
+        print!("{}", tr!(Msg::OnErrorWithWatch));
+        process::exit(-1);
+    }
 
-    // if the recursive traversal option was not set, then return without printing the complete summary
-    if !get_option(PrgOptions::ShowRecursive) {
-        return;
+    if BLOCK_SIZE.get().is_some() && get_option(PrgOptions::HumanReadable) {
+        print!("{}", tr!(Msg::BlockSizeWithHumanReadable));
+        process::exit(-1);
     }
 
-    let file_cnt = int_to_formatted_slice(entry_cnts_full.get_file_cnt()).to_owned();
-    let symlink_cnt = int_to_formatted_slice(entry_cnts_full.get_symlink_cnt()).to_owned();
-    let special_cnt = int_to_formatted_slice(entry_cnts_full.get_special_cnt()).to_owned();
-    let dir_cnt = int_to_formatted_slice(entry_cnts_full.get_dir_cnt()).to_owned();
-    let total_cnt = int_to_formatted_slice(entry_cnts_full.get_entry_cnt()).to_owned();
+    if get_option(PrgOptions::Append) && !get_option(PrgOptions::Output) {
+        print!("{}", tr!(Msg::AppendWithoutOutput));
+        process::exit(-1);
+    }
 
-    // Unformatted summary string for the directory to traverse (including subdirectories)
-    print!(
-        "Including subdirectories\n\
-            <{} files>\n\
-            <{} symlinks>\n\
-            <{} special files>\n\
-            <{} subdirectories>\n\
-            <{} total entries>\n\
-            \n",
-        file_cnt, symlink_cnt, special_cnt, dir_cnt, total_cnt
-    );
-}
+    // the output file replaces the previous one once the scan finishes, which never happens while watching
+    if get_option(PrgOptions::Output) && get_option(PrgOptions::Watch) {
+        print!("{}", tr!(Msg::OutputWhileWatching));
+        process::exit(-1);
+    }
 
-fn search_path_init(p_init_path: &str, p_search_path: &str, p_max_level: &u64) {
-    let mut entry_cnts_match = EntryCounter::new();
-    let mut entry_cnts_total: EntryCounter = EntryCounter::new();
+    if get_option(PrgOptions::SplitOutput) {
+        // each subdirectory is scanned by a separate run, which can not share a recording, a watch or a policy
+        if get_option(PrgOptions::Watch)
+            || get_option(PrgOptions::Record)
+            || get_option(PrgOptions::Replay)
+            || get_option(PrgOptions::TransferEstimate)
+            || get_option(PrgOptions::Policy)
+            || get_option(PrgOptions::Output)
+        {
+            print!("{}", tr!(Msg::SplitOutputModes));
+            process::exit(-1);
+        }
 
-    let init_path = path::Path::new(&p_init_path);
+        let args: Vec<String> = args
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(i, _)| !split_skipped_args.contains(i))
+            .map(|(_, arg)| arg.clone())
+            .collect();
 
-    if let Some(error) = search_path(
-        &mut entry_cnts_match,
-        &mut entry_cnts_total,
-        p_max_level,
-        0,
-        &init_path,
-        p_search_path,
-    ) {
-        if get_option(PrgOptions::ShowErrors) {
-            eprint!(
-                "Error while iterating over \"{}\"\n{}\n",
-                p_init_path, error
+        split_output_init(&init_path, &split_dir, &args);
+
+        process::exit(0);
+    }
+
+    // this is done before the progress is started, since it is only reported if the output is not a terminal (the
+    // output file given to --output takes the place of the policy's)
+    let policy_output = policy.as_ref().and_then(|policy| policy.output.as_ref());
+    if let Some(output) = policy_output.filter(|_| !get_option(PrgOptions::Output)) {
+        if let Err(error) = redirect_stdout(output, false) {
+            print!(
+                "{}",
+                tr!(Msg::PolicyOutputError, output.to_string_lossy(), error)
             );
+            process::exit(-1);
         }
-        return;
     }
 
-    let file_cnt = int_to_formatted_slice(entry_cnts_match.get_file_cnt()).to_owned();
-    let symlink_cnt = int_to_formatted_slice(entry_cnts_match.get_symlink_cnt()).to_owned();
-    let special_cnt = int_to_formatted_slice(entry_cnts_match.get_special_cnt()).to_owned();
-    let dir_cnt = int_to_formatted_slice(entry_cnts_match.get_dir_cnt()).to_owned();
-    let total_cnt = int_to_formatted_slice(entry_cnts_match.get_entry_cnt()).to_owned();
+    if get_option(PrgOptions::Replay) {
+        // the scan starts from the same directory as the recorded one
+        match record::load_replay(path::Path::new(&replay_path)) {
+            Ok(root) => {
+                init_path = root;
+                init_paths = vec![init_path.clone()];
+            }
+            Err(error) => {
+                print!("{}", tr!(Msg::RecordingReadError, replay_path, error));
+                process::exit(-1);
+            }
+        }
+    }
 
-    // Unformatted summary string for number of entries found matching search pattern (in search mode)
-    print!(
-        "\n\
-            Summary of matching entries\n\
-            <{} files>\n\
-            <{} symlinks>\n\
-            <{} special files>\n\
-            <{} subdirectories>\n\
-            <{} total entries>\n\
-            \n",
-        file_cnt, symlink_cnt, special_cnt, dir_cnt, total_cnt
-    );
+    // a replayed scan does not read the filesystem, so its capabilities do not matter
+    if !get_option(PrgOptions::Replay) {
+        capability::check(&init_path);
+    }
 
-    let file_cnt = int_to_formatted_slice(entry_cnts_total.get_file_cnt()).to_owned();
-    let symlink_cnt = int_to_formatted_slice(entry_cnts_total.get_symlink_cnt()).to_owned();
-    let special_cnt = int_to_formatted_slice(entry_cnts_total.get_special_cnt()).to_owned();
-    let dir_cnt = int_to_formatted_slice(entry_cnts_total.get_dir_cnt()).to_owned();
-    let total_cnt = int_to_formatted_slice(entry_cnts_total.get_entry_cnt()).to_owned();
+    // patterns read from a file are compared with the names as a whole, unless they were given with another search mode
+    if !search_patterns.is_empty()
+        && !get_option(PrgOptions::SearchNoext)
+        && !get_option(PrgOptions::SearchContains)
+        && !get_option(PrgOptions::SearchGlob)
+        && !get_option(PrgOptions::SearchFuzzy)
+    {
+        set_option(PrgOptions::SearchExact);
+    }
 
-    // Unformatted summary string for number of entries traversed while matching search pattern (in search mode)
-    print!(
-        "Summary of traversal of \"{}\"\n\
-            <{} files>\n\
-            <{} symlinks>\n\
-            <{} special files>\n\
-            <{} subdirectories>\n\
-            <{} total entries>\n\
-            \n",
-        p_init_path, file_cnt, symlink_cnt, special_cnt, dir_cnt, total_cnt
-    );
-}
+    // names are lowercased while matching them when the case is ignored, so the patterns are lowercased as well
+    if get_option(PrgOptions::IgnoreCase) {
+        for pattern in &mut search_patterns {
+            *pattern = pattern.to_lowercase();
+        }
+    }
 
-fn main() {
-    // Path to start the scan process from
-    let mut init_path: String = ".".to_owned();
+    if get_option(PrgOptions::SearchExact) || get_option(PrgOptions::SearchNoext) {
+        let _ = EXACT_PATTERNS.set(search_patterns.iter().cloned().collect());
+    }
 
-    // Pattern to search for
-    let mut search_path: String = "".to_owned();
+    if get_option(PrgOptions::SearchGlob) {
+        glob::set_patterns(&search_patterns, get_option(PrgOptions::FullPath));
+    }
 
-    // whether the previous flag was "-r" or "--recursive"
-    let mut specify_recur_depth: bool = false;
+    // this is done before the progress is started, since it is only reported if the output is not a terminal
+    if get_option(PrgOptions::Output) {
+        if let Err(error) = start_output(
+            path::Path::new(&output_path),
+            get_option(PrgOptions::Append),
+        ) {
+            eprint!("{}", tr!(Msg::OutputCreateError, output_path, error));
+            process::exit(-1);
+        }
+    }
 
-    let mut specify_search_path: bool = false;
+    if get_option(PrgOptions::Compress) {
+        if let Err(error) = compress::start() {
+            eprint!("{}", tr!(Msg::CompressError, error));
+            process::exit(-1);
+        }
+    }
 
-    // maximum number of levels to recurse until if the PrgOptions::ShowRecursive option is set (a value of 0 denotes no limit)
-    let mut max_recur_level: u64 = 0;
+    // this is done before the recording is started, since it may be estimated from the recording being overwritten
+    if get_option(PrgOptions::Progress) {
+        let expected_cnt = if !progress_path.is_empty() {
+            match record::count_entries(path::Path::new(&progress_path)) {
+                Ok(entry_cnt) => Some(entry_cnt),
+                Err(error) => {
+                    print!("{}", tr!(Msg::RecordingReadError, progress_path, error));
+                    process::exit(-1);
+                }
+            }
+        } else if get_option(PrgOptions::Record) {
+            // the previous recording is only used if it exists (the first scan only shows the number of entries)
+            record::count_entries(path::Path::new(&record_path)).ok()
+        } else {
+            None
+        };
 
-    for (i, arg) in env::args().enumerate().skip(1) {
-        let arg_len = arg.len();
+        progress::start(expected_cnt);
+    }
 
-        if arg_len <= 0 {
-            print!("Ignoring Unknown Option of length 0\n");
+    if get_option(PrgOptions::Record) {
+        if let Err(error) = record::start_recording(path::Path::new(&record_path), &init_path) {
+            print!("{}", tr!(Msg::RecordingCreateError, record_path, error));
+            process::exit(-1);
         }
+    }
 
-        if arg.chars().nth(0).unwrap() != '-' {
-            if specify_recur_depth {
-                specify_recur_depth = false;
-                if let Ok(depth) = arg.parse::<u64>() {
-                    max_recur_level = depth;
-                    if depth <= 0 {
-                        print!("Maximum recursion depth must be greater than 0!\n");
-                        print!("Ignoring recursive option\n");
-                        clear_option(PrgOptions::ShowRecursive);
-                    }
-                    continue;
-                } else {
-                    print!("Could not convert \"{}\" to an integer\n", arg);
-                    print!("Ignoring recursive option\n");
-                    clear_option(PrgOptions::ShowRecursive);
+    // the work pool only hashes files, so it is not needed if no hashes are printed
+    if !get_option(PrgOptions::ShowHash) {
+        work_threads = 0;
+    }
+    pool::start(walk_threads, work_threads);
 
-                    continue;
-                }
-            } else if specify_search_path {
-                search_path = arg.clone();
-                continue;
-            } else {
-                init_path = arg.clone();
-                if init_path.len() > MAX_PATH_LEN {
-                    init_path = init_path[..MAX_PATH_LEN].to_owned();
-                }
-                continue;
-            }
+    // the output is not paged while watching, since it never ends, nor when it is written into a file
+    if !get_option(PrgOptions::Watch)
+        && !get_option(PrgOptions::Output)
+        && !get_option(PrgOptions::Compress)
+        && policy_output.is_none()
+    {
+        // the colors and the width are taken from the terminal before the output is passed through the pager
+        color::enabled();
+        columns::width();
+
+        if let Err(error) = pager::start() {
+            eprint!("{}", tr!(Msg::PagerError, error));
         }
-        specify_recur_depth = false;
-        specify_search_path = false;
+    }
 
-        if arg == "-h" || arg == "--help" {
-            set_option(PrgOptions::Help);
-        } else if arg == "-e" || arg == "--show-err" {
-            set_option(PrgOptions::ShowErrors);
-        } else if arg == "-r" || arg == "--recursive" {
-            set_option(PrgOptions::ShowRecursive);
-            specify_recur_depth = true;
-        } else if arg == "-f" || arg == "--files" {
-            set_option(PrgOptions::ShowFiles);
-        } else if arg == "-l" || arg == "--symlinks" {
-            set_option(PrgOptions::ShowSymlinks);
-        } else if arg == "-s" || arg == "--special" {
-            set_option(PrgOptions::ShowSpecial);
-        } else if arg == "-d" || arg == "--dir-size" {
-            set_option(PrgOptions::ShowDirSize);
-        } else if arg == "--no-tree" {
-            set_option(PrgOptions::ShowNotree);
-        } else if arg == "-S" || arg == "--search" {
-            if get_option(PrgOptions::SearchNoext) || get_option(PrgOptions::SearchContains) {
-                print!("Can only set one search mode at a time\n");
-                print!("Terminating...");
-                process::exit(-1);
-            }
+    // status that the run exits with once everything has been written (only searches set it)
+    let mut search_status = 0;
 
-            specify_search_path = true;
-            set_option(PrgOptions::SearchExact);
+    // searches set the status from the number of matches, whichever mode they print them in
+    let searching = get_option(PrgOptions::SearchExact)
+        || get_option(PrgOptions::SearchNoext)
+        || get_option(PrgOptions::SearchContains)
+        || get_option(PrgOptions::SearchGlob)
+        || get_option(PrgOptions::SearchFuzzy);
+    let mut match_cnt: u64 = 0;
+
+    // the listings and searches of several directories are followed by a summary of all of them
+    let mut combined_summary: Option<Msg> = None;
+    let mut combined_cnts = EntryCounter::new();
+
+    // the plain modes are read by other tools, so the directories are not introduced by their paths
+    let plain = get_option(PrgOptions::NamesOnly)
+        || get_option(PrgOptions::SizesOnly)
+        || get_option(PrgOptions::LsFormat)
+        || get_option(PrgOptions::Printf)
+        || get_option(PrgOptions::Print0);
+
+    for init_path in &init_paths {
+        if init_paths.len() > 1 && !plain {
+            print!("{}", tr!(Msg::PathHeader, init_path));
+        }
 
-            if env::args().len() <= i + 1 {
-                print!("No Search Pattern provided after {} flag\n", arg);
-                process::exit(-1);
+        // this is done after the recording is loaded, since the patterns are relative to the directory that it starts
+        // from
+        if get_option(PrgOptions::SearchGlob) {
+            glob::set_base(path::Path::new(init_path));
+        }
+        if get_option(PrgOptions::FullPath) {
+            if let Ok(mut search_root) = SEARCH_ROOT.write() {
+                *search_root = Some(path::PathBuf::from(init_path));
             }
-        } else if arg == "--search-noext" {
-            if get_option(PrgOptions::SearchExact) || get_option(PrgOptions::SearchContains) {
-                print!("Can only set one search mode at a time\n");
-                print!("Terminating...");
-                process::exit(-1);
+        }
+        filter::reset_filtered_cnt();
+
+        if get_option(PrgOptions::Watch) {
+            watch::watch_path_init(
+                init_path,
+                &search_patterns,
+                &max_recur_level,
+                debounce,
+                &watch_hooks,
+            );
+        } else if let Some(find_expr) = &find_expr {
+            find_expr_init(init_path, find_expr);
+        } else if get_option(PrgOptions::Long) {
+            match ls::long_init(init_path, &search_patterns, &max_recur_level) {
+                Some(shown_cnt) => match_cnt += shown_cnt,
+                None if searching => search_status = EXIT_SEARCH_ERROR,
+                None => {}
             }
-
-            specify_search_path = true;
-            set_option(PrgOptions::SearchNoext);
-
-            if env::args().len() <= i + 1 {
-                print!("No Search Pattern provided after {} flag\n", arg);
-                process::exit(-1);
+        } else if get_option(PrgOptions::NamesOnly)
+            || get_option(PrgOptions::SizesOnly)
+            || get_option(PrgOptions::LsFormat)
+            || get_option(PrgOptions::Format)
+            || get_option(PrgOptions::ExportSqlite)
+            || get_option(PrgOptions::ExportParquet)
+            || get_option(PrgOptions::Printf)
+            || get_option(PrgOptions::Print0)
+        {
+            match plain_init(init_path, &search_patterns, &max_recur_level) {
+                Some(shown_cnt) => match_cnt += shown_cnt,
+                None if searching => search_status = EXIT_SEARCH_ERROR,
+                None => {}
             }
-        } else if arg == "--contains" {
-            if get_option(PrgOptions::SearchNoext) || get_option(PrgOptions::SearchExact) {
-                print!("Can only set one search mode at a time\n");
-                print!("Terminating...");
-                process::exit(-1);
+        } else if searching {
+            combined_summary = Some(Msg::CombinedSearchSummary);
+            match search_path_init(init_path, &search_patterns, &max_recur_level) {
+                Some(entry_cnts_match) => {
+                    match_cnt += entry_cnts_match.get_entry_cnt();
+                    combined_cnts.add(&entry_cnts_match);
+                }
+                None => search_status = EXIT_SEARCH_ERROR,
+            }
+        } else if get_option(PrgOptions::TransferEstimate) {
+            transfer_estimate_init(init_path, &dest_path);
+        } else if get_option(PrgOptions::NameCollisions) {
+            name_collisions_init(init_path, &max_recur_level);
+        } else if get_option(PrgOptions::Hardlinks) {
+            hardlinks_init(init_path, &max_recur_level);
+        } else if get_option(PrgOptions::Oldest) || get_option(PrgOptions::Newest) {
+            file_age_init(init_path, &max_recur_level, oldest_cnt, newest_cnt);
+        } else if get_option(PrgOptions::ColdData) {
+            cold_data_init(init_path, &max_recur_level, cold_age);
+        } else if get_option(PrgOptions::TimestampAnomalies) {
+            timestamp_anomalies_init(init_path, &max_recur_level, ctime_gap);
+        } else if get_option(PrgOptions::EmptyDirs) {
+            empty_dirs_init(init_path, &max_recur_level, nested_empty);
+        } else if get_option(PrgOptions::Treemap) {
+            treemap::treemap_init(init_path, &max_recur_level, &treemap_path);
+        } else if get_option(PrgOptions::Fingerprint) {
+            fingerprint_init(init_path, &max_recur_level);
+        } else if get_option(PrgOptions::DirsOnly) {
+            dirs_only_init(init_path, &max_recur_level);
+        } else {
+            combined_summary = Some(Msg::CombinedScanSummary);
+            if let Some(entry_cnts_full) = scan_path_init(init_path, &max_recur_level) {
+                combined_cnts.add(&entry_cnts_full);
             }
+        }
+    }
 
-            specify_search_path = true;
-            set_option(PrgOptions::SearchContains);
+    if let Some(summary) = combined_summary.filter(|_| init_paths.len() > 1) {
+        let file_cnt = int_to_formatted_slice(combined_cnts.get_file_cnt()).to_owned();
+        let symlink_cnt = int_to_formatted_slice(combined_cnts.get_symlink_cnt()).to_owned();
+        let special_cnt = int_to_formatted_slice(combined_cnts.get_special_cnt()).to_owned();
+        let dir_cnt = int_to_formatted_slice(combined_cnts.get_dir_cnt()).to_owned();
+        let total_cnt = int_to_formatted_slice(combined_cnts.get_entry_cnt()).to_owned();
 
-            if env::args().len() <= i + 1 {
-                print!("No Search Pattern provided after {} flag\n", arg);
-                process::exit(-1);
-            }
-        } else if arg == "-p" || arg == "--permissions" {
-            #[cfg(target_family = "unix")]
-            set_option(PrgOptions::ShowPermissions);
-        } else if arg == "-t" || arg == "--modification-time" {
-            #[cfg(target_family = "unix")]
-            set_option(PrgOptions::ShowLasttime);
-        } else {
-            print!("Ignoring unknown option {}\n", arg);
+        print!(
+            "{}",
+            tr!(
+                summary,
+                init_paths.len(),
+                file_cnt,
+                symlink_cnt,
+                special_cnt,
+                dir_cnt,
+                total_cnt
+            )
+        );
+    }
+
+    // the listing is followed by the notices, while the output of the plain modes is kept as it is for other tools
+    let mut notices = Vec::new();
+    if limit::truncated() {
+        let max_entries = int_to_formatted_slice(limit::max_entries().unwrap_or(0));
+        notices.push(tr!(Msg::EntriesTruncated, max_entries));
+    }
+    if limit::timed_out() {
+        let timeout = limit::timeout().unwrap_or_default().as_secs();
+        notices.push(tr!(Msg::TraversalTimedOut, int_to_formatted_slice(timeout)));
+    }
+    if let Some((dir_path, error)) = errors::aborted() {
+        notices.push(tr!(Msg::ScanAborted, dir_path.to_string_lossy(), error));
+    }
+    if errors::on_error() == errors::OnError::Collect {
+        notices.push(error_report(errors::take_errors()));
+    }
+    for notice in notices {
+        match plain || get_option(PrgOptions::Format) {
+            true => eprint!("{}", notice),
+            false => print!("{}", notice),
         }
     }
 
-    if get_option(PrgOptions::Help) {
-        // Name of current process
-        let process_name = std::env::args().nth(0).unwrap_or("fss".to_owned());
-
-        #[cfg(target_family = "unix")]
-        println!("\n\
-        File System Scanner (dumblebots.com)\n\
-        \n\
-        Usage: {} [PATH] [options]\n\
-        Scan through the filesystem starting from PATH.\n\
-        \n\
-        Example: {} \"..\" --recursive --files\n\
-        \n\
-        Options:\n\
-        -r, --recursive             Recursively scan directories (can be followed by a positive integer to indicate the depth)\n\
-        -p, --permissions           Print Permissions of each entry\n\
-        -t, --modification-time     Print the time when each entry was last modified\n\
-        \n\
-        -f, --files                 Show Regular Files (normally hidden)\n\
-        -l, --symlinks              Show Symlinks (normally hidden)\n\
-        -s, --special               Show Special Files such as sockets, pipes, etc. (normally hidden)\n\
-        \n\
-        -d, --dir-size              Print directory sizes (calculated as the sum of sizes of all contained entries recursively)\n\
-        \n    \
-            --no-tree               Print the absolute path of each entry (without indendation) instead of tree form\n\
-        \n\
-        -S, --search <phrase>       Only show entries whose name completely matches phrase\n    \
-            --search-noext <phrase> Only show entries whose name(not counting the extension) completely matches phrase\n    \
-            --contains <phrase>     Only show entries whose name contains phrase\n\
-        \n\
-        -e, --show-err              Show errors\n\
-        -h, --help                  Print Usage Instructions\n\
-        \n", &process_name, &process_name);
-
-        #[cfg(not(target_family = "unix"))]
-        println!("\n\
-        File System Scanner (dumblebots.com)\n\
-        \n\
-        Usage: {} [PATH] [options]\n\
-        Scan through the filesystem starting from PATH.\n\
-        \n\
-        Example: {} \"..\" --recursive --files\n\
-        \n\
-        Options:\n\
-        -r, --recursive             Recursively scan directories (can be followed by a positive integer to indicate the depth)\n\
-        \n\
-        -f, --files                 Show Regular Files (normally hidden)\n\
-        -l, --symlinks              Show Symlinks (normally hidden)\n\
-        -s, --special               Show Special Files such as sockets, pipes, etc. (normally hidden)\n\
-        \n\
-        -d, --dir-size              Print directory sizes (calculated as the sum of sizes of all contained entries recursively)\n\
-        \n    \
-            --no-tree               Print the absolute path of each entry (without indendation) instead of tree form\n\
-        \n\
-        -S, --search <phrase>       Only show entries whose name completely matches phrase\n    \
-            --search-noext <phrase> Only show entries whose name(not counting the extension) completely matches phrase\n    \
-            --contains <phrase>     Only show entries whose name contains phrase\n\
-        \n\
-        -e, --show-err              Show errors\n\
-        -h, --help                  Print Usage Instructions\n\
-        \n", &process_name, &process_name);
+    // like grep, a search fails if any of the directories could not be searched, even if the others matched
+    if searching && search_status == 0 && match_cnt == 0 {
+        search_status = EXIT_NO_MATCH;
+    }
 
-        process::exit(0);
+    if let Err(error) = record::finish_recording() {
+        print!("{}", tr!(Msg::RecordingWriteError, record_path, error));
     }
 
-    if get_option(PrgOptions::SearchExact)
-        || get_option(PrgOptions::SearchNoext)
-        || get_option(PrgOptions::SearchContains)
-    {
-        search_path_init(&init_path, &search_path, &max_recur_level)
-    } else {
-        scan_path_init(&init_path, &max_recur_level);
+    // the audit is done after the recording is finished, so that it does not record the directories a second time
+    let violation_cnt = match &policy {
+        Some(policy) => policy::audit(policy, &init_path),
+        None => 0,
+    };
+
+    // loops are reported even without "--show-err", since the sizes and counts of the directories above them are off
+    if record::loop_cnt() > 0 {
+        eprint!(
+            "{}",
+            tr!(
+                Msg::FilesystemLoops,
+                int_to_formatted_slice(record::loop_cnt() as u64)
+            )
+        );
+    }
+
+    // persist the hashes calculated during this run (has no effect if no hash cache was given)
+    if let Err(error) = hash::save_hash_cache() {
+        print!("{}", tr!(Msg::HashCacheWriteError, error));
+    }
+
+    // the run completed, so it never needs to be resumed from the checkpoint (unless it stopped early)
+    if !limit::stopped_early() {
+        if let Err(error) = hash::finish_checkpoint() {
+            print!("{}", tr!(Msg::CheckpointRemoveError, error));
+        }
+    }
+
+    if let Err(error) = compress::finish() {
+        eprint!("{}", tr!(Msg::CompressError, error));
+        process::exit(-1);
+    }
+
+    // the output file is only replaced once everything has been written, so it is never left half written
+    if let Err(error) = finish_output() {
+        eprint!("{}", tr!(Msg::OutputWriteError, output_path, error));
+        process::exit(-1);
+    }
+
+    if let Err(error) = pager::finish() {
+        eprint!("{}", tr!(Msg::PagerError, error));
+    }
+
+    if errors::aborted().is_some() {
+        process::exit(EXIT_ABORTED);
+    }
+
+    // searches exit like grep, so that scripts can tell whether anything matched
+    if search_status != 0 {
+        process::exit(search_status);
+    }
+
+    // a run that violates its policy fails, so that audits can be used in scripts
+    if violation_cnt > 0 {
+        process::exit(1);
     }
 }
@@ -0,0 +1,95 @@
+//! Detecting the MIME types of regular files from the magic bytes at their start (for `--mime` and the `mime` field of
+//! `--format` and `--printf`)
+//!
+//! Like file(1), a file is recognised by a signature at a fixed offset within its first few kilobytes rather than by
+//! its extension, so files without an extension (or with a misleading one) are classified by what they hold. Files
+//! without a known signature are `text/plain` if they are text and `application/octet-stream` if they are binary (see
+//! [crate::content]), while empty files are `inode/x-empty`. Only regular files are classified, and files whose
+//! contents can not be read (such as replayed ones) have no MIME type. Detection is only compiled in with the `mime`
+//! feature
+
+use std::path;
+
+#[cfg(feature = "mime")]
+use crate::content;
+use crate::record;
+
+/// Signature that files are recognised by, as the byte strings that must be found at the given offsets along with the
+/// MIME type of the files holding them
+#[cfg(feature = "mime")]
+type Signature = (&'static [(usize, &'static [u8])], &'static str);
+
+/// Signatures that files are recognised by (the first matching signature is used)
+#[cfg(feature = "mime")]
+const SIGNATURES: [Signature; 33] = [
+    (&[(0, b"\x89PNG\r\n\x1a\n")], "image/png"),
+    (&[(0, b"\xff\xd8\xff")], "image/jpeg"),
+    (&[(0, b"GIF87a")], "image/gif"),
+    (&[(0, b"GIF89a")], "image/gif"),
+    (&[(0, b"RIFF"), (8, b"WEBP")], "image/webp"),
+    (&[(0, b"II*\0")], "image/tiff"),
+    (&[(0, b"MM\0*")], "image/tiff"),
+    (&[(0, b"\0\0\x01\0")], "image/vnd.microsoft.icon"),
+    (&[(0, b"%PDF-")], "application/pdf"),
+    (&[(0, b"%!PS")], "application/postscript"),
+    (&[(0, b"PK\x03\x04")], "application/zip"),
+    (&[(0, b"PK\x05\x06")], "application/zip"),
+    (&[(0, b"\x1f\x8b")], "application/gzip"),
+    (&[(0, b"BZh")], "application/x-bzip2"),
+    (&[(0, b"\xfd7zXZ\0")], "application/x-xz"),
+    (&[(0, b"\x28\xb5\x2f\xfd")], "application/zstd"),
+    (&[(0, b"7z\xbc\xaf\x27\x1c")], "application/x-7z-compressed"),
+    (&[(257, b"ustar")], "application/x-tar"),
+    (&[(0, b"\x7fELF")], "application/x-executable"),
+    (&[(0, b"\0asm")], "application/wasm"),
+    (&[(0, b"\xca\xfe\xba\xbe")], "application/java-vm"),
+    (&[(0, b"SQLite format 3\0")], "application/vnd.sqlite3"),
+    (&[(0, b"PAR1")], "application/vnd.apache.parquet"),
+    (&[(0, b"ID3")], "audio/mpeg"),
+    (&[(0, b"OggS")], "audio/ogg"),
+    (&[(0, b"fLaC")], "audio/flac"),
+    (&[(0, b"RIFF"), (8, b"WAVE")], "audio/wav"),
+    (&[(0, b"RIFF"), (8, b"AVI ")], "video/x-msvideo"),
+    (&[(4, b"ftyp")], "video/mp4"),
+    (&[(0, b"\x1a\x45\xdf\xa3")], "video/x-matroska"),
+    (&[(0, b"wOFF")], "font/woff"),
+    (&[(0, b"wOF2")], "font/woff2"),
+    (&[(0, b"OTTO")], "font/otf"),
+];
+
+/// Returns the MIME type of a regular file ([None] if it is not a regular file, could not be read or detection was not
+/// compiled in)
+///
+/// The start of the file is read through [content::head], so with `--no-cache-pollution`, the pages it pulls into the
+/// cache are dropped again
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+#[cfg_attr(not(feature = "mime"), allow(unused_variables))]
+pub fn detect(p_path_os: &path::Path, p_metadata: &record::EntryMetadata) -> Option<&'static str> {
+    #[cfg(feature = "mime")]
+    {
+        let head = content::head(p_path_os, p_metadata)?;
+        if head.is_empty() {
+            return Some("inode/x-empty");
+        }
+
+        let signature = SIGNATURES.iter().find(|(parts, _)| {
+            return parts.iter().all(|(offset, bytes)| {
+                head.get(*offset..)
+                    .is_some_and(|rest| rest.starts_with(bytes))
+            });
+        });
+
+        return match signature {
+            Some((_, mime)) => Some(mime),
+            None if head.contains(&0) => Some("application/octet-stream"),
+            None => Some("text/plain"),
+        };
+    }
+
+    #[cfg(not(feature = "mime"))]
+    return None;
+}
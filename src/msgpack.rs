@@ -0,0 +1,310 @@
+//! Encoding and decoding the subset of MessagePack that snapshots are written in
+//!
+//! Only the types that snapshots use are supported (nil, booleans, integers, strings, binary data, arrays and maps),
+//! and values are always encoded in their smallest form, like the reference implementations do
+
+use std::io::{Read, Write};
+
+/// Value that can be encoded in MessagePack
+#[derive(Clone, PartialEq)]
+pub enum Value {
+    Nil,
+    Bool(bool),
+    Int(i64),
+    Str(String),
+    Bin(Vec<u8>),
+    Array(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+}
+
+impl Value {
+    /// Returns the string held by the value ([None] if it is not a string)
+    pub fn as_str(&self) -> Option<&str> {
+        return match self {
+            Value::Str(value) => Some(value),
+            _ => None,
+        };
+    }
+}
+
+/// Writes the marker of a value followed by its length, using the smallest of the given markers that fits the length
+///
+/// # Arguments
+///
+/// - `p_writer` - the writer
+/// - `p_len` - the length
+/// - `p_fix` - marker that the length is added to if it fits into `p_fix_max` ([None] if there is no such marker)
+/// - `p_fix_max` - largest length that fits into the fixed marker
+/// - `p_markers` - markers followed by a length of 1, 2 and 4 bytes ([None] if there is no such marker)
+fn write_len(
+    p_writer: &mut impl Write,
+    p_len: usize,
+    p_fix: Option<u8>,
+    p_fix_max: usize,
+    p_markers: [Option<u8>; 3],
+) -> std::io::Result<()> {
+    if let Some(fix) = p_fix.filter(|_| p_len <= p_fix_max) {
+        return p_writer.write_all(&[fix | p_len as u8]);
+    }
+
+    return match p_markers {
+        [Some(marker), _, _] if p_len <= u8::MAX as usize => {
+            p_writer.write_all(&[marker, p_len as u8])
+        }
+        [_, Some(marker), _] if p_len <= u16::MAX as usize => {
+            p_writer.write_all(&[marker])?;
+            p_writer.write_all(&(p_len as u16).to_be_bytes())
+        }
+        [_, _, Some(marker)] if p_len <= u32::MAX as usize => {
+            p_writer.write_all(&[marker])?;
+            p_writer.write_all(&(p_len as u32).to_be_bytes())
+        }
+        _ => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "value is too long for MessagePack",
+        )),
+    };
+}
+
+/// Writes the encoding of an integer
+///
+/// # Arguments
+///
+/// - `p_writer` - the writer
+/// - `p_value` - the integer
+fn write_int(p_writer: &mut impl Write, p_value: i64) -> std::io::Result<()> {
+    return match p_value {
+        // positive and negative fixints
+        -32..=0x7f => p_writer.write_all(&[p_value as u8]),
+        0x80..=0xff => p_writer.write_all(&[0xcc, p_value as u8]),
+        0x100..=0xffff => {
+            p_writer.write_all(&[0xcd])?;
+            p_writer.write_all(&(p_value as u16).to_be_bytes())
+        }
+        0x1_0000..=0xffff_ffff => {
+            p_writer.write_all(&[0xce])?;
+            p_writer.write_all(&(p_value as u32).to_be_bytes())
+        }
+        0x1_0000_0000.. => {
+            p_writer.write_all(&[0xcf])?;
+            p_writer.write_all(&(p_value as u64).to_be_bytes())
+        }
+        -0x80..=-33 => p_writer.write_all(&[0xd0, p_value as u8]),
+        -0x8000..=-0x81 => {
+            p_writer.write_all(&[0xd1])?;
+            p_writer.write_all(&(p_value as i16).to_be_bytes())
+        }
+        -0x8000_0000..=-0x8001 => {
+            p_writer.write_all(&[0xd2])?;
+            p_writer.write_all(&(p_value as i32).to_be_bytes())
+        }
+        _ => {
+            p_writer.write_all(&[0xd3])?;
+            p_writer.write_all(&p_value.to_be_bytes())
+        }
+    };
+}
+
+/// Writes the encoding of a value
+///
+/// # Arguments
+///
+/// - `p_writer` - the writer
+/// - `p_value` - the value
+pub fn write_value(p_writer: &mut impl Write, p_value: &Value) -> std::io::Result<()> {
+    match p_value {
+        Value::Nil => p_writer.write_all(&[0xc0])?,
+        Value::Bool(value) => p_writer.write_all(&[if *value { 0xc3 } else { 0xc2 }])?,
+        Value::Int(value) => write_int(p_writer, *value)?,
+        Value::Str(value) => {
+            write_len(
+                p_writer,
+                value.len(),
+                Some(0xa0),
+                31,
+                [Some(0xd9), Some(0xda), Some(0xdb)],
+            )?;
+            p_writer.write_all(value.as_bytes())?;
+        }
+        Value::Bin(value) => {
+            write_len(
+                p_writer,
+                value.len(),
+                None,
+                0,
+                [Some(0xc4), Some(0xc5), Some(0xc6)],
+            )?;
+            p_writer.write_all(value)?;
+        }
+        Value::Array(values) => {
+            write_len(
+                p_writer,
+                values.len(),
+                Some(0x90),
+                15,
+                [None, Some(0xdc), Some(0xdd)],
+            )?;
+            for value in values {
+                write_value(p_writer, value)?;
+            }
+        }
+        Value::Map(pairs) => {
+            write_len(
+                p_writer,
+                pairs.len(),
+                Some(0x80),
+                15,
+                [None, Some(0xde), Some(0xdf)],
+            )?;
+            for (key, value) in pairs {
+                write_value(p_writer, key)?;
+                write_value(p_writer, value)?;
+            }
+        }
+    }
+
+    return Ok(());
+}
+
+/// Returns the given number of bytes read from a reader
+///
+/// # Arguments
+///
+/// - `p_reader` - the reader
+/// - `p_len` - number of bytes to read
+fn read_bytes(p_reader: &mut impl Read, p_len: usize) -> std::io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    p_reader.take(p_len as u64).read_to_end(&mut bytes)?;
+
+    if bytes.len() < p_len {
+        return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+    }
+    return Ok(bytes);
+}
+
+/// Returns an unsigned big endian integer of the given number of bytes read from a reader
+///
+/// # Arguments
+///
+/// - `p_reader` - the reader
+/// - `p_len` - number of bytes of the integer (at most 8)
+fn read_uint(p_reader: &mut impl Read, p_len: usize) -> std::io::Result<u64> {
+    let bytes = read_bytes(p_reader, p_len)?;
+
+    return Ok(bytes
+        .iter()
+        .fold(0, |value, byte| (value << 8) | *byte as u64));
+}
+
+/// Returns the next value read from a reader ([None] if the reader has no more data)
+///
+/// # Arguments
+///
+/// - `p_reader` - the reader
+pub fn read_value(p_reader: &mut impl Read) -> std::io::Result<Option<Value>> {
+    let mut marker = [0u8];
+    if p_reader.read(&mut marker)? == 0 {
+        return Ok(None);
+    }
+
+    let value = match marker[0] {
+        marker @ 0x00..=0x7f => Value::Int(marker as i64),
+        marker @ 0xe0..=0xff => Value::Int(marker as i8 as i64),
+        marker @ 0x80..=0x8f => read_map(p_reader, (marker & 0x0f) as usize)?,
+        marker @ 0x90..=0x9f => read_array(p_reader, (marker & 0x0f) as usize)?,
+        marker @ 0xa0..=0xbf => read_str(p_reader, (marker & 0x1f) as usize)?,
+        0xc0 => Value::Nil,
+        0xc2 => Value::Bool(false),
+        0xc3 => Value::Bool(true),
+        marker @ 0xc4..=0xc6 => {
+            let len = read_uint(p_reader, 1 << (marker - 0xc4))? as usize;
+            Value::Bin(read_bytes(p_reader, len)?)
+        }
+        marker @ 0xcc..=0xcf => {
+            let value = read_uint(p_reader, 1 << (marker - 0xcc))?;
+            Value::Int(i64::try_from(value).map_err(std::io::Error::other)?)
+        }
+        marker @ 0xd0..=0xd3 => {
+            let len = 1 << (marker - 0xd0);
+            let value = read_uint(p_reader, len)?;
+            // sign extend the value from the number of bytes it was read from
+            let shift = 64 - 8 * len;
+            Value::Int(((value << shift) as i64) >> shift)
+        }
+        marker @ 0xd9..=0xdb => {
+            let len = read_uint(p_reader, 1 << (marker - 0xd9))? as usize;
+            read_str(p_reader, len)?
+        }
+        marker @ 0xdc..=0xdd => {
+            let len = read_uint(p_reader, 2 << (marker - 0xdc))? as usize;
+            read_array(p_reader, len)?
+        }
+        marker @ 0xde..=0xdf => {
+            let len = read_uint(p_reader, 2 << (marker - 0xde))? as usize;
+            read_map(p_reader, len)?
+        }
+        marker => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("unsupported MessagePack type 0x{:02x}", marker),
+            ));
+        }
+    };
+
+    return Ok(Some(value));
+}
+
+/// Returns the next value read from a reader, which must not be at its end
+///
+/// # Arguments
+///
+/// - `p_reader` - the reader
+fn read_nested(p_reader: &mut impl Read) -> std::io::Result<Value> {
+    return read_value(p_reader)?
+        .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+}
+
+/// Returns a string of the given length read from a reader
+///
+/// # Arguments
+///
+/// - `p_reader` - the reader
+/// - `p_len` - length of the string in bytes
+fn read_str(p_reader: &mut impl Read, p_len: usize) -> std::io::Result<Value> {
+    let bytes = read_bytes(p_reader, p_len)?;
+
+    return Ok(Value::Str(String::from_utf8(bytes).map_err(|error| {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, error)
+    })?));
+}
+
+/// Returns an array of the given number of values read from a reader
+///
+/// # Arguments
+///
+/// - `p_reader` - the reader
+/// - `p_len` - number of values
+fn read_array(p_reader: &mut impl Read, p_len: usize) -> std::io::Result<Value> {
+    let mut values = Vec::with_capacity(p_len.min(1024));
+    for _ in 0..p_len {
+        values.push(read_nested(p_reader)?);
+    }
+
+    return Ok(Value::Array(values));
+}
+
+/// Returns a map of the given number of pairs read from a reader
+///
+/// # Arguments
+///
+/// - `p_reader` - the reader
+/// - `p_len` - number of pairs
+fn read_map(p_reader: &mut impl Read, p_len: usize) -> std::io::Result<Value> {
+    let mut pairs = Vec::with_capacity(p_len.min(1024));
+    for _ in 0..p_len {
+        let key = read_nested(p_reader)?;
+        pairs.push((key, read_nested(p_reader)?));
+    }
+
+    return Ok(Value::Map(pairs));
+}
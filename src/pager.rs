@@ -0,0 +1,142 @@
+//! Passing the output through a pager (for `--paging`)
+//!
+//! With `--paging auto`, the output of a scan that is written to a terminal is passed through the pager in `PAGER` (or
+//! `less -R` if it is not set), so that a long listing can be scrolled through instead of scrolling away. Everything
+//! printed to stdout is written into a pipe that the pager reads from, the same way as the output is compressed. Unless
+//! `LESS` is set, less is told to quit at once if the output fits on one screen, so that short listings are printed as
+//! usual. `--paging always` also pages output that is not written to a terminal, and `--paging never` (the default)
+//! never pages it
+
+use std::io::{self, IsTerminal};
+use std::process;
+use std::sync;
+
+/// Whether to page the output ([None] to page it only if it is written to a terminal), set with `--paging`
+static MODE: sync::OnceLock<Option<bool>> = sync::OnceLock::new();
+
+/// Pager that the output is passed through ([None] if the output is not paged)
+static PAGER: sync::Mutex<Option<process::Child>> = sync::Mutex::new(None);
+
+/// Pager that is used if `PAGER` is not set
+const DEFAULT_PAGER: &str = "less -R";
+
+/// Options given to less through `LESS` if it is not set (quit if the output fits on one screen, print colors as they
+/// are and do not clear the screen when quitting)
+const DEFAULT_LESS: &str = "FRX";
+
+/// Sets when the output is paged, from the value given to `--paging`
+///
+/// Returns `Err` if the value is not one of `auto`, `always` and `never`
+///
+/// # Arguments
+///
+/// - `p_mode` - the value
+pub fn set_mode(p_mode: &str) -> Result<(), ()> {
+    let mode = match p_mode {
+        "auto" => None,
+        "always" => Some(true),
+        "never" => Some(false),
+        _ => return Err(()),
+    };
+
+    let _ = MODE.set(mode);
+    return Ok(());
+}
+
+/// Returns whether the output should be paged
+fn enabled() -> bool {
+    return match MODE.get() {
+        Some(Some(mode)) => *mode,
+        Some(None) => io::stdout().is_terminal(),
+        None => false,
+    };
+}
+
+#[cfg(target_family = "unix")]
+/// Starts the pager and redirects stdout into it, so that everything printed from then on is paged (has no effect if
+/// the output should not be paged, or if `PAGER` is empty or `cat`)
+pub fn start() -> io::Result<()> {
+    use std::env;
+    use std::io::Write;
+    use std::os::unix::io::AsRawFd;
+
+    if !enabled() {
+        return Ok(());
+    }
+
+    let pager = env::var("PAGER").unwrap_or(DEFAULT_PAGER.to_owned());
+    if pager.trim().is_empty() || pager.trim() == "cat" {
+        return Ok(());
+    }
+
+    // the pager is run by the shell, so that it can be given with its own arguments
+    let mut command = process::Command::new("sh");
+    command.arg("-c").arg(&pager).stdin(process::Stdio::piped());
+    if env::var_os("LESS").is_none() {
+        command.env("LESS", DEFAULT_LESS);
+    }
+    let mut child = command.spawn()?;
+
+    io::stdout().flush()?;
+
+    let Some(stdin) = child.stdin.take() else {
+        return Err(io::Error::other("the pager has no input"));
+    };
+    // the scan stops quietly once the pager is quit, since SIGPIPE is left to end it (see main)
+    unsafe {
+        if libc::dup2(stdin.as_raw_fd(), libc::STDOUT_FILENO) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+
+    if let Ok(mut handle) = PAGER.lock() {
+        *handle = Some(child);
+    }
+
+    return Ok(());
+}
+
+#[cfg(not(target_family = "unix"))]
+/// Starts the pager (the output is never paged on this platform)
+pub fn start() -> io::Result<()> {
+    if !enabled() {
+        return Ok(());
+    }
+
+    return Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "paging the output is only supported on unix",
+    ));
+}
+
+#[cfg(target_family = "unix")]
+/// Waits for the pager to be quit (has no effect if the output is not paged)
+///
+/// Nothing printed to stdout afterwards is written anywhere
+pub fn finish() -> io::Result<()> {
+    use std::io::Write;
+
+    let Some(mut pager) = PAGER.lock().ok().and_then(|mut handle| handle.take()) else {
+        return Ok(());
+    };
+
+    io::stdout().flush()?;
+
+    // stdout is the only end of the pipe that is left, so replacing it lets the pager reach the end of the output
+    unsafe {
+        let null_fd = libc::open(c"/dev/null".as_ptr(), libc::O_WRONLY);
+        if null_fd < 0 || libc::dup2(null_fd, libc::STDOUT_FILENO) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        libc::close(null_fd);
+    }
+
+    pager.wait()?;
+    return Ok(());
+}
+
+#[cfg(not(target_family = "unix"))]
+/// Waits for the pager to be quit (the output is never paged on this platform)
+pub fn finish() -> io::Result<()> {
+    return Ok(());
+}
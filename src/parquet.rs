@@ -0,0 +1,571 @@
+//! Writing the entries of a scan into a Parquet file (for `--export-parquet`)
+//!
+//! The entries are selected like with `--format`, and each one is written as a row with its path, size, time of the
+//! last modification, type and owner. No Parquet library is needed, since uncompressed pages with plainly encoded
+//! values are simple to write directly in the Parquet format (the metadata is encoded with the compact protocol of
+//! Thrift). The rows are buffered and written as a row group once [ROW_GROUP_ROWS] of them have been read, so the
+//! memory held does not grow with the number of entries, and the metadata of all the row groups is written once the
+//! scan is complete
+
+use std::fs;
+use std::io::Write;
+use std::path;
+use std::sync;
+
+use crate::i18n::{tr, Msg};
+use crate::{export, int_to_formatted_slice, ls, record};
+
+/// Bytes that a Parquet file starts and ends with
+const MAGIC: &[u8; 4] = b"PAR1";
+
+/// Number of rows that are buffered before they are written as a row group
+const ROW_GROUP_ROWS: usize = 1 << 17;
+
+/// Name of the program that wrote the file (stored in its metadata)
+const CREATED_BY: &str = concat!("fss version ", env!("CARGO_PKG_VERSION"));
+
+/// Physical type of a column (as numbered by Parquet)
+#[derive(Clone, Copy)]
+enum PhysicalType {
+    Int64 = 2,
+    ByteArray = 6,
+}
+
+/// Column of the file
+struct Column {
+    name: &'static str,
+    physical: PhysicalType,
+    /// Whether the column can hold nulls
+    optional: bool,
+}
+
+/// Columns of the file, in the order they are stored
+const COLUMNS: [Column; 5] = [
+    Column {
+        name: "path",
+        physical: PhysicalType::ByteArray,
+        optional: false,
+    },
+    Column {
+        name: "size",
+        physical: PhysicalType::Int64,
+        optional: true,
+    },
+    Column {
+        name: "mtime",
+        physical: PhysicalType::Int64,
+        optional: true,
+    },
+    Column {
+        name: "type",
+        physical: PhysicalType::ByteArray,
+        optional: false,
+    },
+    Column {
+        name: "owner",
+        physical: PhysicalType::ByteArray,
+        optional: true,
+    },
+];
+
+/// Parquet encoding of plainly encoded values
+const ENCODING_PLAIN: i32 = 0;
+
+/// Parquet encoding of run lengths (used for the definition levels)
+const ENCODING_RLE: i32 = 3;
+
+/// Types of the fields of the compact protocol of Thrift
+const THRIFT_TRUE: u8 = 1;
+const THRIFT_FALSE: u8 = 2;
+const THRIFT_I32: u8 = 5;
+const THRIFT_I64: u8 = 6;
+const THRIFT_BINARY: u8 = 8;
+const THRIFT_LIST: u8 = 9;
+const THRIFT_STRUCT: u8 = 12;
+
+/// Path of the file to write the entries into ([None] if `--export-parquet` was not given)
+static PARQUET_PATH: sync::OnceLock<path::PathBuf> = sync::OnceLock::new();
+
+/// Export that the entries are being written into (set by [begin])
+static EXPORT: sync::Mutex<Option<ParquetExport>> = sync::Mutex::new(None);
+
+/// Encoder of a structure in the compact protocol of Thrift
+struct Thrift {
+    buff: Vec<u8>,
+    /// Id of the field written last in each structure that is being written (fields are encoded relative to it)
+    last_ids: Vec<i16>,
+}
+
+impl Thrift {
+    /// Returns an encoder of a structure
+    fn new() -> Thrift {
+        return Thrift {
+            buff: Vec::new(),
+            last_ids: vec![0],
+        };
+    }
+
+    /// Appends an unsigned variable-length integer (little endian groups of 7 bits, with the high bit set in all but
+    /// the last byte)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_value` - the integer
+    fn varint(&mut self, mut p_value: u64) {
+        while p_value >= 0x80 {
+            self.buff.push((p_value & 0x7f) as u8 | 0x80);
+            p_value >>= 7;
+        }
+        self.buff.push(p_value as u8);
+    }
+
+    /// Appends a signed integer (zigzag encoded, so that small negative integers are short too)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_value` - the integer
+    fn zigzag(&mut self, p_value: i64) {
+        self.varint(((p_value << 1) ^ (p_value >> 63)) as u64);
+    }
+
+    /// Appends the header of a field of the structure being written
+    ///
+    /// # Arguments
+    ///
+    /// - `p_id` - id of the field (larger than that of the field written before it)
+    /// - `p_type` - type of the field
+    fn field(&mut self, p_id: i16, p_type: u8) {
+        let delta = p_id - self.last_ids.last().unwrap();
+
+        if (1..=15).contains(&delta) {
+            self.buff.push((delta as u8) << 4 | p_type);
+        } else {
+            self.buff.push(p_type);
+            self.zigzag(p_id as i64);
+        }
+        *self.last_ids.last_mut().unwrap() = p_id;
+    }
+
+    /// Appends a boolean field
+    fn bool(&mut self, p_id: i16, p_value: bool) {
+        self.field(p_id, if p_value { THRIFT_TRUE } else { THRIFT_FALSE });
+    }
+
+    /// Appends a 32 bit integer field
+    fn i32(&mut self, p_id: i16, p_value: i32) {
+        self.field(p_id, THRIFT_I32);
+        self.zigzag(p_value as i64);
+    }
+
+    /// Appends a 64 bit integer field
+    fn i64(&mut self, p_id: i16, p_value: i64) {
+        self.field(p_id, THRIFT_I64);
+        self.zigzag(p_value);
+    }
+
+    /// Appends a string field
+    fn string(&mut self, p_id: i16, p_value: &str) {
+        self.field(p_id, THRIFT_BINARY);
+        self.varint(p_value.len() as u64);
+        self.buff.extend_from_slice(p_value.as_bytes());
+    }
+
+    /// Appends the header of a list field, which needs to be followed by its elements
+    ///
+    /// # Arguments
+    ///
+    /// - `p_id` - id of the field
+    /// - `p_type` - type of the elements
+    /// - `p_len` - number of elements
+    fn list(&mut self, p_id: i16, p_type: u8, p_len: usize) {
+        self.field(p_id, THRIFT_LIST);
+
+        if p_len < 15 {
+            self.buff.push((p_len as u8) << 4 | p_type);
+        } else {
+            self.buff.push(0xf0 | p_type);
+            self.varint(p_len as u64);
+        }
+    }
+
+    /// Starts a structure field, whose fields are written until [Thrift::end] is called
+    fn begin(&mut self, p_id: i16) {
+        self.field(p_id, THRIFT_STRUCT);
+        self.last_ids.push(0);
+    }
+
+    /// Starts a structure that is an element of a list
+    fn begin_element(&mut self) {
+        self.last_ids.push(0);
+    }
+
+    /// Ends the structure being written
+    fn end(&mut self) {
+        self.buff.push(0);
+        self.last_ids.pop();
+    }
+
+    /// Returns the encoding of the outermost structure
+    fn finish(mut self) -> Vec<u8> {
+        self.end();
+        return self.buff;
+    }
+}
+
+/// Values of a column of the row group being filled
+#[derive(Default)]
+struct ColumnBuffer {
+    /// Whether the value of each row is defined (only written for optional columns)
+    defined: Vec<bool>,
+    /// Plainly encoded values that are defined
+    values: Vec<u8>,
+}
+
+impl ColumnBuffer {
+    /// Appends a 64 bit integer (or a null)
+    fn push_int(&mut self, p_value: Option<i64>) {
+        self.defined.push(p_value.is_some());
+        if let Some(value) = p_value {
+            self.values.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    /// Appends a string (or a null)
+    fn push_str(&mut self, p_value: Option<&str>) {
+        self.defined.push(p_value.is_some());
+        if let Some(value) = p_value {
+            self.values
+                .extend_from_slice(&(value.len() as u32).to_le_bytes());
+            self.values.extend_from_slice(value.as_bytes());
+        }
+    }
+
+    /// Returns the data of a page holding the values of the column (the definition levels of optional columns, run
+    /// length encoded, followed by the values)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_optional` - whether the column can hold nulls
+    fn page_data(&self, p_optional: bool) -> Vec<u8> {
+        let mut data = Vec::with_capacity(self.values.len() + 16);
+
+        if p_optional {
+            // each run of equal levels is the length shifted left by a bit, followed by the level in a byte
+            let mut levels = Thrift::new();
+            for run in self.defined.chunk_by(|a, b| a == b) {
+                levels.varint((run.len() as u64) << 1);
+                levels.buff.push(run[0] as u8);
+            }
+            data.extend_from_slice(&(levels.buff.len() as u32).to_le_bytes());
+            data.extend_from_slice(&levels.buff);
+        }
+
+        data.extend_from_slice(&self.values);
+        return data;
+    }
+}
+
+/// Position and size of a column chunk that was written
+struct ChunkInfo {
+    /// Offset of the page within the file
+    offset: u64,
+    /// Size of the page along with its header
+    size: u64,
+}
+
+/// State of an export into a Parquet file
+struct ParquetExport {
+    file: std::io::BufWriter<fs::File>,
+    /// Number of bytes written so far
+    offset: u64,
+    /// Values of each column of the row group being filled
+    columns: [ColumnBuffer; 5],
+    /// Number of rows in the row group being filled
+    row_cnt: usize,
+    /// Number of rows and the column chunks of each row group written so far
+    row_groups: Vec<(usize, Vec<ChunkInfo>)>,
+    /// First error that occurred while writing (nothing more is written after an error)
+    error: Option<std::io::Error>,
+}
+
+impl ParquetExport {
+    /// Writes bytes after the ones written so far
+    fn write(&mut self, p_bytes: &[u8]) -> std::io::Result<()> {
+        self.file.write_all(p_bytes)?;
+        self.offset += p_bytes.len() as u64;
+
+        return Ok(());
+    }
+
+    /// Writes the buffered rows as a row group, with a single data page per column
+    fn flush_row_group(&mut self) -> std::io::Result<()> {
+        let columns = std::mem::take(&mut self.columns);
+        let mut chunks = Vec::with_capacity(COLUMNS.len());
+
+        for (column, buffer) in COLUMNS.iter().zip(columns.iter()) {
+            let data = buffer.page_data(column.optional);
+
+            let mut header = Thrift::new();
+            // a data page, uncompressed
+            header.i32(1, 0);
+            header.i32(2, data.len() as i32);
+            header.i32(3, data.len() as i32);
+            header.begin(5);
+            header.i32(1, self.row_cnt as i32);
+            header.i32(2, ENCODING_PLAIN);
+            header.i32(3, ENCODING_RLE);
+            header.i32(4, ENCODING_RLE);
+            header.end();
+            let header = header.finish();
+
+            let offset = self.offset;
+            self.write(&header)?;
+            self.write(&data)?;
+            chunks.push(ChunkInfo {
+                offset,
+                size: self.offset - offset,
+            });
+        }
+
+        self.row_groups.push((self.row_cnt, chunks));
+        self.row_cnt = 0;
+
+        return Ok(());
+    }
+
+    /// Appends a row
+    ///
+    /// # Arguments
+    ///
+    /// - `p_path` - path of the entry
+    /// - `p_size` - size of the entry ([None] for directories)
+    /// - `p_mtime` - time of the last modification in microseconds since the UNIX epoch ([None] if not available)
+    /// - `p_type` - type of the entry
+    /// - `p_owner` - owner of the entry ([None] if not available)
+    fn append(
+        &mut self,
+        p_path: &str,
+        p_size: Option<i64>,
+        p_mtime: Option<i64>,
+        p_type: &str,
+        p_owner: Option<&str>,
+    ) -> std::io::Result<()> {
+        self.columns[0].push_str(Some(p_path));
+        self.columns[1].push_int(p_size);
+        self.columns[2].push_int(p_mtime);
+        self.columns[3].push_str(Some(p_type));
+        self.columns[4].push_str(p_owner);
+        self.row_cnt += 1;
+
+        if self.row_cnt == ROW_GROUP_ROWS {
+            self.flush_row_group()?;
+        }
+
+        return Ok(());
+    }
+
+    /// Writes the remaining rows and the metadata of the file (its schema and the positions of the row groups), and
+    /// flushes the file
+    ///
+    /// Returns the number of rows written
+    fn finish(mut self) -> std::io::Result<usize> {
+        if self.row_cnt > 0 {
+            self.flush_row_group()?;
+        }
+        let row_cnt: usize = self.row_groups.iter().map(|(row_cnt, _)| row_cnt).sum();
+
+        let mut metadata = Thrift::new();
+        metadata.i32(1, 1);
+
+        metadata.list(2, THRIFT_STRUCT, COLUMNS.len() + 1);
+        metadata.begin_element();
+        metadata.string(4, "schema");
+        metadata.i32(5, COLUMNS.len() as i32);
+        metadata.end();
+        for column in &COLUMNS {
+            metadata.begin_element();
+            metadata.i32(1, column.physical as i32);
+            metadata.i32(3, column.optional as i32);
+            metadata.string(4, column.name);
+            match column.name {
+                "mtime" => {
+                    // converted type TIMESTAMP_MICROS, and the logical type of a UTC timestamp in microseconds
+                    metadata.i32(6, 10);
+                    metadata.begin(10);
+                    metadata.begin(8);
+                    metadata.bool(1, true);
+                    metadata.begin(2);
+                    metadata.begin(2);
+                    metadata.end();
+                    metadata.end();
+                    metadata.end();
+                    metadata.end();
+                }
+                _ if matches!(column.physical, PhysicalType::ByteArray) => {
+                    // converted type UTF8, and the logical type of a string
+                    metadata.i32(6, 0);
+                    metadata.begin(10);
+                    metadata.begin(1);
+                    metadata.end();
+                    metadata.end();
+                }
+                _ => {}
+            }
+            metadata.end();
+        }
+
+        metadata.i64(3, row_cnt as i64);
+
+        metadata.list(4, THRIFT_STRUCT, self.row_groups.len());
+        for (group_row_cnt, chunks) in &self.row_groups {
+            metadata.begin_element();
+
+            metadata.list(1, THRIFT_STRUCT, chunks.len());
+            for (column, chunk) in COLUMNS.iter().zip(chunks) {
+                metadata.begin_element();
+                metadata.i64(2, chunk.offset as i64);
+                metadata.begin(3);
+                metadata.i32(1, column.physical as i32);
+                metadata.list(2, THRIFT_I32, 2);
+                metadata.zigzag(ENCODING_PLAIN as i64);
+                metadata.zigzag(ENCODING_RLE as i64);
+                metadata.list(3, THRIFT_BINARY, 1);
+                metadata.varint(column.name.len() as u64);
+                metadata.buff.extend_from_slice(column.name.as_bytes());
+                metadata.i32(4, 0);
+                metadata.i64(5, *group_row_cnt as i64);
+                metadata.i64(6, chunk.size as i64);
+                metadata.i64(7, chunk.size as i64);
+                metadata.i64(9, chunk.offset as i64);
+                metadata.end();
+                metadata.end();
+            }
+
+            let group_size: u64 = chunks.iter().map(|chunk| chunk.size).sum();
+            metadata.i64(2, group_size as i64);
+            metadata.i64(3, *group_row_cnt as i64);
+            metadata.end();
+        }
+
+        metadata.string(6, CREATED_BY);
+        let metadata = metadata.finish();
+
+        self.write(&metadata)?;
+        self.write(&(metadata.len() as u32).to_le_bytes())?;
+        self.write(MAGIC)?;
+        self.file.flush()?;
+
+        return Ok(row_cnt);
+    }
+}
+
+/// Sets the path of the file to write the entries into (has no effect if it is already set)
+///
+/// # Arguments
+///
+/// - `p_parquet_path` - path of the file
+pub fn set_parquet_path(p_parquet_path: &str) {
+    let _ = PARQUET_PATH.set(path::PathBuf::from(p_parquet_path));
+}
+
+/// Returns the path of the file to write the entries into ([None] if `--export-parquet` was not given)
+pub fn get_parquet_path() -> Option<&'static path::Path> {
+    return PARQUET_PATH.get().map(path::PathBuf::as_path);
+}
+
+/// Returns the time of the last modification of an entry in microseconds since the UNIX epoch ([None] if it is not
+/// available)
+///
+/// # Arguments
+///
+/// - `p_metadata` - metadata of the entry
+fn modified_micros(p_metadata: &record::EntryMetadata) -> Option<i64> {
+    let modified = p_metadata.modified().ok()?;
+
+    return Some(chrono::DateTime::<chrono::Utc>::from(modified).timestamp_micros());
+}
+
+/// Creates the file (has no effect if `--export-parquet` was not given)
+///
+/// Returns the error if the file could not be created
+pub fn begin() -> std::io::Result<()> {
+    let Some(parquet_path) = get_parquet_path() else {
+        return Ok(());
+    };
+
+    let mut export = ParquetExport {
+        file: std::io::BufWriter::new(fs::File::create(parquet_path)?),
+        offset: 0,
+        columns: Default::default(),
+        row_cnt: 0,
+        row_groups: Vec::new(),
+        error: None,
+    };
+    export.write(MAGIC)?;
+
+    *EXPORT.lock().unwrap() = Some(export);
+
+    return Ok(());
+}
+
+/// Writes an entry into the file
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+pub fn insert_entry(p_path_os: &path::Path, p_metadata: &record::EntryMetadata) {
+    let mut export = EXPORT.lock().unwrap();
+    let Some(export) = export.as_mut() else {
+        return;
+    };
+    if export.error.is_some() {
+        return;
+    }
+
+    let size = if p_metadata.is_dir() {
+        None
+    } else {
+        Some(p_metadata.len() as i64)
+    };
+
+    let result = export.append(
+        &p_path_os.to_string_lossy(),
+        size,
+        modified_micros(p_metadata),
+        export::type_name(p_metadata),
+        ls::owner_name(p_metadata).as_deref(),
+    );
+
+    if let Err(error) = result {
+        export.error = Some(error);
+    }
+}
+
+/// Completes the file and prints the number of entries written into it (has no effect if `--export-parquet` was not
+/// given)
+pub fn finish() {
+    let Some(export) = EXPORT.lock().unwrap().take() else {
+        return;
+    };
+    let parquet_path = get_parquet_path()
+        .unwrap_or(path::Path::new(""))
+        .to_string_lossy();
+
+    let result = match export.error {
+        Some(error) => Err(error),
+        None => export.finish(),
+    };
+
+    match result {
+        Ok(row_cnt) => print!(
+            "{}",
+            tr!(
+                Msg::ParquetSummary,
+                parquet_path,
+                int_to_formatted_slice(row_cnt as u64)
+            )
+        ),
+        Err(error) => print!("{}", tr!(Msg::ParquetWriteError, parquet_path, error)),
+    }
+}
@@ -0,0 +1,636 @@
+//! Reading scan policies and auditing directories against them (for `--policy`)
+//!
+//! A policy is a TOML document that describes a whole scan, so that it can be checked into the root of the scanned
+//! tree and re-run reproducibly -
+//!
+//! ```toml
+//! path = "."                  # directory to scan (relative to the policy, which is also the default)
+//!
+//! [options]                   # options that select the entries, without the dashes (true for flags, arrays to repeat)
+//! recursive = true
+//! files = true
+//!
+//! [excludes]                  # patterns with the syntax of .fssignore, relative to the scanned directory
+//! patterns = ["scratch/", "*.tmp"]
+//!
+//! [budgets]                   # maximum cumulative sizes of directories (relative to the scanned directory)
+//! "." = "500G"
+//!
+//! [retention]                 # maximum ages of the files in directories (relative to the scanned directory)
+//! "logs" = "90d"
+//!
+//! [output]                    # file that everything printed by the scan is written into (relative to the policy)
+//! file = "audit.txt"
+//! ```
+//!
+//! Only the subset of TOML needed for this is supported - tables, bare and quoted keys, strings, integers, booleans
+//! and arrays (which may span multiple lines)
+//!
+//! Policies are meant to be shared along with the tree, so running one must not do anything beyond scanning it. Only
+//! the options in [POLICY_OPTIONS] can be set, which excludes the hooks and watch mode (which run commands) and every
+//! option that writes a file (other than the one of the `[output]` table)
+
+use std::fs;
+use std::path;
+use std::time;
+
+use crate::i18n::{tr, Msg};
+use crate::record;
+use crate::{
+    calc_dir_size, format_age, format_size, get_option, int_to_formatted_slice, parse_age,
+    parse_size,
+};
+use crate::{print_file_age, PrgOptions};
+
+/// Long options (without the dashes) that a policy can set, which only select and filter the scanned entries and
+/// what is calculated for them
+const POLICY_OPTIONS: [&str; 46] = [
+    "recursive",
+    "min-depth",
+    "follow",
+    "max-entries",
+    "timeout",
+    "files",
+    "symlinks",
+    "special",
+    "ext",
+    "type",
+    "min-size",
+    "max-size",
+    "empty-files",
+    "broken-symlinks",
+    "only-text",
+    "only-binary",
+    "mime",
+    "all",
+    "no-hidden",
+    "exclude",
+    "prune-common",
+    "prune-dir",
+    "respect-gitignore",
+    "no-ignore-dot",
+    "skip-fs",
+    "no-skip-fs",
+    "newer-than",
+    "older-than",
+    "accessed-within",
+    "status-changed-within",
+    "owner",
+    "group",
+    "perm",
+    "dir-size",
+    "dirs-only",
+    "search",
+    "search-noext",
+    "contains",
+    "glob",
+    "fuzzy",
+    "ignore-case",
+    "invert-match",
+    "full-path",
+    "max-results",
+    "show-err",
+    "on-error",
+];
+
+/// Value of a key in a policy
+enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    Array(Vec<Value>),
+}
+
+/// Enumerates the errors that can occur while reading a policy
+pub enum PolicyError {
+    /// The policy could not be read
+    Read(std::io::Error),
+    /// A line (numbered from 1) could not be parsed
+    Syntax(usize),
+    /// A table or key that is not known (along with the table it lies in, empty for the top level)
+    UnknownKey(String, String),
+    /// A key whose value is of the wrong type or could not be parsed (along with the table it lies in)
+    InvalidValue(String, String),
+    /// An option that policies can not set (such as the hooks of watch mode, or an option that writes a file)
+    OptionNotAllowed(String),
+}
+
+impl PolicyError {
+    /// Prints the error (translated to the language of the messages)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_path` - path of the policy
+    pub fn print(&self, p_path: &str) {
+        match self {
+            PolicyError::Read(error) => print!("{}", tr!(Msg::PolicyReadError, p_path, error)),
+            PolicyError::Syntax(line) => print!("{}", tr!(Msg::PolicySyntaxError, line, p_path)),
+            PolicyError::UnknownKey(table, key) => {
+                print!(
+                    "{}",
+                    tr!(Msg::PolicyUnknownKey, qualified_key(table, key), p_path)
+                );
+            }
+            PolicyError::InvalidValue(table, key) => {
+                print!(
+                    "{}",
+                    tr!(Msg::PolicyInvalidValue, qualified_key(table, key), p_path)
+                );
+            }
+            PolicyError::OptionNotAllowed(key) => {
+                print!("{}", tr!(Msg::PolicyOptionNotAllowed, key, p_path));
+            }
+        }
+    }
+}
+
+/// Scan described by a policy
+pub struct Policy {
+    /// Arguments that the path and options of the policy expand into (in the order they are listed)
+    pub args: Vec<String>,
+    /// Patterns of the entries to exclude from the scan
+    pub excludes: Vec<String>,
+    /// Maximum cumulative size of each directory (relative to the scanned directory)
+    pub budgets: Vec<(String, u64)>,
+    /// Maximum age of the files in each directory (relative to the scanned directory)
+    pub retention: Vec<(String, time::Duration)>,
+    /// File that the output is written into ([None] to write it to stdout)
+    pub output: Option<path::PathBuf>,
+}
+
+/// Returns a key along with the table it lies in (such as `budgets."home"`), as it would be written in TOML
+///
+/// # Arguments
+///
+/// - `p_table` - name of the table (empty for the top level)
+/// - `p_key` - the key
+fn qualified_key(p_table: &str, p_key: &str) -> String {
+    let bare = !p_key.is_empty()
+        && p_key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    let key = if bare {
+        p_key.to_owned()
+    } else {
+        format!("\"{}\"", p_key)
+    };
+
+    if p_table.is_empty() {
+        return key;
+    }
+    return format!("{}.{}", p_table, key);
+}
+
+/// Cursor over the text of a single key/value pair (or table header) of a policy
+struct Cursor<'a> {
+    /// Characters that are left
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+/// Result of parsing a part of a line ([None] if the text ends before the part is complete, so that the parse can be
+/// retried after appending the next line, and `Err` if it is malformed)
+type Parsed<T> = Result<Option<T>, ()>;
+
+impl Cursor<'_> {
+    /// Skips whitespace, and comments if they are allowed (within arrays, comments can lie between values)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_comments` - whether to skip comments and newlines too
+    fn skip_space(&mut self, p_comments: bool) {
+        while let Some(&c) = self.chars.peek() {
+            if c == ' ' || c == '\t' || (p_comments && (c == '\n' || c == '\r')) {
+                self.chars.next();
+            } else if p_comments && c == '#' {
+                while self.chars.next_if(|&c| c != '\n').is_some() {}
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Returns whether only whitespace and a comment are left
+    fn at_end(&mut self) -> bool {
+        self.skip_space(false);
+        return matches!(self.chars.peek(), None | Some('#'));
+    }
+
+    /// Parses a quoted string (the opening quote is the next character)
+    fn parse_string(&mut self) -> Parsed<String> {
+        let quote = self.chars.next().ok_or(())?;
+        let mut string = String::new();
+
+        loop {
+            let Some(c) = self.chars.next() else {
+                return Err(());
+            };
+
+            match c {
+                '\n' => return Err(()),
+                c if c == quote => return Ok(Some(string)),
+                // only strings in double quotes have escape sequences
+                '\\' if quote == '"' => {
+                    let escaped = match self.chars.next().ok_or(())? {
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        '"' => '"',
+                        '\\' => '\\',
+                        'u' => {
+                            let digits: String = (0..4).filter_map(|_| self.chars.next()).collect();
+                            u32::from_str_radix(&digits, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or(())?
+                        }
+                        _ => return Err(()),
+                    };
+                    string.push(escaped);
+                }
+                c => string.push(c),
+            }
+        }
+    }
+
+    /// Parses a key (bare or quoted)
+    fn parse_key(&mut self) -> Parsed<String> {
+        self.skip_space(false);
+
+        if matches!(self.chars.peek(), Some('"' | '\'')) {
+            return self.parse_string();
+        }
+
+        let mut key = String::new();
+        while let Some(c) = self
+            .chars
+            .next_if(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        {
+            key.push(c);
+        }
+
+        if key.is_empty() {
+            return Err(());
+        }
+        return Ok(Some(key));
+    }
+
+    /// Parses a value
+    fn parse_value(&mut self) -> Parsed<Value> {
+        self.skip_space(false);
+
+        match self.chars.peek() {
+            None => return Ok(None),
+            Some('"' | '\'') => return Ok(self.parse_string()?.map(Value::String)),
+            Some('[') => {
+                self.chars.next();
+                let mut values = Vec::new();
+
+                loop {
+                    self.skip_space(true);
+                    match self.chars.peek() {
+                        None => return Ok(None),
+                        Some(']') => {
+                            self.chars.next();
+                            return Ok(Some(Value::Array(values)));
+                        }
+                        Some(_) => {}
+                    }
+
+                    let Some(value) = self.parse_value()? else {
+                        return Ok(None);
+                    };
+                    values.push(value);
+
+                    // the values are separated by commas (and the last one may be followed by one)
+                    self.skip_space(true);
+                    match self.chars.peek() {
+                        None => return Ok(None),
+                        Some(',') => {
+                            self.chars.next();
+                        }
+                        Some(']') => {}
+                        Some(_) => return Err(()),
+                    }
+                }
+            }
+            Some(_) => {}
+        }
+
+        let mut word = String::new();
+        while let Some(c) = self
+            .chars
+            .next_if(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '_'))
+        {
+            word.push(c);
+        }
+
+        return match word.as_str() {
+            "true" => Ok(Some(Value::Boolean(true))),
+            "false" => Ok(Some(Value::Boolean(false))),
+            _ => match word.replace('_', "").parse() {
+                Ok(number) => Ok(Some(Value::Integer(number))),
+                Err(_) => Err(()),
+            },
+        };
+    }
+}
+
+/// Returns the arguments that an option of a policy expands into
+///
+/// Returns [None] if the value is not of a type that an option can have
+///
+/// # Arguments
+///
+/// - `p_key` - long name of the option without the dashes
+/// - `p_value` - value of the option
+fn option_args(p_key: &str, p_value: &Value) -> Option<Vec<String>> {
+    let flag = format!("--{}", p_key);
+
+    return match p_value {
+        Value::Boolean(true) => Some(vec![flag]),
+        Value::Boolean(false) => Some(Vec::new()),
+        Value::Integer(number) => Some(vec![flag, number.to_string()]),
+        Value::String(string) => Some(vec![flag, string.clone()]),
+        // repeated options (the values can not be arrays themselves)
+        Value::Array(values) => {
+            let mut args = Vec::new();
+            for value in values {
+                if matches!(value, Value::Array(_)) {
+                    return None;
+                }
+                args.extend(option_args(p_key, value)?);
+            }
+            Some(args)
+        }
+    };
+}
+
+/// Reads a policy
+///
+/// # Arguments
+///
+/// - `p_path` - path of the policy
+pub fn load_policy(p_path: &path::Path) -> Result<Policy, PolicyError> {
+    let contents = fs::read_to_string(p_path).map_err(PolicyError::Read)?;
+
+    // the paths in the policy are relative to the directory that it lies in
+    let policy_dir = match p_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => path::PathBuf::from("."),
+    };
+
+    let mut policy = Policy {
+        args: vec![policy_dir.to_string_lossy().into_owned()],
+        excludes: Vec::new(),
+        budgets: Vec::new(),
+        retention: Vec::new(),
+        output: None,
+    };
+
+    let lines: Vec<&str> = contents.lines().collect();
+    let mut table = String::new();
+    let mut line_idx = 0;
+
+    while line_idx < lines.len() {
+        let line_no = line_idx + 1;
+        let trimmed = lines[line_idx].trim();
+        line_idx += 1;
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(header) = trimmed.strip_prefix('[') {
+            let mut cursor = Cursor {
+                chars: header.chars().peekable(),
+            };
+            let Ok(Some(name)) = cursor.parse_key() else {
+                return Err(PolicyError::Syntax(line_no));
+            };
+            cursor.skip_space(false);
+            if cursor.chars.next() != Some(']') || !cursor.at_end() {
+                return Err(PolicyError::Syntax(line_no));
+            }
+
+            if !matches!(
+                name.as_str(),
+                "options" | "excludes" | "budgets" | "retention" | "output"
+            ) {
+                return Err(PolicyError::UnknownKey(String::new(), name));
+            }
+            table = name;
+            continue;
+        }
+
+        // values (such as arrays) can span multiple lines, so the following lines are appended until the value is
+        // complete
+        let mut text = lines[line_no - 1].to_owned();
+        let (key, value) = loop {
+            let mut cursor = Cursor {
+                chars: text.chars().peekable(),
+            };
+
+            let key = match cursor.parse_key() {
+                Ok(Some(key)) => key,
+                _ => return Err(PolicyError::Syntax(line_no)),
+            };
+            cursor.skip_space(false);
+            if cursor.chars.next() != Some('=') {
+                return Err(PolicyError::Syntax(line_no));
+            }
+
+            match cursor.parse_value() {
+                Ok(Some(value)) if cursor.at_end() => break (key, value),
+                Ok(None) if line_idx < lines.len() => {
+                    text.push('\n');
+                    text.push_str(lines[line_idx]);
+                    line_idx += 1;
+                }
+                _ => return Err(PolicyError::Syntax(line_no)),
+            }
+        };
+
+        let invalid = || PolicyError::InvalidValue(table.clone(), key.clone());
+
+        match (table.as_str(), key.as_str(), &value) {
+            ("", "path", Value::String(scan_path)) => {
+                policy.args[0] = policy_dir_path(&policy_dir.to_string_lossy(), scan_path)
+                    .to_string_lossy()
+                    .into_owned();
+            }
+            // policies can not be nested, run commands or write files, so only the options that select the entries
+            // are passed on
+            ("options", _, _) if !POLICY_OPTIONS.contains(&key.as_str()) => {
+                return Err(PolicyError::OptionNotAllowed(key.clone()));
+            }
+            ("options", _, _) => {
+                policy
+                    .args
+                    .extend(option_args(&key, &value).ok_or_else(invalid)?);
+            }
+            ("excludes", "patterns", Value::Array(patterns)) => {
+                for pattern in patterns {
+                    let Value::String(pattern) = pattern else {
+                        return Err(invalid());
+                    };
+                    policy.excludes.push(pattern.clone());
+                }
+            }
+            ("budgets", _, Value::String(size)) => {
+                let size = parse_size(size).ok_or_else(invalid)?;
+                policy.budgets.push((key.clone(), size));
+            }
+            ("budgets", _, Value::Integer(size)) => {
+                let size = u64::try_from(*size).map_err(|_| invalid())?;
+                policy.budgets.push((key.clone(), size));
+            }
+            ("retention", _, Value::String(age)) => {
+                let age = parse_age(age).ok_or_else(invalid)?;
+                policy.retention.push((key.clone(), age));
+            }
+            ("output", "file", Value::String(file)) => {
+                policy.output = Some(policy_dir_path(&policy_dir.to_string_lossy(), file));
+            }
+            ("", "path", _)
+            | ("excludes", "patterns", _)
+            | ("output", "file", _)
+            | ("budgets" | "retention", _, _) => return Err(invalid()),
+            _ => return Err(PolicyError::UnknownKey(table.clone(), key.clone())),
+        }
+    }
+
+    return Ok(policy);
+}
+
+/// Returns the path of an entry named in a policy (`.` for the base directory itself)
+///
+/// # Arguments
+///
+/// - `p_base_path` - path of the directory that the entry is relative to
+/// - `p_name` - the entry as named in the policy
+fn policy_dir_path(p_base_path: &str, p_name: &str) -> path::PathBuf {
+    let base_path = path::Path::new(p_base_path);
+
+    if p_name == "." {
+        return base_path.to_path_buf();
+    }
+    return base_path.join(p_name);
+}
+
+/// Recursively finds the files in a directory that were last modified before the given time
+///
+/// # Arguments
+///
+/// - `p_dir_path` - path of the directory
+/// - `p_threshold` - files last modified before this time are returned
+/// - `p_expired` - the files that are found (along with their sizes and times of the last modification)
+fn find_expired_files(
+    p_dir_path: &path::Path,
+    p_threshold: time::SystemTime,
+    p_expired: &mut Vec<(path::PathBuf, u64, time::SystemTime)>,
+) {
+    let entries = match record::read_dir(p_dir_path) {
+        Ok(entries) => entries,
+        Err(error) => {
+            if get_option(PrgOptions::ShowErrors) {
+                eprint!(
+                    "{}",
+                    tr!(Msg::IterateError, p_dir_path.to_string_lossy(), error)
+                );
+            }
+            return;
+        }
+    };
+
+    for entry in entries {
+        // if the current entry or its metadata could not be read for some reason, then silently skip it
+        let Ok((path_os, metadata)) = entry else {
+            continue;
+        };
+
+        if metadata.is_dir() {
+            find_expired_files(&path_os, p_threshold, p_expired);
+        } else if metadata.is_file() {
+            if let Ok(modified) = metadata.modified() {
+                if modified < p_threshold {
+                    p_expired.push((path_os, metadata.len(), modified));
+                }
+            }
+        }
+    }
+}
+
+/// Prints the directories that exceed their budgets and the files that exceed their retention
+///
+/// Returns the number of violations of the policy
+///
+/// # Arguments
+///
+/// - `p_policy` - the policy
+/// - `p_init_path` - path of the scanned directory
+#[allow(clippy::print_with_newline)]
+pub fn audit(p_policy: &Policy, p_init_path: &str) -> u64 {
+    if p_policy.budgets.is_empty() && p_policy.retention.is_empty() {
+        return 0;
+    }
+
+    let mut over_budget_cnt: u64 = 0;
+    let mut expired_cnt: u64 = 0;
+
+    if !p_policy.budgets.is_empty() {
+        print!("{}", tr!(Msg::PolicyBudgetsHeader, p_init_path));
+
+        for (dir, budget) in &p_policy.budgets {
+            let dir_path = policy_dir_path(p_init_path, dir);
+            let size = calc_dir_size(&dir_path, &dir_path);
+
+            let size_text = match size {
+                Some(size) => format_size(size),
+                None => "ERROR".to_owned(),
+            };
+            print!("{:>20}{:>20}    ", size_text, format_size(*budget));
+
+            if size.is_some_and(|size| size > *budget) {
+                over_budget_cnt += 1;
+                print!("{}", tr!(Msg::PolicyOverBudget, dir));
+            } else {
+                print!("{}\n", dir);
+            }
+        }
+    }
+
+    let now = time::SystemTime::now();
+
+    for (dir, max_age) in &p_policy.retention {
+        print!(
+            "{}",
+            tr!(Msg::PolicyRetentionHeader, dir, format_age(*max_age))
+        );
+
+        // if the time can not be represented, no file is old enough
+        let Some(threshold) = now.checked_sub(*max_age) else {
+            continue;
+        };
+
+        let mut expired = Vec::new();
+        find_expired_files(&policy_dir_path(p_init_path, dir), threshold, &mut expired);
+
+        // the oldest files are shown first
+        expired.sort_by_key(|(_, _, modified)| *modified);
+        for (path_os, size, modified) in &expired {
+            print_file_age(now, *modified, path_os, *size);
+        }
+        expired_cnt += expired.len() as u64;
+    }
+
+    print!(
+        "{}",
+        tr!(
+            Msg::PolicySummary,
+            int_to_formatted_slice(over_budget_cnt).to_owned(),
+            int_to_formatted_slice(expired_cnt).to_owned()
+        )
+    );
+
+    return over_budget_cnt + expired_cnt;
+}
@@ -0,0 +1,273 @@
+//! Thread pools that read directories and hash files ahead of the traversal (for `--walk-threads` and `--work-threads`)
+//!
+//! The traversal itself stays on the main thread, so the output is the same as that of a sequential scan. When the main
+//! thread reads a directory, its subdirectories are handed to the walk pool (only one level deeper than the traversal)
+//! and the files that are going to be hashed to the work pool, and the results are picked up once the traversal reaches
+//! them. Results that were not picked up by the time their directory has been listed are discarded, so only the entries
+//! of the directories currently being listed are held in memory. The pools are separate so that hashing large files
+//! does not hold up reading directories, and reading slow directories does not hold up hashing
+
+use std::collections::HashMap;
+use std::path;
+use std::sync;
+use std::thread;
+
+/// Pool that reads directories ahead of the traversal ([None] if no walk threads were requested)
+static WALK_POOL: sync::OnceLock<Pool> = sync::OnceLock::new();
+
+/// Pool that hashes files ahead of the traversal ([None] if no work threads were requested)
+static WORK_POOL: sync::OnceLock<Pool> = sync::OnceLock::new();
+
+/// Unit of work that is run by a thread of a pool
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Fixed number of threads that run the jobs handed to them in order
+pub struct Pool {
+    /// Queue that the threads take the jobs from
+    jobs: sync::Mutex<sync::mpsc::Sender<Job>>,
+}
+
+/// Enumerates the states of a job whose result is waited for by the traversal
+enum State<T> {
+    /// The job has not been started by any thread yet
+    Queued,
+    /// The job is being run by a thread of the pool
+    Running,
+    /// The job is done (the result is taken out once it is picked up)
+    Done(T),
+    /// The job is no longer needed (either the traversal did it itself, or the directory it was for has been listed)
+    Cancelled,
+}
+
+/// Result of a job handed to a pool, which the traversal can wait for
+struct Slot<T> {
+    state: sync::Mutex<State<T>>,
+    /// Notified when the job is done
+    done: sync::Condvar,
+}
+
+/// Jobs handed to a pool whose results have not been picked up yet, by the path they were done for
+pub struct Pending<T>(sync::Mutex<Option<HashMap<path::PathBuf, sync::Arc<Slot<T>>>>>);
+
+/// Jobs handed to a pool for the entries of a directory, which are discarded when it is dropped (once the directory
+/// has been listed) if their results were not picked up
+pub struct Ahead<T: 'static> {
+    pending: &'static Pending<T>,
+    slots: Vec<(path::PathBuf, sync::Arc<Slot<T>>)>,
+}
+
+impl Pool {
+    /// Starts a pool with the given number of threads
+    ///
+    /// # Arguments
+    ///
+    /// - `p_threads` - number of threads of the pool
+    fn new(p_threads: usize) -> Pool {
+        let (sender, receiver) = sync::mpsc::channel::<Job>();
+        let receiver = sync::Arc::new(sync::Mutex::new(receiver));
+
+        for _ in 0..p_threads {
+            let receiver = receiver.clone();
+
+            // the threads are never joined, since any job still running when the scan completes is no longer needed
+            thread::spawn(move || loop {
+                let Some(job) = receiver
+                    .lock()
+                    .ok()
+                    .and_then(|receiver| receiver.recv().ok())
+                else {
+                    return;
+                };
+                job();
+            });
+        }
+
+        return Pool {
+            jobs: sync::Mutex::new(sender),
+        };
+    }
+
+    /// Hands a job to the threads of the pool
+    ///
+    /// # Arguments
+    ///
+    /// - `p_job` - the job to run
+    fn submit(&self, p_job: Job) {
+        if let Ok(jobs) = self.jobs.lock() {
+            let _ = jobs.send(p_job);
+        }
+    }
+}
+
+impl<T> Slot<T> {
+    /// Marks the job as started by a thread of the pool, and returns whether it still needs to be done
+    fn start(&self) -> bool {
+        let Ok(mut state) = self.state.lock() else {
+            return false;
+        };
+
+        if !matches!(*state, State::Queued) {
+            return false;
+        }
+
+        *state = State::Running;
+        return true;
+    }
+
+    /// Stores the result of the job and wakes up the traversal if it is waiting for it
+    ///
+    /// # Arguments
+    ///
+    /// - `p_result` - result of the job
+    fn finish(&self, p_result: T) {
+        if let Ok(mut state) = self.state.lock() {
+            *state = State::Done(p_result);
+            self.done.notify_all();
+        }
+    }
+
+    /// Returns the result of the job, waiting for it if the job is running ([None] if it had not been started yet, in
+    /// which case it is cancelled so that the traversal can do it without waiting for the jobs queued before it)
+    fn take(&self) -> Option<T> {
+        let mut state = self.state.lock().ok()?;
+
+        while matches!(*state, State::Running) {
+            state = self.done.wait(state).ok()?;
+        }
+
+        return match std::mem::replace(&mut *state, State::Cancelled) {
+            State::Done(result) => Some(result),
+            _ => None,
+        };
+    }
+
+    /// Cancels the job if it has not been started yet
+    fn cancel(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            if matches!(*state, State::Queued) {
+                *state = State::Cancelled;
+            }
+        }
+    }
+}
+
+impl<T: Send + 'static> Pending<T> {
+    /// Returns an empty set of pending jobs
+    pub const fn new() -> Pending<T> {
+        return Pending(sync::Mutex::new(None));
+    }
+
+    /// Hands a job for each of the given paths to a pool, and returns them so that they can be discarded once the
+    /// directory that the paths lie in has been listed
+    ///
+    /// # Arguments
+    ///
+    /// - `p_pool` - pool to run the jobs on
+    /// - `p_paths` - paths to run the job for, each along with the argument that the job is called with for it
+    /// - `p_job` - the job (called with each path and its argument by a thread of the pool)
+    pub fn submit<A, F>(
+        &'static self,
+        p_pool: &Pool,
+        p_paths: Vec<(path::PathBuf, A)>,
+        p_job: F,
+    ) -> Ahead<T>
+    where
+        A: Send + 'static,
+        F: Fn(&path::Path, A) -> T + Send + Sync + 'static,
+    {
+        let mut ahead = Ahead {
+            pending: self,
+            slots: Vec::new(),
+        };
+
+        let Ok(mut pending) = self.0.lock() else {
+            return ahead;
+        };
+        let pending = pending.get_or_insert_with(HashMap::new);
+        let job = sync::Arc::new(p_job);
+
+        for (path_os, arg) in p_paths {
+            let slot = sync::Arc::new(Slot {
+                state: sync::Mutex::new(State::Queued),
+                done: sync::Condvar::new(),
+            });
+
+            // a job that is still pending for the same path (if it is read twice) is replaced
+            if let Some(previous) = pending.insert(path_os.clone(), slot.clone()) {
+                previous.cancel();
+            }
+
+            let job = job.clone();
+            let job_slot = slot.clone();
+            let job_path = path_os.clone();
+            p_pool.submit(Box::new(move || {
+                if job_slot.start() {
+                    job_slot.finish(job(&job_path, arg));
+                }
+            }));
+
+            ahead.slots.push((path_os, slot));
+        }
+
+        return ahead;
+    }
+
+    /// Returns the result of the job for a path, waiting for it if it is running ([None] if no job was handed to a
+    /// pool for the path, or it had not been started yet)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_path` - path that the job was done for
+    pub fn take(&self, p_path: &path::Path) -> Option<T> {
+        let slot = self.0.lock().ok()?.as_mut()?.remove(p_path)?;
+        return slot.take();
+    }
+}
+
+impl<T: 'static> Drop for Ahead<T> {
+    fn drop(&mut self) {
+        let Ok(mut pending) = self.pending.0.lock() else {
+            return;
+        };
+        let Some(pending) = pending.as_mut() else {
+            return;
+        };
+
+        for (path_os, slot) in self.slots.drain(..) {
+            slot.cancel();
+
+            // the path may have been handed to the pool again since (by a later read of the same directory)
+            if pending
+                .get(&path_os)
+                .is_some_and(|pending_slot| sync::Arc::ptr_eq(pending_slot, &slot))
+            {
+                pending.remove(&path_os);
+            }
+        }
+    }
+}
+
+/// Starts the pools (a pool is not started if it is given no threads)
+///
+/// # Arguments
+///
+/// - `p_walk_threads` - number of threads that read directories ahead of the traversal
+/// - `p_work_threads` - number of threads that hash files ahead of the traversal
+pub fn start(p_walk_threads: usize, p_work_threads: usize) {
+    if p_walk_threads != 0 {
+        let _ = WALK_POOL.set(Pool::new(p_walk_threads));
+    }
+    if p_work_threads != 0 {
+        let _ = WORK_POOL.set(Pool::new(p_work_threads));
+    }
+}
+
+/// Returns the pool that reads directories ahead of the traversal ([None] if it was not started)
+pub fn walk_pool() -> Option<&'static Pool> {
+    return WALK_POOL.get();
+}
+
+/// Returns the pool that hashes files ahead of the traversal ([None] if it was not started)
+pub fn work_pool() -> Option<&'static Pool> {
+    return WORK_POOL.get();
+}
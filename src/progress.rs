@@ -0,0 +1,111 @@
+//! Reporting the progress of a scan on stderr
+//!
+//! The progress is only reported if stderr is a terminal and stdout is not (such as when the listing is redirected to
+//! a file), since the progress line would otherwise get mixed up with the listing. If the number of entries that the
+//! scan is expected to read is known (from a recording of a previous scan), a progress bar is drawn along with the
+//! estimated time left, otherwise only the number of entries read so far is shown
+
+use std::io::IsTerminal;
+use std::sync;
+use std::time;
+
+use crate::i18n::{tr, Msg};
+use crate::{format_age, int_to_formatted_slice};
+
+/// Minimum time between two updates of the progress
+const PROGRESS_INTERVAL: time::Duration = time::Duration::from_millis(200);
+
+/// Number of characters in the progress bar
+const PROGRESS_BAR_WIDTH: usize = 30;
+
+/// State of the progress that is being reported ([None] if it is not being reported)
+static PROGRESS: sync::Mutex<Option<Progress>> = sync::Mutex::new(None);
+
+/// Progress of a scan
+struct Progress {
+    /// Time when the scan started
+    start: time::Instant,
+    /// Time when the progress was last reported
+    last_report: time::Instant,
+    /// Number of entries read so far
+    entry_cnt: u64,
+    /// Number of entries that the scan is expected to read ([None] if it is not known)
+    expected_cnt: Option<u64>,
+}
+
+/// Starts reporting the progress of the scan (has no effect if stderr is not a terminal or stdout is)
+///
+/// # Arguments
+///
+/// - `p_expected_cnt` - number of entries that the scan is expected to read ([None] if it is not known)
+pub fn start(p_expected_cnt: Option<u64>) {
+    if !std::io::stderr().is_terminal() || std::io::stdout().is_terminal() {
+        return;
+    }
+
+    if let Ok(mut progress) = PROGRESS.lock() {
+        *progress = Some(Progress {
+            start: time::Instant::now(),
+            last_report: time::Instant::now(),
+            entry_cnt: 0,
+            expected_cnt: p_expected_cnt,
+        });
+    }
+}
+
+/// Counts an entry read by the scan, and reports the progress if enough time has passed since it was last reported
+pub fn tick() {
+    let Ok(mut progress) = PROGRESS.lock() else {
+        return;
+    };
+
+    let Some(progress) = progress.as_mut() else {
+        return;
+    };
+
+    progress.entry_cnt += 1;
+
+    if progress.last_report.elapsed() < PROGRESS_INTERVAL {
+        return;
+    }
+    progress.last_report = time::Instant::now();
+
+    let entry_cnt = int_to_formatted_slice(progress.entry_cnt).to_owned();
+
+    let Some(expected_cnt) = progress.expected_cnt else {
+        eprint!("{}\x1b[K", tr!(Msg::ScanProgress, entry_cnt));
+        return;
+    };
+
+    // the scan may read more entries than expected if the tree has grown since the previous scan
+    let fraction = (progress.entry_cnt as f64 / expected_cnt.max(1) as f64).min(1.0);
+    let filled = (fraction * PROGRESS_BAR_WIDTH as f64) as usize;
+    let left = progress
+        .start
+        .elapsed()
+        .mul_f64((1.0 - fraction) / fraction);
+
+    eprint!(
+        "{}\x1b[K",
+        tr!(
+            Msg::ScanProgressEstimate,
+            "#".repeat(filled),
+            ".".repeat(PROGRESS_BAR_WIDTH - filled),
+            ((fraction * 100.0) as u64).min(99),
+            entry_cnt,
+            int_to_formatted_slice(expected_cnt),
+            format_age(left)
+        )
+    );
+}
+
+/// Stops reporting the progress and clears the progress line (has no effect if the progress is not being reported)
+pub fn finish() {
+    let Ok(mut progress) = PROGRESS.lock() else {
+        return;
+    };
+
+    if progress.take().is_some() {
+        eprint!("\r\x1b[2K");
+    }
+}
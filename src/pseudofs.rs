@@ -0,0 +1,160 @@
+//! Skipping the mount points of pseudo filesystems (such as `/proc`, `/sys` and `/dev`)
+//!
+//! Pseudo filesystems hold no data of their own, so scanning them from `/` only produces nonsense sizes and floods of
+//! permission errors. The mount points of the filesystems whose types are in [PSEUDO_FS_TYPES] (or the types given to
+//! `--skip-fs`) are read from `/proc/self/mountinfo` once, and skipped like ignored directories wherever they lie, so
+//! they are neither shown nor counted. A scan that starts within such a filesystem (such as `fss /proc`) still reads
+//! it, and `--no-skip-fs` skips none of them. Mount points are only known on Linux, so nothing is skipped elsewhere
+
+use std::path;
+use std::sync;
+
+/// Types of the filesystems that are skipped unless `--skip-fs` or `--no-skip-fs` is given
+const PSEUDO_FS_TYPES: [&str; 21] = [
+    "proc",
+    "sysfs",
+    "devtmpfs",
+    "devpts",
+    "cgroup",
+    "cgroup2",
+    "securityfs",
+    "debugfs",
+    "tracefs",
+    "pstore",
+    "bpf",
+    "configfs",
+    "fusectl",
+    "mqueue",
+    "hugetlbfs",
+    "binfmt_misc",
+    "autofs",
+    "efivarfs",
+    "selinuxfs",
+    "rpc_pipefs",
+    "nsfs",
+];
+
+/// Types of the filesystems to skip, set with `--skip-fs` and `--no-skip-fs` ([None] if [PSEUDO_FS_TYPES] are
+/// skipped)
+static TYPES: sync::OnceLock<Vec<String>> = sync::OnceLock::new();
+
+/// Mount points of the filesystems to skip (read when the first directory is checked)
+static MOUNTS: sync::OnceLock<Vec<path::PathBuf>> = sync::OnceLock::new();
+
+/// Sets the types of the filesystems to skip instead of [PSEUDO_FS_TYPES]
+///
+/// Returns [Err] if the list holds no type
+///
+/// # Arguments
+///
+/// - `p_list` - comma-separated types (such as `proc,sysfs`)
+pub fn set_types(p_list: &str) -> Result<(), ()> {
+    let types: Vec<String> = p_list
+        .split(',')
+        .map(str::trim)
+        .filter(|fs_type| !fs_type.is_empty())
+        .map(str::to_owned)
+        .collect();
+
+    if types.is_empty() {
+        return Err(());
+    }
+
+    let _ = TYPES.set(types);
+    return Ok(());
+}
+
+/// Skips no filesystem (for `--no-skip-fs`)
+pub fn skip_none() {
+    let _ = TYPES.set(Vec::new());
+}
+
+/// Returns whether a directory is the mount point of a filesystem that is skipped
+///
+/// # Arguments
+///
+/// - `p_dir_path` - path of the directory
+pub fn is_skipped(p_dir_path: &path::Path) -> bool {
+    let mounts = MOUNTS.get_or_init(read_mounts);
+
+    // the names are compared first, so that most directories are never made absolute
+    let Some(name) = p_dir_path.file_name() else {
+        return false;
+    };
+    if !mounts.iter().any(|mount| mount.file_name() == Some(name)) {
+        return false;
+    }
+
+    return path::absolute(p_dir_path).is_ok_and(|absolute| mounts.contains(&absolute));
+}
+
+/// Returns the mount points of the filesystems whose types are to be skipped (empty if they could not be read)
+#[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+fn read_mounts() -> Vec<path::PathBuf> {
+    let types: Vec<&str> = match TYPES.get() {
+        Some(types) => types.iter().map(String::as_str).collect(),
+        None => PSEUDO_FS_TYPES.to_vec(),
+    };
+    if types.is_empty() {
+        return Vec::new();
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let Ok(mountinfo) = std::fs::read_to_string("/proc/self/mountinfo") else {
+            return Vec::new();
+        };
+
+        // each line holds the mount point as its fifth field, and the type after the " - " separator
+        return mountinfo
+            .lines()
+            .filter_map(|line| {
+                let (fields, fs_fields) = line.split_once(" - ")?;
+                let mount_point = fields.split(' ').nth(4)?;
+                let fs_type = fs_fields.split(' ').next()?;
+
+                return match types.contains(&fs_type) {
+                    true => Some(path::PathBuf::from(unescape(mount_point))),
+                    false => None,
+                };
+            })
+            .collect();
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        return Vec::new();
+    }
+}
+
+/// Returns a mount point with the octal escapes of `/proc/self/mountinfo` (such as `\040` for a space) decoded
+///
+/// # Arguments
+///
+/// - `p_mount_point` - the escaped mount point
+#[cfg(target_os = "linux")]
+fn unescape(p_mount_point: &str) -> String {
+    let bytes = p_mount_point.as_bytes();
+
+    let mut res = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let digits = bytes.get(i + 1..i + 4);
+        let code = digits
+            .filter(|digits| digits.iter().all(|digit| (b'0'..=b'7').contains(digit)))
+            .and_then(|digits| u8::from_str_radix(std::str::from_utf8(digits).ok()?, 8).ok());
+
+        match (bytes[i], code) {
+            (b'\\', Some(code)) => {
+                res.push(code);
+                i += 4;
+            }
+            (byte, _) => {
+                res.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    return String::from_utf8_lossy(&res).into_owned();
+}
@@ -0,0 +1,1348 @@
+//! Recording the entries read during a scan into a file, and replaying scans from such a file
+//!
+//! All reads done while listing or searching go through [read_dir], [canonicalize] and [is_dir]. While recording,
+//! every entry is appended to the recording as it is read (along with its canonical path and, for symlinks, whether
+//! the target is a directory). While replaying, the results are served from the recording instead of the filesystem,
+//! so the formatting, filtering and summaries can be re-run without the original filesystem (with different options,
+//! as long as they do not need directories that the recorded scan did not read)
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, Write};
+use std::path;
+use std::sync;
+use std::time;
+
+use crate::{
+    draws_tree_lines, escape_line_field, get_option, unescape_line_field, PrgOptions,
+    SpecialFileType,
+};
+use crate::{errors, ignore, limit, msgpack, pool};
+
+/// First line of a recording (identifies the format)
+const RECORDING_HEADER: &str = "# fss recording v1";
+
+/// First value of a snapshot (identifies the format)
+///
+/// Snapshots hold the same events as recordings, but each one is written as a MessagePack array instead of a line.
+/// Entries refer to their directory by its index (the directories are numbered from 0 in the order they are opened)
+/// instead of its path, only the canonical paths of symlinks are stored, and entries also hold the time of the last
+/// access, status change and creation along with the owner, group, inode, device and number of links
+const SNAPSHOT_HEADER: &str = "fss snapshot v1";
+
+/// Recording that the read entries are being written into ([None] if no recording is being made)
+static RECORDING: sync::Mutex<Option<Recording>> = sync::Mutex::new(None);
+
+/// Recording that the entries are being served from (only set when replaying)
+static REPLAY: sync::OnceLock<Replay> = sync::OnceLock::new();
+
+/// Directories being read ahead of the traversal by the walk pool
+static DIRS_AHEAD: pool::Pending<DirAhead> = pool::Pending::new();
+
+/// Device and inode of each directory read so far, to find the directories (and with `--follow`, the symlinks) that
+/// loop back to a directory above them
+static DIR_IDS: sync::Mutex<BTreeMap<path::PathBuf, (u64, u64)>> =
+    sync::Mutex::new(BTreeMap::new());
+
+/// Directories (and symlinks followed with `--follow`) that were not read since they loop back to a directory above them
+static LOOPS: sync::Mutex<BTreeSet<path::PathBuf>> = sync::Mutex::new(BTreeSet::new());
+
+/// Scan loaded from a recording or snapshot, whose directories are read from it independently of the scan being run
+pub struct RecordedTree {
+    /// Path of the directory that the recorded scan started from
+    root: path::PathBuf,
+    /// Results of all reads stored in the recording
+    replay: Replay,
+}
+
+impl RecordedTree {
+    /// Returns the path of the directory that the recorded scan started from
+    pub fn root(&self) -> &path::Path {
+        return &self.root;
+    }
+
+    /// Returns the entries of a recorded directory, each along with its metadata (the entries that could not be read
+    /// are left out)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_dir_path` - path of the directory
+    pub fn read_dir(
+        &self,
+        p_dir_path: &path::Path,
+    ) -> std::io::Result<Vec<(path::PathBuf, EntryMetadata)>> {
+        return match self.replay.dirs.get(p_dir_path) {
+            Some(Ok(entries)) => Ok(entries.iter().flatten().cloned().collect()),
+            Some(Err(error)) => Err(replayed_error(error)),
+            None => Err(not_recorded(p_dir_path)),
+        };
+    }
+}
+
+/// Enumerates the types of entries that can be stored in a recording
+#[derive(Clone, Copy, PartialEq)]
+enum EntryKind {
+    File,
+    Dir,
+    Symlink,
+    Socket,
+    BlockDevice,
+    CharDevice,
+    Fifo,
+    /// Any other type of special file
+    Special,
+}
+
+impl EntryKind {
+    /// Returns the name of the type as stored in recordings
+    fn name(&self) -> &'static str {
+        return match self {
+            EntryKind::File => "file",
+            EntryKind::Dir => "dir",
+            EntryKind::Symlink => "symlink",
+            EntryKind::Socket => "socket",
+            EntryKind::BlockDevice => "block",
+            EntryKind::CharDevice => "char",
+            EntryKind::Fifo => "fifo",
+            EntryKind::Special => "special",
+        };
+    }
+
+    /// Returns the type with the given name ([None] if there is no such type)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_name` - name of the type as stored in recordings
+    fn from_name(p_name: &str) -> Option<EntryKind> {
+        return match p_name {
+            "file" => Some(EntryKind::File),
+            "dir" => Some(EntryKind::Dir),
+            "symlink" => Some(EntryKind::Symlink),
+            "socket" => Some(EntryKind::Socket),
+            "block" => Some(EntryKind::BlockDevice),
+            "char" => Some(EntryKind::CharDevice),
+            "fifo" => Some(EntryKind::Fifo),
+            "special" => Some(EntryKind::Special),
+            _ => None,
+        };
+    }
+}
+
+/// Recording that is being made
+enum Recording {
+    /// Recording with a line of tab-separated fields per event
+    Text(std::io::BufWriter<fs::File>),
+    /// Snapshot with a MessagePack array per event (see [SNAPSHOT_HEADER])
+    Snapshot {
+        writer: std::io::BufWriter<fs::File>,
+        /// Directories along the path of the directory opened last, each along with its index
+        dirs: Vec<(path::PathBuf, i64)>,
+        /// Number of directories opened so far
+        dir_cnt: i64,
+    },
+}
+
+/// Metadata of an entry, read either from the filesystem or from a recording
+#[derive(Clone)]
+pub struct EntryMetadata {
+    /// Type of the entry
+    kind: EntryKind,
+    /// Length of the entry (in bytes)
+    len: u64,
+    /// Raw permission bits of the entry (always 0 on platforms other than unix)
+    mode: u32,
+    /// Modification time of the entry ([None] if it could not be read)
+    modified: Option<time::SystemTime>,
+    /// Metadata read from the filesystem ([None] for entries that were replayed)
+    raw: Option<fs::Metadata>,
+}
+
+impl EntryMetadata {
+    /// Returns the metadata of an entry as read from the filesystem
+    ///
+    /// # Arguments
+    ///
+    /// - `p_metadata` - metadata of the entry
+    pub fn from_metadata(p_metadata: fs::Metadata) -> EntryMetadata {
+        let file_type = p_metadata.file_type();
+
+        #[cfg(target_family = "unix")]
+        let (kind, mode) = {
+            use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+            let kind = if file_type.is_symlink() {
+                EntryKind::Symlink
+            } else if file_type.is_dir() {
+                EntryKind::Dir
+            } else if file_type.is_file() {
+                EntryKind::File
+            } else if file_type.is_socket() {
+                EntryKind::Socket
+            } else if file_type.is_block_device() {
+                EntryKind::BlockDevice
+            } else if file_type.is_char_device() {
+                EntryKind::CharDevice
+            } else if file_type.is_fifo() {
+                EntryKind::Fifo
+            } else {
+                EntryKind::Special
+            };
+
+            (kind, p_metadata.permissions().mode())
+        };
+
+        #[cfg(not(target_family = "unix"))]
+        let (kind, mode) = {
+            let kind = if file_type.is_symlink() {
+                EntryKind::Symlink
+            } else if file_type.is_dir() {
+                EntryKind::Dir
+            } else if file_type.is_file() {
+                EntryKind::File
+            } else {
+                EntryKind::Special
+            };
+
+            (kind, 0)
+        };
+
+        return EntryMetadata {
+            kind,
+            len: p_metadata.len(),
+            mode,
+            modified: p_metadata.modified().ok(),
+            raw: Some(p_metadata),
+        };
+    }
+
+    /// Returns whether the entry is a symlink
+    pub fn is_symlink(&self) -> bool {
+        return self.kind == EntryKind::Symlink;
+    }
+
+    /// Returns whether the entry is a regular file
+    pub fn is_file(&self) -> bool {
+        return self.kind == EntryKind::File;
+    }
+
+    /// Returns whether the entry is a directory
+    pub fn is_dir(&self) -> bool {
+        return self.kind == EntryKind::Dir;
+    }
+
+    /// Returns the length of the entry (in bytes)
+    pub fn len(&self) -> u64 {
+        return self.len;
+    }
+
+    #[cfg(target_family = "unix")]
+    /// Returns the permissions of the entry
+    pub fn permissions(&self) -> fs::Permissions {
+        use std::os::unix::fs::PermissionsExt;
+
+        return fs::Permissions::from_mode(self.mode);
+    }
+
+    /// Returns the modification time of the entry
+    pub fn modified(&self) -> std::io::Result<time::SystemTime> {
+        return self
+            .modified
+            .ok_or_else(|| std::io::Error::other("modification time is not available"));
+    }
+
+    /// Returns the time of the last access to the entry (not available for entries that were replayed)
+    pub fn accessed(&self) -> std::io::Result<time::SystemTime> {
+        return match &self.raw {
+            Some(raw) => raw.accessed(),
+            None => Err(std::io::Error::other("access time is not available")),
+        };
+    }
+
+    #[cfg(target_family = "unix")]
+    /// Returns the time of the last change to the status of the entry (such as its permissions, owner or contents, not
+    /// available for entries that were replayed)
+    pub fn status_changed(&self) -> std::io::Result<time::SystemTime> {
+        use std::os::unix::fs::MetadataExt;
+
+        let Some(raw) = &self.raw else {
+            return Err(std::io::Error::other("status change time is not available"));
+        };
+
+        // the seconds can be before the epoch, while the nanoseconds always count forward from them
+        let secs = time::Duration::from_secs(raw.ctime().unsigned_abs());
+        let changed = match raw.ctime() >= 0 {
+            true => time::UNIX_EPOCH.checked_add(secs),
+            false => time::UNIX_EPOCH.checked_sub(secs),
+        };
+        return changed
+            .and_then(|changed| {
+                changed.checked_add(time::Duration::from_nanos(raw.ctime_nsec() as u64))
+            })
+            .ok_or_else(|| std::io::Error::other("status change time is out of range"));
+    }
+
+    #[cfg(not(target_family = "unix"))]
+    /// Returns the time of the last change to the status of the entry (not available on this platform)
+    pub fn status_changed(&self) -> std::io::Result<time::SystemTime> {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "status change time is only available on unix",
+        ));
+    }
+
+    /// Returns the type of special file that the entry is ([SpecialFileType::NA] if it is not one)
+    pub fn special_file_type(&self) -> SpecialFileType {
+        return match self.kind {
+            #[cfg(target_family = "unix")]
+            EntryKind::Socket => SpecialFileType::Socket,
+            #[cfg(target_family = "unix")]
+            EntryKind::BlockDevice => SpecialFileType::BlockDevice,
+            #[cfg(target_family = "unix")]
+            EntryKind::CharDevice => SpecialFileType::CharDevice,
+            #[cfg(target_family = "unix")]
+            EntryKind::Fifo => SpecialFileType::Fifo,
+            _ => SpecialFileType::NA,
+        };
+    }
+
+    /// Returns the metadata as read from the filesystem ([None] if the entry was replayed)
+    pub fn raw(&self) -> Option<&fs::Metadata> {
+        return self.raw.as_ref();
+    }
+}
+
+/// Result of reading a single entry of a directory from a recording (the error is stored as its message)
+type RecordedEntry = Result<(path::PathBuf, EntryMetadata), String>;
+
+/// Result of reading a single entry of a directory, along with its metadata
+type ReadEntry = std::io::Result<(path::PathBuf, EntryMetadata)>;
+
+/// Entries of a directory read ahead of the traversal (or the error that occurred while opening it), along with the
+/// ignore rules of the directory
+type DirAhead = (std::io::Result<Vec<ReadEntry>>, ignore::IgnoreRules);
+
+/// Results of all reads stored in a recording
+struct Replay {
+    /// Entries of each directory in the order they were read (or the error that occurred while opening the directory)
+    dirs: HashMap<path::PathBuf, Result<Vec<RecordedEntry>, String>>,
+    /// Canonicalized path of each entry that was canonicalized (or the error that occurred)
+    canonical: HashMap<path::PathBuf, Result<path::PathBuf, String>>,
+    /// Whether the target of each symlink that was checked is a directory
+    targets: HashMap<path::PathBuf, bool>,
+}
+
+/// Iterator over the entries of a directory, each along with its metadata
+pub enum ReadEntries {
+    /// Entries being read from the filesystem (the path of the directory is kept for recording the entries, and the
+    /// ignore rules of the directory for skipping the ignored ones)
+    Live(fs::ReadDir, path::PathBuf, ignore::IgnoreRules),
+    /// Entries being served from a recording
+    Replayed(std::vec::IntoIter<ReadEntry>),
+    /// Entries that were all read up front (sorted by name with `--ordered`, with the errors last)
+    Buffered {
+        entries: std::vec::IntoIter<ReadEntry>,
+        /// Subdirectories handed to the walk pool to be read ahead (discarded along with the entries)
+        _ahead: Option<pool::Ahead<DirAhead>>,
+    },
+}
+
+impl Iterator for ReadEntries {
+    type Item = ReadEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // entries only count towards the limit of "--max-entries" once they are handed to the traversal, so the ones
+        // read ahead do not
+        return self.next_unlimited().filter(|_| limit::take_entry());
+    }
+}
+
+impl ReadEntries {
+    /// Returns the next entry regardless of the limit of `--max-entries` (for reading all of them up front)
+    fn next_unlimited(&mut self) -> Option<ReadEntry> {
+        return match self {
+            ReadEntries::Live(entries, dir_path, ignore_rules) => {
+                // ignored entries are skipped before being recorded, so replays also lack them
+                let next = loop {
+                    let next = entries.next()?.and_then(|entry| {
+                        let mut metadata = entry.metadata()?;
+
+                        // with "--follow", symlinks to directories are read as the directories that they point to (unless
+                        // they loop back, in which case they stay symlinks so that they are not descended into)
+                        if metadata.is_symlink() && get_option(PrgOptions::Follow) {
+                            if let Ok(target) = fs::metadata(entry.path()) {
+                                if target.is_dir() && !loops_back(dir_path, &entry.path(), &target)
+                                {
+                                    metadata = target;
+                                }
+                            }
+                        }
+
+                        Ok((entry.path(), EntryMetadata::from_metadata(metadata)))
+                    });
+
+                    match &next {
+                        Ok((path_os, metadata))
+                            if ignore_rules.is_ignored(path_os, metadata.is_dir()) => {}
+                        _ => break next,
+                    }
+                };
+
+                if is_recording() {
+                    match &next {
+                        Ok((path_os, metadata)) => record_entry(dir_path, path_os, metadata),
+                        Err(error) => record_entry_error(dir_path, error),
+                    }
+                }
+
+                Some(next)
+            }
+            ReadEntries::Replayed(entries) | ReadEntries::Buffered { entries, .. } => {
+                entries.next()
+            }
+        };
+    }
+
+    /// Returns all of the entries that are left regardless of the limit of `--max-entries`
+    fn collect_unlimited(mut self) -> Vec<ReadEntry> {
+        return std::iter::from_fn(|| self.next_unlimited()).collect();
+    }
+
+    /// Returns the entries that have not been iterated over yet (empty if the entries are being read one at a time)
+    pub fn remaining(&self) -> &[ReadEntry] {
+        return match self {
+            ReadEntries::Live(..) => &[],
+            ReadEntries::Replayed(entries) | ReadEntries::Buffered { entries, .. } => {
+                entries.as_slice()
+            }
+        };
+    }
+}
+
+/// Returns the given time as the number of nanoseconds since the unix epoch ([None] if there is no time)
+///
+/// # Arguments
+///
+/// - `p_time` - the time
+fn time_to_nanos(p_time: Option<time::SystemTime>) -> Option<i128> {
+    let time = p_time?;
+
+    return match time.duration_since(time::UNIX_EPOCH) {
+        Ok(since) => Some(since.as_nanos() as i128),
+        Err(error) => Some(-(error.duration().as_nanos() as i128)),
+    };
+}
+
+/// Reverses the conversion done by [time_to_nanos] ([None] if the time can not be represented)
+///
+/// # Arguments
+///
+/// - `p_nanos` - number of nanoseconds since the unix epoch
+fn time_from_nanos(p_nanos: i128) -> Option<time::SystemTime> {
+    let since = time::Duration::new(
+        (p_nanos.unsigned_abs() / 1_000_000_000) as u64,
+        (p_nanos.unsigned_abs() % 1_000_000_000) as u32,
+    );
+
+    return if p_nanos < 0 {
+        time::UNIX_EPOCH.checked_sub(since)
+    } else {
+        time::UNIX_EPOCH.checked_add(since)
+    };
+}
+
+/// Returns the given time as the number of nanoseconds since the unix epoch ("-" if there is no time)
+///
+/// # Arguments
+///
+/// - `p_time` - the time to format
+fn format_time(p_time: Option<time::SystemTime>) -> String {
+    return match time_to_nanos(p_time) {
+        Some(nanos) => nanos.to_string(),
+        None => "-".to_owned(),
+    };
+}
+
+/// Reverses the formatting done by [format_time] ([None] if the time is missing or could not be parsed)
+///
+/// # Arguments
+///
+/// - `p_time` - the formatted time
+fn parse_time(p_time: &str) -> Option<time::SystemTime> {
+    return time_from_nanos(p_time.parse().ok()?);
+}
+
+/// Returns the given time as a value of a snapshot (the number of nanoseconds since the unix epoch, or nil if there is
+/// no time or it does not fit)
+///
+/// # Arguments
+///
+/// - `p_time` - the time
+fn time_value(p_time: Option<time::SystemTime>) -> msgpack::Value {
+    return time_to_nanos(p_time)
+        .and_then(|nanos| i64::try_from(nanos).ok())
+        .map_or(msgpack::Value::Nil, msgpack::Value::Int);
+}
+
+/// Returns an unsigned integer as a value of a snapshot (nil if it does not fit)
+///
+/// # Arguments
+///
+/// - `p_value` - the integer
+fn uint_value(p_value: u64) -> msgpack::Value {
+    return i64::try_from(p_value).map_or(msgpack::Value::Nil, msgpack::Value::Int);
+}
+
+/// Returns the metadata of an entry that is only stored in snapshots (see [SNAPSHOT_HEADER]), which is nil for the
+/// values that are not available (all of them for entries that were replayed)
+///
+/// # Arguments
+///
+/// - `p_metadata` - metadata of the entry
+fn snapshot_metadata(p_metadata: &EntryMetadata) -> [msgpack::Value; 8] {
+    let mut values: [msgpack::Value; 8] = std::array::from_fn(|_| msgpack::Value::Nil);
+
+    let Some(raw) = p_metadata.raw() else {
+        return values;
+    };
+
+    values[0] = time_value(raw.accessed().ok());
+    values[2] = time_value(raw.created().ok());
+
+    #[cfg(target_family = "unix")]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        values[1] = msgpack::Value::Int(raw.ctime() * 1_000_000_000 + raw.ctime_nsec());
+        values[3] = msgpack::Value::Int(raw.uid() as i64);
+        values[4] = msgpack::Value::Int(raw.gid() as i64);
+        values[5] = uint_value(raw.ino());
+        values[6] = uint_value(raw.dev());
+        values[7] = uint_value(raw.nlink());
+    }
+
+    return values;
+}
+
+impl Recording {
+    /// Appends an event with the given fields (errors while writing are ignored, since they should not interrupt the
+    /// scan being recorded)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_fields` - fields of the event (escaped before being written into text recordings)
+    fn write_fields(&mut self, p_fields: &[&str]) {
+        let values = p_fields
+            .iter()
+            .map(|field| msgpack::Value::Str(field.to_string()))
+            .collect();
+        self.write_values(values, p_fields);
+    }
+
+    /// Appends an event, given both as the values of a snapshot and as the fields of a text recording
+    ///
+    /// # Arguments
+    ///
+    /// - `p_values` - values of the event in a snapshot
+    /// - `p_fields` - fields of the event in a text recording
+    fn write_values(&mut self, p_values: Vec<msgpack::Value>, p_fields: &[&str]) {
+        match self {
+            Recording::Text(writer) => {
+                let fields: Vec<String> = p_fields
+                    .iter()
+                    .map(|field| escape_line_field(field))
+                    .collect();
+                let _ = writeln!(writer, "{}", fields.join("\t"));
+            }
+            Recording::Snapshot { writer, .. } => {
+                let _ = msgpack::write_value(writer, &msgpack::Value::Array(p_values));
+            }
+        }
+    }
+
+    /// Appends the opening of a directory
+    ///
+    /// # Arguments
+    ///
+    /// - `p_dir_path` - path of the directory
+    fn open_dir(&mut self, p_dir_path: &path::Path) {
+        if let Recording::Snapshot { dirs, dir_cnt, .. } = self {
+            // the directories that the one being opened does not lie in have been left
+            while dirs
+                .last()
+                .is_some_and(|(dir_path, _)| !p_dir_path.starts_with(dir_path))
+            {
+                dirs.pop();
+            }
+            dirs.push((p_dir_path.to_path_buf(), *dir_cnt));
+            *dir_cnt += 1;
+        }
+
+        self.write_fields(&["D", &p_dir_path.to_string_lossy()]);
+    }
+
+    /// Returns the index that entries of a directory refer to it by in a snapshot (0 for text recordings)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_dir_path` - path of the directory
+    fn dir_idx(&mut self, p_dir_path: &path::Path) -> i64 {
+        let Recording::Snapshot { dirs, .. } = self else {
+            return 0;
+        };
+
+        if let Some((_, idx)) = dirs
+            .iter()
+            .rev()
+            .find(|(dir_path, _)| dir_path == p_dir_path)
+        {
+            return *idx;
+        }
+
+        // directories are read depth first, so this only happens if the directory was left while it was being read
+        self.open_dir(p_dir_path);
+        return match self {
+            Recording::Snapshot { dir_cnt, .. } => *dir_cnt - 1,
+            Recording::Text(_) => 0,
+        };
+    }
+}
+
+/// Runs a function on the recording (has no effect if no recording is being made)
+///
+/// # Arguments
+///
+/// - `p_record` - the function
+fn with_recording(p_record: impl FnOnce(&mut Recording)) {
+    let Ok(mut recording) = RECORDING.lock() else {
+        return;
+    };
+
+    if let Some(recording) = recording.as_mut() {
+        p_record(recording);
+    }
+}
+
+/// Appends a single event to the recording (has no effect if no recording is being made)
+///
+/// # Arguments
+///
+/// - `p_fields` - fields of the event
+fn record_line(p_fields: &[&str]) {
+    with_recording(|recording| recording.write_fields(p_fields));
+}
+
+/// Returns whether a recording is being made
+fn is_recording() -> bool {
+    return RECORDING.lock().is_ok_and(|recording| recording.is_some());
+}
+
+/// Appends an entry to the recording, along with everything that is read about it while it is shown
+///
+/// Snapshots only store the canonical path of symlinks, since that of any other entry is the canonical path of its
+/// directory joined with its name
+///
+/// # Arguments
+///
+/// - `p_dir_path` - path of the directory that contains the entry
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+fn record_entry(p_dir_path: &path::Path, p_path_os: &path::Path, p_metadata: &EntryMetadata) {
+    let path = p_path_os.to_string_lossy();
+    let name = p_path_os.file_name().unwrap_or_default().to_string_lossy();
+    let mut snapshot = false;
+
+    with_recording(|recording| {
+        let dir_idx = recording.dir_idx(p_dir_path);
+        snapshot = matches!(recording, Recording::Snapshot { .. });
+
+        let mut values = vec![
+            msgpack::Value::Str("E".to_owned()),
+            msgpack::Value::Int(dir_idx),
+            msgpack::Value::Str(name.to_string()),
+            msgpack::Value::Str(p_metadata.kind.name().to_owned()),
+            uint_value(p_metadata.len),
+            msgpack::Value::Int(p_metadata.mode as i64),
+            time_value(p_metadata.modified),
+        ];
+        if snapshot {
+            values.extend(snapshot_metadata(p_metadata));
+        }
+
+        recording.write_values(
+            values,
+            &[
+                "E",
+                &p_dir_path.to_string_lossy(),
+                &name,
+                p_metadata.kind.name(),
+                &p_metadata.len.to_string(),
+                &p_metadata.mode.to_string(),
+                &format_time(p_metadata.modified),
+            ],
+        );
+    });
+
+    if !snapshot || p_metadata.is_symlink() {
+        match p_path_os.canonicalize() {
+            Ok(canonical) => record_line(&["C", &path, &canonical.to_string_lossy()]),
+            Err(error) => record_line(&["c", &path, &error.to_string()]),
+        }
+    }
+
+    if p_metadata.is_symlink() {
+        record_line(&["T", &path, if p_path_os.is_dir() { "1" } else { "0" }]);
+    }
+}
+
+/// Appends an entry of a directory that could not be read to the recording
+///
+/// # Arguments
+///
+/// - `p_dir_path` - path of the directory
+/// - `p_error` - the error that occurred while reading the entry
+fn record_entry_error(p_dir_path: &path::Path, p_error: &std::io::Error) {
+    let error = p_error.to_string();
+
+    with_recording(|recording| {
+        let dir_idx = recording.dir_idx(p_dir_path);
+        recording.write_values(
+            vec![
+                msgpack::Value::Str("e".to_owned()),
+                msgpack::Value::Int(dir_idx),
+                msgpack::Value::Str(error.clone()),
+            ],
+            &["e", &p_dir_path.to_string_lossy(), &error],
+        );
+    });
+}
+
+/// Starts recording all entries read during the scan into the given file (which is overwritten), as a snapshot if the
+/// snapshot option is set
+///
+/// # Arguments
+///
+/// - `p_path` - path of the file to record into
+/// - `p_init_path` - path of the directory that the scan starts from
+pub fn start_recording(p_path: &path::Path, p_init_path: &str) -> std::io::Result<()> {
+    let mut writer = std::io::BufWriter::new(fs::File::create(p_path)?);
+
+    let recording = if get_option(PrgOptions::Snapshot) {
+        msgpack::write_value(
+            &mut writer,
+            &msgpack::Value::Str(SNAPSHOT_HEADER.to_owned()),
+        )?;
+        Recording::Snapshot {
+            writer,
+            dirs: Vec::new(),
+            dir_cnt: 0,
+        }
+    } else {
+        writeln!(writer, "{}", RECORDING_HEADER)?;
+        Recording::Text(writer)
+    };
+
+    if let Ok(mut current) = RECORDING.lock() {
+        *current = Some(recording);
+    }
+
+    record_line(&["R", p_init_path]);
+
+    // the entries are shown by their canonical path relative to that of the initial directory
+    match path::Path::new(p_init_path).canonicalize() {
+        Ok(canonical) => record_line(&["C", p_init_path, &canonical.to_string_lossy()]),
+        Err(error) => record_line(&["c", p_init_path, &error.to_string()]),
+    }
+
+    return Ok(());
+}
+
+/// Flushes the recording to its file and stops recording (has no effect if no recording is being made)
+pub fn finish_recording() -> std::io::Result<()> {
+    let Ok(mut recording) = RECORDING.lock() else {
+        return Ok(());
+    };
+
+    match recording.take() {
+        Some(Recording::Text(mut writer)) | Some(Recording::Snapshot { mut writer, .. }) => {
+            writer.flush()?
+        }
+        None => {}
+    }
+
+    return Ok(());
+}
+
+/// Returns the error for a file that is neither a recording nor a snapshot
+fn not_a_recording() -> std::io::Error {
+    return std::io::Error::new(std::io::ErrorKind::InvalidData, "not a recording");
+}
+
+/// Returns the events of a recording or a snapshot, each as its fields (the header is checked and skipped)
+///
+/// The values of snapshots are converted to the fields that text recordings hold, except that entries refer to their
+/// directory by its index (see [SNAPSHOT_HEADER]) and carry the metadata that only snapshots store
+///
+/// # Arguments
+///
+/// - `p_path` - path of the recording or snapshot
+fn read_events(
+    p_path: &path::Path,
+) -> std::io::Result<Box<dyn Iterator<Item = std::io::Result<Vec<String>>>>> {
+    let mut reader = std::io::BufReader::new(fs::File::open(p_path)?);
+
+    // text recordings start with a comment, while snapshots start with a MessagePack string
+    if reader.fill_buf()?.first() == Some(&b'#') {
+        let mut lines = reader.lines();
+        if lines.next().transpose()?.as_deref() != Some(RECORDING_HEADER) {
+            return Err(not_a_recording());
+        }
+
+        return Ok(Box::new(lines.map(|line| {
+            Ok(line?.split('\t').map(unescape_line_field).collect())
+        })));
+    }
+
+    if msgpack::read_value(&mut reader)?
+        .as_ref()
+        .and_then(msgpack::Value::as_str)
+        != Some(SNAPSHOT_HEADER)
+    {
+        return Err(not_a_recording());
+    }
+
+    return Ok(Box::new(std::iter::from_fn(move || {
+        let values = match msgpack::read_value(&mut reader) {
+            Ok(Some(msgpack::Value::Array(values))) => values,
+            Ok(Some(_)) => return Some(Err(not_a_recording())),
+            Ok(None) => return None,
+            Err(error) => return Some(Err(error)),
+        };
+
+        let fields = values
+            .iter()
+            .map(|value| match value {
+                msgpack::Value::Str(value) => value.clone(),
+                msgpack::Value::Int(value) => value.to_string(),
+                _ => "-".to_owned(),
+            })
+            .collect();
+        Some(Ok(fields))
+    })));
+}
+
+/// Loads the given recording or snapshot, so that all further reads are served from it instead of the filesystem
+///
+/// Returns the path of the directory that the recorded scan started from
+///
+/// # Arguments
+///
+/// - `p_path` - path of the recording or snapshot
+pub fn load_replay(p_path: &path::Path) -> std::io::Result<String> {
+    let (root, replay) = load_recording(p_path)?;
+
+    let _ = REPLAY.set(replay);
+
+    return Ok(root);
+}
+
+/// Loads the given recording or snapshot on its own, without serving any reads from it (for comparing a recorded scan
+/// with another one)
+///
+/// # Arguments
+///
+/// - `p_path` - path of the recording or snapshot
+pub fn load_tree(p_path: &path::Path) -> std::io::Result<RecordedTree> {
+    let (root, replay) = load_recording(p_path)?;
+
+    return Ok(RecordedTree {
+        root: path::PathBuf::from(root),
+        replay,
+    });
+}
+
+/// Returns the results of all reads stored in the given recording or snapshot, along with the path of the directory
+/// that the recorded scan started from
+///
+/// # Arguments
+///
+/// - `p_path` - path of the recording or snapshot
+fn load_recording(p_path: &path::Path) -> std::io::Result<(String, Replay)> {
+    let mut root = String::new();
+    let mut replay = Replay {
+        dirs: HashMap::new(),
+        canonical: HashMap::new(),
+        targets: HashMap::new(),
+    };
+
+    // directories of a snapshot in the order they were opened, which its entries refer to by index
+    let mut snapshot_dirs: Vec<path::PathBuf> = Vec::new();
+
+    for fields in read_events(p_path)? {
+        let fields = fields?;
+
+        match fields.iter().map(String::as_str).collect::<Vec<&str>>()[..] {
+            ["R", init_path] => root = init_path.to_owned(),
+            ["D", dir_path] => {
+                snapshot_dirs.push(path::PathBuf::from(dir_path));
+                replay
+                    .dirs
+                    .insert(path::PathBuf::from(dir_path), Ok(Vec::new()));
+            }
+            ["X", dir_path, error] => {
+                replay
+                    .dirs
+                    .insert(path::PathBuf::from(dir_path), Err(error.to_owned()));
+            }
+            ["E", dir_path, name, kind, len, mode, modified] => {
+                let (Some(kind), Ok(len), Ok(mode)) =
+                    (EntryKind::from_name(kind), len.parse(), mode.parse())
+                else {
+                    continue;
+                };
+
+                let dir_path = path::Path::new(dir_path);
+                let metadata = EntryMetadata {
+                    kind,
+                    len,
+                    mode,
+                    modified: parse_time(modified),
+                    raw: None,
+                };
+
+                if let Some(Ok(entries)) = replay.dirs.get_mut(dir_path) {
+                    entries.push(Ok((dir_path.join(name), metadata)));
+                }
+            }
+            ["E", dir_idx, name, kind, len, mode, modified, ..] => {
+                let (Some(dir_path), Some(kind), Ok(len), Ok(mode)) = (
+                    dir_idx
+                        .parse()
+                        .ok()
+                        .and_then(|idx: usize| snapshot_dirs.get(idx)),
+                    EntryKind::from_name(kind),
+                    len.parse(),
+                    mode.parse(),
+                ) else {
+                    continue;
+                };
+
+                let path_os = dir_path.join(name);
+                let metadata = EntryMetadata {
+                    kind,
+                    len,
+                    mode,
+                    modified: parse_time(modified),
+                    raw: None,
+                };
+
+                // only the canonical paths of symlinks are stored, the others follow from that of their directory
+                if kind != EntryKind::Symlink {
+                    if let Some(canonical) = replay.canonical.get(dir_path) {
+                        let canonical = canonical.clone().map(|canonical| canonical.join(name));
+                        replay.canonical.insert(path_os.clone(), canonical);
+                    }
+                }
+
+                if let Some(Ok(entries)) = replay.dirs.get_mut(dir_path) {
+                    entries.push(Ok((path_os, metadata)));
+                }
+            }
+            ["e", dir, error] => {
+                // text recordings refer to the directory by its path, and snapshots by its index
+                let dir_path = match snapshot_dirs.is_empty() {
+                    true => Some(path::Path::new(dir)),
+                    false => dir
+                        .parse()
+                        .ok()
+                        .and_then(|idx: usize| snapshot_dirs.get(idx))
+                        .map(path::PathBuf::as_path),
+                };
+
+                if let Some(Ok(entries)) =
+                    dir_path.and_then(|dir_path| replay.dirs.get_mut(dir_path))
+                {
+                    entries.push(Err(error.to_owned()));
+                }
+            }
+            ["C", entry_path, canonical] => {
+                replay.canonical.insert(
+                    path::PathBuf::from(entry_path),
+                    Ok(path::PathBuf::from(canonical)),
+                );
+            }
+            ["c", entry_path, error] => {
+                replay
+                    .canonical
+                    .insert(path::PathBuf::from(entry_path), Err(error.to_owned()));
+            }
+            ["T", entry_path, is_dir] => {
+                replay
+                    .targets
+                    .insert(path::PathBuf::from(entry_path), is_dir == "1");
+            }
+            _ => {}
+        }
+    }
+
+    return Ok((root, replay));
+}
+
+/// Returns the number of entries in the given recording or snapshot (used for estimating the progress of a later scan)
+///
+/// # Arguments
+///
+/// - `p_path` - path of the recording or snapshot
+pub fn count_entries(p_path: &path::Path) -> std::io::Result<u64> {
+    let mut entry_cnt: u64 = 0;
+    for fields in read_events(p_path)? {
+        // entries that could not be read are counted too, since they are read again by the next scan
+        if matches!(fields?.first().map(String::as_str), Some("E" | "e")) {
+            entry_cnt += 1;
+        }
+    }
+
+    return Ok(entry_cnt);
+}
+
+/// Returns the error for a read that is not part of the recording being replayed
+///
+/// # Arguments
+///
+/// - `p_path` - path of the entry that was read
+fn not_recorded(p_path: &path::Path) -> std::io::Error {
+    return std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        format!(
+            "\"{}\" is not part of the recording",
+            p_path.to_string_lossy()
+        ),
+    );
+}
+
+/// Returns the error that a recorded error message stands for (with the kind of the original error if it came from the
+/// operating system, so that errors can be told apart by their kinds while replaying)
+///
+/// # Arguments
+///
+/// - `p_error` - the recorded message (such as `Permission denied (os error 13)`)
+fn replayed_error(p_error: &str) -> std::io::Error {
+    let code = p_error
+        .strip_suffix(')')
+        .and_then(|error| error.rsplit_once("(os error "))
+        .and_then(|(_, code)| code.parse::<i32>().ok());
+
+    return match code {
+        Some(code) if std::io::Error::from_raw_os_error(code).to_string() == p_error => {
+            std::io::Error::from_raw_os_error(code)
+        }
+        _ => std::io::Error::other(p_error.to_owned()),
+    };
+}
+
+/// Returns an iterator over the entries of a directory, each along with its metadata
+///
+/// A directory that can not be read is handled as set with `--on-error` before its error is returned
+///
+/// # Arguments
+///
+/// - `p_dir_path` - path of the directory
+pub fn read_dir(p_dir_path: &path::Path) -> std::io::Result<ReadEntries> {
+    return open_entries(p_dir_path).inspect_err(|error| errors::dir_error(p_dir_path, error));
+}
+
+/// Returns an iterator over the entries of a directory, each along with its metadata (from the recording, the walk pool
+/// or the filesystem)
+///
+/// # Arguments
+///
+/// - `p_dir_path` - path of the directory
+fn open_entries(p_dir_path: &path::Path) -> std::io::Result<ReadEntries> {
+    if let Some(replay) = REPLAY.get() {
+        let entries = match replay.dirs.get(p_dir_path) {
+            Some(Ok(entries)) => entries
+                .iter()
+                .map(|entry| entry.clone().map_err(std::io::Error::other))
+                .collect::<Vec<_>>(),
+            Some(Err(error)) => return Err(replayed_error(error)),
+            None => return Err(not_recorded(p_dir_path)),
+        };
+
+        if get_option(PrgOptions::Ordered) {
+            return Ok(buffer_entries(entries, None));
+        }
+        return Ok(ReadEntries::Replayed(entries.into_iter()));
+    }
+
+    if let Some((entries, ignore_rules)) = DIRS_AHEAD.take(p_dir_path) {
+        ignore::enter_dir(p_dir_path, &ignore_rules);
+        return Ok(buffer_entries(entries?, Some(&ignore_rules)));
+    }
+
+    let ignore_rules = ignore::dir_rules(p_dir_path);
+    let entries = read_dir_live(p_dir_path, ignore_rules.clone())?;
+
+    // the entries need to be known up front to be sorted, handed to the pools or drawn with tree lines
+    if get_option(PrgOptions::Ordered)
+        || pool::walk_pool().is_some()
+        || pool::work_pool().is_some()
+        || draws_tree_lines()
+    {
+        return Ok(buffer_entries(
+            entries.collect_unlimited(),
+            Some(&ignore_rules),
+        ));
+    }
+    return Ok(entries);
+}
+
+/// Returns an iterator over the entries of a directory that are read from the filesystem one at a time
+///
+/// # Arguments
+///
+/// - `p_dir_path` - path of the directory
+/// - `p_ignore_rules` - ignore rules of the directory
+fn read_dir_live(
+    p_dir_path: &path::Path,
+    p_ignore_rules: ignore::IgnoreRules,
+) -> std::io::Result<ReadEntries> {
+    return match open_dir(p_dir_path) {
+        Ok(entries) => {
+            with_recording(|recording| recording.open_dir(p_dir_path));
+            Ok(ReadEntries::Live(
+                entries,
+                p_dir_path.to_path_buf(),
+                p_ignore_rules,
+            ))
+        }
+        Err(error) => {
+            record_line(&["X", &p_dir_path.to_string_lossy(), &error.to_string()]);
+            Err(error)
+        }
+    };
+}
+
+/// Returns an iterator over the entries of a directory read straight from the filesystem (neither recorded nor
+/// replayed), for the traversals that do not need the metadata of every entry
+///
+/// A directory that is the same directory as one above it is reported as an error instead of being read, since it
+/// would be descended into forever. Like with [read_dir], the entries count towards the limit of `--max-entries` and
+/// directories that can not be read are handled as set with `--on-error`
+///
+/// # Arguments
+///
+/// - `p_dir_path` - path of the directory
+pub fn read_dir_unrecorded(
+    p_dir_path: &path::Path,
+) -> std::io::Result<impl Iterator<Item = std::io::Result<fs::DirEntry>>> {
+    let entries = open_dir(p_dir_path).inspect_err(|error| errors::dir_error(p_dir_path, error))?;
+
+    return Ok(entries.map_while(|entry| limit::take_entry().then_some(entry)));
+}
+
+/// Opens a directory to be read from the filesystem (failing if it is the same directory as one above it)
+///
+/// # Arguments
+///
+/// - `p_dir_path` - path of the directory
+fn open_dir(p_dir_path: &path::Path) -> std::io::Result<fs::ReadDir> {
+    if let Some(ancestor) = loop_ancestor(p_dir_path) {
+        return Err(std::io::Error::other(format!(
+            "\"{}\" is the same directory as \"{}\" (a filesystem loop)",
+            p_dir_path.to_string_lossy(),
+            ancestor.to_string_lossy()
+        )));
+    }
+
+    return fs::read_dir(p_dir_path);
+}
+
+/// Returns the directory above a directory being read that is the same directory as it, such as through a bind mount
+/// ([None] if there is none, so that reading it can not loop forever)
+///
+/// Remembers the device and inode of the directory, so that the directories and symlinks within it can be checked
+/// against it
+///
+/// # Arguments
+///
+/// - `p_dir_path` - path of the directory
+#[cfg_attr(not(target_family = "unix"), allow(unused_variables))]
+fn loop_ancestor(p_dir_path: &path::Path) -> Option<path::PathBuf> {
+    #[cfg(target_family = "unix")]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        let metadata = fs::metadata(p_dir_path).ok()?;
+        let id = (metadata.dev(), metadata.ino());
+
+        let mut dir_ids = DIR_IDS.lock().ok()?;
+        let ancestor = p_dir_path
+            .ancestors()
+            .skip(1)
+            .find(|ancestor| dir_ids.get(*ancestor) == Some(&id))
+            .map(path::Path::to_path_buf);
+
+        match ancestor {
+            Some(_) => {
+                if let Ok(mut loops) = LOOPS.lock() {
+                    loops.insert(p_dir_path.to_path_buf());
+                }
+            }
+            None => {
+                dir_ids.insert(p_dir_path.to_path_buf(), id);
+            }
+        }
+        return ancestor;
+    }
+
+    #[cfg(not(target_family = "unix"))]
+    {
+        return None;
+    }
+}
+
+/// Returns the number of directories (and symlinks followed with `--follow`) that were not read since they loop back to
+/// a directory above them
+pub fn loop_cnt() -> usize {
+    return LOOPS.lock().map(|loops| loops.len()).unwrap_or(0);
+}
+
+/// Returns whether a directory that a symlink points to is the directory that the symlink lies in or one of the
+/// directories above it (so following the symlink would loop back)
+///
+/// Remembers the symlink if it loops back, so that it is counted along with the other loops
+///
+/// # Arguments
+///
+/// - `p_dir_path` - path of the directory that the symlink lies in
+/// - `p_link_path` - path of the symlink
+/// - `p_target` - metadata of the directory that the symlink points to
+#[cfg_attr(not(target_family = "unix"), allow(unused_variables))]
+fn loops_back(p_dir_path: &path::Path, p_link_path: &path::Path, p_target: &fs::Metadata) -> bool {
+    #[cfg(target_family = "unix")]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        let id = (p_target.dev(), p_target.ino());
+        let Ok(dir_ids) = DIR_IDS.lock() else {
+            return false;
+        };
+        let loops = p_dir_path
+            .ancestors()
+            .any(|ancestor| dir_ids.get(ancestor) == Some(&id));
+
+        // the same symlink may be checked again when its directory is read ahead, so it is only counted once
+        if loops {
+            if let Ok(mut loop_paths) = LOOPS.lock() {
+                loop_paths.insert(p_link_path.to_path_buf());
+            }
+        }
+        return loops;
+    }
+
+    #[cfg(not(target_family = "unix"))]
+    {
+        return false;
+    }
+}
+
+/// Returns an iterator over entries of a directory that were all read up front, sorting them by name if the order
+/// does not have to match the filesystem, and handing the subdirectories to the walk pool to be read ahead
+///
+/// # Arguments
+///
+/// - `p_entries` - the entries
+/// - `p_ignore_rules` - ignore rules of the directory ([None] if the entries were replayed)
+fn buffer_entries(
+    mut p_entries: Vec<ReadEntry>,
+    p_ignore_rules: Option<&ignore::IgnoreRules>,
+) -> ReadEntries {
+    if get_option(PrgOptions::Ordered) {
+        p_entries.sort_by(|first, second| match (first, second) {
+            (Ok((first, _)), Ok((second, _))) => first.file_name().cmp(&second.file_name()),
+            (first, second) => first.is_err().cmp(&second.is_err()),
+        });
+    }
+
+    // nothing is read ahead while recording, since a read that is discarded while it is running would still be
+    // recorded (along with the later read of the same directory)
+    let ahead = match (pool::walk_pool(), p_ignore_rules) {
+        (Some(walk_pool), Some(ignore_rules)) if !is_recording() => {
+            let subdirs = p_entries
+                .iter()
+                .filter_map(|entry| match entry {
+                    Ok((path_os, metadata)) if metadata.is_dir() => {
+                        Some((path_os.clone(), ignore_rules.clone()))
+                    }
+                    _ => None,
+                })
+                .collect();
+
+            Some(
+                DIRS_AHEAD.submit(walk_pool, subdirs, |dir_path, ignore_rules| {
+                    let ignore_rules = ignore_rules.below(dir_path);
+                    let entries = read_dir_live(dir_path, ignore_rules.clone())
+                        .map(ReadEntries::collect_unlimited);
+                    (entries, ignore_rules)
+                }),
+            )
+        }
+        _ => None,
+    };
+
+    return ReadEntries::Buffered {
+        entries: p_entries.into_iter(),
+        _ahead: ahead,
+    };
+}
+
+/// Returns the canonical, absolute form of a path with all symlinks resolved
+///
+/// # Arguments
+///
+/// - `p_path` - the path to canonicalize
+pub fn canonicalize(p_path: &path::Path) -> std::io::Result<path::PathBuf> {
+    if let Some(replay) = REPLAY.get() {
+        return match replay.canonical.get(p_path) {
+            Some(Ok(canonical)) => Ok(canonical.clone()),
+            Some(Err(error)) => Err(std::io::Error::other(error.clone())),
+            None => Err(not_recorded(p_path)),
+        };
+    }
+
+    return p_path.canonicalize();
+}
+
+/// Returns the path that a symlink points to, as it is stored in the symlink (recordings do not hold it, so it can not
+/// be read while replaying)
+///
+/// # Arguments
+///
+/// - `p_path` - path of the symlink
+pub fn read_link(p_path: &path::Path) -> std::io::Result<path::PathBuf> {
+    if REPLAY.get().is_some() {
+        return Err(not_recorded(p_path));
+    }
+
+    return fs::read_link(p_path);
+}
+
+/// Returns whether a recording is being replayed (recordings do not hold the contents of files, so they can not be
+/// read while replaying)
+pub fn is_replaying() -> bool {
+    return REPLAY.get().is_some();
+}
+
+/// Returns whether a path (following symlinks) is a directory
+///
+/// # Arguments
+///
+/// - `p_path` - the path to check
+pub fn is_dir(p_path: &path::Path) -> bool {
+    if let Some(replay) = REPLAY.get() {
+        return replay.targets.get(p_path).copied().unwrap_or(false);
+    }
+
+    return p_path.is_dir();
+}
@@ -0,0 +1,541 @@
+//! Writing the entries of a scan into a SQLite database (for `--export-sqlite`)
+//!
+//! The entries are selected like with `--format`, and each one is written as a row of the `entries` table along with
+//! the id of the directory it lies in (the initial directory is the row with the id 1). No SQLite library is needed,
+//! since a database holding a single table that is only appended to is simple to write directly in the SQLite file
+//! format. The rows are packed into the leaf pages of the table as they are read, so the memory held does not grow with
+//! the number of entries, and the interior pages above them along with the schema on the first page are written once
+//! all the rows are known
+
+use std::fs;
+use std::io::{Seek, Write};
+use std::path;
+use std::sync;
+
+use crate::i18n::{tr, Msg};
+use crate::{export, int_to_formatted_slice, record};
+
+/// Size of the pages of the database
+const PAGE_SIZE: usize = 4096;
+
+/// Largest payload of a row that is stored within a leaf page (larger payloads would need overflow pages, which are
+/// never needed since names are much shorter)
+const MAX_LOCAL_PAYLOAD: usize = PAGE_SIZE - 35;
+
+/// Size of the header of the first page of the database
+const DB_HEADER_SIZE: usize = 100;
+
+/// Size of the header of a leaf page of a table
+const LEAF_HEADER_SIZE: usize = 8;
+
+/// Size of the header of an interior page of a table
+const INTERIOR_HEADER_SIZE: usize = 12;
+
+/// Largest number of children of an interior page (each cell takes up a pointer of 2 bytes, the page number of the
+/// child and a key of at most 9 bytes, and the last child is held in the header)
+const MAX_INTERIOR_CHILDREN: usize = (PAGE_SIZE - INTERIOR_HEADER_SIZE) / (2 + 4 + 9) + 1;
+
+/// Version of SQLite that the format of the database corresponds to (stored in its header)
+const SQLITE_VERSION_NUMBER: u32 = 3_046_000;
+
+/// Statement that creates the table of entries (stored in the schema of the database)
+const SCHEMA_SQL: &str = "CREATE TABLE entries (\
+    id INTEGER PRIMARY KEY, \
+    parent_id INTEGER, \
+    name TEXT NOT NULL, \
+    type TEXT NOT NULL, \
+    size INTEGER, \
+    mtime INTEGER, \
+    perms INTEGER)";
+
+/// Path of the database to write the entries into ([None] if `--export-sqlite` was not given)
+static DB_PATH: sync::OnceLock<path::PathBuf> = sync::OnceLock::new();
+
+/// Export that the entries are being written into (set by [begin])
+static EXPORT: sync::Mutex<Option<SqliteExport>> = sync::Mutex::new(None);
+
+/// Value of a column of a row
+enum Value<'a> {
+    Null,
+    Integer(i64),
+    Text(&'a str),
+}
+
+/// Writer of a database holding a single table, whose rows are appended in the order of their ids
+struct TableWriter {
+    file: std::io::BufWriter<fs::File>,
+    /// Cells of the leaf page being filled
+    cells: Vec<Vec<u8>>,
+    /// Number of bytes of the leaf page being filled that are used (including its header)
+    used: usize,
+    /// Number of pages written so far (the first page is only written once the table is complete)
+    page_cnt: u32,
+    /// Page numbers of the leaf pages written so far, each along with the largest id within it
+    leaves: Vec<(u32, i64)>,
+    /// Id of the row written last
+    last_id: i64,
+}
+
+/// State of an export into a database
+struct SqliteExport {
+    table: TableWriter,
+    /// Directories along the path of the entry written last that were written themselves, each along with its id
+    dirs: Vec<(path::PathBuf, i64)>,
+    /// First error that occurred while writing (nothing more is written after an error)
+    error: Option<std::io::Error>,
+}
+
+/// Appends a variable-length integer in the format used by SQLite (big endian groups of 7 bits, with the high bit set
+/// in all but the last byte, and 8 bits in the ninth byte)
+///
+/// # Arguments
+///
+/// - `p_buff` - buffer to append to
+/// - `p_value` - the integer
+fn put_varint(p_buff: &mut Vec<u8>, mut p_value: u64) {
+    if p_value > 0x00ff_ffff_ffff_ffff {
+        let mut bytes = [0u8; 9];
+        bytes[8] = p_value as u8;
+        p_value >>= 8;
+        for byte in bytes[..8].iter_mut().rev() {
+            *byte = (p_value & 0x7f) as u8 | 0x80;
+            p_value >>= 7;
+        }
+        p_buff.extend_from_slice(&bytes);
+        return;
+    }
+
+    let mut groups = vec![(p_value & 0x7f) as u8];
+    p_value >>= 7;
+    while p_value > 0 {
+        groups.push((p_value & 0x7f) as u8 | 0x80);
+        p_value >>= 7;
+    }
+    p_buff.extend(groups.iter().rev());
+}
+
+/// Returns the serial type of an integer (which selects the number of bytes it is stored in) along with those bytes
+///
+/// # Arguments
+///
+/// - `p_value` - the integer
+fn integer_serial(p_value: i64) -> (u64, Vec<u8>) {
+    let (serial_type, len) = match p_value {
+        0 => (8, 0),
+        1 => (9, 0),
+        -0x80..=0x7f => (1, 1),
+        -0x8000..=0x7fff => (2, 2),
+        -0x80_0000..=0x7f_ffff => (3, 3),
+        -0x8000_0000..=0x7fff_ffff => (4, 4),
+        -0x8000_0000_0000..=0x7fff_ffff_ffff => (5, 6),
+        _ => (6, 8),
+    };
+
+    return (serial_type, p_value.to_be_bytes()[8 - len..].to_vec());
+}
+
+/// Returns a row encoded in the record format of SQLite (a header holding the serial type of each value, followed by
+/// the values)
+///
+/// # Arguments
+///
+/// - `p_values` - values of the columns of the row
+fn encode_record(p_values: &[Value]) -> Vec<u8> {
+    let mut header = Vec::new();
+    let mut body = Vec::new();
+
+    for value in p_values {
+        match value {
+            Value::Null => put_varint(&mut header, 0),
+            Value::Integer(value) => {
+                let (serial_type, bytes) = integer_serial(*value);
+                put_varint(&mut header, serial_type);
+                body.extend_from_slice(&bytes);
+            }
+            Value::Text(text) => {
+                put_varint(&mut header, 2 * text.len() as u64 + 13);
+                body.extend_from_slice(text.as_bytes());
+            }
+        }
+    }
+
+    // the size of the header includes the varint that holds it
+    let mut header_size = header.len() + 1;
+    if header_size >= 0x80 {
+        header_size += 1;
+    }
+
+    let mut record = Vec::with_capacity(header_size + header.len() + body.len());
+    put_varint(&mut record, header_size as u64);
+    record.extend_from_slice(&header);
+    record.extend_from_slice(&body);
+
+    return record;
+}
+
+/// Returns a leaf page of a table holding cells (which need to fit into the page)
+///
+/// # Arguments
+///
+/// - `p_cells` - the cells, in the order of their ids
+/// - `p_header_offset` - offset of the header of the page (100 for the first page, which starts with the header of the
+///   database, 0 otherwise)
+fn leaf_page(p_cells: &[Vec<u8>], p_header_offset: usize) -> Vec<u8> {
+    let mut page = vec![0u8; PAGE_SIZE];
+
+    // the cells are stored at the end of the page, and their offsets after the header
+    let mut content_start = PAGE_SIZE;
+    for (idx, cell) in p_cells.iter().enumerate() {
+        content_start -= cell.len();
+        page[content_start..content_start + cell.len()].copy_from_slice(cell);
+
+        let pointer = p_header_offset + LEAF_HEADER_SIZE + 2 * idx;
+        page[pointer..pointer + 2].copy_from_slice(&(content_start as u16).to_be_bytes());
+    }
+
+    let header = &mut page[p_header_offset..p_header_offset + LEAF_HEADER_SIZE];
+    header[0] = 0x0d;
+    header[3..5].copy_from_slice(&(p_cells.len() as u16).to_be_bytes());
+    header[5..7].copy_from_slice(&((content_start % PAGE_SIZE) as u16).to_be_bytes());
+
+    return page;
+}
+
+/// Returns an interior page of a table pointing to its children
+///
+/// # Arguments
+///
+/// - `p_children` - page numbers of the children, each along with the largest id within it
+fn interior_page(p_children: &[(u32, i64)]) -> Vec<u8> {
+    let mut page = vec![0u8; PAGE_SIZE];
+    let (last, children) = p_children.split_last().unwrap_or((&(0, 0), &[]));
+
+    let mut content_start = PAGE_SIZE;
+    for (idx, (child, max_id)) in children.iter().enumerate() {
+        let mut cell = child.to_be_bytes().to_vec();
+        put_varint(&mut cell, *max_id as u64);
+
+        content_start -= cell.len();
+        page[content_start..content_start + cell.len()].copy_from_slice(&cell);
+
+        let pointer = INTERIOR_HEADER_SIZE + 2 * idx;
+        page[pointer..pointer + 2].copy_from_slice(&(content_start as u16).to_be_bytes());
+    }
+
+    page[0] = 0x05;
+    page[3..5].copy_from_slice(&(children.len() as u16).to_be_bytes());
+    page[5..7].copy_from_slice(&((content_start % PAGE_SIZE) as u16).to_be_bytes());
+    page[8..12].copy_from_slice(&last.0.to_be_bytes());
+
+    return page;
+}
+
+/// Returns a cell of a leaf page of a table holding a row
+///
+/// # Arguments
+///
+/// - `p_id` - id of the row
+/// - `p_record` - the row, encoded with [encode_record]
+fn leaf_cell(p_id: i64, p_record: &[u8]) -> Vec<u8> {
+    let mut cell = Vec::with_capacity(p_record.len() + 12);
+    put_varint(&mut cell, p_record.len() as u64);
+    put_varint(&mut cell, p_id as u64);
+    cell.extend_from_slice(p_record);
+
+    return cell;
+}
+
+impl TableWriter {
+    /// Returns a writer of a new database (an existing file is truncated)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_db_path` - path of the database
+    fn create(p_db_path: &path::Path) -> std::io::Result<TableWriter> {
+        let mut file = std::io::BufWriter::new(fs::File::create(p_db_path)?);
+
+        // the first page is written once the table is complete
+        file.write_all(&[0u8; PAGE_SIZE])?;
+
+        return Ok(TableWriter {
+            file,
+            cells: Vec::new(),
+            used: LEAF_HEADER_SIZE,
+            page_cnt: 1,
+            leaves: Vec::new(),
+            last_id: 0,
+        });
+    }
+
+    /// Writes a page after the ones written so far, and returns its page number
+    ///
+    /// # Arguments
+    ///
+    /// - `p_page` - the page
+    fn write_page(&mut self, p_page: &[u8]) -> std::io::Result<u32> {
+        self.file.write_all(p_page)?;
+        self.page_cnt += 1;
+
+        return Ok(self.page_cnt);
+    }
+
+    /// Writes the leaf page being filled
+    fn flush_leaf(&mut self) -> std::io::Result<()> {
+        let page = leaf_page(&self.cells, 0);
+        let page_no = self.write_page(&page)?;
+        self.leaves.push((page_no, self.last_id));
+
+        self.cells.clear();
+        self.used = LEAF_HEADER_SIZE;
+
+        return Ok(());
+    }
+
+    /// Appends a row to the table, whose id needs to be larger than the ids of the rows appended so far
+    ///
+    /// # Arguments
+    ///
+    /// - `p_id` - id of the row
+    /// - `p_values` - values of the columns of the row (the column of the id is stored as null)
+    fn append(&mut self, p_id: i64, p_values: &[Value]) -> std::io::Result<()> {
+        let record = encode_record(p_values);
+        if record.len() > MAX_LOCAL_PAYLOAD {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "row does not fit into a page",
+            ));
+        }
+
+        let cell = leaf_cell(p_id, &record);
+        if self.used + 2 + cell.len() > PAGE_SIZE {
+            self.flush_leaf()?;
+        }
+
+        self.used += 2 + cell.len();
+        self.cells.push(cell);
+        self.last_id = p_id;
+
+        return Ok(());
+    }
+
+    /// Writes the interior pages of the table and the first page (which holds the header and the schema of the
+    /// database), and flushes the database to its file
+    fn finish(mut self) -> std::io::Result<()> {
+        if !self.cells.is_empty() || self.leaves.is_empty() {
+            self.flush_leaf()?;
+        }
+
+        // each level of interior pages points to the pages of the level below, until a single page (the root) is left
+        let mut level = std::mem::take(&mut self.leaves);
+        while level.len() > 1 {
+            // the children are spread evenly, so that every interior page has at least two of them
+            let page_cnt = level.len().div_ceil(MAX_INTERIOR_CHILDREN);
+            let mut parents = Vec::with_capacity(page_cnt);
+
+            let mut start = 0;
+            for idx in 0..page_cnt {
+                let end = start + (level.len() - start) / (page_cnt - idx);
+                let children = &level[start..end];
+
+                let page_no = self.write_page(&interior_page(children))?;
+                parents.push((page_no, children[children.len() - 1].1));
+                start = end;
+            }
+
+            level = parents;
+        }
+        let root_page = level[0].0;
+
+        let schema = encode_record(&[
+            Value::Text("table"),
+            Value::Text("entries"),
+            Value::Text("entries"),
+            Value::Integer(root_page as i64),
+            Value::Text(SCHEMA_SQL),
+        ]);
+        let mut first_page = leaf_page(&[leaf_cell(1, &schema)], DB_HEADER_SIZE);
+
+        let header = &mut first_page[..DB_HEADER_SIZE];
+        header[..16].copy_from_slice(b"SQLite format 3\0");
+        header[16..18].copy_from_slice(&(PAGE_SIZE as u16).to_be_bytes());
+        // file format versions (legacy rollback journal), no reserved bytes, and the fixed payload fractions
+        header[18..24].copy_from_slice(&[1, 1, 0, 64, 32, 32]);
+        // change counter, size of the database in pages
+        header[24..28].copy_from_slice(&1u32.to_be_bytes());
+        header[28..32].copy_from_slice(&self.page_cnt.to_be_bytes());
+        // schema cookie, schema format, and UTF-8 as the text encoding
+        header[40..44].copy_from_slice(&1u32.to_be_bytes());
+        header[44..48].copy_from_slice(&4u32.to_be_bytes());
+        header[56..60].copy_from_slice(&1u32.to_be_bytes());
+        // the size of the database is only trusted if this matches the change counter
+        header[92..96].copy_from_slice(&1u32.to_be_bytes());
+        header[96..100].copy_from_slice(&SQLITE_VERSION_NUMBER.to_be_bytes());
+
+        self.file.seek(std::io::SeekFrom::Start(0))?;
+        self.file.write_all(&first_page)?;
+        self.file.flush()?;
+
+        return Ok(());
+    }
+}
+
+/// Sets the path of the database to write the entries into (has no effect if it is already set)
+///
+/// # Arguments
+///
+/// - `p_db_path` - path of the database
+pub fn set_db_path(p_db_path: &str) {
+    let _ = DB_PATH.set(path::PathBuf::from(p_db_path));
+}
+
+/// Returns the path of the database to write the entries into ([None] if `--export-sqlite` was not given)
+pub fn get_db_path() -> Option<&'static path::Path> {
+    return DB_PATH.get().map(path::PathBuf::as_path);
+}
+
+/// Returns the time of the last modification of an entry in seconds since the UNIX epoch ([None] if it is not
+/// available)
+///
+/// # Arguments
+///
+/// - `p_metadata` - metadata of the entry
+fn modified_secs(p_metadata: &record::EntryMetadata) -> Option<i64> {
+    let modified = p_metadata.modified().ok()?;
+
+    return Some(chrono::DateTime::<chrono::Utc>::from(modified).timestamp());
+}
+
+/// Returns the permission bits of an entry ([None] on other platforms)
+///
+/// # Arguments
+///
+/// - `p_metadata` - metadata of the entry
+#[cfg_attr(not(target_family = "unix"), allow(unused_variables))]
+fn permission_bits(p_metadata: &record::EntryMetadata) -> Option<i64> {
+    #[cfg(target_family = "unix")]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        return Some((p_metadata.permissions().mode() & 0o7777) as i64);
+    }
+
+    #[cfg(not(target_family = "unix"))]
+    return None;
+}
+
+/// Creates the database and writes the initial directory into it (has no effect if `--export-sqlite` was not given)
+///
+/// Returns the error if the database could not be created
+///
+/// # Arguments
+///
+/// - `p_init_path` - path of the directory that the scan starts from
+pub fn begin(p_init_path: &str) -> std::io::Result<()> {
+    let Some(db_path) = get_db_path() else {
+        return Ok(());
+    };
+
+    let mut table = TableWriter::create(db_path)?;
+
+    // the metadata of the initial directory is not read by the scan, so only its path is known
+    table.append(
+        1,
+        &[
+            Value::Null,
+            Value::Null,
+            Value::Text(p_init_path),
+            Value::Text("dir"),
+            Value::Null,
+            Value::Null,
+            Value::Null,
+        ],
+    )?;
+
+    *EXPORT.lock().unwrap() = Some(SqliteExport {
+        table,
+        dirs: vec![(path::PathBuf::from(p_init_path), 1)],
+        error: None,
+    });
+
+    return Ok(());
+}
+
+/// Writes an entry into the database
+///
+/// The id of its directory is stored along with it, unless the directory was not written itself (such as while
+/// searching, when the name of the directory does not match)
+///
+/// # Arguments
+///
+/// - `p_path_os` - path of the entry
+/// - `p_metadata` - metadata of the entry
+pub fn insert_entry(p_path_os: &path::Path, p_metadata: &record::EntryMetadata) {
+    let mut export = EXPORT.lock().unwrap();
+    let Some(export) = export.as_mut() else {
+        return;
+    };
+    if export.error.is_some() {
+        return;
+    }
+
+    // the entries are written depth first, so the directories that the entry does not lie in have been left
+    while export.dirs.len() > 1 && !p_path_os.starts_with(&export.dirs[export.dirs.len() - 1].0) {
+        export.dirs.pop();
+    }
+    let parent_id = export
+        .dirs
+        .last()
+        .filter(|(dir_path, _)| Some(dir_path.as_path()) == p_path_os.parent())
+        .map(|(_, id)| *id);
+
+    let id = export.table.last_id + 1;
+    let name = p_path_os.file_name().unwrap_or_default().to_string_lossy();
+    let size = if p_metadata.is_dir() {
+        None
+    } else {
+        Some(p_metadata.len() as i64)
+    };
+
+    let result = export.table.append(
+        id,
+        &[
+            Value::Null,
+            parent_id.map_or(Value::Null, Value::Integer),
+            Value::Text(&name),
+            Value::Text(export::type_name(p_metadata)),
+            size.map_or(Value::Null, Value::Integer),
+            modified_secs(p_metadata).map_or(Value::Null, Value::Integer),
+            permission_bits(p_metadata).map_or(Value::Null, Value::Integer),
+        ],
+    );
+
+    match result {
+        Ok(()) if p_metadata.is_dir() => export.dirs.push((p_path_os.to_path_buf(), id)),
+        Ok(()) => {}
+        Err(error) => export.error = Some(error),
+    }
+}
+
+/// Completes the database and prints the number of entries written into it (has no effect if `--export-sqlite` was
+/// not given)
+pub fn finish() {
+    let Some(export) = EXPORT.lock().unwrap().take() else {
+        return;
+    };
+    let db_path = get_db_path()
+        .unwrap_or(path::Path::new(""))
+        .to_string_lossy();
+
+    // the initial directory is not counted as an entry
+    let entry_cnt = int_to_formatted_slice((export.table.last_id - 1) as u64).to_owned();
+
+    let result = match export.error {
+        Some(error) => Err(error),
+        None => export.table.finish(),
+    };
+
+    match result {
+        Ok(()) => print!("{}", tr!(Msg::SqliteSummary, db_path, entry_cnt)),
+        Err(error) => print!("{}", tr!(Msg::SqliteWriteError, db_path, error)),
+    }
+}
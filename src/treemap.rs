@@ -0,0 +1,518 @@
+//! Rendering the cumulative sizes of directories as a squarified treemap (for `--treemap`)
+//!
+//! Like WinDirStat, each directory is drawn as a rectangle whose area is proportional to its size, with its
+//! subdirectories (and its files, if they are shown) nested inside it. The rectangles within a directory are laid out
+//! with the squarified algorithm of Bruls, Huizing and van Wijk, which keeps them close to squares so that they stay
+//! readable. Directories that lie deeper than the maximum recursion depth are drawn as a single rectangle, whose size is
+//! calculated with [calc_dir_size]
+
+use std::fmt::Write;
+use std::fs;
+use std::path;
+
+use crate::i18n::{tr, Msg};
+use crate::{calc_dir_size, get_option, int_to_formatted_slice, record, PrgOptions};
+
+/// Width of the image
+const WIDTH: f64 = 1280.0;
+
+/// Height of the image
+const HEIGHT: f64 = 800.0;
+
+/// Height of the bar at the top of a directory that its name is written in
+const HEADER_HEIGHT: f64 = 14.0;
+
+/// Gap between the edges of a directory and the rectangles nested in it
+const PADDING: f64 = 2.0;
+
+/// Approximate width of a character of the labels (which are written in a monospace font of size 11)
+const CHAR_WIDTH: f64 = 6.7;
+
+/// Length of the sides below which rectangles are not drawn
+const MIN_SIDE: f64 = 1.0;
+
+/// Angle between the hues of consecutive directories directly under the initial one (the golden angle, so that
+/// neighbours never get similar colours)
+const HUE_STEP: f64 = 137.508;
+
+/// Entry of the tree that is drawn
+struct Node {
+    /// Name of the entry (the whole path for the initial directory)
+    name: String,
+    /// Cumulative size of the entry ([None] if it could not be calculated)
+    size: Option<u64>,
+    /// Whether the entry is a directory
+    is_dir: bool,
+    /// Entries nested in the directory, largest first (always empty for files and for directories that lie deeper
+    /// than the maximum recursion depth)
+    children: Vec<Node>,
+}
+
+/// Area of the image that an entry is drawn in
+#[derive(Clone, Copy)]
+struct Rect {
+    x: f64,
+    y: f64,
+    w: f64,
+    h: f64,
+}
+
+/// Returns whether the entries of a directory are drawn within it (the entries of the initial directory are always
+/// drawn, while those of nested directories are only drawn if the recursive option is set and the maximum depth has
+/// not been crossed)
+///
+/// # Arguments
+///
+/// - `p_max_level` - maximum level upto which entries are drawn (a value of 0 denotes no limit)
+/// - `p_level` - level at which the entries of the directory lie (0 for the initial directory)
+fn keeps_children(p_max_level: &u64, p_level: usize) -> bool {
+    return p_level == 0
+        || (get_option(PrgOptions::ShowRecursive)
+            && (*p_max_level == 0u64 || p_level <= (*p_max_level as usize)));
+}
+
+/// Builds the tree of entries that are drawn within a directory, calculating the cumulative size of each directory
+///
+/// Returns the error if the directory could not be read
+///
+/// # Arguments
+///
+/// - `p_init_path` - path of the initial directory (used in error messages)
+/// - `p_max_level` - maximum level upto which entries are drawn (a value of 0 denotes no limit)
+/// - `p_level` - level at which the entries of the directory lie (0 for the initial directory)
+/// - `p_dir_path` - path of the directory
+/// - `p_name` - name that the directory is labelled with
+fn build_tree(
+    p_init_path: &path::Path,
+    p_max_level: &u64,
+    p_level: usize,
+    p_dir_path: &path::Path,
+    p_name: String,
+) -> std::io::Result<Node> {
+    let mut node = Node {
+        name: p_name,
+        size: Some(0),
+        is_dir: true,
+        children: Vec::new(),
+    };
+
+    for entry in record::read_dir(p_dir_path)? {
+        let (path_os, metadata) = match entry {
+            Ok(entry) => entry,
+            Err(error) => {
+                if get_option(PrgOptions::ShowErrors) {
+                    eprint!(
+                        "{}",
+                        tr!(
+                            Msg::DirSizeEntryError,
+                            p_dir_path.to_string_lossy(),
+                            p_init_path.to_string_lossy(),
+                            error
+                        )
+                    );
+                }
+                node.size = None;
+                continue;
+            }
+        };
+
+        // symlinks do not add to the size of a directory (same as calc_dir_size)
+        if metadata.is_symlink() {
+            continue;
+        }
+
+        let name = path_os
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned();
+
+        let child = if metadata.is_file() {
+            // files that are not drawn still take up their share of the directory
+            node.size = node.size.map(|size| size + metadata.len());
+            if !get_option(PrgOptions::ShowFiles) {
+                continue;
+            }
+
+            Node {
+                name,
+                size: Some(metadata.len()),
+                is_dir: false,
+                children: Vec::new(),
+            }
+        } else if metadata.is_dir() {
+            let child = if keeps_children(p_max_level, 1 + p_level) {
+                build_tree(
+                    p_init_path,
+                    p_max_level,
+                    1 + p_level,
+                    &path_os,
+                    name.clone(),
+                )
+            } else {
+                Ok(Node {
+                    name: name.clone(),
+                    size: calc_dir_size(p_init_path, &path_os),
+                    is_dir: true,
+                    children: Vec::new(),
+                })
+            };
+
+            let child = child.unwrap_or_else(|error| {
+                if get_option(PrgOptions::ShowErrors) {
+                    eprint!(
+                        "{}",
+                        tr!(
+                            Msg::DirSizeTraverseError,
+                            path_os.to_string_lossy(),
+                            p_init_path.to_string_lossy(),
+                            error
+                        )
+                    );
+                }
+                Node {
+                    name,
+                    size: None,
+                    is_dir: true,
+                    children: Vec::new(),
+                }
+            });
+
+            node.size = node
+                .size
+                .zip(child.size)
+                .map(|(size, child_size)| size + child_size);
+            child
+        } else {
+            continue;
+        };
+
+        node.children.push(child);
+    }
+
+    // the squarified layout places the largest entries first (entries of the same size are placed by name)
+    node.children.sort_by(|first, second| {
+        second
+            .size
+            .cmp(&first.size)
+            .then_with(|| first.name.cmp(&second.name))
+    });
+
+    return Ok(node);
+}
+
+/// Returns the aspect ratio of the most elongated rectangle of a row, if the row is laid out along a side
+///
+/// # Arguments
+///
+/// - `p_row` - areas of the rectangles of the row
+/// - `p_side` - length of the side that the row is laid out along
+fn worst_ratio(p_row: &[f64], p_side: f64) -> f64 {
+    let sum: f64 = p_row.iter().sum();
+    let max = p_row.iter().cloned().fold(f64::MIN, f64::max);
+    let min = p_row.iter().cloned().fold(f64::MAX, f64::min);
+
+    let side_sq = p_side * p_side;
+    return f64::max(side_sq * max / (sum * sum), (sum * sum) / (side_sq * min));
+}
+
+/// Returns the rectangles that areas are laid out in by the squarified algorithm, in the same order as the areas
+///
+/// Rows of areas are laid out along the shorter side of the space that is left, and an area is only added to a row if
+/// that does not make the most elongated rectangle of the row more elongated
+///
+/// # Arguments
+///
+/// - `p_areas` - areas to lay out, largest first (they must add up to the area of the rectangle)
+/// - `p_rect` - rectangle to lay the areas out in
+fn squarify(p_areas: &[f64], p_rect: Rect) -> Vec<Rect> {
+    let mut rects = Vec::with_capacity(p_areas.len());
+    let mut rest = p_rect;
+
+    let mut start = 0;
+    while start < p_areas.len() {
+        let side = f64::min(rest.w, rest.h);
+
+        let mut end = start + 1;
+        while end < p_areas.len()
+            && worst_ratio(&p_areas[start..=end], side) <= worst_ratio(&p_areas[start..end], side)
+        {
+            end += 1;
+        }
+
+        let row = &p_areas[start..end];
+        let row_sum: f64 = row.iter().sum();
+
+        // the row takes up a strip along the shorter side, and the rest is left for the next rows
+        if rest.w >= rest.h {
+            let strip_w = row_sum / rest.h;
+            let mut y = rest.y;
+            for area in row {
+                let h = area / strip_w;
+                rects.push(Rect {
+                    x: rest.x,
+                    y,
+                    w: strip_w,
+                    h,
+                });
+                y += h;
+            }
+            rest.x += strip_w;
+            rest.w -= strip_w;
+        } else {
+            let strip_h = row_sum / rest.w;
+            let mut x = rest.x;
+            for area in row {
+                let w = area / strip_h;
+                rects.push(Rect {
+                    x,
+                    y: rest.y,
+                    w,
+                    h: strip_h,
+                });
+                x += w;
+            }
+            rest.y += strip_h;
+            rest.h -= strip_h;
+        }
+
+        start = end;
+    }
+
+    return rects;
+}
+
+/// Returns text with the characters that are special in XML escaped
+///
+/// # Arguments
+///
+/// - `p_text` - the text
+fn xml_escape(p_text: &str) -> String {
+    let mut escaped = String::with_capacity(p_text.len());
+    for c in p_text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    return escaped;
+}
+
+/// Returns the colour that an entry is filled with
+///
+/// Each directory directly under the initial one gets its own hue, which the entries nested in it share, getting
+/// lighter with their depth (files are lighter than directories)
+///
+/// # Arguments
+///
+/// - `p_hue` - hue of the entry ([None] for the initial directory)
+/// - `p_depth` - level at which the entry lies
+/// - `p_is_dir` - whether the entry is a directory
+fn fill_color(p_hue: Option<f64>, p_depth: usize, p_is_dir: bool) -> String {
+    let lightness = if p_is_dir {
+        usize::min(45 + 8 * p_depth, 85)
+    } else {
+        90
+    };
+
+    return match p_hue {
+        Some(hue) => format!("hsl({:.0},55%,{}%)", hue, lightness),
+        None => format!("hsl(0,0%,{}%)", lightness),
+    };
+}
+
+/// Draws an entry, along with the entries nested in it
+///
+/// # Arguments
+///
+/// - `p_svg` - the image being drawn
+/// - `p_node` - the entry
+/// - `p_rel_path` - path of the entry relative to the initial directory (shown when hovering over it)
+/// - `p_depth` - level at which the entry lies (0 for the initial directory)
+/// - `p_hue` - hue of the entry ([None] for the initial directory)
+/// - `p_rect` - area that the entry is drawn in
+fn draw_node(
+    p_svg: &mut String,
+    p_node: &Node,
+    p_rel_path: &str,
+    p_depth: usize,
+    p_hue: Option<f64>,
+    p_rect: Rect,
+) {
+    let size = match p_node.size {
+        Some(size) => int_to_formatted_slice(size),
+        None => "ERROR",
+    };
+
+    let _ = writeln!(
+        p_svg,
+        "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\" stroke=\"#333\" stroke-width=\"0.5\">\
+         <title>{} ({} bytes)</title></rect>",
+        p_rect.x,
+        p_rect.y,
+        p_rect.w,
+        p_rect.h,
+        fill_color(p_hue, p_depth, p_node.is_dir),
+        xml_escape(p_rel_path),
+        size
+    );
+
+    // the name is only written if it fits, and the entries of a directory are nested below it
+    let header = if p_rect.h >= 3.0 * HEADER_HEIGHT {
+        HEADER_HEIGHT
+    } else {
+        PADDING
+    };
+    let label_fits = p_rect.h >= HEADER_HEIGHT
+        && p_rect.w >= (p_node.name.chars().count() as f64) * CHAR_WIDTH + 2.0 * PADDING;
+
+    if label_fits && (header == HEADER_HEIGHT || !p_node.is_dir) {
+        let _ = writeln!(
+            p_svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\">{}</text>",
+            p_rect.x + PADDING,
+            p_rect.y + HEADER_HEIGHT - 3.0,
+            xml_escape(&p_node.name)
+        );
+    }
+
+    let inner = Rect {
+        x: p_rect.x + PADDING,
+        y: p_rect.y + header,
+        w: p_rect.w - 2.0 * PADDING,
+        h: p_rect.h - header - PADDING,
+    };
+    if p_node.children.is_empty() || inner.w < MIN_SIDE || inner.h < MIN_SIDE {
+        return;
+    }
+
+    // entries whose size could not be calculated (or is 0) are not drawn, while the files that are not drawn take up
+    // the rest of the directory
+    let children: Vec<&Node> = p_node
+        .children
+        .iter()
+        .filter(|child| child.size.is_some_and(|size| size > 0))
+        .collect();
+    let children_size: u64 = children.iter().filter_map(|child| child.size).sum();
+    let total_size = u64::max(p_node.size.unwrap_or(0), children_size);
+    if total_size == 0 {
+        return;
+    }
+
+    let scale = (inner.w * inner.h) / (total_size as f64);
+    let mut areas: Vec<f64> = children
+        .iter()
+        .map(|child| child.size.unwrap_or(0) as f64 * scale)
+        .collect();
+
+    // the rest is placed among the entries by its size, so that the layout stays squarified
+    let rest = (total_size - children_size) as f64 * scale;
+    let rest_idx = areas.partition_point(|area| *area >= rest);
+    if rest > 0.0 {
+        areas.insert(rest_idx, rest);
+    }
+
+    let rects = squarify(&areas, inner);
+
+    let mut child_idx = 0;
+    for (idx, rect) in rects.into_iter().enumerate() {
+        if rest > 0.0 && idx == rest_idx {
+            continue;
+        }
+
+        let child = children[child_idx];
+        let hue = p_hue.or(Some((child_idx as f64 * HUE_STEP) % 360.0));
+        child_idx += 1;
+
+        if rect.w < MIN_SIDE || rect.h < MIN_SIDE {
+            continue;
+        }
+
+        let rel_path = if p_depth == 0 {
+            child.name.clone()
+        } else {
+            format!("{}/{}", p_rel_path, child.name)
+        };
+        draw_node(p_svg, child, &rel_path, 1 + p_depth, hue, rect);
+    }
+}
+
+/// Returns the number of directories nested in a directory
+///
+/// # Arguments
+///
+/// - `p_node` - the directory
+fn count_dirs(p_node: &Node) -> u64 {
+    return p_node
+        .children
+        .iter()
+        .filter(|child| child.is_dir)
+        .map(|child| 1 + count_dirs(child))
+        .sum();
+}
+
+/// Renders the cumulative sizes of the directories within the initial directory as a squarified treemap, and writes it
+/// into an SVG image
+///
+/// # Arguments
+///
+/// - `p_init_path` - path of the directory that the scan starts from
+/// - `p_max_level` - maximum level upto which entries are drawn (a value of 0 denotes no limit)
+/// - `p_svg_path` - path of the image to write
+pub fn treemap_init(p_init_path: &str, p_max_level: &u64, p_svg_path: &str) {
+    let init_path = path::Path::new(&p_init_path);
+
+    let root = match build_tree(init_path, p_max_level, 0, init_path, p_init_path.to_owned()) {
+        Ok(root) => root,
+        Err(error) => {
+            print!("{}", tr!(Msg::IterateError, p_init_path, error));
+            return;
+        }
+    };
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" \
+         font-family=\"monospace\" font-size=\"11\">\n",
+        w = WIDTH,
+        h = HEIGHT
+    );
+    draw_node(
+        &mut svg,
+        &root,
+        p_init_path,
+        0,
+        None,
+        Rect {
+            x: 0.0,
+            y: 0.0,
+            w: WIDTH,
+            h: HEIGHT,
+        },
+    );
+    svg.push_str("</svg>\n");
+
+    if let Err(error) = fs::write(p_svg_path, svg) {
+        print!("{}", tr!(Msg::TreemapWriteError, p_svg_path, error));
+        return;
+    }
+
+    let dir_cnt = int_to_formatted_slice(count_dirs(&root)).to_owned();
+    let total_size = match root.size {
+        Some(size) => int_to_formatted_slice(size),
+        None => "ERROR",
+    };
+
+    print!(
+        "{}",
+        tr!(
+            Msg::TreemapSummary,
+            p_init_path,
+            p_svg_path,
+            dir_cnt,
+            total_size
+        )
+    );
+}
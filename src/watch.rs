@@ -0,0 +1,355 @@
+//! Watching a directory for changes by periodically scanning it and comparing the results
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path;
+use std::process;
+use std::thread;
+use std::time;
+
+use crate::i18n::{tr, Msg};
+use crate::{escape, filter, get_option, ignore, matches_search, record, PrgOptions};
+
+/// Time between two consecutive scans of the watched directory
+const WATCH_POLL_INTERVAL: time::Duration = time::Duration::from_millis(500);
+
+/// Maximum number of changed entries to list in a single notification (the rest are only counted)
+const MAX_WATCH_LISTED: usize = 20;
+
+/// Enumerates the kinds of changes that can happen to an entry
+#[derive(Clone, Copy, PartialEq)]
+pub enum ChangeKind {
+    /// The entry did not exist in the previous scan
+    Created,
+    /// The type, size or modification time of the entry differs from the previous scan
+    Modified,
+    /// The entry does not exist anymore
+    Deleted,
+}
+
+impl ChangeKind {
+    /// Returns the name of the change as shown in notifications
+    pub fn name(&self) -> &'static str {
+        return match self {
+            ChangeKind::Created => "created",
+            ChangeKind::Modified => "modified",
+            ChangeKind::Deleted => "deleted",
+        };
+    }
+}
+
+/// Commands to run when entries change (each command is run with the path of the changed entry substituted for `{}`)
+pub struct WatchHooks {
+    /// Command to run when an entry is created
+    pub on_create: Option<String>,
+    /// Command to run when an entry is modified
+    pub on_modify: Option<String>,
+    /// Command to run when an entry is deleted
+    pub on_delete: Option<String>,
+}
+
+impl WatchHooks {
+    /// Returns the command to run for the given kind of change ([None] if no command was given for it)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_kind` - the kind of change
+    fn get(&self, p_kind: ChangeKind) -> Option<&String> {
+        return match p_kind {
+            ChangeKind::Created => self.on_create.as_ref(),
+            ChangeKind::Modified => self.on_modify.as_ref(),
+            ChangeKind::Deleted => self.on_delete.as_ref(),
+        };
+    }
+}
+
+/// State of an entry as seen during a single scan
+#[derive(PartialEq)]
+struct EntryState {
+    /// Whether the entry is a directory
+    is_dir: bool,
+    /// Length of the entry (in bytes)
+    len: u64,
+    /// Modification time of the entry ([None] if it could not be read)
+    modified: Option<time::SystemTime>,
+}
+
+/// Recursively records the state of all entries within a directory that match the search pattern
+///
+/// Like in the listings, entries that are ignored (such as by `--exclude` or a `.fssignore`) or hidden are left out,
+/// and the directories among them are not read
+///
+/// # Arguments
+///
+/// - `p_snapshot` - map to record the states into (indexed by path)
+/// - `p_max_level` - maximum level upto which to recurse (a value of 0 denotes no limit)
+/// - `p_level` - level of the entries of the current directory
+/// - `p_dir_path` - path of the current directory
+/// - `p_search_patterns` - the patterns that entries need to match one of to be recorded
+fn take_snapshot(
+    p_snapshot: &mut HashMap<path::PathBuf, EntryState>,
+    p_max_level: &u64,
+    p_level: usize,
+    p_dir_path: &path::Path,
+    p_search_patterns: &[String],
+) {
+    // directories that can not be read (such as ones that were just deleted) are silently skipped
+    let Ok(entries) = record::read_dir_unrecorded(p_dir_path) else {
+        return;
+    };
+    let ignore_rules = ignore::dir_rules(p_dir_path);
+
+    for entry in entries {
+        let Ok(entry) = entry else {
+            continue;
+        };
+
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+
+        let path_os = entry.path();
+        let metadata = record::EntryMetadata::from_metadata(metadata);
+
+        if ignore_rules.is_ignored(&path_os, metadata.is_dir())
+            || filter::hides(&path_os, &metadata, true)
+        {
+            continue;
+        }
+
+        if matches_search(&path_os, p_search_patterns) == Some(true) {
+            p_snapshot.insert(
+                path_os.clone(),
+                EntryState {
+                    is_dir: metadata.is_dir(),
+                    len: metadata.len(),
+                    modified: metadata.modified().ok(),
+                },
+            );
+        }
+
+        if metadata.is_dir()
+            && get_option(PrgOptions::ShowRecursive)
+            && (*p_max_level == 0u64 || p_level < (*p_max_level as usize))
+        {
+            take_snapshot(
+                p_snapshot,
+                p_max_level,
+                1 + p_level,
+                &path_os,
+                p_search_patterns,
+            );
+        }
+    }
+}
+
+/// Merges a new change of an entry into the change that is already pending for it (if any)
+///
+/// For example, an entry that was created and then modified is reported as created, and an entry that was created
+/// and then deleted is not reported at all
+///
+/// # Arguments
+///
+/// - `p_pending` - the changes that have not been reported yet (indexed by path)
+/// - `p_path` - path of the entry that changed
+/// - `p_change` - the new change of the entry
+fn coalesce_change(
+    p_pending: &mut BTreeMap<path::PathBuf, ChangeKind>,
+    p_path: path::PathBuf,
+    p_change: ChangeKind,
+) {
+    let merged = match (p_pending.get(&p_path), p_change) {
+        (None, change) => Some(change),
+        (Some(ChangeKind::Created), ChangeKind::Deleted) => None,
+        (Some(ChangeKind::Created), _) => Some(ChangeKind::Created),
+        (Some(ChangeKind::Deleted), ChangeKind::Created) => Some(ChangeKind::Modified),
+        (Some(_), change) => Some(change),
+    };
+
+    match merged {
+        Some(change) => {
+            p_pending.insert(p_path, change);
+        }
+        None => {
+            p_pending.remove(&p_path);
+        }
+    }
+}
+
+/// Prints a single notification summarizing the given changes
+///
+/// # Arguments
+///
+/// - `p_pending` - the changes to report (indexed by path)
+#[allow(clippy::print_with_newline)]
+fn report_changes(p_pending: &BTreeMap<path::PathBuf, ChangeKind>) {
+    let time = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+
+    let count = |p_kind: ChangeKind| p_pending.values().filter(|kind| **kind == p_kind).count();
+
+    print!(
+        "{}",
+        tr!(
+            Msg::WatchReport,
+            time,
+            count(ChangeKind::Created),
+            count(ChangeKind::Modified),
+            count(ChangeKind::Deleted)
+        )
+    );
+
+    for (path, kind) in p_pending.iter().take(MAX_WATCH_LISTED) {
+        print!("{:>20}    {}\n", kind.name(), escape::name(path));
+    }
+
+    if p_pending.len() > MAX_WATCH_LISTED {
+        print!(
+            "{:>20}    {}\n",
+            "",
+            tr!(Msg::WatchMore, p_pending.len() - MAX_WATCH_LISTED)
+        );
+    }
+}
+
+/// Runs a hook command for a changed entry and waits for it to finish
+///
+/// On unix, the command is run by `sh` with the path passed as a positional parameter (`{}` is replaced by `"$1"`),
+/// so paths containing spaces or quotes can not break the command. On windows, `{}` is replaced by the quoted path
+/// and the command is run by `cmd`
+///
+/// # Arguments
+///
+/// - `p_command` - the command to run
+/// - `p_path` - path of the changed entry
+fn run_hook(p_command: &str, p_path: &path::Path) {
+    #[cfg(target_family = "unix")]
+    let status = process::Command::new("sh")
+        .arg("-c")
+        .arg(p_command.replace("{}", "\"$1\""))
+        .arg("fss")
+        .arg(p_path)
+        .status();
+
+    #[cfg(not(target_family = "unix"))]
+    let status = process::Command::new("cmd")
+        .arg("/C")
+        .arg(p_command.replace("{}", &format!("\"{}\"", p_path.to_string_lossy())))
+        .status();
+
+    match status {
+        Ok(status) if !status.success() => {
+            if get_option(PrgOptions::ShowErrors) {
+                eprint!(
+                    "{}",
+                    tr!(
+                        Msg::HookExitError,
+                        p_command,
+                        p_path.to_string_lossy(),
+                        status
+                    )
+                );
+            }
+        }
+        Ok(_) => {}
+        Err(error) => {
+            if get_option(PrgOptions::ShowErrors) {
+                eprint!(
+                    "{}",
+                    tr!(
+                        Msg::HookRunError,
+                        p_command,
+                        p_path.to_string_lossy(),
+                        error
+                    )
+                );
+            }
+        }
+    }
+}
+
+/// Watches a directory for changes until the process is terminated, printing a notification for every change
+///
+/// Changes are detected by scanning the directory periodically. If a debounce time is given, changes are collected
+/// until no further changes are seen for that long, and then reported together in a single notification. After
+/// a notification is printed, the hook for each reported change is run (one at a time, in order of path)
+///
+/// # Arguments
+///
+/// - `p_init_path` - path of the directory to watch
+/// - `p_search_patterns` - the patterns that entries need to match one of for their changes to be reported
+/// - `p_max_level` - maximum level upto which to recurse (a value of 0 denotes no limit)
+/// - `p_debounce` - time to wait after the last change before reporting
+/// - `p_hooks` - commands to run for each reported change
+pub fn watch_path_init(
+    p_init_path: &str,
+    p_search_patterns: &[String],
+    p_max_level: &u64,
+    p_debounce: time::Duration,
+    p_hooks: &WatchHooks,
+) {
+    let init_path = path::Path::new(p_init_path);
+
+    if let Err(error) = fs::read_dir(init_path) {
+        print!("{}", tr!(Msg::IterateError, p_init_path, error));
+        return;
+    }
+
+    let mut prev_snapshot = HashMap::new();
+    take_snapshot(
+        &mut prev_snapshot,
+        p_max_level,
+        0,
+        init_path,
+        p_search_patterns,
+    );
+
+    print!("{}", tr!(Msg::Watching, p_init_path));
+
+    let mut pending: BTreeMap<path::PathBuf, ChangeKind> = BTreeMap::new();
+    let mut last_change = time::Instant::now();
+
+    loop {
+        thread::sleep(WATCH_POLL_INTERVAL);
+
+        let mut snapshot = HashMap::new();
+        take_snapshot(&mut snapshot, p_max_level, 0, init_path, p_search_patterns);
+
+        let mut changed = false;
+
+        for (path_os, state) in &snapshot {
+            let change = match prev_snapshot.get(path_os) {
+                None => ChangeKind::Created,
+                Some(prev_state) if prev_state != state => ChangeKind::Modified,
+                Some(_) => continue,
+            };
+
+            coalesce_change(&mut pending, path_os.clone(), change);
+            changed = true;
+        }
+
+        for path_os in prev_snapshot.keys() {
+            if !snapshot.contains_key(path_os) {
+                coalesce_change(&mut pending, path_os.clone(), ChangeKind::Deleted);
+                changed = true;
+            }
+        }
+
+        prev_snapshot = snapshot;
+
+        if changed {
+            last_change = time::Instant::now();
+        }
+
+        if !pending.is_empty() && last_change.elapsed() >= p_debounce {
+            report_changes(&pending);
+
+            for (path_os, kind) in &pending {
+                if let Some(command) = p_hooks.get(*kind) {
+                    run_hook(command, path_os);
+                }
+            }
+
+            pending.clear();
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! Regression test for the sizes printed next to the aggregate entries of a listing
+//!
+//! The size of the files of a directory used to be formatted into the same buffer as the number of files printed after
+//! it, so the leading digits of the size were overwritten by the count (77 bytes in 1 file were printed as 17)
+
+use std::fs;
+use std::process;
+
+#[test]
+fn aggregate_file_size() {
+    let root = std::env::temp_dir().join(format!("fss-aggregate-size-{}", process::id()));
+
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("dir")).unwrap();
+    fs::write(root.join("dir").join("a"), [0u8; 77]).unwrap();
+    fs::write(root.join("b"), [0u8; 3000]).unwrap();
+    fs::write(root.join("c"), [0u8; 2000]).unwrap();
+
+    let output = process::Command::new(env!("CARGO_BIN_EXE_fss"))
+        .arg(&root)
+        .args(["-r", "-d"])
+        .output()
+        .unwrap();
+
+    let _ = fs::remove_dir_all(&root);
+
+    // each aggregate entry is printed as its size followed by the number of files in angle brackets (unlike the
+    // counts of the summary)
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let aggregates: Vec<(&str, &str)> = stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.ends_with(" files>") && !line.starts_with('<'))
+        .filter_map(|line| line.split_once(' '))
+        .map(|(size, name)| (size, name.trim()))
+        .collect();
+
+    assert_eq!(
+        aggregates,
+        [("77", "<1 files>"), ("5,000", "<2 files>")],
+        "{}",
+        stdout
+    );
+}
@@ -0,0 +1,3653 @@
+//! Golden-output tests that run the complete command-line pipeline and compare its output against stored files
+//!
+//! Listing and searching are replayed from a fixed recording (`fixtures/tree.rec`), so their output does not depend on
+//! the filesystem, the order in which entries are read or the time of the run. The modes that can not be replayed are
+//! run over small trees built in a temporary directory.
+//!
+//! Run with `UPDATE_GOLDEN=1` to rewrite the golden files after an intended change to the output
+
+#![cfg(target_family = "unix")]
+#![allow(clippy::needless_return)]
+
+use std::fs;
+use std::path;
+use std::process;
+use std::time;
+
+/// Path of the recording that listing and searching are replayed from
+const FIXTURE_RECORDING: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/tree.rec");
+
+/// Directory that the golden files are stored in
+const GOLDEN_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden");
+
+/// Modification time given to every entry of the temporary trees (Nov 14 2023 22:13:20 UTC)
+const FIXED_MTIME_SECS: u64 = 1_700_000_000;
+
+/// Options that listing is run with while replaying the fixture (name of the golden file, options)
+const LISTING_CASES: &[(&str, &[&str])] = &[
+    ("list_default", &[]),
+    ("list_files", &["-f"]),
+    ("list_symlinks", &["-l"]),
+    ("list_special", &["-s"]),
+    ("list_all_types", &["-f", "-l", "-s"]),
+    ("list_recursive", &["-r"]),
+    ("list_recursive_depth", &["-r", "1", "-f"]),
+    ("list_recursive_files", &["-r", "-f"]),
+    ("list_permissions", &["-f", "-l", "-s", "-p"]),
+    ("list_time", &["-f", "-l", "-s", "-t"]),
+    ("list_permissions_time", &["-r", "-f", "-p", "-t"]),
+    (
+        "list_columns",
+        &[
+            "-r",
+            "-f",
+            "-l",
+            "-d",
+            "--columns",
+            "name,mtime,owner,size,perms",
+        ],
+    ),
+    // the columns that are left out are not printed, even if their options are set
+    (
+        "list_columns_override",
+        &["-r", "-f", "-p", "-t", "--columns", "size,name"],
+    ),
+    ("list_columns_unknown", &["--columns", "size,color"]),
+    ("list_dir_size", &["-r", "-d"]),
+    ("list_dir_size_files", &["-r", "-d", "-f"]),
+    ("list_human_readable", &["-r", "-d", "--human-readable"]),
+    (
+        "list_human_readable_files",
+        &["-r", "-d", "-f", "--human-readable"],
+    ),
+    ("list_si", &["-r", "-d", "-f", "--si"]),
+    ("list_block_size", &["-r", "-d", "-f", "--block-size", "1K"]),
+    (
+        "list_block_size_conflict",
+        &["-d", "--block-size", "1K", "--si"],
+    ),
+    ("list_block_size_invalid", &["-d", "--block-size", "0"]),
+    ("list_errors", &["-r", "-f", "-l", "-e"]),
+    (
+        "list_on_error_collect",
+        &["-r", "-d", "--on-error", "collect"],
+    ),
+    ("list_on_error_abort", &["-r", "-f", "--on-error", "abort"]),
+    (
+        "list_on_error_abort_names_only",
+        &["-r", "-f", "--names-only", "--on-error", "abort"],
+    ),
+    ("list_on_error_unknown", &["--on-error", "retry"]),
+    ("list_no_tree", &["--no-tree"]),
+    (
+        "list_no_tree_recursive",
+        &["-r", "-f", "-l", "-s", "--no-tree"],
+    ),
+    (
+        "list_no_tree_everything",
+        &["-r", "-f", "-l", "-s", "-p", "-t", "-d", "--no-tree", "-e"],
+    ),
+    (
+        "list_everything",
+        &["-r", "-f", "-l", "-s", "-p", "-t", "-d", "-e"],
+    ),
+    ("list_lang_de", &["-r", "-f", "-l", "-e", "--lang", "de"]),
+    ("list_lang_es", &["-r", "-f", "-l", "-e", "--lang", "es"]),
+    (
+        "list_indent_bars",
+        &["-r", "-f", "--indent", "2", "--indent-style", "bars"],
+    ),
+    ("list_indent_dots", &["-r", "-d", "--indent-style", "dots"]),
+    ("list_indent_zero", &["-r", "-f", "--indent", "0"]),
+    ("list_indent_style_unknown", &["--indent-style", "stars"]),
+    ("list_tree", &["-r", "-f", "-l", "-s", "--tree"]),
+    ("list_tree_aggregates", &["-r", "-d", "--tree"]),
+    (
+        "list_tree_narrow",
+        &["-r", "2", "-f", "--indent", "2", "--indent-style", "tree"],
+    ),
+    ("list_names_only", &["-r", "-f", "-l", "-s", "--names-only"]),
+    (
+        "list_names_only_search",
+        &["-r", "-f", "--contains", "i", "--names-only"],
+    ),
+    ("list_sizes_only", &["-r", "-f", "-e", "--sizes-only"]),
+    ("list_sizes_only_depth", &["-r", "1", "--sizes-only"]),
+    (
+        "list_ls_format",
+        &["-r", "-f", "-l", "-s", "-e", "--ls-format"],
+    ),
+    (
+        "list_ls_format_search",
+        &["-r", "-f", "-S", "old", "--ls-format"],
+    ),
+    ("list_long", &["--long"]),
+    ("list_long_recursive", &["-r", "-e", "--long"]),
+    ("list_long_depth", &["-r", "1", "--long"]),
+    ("list_plain_modes", &["--names-only", "--sizes-only"]),
+    ("list_plain_modes_long", &["--ls-format", "--long"]),
+    ("list_plain_modes_ls", &["--sizes-only", "--ls-format"]),
+    ("list_ordered", &["-r", "-f", "-l", "-s", "-e", "--ordered"]),
+    (
+        "list_ordered_names_only",
+        &["-r", "-f", "--names-only", "--ordered"],
+    ),
+    (
+        "list_format_json",
+        &["-r", "-f", "-l", "-s", "-e", "--format", "json"],
+    ),
+    (
+        "list_format_json_search",
+        &["-r", "-f", "--contains", "i", "--format", "json"],
+    ),
+    (
+        "list_format_ndjson",
+        &["-r", "-f", "-l", "-s", "-e", "--format", "ndjson"],
+    ),
+    (
+        "list_format_csv",
+        &["-r", "-f", "-l", "-s", "-e", "--format", "csv"],
+    ),
+    (
+        "list_format_tsv",
+        &["-r", "-f", "--contains", "i", "--format", "tsv"],
+    ),
+    (
+        "list_format_markdown",
+        &["-f", "-l", "-s", "--format", "markdown"],
+    ),
+    (
+        "list_format_markdown_recursive",
+        &["-r", "-f", "-l", "-s", "-e", "--format", "markdown"],
+    ),
+    (
+        "list_format_markdown_search",
+        &["-r", "-f", "-S", "2019.md", "--format", "markdown"],
+    ),
+    (
+        "list_format_mtree",
+        &["-r", "-f", "-l", "-s", "-e", "--format", "mtree"],
+    ),
+    (
+        "list_format_dot",
+        &["-r", "-f", "-l", "-s", "--format", "dot"],
+    ),
+    (
+        "list_format_dot_depth",
+        &["-r", "1", "-f", "--format", "dot"],
+    ),
+    ("list_format_unknown", &["--format", "xml"]),
+    (
+        "list_plain_modes_format",
+        &["--names-only", "--format", "json"],
+    ),
+    (
+        "list_printf",
+        &[
+            "-r",
+            "-f",
+            "-l",
+            "-s",
+            "--printf",
+            "{depth} {type} {perms} {mtime}\\t{size}\\t{{{name}}} {path}",
+        ],
+    ),
+    ("list_printf_unknown", &["--printf", "{path} {owner}"]),
+    ("list_printf_missing", &["--printf"]),
+    ("list_ext", &["-r", "-f", "-l", "-s", "--ext", "md,.RS"]),
+    ("list_ext_hidden_files", &["-r", "--ext", "md"]),
+    (
+        "list_ext_names_only",
+        &["-r", "-f", "--ext", "md", "--names-only"],
+    ),
+    ("list_ext_empty", &["--ext", ","]),
+    ("list_type", &["-r", "--type", "f,p"]),
+    ("list_type_dirs", &["-r", "-f", "-l", "--type", "d"]),
+    (
+        "list_type_names_only",
+        &["-r", "--type", "l,d", "--names-only"],
+    ),
+    ("list_type_unknown", &["--type", "f,x"]),
+    (
+        "list_size_range",
+        &["-r", "-f", "--min-size", "100", "--max-size", "3K"],
+    ),
+    ("list_size_invalid", &["--min-size", "10X"]),
+    (
+        "list_newer_than",
+        &["-r", "-f", "-t", "--newer-than", "2023-11-14 22:14"],
+    ),
+    (
+        "list_modified_window",
+        &[
+            "-r",
+            "-f",
+            "-t",
+            "--newer-than",
+            "2021-01-01",
+            "--older-than",
+            "2023-11-14",
+        ],
+    ),
+    // the fixture was last modified years ago, so none of it is newer than a few days
+    ("list_newer_than_age", &["-r", "-f", "--newer-than", "30d"]),
+    ("list_newer_than_invalid", &["--newer-than", "yesterday"]),
+    ("list_perm", &["-r", "-f", "-l", "-p", "--perm", "644"]),
+    ("list_perm_all", &["-r", "-f", "-l", "-p", "--perm", "-u+x"]),
+    ("list_perm_any", &["-r", "-f", "-l", "-p", "--perm", "/022"]),
+    (
+        "list_perm_symbolic",
+        &["-r", "-f", "-p", "--perm", "u=rw,go=r"],
+    ),
+    ("list_perm_invalid", &["--perm", "u+q"]),
+    ("list_min_depth", &["-r", "-f", "--min-depth", "2"]),
+    (
+        "list_min_depth_tree",
+        &["-r", "-f", "--min-depth", "2", "--tree"],
+    ),
+    (
+        "list_min_depth_names_only",
+        &["-r", "-f", "--min-depth", "2", "--names-only"],
+    ),
+    ("list_min_depth_invalid", &["-f", "--min-depth", "x"]),
+    // the progress is only reported on a terminal, so the listing must not change
+    (
+        "list_progress",
+        &["-r", "-f", "--progress-from", FIXTURE_RECORDING],
+    ),
+    (
+        "list_progress_not_recording",
+        &["--progress-from", "tests/golden/help.txt"],
+    ),
+];
+
+/// Options that searching is run with while replaying the fixture (name of the golden file, options)
+const SEARCH_CASES: &[(&str, &[&str])] = &[
+    ("search_exact", &["-r", "-f", "-S", "guide.md"]),
+    ("search_exact_dir", &["-r", "-S", "old"]),
+    (
+        "search_full_path_exact",
+        &["-r", "-f", "-S", "src/lib.rs", "--full-path"],
+    ),
+    (
+        "search_full_path_contains",
+        &["-r", "-f", "--contains", "docs/", "--full-path"],
+    ),
+    // a glob without a slash only matches the entries directly within the initial directory
+    (
+        "search_full_path_glob",
+        &["-r", "-f", "--glob", "*.md", "--full-path"],
+    ),
+    (
+        "search_full_path_fuzzy",
+        &["-r", "-f", "--fuzzy", "dcgd", "--full-path"],
+    ),
+    (
+        "search_contains_min_depth",
+        &["-r", "-f", "--contains", "i", "--min-depth", "2"],
+    ),
+    (
+        "search_contains_max_results",
+        &["-r", "-f", "--contains", "i", "--max-results", "3"],
+    ),
+    (
+        "search_fuzzy_max_results",
+        &["-r", "-f", "--fuzzy", "md", "--max-results", "2"],
+    ),
+    (
+        "search_contains_bfs",
+        &["-r", "-f", "-d", "--contains", "i", "--bfs"],
+    ),
+    // listings, and searches printed in other formats, are not done breadth-first
+    ("list_bfs", &["-r", "--bfs"]),
+    (
+        "search_contains_bfs_names_only",
+        &["-r", "--contains", "i", "--bfs", "--names-only"],
+    ),
+    // the shallowest matches are the ones shown
+    (
+        "search_contains_bfs_max_results",
+        &["-r", "-f", "--contains", "i", "--bfs", "--max-results", "2"],
+    ),
+    (
+        "search_contains_counts_by_dir",
+        &["-r", "-f", "-d", "--contains", "i", "--counts-by-dir"],
+    ),
+    (
+        "search_fuzzy_counts_by_dir",
+        &["-r", "-f", "--fuzzy", "md", "--counts-by-dir"],
+    ),
+    (
+        "search_contains_tree",
+        &["-r", "-f", "-l", "--contains", "i", "--search-tree"],
+    ),
+    // the directories above the minimum depth are not printed either
+    (
+        "search_contains_tree_min_depth",
+        &[
+            "-r",
+            "-f",
+            "--contains",
+            "i",
+            "--search-tree",
+            "--min-depth",
+            "2",
+        ],
+    ),
+    ("search_exact_not_recursive", &["-f", "-S", "guide.md"]),
+    ("search_noext", &["-r", "-f", "--search-noext", "main"]),
+    (
+        "search_contains",
+        &["-r", "-f", "-l", "-s", "--contains", "i"],
+    ),
+    (
+        "search_contains_depth",
+        &["-r", "1", "-f", "--contains", "i"],
+    ),
+    (
+        "search_contains_details",
+        &["-r", "-f", "-p", "-t", "-d", "--contains", "o"],
+    ),
+    ("search_hidden_types", &["-r", "--contains", "e"]),
+    ("search_glob", &["-r", "-f", "--glob", "*.{rs,md}"]),
+    ("search_glob_path", &["-r", "-f", "-l", "--glob", "docs/*"]),
+    (
+        "search_glob_class",
+        &["-r", "-f", "--glob", "**/[a-m]*.?[sd]"],
+    ),
+    ("search_glob_conflict", &["-r", "-S", "old", "--glob", "*"]),
+    ("search_ignore_case", &["-r", "-f", "-i", "-S", "readme.MD"]),
+    (
+        "search_ignore_case_contains",
+        &["-r", "-f", "--ignore-case", "--contains", "MAIN"],
+    ),
+    (
+        "search_ignore_case_glob",
+        &["-r", "-f", "-i", "--glob", "SRC/[K-M]*"],
+    ),
+    ("search_case_sensitive", &["-r", "-f", "--contains", "MAIN"]),
+    (
+        "search_multiple",
+        &[
+            "-r",
+            "-f",
+            "-s",
+            "--contains",
+            "i",
+            "--contains",
+            "md",
+            "--contains",
+            "zzz",
+        ],
+    ),
+    (
+        "search_multiple_glob",
+        &["-r", "-f", "--glob", "src/*", "-i", "--glob", "*.MD"],
+    ),
+    (
+        "search_multiple_names_only",
+        &["-r", "-f", "-S", "lib.rs", "-S", "guide.md", "--names-only"],
+    ),
+    (
+        "search_invert",
+        &["-r", "-f", "-s", "--invert-match", "--glob", "*.md"],
+    ),
+    (
+        "search_invert_multiple",
+        &["-r", "--invert-match", "--contains", "i", "--contains", "o"],
+    ),
+    (
+        "search_invert_fuzzy",
+        &["-r", "-f", "--invert-match", "--fuzzy", "mrs"],
+    ),
+    (
+        "search_ext",
+        &["-r", "-f", "--ext", "rs", "--contains", "i"],
+    ),
+    (
+        "search_ext_dirs",
+        &["-r", "-f", "--contains", "d", "--ext", "md"],
+    ),
+    ("search_type", &["-r", "--type", "d,l", "--contains", "o"]),
+    (
+        "search_size_range",
+        &["-r", "-f", "-l", "--contains", "i", "--min-size", "1K"],
+    ),
+    (
+        "search_older_than",
+        &["-r", "-t", "--contains", "o", "--older-than", "2022-01-01"],
+    ),
+    (
+        "search_lang_de",
+        &["-r", "-f", "--contains", "i", "--lang", "de_DE.UTF-8"],
+    ),
+    ("search_find_expr", &["--find-expr", "-type f -name '*.md'"]),
+    (
+        "search_ordered",
+        &["-r", "-f", "--contains", "i", "--ordered"],
+    ),
+    (
+        "search_find_expr_prune",
+        &[
+            "--find-expr",
+            "-name old -prune -o ( -type f -o -type l ) -print",
+        ],
+    ),
+    (
+        "search_find_expr_depth_size",
+        &["--find-expr", "-maxdepth 1 ! -type d -size +1k"],
+    ),
+    (
+        "search_find_expr_mtime",
+        &["--find-expr", "-mtime +365 -iname 'SRC'"],
+    ),
+    (
+        "search_find_expr_invalid",
+        &["--find-expr", "-name '*.md' -newer x"],
+    ),
+];
+
+/// Directory in the temporary directory of the system that is removed when dropped
+struct TempTree {
+    root: path::PathBuf,
+}
+
+impl TempTree {
+    /// Creates an empty temporary directory whose name is unique to the test
+    ///
+    /// # Arguments
+    ///
+    /// - `p_name` - name of the test
+    fn new(p_name: &str) -> TempTree {
+        let root = std::env::temp_dir().join(format!("fss-golden-{}-{}", p_name, process::id()));
+
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+
+        // canonicalized so that paths printed without indentation match the root (the temporary directory can be a symlink)
+        let root = root.canonicalize().unwrap();
+
+        return TempTree { root };
+    }
+
+    /// Creates a file with the given contents (creating its parent directories as well)
+    ///
+    /// # Arguments
+    ///
+    /// - `p_rel_path` - path of the file relative to the root of the tree
+    /// - `p_contents` - contents of the file
+    fn file(&self, p_rel_path: &str, p_contents: &[u8]) -> &TempTree {
+        let path_os = self.root.join(p_rel_path);
+
+        fs::create_dir_all(path_os.parent().unwrap()).unwrap();
+        fs::write(&path_os, p_contents).unwrap();
+
+        return self;
+    }
+
+    /// Sets the modification time of every entry in the tree to [FIXED_MTIME_SECS]
+    fn fix_times(&self) {
+        fn visit(p_dir_path: &path::Path) {
+            for entry in fs::read_dir(p_dir_path).unwrap() {
+                let path_os = entry.unwrap().path();
+
+                if path_os.is_dir() {
+                    visit(&path_os);
+                }
+
+                set_mtime(&path_os);
+            }
+        }
+
+        fn set_mtime(p_path: &path::Path) {
+            let time = time::UNIX_EPOCH + time::Duration::from_secs(FIXED_MTIME_SECS);
+            fs::File::open(p_path).unwrap().set_modified(time).unwrap();
+        }
+
+        visit(&self.root);
+        set_mtime(&self.root);
+    }
+
+    /// Sets the modification time of an entry to the given number of seconds (plus half a minute) before now
+    ///
+    /// The extra half minute keeps ages that are printed with a precision of a minute stable while the test runs
+    ///
+    /// # Arguments
+    ///
+    /// - `p_rel_path` - path of the entry relative to the root of the tree
+    /// - `p_age_secs` - age of the entry in seconds
+    fn set_age(&self, p_rel_path: &str, p_age_secs: u64) -> &TempTree {
+        let time = time::SystemTime::now() - time::Duration::from_secs(p_age_secs + 30);
+        fs::File::open(self.root.join(p_rel_path))
+            .unwrap()
+            .set_modified(time)
+            .unwrap();
+
+        return self;
+    }
+
+    /// Sets the modification time of an entry to the given number of seconds since the UNIX epoch
+    ///
+    /// # Arguments
+    ///
+    /// - `p_rel_path` - path of the entry relative to the root of the tree
+    /// - `p_secs` - the modification time
+    fn set_mtime(&self, p_rel_path: &str, p_secs: u64) -> &TempTree {
+        let time = time::UNIX_EPOCH + time::Duration::from_secs(p_secs);
+        fs::File::open(self.root.join(p_rel_path))
+            .unwrap()
+            .set_modified(time)
+            .unwrap();
+
+        return self;
+    }
+
+    /// Sets the time of last access of an entry to the given number of days before now
+    ///
+    /// # Arguments
+    ///
+    /// - `p_rel_path` - path of the entry relative to the root of the tree
+    /// - `p_age_days` - number of days since the entry was last accessed
+    fn set_access_age(&self, p_rel_path: &str, p_age_days: u64) -> &TempTree {
+        let time = time::SystemTime::now() - time::Duration::from_secs(p_age_days * 86400);
+        let times = fs::FileTimes::new().set_accessed(time);
+        fs::File::open(self.root.join(p_rel_path))
+            .unwrap()
+            .set_times(times)
+            .unwrap();
+
+        return self;
+    }
+
+    /// Returns the path of an entry of the tree as a string
+    ///
+    /// # Arguments
+    ///
+    /// - `p_rel_path` - path of the entry relative to the root of the tree (empty for the root itself)
+    fn path(&self, p_rel_path: &str) -> String {
+        // joining an empty path would add a trailing separator
+        if p_rel_path.is_empty() {
+            return self.root.to_string_lossy().into_owned();
+        }
+
+        return self.root.join(p_rel_path).to_string_lossy().into_owned();
+    }
+}
+
+impl Drop for TempTree {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+/// Runs fss with the given arguments and returns its output (stderr is appended after a separator, if not empty)
+///
+/// Times are printed in UTC and messages in English (unless selected with `--lang`), so that the output does not depend
+/// on the timezone or the locale of the machine
+///
+/// # Arguments
+///
+/// - `p_args` - the arguments to run fss with
+fn run_fss(p_args: &[&str]) -> String {
+    return run_fss_env(p_args, &[]);
+}
+
+/// Runs fss with the given arguments and additional environment variables, and returns its output like [run_fss]
+///
+/// # Arguments
+///
+/// - `p_args` - the arguments to run fss with
+/// - `p_vars` - names and values of the environment variables to set
+fn run_fss_env(p_args: &[&str], p_vars: &[(&str, &str)]) -> String {
+    let output = process::Command::new(env!("CARGO_BIN_EXE_fss"))
+        .args(p_args)
+        .env("TZ", "UTC")
+        .env("LANG", "C")
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .env_remove("CLICOLOR_FORCE")
+        .env_remove("LS_COLORS")
+        .env_remove("NO_COLOR")
+        .env_remove("PAGER")
+        .env_remove("LESS")
+        .envs(p_vars.iter().copied())
+        .output()
+        .unwrap();
+
+    let mut res = String::from_utf8_lossy(&output.stdout).into_owned();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if !stderr.is_empty() {
+        res.push_str("--- stderr ---\n");
+        res.push_str(&stderr);
+    }
+
+    return res;
+}
+
+/// Compares the output of a case against its golden file, and returns a description of the difference (if any)
+///
+/// If `UPDATE_GOLDEN` is set, the golden file is overwritten with the output instead
+///
+/// # Arguments
+///
+/// - `p_name` - name of the golden file (without the extension)
+/// - `p_output` - the output of the case
+fn check_golden(p_name: &str, p_output: &str) -> Option<String> {
+    let golden_path = path::Path::new(GOLDEN_DIR).join(format!("{}.txt", p_name));
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&golden_path, p_output).unwrap();
+        return None;
+    }
+
+    let expected = match fs::read_to_string(&golden_path) {
+        Ok(expected) => expected,
+        Err(error) => {
+            return Some(format!(
+                "{}: could not read golden file ({})",
+                p_name, error
+            ))
+        }
+    };
+
+    if expected == p_output {
+        return None;
+    }
+
+    // report the first line that differs, since the complete outputs can be long
+    let line_cnt = expected.lines().count().max(p_output.lines().count());
+    let Some((line, (expected_line, actual_line))) = expected
+        .lines()
+        .chain(std::iter::repeat("<end of output>"))
+        .zip(p_output.lines().chain(std::iter::repeat("<end of output>")))
+        .take(line_cnt)
+        .enumerate()
+        .find(|(_, (expected_line, actual_line))| expected_line != actual_line)
+    else {
+        return Some(format!("{}: line endings differ", p_name));
+    };
+
+    return Some(format!(
+        "{}: line {} differs\n    expected: {}\n    actual:   {}",
+        p_name,
+        line + 1,
+        expected_line,
+        actual_line
+    ));
+}
+
+/// Runs every case by replaying the fixture recording and checks all of them against their golden files
+///
+/// # Arguments
+///
+/// - `p_cases` - the cases to run (name of the golden file, options)
+fn check_replayed_cases(p_cases: &[(&str, &[&str])]) {
+    let failures: Vec<String> = p_cases
+        .iter()
+        .filter_map(|(name, options)| {
+            let mut args = vec!["--replay", FIXTURE_RECORDING];
+            args.extend_from_slice(options);
+
+            check_golden(name, &run_fss(&args))
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+/// Returns the output of a run with the read latencies replaced by `<TIME>`, and the directories of each report sorted
+/// by path (since the latencies, and with them the order of the directories, differ between runs)
+///
+/// # Arguments
+///
+/// - `p_output` - output of the run
+fn normalise_latencies(p_output: &str) -> String {
+    let mut res = String::new();
+    let mut timed_lines: Vec<String> = Vec::new();
+
+    for line in p_output.lines().map(Some).chain([None]) {
+        if let Some(line) = line.filter(|line| line.contains(" ms")) {
+            let words: Vec<&str> = line
+                .split_whitespace()
+                .map(
+                    |word| match word.contains('.') && word.parse::<f64>().is_ok() {
+                        true => "<TIME>",
+                        false => word,
+                    },
+                )
+                .collect();
+            timed_lines.push(words.join(" "));
+            continue;
+        }
+
+        timed_lines.sort();
+        for timed_line in timed_lines.drain(..) {
+            res.push_str(&timed_line);
+            res.push('\n');
+        }
+        if let Some(line) = line {
+            res.push_str(line);
+            res.push('\n');
+        }
+    }
+
+    return res;
+}
+
+#[test]
+fn listing() {
+    check_replayed_cases(LISTING_CASES);
+}
+
+#[test]
+fn searching() {
+    check_replayed_cases(SEARCH_CASES);
+}
+
+#[test]
+fn dirs_only() {
+    let tree = TempTree::new("dirs-only");
+    tree.file("small/a.txt", &[0; 100])
+        .file("large/b.txt", &[0; 3000])
+        .file("large/nested/c.txt", &[0; 50])
+        .file("top.txt", &[0; 7]);
+
+    let root = tree.path("");
+    let failures: Vec<String> = [
+        (
+            "dirs_only_size",
+            vec![root.as_str(), "--dirs-only", "-r", "-d"],
+        ),
+        (
+            "dirs_only_size_depth",
+            vec![root.as_str(), "--dirs-only", "-r", "1", "-d"],
+        ),
+        (
+            "dirs_only_indent_dots",
+            vec![
+                root.as_str(),
+                "--dirs-only",
+                "-r",
+                "-d",
+                "--indent-style",
+                "dots",
+            ],
+        ),
+        (
+            "dirs_only_tree",
+            vec![root.as_str(), "--dirs-only", "-r", "-d", "--tree"],
+        ),
+        (
+            "dirs_only_no_tree",
+            vec![root.as_str(), "--dirs-only", "-r", "-d", "--no-tree"],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn fingerprint() {
+    let tree = TempTree::new("fingerprint");
+    tree.file("a.txt", b"alpha")
+        .file("sub/b.txt", b"beta")
+        .file("sub/deeper/c.txt", b"gamma")
+        .file("other/d.txt", b"delta");
+    tree.fix_times();
+
+    let root = tree.path("");
+    let failures: Vec<String> = [
+        ("fingerprint", vec![root.as_str(), "--fingerprint"]),
+        (
+            "fingerprint_recursive",
+            vec![root.as_str(), "--fingerprint", "-r"],
+        ),
+        (
+            "fingerprint_depth",
+            vec![root.as_str(), "--fingerprint", "-r", "1"],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn name_collisions() {
+    let tree = TempTree::new("name-collisions");
+    tree.file("app/config.yaml", &[0; 120])
+        .file("app/old/config.yaml", &[0; 80])
+        .file("lib/config.yaml", &[0; 120])
+        .file("data.csv", &[0; 10])
+        .file("lib/data.csv", &[0; 10])
+        .file("lib/unique.txt", &[0; 5]);
+
+    let root = tree.path("");
+    let failures: Vec<String> = [
+        ("name_collisions", vec![root.as_str(), "--name-collisions"]),
+        (
+            "name_collisions_same_size",
+            vec![root.as_str(), "--name-collisions", "--same-size"],
+        ),
+        (
+            "name_collisions_depth",
+            vec![root.as_str(), "--name-collisions", "-r", "1"],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn measure_latency() {
+    let tree = TempTree::new("measure-latency");
+    tree.file("a/b/deep.txt", &[0; 30])
+        .file("a/top.txt", &[0; 20])
+        .file("c/other.txt", &[0; 10]);
+
+    let root = tree.path("");
+    let failures: Vec<String> = [
+        (
+            "measure_latency",
+            vec![root.as_str(), "-r", "--ordered", "--measure-latency"],
+        ),
+        (
+            "measure_latency_depth",
+            vec![root.as_str(), "-r", "1", "--ordered", "--measure-latency"],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| {
+        check_golden(
+            name,
+            &normalise_latencies(&run_fss(args)).replace(&root, "<ROOT>"),
+        )
+    })
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn file_ages() {
+    const DAY: u64 = 86400;
+
+    let tree = TempTree::new("file-ages");
+    tree.file("fresh.txt", &[0; 10])
+        .file("docs/week.md", &[0; 200])
+        .file("docs/old/year.md", &[0; 3000])
+        .file("archive/ancient.tar", &[0; 40]);
+    tree.set_age("fresh.txt", 2 * 3600)
+        .set_age("docs/week.md", 7 * DAY + 3 * 3600)
+        .set_age("docs/old/year.md", 400 * DAY)
+        .set_age("archive/ancient.tar", 3 * 365 * DAY + 2 * DAY);
+
+    let root = tree.path("");
+    let failures: Vec<String> = [
+        ("oldest", vec![root.as_str(), "--oldest", "2"]),
+        ("newest", vec![root.as_str(), "--newest", "3"]),
+        (
+            "oldest_newest_depth",
+            vec![root.as_str(), "--oldest", "10", "--newest", "1", "-r", "1"],
+        ),
+        (
+            "oldest_invalid",
+            vec![root.as_str(), "--oldest", "0", "--newest", "1"],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn cold_data() {
+    let tree = TempTree::new("cold-data");
+    tree.file("hot.txt", &[0; 10])
+        .file("reports/2019.pdf", &[0; 4000])
+        .file("reports/2024.pdf", &[0; 1000])
+        .file("reports/old/2010.pdf", &[0; 500])
+        .file("media/clip.mp4", &[0; 200]);
+    tree.set_access_age("hot.txt", 1)
+        .set_access_age("reports/2019.pdf", 400)
+        .set_access_age("reports/2024.pdf", 30)
+        .set_access_age("reports/old/2010.pdf", 2000)
+        .set_access_age("media/clip.mp4", 200);
+
+    let root = tree.path("");
+    let failures: Vec<String> = [
+        ("cold_data", vec![root.as_str(), "--cold-data"]),
+        (
+            "cold_data_age",
+            vec![root.as_str(), "--cold-data", "--atime-older", "2w"],
+        ),
+        (
+            "cold_data_depth",
+            vec![
+                root.as_str(),
+                "--cold-data",
+                "--atime-older",
+                "1y",
+                "-r",
+                "1",
+            ],
+        ),
+        (
+            "cold_data_invalid_age",
+            vec![root.as_str(), "--cold-data", "--atime-older", "soon"],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn access_change_times() {
+    let tree = TempTree::new("access-change-times");
+    tree.file("notes.txt", &[0; 10])
+        .file("archive/2019.tar", &[0; 4000])
+        .file("archive/2023.tar", &[0; 2000])
+        .file("cache/blob.bin", &[0; 300]);
+    tree.set_access_age("notes.txt", 2)
+        .set_access_age("archive/2019.tar", 900)
+        .set_access_age("archive/2023.tar", 90)
+        .set_access_age("cache/blob.bin", 10);
+
+    let root = tree.path("");
+    let failures: Vec<String> = [
+        (
+            "accessed_within",
+            vec![
+                root.as_str(),
+                "-r",
+                "-f",
+                "--ordered",
+                "--accessed-within",
+                "30d",
+            ],
+        ),
+        (
+            "accessed_within_columns",
+            vec![
+                root.as_str(),
+                "-r",
+                "-f",
+                "--ordered",
+                "--accessed-within",
+                "1y",
+                "--columns",
+                "atime,size,name",
+                "--relative-time",
+            ],
+        ),
+        (
+            "status_changed_within",
+            vec![
+                root.as_str(),
+                "-r",
+                "-f",
+                "--ordered",
+                "--status-changed-within",
+                "1h",
+                "--accessed-within",
+                "1w",
+            ],
+        ),
+        (
+            "status_changed_within_none",
+            vec![
+                root.as_str(),
+                "-r",
+                "-f",
+                "--ordered",
+                "--status-changed-within",
+                "2999-01-01",
+            ],
+        ),
+        (
+            "accessed_within_invalid",
+            vec![root.as_str(), "-r", "-f", "--accessed-within", "lately"],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn min_depth_dirs_only() {
+    let tree = TempTree::new("min-depth-dirs-only");
+    tree.file("a/b/c/data.bin", &[0; 4000])
+        .file("a/x/notes.txt", &[0; 300])
+        .file("d/readme.md", &[0; 20]);
+
+    let root = tree.path("");
+    let failures: Vec<String> = [(
+        "min_depth_dirs_only",
+        vec![root.as_str(), "-r", "-d", "--dirs-only", "--min-depth", "2"],
+    )]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn excludes() {
+    let tree = TempTree::new("excludes");
+    tree.file("main.o", &[0; 10])
+        .file("src/main.rs", &[0; 300])
+        .file("src/util.o", &[0; 20])
+        .file("src/target/notes.txt", &[0; 30])
+        .file("target/debug/app", &[0; 9000])
+        .file("target/debug/app.o", &[0; 4000])
+        .file(
+            "excludes.txt",
+            b"# build outputs\n; rsync comment\ntarget/**\n*.o\n\n/excludes.txt\n",
+        );
+
+    let root = tree.path("");
+    let exclude_file = tree.path("excludes.txt");
+
+    // the patterns of an exclude file are applied like those given to --exclude
+    let listed = run_fss(&[
+        &root,
+        "-r",
+        "-f",
+        "--ordered",
+        "--names-only",
+        "--exclude",
+        "target/**",
+        "--exclude",
+        "*.o",
+        "--exclude",
+        "excludes.txt",
+    ]);
+    let from_file = run_fss(&[
+        &root,
+        "-r",
+        "-f",
+        "--ordered",
+        "--names-only",
+        "--exclude-from",
+        &exclude_file,
+    ]);
+    assert_eq!(listed, from_file);
+
+    let failures: Vec<String> = [
+        ("excludes", listed),
+        // the excluded directory is skipped along with its size, instead of being listed empty
+        (
+            "excludes_dir_size",
+            run_fss(&[
+                &root,
+                "-r",
+                "-d",
+                "--ordered",
+                "--dirs-only",
+                "--exclude",
+                "target/**",
+            ]),
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, output)| check_golden(name, &output.replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn prune_common() {
+    let tree = TempTree::new("prune-common");
+    tree.file("app/node_modules/left-pad/index.js", &[0; 100])
+        .file("app/src/main.js", &[0; 200])
+        .file("app/dist/main.min.js", &[0; 300])
+        .file("crate/target/debug/app", &[0; 9000])
+        .file("crate/src/target.rs", &[0; 400])
+        .file("py/.venv/bin/python", &[0; 500])
+        .file("py/pkg/__pycache__/mod.pyc", &[0; 600])
+        .file("py/pkg/mod.py", &[0; 700])
+        .file(".git/HEAD", &[0; 20]);
+
+    let root = tree.path("");
+    let failures: Vec<String> = [
+        (
+            "prune_common",
+            vec![
+                root.as_str(),
+                "-r",
+                "-f",
+                "-a",
+                "--ordered",
+                "--names-only",
+                "--prune-common",
+            ],
+        ),
+        (
+            "prune_common_extended",
+            vec![
+                root.as_str(),
+                "-r",
+                "-d",
+                "--ordered",
+                "--dirs-only",
+                "--prune-common",
+                "--prune-dir",
+                "dist",
+            ],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn dot_ignore_files() {
+    let tree = TempTree::new("dot-ignore-files");
+    tree.file(".ignore", b"*.tmp\n")
+        .file(".fssignore", b"!keep.tmp\n")
+        .file("a.tmp", &[0; 10])
+        .file("keep.tmp", &[0; 20])
+        .file("src/.fdignore", b"cache/\n")
+        .file("src/main.rs", &[0; 300])
+        .file("src/cache/blob", &[0; 4000]);
+
+    let root = tree.path("");
+    let failures: Vec<String> = [
+        (
+            "dot_ignore_files",
+            vec![root.as_str(), "-r", "-f", "--ordered", "--names-only"],
+        ),
+        (
+            "dot_ignore_files_disabled",
+            vec![
+                root.as_str(),
+                "-r",
+                "-f",
+                "--ordered",
+                "--names-only",
+                "--no-ignore-dot",
+            ],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn gitignore_files() {
+    let tree = TempTree::new("gitignore-files");
+    tree.file("home/.config/git/ignore", b"*.swp\n")
+        .file("repo/.git/info/exclude", b"local/\n")
+        .file("repo/.gitignore", b"target/\n*.log\n/top.txt\n")
+        .file("repo/top.txt", &[0; 10])
+        .file("repo/a.swp", &[0; 20])
+        .file("repo/debug.log", &[0; 30])
+        .file("repo/target/out.o", &[0; 9000])
+        .file("repo/local/notes.txt", &[0; 40])
+        .file("repo/src/.gitignore", b"gen/\n!keep.log\n")
+        .file("repo/src/main.rs", &[0; 300])
+        .file("repo/src/b.swp", &[0; 50])
+        .file("repo/src/keep.log", &[0; 60])
+        .file("repo/src/other.log", &[0; 70])
+        .file("repo/src/gen/out.rs", &[0; 80])
+        .file("repo/src/lib/top.txt", &[0; 90]);
+
+    let root = tree.path("");
+    let repo = tree.path("repo");
+    let src = tree.path("repo/src");
+    let home = tree.path("home");
+    let config = tree.path("home/.config");
+    let vars = [
+        ("HOME", home.as_str()),
+        ("XDG_CONFIG_HOME", config.as_str()),
+    ];
+
+    let failures: Vec<String> = [
+        (
+            "gitignore_files",
+            vec![repo.as_str(), "-r", "-f", "--ordered", "--names-only"],
+        ),
+        (
+            "gitignore_files_respected",
+            vec![
+                repo.as_str(),
+                "-r",
+                "-f",
+                "--ordered",
+                "--names-only",
+                "--respect-gitignore",
+            ],
+        ),
+        // the .gitignore of the root of the repository applies to a scan of a directory within it
+        (
+            "gitignore_files_subdir",
+            vec![
+                src.as_str(),
+                "-r",
+                "-f",
+                "--ordered",
+                "--names-only",
+                "--respect-gitignore",
+            ],
+        ),
+        (
+            "gitignore_files_sizes_only",
+            vec![
+                repo.as_str(),
+                "-r",
+                "--ordered",
+                "--sizes-only",
+                "--respect-gitignore",
+            ],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| {
+        check_golden(name, &run_fss_env(args, &vars).replace(&root, "<ROOT>"))
+    })
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn content_kinds() {
+    let tree = TempTree::new("content-kinds");
+    tree.file("notes.txt", b"plain text\n")
+        .file("empty.txt", b"")
+        .file("image.png", b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR")
+        .file("src/main.rs", b"fn main() {}\n")
+        .file("src/app.o", &[0; 64]);
+
+    let root = tree.path("");
+    let failures: Vec<String> = [
+        (
+            "content_only_text",
+            vec![
+                root.as_str(),
+                "-r",
+                "--ordered",
+                "--names-only",
+                "--only-text",
+            ],
+        ),
+        (
+            "content_only_binary",
+            vec![root.as_str(), "-r", "--ordered", "--only-binary"],
+        ),
+        (
+            "content_column",
+            vec![
+                root.as_str(),
+                "-r",
+                "-f",
+                "--ordered",
+                "--columns",
+                "content,size,name",
+            ],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+#[cfg(feature = "mime")]
+fn mime_types() {
+    let tree = TempTree::new("mime-types");
+    tree.file("photo", b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR")
+        .file("scan.jpg.txt", b"\xff\xd8\xff\xe0\0\x10JFIF\0")
+        .file("report", b"%PDF-1.7\n%\xe2\xe3\xcf\xd3\n")
+        .file("notes.md", b"# notes\n")
+        .file("empty", b"")
+        .file("build/app", b"\x7fELF\x02\x01\x01\0\0\0\0\0")
+        .file("build/cache", &[0; 64]);
+    tree.fix_times();
+
+    let root = tree.path("");
+    let failures: Vec<String> = [
+        (
+            "mime_images",
+            vec![
+                root.as_str(),
+                "-r",
+                "--ordered",
+                "--names-only",
+                "--mime",
+                "image/*",
+            ],
+        ),
+        (
+            "mime_list",
+            vec![
+                root.as_str(),
+                "-r",
+                "--ordered",
+                "--names-only",
+                "--mime",
+                "application,text/plain",
+            ],
+        ),
+        (
+            "mime_format",
+            vec![
+                root.as_str(),
+                "-r",
+                "--ordered",
+                "--format",
+                "csv",
+                "--mime",
+                "image,application",
+            ],
+        ),
+        (
+            "mime_printf",
+            vec![
+                root.as_str(),
+                "-r",
+                "-f",
+                "--ordered",
+                "--printf",
+                "{path}\\t{mime}",
+            ],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn hidden_entries() {
+    let tree = TempTree::new("hidden-entries");
+    tree.file(".env", &[0; 12])
+        .file(".git/config", &[0; 90])
+        .file("src/main.rs", &[0; 300])
+        .file("src/.env.example", &[0; 8]);
+
+    let root = tree.path("");
+    let failures: Vec<String> = [
+        ("hidden", vec![root.as_str(), "-r", "-f", "--ordered"]),
+        (
+            "hidden_all",
+            vec![root.as_str(), "-r", "-f", "--ordered", "-a"],
+        ),
+        (
+            "hidden_names_only",
+            vec![root.as_str(), "-r", "-f", "--ordered", "--names-only"],
+        ),
+        // sizes and trees do not show hidden entries, but still count them (searches only skip them with "--no-hidden")
+        (
+            "hidden_sizes_only",
+            vec![root.as_str(), "-r", "--ordered", "--sizes-only"],
+        ),
+        (
+            "hidden_sizes_only_all",
+            vec![root.as_str(), "-r", "--ordered", "--sizes-only", "-a"],
+        ),
+        (
+            "hidden_dirs_only",
+            vec![root.as_str(), "-r", "--dirs-only", "-d", "--ordered"],
+        ),
+        ("hidden_dir_size", vec![root.as_str(), "-d", "--ordered"]),
+        (
+            "hidden_search",
+            vec![root.as_str(), "-r", "-f", "--ordered", "--contains", "env"],
+        ),
+        (
+            "hidden_search_no_hidden",
+            vec![
+                root.as_str(),
+                "-r",
+                "-f",
+                "--ordered",
+                "--contains",
+                "env",
+                "--no-hidden",
+            ],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+
+    // the listing and the tree of directories give the same size to a directory holding a hidden file
+    let src_size = |p_output: String| -> String {
+        let line = p_output
+            .lines()
+            .find(|line| line.trim_end().ends_with("<src>"))
+            .unwrap()
+            .to_owned();
+        return line.split_whitespace().next().unwrap().to_owned();
+    };
+    assert_eq!(
+        src_size(run_fss(&[&root, "-d"])),
+        src_size(run_fss(&[&root, "--dirs-only", "-d"]))
+    );
+}
+
+#[test]
+fn empty_files() {
+    let tree = TempTree::new("empty-files");
+    tree.file("notes.txt", &[0; 10])
+        .file("downloads/movie.mkv.part", &[])
+        .file("downloads/album.zip", &[0; 700])
+        .file("downloads/setup.part", &[0; 30])
+        .file("assets/.keep", &[]);
+    fs::create_dir_all(tree.path("cache")).unwrap();
+
+    let root = tree.path("");
+    let failures: Vec<String> = [
+        (
+            "empty_files",
+            vec![root.as_str(), "-r", "-a", "--ordered", "--empty-files"],
+        ),
+        (
+            "empty_files_search",
+            vec![
+                root.as_str(),
+                "-r",
+                "--ordered",
+                "--empty-files",
+                "--contains",
+                "part",
+            ],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn symlink_loops() {
+    let tree = TempTree::new("symlink-loops");
+    tree.file("data/notes.txt", &[0; 120])
+        .file("data/docs/guide.md", &[0; 80]);
+    std::os::unix::fs::symlink(".", tree.path("data/self")).unwrap();
+
+    let root = tree.path("");
+    let failures: Vec<String> = [
+        (
+            "symlink_loops",
+            vec![root.as_str(), "-r", "--ordered", "--dir-size", "-L"],
+        ),
+        (
+            "symlink_loops_off",
+            vec![root.as_str(), "-r", "--ordered", "--dir-size"],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn broken_symlinks() {
+    let tree = TempTree::new("broken-symlinks");
+    tree.file("bin/tool", &[0; 64])
+        .file("lib/libold.so.1", &[0; 32]);
+    std::os::unix::fs::symlink("tool", tree.path("bin/current")).unwrap();
+    std::os::unix::fs::symlink("tool-2.0", tree.path("bin/latest")).unwrap();
+    std::os::unix::fs::symlink("libold.so.2", tree.path("lib/libold.so")).unwrap();
+    std::os::unix::fs::symlink(tree.path("gone"), tree.path("cache")).unwrap();
+
+    let root = tree.path("");
+    let failures: Vec<String> = [
+        (
+            "broken_symlinks",
+            vec![root.as_str(), "-r", "--ordered", "--broken-symlinks"],
+        ),
+        (
+            "broken_symlinks_names_only",
+            vec![
+                root.as_str(),
+                "-r",
+                "--ordered",
+                "--broken-symlinks",
+                "--names-only",
+            ],
+        ),
+        (
+            "broken_symlinks_search",
+            vec![
+                root.as_str(),
+                "-r",
+                "--ordered",
+                "--broken-symlinks",
+                "--contains",
+                "lib",
+            ],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+
+    // like ls, the long listing shows the targets as they are stored in the symlinks (the owners differ between
+    // machines, so only the names and targets are compared)
+    let output = run_fss(&[&root, "-r", "--long"]);
+    let mut targets: Vec<&str> = output
+        .lines()
+        .filter(|line| line.starts_with('l'))
+        .filter_map(|line| line.split_once(':'))
+        .map(|(_, rest)| rest.split_once(' ').map_or(rest, |(_, name)| name))
+        .collect();
+    targets.sort();
+    assert_eq!(
+        targets,
+        [
+            format!("cache -> {}", tree.path("gone")),
+            "current -> tool".to_owned(),
+            "latest -> tool-2.0".to_owned(),
+            "libold.so -> libold.so.2".to_owned(),
+        ],
+        "{}",
+        output
+    );
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn follow_symlinks() {
+    let tree = TempTree::new("follow-symlinks");
+    tree.file("shared/lib.rs", &[0; 300])
+        .file("project/main.rs", &[0; 90])
+        .file("project/src/util.rs", &[0; 40]);
+    std::os::unix::fs::symlink("../shared", tree.path("project/shared")).unwrap();
+    std::os::unix::fs::symlink("..", tree.path("project/src/parent")).unwrap();
+
+    let root = tree.path("");
+    let project = tree.path("project");
+    let failures: Vec<String> = [
+        (
+            "follow_symlinks",
+            vec![
+                project.as_str(),
+                "-r",
+                "-f",
+                "-d",
+                "-l",
+                "--ordered",
+                "--dir-size",
+                "-L",
+            ],
+        ),
+        (
+            "follow_symlinks_search",
+            vec![
+                project.as_str(),
+                "-r",
+                "-f",
+                "--ordered",
+                "--contains",
+                "lib",
+                "--follow",
+            ],
+        ),
+        (
+            "follow_symlinks_off",
+            vec![
+                project.as_str(),
+                "-r",
+                "-f",
+                "-d",
+                "-l",
+                "--ordered",
+                "--dir-size",
+            ],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn pseudo_filesystems() {
+    // the mount points are only skipped where /proc is mounted, since that is where they are read from
+    if !std::path::Path::new("/proc/self/mountinfo").exists() {
+        return;
+    }
+
+    let names = |p_args: &[&str]| {
+        return run_fss(&[&["/", "-d", "--names-only"], p_args].concat())
+            .lines()
+            .any(|line| line == "/proc");
+    };
+
+    assert!(!names(&[]));
+    assert!(names(&["--no-skip-fs"]));
+    assert!(names(&["--skip-fs", "sysfs"]));
+
+    let failures: Vec<String> = [
+        ("skip_fs_missing", run_fss(&["/", "--skip-fs"])),
+        ("skip_fs_invalid", run_fss(&["/", "--skip-fs", " , "])),
+    ]
+    .iter()
+    .filter_map(|(name, output)| check_golden(name, output))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn owners() {
+    use std::os::unix::fs::MetadataExt;
+
+    let tree = TempTree::new("owners");
+    tree.file("src/main.rs", &[0; 120])
+        .file("src/lib.rs", &[0; 80])
+        .file("README.md", &[0; 40]);
+
+    // the entries are owned by whoever runs the tests, so they are filtered by the IDs of their owner and group
+    let root = tree.path("");
+    let metadata = fs::metadata(&root).unwrap();
+    let (uid, gid) = (metadata.uid().to_string(), metadata.gid().to_string());
+    let other_uid = (metadata.uid() ^ 0x5a5a5).to_string();
+
+    let failures: Vec<String> = [
+        (
+            "owner",
+            vec![root.as_str(), "-r", "-f", "--ordered", "--owner", &uid],
+        ),
+        (
+            "owner_group",
+            vec![
+                root.as_str(),
+                "-r",
+                "-f",
+                "--ordered",
+                "--owner",
+                &uid,
+                "--group",
+                &gid,
+            ],
+        ),
+        (
+            "owner_other",
+            vec![
+                root.as_str(),
+                "-r",
+                "-f",
+                "--ordered",
+                "--owner",
+                &other_uid,
+            ],
+        ),
+        (
+            "owner_unknown",
+            vec![root.as_str(), "-r", "-f", "--owner", "no-such-user"],
+        ),
+        (
+            "group_unknown",
+            vec![root.as_str(), "-r", "-f", "--group", "no-such-group"],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn empty_dirs() {
+    let tree = TempTree::new("empty-dirs");
+    tree.file("src/main.rs", &[0; 100])
+        .file("logs/app.log", &[0; 20]);
+    for dir in ["cache", "build/tmp/objects", "build/deps", "logs/old"] {
+        fs::create_dir_all(tree.path(dir)).unwrap();
+    }
+
+    let root = tree.path("");
+    let failures: Vec<String> = [
+        ("empty_dirs", vec![root.as_str(), "-r", "--empty-dirs"]),
+        (
+            "empty_dirs_nested",
+            vec![root.as_str(), "-r", "--empty-dirs", "--nested-empty"],
+        ),
+        // the subdirectories of build are not traversed, so it is not known to be empty
+        (
+            "empty_dirs_nested_depth",
+            vec![root.as_str(), "-r", "1", "--empty-dirs", "--nested-empty"],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn hardlinks() {
+    use std::os::unix::fs::MetadataExt;
+
+    let tree = TempTree::new("hardlinks");
+    tree.file("daily.1/etc/hosts", &[0; 40])
+        .file("daily.1/etc/passwd", &[0; 120])
+        .file("daily.1/notes.txt", &[0; 10])
+        .file("shared.bin", &[0; 7]);
+    for dir in ["daily.2/etc", "daily.3/etc", "outside"] {
+        fs::create_dir_all(tree.path(dir)).unwrap();
+    }
+    for (src, dst) in [
+        ("daily.1/etc/hosts", "daily.2/etc/hosts"),
+        ("daily.1/etc/hosts", "daily.3/etc/hosts"),
+        ("daily.1/etc/passwd", "daily.2/etc/passwd"),
+        ("shared.bin", "outside/shared.bin"),
+    ] {
+        fs::hard_link(tree.path(src), tree.path(dst)).unwrap();
+    }
+
+    // inode numbers differ between runs, so they are replaced along with the root
+    let mask_inodes = |p_output: String| {
+        let mut output = p_output.replace(&tree.path(""), "<ROOT>");
+        for file in ["daily.1/etc/hosts", "daily.1/etc/passwd", "shared.bin"] {
+            let ino = fs::metadata(tree.path(file)).unwrap().ino();
+            output = output.replace(&format!("Inode {} ", ino), "Inode <INO> ");
+        }
+        return output;
+    };
+
+    let root = tree.path("");
+    let daily = tree.path("daily.1");
+    let failures: Vec<String> = [
+        ("hardlinks", vec![root.as_str(), "-r", "--hardlinks"]),
+        // the links outside of daily.1 are not found, though they are still counted
+        (
+            "hardlinks_subdir",
+            vec![daily.as_str(), "-r", "--hardlinks"],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &mask_inodes(run_fss(args))))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn timestamp_anomalies() {
+    let tree = TempTree::new("timestamp-anomalies");
+    tree.file("normal.txt", &[0; 10])
+        .file("copies/future.txt", &[0; 10])
+        .file("copies/zeroed.txt", &[0; 10])
+        .file("copies/old/restored.txt", &[0; 10]);
+    tree.set_mtime("normal.txt", FIXED_MTIME_SECS)
+        .set_mtime("copies/future.txt", 4_102_444_800)
+        .set_mtime("copies/zeroed.txt", 0)
+        .set_mtime("copies/old/restored.txt", 631_152_000);
+
+    let root = tree.path("");
+    let failures: Vec<String> = [
+        (
+            "timestamp_anomalies",
+            vec![root.as_str(), "--timestamp-anomalies", "--ctime-gap", "20y"],
+        ),
+        (
+            "timestamp_anomalies_depth",
+            vec![
+                root.as_str(),
+                "--timestamp-anomalies",
+                "--ctime-gap",
+                "20y",
+                "-r",
+                "1",
+            ],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn treemap() {
+    let tree = TempTree::new("treemap");
+    tree.file("data/a/x.bin", &[0; 5000])
+        .file("data/b/c/y.bin", &[0; 3000])
+        .file("data/b/c/d/deep.bin", &[0; 600])
+        .file("data/b/z <&>.txt", &[0; 900])
+        .file("data/top.txt", &[0; 400]);
+
+    // the image follows the output of the run (directories below the maximum depth are drawn without their entries)
+    let root = tree.path("");
+    let data = tree.path("data");
+    let image = tree.path("treemap.svg");
+    let failures: Vec<String> = [
+        ("treemap", vec![data.as_str(), "--treemap", image.as_str()]),
+        (
+            "treemap_recursive",
+            vec![data.as_str(), "-r", "1", "-f", "--treemap", image.as_str()],
+        ),
+        (
+            "treemap_unreadable",
+            vec![image.as_str(), "--treemap", image.as_str()],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| {
+        let _ = fs::remove_file(&image);
+        let mut output = run_fss(args);
+        if let Ok(svg) = fs::read_to_string(&image) {
+            output.push_str("--- treemap.svg ---\n");
+            output.push_str(&svg);
+        }
+        check_golden(name, &output.replace(&root, "<ROOT>"))
+    })
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn export_sqlite() {
+    let tree = TempTree::new("export-sqlite");
+    tree.file("data/a/x.bin", &[0; 5000])
+        .file("data/b/y.txt", &[0; 300])
+        .file("data/top.txt", &[0; 40])
+        .file("data/.cache/blob.bin", &[0; 900]);
+
+    let root = tree.path("");
+    let data = tree.path("data");
+    let db = tree.path("scan.db");
+    let failures: Vec<String> = [
+        (
+            "export_sqlite",
+            vec![data.as_str(), "-r", "-f", "--export-sqlite", db.as_str()],
+        ),
+        (
+            "export_sqlite_create_error",
+            vec![data.as_str(), "--export-sqlite", &root, "-f"],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+
+    // the header gives the size of the database in pages, which SQLite only trusts if it matches the file
+    let bytes = fs::read(&db).unwrap();
+    assert_eq!(&bytes[..16], b"SQLite format 3\0");
+    let page_size = u16::from_be_bytes([bytes[16], bytes[17]]) as usize;
+    let page_cnt = u32::from_be_bytes([bytes[28], bytes[29], bytes[30], bytes[31]]) as usize;
+    assert_eq!(bytes.len(), page_size * page_cnt);
+    // hidden entries are only exported with "-a"
+    assert!(!bytes.windows(6).any(|window| window == b".cache"));
+}
+
+#[test]
+#[cfg(feature = "export-parquet")]
+fn export_parquet() {
+    let tree = TempTree::new("export-parquet");
+    tree.file("data/a/x.bin", &[0; 5000])
+        .file("data/b/y.txt", &[0; 300])
+        .file("data/top.txt", &[0; 40])
+        .file("data/.cache/blob.bin", &[0; 900]);
+
+    let root = tree.path("");
+    let data = tree.path("data");
+    let parquet = tree.path("scan.parquet");
+    let failures: Vec<String> = [
+        (
+            "export_parquet",
+            vec![
+                data.as_str(),
+                "-r",
+                "-f",
+                "--export-parquet",
+                parquet.as_str(),
+            ],
+        ),
+        (
+            "export_parquet_create_error",
+            vec![data.as_str(), "--export-parquet", &root, "-f"],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+
+    // the file starts and ends with the magic bytes, and the metadata before the end fits within the file
+    let bytes = fs::read(&parquet).unwrap();
+    assert_eq!(&bytes[..4], b"PAR1");
+    assert_eq!(&bytes[bytes.len() - 4..], b"PAR1");
+    let metadata_len =
+        u32::from_le_bytes(bytes[bytes.len() - 8..bytes.len() - 4].try_into().unwrap());
+    assert!((metadata_len as usize) < bytes.len() - 12);
+    // the values are stored uncompressed, so every path is present in the file
+    for name in ["a/x.bin", "b/y.txt", "top.txt"] {
+        assert!(bytes
+            .windows(name.len())
+            .any(|window| window == name.as_bytes()));
+    }
+    // hidden entries are only exported with "-a"
+    assert!(!bytes.windows(6).any(|window| window == b".cache"));
+}
+
+#[test]
+fn snapshot() {
+    let tree = TempTree::new("snapshot");
+    tree.file("data/a/x.bin", &[0; 5000])
+        .file("data/a/deep/z.txt", &[0; 70])
+        .file("data/b/y.txt", &[0; 300])
+        .file("data/top.txt", &[0; 40]);
+
+    let root = tree.path("");
+    let data = tree.path("data");
+    let snapshot = tree.path("scan.fss");
+    let recording = tree.path("scan.txt");
+    let args = ["-r", "-f", "-s", "--ordered"];
+
+    let live = run_fss(&[&[data.as_str(), "--snapshot", &snapshot], &args[..]].concat());
+    let replayed = run_fss(&[&["--replay", snapshot.as_str()], &args[..]].concat());
+    assert_eq!(live, replayed);
+
+    // the snapshot holds the same scan as a text recording of it
+    run_fss(&[&[data.as_str(), "--record", &recording], &args[..]].concat());
+    assert_eq!(
+        replayed,
+        run_fss(&[&["--replay", recording.as_str()], &args[..]].concat())
+    );
+    assert_eq!(&fs::read(&snapshot).unwrap()[..16], b"\xaffss snapshot v1");
+
+    let failures: Vec<String> = check_golden("snapshot", &replayed.replace(&root, "<ROOT>"))
+        .into_iter()
+        .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn age_heatmap() {
+    const DAY: u64 = 86400;
+
+    let tree = TempTree::new("age-heatmap");
+    tree.file("active/main.rs", &[0; 400])
+        .file("recent/notes.md", &[0; 300])
+        .file("stale/report.pdf", &[0; 200])
+        .file("ancient/backup.tar", &[0; 100]);
+    tree.set_age("active", 2 * 3600)
+        .set_age("recent", 3 * DAY)
+        .set_age("stale", 200 * DAY)
+        .set_age("ancient", 5 * 365 * DAY)
+        .set_age("stale/report.pdf", 2 * 365 * DAY);
+
+    let root = tree.path("");
+    let stale = tree.path("stale");
+    let colored = [("CLICOLOR_FORCE", "1")];
+    let failures: Vec<String> = [
+        (
+            "age_heatmap",
+            vec![root.as_str(), "--dirs-only", "-r", "-d", "--age-heatmap"],
+            &colored[..],
+        ),
+        (
+            "age_heatmap_files",
+            vec![stale.as_str(), "-f", "--age-heatmap"],
+            &colored[..],
+        ),
+        (
+            "age_heatmap_no_terminal",
+            vec![root.as_str(), "--dirs-only", "-r", "-d", "--age-heatmap"],
+            &[][..],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args, vars)| {
+        // the escape sequences are made visible, so that the golden files can be read
+        let output = run_fss_env(args, vars).replace('\x1b', "\\e");
+        check_golden(name, &output.replace(&root, "<ROOT>"))
+    })
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn size_heatmap() {
+    let tree = TempTree::new("size-heatmap");
+    tree.file("tiny/a.txt", &[0; 100])
+        .file("small/b.txt", &[0; 10 << 10])
+        .file("medium/c.bin", &[0; 200 << 10])
+        .file("large/d.bin", &[0; 2 << 20]);
+
+    let root = tree.path("");
+    let large = tree.path("large");
+    let colored = [("CLICOLOR_FORCE", "1")];
+    let failures: Vec<String> = [
+        (
+            "size_heatmap",
+            vec![root.as_str(), "--dirs-only", "-r", "-d", "--size-heatmap"],
+        ),
+        (
+            "size_heatmap_no_tree",
+            vec![large.as_str(), "-f", "--no-tree", "--size-heatmap"],
+        ),
+        (
+            "size_heatmap_age_heatmap",
+            vec![root.as_str(), "--size-heatmap", "--age-heatmap"],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| {
+        // the escape sequences are made visible, so that the golden files can be read
+        let output = run_fss_env(args, &colored).replace('\x1b', "\\e");
+        check_golden(name, &output.replace(&root, "<ROOT>"))
+    })
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn kind_colors() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tree = TempTree::new("kind-colors");
+    tree.file("data/docs/guide.md", &[0; 30])
+        .file("data/run.sh", &[0; 10])
+        .file("data/backup.tar", &[0; 20])
+        .file("data/notes.txt", &[0; 5]);
+    fs::set_permissions(tree.path("data/run.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+    std::os::unix::fs::symlink(tree.path("data/docs"), tree.path("data/link")).unwrap();
+
+    let root = tree.path("");
+    let data = tree.path("data");
+    let args = [data.as_str(), "-r", "-f", "-l", "--ordered"];
+    let failures: Vec<String> = [
+        (
+            "kind_colors",
+            run_fss(&[&args[..], &["--color", "always"]].concat()),
+        ),
+        (
+            "kind_colors_ls_colors",
+            run_fss_env(
+                &[&args[..], &["--color", "always"]].concat(),
+                &[("LS_COLORS", "di=01;33:ex=00:*.tar=01;31")],
+            ),
+        ),
+        (
+            "kind_colors_never",
+            run_fss_env(
+                &[&args[..], &["--color", "never"]].concat(),
+                &[("CLICOLOR_FORCE", "1")],
+            ),
+        ),
+        (
+            "kind_colors_unknown",
+            run_fss(&[&data, "--color", "sometimes"]),
+        ),
+    ]
+    .iter()
+    .map(|(name, output)| (name, output.replace('\x1b', "\\e")))
+    .filter_map(|(name, output)| check_golden(name, &output.replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn width() {
+    let tree = TempTree::new("width");
+    tree.file(
+        "data/quarterly-reports-of-the-northern-region/summary-of-the-third-quarter.pdf",
+        &[0; 40],
+    )
+    .file("data/short.txt", &[0; 5]);
+
+    let root = tree.path("");
+    let data = tree.path("data");
+    let args = [data.as_str(), "-r", "-f", "--ordered"];
+    let failures: Vec<String> = [
+        ("width", run_fss(&[&args[..], &["--width", "50"]].concat())),
+        (
+            "width_colored",
+            run_fss(&[&args[..], &["--width", "50", "--color", "always"]].concat()),
+        ),
+        (
+            "width_not_terminal",
+            run_fss_env(&args, &[("COLUMNS", "50")]),
+        ),
+        (
+            "width_disabled",
+            run_fss(&[&args[..], &["--width", "0"]].concat()),
+        ),
+        ("width_invalid", run_fss(&[&data, "--width", "wide"])),
+    ]
+    .iter()
+    .map(|(name, output)| (name, output.replace('\x1b', "\\e")))
+    .filter_map(|(name, output)| check_golden(name, &output.replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn paging() {
+    let tree = TempTree::new("paging");
+    tree.file("data/docs/guide.md", &[0; 30])
+        .file("data/notes.txt", &[0; 5]);
+
+    let root = tree.path("");
+    let data = tree.path("data");
+    let args = [data.as_str(), "-r", "-f", "--ordered"];
+    let pager = "echo \"LESS=$LESS\"; sed 's/^/| /'";
+    let failures: Vec<String> = [
+        (
+            "paging",
+            run_fss_env(
+                &[&args[..], &["--paging", "always"]].concat(),
+                &[("PAGER", pager)],
+            ),
+        ),
+        (
+            "paging_less_set",
+            run_fss_env(
+                &[&args[..], &["--paging", "always"]].concat(),
+                &[("PAGER", pager), ("LESS", "S")],
+            ),
+        ),
+        (
+            "paging_auto",
+            run_fss_env(
+                &[&args[..], &["--paging", "auto"]].concat(),
+                &[("PAGER", pager)],
+            ),
+        ),
+        ("paging_unknown", run_fss(&[&data, "--paging", "sometimes"])),
+    ]
+    .iter()
+    .filter_map(|(name, output)| check_golden(name, &output.replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn fuzzy() {
+    let tree = TempTree::new("fuzzy");
+    tree.file("data/src/main.rs", &[0; 90])
+        .file("data/src/domain.rs", &[0; 40])
+        .file("data/docs/readme_main_notes.md", &[0; 30])
+        .file("data/m_a_i_n.txt", &[0; 20])
+        .file("data/MainWindow.cpp", &[0; 10])
+        .file("data/notes.txt", &[0; 5]);
+
+    let root = tree.path("");
+    let data = tree.path("data");
+    let args = [data.as_str(), "-r", "-f"];
+    let failures: Vec<String> = [
+        (
+            "fuzzy",
+            run_fss(&[&args[..], &["--fuzzy", "main"]].concat()),
+        ),
+        (
+            "fuzzy_smart_case",
+            run_fss(&[&args[..], &["--fuzzy", "MaW"]].concat()),
+        ),
+        (
+            "fuzzy_ignore_case",
+            run_fss(&[&args[..], &["-i", "--fuzzy", "MAIN"]].concat()),
+        ),
+        (
+            "fuzzy_conflict",
+            run_fss(&[&data, "--glob", "*", "--fuzzy", "main"]),
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, output)| check_golden(name, &output.replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn search_exit_status() {
+    let tree = TempTree::new("search_exit_status");
+    tree.file("data/main.rs", &[0; 10])
+        .file("data/notes.txt", &[0; 5]);
+
+    let data = tree.path("data");
+    let missing = tree.path("missing");
+    let status = |p_args: &[&str]| {
+        return process::Command::new(env!("CARGO_BIN_EXE_fss"))
+            .args(p_args)
+            .stdout(process::Stdio::null())
+            .stderr(process::Stdio::null())
+            .status()
+            .unwrap()
+            .code();
+    };
+
+    // searches exit like grep - 0 if anything matched, 1 if nothing did and 2 if the search could not be run
+    assert_eq!(status(&[&data, "-f", "--contains", "main"]), Some(0));
+    assert_eq!(status(&[&data, "-f", "--contains", "absent"]), Some(1));
+    assert_eq!(status(&[&data, "-f", "--fuzzy", "mnrs"]), Some(0));
+    assert_eq!(status(&[&data, "-f", "--fuzzy", "zzz"]), Some(1));
+    assert_eq!(status(&[&missing, "--contains", "main"]), Some(2));
+
+    // the same holds whichever mode the matches are printed in
+    for mode in [
+        &["--names-only"][..],
+        &["--format", "csv"],
+        &["-0"],
+        &["--ls-format"],
+        &["--printf", "{path}"],
+        &["--long"],
+    ] {
+        let run = |p_path: &str, p_pattern: &str| {
+            let mut args = vec![p_path, "-f", "--contains", p_pattern];
+            args.extend_from_slice(mode);
+            return status(&args);
+        };
+
+        assert_eq!(run(&data, "main"), Some(0), "{:?}", mode);
+        assert_eq!(run(&data, "absent"), Some(1), "{:?}", mode);
+        assert_eq!(run(&missing, "main"), Some(2), "{:?}", mode);
+    }
+
+    // a run that is aborted at a directory that could not be read fails with its own status, even if it is not a
+    // search
+    assert_eq!(status(&[&data, &missing, "--on-error", "abort"]), Some(3));
+    assert_eq!(status(&[&data, &missing, "--on-error", "collect"]), Some(0));
+
+    // with several paths, a search fails if any of them could not be searched, even if another one matched
+    assert_eq!(status(&[&data, &data, "-f", "--contains", "main"]), Some(0));
+    assert_eq!(
+        status(&[&data, &missing, "-f", "--contains", "main"]),
+        Some(2)
+    );
+
+    // other runs succeed regardless of what they list
+    assert_eq!(status(&[&data]), Some(0));
+    assert_eq!(status(&[&missing, "--names-only"]), Some(0));
+}
+
+#[test]
+fn multiple_paths() {
+    let tree = TempTree::new("multiple-paths");
+    tree.file("one/main.rs", &[0; 90])
+        .file("one/src/lib.rs", &[0; 40])
+        .file("two/notes.txt", &[0; 5])
+        .file("two/build.rs", &[0; 20]);
+    tree.fix_times();
+
+    let root = tree.path("");
+    let one = tree.path("one");
+    let two = tree.path("two");
+    let failures: Vec<String> = [
+        (
+            "multiple_paths",
+            vec![one.as_str(), two.as_str(), "-r", "-f"],
+        ),
+        (
+            "multiple_paths_search",
+            vec![
+                one.as_str(),
+                two.as_str(),
+                "-r",
+                "-f",
+                "--glob",
+                "{src/*.rs,build.rs}",
+            ],
+        ),
+        (
+            "multiple_paths_names_only",
+            vec![one.as_str(), two.as_str(), "-r", "-f", "--names-only"],
+        ),
+        (
+            "multiple_paths_unsupported",
+            vec![one.as_str(), two.as_str(), "--treemap", "map.html"],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn paths_from() {
+    use std::io::Write;
+
+    let tree = TempTree::new("paths-from");
+    tree.file("one/main.rs", &[0; 90])
+        .file("two/notes.txt", &[0; 5])
+        .file("empty.txt", b"");
+
+    let root = tree.path("");
+    let one = tree.path("one");
+    let two = tree.path("two");
+    tree.file("dirs.txt", format!("{}\r\n\n{}\n", one, two).as_bytes());
+
+    // runs fss with the given input on stdin
+    let run_stdin = |p_args: &[&str], p_input: &str| {
+        let mut child = process::Command::new(env!("CARGO_BIN_EXE_fss"))
+            .args(p_args)
+            .env("LANG", "C")
+            .env_remove("LC_ALL")
+            .env_remove("LC_MESSAGES")
+            .stdin(process::Stdio::piped())
+            .stdout(process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(p_input.as_bytes())
+            .unwrap();
+
+        let output = child.wait_with_output().unwrap();
+        return String::from_utf8_lossy(&output.stdout).replace(&root, "<ROOT>");
+    };
+
+    // the paths are the same whether they come from a file, separated by lines, or from stdin, separated by NUL bytes
+    let from_file = run_fss(&["--paths-from", &tree.path("dirs.txt"), "-f", "--names-only"]);
+    let from_stdin = run_stdin(
+        &["--paths-from", "-", "-f", "--names-only"],
+        &format!("{}\0{}\0", one, two),
+    );
+    assert_eq!(from_file.replace(&root, "<ROOT>"), from_stdin);
+
+    let failures: Vec<String> = [
+        ("paths_from", from_stdin),
+        (
+            "paths_from_empty",
+            run_fss(&["--paths-from", &tree.path("empty.txt")]).replace(&root, "<ROOT>"),
+        ),
+        (
+            "paths_from_missing",
+            run_fss(&["--paths-from", &tree.path("missing.txt")]).replace(&root, "<ROOT>"),
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, output)| check_golden(name, output))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn max_entries() {
+    let tree = TempTree::new("max-entries");
+    tree.file("data/a/one.txt", &[0; 10])
+        .file("data/a/two.txt", &[0; 20])
+        .file("data/b/three.txt", &[0; 30])
+        .file("data/four.txt", &[0; 40]);
+    tree.fix_times();
+
+    let root = tree.path("");
+    let data = tree.path("data");
+    let failures: Vec<String> = [
+        (
+            "max_entries",
+            vec![data.as_str(), "-r", "-f", "--ordered", "--max-entries", "4"],
+        ),
+        (
+            "max_entries_names_only",
+            vec![
+                data.as_str(),
+                "-r",
+                "-f",
+                "--ordered",
+                "--names-only",
+                "--max-entries",
+                "4",
+            ],
+        ),
+        (
+            "max_entries_not_reached",
+            vec![
+                data.as_str(),
+                "-r",
+                "-f",
+                "--ordered",
+                "--names-only",
+                "--max-entries",
+                "6",
+            ],
+        ),
+        (
+            "max_entries_watch",
+            vec![data.as_str(), "--watch", "--max-entries", "4"],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn timeout() {
+    let tree = TempTree::new("timeout");
+    tree.file("data/a/one.txt", &[0; 10])
+        .file("data/four.txt", &[0; 40]);
+
+    let root = tree.path("");
+    let data = tree.path("data");
+
+    // the traversal can not be made to run out of time reliably, so only a budget that is never used up is checked
+    let failures: Vec<String> = [
+        (
+            "timeout_not_reached",
+            vec![
+                data.as_str(),
+                "-r",
+                "-f",
+                "--ordered",
+                "--names-only",
+                "--timeout",
+                "1h",
+            ],
+        ),
+        ("timeout_invalid", vec![data.as_str(), "--timeout", "0"]),
+        (
+            "timeout_watch",
+            vec![data.as_str(), "--watch", "--timeout", "30"],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn watch_filters() {
+    use std::io::BufRead;
+
+    let tree = TempTree::new("watch-filters");
+    tree.file("keep/old.txt", b"old")
+        .file("skip/old.txt", b"old");
+
+    let mut child = process::Command::new(env!("CARGO_BIN_EXE_fss"))
+        .args([
+            &tree.path(""),
+            "-r",
+            "--watch",
+            "--debounce",
+            "0",
+            "--exclude",
+            "skip",
+        ])
+        .env("LANG", "C")
+        .stdout(process::Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // the lines are read on another thread, so that waiting for them can time out
+    let stdout = child.stdout.take().unwrap();
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for line in std::io::BufReader::new(stdout)
+            .lines()
+            .map_while(Result::ok)
+        {
+            let _ = sender.send(line);
+        }
+    });
+    let next_line = || receiver.recv_timeout(time::Duration::from_secs(10));
+
+    // the changes are only made once the first scan is done (the watched directory is announced after it)
+    let mut lines = vec![next_line().unwrap()];
+    assert!(lines[0].starts_with("Watching"), "{:?}", lines);
+
+    // the changes are reported in the order of their paths, so the excluded and hidden entries would be listed before
+    // the last one
+    tree.file("skip/new.txt", b"new")
+        .file(".hidden", b"new")
+        .file("keep/new.txt", b"new")
+        .file("watched.txt", b"new");
+
+    while !lines.last().unwrap().ends_with("watched.txt") {
+        match next_line() {
+            Ok(line) => lines.push(line),
+            Err(_) => break,
+        }
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+
+    let changed: Vec<&str> = lines
+        .iter()
+        .filter_map(|line| line.trim_start().strip_prefix("created"))
+        .map(str::trim)
+        .collect();
+
+    assert_eq!(
+        changed,
+        [tree.path("keep/new.txt"), tree.path("watched.txt")],
+        "{:?}",
+        lines
+    );
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn broken_pipe() {
+    use std::io::BufRead;
+    use std::os::unix::process::ExitStatusExt;
+
+    // the output has to be larger than the buffer of the pipe, so that the run is still printing once it is closed
+    let tree = TempTree::new("broken-pipe");
+    for idx in 0..3000 {
+        tree.file(&format!("data/file-{}.txt", idx), b"");
+    }
+
+    let root = tree.path("");
+    let cases: [&[&str]; 2] = [
+        &["-r", "-f", "--format", "ndjson"],
+        &["-r", "-f", "--names-only"],
+    ];
+    for args in cases {
+        let mut child = process::Command::new(env!("CARGO_BIN_EXE_fss"))
+            .arg(&root)
+            .args(args)
+            .env("LANG", "C")
+            .stdout(process::Stdio::piped())
+            .stderr(process::Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        // like head, only the first line is read before the pipe is closed
+        let mut first = String::new();
+        std::io::BufReader::new(child.stdout.take().unwrap())
+            .read_line(&mut first)
+            .unwrap();
+        let output = child.wait_with_output().unwrap();
+
+        assert!(!first.is_empty(), "{:?}", args);
+        assert_eq!(output.status.signal(), Some(13), "{:?}", args);
+        assert_eq!(String::from_utf8_lossy(&output.stderr), "", "{:?}", args);
+    }
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn watch_hooks() {
+    use std::io::BufRead;
+
+    let tree = TempTree::new("watch-hooks");
+    tree.file("edited.txt", b"old")
+        .file("removed.txt", b"old")
+        .file("kept.txt", b"old");
+
+    // the hooks print to the same output as the watch, and quote the paths themselves
+    let mut child = process::Command::new(env!("CARGO_BIN_EXE_fss"))
+        .args([
+            &tree.path(""),
+            "--watch",
+            "--debounce",
+            "0",
+            "--on-modify",
+            "echo hook modified {}",
+            "--on-delete",
+            "echo hook deleted {}",
+        ])
+        .env("LANG", "C")
+        .stdout(process::Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // the lines are read on another thread, so that waiting for them can time out (the watch never ends by itself,
+    // since neither --max-entries nor --timeout can be given along with it)
+    let stdout = child.stdout.take().unwrap();
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for line in std::io::BufReader::new(stdout)
+            .lines()
+            .map_while(Result::ok)
+        {
+            let _ = sender.send(line);
+        }
+    });
+    let next_line = || receiver.recv_timeout(time::Duration::from_secs(10));
+
+    let first = next_line().unwrap();
+    assert!(first.starts_with("Watching"), "{:?}", first);
+
+    tree.file("edited.txt", b"new contents");
+    fs::remove_file(tree.path("removed.txt")).unwrap();
+
+    let mut hooks: Vec<String> = Vec::new();
+    while hooks.len() < 2 {
+        match next_line() {
+            Ok(line) => {
+                if let Some(hook) = line.strip_prefix("hook ") {
+                    hooks.push(hook.to_owned());
+                }
+            }
+            Err(_) => break,
+        }
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+
+    hooks.sort();
+    assert_eq!(
+        hooks,
+        [
+            format!("deleted {}", tree.path("removed.txt")),
+            format!("modified {}", tree.path("edited.txt")),
+        ]
+    );
+}
+
+#[test]
+fn patterns_from() {
+    let tree = TempTree::new("patterns-from");
+    tree.file("data/src/main.rs", &[0; 90])
+        .file("data/src/lib.rs", &[0; 40])
+        .file("data/docs/README.md", &[0; 30])
+        .file("data/Cargo.toml", &[0; 20])
+        .file("data/notes.txt", &[0; 5])
+        .file(
+            "names.txt",
+            b"main.rs\r\n\nreadme.md\nCargo.toml\nmissing.txt\n",
+        )
+        .file("stems.txt", b"lib\nnotes\n")
+        .file("empty.txt", b"\n\n");
+
+    let root = tree.path("");
+    let data = tree.path("data");
+    let args = [data.as_str(), "-r", "-f"];
+    let failures: Vec<String> = [
+        (
+            "patterns_from",
+            run_fss(&[&args[..], &["--patterns-from", &tree.path("names.txt")]].concat()),
+        ),
+        (
+            "patterns_from_ignore_case",
+            run_fss(
+                &[
+                    &args[..],
+                    &["-i", "--patterns-from", &tree.path("names.txt")],
+                ]
+                .concat(),
+            ),
+        ),
+        (
+            "patterns_from_noext",
+            run_fss(
+                &[
+                    &args[..],
+                    &["--search-noext", "main"],
+                    &["--patterns-from", &tree.path("stems.txt")],
+                ]
+                .concat(),
+            ),
+        ),
+        (
+            "patterns_from_empty",
+            run_fss(&[&args[..], &["--patterns-from", &tree.path("empty.txt")]].concat()),
+        ),
+        (
+            "patterns_from_missing",
+            run_fss(&[&args[..], &["--patterns-from", &tree.path("nothing.txt")]].concat()),
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, output)| check_golden(name, &output.replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn split_output() {
+    let tree = TempTree::new("split-output");
+    tree.file("alpha/main.rs", &[0; 120])
+        .file("beta/docs/guide.md", &[0; 300])
+        .file(".cache/blob.bin", &[0; 900])
+        .file("top.txt", &[0; 7]);
+
+    let root = tree.path("");
+    let reports = tree.path("reports");
+    let mut output = run_fss(&[&root, "-r", "-f", "--split-output", &reports]);
+
+    // the reports follow the output of the run itself (the directory of the reports and hidden directories are never
+    // reported on)
+    let mut report_names: Vec<String> = fs::read_dir(&reports)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    report_names.sort();
+
+    for name in report_names {
+        output.push_str(&format!("--- {} ---\n", name));
+        output.push_str(&fs::read_to_string(path::Path::new(&reports).join(name)).unwrap());
+    }
+
+    let failures: Vec<String> = [
+        ("split_output", output),
+        (
+            "split_output_record",
+            run_fss(&[&root, "--split-output", &reports, "--record", "scan.txt"]),
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, output)| check_golden(name, &output.replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn output_file() {
+    let tree = TempTree::new("output-file");
+    tree.file("data/a/x.bin", &[0; 5000])
+        .file("data/top.txt", &[0; 40]);
+
+    let root = tree.path("");
+    let data = tree.path("data");
+    let out = tree.path("out.txt");
+    std::os::unix::fs::symlink(tree.path("missing"), tree.path("data/broken")).unwrap();
+
+    // the errors are still printed while the listing is written into the file (nothing is left next to it)
+    let mut output = run_fss(&[&data, "-r", "-f", "-l", "-e", "--ordered", "--output", &out]);
+    output.push_str("--- out.txt ---\n");
+    output.push_str(&fs::read_to_string(&out).unwrap());
+    run_fss(&[
+        &data,
+        "-r",
+        "-f",
+        "--names-only",
+        "--ordered",
+        "--output",
+        &out,
+        "--append",
+    ]);
+    output.push_str("--- out.txt (appended) ---\n");
+    output.push_str(&fs::read_to_string(&out).unwrap());
+
+    // a run that fails after the output was started leaves the output file as it was
+    let written = fs::read_to_string(&out).unwrap();
+    run_fss(&[
+        &data,
+        "--output",
+        &out,
+        "--record",
+        &tree.path("missing/scan.txt"),
+    ]);
+    assert_eq!(fs::read_to_string(&out).unwrap(), written);
+
+    // the temporary files written instead of the output file are gone, however the runs ended
+    let mut names: Vec<String> = fs::read_dir(&root)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["data", "out.txt"]);
+
+    let failures: Vec<String> = [
+        ("output_file", output),
+        ("output_append_only", run_fss(&[&data, "--append"])),
+        (
+            "output_create_error",
+            run_fss(&[&data, "--output", &tree.path("missing/out.txt")]),
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, output)| check_golden(name, &output.replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn print0() {
+    let tree = TempTree::new("print0");
+    tree.file("data/a/x.bin", &[0; 50])
+        .file("data/two words.txt", &[0; 5])
+        .file("data/line\nbreak.txt", &[0; 5]);
+
+    let root = tree.path("");
+    let data = tree.path("data");
+
+    // each path is followed by a NUL byte, which is shown as a marker so that the newline in a name stands out
+    let output = run_fss(&[&data, "-r", "-f", "-0", "--ordered"]);
+    assert_eq!(output.matches('\0').count(), 4);
+
+    let failures: Vec<String> = [
+        ("print0", output.replace('\0', "<NUL>\n")),
+        (
+            "print0_conflict",
+            run_fss(&[&data, "--print0", "--names-only"]),
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, output)| check_golden(name, &output.replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn escaped_names() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let tree = TempTree::new("escaped-names");
+    tree.file("data/dir\tone/line\nbreak.txt", &[0; 5])
+        .file("data/say \"hi\".txt", &[0; 7])
+        .file("data/back\\slash\x1b[31m.txt", &[0; 3]);
+    fs::write(
+        path::Path::new(&tree.path("data")).join(std::ffi::OsStr::from_bytes(b"bad\xffbyte")),
+        [0; 2],
+    )
+    .unwrap();
+
+    let root = tree.path("");
+    let data = tree.path("data");
+
+    let failures: Vec<String> = [
+        (
+            "escaped_names",
+            run_fss(&[&data, "-r", "-f", "--escape", "--ordered"]),
+        ),
+        (
+            "escaped_names_quote",
+            run_fss(&[&data, "-r", "-f", "-Q", "--names-only", "--ordered"]),
+        ),
+        (
+            "escaped_names_sizes",
+            run_fss(&[&data, "-r", "-f", "-b", "--sizes-only", "--ordered"]),
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, output)| check_golden(name, &output.replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn relative_time() {
+    const DAY: u64 = 86400;
+
+    let tree = TempTree::new("relative-time");
+    let now = time::SystemTime::now();
+    let ages: [(&str, time::SystemTime); 6] = [
+        ("a-now.txt", now),
+        ("b-minute.txt", now - time::Duration::from_secs(90)),
+        (
+            "c-hours.txt",
+            now - time::Duration::from_secs(5 * 3600 + 60),
+        ),
+        ("d-days.txt", now - time::Duration::from_secs(3 * DAY + 60)),
+        ("e-year.txt", now - time::Duration::from_secs(400 * DAY)),
+        ("f-future.txt", now + time::Duration::from_secs(DAY)),
+    ];
+    for (name, modified) in ages {
+        tree.file(&format!("data/{}", name), &[0; 4]);
+        fs::File::options()
+            .write(true)
+            .open(tree.path(&format!("data/{}", name)))
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+
+    let root = tree.path("");
+    let data = tree.path("data");
+
+    let failures: Vec<String> = [
+        (
+            "relative_time",
+            run_fss(&[&data, "-f", "-t", "--relative-time", "--ordered"]),
+        ),
+        (
+            "relative_time_de",
+            run_fss_env(
+                &[
+                    &data,
+                    "-f",
+                    "--columns",
+                    "mtime,name",
+                    "--relative-time",
+                    "--ordered",
+                ],
+                &[("LANG", "de_DE.UTF-8")],
+            ),
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, output)| check_golden(name, &output.replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn time_format_zone() {
+    let tree = TempTree::new("time-format-zone");
+    tree.file("data/a.txt", &[0; 4]);
+    fs::File::options()
+        .write(true)
+        .open(tree.path("data/a.txt"))
+        .unwrap()
+        .set_modified(time::UNIX_EPOCH + time::Duration::from_secs(1704164645))
+        .unwrap();
+
+    let root = tree.path("");
+    let data = tree.path("data");
+    let format = "%Y-%m-%d %H:%M:%S %z";
+
+    let failures: Vec<String> = [
+        (
+            "time_format_zone",
+            run_fss(&[
+                &data,
+                "-f",
+                "-t",
+                "--time-format",
+                format,
+                "--time-zone",
+                "+05:30",
+            ]),
+        ),
+        (
+            "time_format_zone_west",
+            run_fss(&[&data, "-f", "--format", "csv", "--time-zone", "-08:00"]),
+        ),
+        (
+            "time_format_invalid",
+            run_fss(&[&data, "-f", "-t", "--time-format", "%Y %Q"]),
+        ),
+        (
+            "time_zone_unknown",
+            run_fss(&[&data, "-f", "-t", "--time-zone", "Nowhere/City"]),
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, output)| check_golden(name, &output.replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+#[cfg(all(
+    target_family = "unix",
+    feature = "compress-gzip",
+    feature = "compress-zstd"
+))]
+fn compressed_output() {
+    let tree = TempTree::new("compressed-output");
+    for i in 0..200 {
+        tree.file(&format!("data/dir{}/file{}.txt", i % 7, i), &[0; 10]);
+    }
+
+    let root = tree.path("");
+    let data = tree.path("data");
+    let gz = tree.path("scan.csv.gz");
+    let plain = run_fss(&[&data, "-r", "-f", "--format", "csv"]);
+    run_fss(&[&data, "-r", "-f", "--format", "csv", "--output", &gz]);
+
+    // the trailer of the gzip member holds the CRC-32 and the length of the uncompressed output
+    let bytes = fs::read(&gz).unwrap();
+    let trailer = &bytes[bytes.len() - 8..];
+    let crc = plain.bytes().fold(!0u32, |mut crc, byte| {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xedb88320 & (crc & 1).wrapping_neg());
+        }
+        crc
+    });
+    assert_eq!(&bytes[..3], b"\x1f\x8b\x08");
+    assert_eq!(trailer[..4], (!crc).to_le_bytes());
+    assert_eq!(trailer[4..], (plain.len() as u32).to_le_bytes());
+    assert!(bytes.len() < plain.len() / 2);
+
+    // files ending with .zst are compressed with zstd, as is the output with "--compress-algo zstd"
+    let zst = tree.path("scan.csv.zst");
+    run_fss(&[&data, "-r", "-f", "--format", "csv", "--output", &zst]);
+    let bytes = fs::read(&zst).unwrap();
+    assert_eq!(&bytes[..4], b"\x28\xb5\x2f\xfd");
+    assert_eq!(zstd::decode_all(&bytes[..]).unwrap(), plain.as_bytes());
+    assert!(bytes.len() < plain.len() / 2);
+
+    let output = process::Command::new(env!("CARGO_BIN_EXE_fss"))
+        .args([
+            &data,
+            "-r",
+            "-f",
+            "--format",
+            "csv",
+            "--compress-algo",
+            "zstd",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(
+        zstd::decode_all(&output.stdout[..]).unwrap(),
+        plain.as_bytes()
+    );
+
+    let failures: Vec<String> = check_golden(
+        "compressed_output_unknown",
+        &run_fss(&[&data, "--compress-algo", "lz4"]).replace(&root, "<ROOT>"),
+    )
+    .into_iter()
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn ignore_files() {
+    let tree = TempTree::new("ignore-files");
+    tree.file(
+        ".fssignore",
+        b"*.log\n!keep.log\n# scratch areas\nscratch/\n",
+    )
+    .file("a.log", &[0; 10])
+    .file("keep.log", &[0; 20])
+    .file("scratch/deep/data.bin", &[0; 4000])
+    .file("src/.fssignore", b"build/\n/local.tmp\n")
+    .file("src/main.rs", &[0; 300])
+    .file("src/local.tmp", &[0; 50])
+    .file("src/build/out.o", &[0; 9000])
+    .file("src/nested/local.tmp", &[0; 60]);
+
+    let root = tree.path("");
+    let sorted_lines = |p_output: String| {
+        let mut lines: Vec<&str> = p_output.lines().collect();
+        lines.sort();
+        lines.join("\n") + "\n"
+    };
+
+    // the recording lacks the ignored entries, so replaying it gives the same listing without the ignore files
+    let recording = std::env::temp_dir().join(format!("fss-golden-ignore-{}.rec", process::id()));
+    let recording = recording.to_string_lossy().into_owned();
+    let live = sorted_lines(run_fss(&[
+        &root,
+        "-r",
+        "-f",
+        "-a",
+        "--names-only",
+        "--record",
+        &recording,
+    ]));
+    let replayed = sorted_lines(run_fss(&[
+        &root,
+        "-r",
+        "-f",
+        "-a",
+        "--names-only",
+        "--replay",
+        &recording,
+    ]));
+    let _ = fs::remove_file(&recording);
+    assert_eq!(live, replayed);
+
+    let failures: Vec<String> = [
+        ("ignore_files", live),
+        (
+            "ignore_files_dirs_only",
+            run_fss(&[&root, "--dirs-only", "-r", "-d"]),
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, output)| check_golden(name, &output.replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn policy() {
+    let tree = TempTree::new("policy");
+    tree.file(
+        "audit.toml",
+        b"# audit of the tree\n\
+        [options]\n\
+        dirs-only = true\n\
+        recursive = true\n\
+        \"dir-size\" = true   # sizes are needed for the budgets anyway\n\
+        \n\
+        [excludes]\n\
+        patterns = [\n\
+        \x20   \"scratch/\",\n\
+        \x20   \"report.txt\",  # written by sink.toml, its size depends on the path of the tree\n\
+        ]\n\
+        \n\
+        [budgets]\n\
+        \".\" = \"4K\"\n\
+        data = 1000\n\
+        \n\
+        [retention]\n\
+        logs = \"30d\"\n",
+    )
+    .file(
+        "sink.toml",
+        b"[options]\ndirs-only = true\ndir-size = true\n\n[output]\nfile = \"report.txt\"\n",
+    )
+    .file("broken.toml", b"[options]\nrecursive = [[1]]\n")
+    .file("data/big.bin", &[0; 3000])
+    .file("logs/old.log", &[0; 100])
+    .file("logs/new.log", &[0; 50])
+    .file("scratch/tmp.bin", &[0; 9000]);
+    tree.set_age("logs/old.log", 90 * 86400);
+
+    let root = tree.path("");
+    let audit = tree.path("audit.toml");
+    let sink = tree.path("sink.toml");
+
+    // policies that would run commands or write files are kept apart, so that they do not count towards the sizes
+    let refused = TempTree::new("policy-refused");
+    let refused_root = refused.path("");
+    let export_path = refused.path("scan.db");
+    refused
+        .file(
+            "hooks.toml",
+            b"[options]\nwatch = true\non-create = \"touch created\"\n",
+        )
+        .file(
+            "export.toml",
+            format!("[options]\nexport-sqlite = \"{}\"\n", export_path).as_bytes(),
+        );
+
+    // a policy that is violated makes the run fail
+    let status = process::Command::new(env!("CARGO_BIN_EXE_fss"))
+        .args(["--policy", &audit])
+        .stdout(process::Stdio::null())
+        .status()
+        .unwrap();
+    assert_eq!(status.code(), Some(1));
+
+    // the output of a policy with an output file is only written into the file
+    let mut sink_output = run_fss(&["--policy", &sink]);
+    sink_output.push_str("--- report.txt ---\n");
+    sink_output.push_str(&fs::read_to_string(tree.path("report.txt")).unwrap());
+
+    let failures: Vec<String> = [
+        ("policy", run_fss(&["--policy", &audit])),
+        (
+            "policy_lang_de",
+            run_fss(&["--policy", &audit, "--lang", "de"]),
+        ),
+        ("policy_output", sink_output),
+        (
+            "policy_invalid",
+            run_fss(&["--policy", &tree.path("broken.toml")]),
+        ),
+        (
+            "policy_hooks",
+            run_fss(&["--policy", &refused.path("hooks.toml")]),
+        ),
+        (
+            "policy_export",
+            run_fss(&["--policy", &refused.path("export.toml")]),
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, output)| {
+        let output = output.replace(&root, "<ROOT>");
+        return check_golden(name, &output.replace(&refused_root, "<ROOT>"));
+    })
+    .collect();
+
+    // options that run commands or write files are refused before anything is done
+    assert!(!path::Path::new(&export_path).exists());
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn transfer_estimate() {
+    let tree = TempTree::new("transfer-estimate");
+    tree.file("src/same.txt", b"unchanged contents")
+        .file("src/changed.txt", &[1; 4000])
+        .file("src/new.txt", &[2; 250])
+        .file("src/dir/new.txt", &[3; 50])
+        .file("dest/same.txt", b"unchanged contents")
+        .file("dest/changed.txt", &[1; 3000]);
+    tree.fix_times();
+
+    let (src, dest) = (tree.path("src"), tree.path("dest"));
+
+    // either side can be a snapshot or a recording of it instead, which gives the same estimate
+    let (src_snapshot, dest_snapshot) = (tree.path("src.fss"), tree.path("dest.fss"));
+    let dest_recording = tree.path("dest.txt");
+    run_fss(&[&src, "-r", "--snapshot", &src_snapshot]);
+    run_fss(&[&dest, "-r", "--snapshot", &dest_snapshot]);
+    run_fss(&[&dest, "-r", "--record", &dest_recording]);
+
+    let failures: Vec<String> = [
+        (
+            "transfer_estimate",
+            vec![src.as_str(), "--transfer-estimate", dest.as_str()],
+        ),
+        (
+            "transfer_estimate_blocks",
+            vec![
+                src.as_str(),
+                "--transfer-estimate",
+                dest.as_str(),
+                "--block-estimate",
+            ],
+        ),
+        (
+            "transfer_estimate",
+            vec![src.as_str(), "--transfer-estimate", dest_snapshot.as_str()],
+        ),
+        (
+            "transfer_estimate",
+            vec![src_snapshot.as_str(), "--transfer-estimate", dest.as_str()],
+        ),
+        (
+            "transfer_estimate",
+            vec![
+                src_snapshot.as_str(),
+                "--transfer-estimate",
+                dest_recording.as_str(),
+            ],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| {
+        let output = run_fss(args)
+            .replace(&src_snapshot, "<SRC>")
+            .replace(&dest_snapshot, "<DEST>")
+            .replace(&dest_recording, "<DEST>")
+            .replace(&src, "<SRC>")
+            .replace(&dest, "<DEST>");
+        check_golden(name, &output)
+    })
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn hashing() {
+    let tree = TempTree::new("hashing");
+    tree.file("hello.txt", b"hello world\n");
+
+    let root = tree.path("");
+    let algos: Vec<&str> = [
+        #[cfg(feature = "hash-blake3")]
+        "blake3",
+        #[cfg(feature = "hash-sha256")]
+        "sha256",
+        #[cfg(feature = "hash-xxh3")]
+        "xxh3",
+    ]
+    .to_vec();
+
+    let failures: Vec<String> = algos
+        .iter()
+        .flat_map(|algo| {
+            [
+                (
+                    format!("hash_{}", algo),
+                    vec![root.as_str(), "-f", "--hash-algo", algo],
+                ),
+                (
+                    format!("hash_{}_no_tree", algo),
+                    vec![root.as_str(), "-f", "--no-tree", "--hash-algo", algo],
+                ),
+            ]
+        })
+        .filter_map(|(name, args)| check_golden(&name, &run_fss(&args).replace(&root, "<ROOT>")))
+        .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+#[cfg(all(feature = "hash-blake3", feature = "hash-sha256"))]
+fn hash_cache() {
+    let tree = TempTree::new("hash-cache");
+    tree.file("data/a.txt", b"alpha\n")
+        .file("data/b.txt", b"bravo\n");
+    tree.fix_times();
+
+    let root = tree.path("");
+    let data = tree.path("data");
+    let cache = tree.path("hashes.txt");
+    let run = |p_algo: &str| -> String {
+        return run_fss(&[
+            &data,
+            "-f",
+            "--no-tree",
+            "--ordered",
+            "--hash-algo",
+            p_algo,
+            "--hash-cache",
+            &cache,
+        ]);
+    };
+
+    let mut output = String::from("--- first run ---\n");
+    output.push_str(&run("blake3"));
+
+    // the hashes in the cache are replaced, so that the ones that are reused show up as such
+    let tampered: String = fs::read_to_string(&cache)
+        .unwrap()
+        .lines()
+        .map(|line| {
+            let mut fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() == 7 {
+                fields[5] = "cached";
+            }
+            return fields.join("\t") + "\n";
+        })
+        .collect();
+    fs::write(&cache, tampered).unwrap();
+
+    // the hashes of another algorithm are kept next to the ones that were cached before
+    output.push_str("--- other algorithm ---\n");
+    output.push_str(&run("sha256"));
+    output.push_str("--- unchanged ---\n");
+    output.push_str(&run("blake3"));
+
+    // a changed modification time or size means that the file is hashed again
+    tree.file("data/b.txt", b"bravo!\n");
+    tree.fix_times();
+    let time = time::UNIX_EPOCH + time::Duration::from_secs(FIXED_MTIME_SECS + 60);
+    fs::File::open(tree.path("data/a.txt"))
+        .unwrap()
+        .set_modified(time)
+        .unwrap();
+    output.push_str("--- changed ---\n");
+    output.push_str(&run("blake3"));
+
+    assert_eq!(
+        check_golden("hash_cache", &output.replace(&root, "<ROOT>")),
+        None
+    );
+}
+
+#[test]
+#[cfg(feature = "hash-blake3")]
+fn hash_checkpoint() {
+    let tree = TempTree::new("hash-checkpoint");
+    tree.file("data/a.txt", b"alpha\n")
+        .file("data/b.txt", b"bravo\n");
+    tree.fix_times();
+
+    let root = tree.path("");
+    let data = tree.path("data");
+    let cache = tree.path("hashes.txt");
+    let checkpoint = tree.path("checkpoint.txt");
+    let args = ["-f", "--no-tree", "--ordered", "--hash-algo", "blake3"];
+    run_fss(&[&[data.as_str(), "--hash-cache", &cache], &args[..]].concat());
+
+    // the checkpoint of an interrupted run holds the hash of the first file only (under a marker, so that it shows up
+    // when it is resumed from)
+    let seeded: String = fs::read_to_string(&cache)
+        .unwrap()
+        .lines()
+        .filter(|line| !line.ends_with("b.txt"))
+        .map(|line| {
+            let mut fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() == 7 {
+                fields[5] = "seeded";
+            }
+            return fields.join("\t") + "\n";
+        })
+        .collect();
+    fs::write(&checkpoint, seeded).unwrap();
+
+    let output = run_fss(&[&[data.as_str(), "--checkpoint", &checkpoint], &args[..]].concat());
+    assert_eq!(
+        check_golden("hash_checkpoint", &output.replace(&root, "<ROOT>")),
+        None
+    );
+
+    // the checkpoint is removed once the run completes
+    assert!(!path::Path::new(&checkpoint).exists());
+}
+
+#[test]
+fn no_cache_pollution() {
+    let tree = TempTree::new("no-cache-pollution");
+    let big: Vec<u8> = (0..100_000u32).map(|idx| (idx % 251) as u8).collect();
+    tree.file("notes.txt", b"plain text\n")
+        .file("image.png", b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR")
+        .file("data/big.bin", &big);
+
+    // dropping the pages read from the cache must not change what is read (by hashing or by sniffing the contents)
+    let root = tree.path("");
+    let cases: [&[&str]; 5] = [
+        &["-r", "-f", "--ordered", "--no-tree", "--hash"],
+        &["-r", "--ordered", "--names-only", "--only-text"],
+        &["-r", "--ordered", "--names-only", "--only-binary"],
+        &["-r", "-f", "--ordered", "--columns", "content,size,name"],
+        &["-r", "--ordered", "--names-only", "--mime", "image/png"],
+    ];
+    for args in cases {
+        let mut flagged = vec![root.as_str(), "--no-cache-pollution"];
+        flagged.extend_from_slice(args);
+        let mut plain = vec![root.as_str()];
+        plain.extend_from_slice(args);
+
+        assert_eq!(run_fss(&flagged), run_fss(&plain), "{:?}", args);
+    }
+
+    let failures: Vec<String> = [
+        (
+            "no_cache_pollution_only_text",
+            vec![
+                root.as_str(),
+                "-r",
+                "--ordered",
+                "--names-only",
+                "--only-text",
+                "--no-cache-pollution",
+            ],
+        ),
+        (
+            "no_cache_pollution_content",
+            vec![
+                root.as_str(),
+                "-r",
+                "-f",
+                "--ordered",
+                "--columns",
+                "content,size,name",
+                "--no-cache-pollution",
+            ],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+// the report depends on the features and the platform, so its golden file assumes the default features on linux
+#[cfg(all(
+    feature = "hash-blake3",
+    feature = "hash-sha256",
+    feature = "hash-xxh3",
+    any(target_os = "linux", target_os = "android")
+))]
+fn capabilities() {
+    let tree = TempTree::new("capabilities");
+    tree.file("hello.txt", b"hello world\n");
+
+    let root = tree.path("");
+    let failures: Vec<String> = [
+        ("capabilities", vec![root.as_str(), "--capabilities"]),
+        (
+            "capabilities_lang_de",
+            vec![root.as_str(), "--capabilities", "--lang", "de"],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+#[cfg(not(any(
+    feature = "hash-blake3",
+    feature = "hash-sha256",
+    feature = "hash-xxh3"
+)))]
+fn capabilities_unavailable() {
+    let tree = TempTree::new("capabilities-unavailable");
+    tree.file("hello.txt", b"hello world\n");
+
+    // the missing capability is reported once, and the files are listed without the hash column
+    let root = tree.path("");
+    let failures: Vec<String> = [
+        (
+            "capabilities_unavailable",
+            vec![root.as_str(), "-f", "--hash"],
+        ),
+        (
+            "capabilities_unavailable_no_tree",
+            vec![root.as_str(), "-f", "--no-tree", "--hash"],
+        ),
+    ]
+    .iter()
+    .filter_map(|(name, args)| check_golden(name, &run_fss(args).replace(&root, "<ROOT>")))
+    .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+#[test]
+fn read_ahead() {
+    let tree = TempTree::new("read-ahead");
+    tree.file(".fssignore", b"*.tmp\n")
+        .file("top.txt", b"top")
+        .file("a/main.rs", &[1; 300])
+        .file("a/b/c/deep.rs", &[2; 5000])
+        .file("a/b/skip.tmp", &[3; 70])
+        .file("d/.fssignore", b"build/\n")
+        .file("d/build/out.o", &[4; 9000])
+        .file("d/e/main.rs", &[5; 20]);
+
+    let root = tree.path("");
+    let cases: Vec<Vec<&str>> = vec![
+        vec![&root, "-r", "-f", "-d"],
+        vec![&root, "-r", "1", "-f", "--ordered"],
+        vec![&root, "-r", "-f", "-S", "main.rs"],
+        vec![&root, "-r", "-f", "--sizes-only"],
+        #[cfg(any(
+            feature = "hash-blake3",
+            feature = "hash-sha256",
+            feature = "hash-xxh3"
+        ))]
+        vec![&root, "-r", "-f", "--hash"],
+        #[cfg(any(
+            feature = "hash-blake3",
+            feature = "hash-sha256",
+            feature = "hash-xxh3"
+        ))]
+        vec![&root, "-r", "-f", "--contains", "main", "--hash"],
+    ];
+
+    // reading ahead on other threads must not change the output
+    for args in cases {
+        let threaded: Vec<&str> = args
+            .iter()
+            .copied()
+            .chain(["--walk-threads", "3", "--work-threads", "2"])
+            .collect();
+        assert_eq!(run_fss(&args), run_fss(&threaded), "{:?}", args);
+    }
+}
+
+#[test]
+// the help only lists the options that are available, so its golden files assume the default features
+#[cfg(all(
+    feature = "hash-blake3",
+    feature = "hash-sha256",
+    feature = "hash-xxh3",
+    feature = "export-parquet",
+    any(target_os = "linux", target_os = "android", target_os = "macos")
+))]
+fn help() {
+    let cases: &[(&str, &[&str])] = &[
+        ("help", &["-h", "--lang", "en"]),
+        ("help_lang_de", &["-h", "--lang", "de"]),
+        ("help_lang_es", &["-h", "--lang", "es"]),
+        ("help_full", &["--help-full", "--lang", "en"]),
+        ("man", &["--man", "--lang", "en"]),
+    ];
+
+    let failures: Vec<String> = cases
+        .iter()
+        .filter_map(|(name, args)| {
+            let output = run_fss(args).replace(env!("CARGO_BIN_EXE_fss"), "fss");
+            check_golden(name, &output)
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}